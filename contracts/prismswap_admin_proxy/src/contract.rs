@@ -0,0 +1,306 @@
+use crate::state::{read_proposals, Config, Proposal, CONFIG, NEXT_PROPOSAL_ID, PROPOSALS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, WasmMsg,
+};
+use cw_storage_plus::U64Key;
+
+use prismswap::admin_proxy::{
+    Action, ConfigResponse, ExecuteMsg, InstantiateMsg, ProposalResponse, ProposalsResponse,
+    QueryMsg,
+};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::router::ExecuteMsg as RouterExecuteMsg;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let signers = validate_signers(
+        deps.as_ref(),
+        msg.signers,
+        msg.threshold,
+        msg.pause_threshold,
+    )?;
+    let config = Config {
+        signers,
+        threshold: msg.threshold,
+        pause_threshold: msg.pause_threshold,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_PROPOSAL_ID.save(deps.storage, &1)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Propose { action } => execute_propose(deps, info, action),
+        ExecuteMsg::Confirm { proposal_id } => execute_confirm(deps, info, proposal_id),
+        ExecuteMsg::Revoke { proposal_id } => execute_revoke(deps, info, proposal_id),
+        ExecuteMsg::Execute { proposal_id } => execute_proposal(deps, proposal_id),
+    }
+}
+
+fn validate_signers(
+    deps: Deps,
+    signers: Vec<Addr>,
+    threshold: u64,
+    pause_threshold: u64,
+) -> StdResult<Vec<Addr>> {
+    let mut validated: Vec<Addr> = vec![];
+    for signer in signers {
+        let signer = deps.api.addr_validate(signer.as_str())?;
+        if validated.contains(&signer) {
+            return Err(StdError::generic_err(format!(
+                "duplicate signer {}",
+                signer
+            )));
+        }
+        validated.push(signer);
+    }
+
+    if pause_threshold == 0 || pause_threshold > threshold {
+        return Err(StdError::generic_err(
+            "pause threshold must be between one and the threshold",
+        ));
+    }
+    if threshold > validated.len() as u64 {
+        return Err(StdError::generic_err(
+            "threshold must not exceed the number of signers",
+        ));
+    }
+
+    Ok(validated)
+}
+
+fn load_proposal(deps: Deps, proposal_id: u64) -> StdResult<Proposal> {
+    PROPOSALS
+        .may_load(deps.storage, U64Key::new(proposal_id))?
+        .ok_or_else(|| StdError::generic_err("proposal not found"))
+}
+
+// Only signers can execute it
+pub fn execute_propose(deps: DepsMut, info: MessageInfo, action: Action) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if !config.signers.contains(&info.sender) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match &action {
+        Action::Pause {} => {}
+        Action::Execute { contract_addr, .. } => {
+            if *contract_addr != config.factory && *contract_addr != config.router {
+                return Err(StdError::generic_err(
+                    "only the factory and the router can be executed",
+                ));
+            }
+        }
+        Action::UpdateSigners {
+            signers,
+            threshold,
+            pause_threshold,
+        } => {
+            validate_signers(deps.as_ref(), signers.clone(), *threshold, *pause_threshold)?;
+        }
+    }
+
+    let proposal_id = NEXT_PROPOSAL_ID.load(deps.storage)?;
+    NEXT_PROPOSAL_ID.save(deps.storage, &(proposal_id + 1))?;
+
+    let proposal = Proposal {
+        proposer: info.sender.clone(),
+        action,
+        confirmations: vec![info.sender],
+        executed: false,
+    };
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose"),
+        ("proposal_id", &proposal_id.to_string()),
+    ]))
+}
+
+// Only signers can execute it
+pub fn execute_confirm(deps: DepsMut, info: MessageInfo, proposal_id: u64) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if !config.signers.contains(&info.sender) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut proposal = load_proposal(deps.as_ref(), proposal_id)?;
+    if proposal.executed {
+        return Err(StdError::generic_err("proposal already executed"));
+    }
+    if proposal.confirmations.contains(&info.sender) {
+        return Err(StdError::generic_err("proposal already confirmed"));
+    }
+
+    proposal.confirmations.push(info.sender);
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "confirm"),
+        ("proposal_id", &proposal_id.to_string()),
+    ]))
+}
+
+// Only signers that confirmed the proposal can execute it
+pub fn execute_revoke(deps: DepsMut, info: MessageInfo, proposal_id: u64) -> StdResult<Response> {
+    let mut proposal = load_proposal(deps.as_ref(), proposal_id)?;
+    if proposal.executed {
+        return Err(StdError::generic_err("proposal already executed"));
+    }
+    if !proposal.confirmations.contains(&info.sender) {
+        return Err(StdError::generic_err("proposal not confirmed"));
+    }
+
+    proposal
+        .confirmations
+        .retain(|confirmation| *confirmation != info.sender);
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "revoke"),
+        ("proposal_id", &proposal_id.to_string()),
+    ]))
+}
+
+pub fn execute_proposal(deps: DepsMut, proposal_id: u64) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+    let mut proposal = load_proposal(deps.as_ref(), proposal_id)?;
+    if proposal.executed {
+        return Err(StdError::generic_err("proposal already executed"));
+    }
+
+    // the confirmations of former signers no longer count
+    let confirmations = current_confirmations(&config, &proposal).len() as u64;
+    if confirmations < config.required(&proposal.action) {
+        return Err(StdError::generic_err("not enough confirmations"));
+    }
+
+    proposal.executed = true;
+    PROPOSALS.save(deps.storage, U64Key::new(proposal_id), &proposal)?;
+
+    let messages: Vec<CosmosMsg> = match proposal.action {
+        Action::Pause {} => vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.router.to_string(),
+                msg: to_binary(&RouterExecuteMsg::Pause {})?,
+                funds: vec![],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.factory.to_string(),
+                msg: to_binary(&FactoryExecuteMsg::SetWithdrawOnly {
+                    withdraw_only: true,
+                })?,
+                funds: vec![],
+            }),
+        ],
+        Action::Execute { contract_addr, msg } => vec![CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg,
+            funds: vec![],
+        })],
+        Action::UpdateSigners {
+            signers,
+            threshold,
+            pause_threshold,
+        } => {
+            config.signers = validate_signers(deps.as_ref(), signers, threshold, pause_threshold)?;
+            config.threshold = threshold;
+            config.pause_threshold = pause_threshold;
+            CONFIG.save(deps.storage, &config)?;
+            vec![]
+        }
+    };
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "execute"),
+        ("proposal_id", &proposal_id.to_string()),
+    ]))
+}
+
+fn current_confirmations(config: &Config, proposal: &Proposal) -> Vec<Addr> {
+    proposal
+        .confirmations
+        .iter()
+        .filter(|confirmation| config.signers.contains(confirmation))
+        .cloned()
+        .collect()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Proposal { proposal_id } => to_binary(&query_proposal(deps, proposal_id)?),
+        QueryMsg::Proposals { start_after, limit } => {
+            to_binary(&query_proposals(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        signers: config.signers,
+        threshold: config.threshold,
+        pause_threshold: config.pause_threshold,
+        factory: config.factory,
+        router: config.router,
+    })
+}
+
+fn proposal_response(config: &Config, proposal_id: u64, proposal: Proposal) -> ProposalResponse {
+    ProposalResponse {
+        proposal_id,
+        confirmations: current_confirmations(config, &proposal),
+        required: config.required(&proposal.action),
+        proposer: proposal.proposer,
+        action: proposal.action,
+        executed: proposal.executed,
+    }
+}
+
+pub fn query_proposal(deps: Deps, proposal_id: u64) -> StdResult<ProposalResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let proposal = load_proposal(deps, proposal_id)?;
+
+    Ok(proposal_response(&config, proposal_id, proposal))
+}
+
+pub fn query_proposals(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<ProposalsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let proposals = read_proposals(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(proposal_id, proposal)| proposal_response(&config, proposal_id, proposal))
+        .collect();
+
+    Ok(ProposalsResponse { proposals })
+}
@@ -0,0 +1,60 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::admin_proxy::Action;
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub signers: Vec<Addr>,
+    pub threshold: u64,
+    pub pause_threshold: u64,
+    pub factory: Addr,
+    pub router: Addr,
+}
+
+impl Config {
+    /// Returns the confirmations an action needs
+    pub fn required(&self, action: &Action) -> u64 {
+        match action {
+            Action::Pause {} => self.pause_threshold,
+            _ => self.threshold,
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub action: Action,
+    pub confirmations: Vec<Addr>,
+    pub executed: bool,
+}
+
+pub const PROPOSALS: Map<U64Key, Proposal> = Map::new("proposals");
+
+/// the id of the next proposal opened
+pub const NEXT_PROPOSAL_ID: Item<u64> = Item::new("next_proposal_id");
+
+pub fn read_proposals(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Proposal)>> {
+    let start = start_after_id_bound(start_after);
+
+    PROPOSALS
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .collect()
+}
@@ -0,0 +1,261 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, OwnedDeps, StdError, WasmMsg};
+use prismswap::admin_proxy::{
+    Action, ConfigResponse, ExecuteMsg, InstantiateMsg, ProposalResponse, QueryMsg,
+};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::router::ExecuteMsg as RouterExecuteMsg;
+
+/// Instantiates the contract with three signers, two confirming config changes and one a pause
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        signers: vec![
+            Addr::unchecked("signer0000"),
+            Addr::unchecked("signer0001"),
+            Addr::unchecked("signer0002"),
+        ],
+        threshold: 2,
+        pause_threshold: 1,
+        factory: Addr::unchecked("factory0000"),
+        router: Addr::unchecked("router0000"),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    deps
+}
+
+fn query_proposal(
+    deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    proposal_id: u64,
+) -> ProposalResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Proposal { proposal_id },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn config_change() {
+    let mut deps = setup();
+
+    let router_msg = to_binary(&RouterExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("gov0000")),
+        guardian: None,
+    })
+    .unwrap();
+    let propose = |contract_addr: &str| ExecuteMsg::Propose {
+        action: Action::Execute {
+            contract_addr: Addr::unchecked(contract_addr),
+            msg: router_msg.clone(),
+        },
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        propose("router0000"),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0000", &[]),
+        propose("token0000"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("only the factory and the router can be executed")
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0000", &[]),
+        propose("router0000"),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("not enough confirmations"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0001", &[]),
+        ExecuteMsg::Confirm { proposal_id: 1 },
+    )
+    .unwrap();
+    let proposal = query_proposal(&deps, 1);
+    assert_eq!(proposal.confirmations.len(), 2);
+    assert_eq!(proposal.required, 2);
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router0000".to_string(),
+            msg: router_msg.clone(),
+            funds: vec![],
+        })
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("proposal already executed"));
+}
+
+#[test]
+fn pause() {
+    let mut deps = setup();
+
+    // a single signer pauses
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0002", &[]),
+        ExecuteMsg::Propose {
+            action: Action::Pause {},
+        },
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0002", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages
+            .into_iter()
+            .map(|msg| msg.msg)
+            .collect::<Vec<CosmosMsg>>(),
+        vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "router0000".to_string(),
+                msg: to_binary(&RouterExecuteMsg::Pause {}).unwrap(),
+                funds: vec![],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "factory0000".to_string(),
+                msg: to_binary(&FactoryExecuteMsg::SetWithdrawOnly {
+                    withdraw_only: true
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+}
+
+#[test]
+fn update_signers() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::Propose {
+        action: Action::UpdateSigners {
+            signers: vec![Addr::unchecked("signer0000")],
+            threshold: 2,
+            pause_threshold: 1,
+        },
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("signer0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("threshold must not exceed the number of signers")
+    );
+
+    let msg = ExecuteMsg::Propose {
+        action: Action::UpdateSigners {
+            signers: vec![Addr::unchecked("signer0000"), Addr::unchecked("signer0003")],
+            threshold: 2,
+            pause_threshold: 2,
+        },
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("signer0000", &[]), msg).unwrap();
+    // a pending pause confirmed by a signer about to be removed
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0001", &[]),
+        ExecuteMsg::Propose {
+            action: Action::Pause {},
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0001", &[]),
+        ExecuteMsg::Revoke { proposal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("proposal not confirmed"));
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("signer0002", &[]),
+        ExecuteMsg::Confirm { proposal_id: 1 },
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Execute { proposal_id: 1 },
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.signers,
+        vec![Addr::unchecked("signer0000"), Addr::unchecked("signer0003")]
+    );
+    assert_eq!(config.pause_threshold, 2);
+
+    // the confirmation of the removed signer no longer counts
+    let proposal = query_proposal(&deps, 2);
+    assert_eq!(proposal.confirmations, Vec::<Addr>::new());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Execute { proposal_id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("not enough confirmations"));
+}
@@ -0,0 +1,578 @@
+use crate::error::ContractError;
+use crate::state::{Config, State, CONFIG, PURCHASED, REGISTERED, SEEDED_PAIR, STATE};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
+};
+
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::auction::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, ProceedsTarget, QueryMsg,
+    StateResponse,
+};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+use prismswap::querier::{query_pair_info, query_supply};
+use prismswap::sale::{ParticipantResponse, Whitelist};
+
+const SEED_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    msg.sale_asset.check(deps.api)?;
+    msg.quote_asset.check(deps.api)?;
+
+    if msg.sale_asset == msg.quote_asset {
+        return Err(ContractError::DoublingAssets {});
+    }
+
+    if msg.start_time >= msg.end_time {
+        return Err(ContractError::InvalidSchedule {});
+    }
+
+    if msg.end_price.is_zero() || msg.start_price <= msg.end_price {
+        return Err(ContractError::InvalidPrices {});
+    }
+
+    let whitelist = match msg.whitelist {
+        Some(Whitelist::Addresses { addresses }) => Some(Whitelist::Addresses {
+            addresses: addresses
+                .iter()
+                .map(|address| deps.api.addr_validate(address.as_str()))
+                .collect::<StdResult<Vec<Addr>>>()?,
+        }),
+        Some(whitelist) => {
+            whitelist.validate()?;
+            Some(whitelist)
+        }
+        None => None,
+    };
+
+    if let Some(purchase_cap) = &msg.purchase_cap {
+        if purchase_cap.info != msg.sale_asset {
+            return Err(ContractError::AssetMismatch {});
+        }
+    }
+
+    let proceeds_target = match msg.proceeds_target {
+        ProceedsTarget::Treasury { address } => ProceedsTarget::Treasury {
+            address: deps.api.addr_validate(address.as_str())?,
+        },
+        ProceedsTarget::Pair {} => ProceedsTarget::Pair {},
+    };
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        sale_asset: msg.sale_asset,
+        quote_asset: msg.quote_asset,
+        start_price: msg.start_price,
+        end_price: msg.end_price,
+        start_time: msg.start_time,
+        end_time: msg.end_time,
+        proceeds_target,
+        whitelist,
+        purchase_cap: msg.purchase_cap,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    STATE.save(deps.storage, &State::default())?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Deposit { asset } => {
+            asset.info.check(deps.api)?;
+            deposit(deps, env, info, asset)
+        }
+        ExecuteMsg::Buy { max_price, to } => {
+            let config: Config = CONFIG.load(deps.storage)?;
+            if !config.quote_asset.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let offer_asset = Asset {
+                amount: info
+                    .funds
+                    .iter()
+                    .find(|coin| config.quote_asset == AssetInfo::Native(coin.denom.clone()))
+                    .map(|coin| coin.amount)
+                    .unwrap_or_default(),
+                info: config.quote_asset,
+            };
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(deps.api.addr_validate(&to_addr)?)
+            } else {
+                None
+            };
+
+            buy(deps, env, info.sender, offer_asset, max_price, to_addr)
+        }
+        ExecuteMsg::Settle {} => settle(deps, env),
+        ExecuteMsg::Register { proof } => register(deps, info, proof),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let contract_addr = info.sender;
+
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Buy { max_price, to }) => {
+            // only quote asset contract can execute this message
+            let config: Config = CONFIG.load(deps.storage)?;
+            if config.quote_asset != AssetInfo::Cw20(contract_addr.clone()) {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(deps.api.addr_validate(to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            buy(
+                deps,
+                env,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Cw20(contract_addr),
+                    amount: cw20_msg.amount,
+                },
+                max_price,
+                to_addr,
+            )
+        }
+        Err(err) => Err(ContractError::Std(err)),
+    }
+}
+
+// Only owner can execute it
+/// CONTRACT - should approve contract to use the amount of token
+pub fn deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset: Asset,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() >= config.start_time {
+        return Err(ContractError::AlreadyStarted {});
+    }
+
+    if asset.info != config.sale_asset {
+        return Err(ContractError::AssetMismatch {});
+    }
+    asset.assert_sent_native_token_balance(&info)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    // If the sale asset is token contract, then we need to execute TransferFrom msg to receive funds
+    if let AssetInfo::Cw20(contract_addr) = &asset.info {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: info.sender.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount: asset.amount,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "deposit"),
+        ("sender", info.sender.as_str()),
+        ("asset", &asset.to_string()),
+    ]))
+}
+
+pub fn buy(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer_asset: Asset,
+    max_price: Option<Decimal>,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    if now < config.start_time || now >= config.end_time {
+        return Err(ContractError::NotActive {});
+    }
+
+    if !is_whitelisted(deps.as_ref(), &config, &sender) {
+        return Err(ContractError::NotWhitelisted {});
+    }
+
+    let price = config.price(now);
+    if let Some(max_price) = max_price {
+        if price > max_price {
+            return Err(ContractError::MaxPriceAssertion {});
+        }
+    }
+
+    let available = config
+        .sale_asset
+        .query_pool(&deps.querier, &env.contract.address)?;
+    if available.is_zero() {
+        return Err(ContractError::SoldOut {});
+    }
+
+    let price256: Decimal256 = price.into();
+    let mut bought_amount: Uint128 = (Uint256::from(offer_asset.amount) / price256).into();
+    let mut cost_amount = offer_asset.amount;
+    // the last buyer gets the sale asset left, the rest of the offer being refunded
+    if bought_amount > available {
+        bought_amount = available;
+        cost_amount = (Uint256::from(available) * price256).into();
+    }
+    if bought_amount.is_zero() {
+        return Err(ContractError::PurchaseTooSmall {});
+    }
+    let refund_amount = offer_asset.amount.checked_sub(cost_amount)?;
+    record_purchase(
+        deps.branch(),
+        &config,
+        &sender,
+        &Asset::new(config.sale_asset.clone(), bought_amount),
+    )?;
+
+    let mut state: State = STATE.load(deps.storage)?;
+    state.sold += bought_amount;
+    state.raised += cost_amount;
+    state.clearing_price = Some(price);
+    STATE.save(deps.storage, &state)?;
+
+    let receiver = to.unwrap_or_else(|| sender.clone());
+    let mut messages: Vec<CosmosMsg> =
+        vec![Asset::new(config.sale_asset.clone(), bought_amount).transfer_msg(&receiver)?];
+    if !refund_amount.is_zero() {
+        messages.push(Asset::new(offer_asset.info.clone(), refund_amount).transfer_msg(&sender)?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "buy"),
+        ("sender", sender.as_str()),
+        ("receiver", receiver.as_str()),
+        ("price", &price.to_string()),
+        ("offer_amount", &cost_amount.to_string()),
+        ("return_amount", &bought_amount.to_string()),
+        ("refund_amount", &refund_amount.to_string()),
+    ]))
+}
+
+pub fn settle(deps: DepsMut, env: Env) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut state: State = STATE.load(deps.storage)?;
+
+    if state.settled {
+        return Err(ContractError::AlreadySettled {});
+    }
+
+    let available = config
+        .sale_asset
+        .query_pool(&deps.querier, &env.contract.address)?;
+    if env.block.time.seconds() < config.end_time && !available.is_zero() {
+        return Err(ContractError::NotOver {});
+    }
+
+    match &config.proceeds_target {
+        // without proceeds there is no price to seed a pair at
+        ProceedsTarget::Pair {} if !state.raised.is_zero() => {
+            match query_pair_info(&deps.querier, &config.factory, &pair_asset_infos(&config)) {
+                Ok(pair_info) => seed_pair(deps, env, config, state, pair_info),
+                // create the pair first, seeding it once created
+                Err(_) => Ok(Response::new()
+                    .add_submessage(SubMsg::reply_on_success(
+                        WasmMsg::Execute {
+                            contract_addr: config.factory.to_string(),
+                            msg: to_binary(&FactoryExecuteMsg::CreatePair {
+                                asset_infos: pair_asset_infos(&config),
+                                fee_config: None,
+                            })?,
+                            funds: vec![],
+                        },
+                        SEED_REPLY_ID,
+                    ))
+                    .add_attribute("action", "create_auction_pair")),
+            }
+        }
+        proceeds_target => {
+            let mut messages: Vec<CosmosMsg> = vec![];
+            if let ProceedsTarget::Treasury { address } = proceeds_target {
+                if !state.raised.is_zero() {
+                    messages.push(
+                        Asset::new(config.quote_asset.clone(), state.raised)
+                            .transfer_msg(address)?,
+                    );
+                }
+            }
+            if !available.is_zero() {
+                messages.push(
+                    Asset::new(config.sale_asset.clone(), available).transfer_msg(&config.owner)?,
+                );
+            }
+
+            state.settled = true;
+            STATE.save(deps.storage, &state)?;
+
+            Ok(Response::new().add_messages(messages).add_attributes(vec![
+                ("action", "settle"),
+                ("raised_amount", &state.raised.to_string()),
+                ("unsold_amount", &available.to_string()),
+            ]))
+        }
+    }
+}
+
+fn pair_asset_infos(config: &Config) -> [AssetInfo; 2] {
+    [config.sale_asset.clone(), config.quote_asset.clone()]
+}
+
+/// Seeds the pair with the proceeds at the clearing price, the unsold sale asset exceeding that
+/// price being sent to the owner with the LP tokens
+fn seed_pair(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    mut state: State,
+    pair_info: PairInfo,
+) -> Result<Response, ContractError> {
+    // the first provide sets the price of the pair
+    if !query_supply(&deps.querier, &pair_info.liquidity_token)?.is_zero() {
+        return Err(ContractError::PairNotEmpty {});
+    }
+
+    let available = config
+        .sale_asset
+        .query_pool(&deps.querier, &env.contract.address)?;
+    let price: Decimal256 = state.clearing_price.unwrap_or(config.end_price).into();
+
+    let sale_amount: Uint128 = (Uint256::from(state.raised) / price).into();
+    let (sale_amount, quote_amount) = if sale_amount <= available {
+        (sale_amount, state.raised)
+    } else {
+        (available, (Uint256::from(available) * price).into())
+    };
+    let deposits = [
+        Asset::new(config.sale_asset.clone(), sale_amount),
+        Asset::new(config.quote_asset.clone(), quote_amount),
+    ];
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for deposit in deposits.iter() {
+        match &deposit.info {
+            AssetInfo::Cw20(contract_addr) => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: pair_info.contract_addr.to_string(),
+                    amount: deposit.amount,
+                    expires: None,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::Native(denom) => funds.push(Coin {
+                denom: denom.to_string(),
+                amount: deposit.amount,
+            }),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair_info.contract_addr.to_string(),
+        msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets: deposits.clone(),
+            slippage_tolerance: None,
+            receiver: Some(config.owner.to_string()),
+            auto_stake: None,
+        })?,
+        funds,
+    }));
+
+    let rests = [
+        Asset::new(
+            config.sale_asset.clone(),
+            available.checked_sub(sale_amount)?,
+        ),
+        Asset::new(
+            config.quote_asset.clone(),
+            state.raised.checked_sub(quote_amount)?,
+        ),
+    ];
+    for rest in rests.iter() {
+        if !rest.amount.is_zero() {
+            messages.push(rest.transfer_msg(&config.owner)?);
+        }
+    }
+
+    state.settled = true;
+    STATE.save(deps.storage, &state)?;
+    SEEDED_PAIR.save(deps.storage, &pair_info.contract_addr)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "settle"),
+        ("pair", pair_info.contract_addr.as_str()),
+        ("assets", &format!("{}, {}", deposits[0], deposits[1])),
+    ]))
+}
+
+/// Seeds the pair created by the factory for the settlement
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != SEED_REPLY_ID {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+    let pair_info = query_pair_info(&deps.querier, &config.factory, &pair_asset_infos(&config))?;
+
+    seed_pair(deps, env, config, state, pair_info)
+}
+
+pub fn register(
+    deps: DepsMut,
+    info: MessageInfo,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    match &config.whitelist {
+        Some(whitelist @ Whitelist::MerkleRoot { .. }) => {
+            if !whitelist.verify(&info.sender, &proof)? {
+                return Err(ContractError::InvalidProof {});
+            }
+        }
+        _ => return Err(ContractError::NoMerkleRoot {}),
+    }
+
+    REGISTERED.save(deps.storage, &info.sender, &true)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register"),
+        ("address", info.sender.as_str()),
+    ]))
+}
+
+fn is_whitelisted(deps: Deps, config: &Config, address: &Addr) -> bool {
+    match &config.whitelist {
+        None => true,
+        Some(Whitelist::Addresses { addresses }) => addresses.contains(address),
+        Some(Whitelist::MerkleRoot { .. }) => REGISTERED.has(deps.storage, address),
+    }
+}
+
+/// Adds `asset` to the purchases of `buyer`, if it is the capped asset
+fn record_purchase(
+    deps: DepsMut,
+    config: &Config,
+    buyer: &Addr,
+    asset: &Asset,
+) -> Result<(), ContractError> {
+    if let Some(purchase_cap) = &config.purchase_cap {
+        if purchase_cap.info == asset.info {
+            let purchased = PURCHASED
+                .may_load(deps.storage, buyer)?
+                .unwrap_or_default()
+                .checked_add(asset.amount)?;
+            if purchased > purchase_cap.amount {
+                return Err(ContractError::PurchaseCapExceeded {});
+            }
+            PURCHASED.save(deps.storage, buyer, &purchased)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
+        QueryMsg::State {} => Ok(to_binary(&query_state(deps, env)?)?),
+        QueryMsg::Participant { address } => Ok(to_binary(&query_participant(deps, address)?)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        sale_asset: config.sale_asset,
+        quote_asset: config.quote_asset,
+        start_price: config.start_price,
+        end_price: config.end_price,
+        start_time: config.start_time,
+        end_time: config.end_time,
+        proceeds_target: config.proceeds_target,
+        whitelist: config.whitelist,
+        purchase_cap: config.purchase_cap,
+    })
+}
+
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let state: State = STATE.load(deps.storage)?;
+
+    Ok(StateResponse {
+        price: config.price(env.block.time.seconds()),
+        available: config
+            .sale_asset
+            .query_pool(&deps.querier, &env.contract.address)?,
+        sold: state.sold,
+        raised: state.raised,
+        clearing_price: state.clearing_price,
+        settled: state.settled,
+        pair: SEEDED_PAIR.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_participant(
+    deps: Deps,
+    address: String,
+) -> Result<ParticipantResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(ParticipantResponse {
+        whitelisted: is_whitelisted(deps, &config, &address),
+        purchased: PURCHASED
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+        address,
+    })
+}
@@ -0,0 +1,62 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Sender is not whitelisted")]
+    NotWhitelisted {},
+
+    #[error("Whitelist is not a merkle root")]
+    NoMerkleRoot {},
+
+    #[error("Invalid merkle proof")]
+    InvalidProof {},
+
+    #[error("Purchase exceeds the purchase cap")]
+    PurchaseCapExceeded {},
+
+    #[error("Asset mismatch")]
+    AssetMismatch {},
+
+    #[error("Sale and quote assets must differ")]
+    DoublingAssets {},
+
+    #[error("Auction must end after it starts")]
+    InvalidSchedule {},
+
+    #[error("Price must descend from the start price to a nonzero end price")]
+    InvalidPrices {},
+
+    #[error("Auction has already started")]
+    AlreadyStarted {},
+
+    #[error("Auction is not active")]
+    NotActive {},
+
+    #[error("Auction is neither over nor sold out")]
+    NotOver {},
+
+    #[error("Sale asset is sold out")]
+    SoldOut {},
+
+    #[error("Current price exceeds the max price")]
+    MaxPriceAssertion {},
+
+    #[error("Purchase is too small to buy any sale asset")]
+    PurchaseTooSmall {},
+
+    #[error("Auction has already been settled")]
+    AlreadySettled {},
+
+    #[error("Pair to seed already has liquidity")]
+    PairNotEmpty {},
+}
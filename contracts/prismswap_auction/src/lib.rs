@@ -0,0 +1,10 @@
+pub mod contract;
+pub mod state;
+
+mod error;
+
+#[cfg(test)]
+mod testing;
+
+#[cfg(test)]
+mod mock_querier;
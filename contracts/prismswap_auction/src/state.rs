@@ -0,0 +1,65 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::auction::ProceedsTarget;
+use prismswap::sale::Whitelist;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const STATE: Item<State> = Item::new("state");
+
+/// the pair the proceeds seeded
+pub const SEEDED_PAIR: Item<Addr> = Item::new("seeded_pair");
+
+/// the addresses that proved their leaf in the merkle tree of the whitelist
+pub const REGISTERED: Map<&Addr, bool> = Map::new("registered");
+
+/// the amount of the capped asset bought by every address
+pub const PURCHASED: Map<&Addr, Uint128> = Map::new("purchased");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// the project depositing the sale asset and settling the auction
+    pub owner: Addr,
+    /// the factory the pair seeded with the proceeds is looked up in
+    pub factory: Addr,
+    pub sale_asset: AssetInfo,
+    pub quote_asset: AssetInfo,
+    pub start_price: Decimal,
+    pub end_price: Decimal,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub proceeds_target: ProceedsTarget,
+    pub whitelist: Option<Whitelist>,
+    pub purchase_cap: Option<Asset>,
+}
+
+impl Config {
+    /// the price at `time`, descending linearly from the start price to the end price
+    pub fn price(&self, time: u64) -> Decimal {
+        if time <= self.start_time {
+            return self.start_price;
+        }
+        if time >= self.end_time {
+            return self.end_price;
+        }
+
+        let progress =
+            Decimal256::from_ratio(time - self.start_time, self.end_time - self.start_time);
+        let start: Decimal256 = self.start_price.into();
+        let end: Decimal256 = self.end_price.into();
+        (start - (start - end) * progress).into()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct State {
+    pub sold: Uint128,
+    /// the quote asset paid for the sale asset sold
+    pub raised: Uint128,
+    /// the price of the last purchase
+    pub clearing_price: Option<Decimal>,
+    pub settled: bool,
+}
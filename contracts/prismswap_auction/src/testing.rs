@@ -0,0 +1,573 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, ContractResult, CosmosMsg, Decimal, Env, Reply,
+    StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::auction::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, ProceedsTarget, QueryMsg, StateResponse,
+};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+use prismswap::sale::{ParticipantResponse, Whitelist};
+use std::str::FromStr;
+
+const START: u64 = 100;
+const END: u64 = 1100;
+
+fn mock_env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    let now = mock_env().block.time.seconds();
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        factory: Addr::unchecked("factory0000"),
+        sale_asset: AssetInfo::Native("ulaunch".to_string()),
+        quote_asset: AssetInfo::Native("uusd".to_string()),
+        start_price: Decimal::from_str("2").unwrap(),
+        end_price: Decimal::from_str("0.5").unwrap(),
+        start_time: now + START,
+        end_time: now + END,
+        proceeds_target: ProceedsTarget::Treasury {
+            address: Addr::unchecked("treasury0000"),
+        },
+        whitelist: None,
+        purchase_cap: None,
+    }
+}
+
+fn buy_msg() -> ExecuteMsg {
+    ExecuteMsg::Buy {
+        max_price: None,
+        to: None,
+    }
+}
+
+fn query_state(deps: cosmwasm_std::Deps, env: Env) -> StateResponse {
+    from_binary(&query(deps, env, QueryMsg::State {}).unwrap()).unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.quote_asset = AssetInfo::Native("ulaunch".to_string());
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::DoublingAssets {});
+
+    let mut msg = instantiate_msg();
+    msg.end_time = msg.start_time;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule {});
+
+    let mut msg = instantiate_msg();
+    msg.end_price = msg.start_price;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPrices {});
+
+    let mut msg = instantiate_msg();
+    msg.end_price = Decimal::zero();
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidPrices {});
+
+    let msg = instantiate_msg();
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: msg.owner,
+            factory: msg.factory,
+            sale_asset: msg.sale_asset,
+            quote_asset: msg.quote_asset,
+            start_price: msg.start_price,
+            end_price: msg.end_price,
+            start_time: msg.start_time,
+            end_time: msg.end_time,
+            proceeds_target: msg.proceeds_target,
+            whitelist: msg.whitelist,
+            purchase_cap: msg.purchase_cap,
+        }
+    );
+
+    // the price descends linearly from the start price to the end price
+    let prices: Vec<Decimal> = [0, START + 500, END, END + 100]
+        .iter()
+        .map(|seconds| query_state(deps.as_ref(), mock_env_after(*seconds)).price)
+        .collect();
+    assert_eq!(
+        prices,
+        vec![
+            Decimal::from_str("2").unwrap(),
+            Decimal::from_str("1.25").unwrap(),
+            Decimal::from_str("0.5").unwrap(),
+            Decimal::from_str("0.5").unwrap(),
+        ]
+    );
+}
+
+#[test]
+fn deposit() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Deposit {
+        asset: Asset::new(AssetInfo::Native("ulaunch".to_string()), 1_000_000u128),
+    };
+
+    let info = mock_info("addr0000", &[coin(1_000_000, "ulaunch")]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("owner0000", &[coin(1_000_000, "ulaunch")]);
+    let err = execute(deps.as_mut(), mock_env_after(START), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::AlreadyStarted {});
+
+    let info = mock_info("owner0000", &[coin(1_000_000, "uusd")]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::Deposit {
+            asset: Asset::new(AssetInfo::Native("uusd".to_string()), 1_000_000u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let info = mock_info("owner0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    let info = mock_info("owner0000", &[coin(1_000_000, "ulaunch")]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+}
+
+#[test]
+fn buy() {
+    let mut deps = mock_dependencies(&[coin(1_000_000, "ulaunch")]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let info = mock_info("addr0000", &[coin(1_000_000, "uusd")]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), buy_msg()).unwrap_err();
+    assert_eq!(err, ContractError::NotActive {});
+    let err = execute(deps.as_mut(), mock_env_after(END), info.clone(), buy_msg()).unwrap_err();
+    assert_eq!(err, ContractError::NotActive {});
+
+    let env = mock_env_after(START + 500);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        info.clone(),
+        ExecuteMsg::Buy {
+            max_price: Some(Decimal::from_str("1.2").unwrap()),
+            to: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MaxPriceAssertion {});
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(1, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PurchaseTooSmall {});
+
+    // 1_000_000 uusd buys 800_000 ulaunch at 1.25
+    let res = execute(deps.as_mut(), env.clone(), info, buy_msg()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(800_000, "ulaunch")],
+        }))]
+    );
+
+    // the last buyer gets the 200_000 ulaunch left for 250_000 uusd, the rest being refunded
+    deps.querier.with_balance(&[(
+        &MOCK_CONTRACT_ADDR.to_string(),
+        vec![coin(200_000, "ulaunch")],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[coin(500_000, "uusd")]),
+        ExecuteMsg::Buy {
+            max_price: Some(Decimal::from_str("1.25").unwrap()),
+            to: Some("addr0002".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0002".to_string(),
+                amount: vec![coin(200_000, "ulaunch")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0001".to_string(),
+                amount: vec![coin(250_000, "uusd")],
+            })),
+        ]
+    );
+
+    deps.querier
+        .with_balance(&[(&MOCK_CONTRACT_ADDR.to_string(), vec![])]);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(1_000_000, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::SoldOut {});
+
+    assert_eq!(
+        query_state(deps.as_ref(), env),
+        StateResponse {
+            price: Decimal::from_str("1.25").unwrap(),
+            available: Uint128::zero(),
+            sold: Uint128::from(1_000_000u128),
+            raised: Uint128::from(1_250_000u128),
+            clearing_price: Some(Decimal::from_str("1.25").unwrap()),
+            settled: false,
+            pair: None,
+        }
+    );
+}
+
+#[test]
+fn merkle_whitelist() {
+    let mut deps = mock_dependencies(&[coin(1_000_000, "ulaunch")]);
+
+    let mut msg = instantiate_msg();
+    msg.whitelist = Some(Whitelist::MerkleRoot {
+        root: "abcd".to_string(),
+    });
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Merkle hashes must be 32 hex encoded bytes"
+        ))
+    );
+
+    let mut msg = instantiate_msg();
+    msg.purchase_cap = Some(Asset::new(
+        AssetInfo::Native("uusd".to_string()),
+        500_000u128,
+    ));
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    // the leaves of the tree are addr0000 and addr0001
+    let mut msg = instantiate_msg();
+    msg.whitelist = Some(Whitelist::MerkleRoot {
+        root: "ed3c59443e4b48ebbd051989e3280f76c66cf50ecfa9def5f8b843ebdc636874".to_string(),
+    });
+    msg.purchase_cap = Some(Asset::new(
+        AssetInfo::Native("ulaunch".to_string()),
+        500_000u128,
+    ));
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let env = mock_env_after(START + 500);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(500_000, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotWhitelisted {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::Register {
+            proof: vec![
+                "72c1df537643913c18ecab721a7488dcc1e4403de2508c532c8f347caeb101b7".to_string(),
+            ],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidProof {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Register {
+            proof: vec![
+                "72c1df537643913c18ecab721a7488dcc1e4403de2508c532c8f347caeb101b7".to_string(),
+            ],
+        },
+    )
+    .unwrap();
+
+    // 500_000 uusd buys 400_000 ulaunch at 1.25, another 200_000 uusd would exceed the cap
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(500_000, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[coin(200_000, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PurchaseCapExceeded {});
+
+    let participants: Vec<ParticipantResponse> = ["addr0000", "addr0001"]
+        .iter()
+        .map(|address| {
+            from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::Participant {
+                        address: address.to_string(),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap()
+        })
+        .collect();
+    assert_eq!(
+        participants,
+        vec![
+            ParticipantResponse {
+                address: Addr::unchecked("addr0000"),
+                whitelisted: true,
+                purchased: Uint128::from(400_000u128),
+            },
+            ParticipantResponse {
+                address: Addr::unchecked("addr0001"),
+                whitelisted: false,
+                purchased: Uint128::zero(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn settle_to_treasury() {
+    let mut deps = mock_dependencies(&[coin(1_000_000, "ulaunch")]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let env = mock_env_after(START + 500);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[coin(1_000_000, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap();
+    deps.querier.with_balance(&[(
+        &MOCK_CONTRACT_ADDR.to_string(),
+        vec![coin(200_000, "ulaunch")],
+    )]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END - 1),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Settle {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOver {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Settle {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury0000".to_string(),
+                amount: vec![coin(1_000_000, "uusd")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner0000".to_string(),
+                amount: vec![coin(200_000, "ulaunch")],
+            })),
+        ]
+    );
+    assert!(query_state(deps.as_ref(), mock_env_after(END)).settled);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Settle {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadySettled {});
+}
+
+#[test]
+fn settle_into_pair() {
+    let mut deps = mock_dependencies(&[coin(1_000_000, "ulaunch")]);
+    let mut msg = instantiate_msg();
+    msg.proceeds_target = ProceedsTarget::Pair {};
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env_after(START + 500),
+        mock_info("addr0000", &[coin(1_000_000, "uusd")]),
+        buy_msg(),
+    )
+    .unwrap();
+    deps.querier.with_balance(&[(
+        &MOCK_CONTRACT_ADDR.to_string(),
+        vec![coin(1_000_000, "ulaunch")],
+    )]);
+
+    // the factory has no pair yet, create it first
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Settle {},
+    )
+    .unwrap();
+    let asset_infos = [
+        AssetInfo::Native("ulaunch".to_string()),
+        AssetInfo::Native("uusd".to_string()),
+    ];
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: "factory0000".to_string(),
+                msg: to_binary(&FactoryExecuteMsg::CreatePair {
+                    asset_infos: asset_infos.clone(),
+                    fee_config: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+            1
+        )]
+    );
+
+    deps.querier.with_pairs(&[asset_infos]);
+    deps.querier
+        .with_token_supplies(&[(&"liquidity0000".to_string(), &Uint128::from(1000u128))]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Settle {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotEmpty {});
+
+    deps.querier
+        .with_token_supplies(&[(&"liquidity0000".to_string(), &Uint128::zero())]);
+    let res = reply(
+        deps.as_mut(),
+        mock_env_after(END),
+        Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    // the 1_000_000 uusd raised are paired with 800_000 ulaunch at the clearing price of 1.25
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        Asset::new(AssetInfo::Native("ulaunch".to_string()), 800_000u128),
+                        Asset::new(AssetInfo::Native("uusd".to_string()), 1_000_000u128),
+                    ],
+                    slippage_tolerance: None,
+                    receiver: Some("owner0000".to_string()),
+                    auto_stake: None,
+                })
+                .unwrap(),
+                funds: vec![coin(800_000, "ulaunch"), coin(1_000_000, "uusd")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner0000".to_string(),
+                amount: vec![coin(200_000, "ulaunch")],
+            })),
+        ]
+    );
+
+    let state = query_state(deps.as_ref(), mock_env_after(END));
+    assert!(state.settled);
+    assert_eq!(state.pair, Some(Addr::unchecked("pair0000")));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Settle {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadySettled {});
+}
@@ -0,0 +1,447 @@
+use crate::state::{Bond, Config, Market, BONDS, CONFIG, MARKETS, TOTAL_PAYOUT};
+
+use cosmwasm_bignumber::{Decimal256, Uint256};
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use prismswap::asset::{AssetInfo, PrismSwapAssetInfo};
+use prismswap::bond::{
+    BondResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MarketResponse, QueryMsg,
+};
+use prismswap::math::{decimal_atomics, DECIMAL_FRACTIONAL, U256};
+use prismswap::querier::{
+    query_pair_info_from_pair, query_supply, query_token_balance, PrismSwapQuerier,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        prism_token: deps.api.addr_validate(msg.prism_token.as_str())?,
+        treasury: deps.api.addr_validate(msg.treasury.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    TOTAL_PAYOUT.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { owner, treasury } => {
+            execute_update_config(deps, info, owner, treasury)
+        }
+        ExecuteMsg::SetMarket {
+            lp_token,
+            pair,
+            max_discount,
+            vesting_term,
+            max_debt,
+            twap_window,
+        } => execute_set_market(
+            deps,
+            env,
+            info,
+            lp_token,
+            pair,
+            max_discount,
+            vesting_term,
+            max_debt,
+            twap_window,
+        ),
+        ExecuteMsg::RemoveMarket { lp_token } => execute_remove_market(deps, info, lp_token),
+        ExecuteMsg::Claim { lp_token } => execute_claim(deps, env, info, lp_token),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Bond { min_payout } => {
+            let bonder = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_bond(deps, env, info.sender, bonder, cw20_msg.amount, min_payout)
+        }
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    treasury: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(treasury) = treasury {
+        config.treasury = deps.api.addr_validate(treasury.as_str())?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_set_market(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+    pair: Addr,
+    max_discount: Decimal,
+    vesting_term: u64,
+    max_debt: Uint128,
+    twap_window: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if max_discount >= Decimal::one() {
+        return Err(StdError::generic_err("max discount must be below 1"));
+    }
+
+    if vesting_term == 0 {
+        return Err(StdError::generic_err("vesting term must be greater than 0"));
+    }
+
+    if twap_window == 0 {
+        return Err(StdError::generic_err("twap window must be greater than 0"));
+    }
+
+    // the LP tokens are valued through the PRISM side of the pair
+    let pair_info = query_pair_info_from_pair(&deps.querier, &pair)?;
+    if pair_info.liquidity_token != lp_token {
+        return Err(StdError::generic_err("invalid lp token"));
+    }
+
+    let prism = AssetInfo::Cw20(config.prism_token);
+    if !pair_info.asset_infos.contains(&prism) {
+        return Err(StdError::generic_err("pair must include the prism token"));
+    }
+
+    let market = match MARKETS.may_load(deps.storage, &lp_token)? {
+        Some(mut market) => {
+            market.decay_debt(env.block.time.seconds());
+            Market {
+                pair: pair_info.contract_addr,
+                max_discount,
+                vesting_term,
+                max_debt,
+                twap_window,
+                ..market
+            }
+        }
+        None => Market {
+            pair: pair_info.contract_addr,
+            max_discount,
+            vesting_term,
+            max_debt,
+            twap_window,
+            total_debt: Uint128::zero(),
+            last_decay_time: env.block.time.seconds(),
+        },
+    };
+
+    MARKETS.save(deps.storage, &lp_token, &market)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_market"),
+        ("lp_token", lp_token.as_str()),
+        ("max_discount", &max_discount.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_remove_market(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: Addr,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    load_market(deps.as_ref(), &lp_token)?;
+    MARKETS.remove(deps.storage, &lp_token);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_market"),
+        ("lp_token", lp_token.as_str()),
+    ]))
+}
+
+pub fn execute_bond(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    bonder: Addr,
+    amount: Uint128,
+    min_payout: Option<Uint128>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut market = load_market(deps.as_ref(), &lp_token)?;
+    let now = env.block.time.seconds();
+    market.decay_debt(now);
+
+    let (_, price) = compute_price(deps.as_ref(), &config, &lp_token, &market)?;
+    let payout = amount * price;
+    if payout.is_zero() {
+        return Err(StdError::generic_err("bond too small"));
+    }
+
+    if let Some(min_payout) = min_payout {
+        if payout < min_payout {
+            return Err(StdError::generic_err("payout below minimum"));
+        }
+    }
+
+    if market.total_debt + payout > market.max_debt {
+        return Err(StdError::generic_err("bond capacity exceeded"));
+    }
+
+    let total_payout = TOTAL_PAYOUT.load(deps.storage)?;
+    let prism_balance =
+        query_token_balance(&deps.querier, &config.prism_token, &env.contract.address)?;
+    if prism_balance < total_payout + payout {
+        return Err(StdError::generic_err("insufficient prism balance"));
+    }
+
+    // what the previous bond vested is paid out, the rest vesting again with the new payout
+    let bond = BONDS
+        .may_load(deps.storage, (&lp_token, &bonder))?
+        .unwrap_or(Bond {
+            payout: Uint128::zero(),
+            last_claim_time: now,
+            vesting_end: now,
+        });
+    let claimable = bond.claimable(now);
+    let bond = Bond {
+        payout: bond.payout - claimable + payout,
+        last_claim_time: now,
+        vesting_end: now + market.vesting_term,
+    };
+    BONDS.save(deps.storage, (&lp_token, &bonder), &bond)?;
+
+    market.total_debt += payout;
+    MARKETS.save(deps.storage, &lp_token, &market)?;
+    TOTAL_PAYOUT.save(deps.storage, &(total_payout + payout - claimable))?;
+
+    let mut messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: config.treasury.to_string(),
+            amount,
+        })?,
+    })];
+    if !claimable.is_zero() {
+        messages.push(prism_transfer_msg(&config, &bonder, claimable)?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "bond"),
+        ("bonder", bonder.as_str()),
+        ("lp_token", lp_token.as_str()),
+        ("amount", &amount.to_string()),
+        ("payout", &payout.to_string()),
+        ("claimed_amount", &claimable.to_string()),
+    ]))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    let mut bond = BONDS
+        .may_load(deps.storage, (&lp_token, &info.sender))?
+        .ok_or_else(|| StdError::generic_err("bond not found"))?;
+    let claimable = bond.claimable(now);
+    if claimable.is_zero() {
+        return Err(StdError::generic_err("nothing to claim"));
+    }
+
+    bond.payout -= claimable;
+    bond.last_claim_time = now;
+    if bond.payout.is_zero() {
+        BONDS.remove(deps.storage, (&lp_token, &info.sender));
+    } else {
+        BONDS.save(deps.storage, (&lp_token, &info.sender), &bond)?;
+    }
+
+    let total_payout = TOTAL_PAYOUT.load(deps.storage)?;
+    TOTAL_PAYOUT.save(deps.storage, &(total_payout - claimable))?;
+
+    Ok(Response::new()
+        .add_message(prism_transfer_msg(&config, &info.sender, claimable)?)
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("bonder", info.sender.as_str()),
+            ("lp_token", lp_token.as_str()),
+            ("claimed_amount", &claimable.to_string()),
+        ]))
+}
+
+fn load_market(deps: Deps, lp_token: &Addr) -> StdResult<Market> {
+    MARKETS
+        .may_load(deps.storage, lp_token)?
+        .ok_or_else(|| StdError::generic_err("market not found"))
+}
+
+/// Returns the discount at the outstanding debt, falling linearly from the max discount to zero
+/// as the debt reaches its max, and the PRISM an LP token is bonded for at that discount.
+///
+/// An LP token is worth its share of `2 * sqrt(prism_pool * other_pool * twap)`, the value in
+/// PRISM of the pools rebalanced at the TWAP of the other asset. The product of the pools only
+/// grows with a swap, so moving the spot reserves does not move the price.
+fn compute_price(
+    deps: Deps,
+    config: &Config,
+    lp_token: &Addr,
+    market: &Market,
+) -> StdResult<(Decimal, Decimal)> {
+    let discount = if market.total_debt >= market.max_debt {
+        Decimal::zero()
+    } else {
+        (Decimal256::from(market.max_discount)
+            * Decimal256::from_ratio(
+                Uint256::from(market.max_debt - market.total_debt),
+                Uint256::from(market.max_debt),
+            ))
+        .into()
+    };
+
+    let supply = query_supply(&deps.querier, lp_token)?;
+    if supply.is_zero() {
+        return Err(StdError::generic_err("pool is empty"));
+    }
+
+    let twap = PrismSwapQuerier::new(&deps.querier).twap(&market.pair, market.twap_window)?;
+    let prism_info = AssetInfo::Cw20(config.prism_token.clone());
+    // the TWAP of the other asset in PRISM
+    let (other_info, other_price) = if twap.asset_infos[0] == prism_info {
+        (&twap.asset_infos[1], twap.price1)
+    } else {
+        (&twap.asset_infos[0], twap.price0)
+    };
+
+    let prism_pool = prism_info.query_pool(&deps.querier, &market.pair)?;
+    let other_pool = other_info.query_pool(&deps.querier, &market.pair)?;
+    let pool_value = U256::from(prism_pool.u128())
+        .checked_mul(U256::from(other_pool.u128()))
+        .and_then(|product| product.checked_mul(U256::from(decimal_atomics(other_price))))
+        .map(|product| (product / U256::from(DECIMAL_FRACTIONAL)).integer_sqrt() * U256::from(2u8))
+        .filter(|value| *value <= U256::from(u128::MAX))
+        .ok_or_else(|| StdError::generic_err("pool value overflow"))?;
+
+    let value = Decimal256::from_ratio(Uint256::from(pool_value.as_u128()), Uint256::from(supply));
+    let price = value / (Decimal256::one() - Decimal256::from(discount));
+
+    Ok((discount, price.into()))
+}
+
+fn prism_transfer_msg(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.prism_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Market { lp_token } => to_binary(&query_market(deps, env, lp_token)?),
+        QueryMsg::Bond { address, lp_token } => {
+            to_binary(&query_bond(deps, env, address, lp_token)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        prism_token: config.prism_token,
+        treasury: config.treasury,
+        total_payout: TOTAL_PAYOUT.load(deps.storage)?,
+    })
+}
+
+pub fn query_market(deps: Deps, env: Env, lp_token: Addr) -> StdResult<MarketResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut market = load_market(deps, &lp_token)?;
+    market.decay_debt(env.block.time.seconds());
+
+    let (discount, price) = compute_price(deps, &config, &lp_token, &market)?;
+
+    Ok(MarketResponse {
+        lp_token,
+        pair: market.pair,
+        max_discount: market.max_discount,
+        vesting_term: market.vesting_term,
+        max_debt: market.max_debt,
+        twap_window: market.twap_window,
+        total_debt: market.total_debt,
+        discount,
+        price,
+    })
+}
+
+pub fn query_bond(deps: Deps, env: Env, address: Addr, lp_token: Addr) -> StdResult<BondResponse> {
+    let bond = BONDS
+        .may_load(deps.storage, (&lp_token, &address))?
+        .ok_or_else(|| StdError::generic_err("bond not found"))?;
+
+    Ok(BondResponse {
+        claimable: bond.claimable(env.block.time.seconds()),
+        address,
+        lp_token,
+        payout: bond.payout,
+        vesting_end: bond.vesting_end,
+    })
+}
@@ -0,0 +1,70 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub prism_token: Addr,
+    pub treasury: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// the PRISM owed to the vesting bonds, which the contract balance must cover
+pub const TOTAL_PAYOUT: Item<Uint128> = Item::new("total_payout");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Market {
+    pub pair: Addr,
+    pub max_discount: Decimal,
+    pub vesting_term: u64,
+    pub max_debt: Uint128,
+    pub twap_window: u64,
+    /// the outstanding debt, decaying linearly over the vesting term
+    pub total_debt: Uint128,
+    pub last_decay_time: u64,
+}
+
+impl Market {
+    /// Decays the outstanding debt to the given time, as the bonds vest
+    pub fn decay_debt(&mut self, time: u64) {
+        let elapsed = time.saturating_sub(self.last_decay_time);
+        let decay = self
+            .total_debt
+            .multiply_ratio(std::cmp::min(elapsed, self.vesting_term), self.vesting_term);
+
+        self.total_debt -= decay;
+        self.last_decay_time = time;
+    }
+}
+
+/// the bond markets, keyed by the LP token
+pub const MARKETS: Map<&Addr, Market> = Map::new("markets");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bond {
+    /// the PRISM not claimed yet
+    pub payout: Uint128,
+    /// the time of the last claim, from which the payout vests
+    pub last_claim_time: u64,
+    pub vesting_end: u64,
+}
+
+impl Bond {
+    /// Returns the part of the payout vested since the last claim
+    pub fn claimable(&self, time: u64) -> Uint128 {
+        if time >= self.vesting_end {
+            return self.payout;
+        }
+
+        self.payout.multiply_ratio(
+            time.saturating_sub(self.last_claim_time),
+            self.vesting_end - self.last_claim_time,
+        )
+    }
+}
+
+/// the bonds, keyed by the LP token and the bonder
+pub const BONDS: Map<(&Addr, &Addr), Bond> = Map::new("bonds");
@@ -0,0 +1,461 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Decimal, Env, OwnedDeps, StdError, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{AssetInfo, PairInfo};
+use prismswap::bond::{
+    BondResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MarketResponse, QueryMsg,
+};
+use prismswap::pair::TwapResponse;
+use prismswap::price::invert_price;
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+/// The TWAP of the PRISM-uusd pair, a uusd being worth the given PRISM
+fn twap(uusd_price: Decimal) -> TwapResponse {
+    TwapResponse {
+        asset_infos: [
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            AssetInfo::Native("uusd".to_string()),
+        ],
+        price0: invert_price(uusd_price).unwrap(),
+        price1: uusd_price,
+        window: 600,
+    }
+}
+
+/// Instantiates the contract holding 10000 PRISM, the LP tokens of the PRISM-uusd pair being
+/// worth 2 PRISM each
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_pair_info(PairInfo {
+        asset_infos: [
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            AssetInfo::Native("uusd".to_string()),
+        ],
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+    });
+    deps.querier.with_pair_info(PairInfo {
+        asset_infos: [
+            AssetInfo::Native("uluna".to_string()),
+            AssetInfo::Native("uusd".to_string()),
+        ],
+        contract_addr: Addr::unchecked("pair0001"),
+        liquidity_token: Addr::unchecked("liquidity0001"),
+    });
+    deps.querier
+        .with_token_balance("prism0000", "pair0000", Uint128::from(1000u128));
+    deps.querier
+        .with_token_balance("prism0000", MOCK_CONTRACT_ADDR, Uint128::from(10000u128));
+    deps.querier
+        .with_token_supply("liquidity0000", Uint128::from(1000u128));
+    deps.querier.with_balance("pair0000", &[coin(1000, "uusd")]);
+    deps.querier.with_twap("pair0000", twap(Decimal::one()));
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+            prism_token: Addr::unchecked("prism0000"),
+            treasury: Addr::unchecked("treasury0000"),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn market_msg(lp_token: &str, pair: &str, max_discount: Decimal, vesting_term: u64) -> ExecuteMsg {
+    ExecuteMsg::SetMarket {
+        lp_token: Addr::unchecked(lp_token),
+        pair: Addr::unchecked(pair),
+        max_discount,
+        vesting_term,
+        max_debt: Uint128::from(1000u128),
+        twap_window: 600,
+    }
+}
+
+fn bond_msg(sender: &str, amount: u128, min_payout: Option<u128>) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            min_payout: min_payout.map(Uint128::from),
+        })
+        .unwrap(),
+    })
+}
+
+fn query_bond(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, env: Env) -> BondResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Bond {
+                address: Addr::unchecked("addr0000"),
+                lp_token: Addr::unchecked("liquidity0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = setup();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            prism_token: Addr::unchecked("prism0000"),
+            treasury: Addr::unchecked("treasury0000"),
+            total_payout: Uint128::zero(),
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::percent(20), 1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::one(), 1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("max discount must be below 1"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::percent(20), 0),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("vesting term must be greater than 0")
+    );
+
+    let mut msg = market_msg("liquidity0000", "pair0000", Decimal::percent(20), 1000);
+    if let ExecuteMsg::SetMarket { twap_window, .. } = &mut msg {
+        *twap_window = 0;
+    }
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("twap window must be greater than 0")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0001", "pair0000", Decimal::percent(20), 1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid lp token"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0001", "pair0001", Decimal::percent(20), 1000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("pair must include the prism token")
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::percent(20), 1000),
+    )
+    .unwrap();
+
+    // an LP token worth 2 PRISM is bonded for 2 / 0.8 PRISM without outstanding debt
+    let market: MarketResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Market {
+                lp_token: Addr::unchecked("liquidity0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(market.discount, Decimal::percent(20));
+    assert_eq!(market.price, Decimal::from_ratio(5u128, 2u128));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RemoveMarket {
+            lp_token: Addr::unchecked("liquidity0000"),
+        },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0000", 40, None),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("market not found"));
+}
+
+#[test]
+fn bond() {
+    let mut deps = setup();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::percent(20), 1000),
+    )
+    .unwrap();
+
+    // only the LP token of the market can be bonded
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0001", &[]),
+        bond_msg("addr0000", 40, None),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("market not found"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0000", 40, Some(101)),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("payout below minimum"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0000", 40, Some(100)),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "liquidity0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury0000".to_string(),
+                amount: Uint128::from(40u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // the discount falls with the outstanding debt
+    let market: MarketResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Market {
+                lp_token: Addr::unchecked("liquidity0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(market.total_debt, Uint128::from(100u128));
+    assert_eq!(market.discount, Decimal::percent(18));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0001", 400, None),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("bond capacity exceeded"));
+
+    // the PRISM held must cover every payout
+    deps.querier
+        .with_token_balance("prism0000", MOCK_CONTRACT_ADDR, Uint128::from(120u128));
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0001", 10, None),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("insufficient prism balance"));
+
+    let market: MarketResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(500),
+            QueryMsg::Market {
+                lp_token: Addr::unchecked("liquidity0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(market.total_debt, Uint128::from(50u128));
+}
+
+#[test]
+fn claim() {
+    let mut deps = setup();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::percent(20), 1000),
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0000", 40, None),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("liquidity0000"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("nothing to claim"));
+
+    // half of the payout vests over half of the term
+    let res = execute(
+        deps.as_mut(),
+        env_at(500),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("liquidity0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(50u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let bond = query_bond(&deps, env_at(750));
+    assert_eq!(bond.payout, Uint128::from(50u128));
+    assert_eq!(bond.claimable, Uint128::from(25u128));
+
+    // bonding again pays out what vested, the rest vesting again with the new payout of
+    // 10 LP tokens at a 19.5% discount
+    let res = execute(
+        deps.as_mut(),
+        env_at(750),
+        mock_info("liquidity0000", &[]),
+        bond_msg("addr0000", 10, None),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(25u128),
+            })
+            .unwrap(),
+        }))
+    );
+
+    let bond = query_bond(&deps, env_at(750));
+    assert_eq!(bond.payout, Uint128::from(49u128));
+    assert_eq!(bond.vesting_end, env_at(1750).block.time.seconds());
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.total_payout, Uint128::from(49u128));
+}
+
+#[test]
+fn price_follows_twap() {
+    let mut deps = setup();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        market_msg("liquidity0000", "pair0000", Decimal::zero(), 1000),
+    )
+    .unwrap();
+
+    let query_price = |deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>| {
+        let market: MarketResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::Market {
+                    lp_token: Addr::unchecked("liquidity0000"),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        market.price
+    };
+    assert_eq!(query_price(&deps), Decimal::from_ratio(2u128, 1u128));
+
+    // a swap moving the spot reserves to 1250 PRISM and 800 uusd does not move the price
+    deps.querier
+        .with_token_balance("prism0000", "pair0000", Uint128::from(1250u128));
+    deps.querier.with_balance("pair0000", &[coin(800, "uusd")]);
+    assert_eq!(query_price(&deps), Decimal::from_ratio(2u128, 1u128));
+
+    // the TWAP does, an LP token being worth 2 * sqrt(1000000 * 1.21) / 1000 PRISM
+    deps.querier
+        .with_twap("pair0000", twap(Decimal::percent(121)));
+    assert_eq!(query_price(&deps), Decimal::from_ratio(22u128, 10u128));
+}
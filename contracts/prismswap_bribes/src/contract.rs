@@ -0,0 +1,374 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::U64Key;
+
+use crate::state::{Config, EpochBribes, BRIBES, CLAIMS, CONFIG};
+
+use cw20::Cw20ReceiveMsg;
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::bribes::{
+    Bribe, BribesResponse, ClaimableResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg,
+    InstantiateMsg, QueryMsg,
+};
+use prismswap::gauge::{EpochResponse, QueryMsg as GaugeQueryMsg, VoterWeightResponse};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        gauge: deps.api.addr_validate(msg.gauge.as_str())?,
+        claim_epochs: msg.claim_epochs,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            claim_epochs,
+        } => execute_update_config(deps, info, owner, claim_epochs),
+        ExecuteMsg::AddBribe {
+            lp_token,
+            epoch,
+            asset,
+        } => {
+            if !asset.info.is_native_token() {
+                return Err(StdError::generic_err(
+                    "cw20 bribes must be sent with the receive hook",
+                ));
+            }
+            asset.assert_sent_native_token_balance(&info)?;
+            add_bribe(deps, lp_token, epoch, asset)
+        }
+        ExecuteMsg::Claim { lp_token, epoch } => execute_claim(deps, info, lp_token, epoch),
+        ExecuteMsg::Rollover { lp_token, epoch } => execute_rollover(deps, env, lp_token, epoch),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::AddBribe { lp_token, epoch } => {
+            let asset = Asset::new(AssetInfo::Cw20(info.sender), cw20_msg.amount);
+            add_bribe(deps, lp_token, epoch, asset)
+        }
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    claim_epochs: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    if let Some(claim_epochs) = claim_epochs {
+        config.claim_epochs = claim_epochs;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn add_bribe(deps: DepsMut, lp_token: Addr, epoch: u64, asset: Asset) -> StdResult<Response> {
+    if asset.amount.is_zero() {
+        return Err(StdError::generic_err("bribe must be greater than zero"));
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    // the votes of a finished epoch are known already
+    if epoch < current_epoch(deps.as_ref(), &config)? {
+        return Err(StdError::generic_err(format!("epoch {} is over", epoch)));
+    }
+
+    let attributes = vec![
+        ("action", "add_bribe".to_string()),
+        ("lp_token", lp_token.to_string()),
+        ("epoch", epoch.to_string()),
+        ("asset", asset.to_string()),
+    ];
+    add_to_bribes(deps.storage, &lp_token, epoch, asset)?;
+
+    Ok(Response::new().add_attributes(attributes))
+}
+
+fn add_to_bribes(
+    storage: &mut dyn Storage,
+    lp_token: &Addr,
+    epoch: u64,
+    asset: Asset,
+) -> StdResult<()> {
+    BRIBES.update(storage, (U64Key::new(epoch), lp_token), |prev| {
+        let mut epoch_bribes: EpochBribes = prev.unwrap_or_default();
+        match epoch_bribes
+            .bribes
+            .iter_mut()
+            .find(|bribe| bribe.asset.info == asset.info)
+        {
+            Some(bribe) => bribe.asset.amount = bribe.asset.amount.checked_add(asset.amount)?,
+            None => epoch_bribes.bribes.push(Bribe {
+                asset,
+                claimed_amount: Uint128::zero(),
+            }),
+        }
+        Ok::<_, StdError>(epoch_bribes)
+    })?;
+
+    Ok(())
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: Addr,
+    epoch: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if epoch >= current_epoch(deps.as_ref(), &config)? {
+        return Err(StdError::generic_err(format!(
+            "epoch {} is not over",
+            epoch
+        )));
+    }
+
+    let claim_key = (U64Key::new(epoch), &lp_token, &info.sender);
+    if CLAIMS.has(deps.storage, claim_key.clone()) {
+        return Err(StdError::generic_err("bribes already claimed"));
+    }
+
+    let mut epoch_bribes = BRIBES
+        .may_load(deps.storage, (U64Key::new(epoch), &lp_token))?
+        .unwrap_or_default();
+    if epoch_bribes.rolled_over {
+        return Err(StdError::generic_err("bribes were rolled over"));
+    }
+
+    let shares = claimable_assets(
+        deps.as_ref(),
+        &config,
+        &epoch_bribes,
+        &info.sender,
+        &lp_token,
+        epoch,
+    )?;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (bribe, share) in epoch_bribes.bribes.iter_mut().zip(shares.iter()) {
+        if share.amount.is_zero() {
+            continue;
+        }
+        bribe.claimed_amount = bribe.claimed_amount.checked_add(share.amount)?;
+        messages.push(share.transfer_msg(&info.sender)?);
+    }
+    if messages.is_empty() {
+        return Err(StdError::generic_err("nothing to claim"));
+    }
+
+    CLAIMS.save(deps.storage, claim_key, &true)?;
+    BRIBES.save(deps.storage, (U64Key::new(epoch), &lp_token), &epoch_bribes)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "claim"),
+        ("voter", info.sender.as_str()),
+        ("lp_token", lp_token.as_str()),
+        ("epoch", &epoch.to_string()),
+    ]))
+}
+
+/// Move what is left of the bribes of a finished epoch to the current one, once the pool got no
+/// votes in the epoch or its claim period is over
+pub fn execute_rollover(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    epoch: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let current_epoch = current_epoch(deps.as_ref(), &config)?;
+    if epoch >= current_epoch {
+        return Err(StdError::generic_err(format!(
+            "epoch {} is not over",
+            epoch
+        )));
+    }
+
+    let mut epoch_bribes = BRIBES
+        .may_load(deps.storage, (U64Key::new(epoch), &lp_token))?
+        .unwrap_or_default();
+    if epoch_bribes.rolled_over {
+        return Err(StdError::generic_err("bribes were rolled over"));
+    }
+
+    // the contract never votes, only the weight of the pool is of use
+    let voter_weight = query_voter_weight(
+        deps.as_ref(),
+        &config,
+        &env.contract.address,
+        &lp_token,
+        epoch,
+    )?;
+    if !voter_weight.gauge_weight.is_zero() && epoch + config.claim_epochs >= current_epoch {
+        return Err(StdError::generic_err("bribes can still be claimed"));
+    }
+
+    for bribe in epoch_bribes.bribes.iter() {
+        let amount = bribe.asset.amount.checked_sub(bribe.claimed_amount)?;
+        if !amount.is_zero() {
+            let asset = Asset::new(bribe.asset.info.clone(), amount);
+            add_to_bribes(deps.storage, &lp_token, current_epoch, asset)?;
+        }
+    }
+    epoch_bribes.rolled_over = true;
+    BRIBES.save(deps.storage, (U64Key::new(epoch), &lp_token), &epoch_bribes)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "rollover"),
+        ("lp_token", lp_token.as_str()),
+        ("epoch", &epoch.to_string()),
+        ("to_epoch", &current_epoch.to_string()),
+    ]))
+}
+
+/// The share of every bribe of the epoch the voter can claim, in proportion to its votes for the
+/// pool
+fn claimable_assets(
+    deps: Deps,
+    config: &Config,
+    epoch_bribes: &EpochBribes,
+    voter: &Addr,
+    lp_token: &Addr,
+    epoch: u64,
+) -> StdResult<Vec<Asset>> {
+    let voter_weight = query_voter_weight(deps, config, voter, lp_token, epoch)?;
+
+    Ok(epoch_bribes
+        .bribes
+        .iter()
+        .map(|bribe| {
+            let amount = if voter_weight.gauge_weight.is_zero() {
+                Uint128::zero()
+            } else {
+                bribe
+                    .asset
+                    .amount
+                    .multiply_ratio(voter_weight.weight, voter_weight.gauge_weight)
+            };
+            Asset::new(bribe.asset.info.clone(), amount)
+        })
+        .collect())
+}
+
+fn current_epoch(deps: Deps, config: &Config) -> StdResult<u64> {
+    let epoch: EpochResponse = deps
+        .querier
+        .query_wasm_smart(&config.gauge, &GaugeQueryMsg::Epoch {})?;
+
+    Ok(epoch.epoch)
+}
+
+fn query_voter_weight(
+    deps: Deps,
+    config: &Config,
+    voter: &Addr,
+    lp_token: &Addr,
+    epoch: u64,
+) -> StdResult<VoterWeightResponse> {
+    deps.querier.query_wasm_smart(
+        &config.gauge,
+        &GaugeQueryMsg::VoterWeight {
+            voter: voter.clone(),
+            epoch,
+            lp_token: lp_token.clone(),
+        },
+    )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Bribes { lp_token, epoch } => to_binary(&query_bribes(deps, lp_token, epoch)?),
+        QueryMsg::Claimable {
+            voter,
+            lp_token,
+            epoch,
+        } => to_binary(&query_claimable(deps, voter, lp_token, epoch)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        gauge: config.gauge,
+        claim_epochs: config.claim_epochs,
+    })
+}
+
+pub fn query_bribes(deps: Deps, lp_token: Addr, epoch: u64) -> StdResult<BribesResponse> {
+    let epoch_bribes = BRIBES
+        .may_load(deps.storage, (U64Key::new(epoch), &lp_token))?
+        .unwrap_or_default();
+
+    Ok(BribesResponse {
+        bribes: epoch_bribes.bribes,
+        rolled_over: epoch_bribes.rolled_over,
+    })
+}
+
+pub fn query_claimable(
+    deps: Deps,
+    voter: Addr,
+    lp_token: Addr,
+    epoch: u64,
+) -> StdResult<ClaimableResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let epoch_bribes = BRIBES
+        .may_load(deps.storage, (U64Key::new(epoch), &lp_token))?
+        .unwrap_or_default();
+    if epoch_bribes.rolled_over
+        || epoch >= current_epoch(deps, &config)?
+        || CLAIMS.has(deps.storage, (U64Key::new(epoch), &lp_token, &voter))
+    {
+        return Ok(ClaimableResponse { assets: vec![] });
+    }
+
+    let assets = claimable_assets(deps, &config, &epoch_bribes, &voter, &lp_token, epoch)?
+        .into_iter()
+        .filter(|asset| !asset.amount.is_zero())
+        .collect();
+
+    Ok(ClaimableResponse { assets })
+}
@@ -0,0 +1,115 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, ContractResult, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use prismswap::gauge::{EpochResponse, QueryMsg as GaugeQueryMsg, VoterWeightResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    epoch: u64,
+    voter_weights: HashMap<(String, String, u64), Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => match from_binary(msg).unwrap() {
+                GaugeQueryMsg::Epoch {} => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&EpochResponse {
+                        epoch: self.epoch,
+                        start_time: 0,
+                        end_time: 0,
+                        total_weight: Uint128::zero(),
+                    })
+                    .unwrap(),
+                )),
+                GaugeQueryMsg::VoterWeight {
+                    voter,
+                    epoch,
+                    lp_token,
+                } => {
+                    let weight = self
+                        .voter_weights
+                        .get(&(voter.to_string(), lp_token.to_string(), epoch))
+                        .copied()
+                        .unwrap_or_default();
+                    let gauge_weight = self
+                        .voter_weights
+                        .iter()
+                        .filter(|((_, l, e), _)| l == lp_token.as_str() && *e == epoch)
+                        .map(|(_, weight)| *weight)
+                        .sum();
+
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&VoterWeightResponse {
+                            weight,
+                            gauge_weight,
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => panic!("DO NOT ENTER HERE"),
+            },
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            epoch: 0,
+            voter_weights: HashMap::new(),
+        }
+    }
+
+    pub fn with_epoch(&mut self, epoch: u64) {
+        self.epoch = epoch;
+    }
+
+    pub fn with_voter_weights(&mut self, weights: &[(&str, &str, u64, u128)]) {
+        self.voter_weights = weights
+            .iter()
+            .map(|(voter, lp_token, epoch, weight)| {
+                (
+                    (voter.to_string(), lp_token.to_string(), *epoch),
+                    Uint128::from(*weight),
+                )
+            })
+            .collect();
+    }
+}
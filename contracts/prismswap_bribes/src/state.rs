@@ -0,0 +1,28 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::bribes::Bribe;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub gauge: Addr,
+    pub claim_epochs: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct EpochBribes {
+    /// one entry per bribed asset
+    pub bribes: Vec<Bribe>,
+    pub rolled_over: bool,
+}
+
+/// the bribes for the votes of an epoch, keyed by epoch then LP token
+pub const BRIBES: Map<(U64Key, &Addr), EpochBribes> = Map::new("bribes");
+
+/// the voters who claimed the bribes for a pool in an epoch
+pub const CLAIMS: Map<(U64Key, &Addr, &Addr), bool> = Map::new("claims");
@@ -0,0 +1,361 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::bribes::{
+    Bribe, BribesResponse, ClaimableResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg,
+    InstantiateMsg, QueryMsg,
+};
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        gauge: Addr::unchecked("gauge0000"),
+        claim_epochs: 2,
+    }
+}
+
+fn add_cw20_bribe_msg(token: &str, amount: u128, epoch: u64) -> (ExecuteMsg, &str) {
+    (
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "briber0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::AddBribe {
+                lp_token: Addr::unchecked("lptoken0000"),
+                epoch,
+            })
+            .unwrap(),
+        }),
+        token,
+    )
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            gauge: Addr::unchecked("gauge0000"),
+            claim_epochs: 2,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        claim_epochs: Some(4),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.claim_epochs, 4);
+}
+
+#[test]
+fn add_bribe_and_claim() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_epoch(3);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // finished epochs can't be bribed
+    let (msg, token) = add_cw20_bribe_msg("token0000", 300, 2);
+    let err = execute(deps.as_mut(), mock_env(), mock_info(token, &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("epoch 2 is over"));
+
+    let (msg, token) = add_cw20_bribe_msg("token0000", 300, 3);
+    let _res = execute(deps.as_mut(), mock_env(), mock_info(token, &[]), msg).unwrap();
+    let (msg, token) = add_cw20_bribe_msg("token0000", 600, 3);
+    let _res = execute(deps.as_mut(), mock_env(), mock_info(token, &[]), msg).unwrap();
+
+    let native_bribe = ExecuteMsg::AddBribe {
+        lp_token: Addr::unchecked("lptoken0000"),
+        epoch: 3,
+        asset: Asset::new(AssetInfo::Native("uusd".to_string()), 90u128),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber0000", &[]),
+        native_bribe.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        )
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("briber0000", &[coin(90, "uusd")]),
+        native_bribe,
+    )
+    .unwrap();
+
+    let bribes: BribesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bribes {
+                lp_token: Addr::unchecked("lptoken0000"),
+                epoch: 3,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        bribes,
+        BribesResponse {
+            bribes: vec![
+                Bribe {
+                    asset: Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 900u128),
+                    claimed_amount: Uint128::zero(),
+                },
+                Bribe {
+                    asset: Asset::new(AssetInfo::Native("uusd".to_string()), 90u128),
+                    claimed_amount: Uint128::zero(),
+                },
+            ],
+            rolled_over: false,
+        }
+    );
+
+    let claim = ExecuteMsg::Claim {
+        lp_token: Addr::unchecked("lptoken0000"),
+        epoch: 3,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("voter0000", &[]),
+        claim.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("epoch 3 is not over"));
+
+    // voter0000 cast a third of the votes for the pool
+    deps.querier.with_epoch(4);
+    deps.querier.with_voter_weights(&[
+        ("voter0000", "lptoken0000", 3, 100),
+        ("voter0001", "lptoken0000", 3, 200),
+    ]);
+
+    let claimable: ClaimableResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Claimable {
+                voter: Addr::unchecked("voter0000"),
+                lp_token: Addr::unchecked("lptoken0000"),
+                epoch: 3,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        claimable.assets,
+        vec![
+            Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 300u128),
+            Asset::new(AssetInfo::Native("uusd".to_string()), 30u128),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("voter0000", &[]),
+        claim.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "voter0000".to_string(),
+                    amount: Uint128::from(300u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "voter0000".to_string(),
+                amount: vec![coin(30, "uusd")],
+            })),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("voter0000", &[]),
+        claim,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("bribes already claimed"));
+
+    let bribes: BribesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bribes {
+                lp_token: Addr::unchecked("lptoken0000"),
+                epoch: 3,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(bribes.bribes[0].claimed_amount, Uint128::from(300u128));
+    assert_eq!(bribes.bribes[1].claimed_amount, Uint128::from(30u128));
+}
+
+#[test]
+fn rollover() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_epoch(3);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let (msg, token) = add_cw20_bribe_msg("token0000", 900, 3);
+    let _res = execute(deps.as_mut(), mock_env(), mock_info(token, &[]), msg).unwrap();
+
+    deps.querier.with_epoch(4);
+    deps.querier.with_voter_weights(&[
+        ("voter0000", "lptoken0000", 3, 100),
+        ("voter0001", "lptoken0000", 3, 200),
+    ]);
+    let claim = ExecuteMsg::Claim {
+        lp_token: Addr::unchecked("lptoken0000"),
+        epoch: 3,
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("voter0000", &[]),
+        claim,
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Rollover {
+        lp_token: Addr::unchecked("lptoken0000"),
+        epoch: 3,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("bribes can still be claimed"));
+
+    // the claim period is over
+    deps.querier.with_epoch(6);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("bribes were rolled over"));
+
+    let claim = ExecuteMsg::Claim {
+        lp_token: Addr::unchecked("lptoken0000"),
+        epoch: 3,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("voter0001", &[]),
+        claim,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("bribes were rolled over"));
+
+    let bribes: BribesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bribes {
+                lp_token: Addr::unchecked("lptoken0000"),
+                epoch: 6,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        bribes.bribes,
+        vec![Bribe {
+            asset: Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 600u128),
+            claimed_amount: Uint128::zero(),
+        }]
+    );
+
+    // the bribes of a pool nobody voted for move on right away
+    let (msg, token) = add_cw20_bribe_msg("token0000", 500, 6);
+    let _res = execute(deps.as_mut(), mock_env(), mock_info(token, &[]), msg).unwrap();
+    deps.querier.with_epoch(7);
+    let msg = ExecuteMsg::Rollover {
+        lp_token: Addr::unchecked("lptoken0000"),
+        epoch: 6,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let bribes: BribesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Bribes {
+                lp_token: Addr::unchecked("lptoken0000"),
+                epoch: 7,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(bribes.bribes[0].asset.amount, Uint128::from(1100u128));
+}
@@ -0,0 +1,688 @@
+use std::str::FromStr;
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, Event, MessageInfo,
+    Response, StdError, StdResult, Uint128, WasmMsg,
+};
+
+use crate::state::{
+    is_withdraw_only, Config, DistributionInfo, CONFIG, DISTRIBUTIONS, GUARDIAN,
+    LAST_DISTRIBUTED_EPOCH, PARAM_REGISTRY, ROUTES, WITHDRAW_ONLY,
+};
+
+use cw20::Cw20ExecuteMsg;
+use cw_storage_plus::U64Key;
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::collector::{
+    CallbackMsg, ConfigResponse, DistributionConfig, DistributionResponse, ExecuteMsg,
+    FeeConfigResponse, InstantiateMsg, QueryMsg, RouteResponse, MAX_CALLER_INCENTIVE,
+};
+use prismswap::fee::FeeConfig;
+use prismswap::params::{MAX_CALLER_INCENTIVE_KEY, PAUSED_KEY};
+use prismswap::querier::{query_fee_info, query_pair_info, query_param};
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation, TwapConfig,
+};
+use prismswap::tax::TaxInfo;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_distribution(deps.as_ref(), &msg.distribution)?;
+    assert_caller_incentive(msg.caller_incentive)?;
+    assert_max_spread(msg.max_spread)?;
+    assert_twap_config(&msg.twap_config)?;
+    if msg.epoch_length == 0 {
+        return Err(StdError::generic_err(
+            "epoch length must be greater than zero",
+        ));
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        prism_token: deps.api.addr_validate(msg.prism_token.as_str())?,
+        distribution: msg.distribution,
+        caller_incentive: msg.caller_incentive,
+        max_spread: msg.max_spread,
+        twap_config: msg.twap_config,
+        epoch_length: msg.epoch_length,
+        start_time: env.block.time.seconds(),
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            router,
+            distribution,
+            caller_incentive,
+            max_spread,
+            twap_config,
+        } => execute_update_config(
+            deps,
+            info,
+            owner,
+            router,
+            distribution,
+            caller_incentive,
+            max_spread,
+            twap_config,
+        ),
+        ExecuteMsg::SetRoute {
+            asset_info,
+            operations,
+        } => execute_set_route(deps, info, asset_info, operations),
+        ExecuteMsg::UpdateGuardian { guardian } => execute_update_guardian(deps, info, guardian),
+        ExecuteMsg::SetWithdrawOnly { withdraw_only } => {
+            execute_set_withdraw_only(deps, info, withdraw_only)
+        }
+        ExecuteMsg::UpdateParamRegistry { param_registry } => {
+            execute_update_param_registry(deps, info, param_registry)
+        }
+        ExecuteMsg::Collect { assets } => execute_collect(deps, env, info, assets),
+        ExecuteMsg::Callback(msg) => {
+            if env.contract.address != info.sender {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match msg {
+                CallbackMsg::Distribute {
+                    caller,
+                    prev_balance,
+                } => execute_distribute(deps, env, caller, prev_balance),
+            }
+        }
+    }
+}
+
+// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    router: Option<Addr>,
+    distribution: Option<DistributionConfig>,
+    caller_incentive: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    twap_config: Option<TwapConfig>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    if let Some(router) = router {
+        deps.api.addr_validate(router.as_str())?;
+        config.router = router;
+    }
+
+    if let Some(distribution) = distribution {
+        assert_distribution(deps.as_ref(), &distribution)?;
+        config.distribution = distribution;
+    }
+
+    if let Some(caller_incentive) = caller_incentive {
+        assert_caller_incentive(caller_incentive)?;
+        config.caller_incentive = caller_incentive;
+    }
+
+    if let Some(max_spread) = max_spread {
+        assert_max_spread(max_spread)?;
+        config.max_spread = max_spread;
+    }
+
+    if let Some(twap_config) = twap_config {
+        assert_twap_config(&twap_config)?;
+        config.twap_config = twap_config;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_distribution(deps: Deps, distribution: &DistributionConfig) -> StdResult<()> {
+    if !distribution.is_valid() {
+        return Err(StdError::generic_err(
+            "burn, xPRISM and insurance ratios must not exceed 1",
+        ));
+    }
+    deps.api
+        .addr_validate(distribution.xprism_contract.as_str())?;
+    deps.api
+        .addr_validate(distribution.insurance_fund.as_str())?;
+    deps.api.addr_validate(distribution.treasury.as_str())?;
+
+    Ok(())
+}
+
+fn assert_caller_incentive(caller_incentive: Decimal) -> StdResult<()> {
+    if caller_incentive > Decimal::from_str(MAX_CALLER_INCENTIVE).unwrap() {
+        return Err(StdError::generic_err(format!(
+            "caller incentive must not exceed {}",
+            MAX_CALLER_INCENTIVE
+        )));
+    }
+
+    Ok(())
+}
+
+fn assert_max_spread(max_spread: Decimal) -> StdResult<()> {
+    if max_spread >= Decimal::one() {
+        return Err(StdError::generic_err("max spread must be lower than 1"));
+    }
+
+    Ok(())
+}
+
+fn assert_twap_config(twap_config: &TwapConfig) -> StdResult<()> {
+    if twap_config.window == 0 {
+        return Err(StdError::generic_err(
+            "twap window must be greater than zero",
+        ));
+    }
+
+    if twap_config.max_deviation >= Decimal::one() {
+        return Err(StdError::generic_err(
+            "twap max deviation must be lower than 1",
+        ));
+    }
+
+    Ok(())
+}
+
+// Only owner can execute it
+pub fn execute_update_guardian(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardian: Option<Addr>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match guardian {
+        Some(guardian) => {
+            deps.api.addr_validate(guardian.as_str())?;
+            GUARDIAN.save(deps.storage, &guardian)?;
+        }
+        None => GUARDIAN.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_guardian"))
+}
+
+// Only owner or guardian can execute it
+pub fn execute_set_withdraw_only(
+    deps: DepsMut,
+    info: MessageInfo,
+    withdraw_only: bool,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let guardian: Option<Addr> = GUARDIAN.may_load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner && Some(&info.sender) != guardian.as_ref() {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    WITHDRAW_ONLY.save(deps.storage, &withdraw_only)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_withdraw_only"),
+        ("withdraw_only", &withdraw_only.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_param_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    param_registry: Option<Addr>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match param_registry {
+        Some(param_registry) => {
+            let param_registry = deps.api.addr_validate(param_registry.as_str())?;
+            PARAM_REGISTRY.save(deps.storage, &param_registry)?;
+        }
+        None => PARAM_REGISTRY.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_param_registry"))
+}
+
+// Only owner can execute it
+pub fn execute_set_route(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    operations: Option<Vec<SwapOperation>>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match operations {
+        Some(operations) => {
+            assert_route(&config, &asset_info, &operations)?;
+            ROUTES.save(deps.storage, asset_info.as_bytes(), &operations)?;
+        }
+        None => ROUTES.remove(deps.storage, asset_info.as_bytes()),
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_route"),
+        ("asset_info", &asset_info.to_string()),
+    ]))
+}
+
+fn assert_route(
+    config: &Config,
+    asset_info: &AssetInfo,
+    operations: &[SwapOperation],
+) -> StdResult<()> {
+    if *asset_info == AssetInfo::Cw20(config.prism_token.clone()) {
+        return Err(StdError::generic_err("PRISM is distributed as it is"));
+    }
+
+    let first_operation = operations
+        .first()
+        .ok_or_else(|| StdError::generic_err("must provide operations"))?;
+    if first_operation.get_offer_asset_info() != *asset_info {
+        return Err(StdError::generic_err(
+            "invalid operations; first operation must offer the asset",
+        ));
+    }
+
+    for window in operations.windows(2) {
+        if window[0].get_target_asset_info() != window[1].get_offer_asset_info() {
+            return Err(StdError::generic_err(
+                "invalid operations; each operation must offer the previous ask asset",
+            ));
+        }
+    }
+
+    if operations.last().unwrap().get_target_asset_info()
+        != AssetInfo::Cw20(config.prism_token.clone())
+    {
+        return Err(StdError::generic_err(
+            "invalid operations; last operation must ask PRISM",
+        ));
+    }
+
+    Ok(())
+}
+
+/// The registered route of an asset, or else the direct pair with PRISM if there is one
+fn route(
+    deps: Deps,
+    config: &Config,
+    asset_info: &AssetInfo,
+) -> StdResult<(Vec<SwapOperation>, bool)> {
+    if let Some(operations) = ROUTES.may_load(deps.storage, asset_info.as_bytes())? {
+        return Ok((operations, false));
+    }
+
+    let prism_info = AssetInfo::Cw20(config.prism_token.clone());
+    query_pair_info(
+        &deps.querier,
+        &config.factory,
+        &[asset_info.clone(), prism_info.clone()],
+    )
+    .map_err(|_| StdError::generic_err(format!("no route for {}", asset_info)))?;
+
+    Ok((
+        vec![SwapOperation::PrismSwap {
+            offer_asset_info: asset_info.clone(),
+            ask_asset_info: prism_info,
+            pair: None,
+            max_spread: None,
+        }],
+        true,
+    ))
+}
+
+pub fn execute_collect(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<AssetInfo>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if is_withdraw_only(deps.storage)? {
+        return Err(StdError::generic_err("collector is in withdraw-only mode"));
+    }
+    if let Some(param_registry) = PARAM_REGISTRY.may_load(deps.storage)? {
+        if let Some(paused) = query_param(&deps.querier, &param_registry, PAUSED_KEY)? {
+            if paused.as_bool()? {
+                return Err(StdError::generic_err("collector is paused"));
+            }
+        }
+    }
+
+    let prism_info = AssetInfo::Cw20(config.prism_token.clone());
+    // the caller is only paid on the PRISM this collect converts
+    let prev_balance = prism_info.query_pool(&deps.querier, &env.contract.address)?;
+    let denoms: Vec<String> = assets
+        .iter()
+        .filter_map(|asset_info| match asset_info {
+            AssetInfo::Native(denom) => Some(denom.to_string()),
+            AssetInfo::Cw20(_) => None,
+        })
+        .collect();
+    let tax_info = TaxInfo::query(&deps.querier, &denoms)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for (i, asset_info) in assets.iter().enumerate() {
+        if assets[..i].contains(asset_info) {
+            return Err(StdError::generic_err(format!(
+                "duplicate asset {}",
+                asset_info
+            )));
+        }
+        // the PRISM collected is distributed as it is
+        if *asset_info == prism_info {
+            continue;
+        }
+
+        let mut amount = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+        // the tax of the native balance sent to the router is charged on top of it
+        if let AssetInfo::Native(denom) = asset_info {
+            amount = tax_info.deduct_tax(&Coin {
+                denom: denom.to_string(),
+                amount,
+            })?;
+        }
+        if !amount.is_zero() {
+            let (operations, _) = route(deps.as_ref(), &config, asset_info)?;
+            let asset = Asset::new(asset_info.clone(), amount);
+            messages.push(swap_msg(&config, &env, asset, operations)?);
+        }
+    }
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::Distribute {
+            caller: info.sender.clone(),
+            prev_balance,
+        }))?,
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "collect"),
+        ("caller", info.sender.as_str()),
+    ]))
+}
+
+/// Swap the asset to PRISM through the router, sending the output back to the contract
+fn swap_msg(
+    config: &Config,
+    env: &Env,
+    asset: Asset,
+    operations: Vec<SwapOperation>,
+) -> StdResult<CosmosMsg> {
+    let swap = ExecuteSwapOperationsMsg {
+        operations,
+        minimum_receive: None,
+        // a shallow pool moved right before the collect can't skim the fees
+        max_spread: Some(config.max_spread),
+        to: Some(env.contract.address.clone()),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        // the bound does not rely on the router having a TWAP check configured
+        twap_check: None,
+        twap_config: Some(config.twap_config.clone()),
+        staking_contract: None,
+    };
+
+    match &asset.info {
+        AssetInfo::Cw20(contract_addr) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: config.router.to_string(),
+                amount: asset.amount,
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap))?,
+            })?,
+        })),
+        AssetInfo::Native(denom) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.router.to_string(),
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: asset.amount,
+            }],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap))?,
+        })),
+    }
+}
+
+pub fn execute_distribute(
+    deps: DepsMut,
+    env: Env,
+    caller: Addr,
+    prev_balance: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let epoch = current_epoch(&config, &env);
+
+    let prism_info = AssetInfo::Cw20(config.prism_token.clone());
+    let amount = prism_info.query_pool(&deps.querier, &env.contract.address)?;
+    let converted_amount = amount.checked_sub(prev_balance)?;
+
+    // the registry bound caps the configured incentive
+    let mut caller_incentive = config.caller_incentive;
+    if let Some(param_registry) = PARAM_REGISTRY.may_load(deps.storage)? {
+        if let Some(max_caller_incentive) =
+            query_param(&deps.querier, &param_registry, MAX_CALLER_INCENTIVE_KEY)?
+        {
+            caller_incentive = caller_incentive.min(max_caller_incentive.as_decimal()?);
+        }
+    }
+
+    let caller_amount = converted_amount * caller_incentive;
+
+    // the PRISM accumulates until the next epoch, the caller being paid for the conversion
+    if LAST_DISTRIBUTED_EPOCH.may_load(deps.storage)? == Some(epoch) {
+        let mut messages: Vec<CosmosMsg> = vec![];
+        if !caller_amount.is_zero() {
+            messages.push(Asset::new(prism_info, caller_amount).transfer_msg(&caller)?);
+        }
+
+        return Ok(Response::new().add_messages(messages).add_attributes(vec![
+            ("action", "skip_distribution"),
+            ("epoch", &epoch.to_string()),
+            ("caller_amount", &caller_amount.to_string()),
+        ]));
+    }
+
+    let distributed_amount = amount.checked_sub(caller_amount)?;
+    let distribution = &config.distribution;
+    let burn_amount = distributed_amount * distribution.burn_ratio;
+    let xprism_amount = distributed_amount * distribution.xprism_ratio;
+    let insurance_amount = distributed_amount * distribution.insurance_ratio;
+    let treasury_amount =
+        distributed_amount.checked_sub(burn_amount + xprism_amount + insurance_amount)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !caller_amount.is_zero() {
+        messages.push(Asset::new(prism_info.clone(), caller_amount).transfer_msg(&caller)?);
+    }
+    if !burn_amount.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.prism_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: burn_amount,
+            })?,
+        }));
+    }
+    if !xprism_amount.is_zero() {
+        messages.push(
+            Asset::new(prism_info.clone(), xprism_amount)
+                .transfer_msg(&distribution.xprism_contract)?,
+        );
+    }
+    if !insurance_amount.is_zero() {
+        messages.push(
+            Asset::new(prism_info.clone(), insurance_amount)
+                .transfer_msg(&distribution.insurance_fund)?,
+        );
+    }
+    if !treasury_amount.is_zero() {
+        messages
+            .push(Asset::new(prism_info, treasury_amount).transfer_msg(&distribution.treasury)?);
+    }
+
+    LAST_DISTRIBUTED_EPOCH.save(deps.storage, &epoch)?;
+    DISTRIBUTIONS.save(
+        deps.storage,
+        U64Key::new(epoch),
+        &DistributionInfo {
+            time: env.block.time.seconds(),
+            caller: caller.clone(),
+            caller_amount,
+            burn_amount,
+            xprism_amount,
+            insurance_amount,
+            treasury_amount,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_event(
+            Event::new("distribution")
+                .add_attribute("epoch", epoch.to_string())
+                .add_attribute("time", env.block.time.seconds().to_string())
+                .add_attribute("caller", caller.as_str())
+                .add_attribute("caller_amount", caller_amount.to_string())
+                .add_attribute("burn_amount", burn_amount.to_string())
+                .add_attribute("xprism_amount", xprism_amount.to_string())
+                .add_attribute("insurance_amount", insurance_amount.to_string())
+                .add_attribute("treasury_amount", treasury_amount.to_string()),
+        )
+        .add_attributes(vec![
+            ("action", "distribute"),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn current_epoch(config: &Config, env: &Env) -> u64 {
+    (env.block.time.seconds() - config.start_time) / config.epoch_length
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Route { asset_info } => to_binary(&query_route(deps, asset_info)?),
+        QueryMsg::Distribution { epoch } => to_binary(&query_distribution(deps, epoch)?),
+        QueryMsg::FeeConfig { asset_infos } => {
+            to_binary(&query_fee_config(deps, env, asset_infos)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        router: config.router,
+        factory: config.factory,
+        prism_token: config.prism_token,
+        distribution: config.distribution,
+        caller_incentive: config.caller_incentive,
+        max_spread: config.max_spread,
+        twap_config: config.twap_config,
+        epoch_length: config.epoch_length,
+        start_time: config.start_time,
+        last_distributed_epoch: LAST_DISTRIBUTED_EPOCH.may_load(deps.storage)?,
+        guardian: GUARDIAN.may_load(deps.storage)?,
+        withdraw_only: is_withdraw_only(deps.storage)?,
+        param_registry: PARAM_REGISTRY.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_route(deps: Deps, asset_info: AssetInfo) -> StdResult<RouteResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (operations, is_default) = route(deps, &config, &asset_info)?;
+
+    Ok(RouteResponse {
+        asset_info,
+        operations,
+        is_default,
+    })
+}
+
+pub fn query_distribution(deps: Deps, epoch: u64) -> StdResult<DistributionResponse> {
+    let distribution = DISTRIBUTIONS
+        .may_load(deps.storage, U64Key::new(epoch))?
+        .ok_or_else(|| StdError::generic_err(format!("epoch {} has no distribution", epoch)))?;
+
+    Ok(DistributionResponse {
+        epoch,
+        time: distribution.time,
+        caller: distribution.caller,
+        caller_amount: distribution.caller_amount,
+        burn_amount: distribution.burn_amount,
+        xprism_amount: distribution.xprism_amount,
+        insurance_amount: distribution.insurance_amount,
+        treasury_amount: distribution.treasury_amount,
+    })
+}
+
+pub fn query_fee_config(
+    deps: Deps,
+    env: Env,
+    asset_infos: [AssetInfo; 2],
+) -> StdResult<FeeConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let fee_info = query_fee_info(&deps.querier, &config.factory, &asset_infos)?;
+
+    Ok(FeeConfigResponse {
+        is_collected: fee_info.collector == env.contract.address,
+        fee_config: FeeConfig {
+            collector: Some(fee_info.collector),
+            ..fee_info.fee_config
+        },
+    })
+}
@@ -0,0 +1,201 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use prismswap::asset::{AssetInfo, PairInfo};
+use prismswap::factory::{FeeConfig, FeeInfoResponse, QueryMsg as FactoryQueryMsg};
+use prismswap::params::{ParamResponse, ParamValue, QueryMsg as ParamsQueryMsg};
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    pairs: Vec<[AssetInfo; 2]>,
+    fee_config: FeeConfig,
+    params: HashMap<String, ParamValue>,
+    tax_rate: Decimal,
+    tax_caps: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match &request {
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data })
+                if route == &TerraRoute::Treasury =>
+            {
+                match query_data {
+                    TerraQuery::TaxRate {} => {
+                        let res = TaxRateResponse {
+                            rate: self.tax_rate,
+                        };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    TerraQuery::TaxCap { denom } => {
+                        let cap = self.tax_caps.get(denom).copied().unwrap_or_default();
+                        let res = TaxCapResponse { cap };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if matches!(from_binary(msg), Ok(ParamsQueryMsg::Param { .. })) =>
+            {
+                match from_binary(msg).unwrap() {
+                    ParamsQueryMsg::Param { key } => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&ParamResponse {
+                            value: self.params.get(&key).cloned(),
+                            key,
+                            bounds: None,
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<FactoryQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::Pair { asset_infos } => {
+                        let exists = self.pairs.iter().any(|pair| {
+                            pair[0] == asset_infos[0] && pair[1] == asset_infos[1]
+                                || pair[0] == asset_infos[1] && pair[1] == asset_infos[0]
+                        });
+                        if !exists {
+                            return SystemResult::Ok(ContractResult::Err(
+                                "No pair info exists".to_string(),
+                            ));
+                        }
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&PairInfo {
+                                asset_infos,
+                                contract_addr: Addr::unchecked("pair0000"),
+                                liquidity_token: Addr::unchecked("liquidity0000"),
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    FactoryQueryMsg::FeeInfo { .. } => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&FeeInfoResponse {
+                            collector: self
+                                .fee_config
+                                .collector_or(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+                            fee_config: self.fee_config.clone(),
+                            withdraw_only: false,
+                            param_registry: None,
+                            staking_contract: None,
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            pairs: vec![],
+            fee_config: FeeConfig::default(),
+            params: HashMap::new(),
+            tax_rate: Decimal::zero(),
+            tax_caps: HashMap::new(),
+        }
+    }
+
+    pub fn with_fee_config(&mut self, fee_config: FeeConfig) {
+        self.fee_config = fee_config;
+    }
+
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_balances = balances
+            .iter()
+            .map(|(contract_addr, balances)| {
+                (
+                    contract_addr.to_string(),
+                    balances
+                        .iter()
+                        .map(|(addr, balance)| (addr.to_string(), **balance))
+                        .collect(),
+                )
+            })
+            .collect();
+    }
+
+    pub fn with_pairs(&mut self, pairs: &[[AssetInfo; 2]]) {
+        self.pairs = pairs.to_vec();
+    }
+
+    pub fn with_params(&mut self, params: &[(&str, ParamValue)]) {
+        self.params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&str, Uint128)]) {
+        self.tax_rate = rate;
+        self.tax_caps = caps
+            .iter()
+            .map(|(denom, cap)| (denom.to_string(), *cap))
+            .collect();
+    }
+}
@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::collector::DistributionConfig;
+use prismswap::router::{SwapOperation, TwapConfig};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub router: Addr,
+    pub factory: Addr,
+    pub prism_token: Addr,
+    pub distribution: DistributionConfig,
+    pub caller_incentive: Decimal,
+    pub max_spread: Decimal,
+    pub twap_config: TwapConfig,
+    pub epoch_length: u64,
+    pub start_time: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// the last epoch the PRISM was distributed in
+pub const LAST_DISTRIBUTED_EPOCH: Item<u64> = Item::new("last_distributed_epoch");
+
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+/// whether the fee collection was halted by the owner or the guardian
+pub const WITHDRAW_ONLY: Item<bool> = Item::new("withdraw_only");
+
+/// the parameter registry read for the pause flag and the caller incentive bound when set
+pub const PARAM_REGISTRY: Item<Addr> = Item::new("param_registry");
+
+pub fn is_withdraw_only(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(WITHDRAW_ONLY.may_load(storage)?.unwrap_or(false))
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionInfo {
+    pub time: u64,
+    pub caller: Addr,
+    pub caller_amount: Uint128,
+    pub burn_amount: Uint128,
+    pub xprism_amount: Uint128,
+    pub insurance_amount: Uint128,
+    pub treasury_amount: Uint128,
+}
+
+/// the distribution of every epoch that had one
+pub const DISTRIBUTIONS: Map<U64Key, DistributionInfo> = Map::new("distributions");
+
+/// the registered routes to PRISM, keyed by offer asset
+pub const ROUTES: Map<&[u8], Vec<SwapOperation>> = Map::new("routes");
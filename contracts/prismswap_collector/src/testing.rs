@@ -0,0 +1,799 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Decimal, Deps, Event, StdError, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use prismswap::asset::AssetInfo;
+use prismswap::collector::{
+    CallbackMsg, ConfigResponse, DistributionConfig, DistributionResponse, ExecuteMsg,
+    FeeConfigResponse, InstantiateMsg, QueryMsg, RouteResponse,
+};
+use prismswap::fee::FeeConfig;
+use prismswap::params::{ParamValue, MAX_CALLER_INCENTIVE_KEY, PAUSED_KEY};
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation, TwapConfig,
+};
+
+const EPOCH_LENGTH: u64 = 86400;
+
+fn distribution(treasury: &str) -> DistributionConfig {
+    DistributionConfig {
+        burn_ratio: Decimal::percent(20),
+        xprism_ratio: Decimal::percent(50),
+        insurance_ratio: Decimal::percent(10),
+        xprism_contract: Addr::unchecked("xprism0000"),
+        insurance_fund: Addr::unchecked("insurance0000"),
+        treasury: Addr::unchecked(treasury),
+    }
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        router: Addr::unchecked("router0000"),
+        factory: Addr::unchecked("factory0000"),
+        prism_token: Addr::unchecked("prism0000"),
+        distribution: distribution("treasury0000"),
+        caller_incentive: Decimal::permille(5),
+        max_spread: Decimal::percent(1),
+        twap_config: twap_config(Decimal::percent(2)),
+        epoch_length: EPOCH_LENGTH,
+    }
+}
+
+fn twap_config(max_deviation: Decimal) -> TwapConfig {
+    TwapConfig {
+        window: 3600,
+        max_deviation,
+    }
+}
+
+fn swap(offer_asset_info: AssetInfo, ask_asset_info: AssetInfo) -> SwapOperation {
+    SwapOperation::PrismSwap {
+        offer_asset_info,
+        ask_asset_info,
+        pair: None,
+        max_spread: None,
+    }
+}
+
+fn swap_to_prism(operations: Vec<SwapOperation>) -> ExecuteSwapOperationsMsg {
+    ExecuteSwapOperationsMsg {
+        operations,
+        minimum_receive: None,
+        max_spread: Some(Decimal::percent(1)),
+        to: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: Some(twap_config(Decimal::percent(2))),
+        staking_contract: None,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        distribution: DistributionConfig {
+            burn_ratio: Decimal::percent(60),
+            ..distribution("treasury0000")
+        },
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("burn, xPRISM and insurance ratios must not exceed 1")
+    );
+
+    let msg = InstantiateMsg {
+        caller_incentive: Decimal::percent(2),
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("caller incentive must not exceed 0.01")
+    );
+
+    let msg = InstantiateMsg {
+        max_spread: Decimal::one(),
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("max spread must be lower than 1")
+    );
+
+    let msg = InstantiateMsg {
+        twap_config: TwapConfig {
+            window: 0,
+            max_deviation: Decimal::percent(2),
+        },
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("twap window must be greater than zero")
+    );
+
+    let msg = InstantiateMsg {
+        twap_config: twap_config(Decimal::one()),
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("twap max deviation must be lower than 1")
+    );
+
+    let msg = InstantiateMsg {
+        epoch_length: 0,
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("epoch length must be greater than zero")
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            router: Addr::unchecked("router0000"),
+            factory: Addr::unchecked("factory0000"),
+            prism_token: Addr::unchecked("prism0000"),
+            distribution: distribution("treasury0000"),
+            caller_incentive: Decimal::permille(5),
+            max_spread: Decimal::percent(1),
+            twap_config: twap_config(Decimal::percent(2)),
+            epoch_length: EPOCH_LENGTH,
+            start_time: mock_env().block.time.seconds(),
+            last_distributed_epoch: None,
+            guardian: None,
+            withdraw_only: false,
+            param_registry: None,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        router: Some(Addr::unchecked("router0001")),
+        distribution: Some(distribution("treasury0001")),
+        caller_incentive: Some(Decimal::permille(10)),
+        max_spread: Some(Decimal::percent(2)),
+        twap_config: Some(twap_config(Decimal::percent(5))),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0001"),
+            router: Addr::unchecked("router0001"),
+            factory: Addr::unchecked("factory0000"),
+            prism_token: Addr::unchecked("prism0000"),
+            distribution: distribution("treasury0001"),
+            caller_incentive: Decimal::permille(10),
+            max_spread: Decimal::percent(2),
+            twap_config: twap_config(Decimal::percent(5)),
+            epoch_length: EPOCH_LENGTH,
+            start_time: mock_env().block.time.seconds(),
+            last_distributed_epoch: None,
+            guardian: None,
+            withdraw_only: false,
+            param_registry: None,
+        }
+    );
+}
+
+#[test]
+fn collect() {
+    let mut deps = mock_dependencies(&[coin(1000, "uusd")]);
+    deps.querier.with_token_balances(&[
+        (
+            &"token0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(500u128))],
+        ),
+        (
+            &"prism0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(2000u128))],
+        ),
+    ]);
+
+    let uusd = AssetInfo::Native("uusd".to_string());
+    let token = AssetInfo::Cw20(Addr::unchecked("token0000"));
+    let prism = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+    deps.querier.with_pairs(&[
+        [uusd.clone(), token.clone()],
+        [token.clone(), prism.clone()],
+    ]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // uusd has no pair with PRISM
+    let msg = ExecuteMsg::Collect {
+        assets: vec![uusd.clone()],
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("caller0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("no route for native:uusd"));
+
+    let msg = ExecuteMsg::SetRoute {
+        asset_info: uusd.clone(),
+        operations: Some(vec![
+            swap(uusd.clone(), token.clone()),
+            swap(token.clone(), prism.clone()),
+        ]),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Collect {
+        assets: vec![
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("token0000")),
+            AssetInfo::Cw20(Addr::unchecked("token0001")),
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        ],
+    };
+    // the tax of the uusd sent to the router is charged on top of it
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(1000000u128))]);
+    // anyone can collect
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("caller0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "router0000".to_string(),
+                funds: vec![coin(990, "uusd")],
+                msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap_to_prism(
+                    vec![
+                        swap(uusd.clone(), token.clone()),
+                        swap(token.clone(), prism.clone())
+                    ]
+                )))
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "router0000".to_string(),
+                    amount: Uint128::from(500u128),
+                    msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap_to_prism(
+                        vec![swap(token.clone(), prism.clone())]
+                    )))
+                    .unwrap(),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::Distribute {
+                    caller: Addr::unchecked("caller0000"),
+                    prev_balance: Uint128::from(2000u128),
+                }))
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the tax is capped
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(3u128))]);
+    let res = execute(deps.as_mut(), mock_env(), mock_info("caller0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router0000".to_string(),
+            funds: vec![coin(997, "uusd")],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap_to_prism(
+                vec![
+                    swap(uusd.clone(), token.clone()),
+                    swap(token.clone(), prism.clone())
+                ]
+            )))
+            .unwrap(),
+        }))
+    );
+
+    // the caller is paid on the PRISM converted since the collect, the rest being distributed
+    let msg = ExecuteMsg::Callback(CallbackMsg::Distribute {
+        caller: Addr::unchecked("caller0000"),
+        prev_balance: Uint128::zero(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "caller0000".to_string(),
+                    amount: Uint128::from(10u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::from(398u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "xprism0000".to_string(),
+                    amount: Uint128::from(995u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "insurance0000".to_string(),
+                    amount: Uint128::from(199u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "treasury0000".to_string(),
+                    amount: Uint128::from(398u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+    assert_eq!(
+        res.events,
+        vec![Event::new("distribution")
+            .add_attribute("epoch", "0")
+            .add_attribute("time", mock_env().block.time.seconds().to_string())
+            .add_attribute("caller", "caller0000")
+            .add_attribute("caller_amount", "10")
+            .add_attribute("burn_amount", "398")
+            .add_attribute("xprism_amount", "995")
+            .add_attribute("insurance_amount", "199")
+            .add_attribute("treasury_amount", "398")]
+    );
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.last_distributed_epoch, Some(0));
+
+    let distribution: DistributionResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Distribution { epoch: 0 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        distribution,
+        DistributionResponse {
+            epoch: 0,
+            time: mock_env().block.time.seconds(),
+            caller: Addr::unchecked("caller0000"),
+            caller_amount: Uint128::from(10u128),
+            burn_amount: Uint128::from(398u128),
+            xprism_amount: Uint128::from(995u128),
+            insurance_amount: Uint128::from(199u128),
+            treasury_amount: Uint128::from(398u128),
+        }
+    );
+
+    // the PRISM converted later in the epoch waits for the next one, its caller being paid
+    let msg = ExecuteMsg::Callback(CallbackMsg::Distribute {
+        caller: Addr::unchecked("caller0001"),
+        prev_balance: Uint128::from(1000u128),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "caller0001".to_string(),
+                amount: Uint128::from(5u128),
+            })
+            .unwrap(),
+        }))]
+    );
+    assert!(res.events.is_empty());
+
+    // a collect converting nothing pays nothing
+    let msg = ExecuteMsg::Callback(CallbackMsg::Distribute {
+        caller: Addr::unchecked("caller0001"),
+        prev_balance: Uint128::from(2000u128),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert!(res.messages.is_empty());
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(EPOCH_LENGTH);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 4);
+
+    let distribution: DistributionResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::Distribution { epoch: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(distribution.caller, Addr::unchecked("caller0001"));
+}
+
+#[test]
+fn set_route() {
+    let mut deps = mock_dependencies(&[]);
+    let uusd = AssetInfo::Native("uusd".to_string());
+    let token = AssetInfo::Cw20(Addr::unchecked("token0000"));
+    let prism = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+    deps.querier.with_pairs(&[[uusd.clone(), prism.clone()]]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let route: RouteResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Route {
+                asset_info: uusd.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        route,
+        RouteResponse {
+            asset_info: uusd.clone(),
+            operations: vec![swap(uusd.clone(), prism.clone())],
+            is_default: true,
+        }
+    );
+
+    let set_route = |operations: Vec<SwapOperation>| ExecuteMsg::SetRoute {
+        asset_info: uusd.clone(),
+        operations: Some(operations),
+    };
+    let msg = set_route(vec![swap(uusd.clone(), token.clone())]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("invalid operations; last operation must ask PRISM")
+    );
+
+    let msg = set_route(vec![swap(token.clone(), prism.clone())]);
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("invalid operations; first operation must offer the asset")
+    );
+
+    let msg = set_route(vec![
+        swap(uusd.clone(), prism.clone()),
+        swap(token.clone(), prism.clone()),
+    ]);
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "invalid operations; each operation must offer the previous ask asset"
+        )
+    );
+
+    let operations = vec![
+        swap(uusd.clone(), token.clone()),
+        swap(token.clone(), prism.clone()),
+    ];
+    let msg = set_route(operations.clone());
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let route: RouteResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Route {
+                asset_info: uusd.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(route.operations, operations);
+    assert!(!route.is_default);
+
+    // removing the route falls back to the direct pair
+    let msg = ExecuteMsg::SetRoute {
+        asset_info: uusd.clone(),
+        operations: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let route: RouteResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Route { asset_info: uusd },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(route.is_default);
+}
+
+#[test]
+fn withdraw_only() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateGuardian {
+        guardian: Some(Addr::unchecked("guardian0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetWithdrawOnly {
+        withdraw_only: true,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.guardian, Some(Addr::unchecked("guardian0000")));
+    assert!(config.withdraw_only);
+
+    let msg = ExecuteMsg::Collect {
+        assets: vec![AssetInfo::Native("uusd".to_string())],
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("caller0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("collector is in withdraw-only mode")
+    );
+
+    let msg = ExecuteMsg::SetWithdrawOnly {
+        withdraw_only: false,
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg,
+    )
+    .unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert!(!config.withdraw_only);
+}
+
+#[test]
+fn param_registry() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[(
+        &"prism0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(2000u128))],
+    )]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateParamRegistry {
+        param_registry: Some(Addr::unchecked("params0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    deps.querier.with_params(&[
+        (PAUSED_KEY, ParamValue::Bool(true)),
+        (
+            MAX_CALLER_INCENTIVE_KEY,
+            ParamValue::Decimal(Decimal::permille(1)),
+        ),
+    ]);
+    let msg = ExecuteMsg::Collect {
+        assets: vec![AssetInfo::Cw20(Addr::unchecked("prism0000"))],
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("caller0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("collector is paused"));
+
+    // the registry bound caps the 0.5% caller incentive to 0.1%
+    let msg = ExecuteMsg::Callback(CallbackMsg::Distribute {
+        caller: Addr::unchecked("caller0000"),
+        prev_balance: Uint128::zero(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "caller0000".to_string(),
+                amount: Uint128::from(2u128),
+            })
+            .unwrap(),
+        }))
+    );
+}
+
+#[test]
+fn fee_config() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let asset_infos = [
+        AssetInfo::Native("uusd".to_string()),
+        AssetInfo::Cw20(Addr::unchecked("prism0000")),
+    ];
+    let fee_config_query = |deps: Deps| -> FeeConfigResponse {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::FeeConfig {
+                    asset_infos: asset_infos.clone(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // the factory collector is this contract
+    assert_eq!(
+        fee_config_query(deps.as_ref()),
+        FeeConfigResponse {
+            fee_config: FeeConfig {
+                collector: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+                ..FeeConfig::default()
+            },
+            is_collected: true,
+        }
+    );
+
+    // a pair sending its protocol share to another collector
+    let fee_config = FeeConfig {
+        collector: Some(Addr::unchecked("collector0001")),
+        ..FeeConfig::from_bps(30, 5000)
+    };
+    deps.querier.with_fee_config(fee_config.clone());
+    assert_eq!(
+        fee_config_query(deps.as_ref()),
+        FeeConfigResponse {
+            fee_config,
+            is_collected: false,
+        }
+    );
+}
@@ -0,0 +1,410 @@
+use crate::state::{read_schedules, Config, Schedule, CONFIG, NEXT_SCHEDULE_ID, SCHEDULES};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use std::str::FromStr;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::dca::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ScheduleResponse,
+    SchedulesResponse, MAX_KEEPER_FEE,
+};
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation, TwapConfig,
+};
+use prismswap::tax::TaxInfo;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_keeper_fee(msg.keeper_fee)?;
+    assert_twap_window(msg.twap_window)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+        keeper_fee: msg.keeper_fee,
+        twap_window: msg.twap_window,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_SCHEDULE_ID.save(deps.storage, &1u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateSchedule {
+            budget,
+            amount_per_interval,
+            interval,
+            operations,
+            max_spread,
+        } => {
+            if !budget.info.is_native_token() {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            budget.assert_sent_native_token_balance(&info)?;
+
+            execute_create_schedule(
+                deps,
+                env,
+                info.sender,
+                budget,
+                amount_per_interval,
+                interval,
+                operations,
+                max_spread,
+            )
+        }
+        ExecuteMsg::CancelSchedule { schedule_id } => {
+            execute_cancel_schedule(deps, info, schedule_id)
+        }
+        ExecuteMsg::ExecuteSchedule { schedule_id } => {
+            execute_execute_schedule(deps, env, info, schedule_id)
+        }
+        ExecuteMsg::UpdateConfig {
+            owner,
+            keeper_fee,
+            twap_window,
+        } => execute_update_config(deps, info, owner, keeper_fee, twap_window),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateSchedule {
+            amount_per_interval,
+            interval,
+            operations,
+            max_spread,
+        } => {
+            let owner = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_create_schedule(
+                deps,
+                env,
+                owner,
+                Asset::new(AssetInfo::Cw20(info.sender), cw20_msg.amount),
+                amount_per_interval,
+                interval,
+                operations,
+                max_spread,
+            )
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_schedule(
+    deps: DepsMut,
+    env: Env,
+    owner: Addr,
+    budget: Asset,
+    amount_per_interval: Uint128,
+    interval: u64,
+    operations: Vec<SwapOperation>,
+    max_spread: Decimal,
+) -> StdResult<Response> {
+    if amount_per_interval.is_zero() || budget.amount < amount_per_interval {
+        return Err(StdError::generic_err(
+            "amount per interval must be greater than 0 and not exceed the budget",
+        ));
+    }
+
+    if interval == 0 {
+        return Err(StdError::generic_err("interval must be greater than 0"));
+    }
+
+    if max_spread >= Decimal::one() {
+        return Err(StdError::generic_err("max spread must be lower than 1"));
+    }
+
+    match operations.first() {
+        Some(operation) if operation.get_offer_asset_info() == budget.info => {}
+        _ => {
+            return Err(StdError::generic_err(
+                "invalid operations; first operation must offer the budget asset",
+            ))
+        }
+    }
+
+    let schedule_id = NEXT_SCHEDULE_ID.load(deps.storage)?;
+    NEXT_SCHEDULE_ID.save(deps.storage, &(schedule_id + 1))?;
+    SCHEDULES.save(
+        deps.storage,
+        U64Key::new(schedule_id),
+        &Schedule {
+            owner: owner.clone(),
+            budget: budget.clone(),
+            amount_per_interval,
+            interval,
+            operations,
+            max_spread,
+            // the first installment can be executed at once
+            next_execution_time: env.block.time.seconds(),
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "create_schedule"),
+        ("schedule_id", &schedule_id.to_string()),
+        ("owner", owner.as_str()),
+        ("budget", &budget.to_string_legacy()),
+    ]))
+}
+
+// Only schedule owner can execute it
+pub fn execute_cancel_schedule(
+    deps: DepsMut,
+    info: MessageInfo,
+    schedule_id: u64,
+) -> StdResult<Response> {
+    let schedule = load_schedule(deps.as_ref(), schedule_id)?;
+
+    // permission check
+    if info.sender != schedule.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    SCHEDULES.remove(deps.storage, U64Key::new(schedule_id));
+
+    // the tax of a native refund is charged on top of it against the budget left
+    Ok(Response::new()
+        .add_messages(
+            schedule
+                .budget
+                .into_transfer_msg_with_tax(&deps.querier, &schedule.owner)?,
+        )
+        .add_attributes(vec![
+            ("action", "cancel_schedule"),
+            ("schedule_id", &schedule_id.to_string()),
+        ]))
+}
+
+pub fn execute_execute_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    schedule_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut schedule = load_schedule(deps.as_ref(), schedule_id)?;
+
+    if env.block.time.seconds() < schedule.next_execution_time {
+        return Err(StdError::generic_err("installment not due"));
+    }
+
+    // the last installment takes the budget left
+    let amount = schedule.amount_per_interval.min(schedule.budget.amount);
+    let keeper_fee_amount = amount * config.keeper_fee;
+    let mut swap_amount = amount - keeper_fee_amount;
+
+    schedule.budget.amount -= amount;
+    schedule.next_execution_time = env.block.time.seconds() + schedule.interval;
+    if schedule.budget.amount.is_zero() {
+        SCHEDULES.remove(deps.storage, U64Key::new(schedule_id));
+    } else {
+        SCHEDULES.save(deps.storage, U64Key::new(schedule_id), &schedule)?;
+    }
+
+    // the tax of the native swap and keeper fee transfers is charged on top of them
+    if let AssetInfo::Native(denom) = &schedule.budget.info {
+        swap_amount = TaxInfo::query(&deps.querier, &[denom.to_string()])?.deduct_tax(&Coin {
+            denom: denom.to_string(),
+            amount: swap_amount,
+        })?;
+    }
+    let mut messages = vec![swap_msg(
+        &config,
+        &schedule,
+        Asset::new(schedule.budget.info.clone(), swap_amount),
+    )?];
+    messages.extend(
+        Asset::new(schedule.budget.info.clone(), keeper_fee_amount)
+            .into_transfer_msg_with_tax(&deps.querier, &info.sender)?,
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "execute_schedule"),
+        ("schedule_id", &schedule_id.to_string()),
+        ("keeper", info.sender.as_str()),
+        ("swap_amount", &swap_amount.to_string()),
+        ("keeper_fee_amount", &keeper_fee_amount.to_string()),
+        ("budget_left", &schedule.budget.amount.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    keeper_fee: Option<Decimal>,
+    twap_window: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(keeper_fee) = keeper_fee {
+        assert_keeper_fee(keeper_fee)?;
+        config.keeper_fee = keeper_fee;
+    }
+
+    if let Some(twap_window) = twap_window {
+        assert_twap_window(twap_window)?;
+        config.twap_window = twap_window;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_keeper_fee(keeper_fee: Decimal) -> StdResult<()> {
+    if keeper_fee > Decimal::from_str(MAX_KEEPER_FEE).unwrap() {
+        return Err(StdError::generic_err(format!(
+            "keeper fee must not exceed {}",
+            MAX_KEEPER_FEE
+        )));
+    }
+
+    Ok(())
+}
+
+fn assert_twap_window(twap_window: u64) -> StdResult<()> {
+    if twap_window == 0 {
+        return Err(StdError::generic_err("twap window must be greater than 0"));
+    }
+
+    Ok(())
+}
+
+fn load_schedule(deps: Deps, schedule_id: u64) -> StdResult<Schedule> {
+    SCHEDULES
+        .may_load(deps.storage, U64Key::new(schedule_id))?
+        .ok_or_else(|| StdError::generic_err("schedule not found"))
+}
+
+/// Swap the installment through the router, the output going to the schedule owner
+fn swap_msg(config: &Config, schedule: &Schedule, offer_asset: Asset) -> StdResult<CosmosMsg> {
+    let swap = ExecuteSwapOperationsMsg {
+        operations: schedule.operations.clone(),
+        minimum_receive: None,
+        max_spread: Some(schedule.max_spread),
+        to: Some(schedule.owner.clone()),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        // the output is bounded by the pair TWAP the installment is swapped at
+        twap_check: None,
+        twap_config: Some(TwapConfig {
+            window: config.twap_window,
+            max_deviation: schedule.max_spread,
+        }),
+        staking_contract: None,
+    };
+
+    Ok(match &offer_asset.info {
+        AssetInfo::Native(denom) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.router.to_string(),
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: offer_asset.amount,
+            }],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap))?,
+        }),
+        AssetInfo::Cw20(contract_addr) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: config.router.to_string(),
+                amount: offer_asset.amount,
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap))?,
+            })?,
+        }),
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Schedule { schedule_id } => to_binary(&query_schedule(deps, schedule_id)?),
+        QueryMsg::Schedules {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_schedules(deps, owner, start_after, limit)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        router: config.router,
+        keeper_fee: config.keeper_fee,
+        twap_window: config.twap_window,
+    })
+}
+
+pub fn query_schedule(deps: Deps, schedule_id: u64) -> StdResult<ScheduleResponse> {
+    let schedule = load_schedule(deps, schedule_id)?;
+
+    Ok(schedule_response(schedule_id, schedule))
+}
+
+pub fn query_schedules(
+    deps: Deps,
+    owner: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<SchedulesResponse> {
+    let schedules = read_schedules(deps.storage, owner, start_after, limit)?
+        .into_iter()
+        .map(|(schedule_id, schedule)| schedule_response(schedule_id, schedule))
+        .collect();
+
+    Ok(SchedulesResponse { schedules })
+}
+
+fn schedule_response(schedule_id: u64, schedule: Schedule) -> ScheduleResponse {
+    ScheduleResponse {
+        schedule_id,
+        owner: schedule.owner,
+        budget: schedule.budget,
+        amount_per_interval: schedule.amount_per_interval,
+        interval: schedule.interval,
+        operations: schedule.operations,
+        max_spread: schedule.max_spread,
+        next_execution_time: schedule.next_execution_time,
+    }
+}
@@ -0,0 +1,59 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::asset::Asset;
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+use prismswap::router::SwapOperation;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub router: Addr,
+    pub keeper_fee: Decimal,
+    pub twap_window: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Schedule {
+    pub owner: Addr,
+    /// the budget left, lowered by every installment
+    pub budget: Asset,
+    pub amount_per_interval: Uint128,
+    pub interval: u64,
+    pub operations: Vec<SwapOperation>,
+    pub max_spread: Decimal,
+    pub next_execution_time: u64,
+}
+
+pub const SCHEDULES: Map<U64Key, Schedule> = Map::new("schedules");
+
+/// the id of the next schedule created
+pub const NEXT_SCHEDULE_ID: Item<u64> = Item::new("next_schedule_id");
+
+pub fn read_schedules(
+    storage: &dyn Storage,
+    owner: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Schedule)>> {
+    let start = start_after_id_bound(start_after);
+
+    SCHEDULES
+        .range(storage, start, None, Order::Ascending)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .filter(|item| match (&owner, item) {
+            (Some(owner), Ok((_, schedule))) => schedule.owner == *owner,
+            _ => true,
+        })
+        .take(calc_limit(limit))
+        .collect()
+}
@@ -0,0 +1,428 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    attr, coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, StdError, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::dca::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ScheduleResponse,
+    SchedulesResponse,
+};
+use prismswap::router::{
+    ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg, SwapOperation, TwapConfig,
+};
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        router: Addr::unchecked("router0000"),
+        keeper_fee: Decimal::percent(1),
+        twap_window: 3600,
+    }
+}
+
+fn uusd_to_prism() -> Vec<SwapOperation> {
+    vec![SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        pair: None,
+        max_spread: None,
+    }]
+}
+
+fn create_msg(budget: u128, amount_per_interval: u128) -> ExecuteMsg {
+    ExecuteMsg::CreateSchedule {
+        budget: Asset::new(AssetInfo::Native("uusd".to_string()), budget),
+        amount_per_interval: Uint128::from(amount_per_interval),
+        interval: 86400,
+        operations: uusd_to_prism(),
+        max_spread: Decimal::percent(1),
+    }
+}
+
+fn swap_msg(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "router0000".to_string(),
+        funds: vec![coin(amount, "uusd")],
+        msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(
+            ExecuteSwapOperationsMsg {
+                operations: uusd_to_prism(),
+                minimum_receive: None,
+                max_spread: Some(Decimal::percent(1)),
+                to: Some(Addr::unchecked("addr0000")),
+                deadline: None,
+                referral: None,
+                swap_all: None,
+                twap_check: None,
+                twap_config: Some(TwapConfig {
+                    window: 3600,
+                    max_deviation: Decimal::percent(1),
+                }),
+                staking_contract: None,
+            },
+        ))
+        .unwrap(),
+    }))
+}
+
+fn keeper_fee_msg(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: "keeper0000".to_string(),
+        amount: vec![coin(amount, "uusd")],
+    }))
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.keeper_fee = Decimal::percent(6);
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("keeper fee must not exceed 0.05")
+    );
+
+    let mut msg = instantiate_msg();
+    msg.twap_window = 0;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("twap window must be greater than 0")
+    );
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            router: Addr::unchecked("router0000"),
+            keeper_fee: Decimal::percent(1),
+            twap_window: 3600,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        keeper_fee: Some(Decimal::percent(2)),
+        twap_window: Some(7200),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.keeper_fee, Decimal::percent(2));
+    assert_eq!(config.twap_window, 7200);
+}
+
+#[test]
+fn create_and_cancel_schedule() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        create_msg(1000, 2000),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "amount per interval must be greater than 0 and not exceed the budget"
+        )
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        ExecuteMsg::CreateSchedule {
+            budget: Asset::new(AssetInfo::Native("uusd".to_string()), 1000u128),
+            amount_per_interval: Uint128::from(300u128),
+            interval: 86400,
+            operations: vec![SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("uluna".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                pair: None,
+                max_spread: None,
+            }],
+            max_spread: Decimal::percent(1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("invalid operations; first operation must offer the budget asset")
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        create_msg(1000, 300),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("schedule_id", "1"));
+
+    // CW20 budgets are sent through their hook
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0001".to_string(),
+            amount: Uint128::from(500u128),
+            msg: to_binary(&Cw20HookMsg::CreateSchedule {
+                amount_per_interval: Uint128::from(100u128),
+                interval: 3600,
+                operations: vec![SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                    ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                    pair: None,
+                    max_spread: None,
+                }],
+                max_spread: Decimal::percent(1),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("schedule_id", "2"));
+
+    let schedules: SchedulesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Schedules {
+                owner: Some(Addr::unchecked("addr0000")),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        schedules.schedules,
+        vec![ScheduleResponse {
+            schedule_id: 1,
+            owner: Addr::unchecked("addr0000"),
+            budget: Asset::new(AssetInfo::Native("uusd".to_string()), 1000u128),
+            amount_per_interval: Uint128::from(300u128),
+            interval: 86400,
+            operations: uusd_to_prism(),
+            max_spread: Decimal::percent(1),
+            next_execution_time: mock_env().block.time.seconds(),
+        }]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelSchedule { schedule_id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelSchedule { schedule_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::from(500u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Schedule { schedule_id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("schedule not found"));
+}
+
+#[test]
+fn execute_schedule() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        create_msg(1000, 300),
+    )
+    .unwrap();
+
+    // the first installment is due at once, the keeper taking 1% of it
+    let mut env = mock_env();
+    let execute_msg = ExecuteMsg::ExecuteSchedule { schedule_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper0000", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![swap_msg(297), keeper_fee_msg(3)]);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper0000", &[]),
+        execute_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("installment not due"));
+
+    for _ in 0..2 {
+        env.block.time = env.block.time.plus_seconds(86400);
+        let _res = execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("keeper0000", &[]),
+            execute_msg.clone(),
+        )
+        .unwrap();
+    }
+
+    let schedule: ScheduleResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Schedule { schedule_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(schedule.budget.amount, Uint128::from(100u128));
+    assert_eq!(
+        schedule.next_execution_time,
+        env.block.time.seconds() + 86400
+    );
+
+    // the last installment takes the budget left, closing the schedule
+    env.block.time = env.block.time.plus_seconds(86400);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper0000", &[]),
+        execute_msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![swap_msg(99), keeper_fee_msg(1)]);
+
+    let err = query(deps.as_ref(), env, QueryMsg::Schedule { schedule_id: 1 }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("schedule not found"));
+}
+
+#[test]
+fn execute_schedule_with_tax() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(1000000u128))]);
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(2000, "uusd")]),
+        create_msg(2000, 1000),
+    )
+    .unwrap();
+
+    // the tax of the swap and of the keeper fee is charged against the installment,
+    // 980 + 10 and 9 + 1
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteSchedule { schedule_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![swap_msg(980), keeper_fee_msg(9)]);
+
+    // and so is the tax of the refund, 990 + 10
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelSchedule { schedule_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(990, "uusd")],
+        }))]
+    );
+}
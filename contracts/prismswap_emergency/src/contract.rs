@@ -0,0 +1,231 @@
+use crate::state::{is_shutdown, Config, CONFIG, RESTORE_TIME, SHUTDOWN};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, WasmMsg,
+};
+
+use prismswap::collector::ExecuteMsg as CollectorExecuteMsg;
+use prismswap::emergency::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::router::ExecuteMsg as RouterExecuteMsg;
+use prismswap::staking::ExecuteMsg as StakingExecuteMsg;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        guardians: validate_addresses(deps.as_ref(), msg.guardians)?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+        staking: deps.api.addr_validate(msg.staking.as_str())?,
+        collector: deps.api.addr_validate(msg.collector.as_str())?,
+        restore_delay: msg.restore_delay,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    SHUTDOWN.save(deps.storage, &false)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            guardians,
+            restore_delay,
+        } => execute_update_config(deps, info, owner, guardians, restore_delay),
+        ExecuteMsg::Shutdown {} => execute_shutdown(deps, info),
+        ExecuteMsg::QueueRestore {} => execute_queue_restore(deps, env, info),
+        ExecuteMsg::CancelRestore {} => execute_cancel_restore(deps, info),
+        ExecuteMsg::ExecuteRestore {} => execute_restore(deps, env),
+    }
+}
+
+fn validate_addresses(deps: Deps, addresses: Vec<Addr>) -> StdResult<Vec<Addr>> {
+    addresses
+        .iter()
+        .map(|address| deps.api.addr_validate(address.as_str()))
+        .collect()
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    guardians: Option<Vec<Addr>>,
+    restore_delay: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(guardians) = guardians {
+        config.guardians = validate_addresses(deps.as_ref(), guardians)?;
+    }
+
+    if let Some(restore_delay) = restore_delay {
+        config.restore_delay = restore_delay;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner or guardians can execute it
+pub fn execute_shutdown(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if !config.can_shutdown(&info.sender) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    SHUTDOWN.save(deps.storage, &true)?;
+    RESTORE_TIME.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_messages(switch_messages(&config, true)?)
+        .add_attributes(vec![
+            ("action", "shutdown"),
+            ("sender", info.sender.as_str()),
+        ]))
+}
+
+// Only owner can execute it
+pub fn execute_queue_restore(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if !is_shutdown(deps.storage)? {
+        return Err(StdError::generic_err("protocol is not shut down"));
+    }
+
+    let restore_time = env.block.time.seconds() + config.restore_delay;
+    RESTORE_TIME.save(deps.storage, &restore_time)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "queue_restore"),
+        ("restore_time", &restore_time.to_string()),
+    ]))
+}
+
+// Only owner or guardians can execute it
+pub fn execute_cancel_restore(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if !config.can_shutdown(&info.sender) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if RESTORE_TIME.may_load(deps.storage)?.is_none() {
+        return Err(StdError::generic_err("no restore queued"));
+    }
+
+    RESTORE_TIME.remove(deps.storage);
+
+    Ok(Response::new().add_attribute("action", "cancel_restore"))
+}
+
+pub fn execute_restore(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let restore_time = RESTORE_TIME
+        .may_load(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("no restore queued"))?;
+    if env.block.time.seconds() < restore_time {
+        return Err(StdError::generic_err("restore is not due yet"));
+    }
+
+    SHUTDOWN.save(deps.storage, &false)?;
+    RESTORE_TIME.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_messages(switch_messages(&config, false)?)
+        .add_attribute("action", "restore"))
+}
+
+/// Returns the messages switching the withdraw-only mode of the factory, the staking and the
+/// collector and pausing or unpausing the router
+fn switch_messages(config: &Config, withdraw_only: bool) -> StdResult<Vec<CosmosMsg>> {
+    let router_msg = if withdraw_only {
+        RouterExecuteMsg::Pause {}
+    } else {
+        RouterExecuteMsg::Unpause {}
+    };
+
+    Ok(vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.factory.to_string(),
+            msg: to_binary(&FactoryExecuteMsg::SetWithdrawOnly { withdraw_only })?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.router.to_string(),
+            msg: to_binary(&router_msg)?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.staking.to_string(),
+            msg: to_binary(&StakingExecuteMsg::SetWithdrawOnly { withdraw_only })?,
+            funds: vec![],
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.collector.to_string(),
+            msg: to_binary(&CollectorExecuteMsg::SetWithdrawOnly { withdraw_only })?,
+            funds: vec![],
+        }),
+    ])
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        guardians: config.guardians,
+        factory: config.factory,
+        router: config.router,
+        staking: config.staking,
+        collector: config.collector,
+        restore_delay: config.restore_delay,
+    })
+}
+
+pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    Ok(StateResponse {
+        shutdown: is_shutdown(deps.storage)?,
+        restore_time: RESTORE_TIME.may_load(deps.storage)?,
+    })
+}
@@ -0,0 +1,32 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::Item;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub guardians: Vec<Addr>,
+    pub factory: Addr,
+    pub router: Addr,
+    pub staking: Addr,
+    pub collector: Addr,
+    pub restore_delay: u64,
+}
+
+impl Config {
+    pub fn can_shutdown(&self, address: &Addr) -> bool {
+        self.owner == *address || self.guardians.contains(address)
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const SHUTDOWN: Item<bool> = Item::new("shutdown");
+/// the block time in seconds the queued restore can be executed from
+pub const RESTORE_TIME: Item<u64> = Item::new("restore_time");
+
+pub fn is_shutdown(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(SHUTDOWN.may_load(storage)?.unwrap_or(false))
+}
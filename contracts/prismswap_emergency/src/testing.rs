@@ -0,0 +1,254 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, to_binary, Addr, CosmosMsg, Env, OwnedDeps, StdError, WasmMsg};
+use prismswap::emergency::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::router::ExecuteMsg as RouterExecuteMsg;
+use prismswap::staking::ExecuteMsg as StakingExecuteMsg;
+
+const RESTORE_DELAY: u64 = 86400;
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("gov0000"),
+        guardians: vec![Addr::unchecked("guardian0000")],
+        factory: Addr::unchecked("factory0000"),
+        router: Addr::unchecked("router0000"),
+        staking: Addr::unchecked("staking0000"),
+        collector: Addr::unchecked("collector0000"),
+        restore_delay: RESTORE_DELAY,
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    deps
+}
+
+fn query_state(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>) -> StateResponse {
+    from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap()
+}
+
+#[test]
+fn shutdown() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Shutdown {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Shutdown {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 4);
+    assert_eq!(
+        res.messages[0].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "factory0000".to_string(),
+            msg: to_binary(&FactoryExecuteMsg::SetWithdrawOnly {
+                withdraw_only: true
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router0000".to_string(),
+            msg: to_binary(&RouterExecuteMsg::Pause {}).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        res.messages[2].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            msg: to_binary(&StakingExecuteMsg::SetWithdrawOnly {
+                withdraw_only: true
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+
+    assert_eq!(
+        query_state(&deps),
+        StateResponse {
+            shutdown: true,
+            restore_time: None,
+        }
+    );
+}
+
+#[test]
+fn restore() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        ExecuteMsg::QueueRestore {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("protocol is not shut down"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Shutdown {},
+    )
+    .unwrap();
+
+    // only governance can queue a restore
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::QueueRestore {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        ExecuteMsg::QueueRestore {},
+    )
+    .unwrap();
+    assert_eq!(
+        query_state(&deps),
+        StateResponse {
+            shutdown: true,
+            restore_time: Some(mock_env().block.time.seconds() + RESTORE_DELAY),
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(RESTORE_DELAY - 1),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteRestore {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("restore is not due yet"));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(RESTORE_DELAY),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteRestore {},
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 4);
+    assert_eq!(
+        res.messages[1].msg,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router0000".to_string(),
+            msg: to_binary(&RouterExecuteMsg::Unpause {}).unwrap(),
+            funds: vec![],
+        })
+    );
+    assert_eq!(
+        query_state(&deps),
+        StateResponse {
+            shutdown: false,
+            restore_time: None,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(RESTORE_DELAY),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteRestore {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no restore queued"));
+}
+
+#[test]
+fn cancel_restore() {
+    let mut deps = setup();
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Shutdown {},
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        ExecuteMsg::QueueRestore {},
+    )
+    .unwrap();
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::CancelRestore {},
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        env_at(RESTORE_DELAY),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteRestore {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no restore queued"));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        guardians: Some(vec![]),
+        restore_delay: Some(3600),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.guardians, Vec::<Addr>::new());
+    assert_eq!(config.restore_delay, 3600);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Shutdown {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+}
@@ -1,18 +1,20 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError,
-    StdResult, SubMsg, WasmMsg,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdResult,
+    SubMsg, WasmMsg,
 };
 
+use crate::error::ContractError;
 use crate::migration::migrate_config;
 use crate::parse_reply::parse_reply_instantiate_data;
 use crate::querier::query_pair_info;
 use crate::state::{
-    pair_key, read_pairs, Config, PairConfig, TmpPairInfo, CONFIG, PAIRS, TMP_PAIR_INFO,
+    is_pair_creator, is_withdraw_only, read_pairs, Config, PairConfig, TmpPairInfo, CONFIG,
+    GUARDIAN, PAIRS, PAIR_CREATORS, PARAM_REGISTRY, STAKING_CONTRACT, TMP_PAIR_INFO, WITHDRAW_ONLY,
 };
 
-use prismswap::asset::{AssetInfo, PairInfo, PrismSwapAssetInfo};
+use prismswap::asset::{pair_key, AssetInfo, PairInfo, PrismSwapAssetInfo};
 use prismswap::factory::{
     ConfigResponse, ExecuteMsg, FeeConfig, FeeInfoResponse, InstantiateMsg, MigrateMsg,
     PairConfigResponse, PairsConfigResponse, PairsResponse, QueryMsg,
@@ -25,7 +27,7 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config = Config {
         owner: deps.api.addr_validate(msg.owner.as_str())?,
         token_code_id: msg.token_code_id,
@@ -40,7 +42,12 @@ pub fn instantiate(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdateConfig {
             owner,
@@ -57,12 +64,25 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             collector,
             pairs_admin,
         ),
+        ExecuteMsg::UpdatePairCreators { addresses } => {
+            execute_update_pair_creators(deps, info, addresses)
+        }
+        ExecuteMsg::UpdateGuardian { guardian } => execute_update_guardian(deps, info, guardian),
+        ExecuteMsg::SetWithdrawOnly { withdraw_only } => {
+            execute_set_withdraw_only(deps, info, withdraw_only)
+        }
+        ExecuteMsg::UpdateParamRegistry { param_registry } => {
+            execute_update_param_registry(deps, info, param_registry)
+        }
+        ExecuteMsg::UpdateStakingContract { staking_contract } => {
+            execute_update_staking_contract(deps, info, staking_contract)
+        }
         ExecuteMsg::CreatePair {
             asset_infos,
             fee_config,
         } => {
-            asset_infos[0].check(deps.api)?;
-            asset_infos[1].check(deps.api)?;
+            asset_infos[0].validate(deps.api)?;
+            asset_infos[1].validate(deps.api)?;
             execute_create_pair(deps, info, env, asset_infos, fee_config)
         }
         ExecuteMsg::UpdatePairConfig {
@@ -90,12 +110,12 @@ pub fn execute_update_config(
     pair_code_id: Option<u64>,
     collector: Option<Addr>,
     pairs_admin: Option<Addr>,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     if let Some(owner) = owner {
@@ -126,39 +146,154 @@ pub fn execute_update_config(
     Ok(Response::new().add_attribute("action", "update_config"))
 }
 
-// Only owner can create pairs
+// Only owner can execute it
+pub fn execute_update_pair_creators(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<Addr>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in &addresses {
+        deps.api.addr_validate(address.as_str())?;
+    }
+    PAIR_CREATORS.save(deps.storage, &addresses)?;
+
+    Ok(Response::new().add_attribute("action", "update_pair_creators"))
+}
+
+// Only owner can execute it
+pub fn execute_update_guardian(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardian: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match guardian {
+        Some(guardian) => {
+            deps.api.addr_validate(guardian.as_str())?;
+            GUARDIAN.save(deps.storage, &guardian)?;
+        }
+        None => GUARDIAN.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_guardian"))
+}
+
+// Only owner or guardian can execute it
+pub fn execute_set_withdraw_only(
+    deps: DepsMut,
+    info: MessageInfo,
+    withdraw_only: bool,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let guardian: Option<Addr> = GUARDIAN.may_load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner && Some(&info.sender) != guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    WITHDRAW_ONLY.save(deps.storage, &withdraw_only)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_withdraw_only"),
+        ("withdraw_only", &withdraw_only.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_param_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    param_registry: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match param_registry {
+        Some(param_registry) => {
+            deps.api.addr_validate(param_registry.as_str())?;
+            PARAM_REGISTRY.save(deps.storage, &param_registry)?;
+        }
+        None => PARAM_REGISTRY.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_param_registry"))
+}
+
+// Only owner can execute it
+pub fn execute_update_staking_contract(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_contract: Option<Addr>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match staking_contract {
+        Some(staking_contract) => {
+            deps.api.addr_validate(staking_contract.as_str())?;
+            STAKING_CONTRACT.save(deps.storage, &staking_contract)?;
+        }
+        None => STAKING_CONTRACT.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_staking_contract"))
+}
+
+// Only owner and pair creators can create pairs
 pub fn execute_create_pair(
     deps: DepsMut,
     info: MessageInfo,
     env: Env,
     asset_infos: [AssetInfo; 2],
     fee_config: Option<FeeConfig>,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
-    if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+    if info.sender != config.owner && !is_pair_creator(deps.storage, &info.sender)? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if is_withdraw_only(deps.storage)? {
+        return Err(ContractError::WithdrawOnly {});
     }
 
     // validate the given fee configuration
     let fee_config: FeeConfig = fee_config.unwrap_or_default();
-    if !fee_config.is_valid() {
-        return Err(StdError::generic_err(
-            "The given fee configuration is not valid",
-        ));
-    }
+    fee_config.validate(deps.api)?;
 
     let pair_key = pair_key(&asset_infos);
     if PAIRS.may_load(deps.storage, &pair_key)?.is_some() {
-        return Err(StdError::generic_err("Pair already exists"));
+        return Err(ContractError::PairAlreadyExists {});
     }
 
     TMP_PAIR_INFO.save(
         deps.storage,
         &TmpPairInfo {
             pair_key,
-            fee_config,
+            fee_config: fee_config.clone(),
         },
     )?;
 
@@ -174,11 +309,12 @@ pub fn execute_create_pair(
                 code_id: config.pair_code_id,
                 funds: vec![],
                 admin: Some(config.pairs_admin.to_string()),
-                label: "".to_string(),
+                label: "PrismSwap pair".to_string(),
                 msg: to_binary(&PairInstantiateMsg {
                     asset_infos,
                     token_code_id: config.token_code_id,
                     factory: env.contract.address,
+                    fee_config,
                 })?,
             }
             .into(),
@@ -192,25 +328,21 @@ pub fn execute_update_pair_config(
     info: MessageInfo,
     asset_infos: [AssetInfo; 2],
     fee_config: FeeConfig,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     // validate the given fee configuration
-    if !fee_config.is_valid() {
-        return Err(StdError::generic_err(
-            "The given fee configuration is not valid",
-        ));
-    }
+    fee_config.validate(deps.api)?;
 
     let pair_key = pair_key(&asset_infos);
     let mut pair_config: PairConfig = PAIRS
         .load(deps.storage, &pair_key)
-        .map_err(|_| StdError::generic_err("There is no pair registered with the provided info"))?;
+        .map_err(|_| ContractError::InvalidPair {})?;
 
     pair_config.fee_config = fee_config;
 
@@ -224,12 +356,12 @@ pub fn execute_deregister(
     deps: DepsMut,
     info: MessageInfo,
     asset_infos: [AssetInfo; 2],
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
     // permission check
     if info.sender != config.owner {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     let pair_key = pair_key(&asset_infos);
@@ -237,7 +369,7 @@ pub fn execute_deregister(
     // check if pair exists
     PAIRS
         .load(deps.storage, &pair_key)
-        .map_err(|_| StdError::generic_err("There is no pair registered with the provided info"))?;
+        .map_err(|_| ContractError::InvalidPair {})?;
 
     // delete the pair from storage
     PAIRS.remove(deps.storage, &pair_key);
@@ -247,11 +379,10 @@ pub fn execute_deregister(
 
 /// This just stores the result for future query
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
     let tmp_pair_info = TMP_PAIR_INFO.load(deps.storage)?;
 
-    let res = parse_reply_instantiate_data(msg)
-        .map_err(|err| StdError::generic_err(format!("{}", err)))?;
+    let res = parse_reply_instantiate_data(msg).map_err(|_| ContractError::ParseReplyError {})?;
     let pair_contract = res.contract_address;
 
     PAIRS.save(
@@ -290,6 +421,11 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
         pair_code_id: config.pair_code_id,
         collector: config.collector,
         pairs_admin: config.pairs_admin,
+        pair_creators: PAIR_CREATORS.may_load(deps.storage)?.unwrap_or_default(),
+        guardian: GUARDIAN.may_load(deps.storage)?,
+        withdraw_only: is_withdraw_only(deps.storage)?,
+        param_registry: PARAM_REGISTRY.may_load(deps.storage)?,
+        staking_contract: STAKING_CONTRACT.may_load(deps.storage)?,
     };
 
     Ok(resp)
@@ -326,8 +462,11 @@ pub fn query_fee_config(deps: Deps, asset_infos: [AssetInfo; 2]) -> StdResult<Fe
     };
 
     Ok(FeeInfoResponse {
-        collector: config.collector,
+        collector: fee_config.collector_or(config.collector),
         fee_config,
+        withdraw_only: is_withdraw_only(deps.storage)?,
+        param_registry: PARAM_REGISTRY.may_load(deps.storage)?,
+        staking_contract: STAKING_CONTRACT.may_load(deps.storage)?,
     })
 }
 
@@ -366,9 +505,13 @@ pub fn query_pairs_config(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
-    let pairs_admin: Addr = deps.api.addr_validate(msg.pairs_admin.as_str())?;
-    migrate_config(deps.storage, pairs_admin)?;
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::V1_0_1 { pairs_admin } => {
+            let pairs_admin: Addr = deps.api.addr_validate(pairs_admin.as_str())?;
+            migrate_config(deps.storage, pairs_admin)?;
+        }
+    }
 
     Ok(Response::default())
 }
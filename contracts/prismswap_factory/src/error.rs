@@ -0,0 +1,23 @@
+use cosmwasm_std::StdError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Factory is in withdraw-only mode")]
+    WithdrawOnly {},
+
+    #[error("Pair already exists")]
+    PairAlreadyExists {},
+
+    #[error("There is no pair registered with the provided info")]
+    InvalidPair {},
+
+    #[error("ParseReplyError")]
+    ParseReplyError {},
+}
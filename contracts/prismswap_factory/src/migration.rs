@@ -31,9 +31,12 @@ pub fn migrate_config(storage: &mut dyn Storage, pairs_admin: Addr) -> StdResult
 
 #[cfg(test)]
 mod migrate_tests {
-    use cosmwasm_std::{testing::mock_dependencies, Api};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{from_slice, Api};
+    use prismswap::factory::MigrateMsg;
 
     use crate::{
+        contract::migrate,
         migration::{migrate_config, LegacyConfig, LEGACY},
         state::{Config, CONFIG},
     };
@@ -72,4 +75,36 @@ mod migrate_tests {
             }
         )
     }
+
+    #[test]
+    fn test_versioned_migration() {
+        let mut deps = mock_dependencies(&[]);
+
+        LEGACY
+            .save(
+                &mut deps.storage,
+                &LegacyConfig {
+                    owner: deps.api.addr_validate("owner0000").unwrap(),
+                    token_code_id: 2,
+                    pair_code_id: 33,
+                    collector: deps.api.addr_validate("collector0000").unwrap(),
+                },
+            )
+            .unwrap();
+
+        let msg: MigrateMsg = from_slice(br#"{"v1_0_1":{"pairs_admin":"admin0000"}}"#).unwrap();
+        assert_eq!(
+            msg,
+            MigrateMsg::V1_0_1 {
+                pairs_admin: deps.api.addr_validate("admin0000").unwrap(),
+            }
+        );
+        migrate(deps.as_mut(), mock_env(), msg).unwrap();
+
+        let config: Config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(
+            config.pairs_admin,
+            deps.api.addr_validate("admin0000").unwrap()
+        );
+    }
 }
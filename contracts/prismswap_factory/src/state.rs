@@ -1,12 +1,13 @@
 use prismswap::{
-    asset::{AssetInfo, PrismSwapAssetInfo},
+    asset::{pair_key, AssetInfo},
     factory::FeeConfig,
+    pagination::{calc_limit, calc_range_start, start_after_bound},
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Addr, Order, StdResult, Storage};
-use cw_storage_plus::{Bound, Item, Map};
+use cw_storage_plus::{Item, Map};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -19,6 +20,29 @@ pub struct Config {
 
 pub const CONFIG: Item<Config> = Item::new("config");
 
+/// the addresses allowed to create pairs besides the owner, like the LBPs graduating into a pair
+pub const PAIR_CREATORS: Item<Vec<Addr>> = Item::new("pair_creators");
+
+pub fn is_pair_creator(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    Ok(PAIR_CREATORS
+        .may_load(storage)?
+        .is_some_and(|pair_creators| pair_creators.contains(address)))
+}
+
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+/// whether the protocol was put in withdraw-only mode by the owner or the guardian
+pub const WITHDRAW_ONLY: Item<bool> = Item::new("withdraw_only");
+
+/// the parameter registry the pairs read their tunables from
+pub const PARAM_REGISTRY: Item<Addr> = Item::new("param_registry");
+
+/// the staking contract the pairs bond the liquidity tokens of the auto-staking providers in
+pub const STAKING_CONTRACT: Item<Addr> = Item::new("staking_contract");
+
+pub fn is_withdraw_only(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(WITHDRAW_ONLY.may_load(storage)?.unwrap_or(false))
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct TmpPairInfo {
     pub pair_key: Vec<u8>,
@@ -34,45 +58,21 @@ pub struct PairConfig {
 pub const TMP_PAIR_INFO: Item<TmpPairInfo> = Item::new("tmp_pair_info");
 pub const PAIRS: Map<&[u8], PairConfig> = Map::new("pair_config");
 
-pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
-    let mut asset_infos = asset_infos.to_vec();
-    asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
-
-    [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat()
-}
-
-// settings for pagination
-const MAX_LIMIT: u32 = 30;
-const DEFAULT_LIMIT: u32 = 10;
 pub fn read_pairs(
     storage: &dyn Storage,
     start_after: Option<[AssetInfo; 2]>,
     limit: Option<u32>,
 ) -> StdResult<Vec<PairConfig>> {
-    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
-    let start = calc_range_start(start_after).map(Bound::exclusive);
+    let start = start_after_bound(calc_range_start(
+        start_after.map(|asset_infos| pair_key(&asset_infos)),
+    ));
 
     PAIRS
         .range(storage, start, None, Order::Ascending)
-        .take(limit)
+        .take(calc_limit(limit))
         .map(|item| {
             let (_, v) = item?;
             Ok(v)
         })
         .collect::<StdResult<Vec<PairConfig>>>()
 }
-
-// this will set the first key after the provided key, by appending a 1 byte
-fn calc_range_start(start_after: Option<[AssetInfo; 2]>) -> Option<Vec<u8>> {
-    start_after.map(|asset_infos| {
-        let mut asset_infos = asset_infos.to_vec();
-        asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
-
-        let mut v = [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()]
-            .concat()
-            .as_slice()
-            .to_vec();
-        v.push(1);
-        v
-    })
-}
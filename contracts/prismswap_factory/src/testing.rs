@@ -1,16 +1,17 @@
 use std::str::FromStr;
 
 use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
 use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
 
-use crate::state::{pair_key, TmpPairInfo, TMP_PAIR_INFO};
+use crate::state::{TmpPairInfo, TMP_PAIR_INFO};
 
 use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     attr, from_binary, to_binary, Addr, ContractResult, Decimal, MemoryStorage, OwnedDeps, Reply,
     ReplyOn, StdError, SubMsg, SubMsgExecutionResponse, WasmMsg,
 };
-use prismswap::asset::{AssetInfo, PairInfo};
+use prismswap::asset::{pair_key, AssetInfo, PairInfo};
 use prismswap::factory::{
     ConfigResponse, ExecuteMsg, FeeConfig, FeeInfoResponse, InstantiateMsg, PairConfigResponse,
     PairsConfigResponse, PairsResponse, QueryMsg, DEFAULT_PROTOCOL_FEE, DEFAULT_TOTAL_FEE,
@@ -116,7 +117,7 @@ fn update_config() {
 
     let res = execute(deps.as_mut(), env, info, msg);
     match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        Err(ContractError::Unauthorized {}) => {}
         _ => panic!("Must return unauthorized error"),
     }
 }
@@ -152,9 +153,24 @@ fn create_pair() {
     // unauthorized attempt
     let info = mock_info("addr0000", &[]);
     let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
-    assert_eq!(err, StdError::generic_err("unauthorized"));
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // only the owner manages the pair creators
+    let update_msg = ExecuteMsg::UpdatePairCreators {
+        addresses: vec![Addr::unchecked("lbp0000")],
+    };
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, update_msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
 
     let info = mock_info("owner0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, update_msg).unwrap();
+    let query_res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_res: ConfigResponse = from_binary(&query_res).unwrap();
+    assert_eq!(config_res.pair_creators, vec![Addr::unchecked("lbp0000")]);
+
+    // a pair creator can create pairs as the owner
+    let info = mock_info("lbp0000", &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     assert_eq!(
         res.attributes,
@@ -174,11 +190,12 @@ fn create_pair() {
                     factory: Addr::unchecked(MOCK_CONTRACT_ADDR),
                     asset_infos: asset_infos.clone(),
                     token_code_id: 123u64,
+                    fee_config: FeeConfig::default(),
                 })
                 .unwrap(),
                 code_id: 321u64,
                 funds: vec![],
-                label: "".to_string(),
+                label: "PrismSwap pair".to_string(),
                 admin: Some("admin0000".to_string())
             }
             .into()
@@ -194,6 +211,263 @@ fn create_pair() {
     );
 }
 
+#[test]
+fn create_pair_validates_native_denoms() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_code_id: 321u64,
+        token_code_id: 123u64,
+        owner: Addr::unchecked("owner0000"),
+        collector: Addr::unchecked("collector0000"),
+        pairs_admin: Addr::unchecked("admin0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let create_pair = |denom: &str| ExecuteMsg::CreatePair {
+        asset_infos: [
+            AssetInfo::Native(denom.to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        fee_config: None,
+    };
+
+    for (denom, err) in [
+        ("ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB", "invalid ibc denom ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB: the hash must be 64 uppercase hexadecimal characters"),
+        ("ibc/27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2", "invalid ibc denom ibc/27394fb092d2eccd56123c74f36e4c1f926001ceada9ca97ea622b25f41e5eb2: the hash must be 64 uppercase hexadecimal characters"),
+        ("factory/creator0000", "invalid token factory denom factory/creator0000: expected factory/{creator}/{subdenom}"),
+        ("factory/creator0000/", "invalid token factory denom factory/creator0000/: the subdenom is empty"),
+        ("u", "invalid native denom u"),
+        ("1usd", "invalid native denom 1usd"),
+        ("u usd", "invalid native denom u usd"),
+    ] {
+        let info = mock_info("owner0000", &[]);
+        let res = execute(deps.as_mut(), mock_env(), info, create_pair(denom));
+        assert_eq!(
+            res.unwrap_err(),
+            ContractError::Std(StdError::generic_err(err)),
+            "{}",
+            denom
+        );
+    }
+
+    for denom in [
+        "uusd",
+        "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2",
+        "factory/creator0000/uprism",
+    ] {
+        let info = mock_info("owner0000", &[]);
+        execute(deps.as_mut(), mock_env(), info, create_pair(denom)).unwrap();
+    }
+}
+
+#[test]
+fn withdraw_only() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_code_id: 321u64,
+        token_code_id: 123u64,
+        owner: Addr::unchecked("owner0000"),
+        collector: Addr::unchecked("collector0000"),
+        pairs_admin: Addr::unchecked("admin0000"),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // only the owner sets the guardian
+    let msg = ExecuteMsg::UpdateGuardian {
+        guardian: Some(Addr::unchecked("guardian0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetWithdrawOnly {
+        withdraw_only: true,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let config_res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config_res.guardian, Some(Addr::unchecked("guardian0000")));
+    assert!(config_res.withdraw_only);
+
+    // the pairs read the mode along with their fees
+    let asset_infos = [
+        AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        AssetInfo::Cw20(Addr::unchecked("asset0001")),
+    ];
+    let fee_info_res: FeeInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeInfo {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(fee_info_res.withdraw_only);
+
+    let msg = ExecuteMsg::CreatePair {
+        asset_infos,
+        fee_config: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::WithdrawOnly {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::SetWithdrawOnly {
+            withdraw_only: false,
+        },
+    )
+    .unwrap();
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+}
+
+#[test]
+fn update_param_registry() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_code_id: 321u64,
+        token_code_id: 123u64,
+        owner: Addr::unchecked("owner0000"),
+        collector: Addr::unchecked("collector0000"),
+        pairs_admin: Addr::unchecked("admin0000"),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateParamRegistry {
+        param_registry: Some(Addr::unchecked("params0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    // the pairs read the registry along with their fees
+    let fee_info_res: FeeInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeInfo {
+                asset_infos: [
+                    AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                ],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        fee_info_res.param_registry,
+        Some(Addr::unchecked("params0000"))
+    );
+
+    let msg = ExecuteMsg::UpdateParamRegistry {
+        param_registry: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config_res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config_res.param_registry, None);
+}
+
+#[test]
+fn update_staking_contract() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        pair_code_id: 321u64,
+        token_code_id: 123u64,
+        owner: Addr::unchecked("owner0000"),
+        collector: Addr::unchecked("collector0000"),
+        pairs_admin: Addr::unchecked("admin0000"),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateStakingContract {
+        staking_contract: Some(Addr::unchecked("staking0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    // the pairs bond the liquidity of the auto-staking providers in it
+    let fee_info_res: FeeInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeInfo {
+                asset_infos: [
+                    AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                ],
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        fee_info_res.staking_contract,
+        Some(Addr::unchecked("staking0000"))
+    );
+
+    let msg = ExecuteMsg::UpdateStakingContract {
+        staking_contract: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config_res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config_res.staking_contract, None);
+}
+
 #[test]
 fn reply_test() {
     let mut deps = mock_dependencies(&[]);
@@ -318,7 +592,7 @@ fn create_pair_2() {
     // unauthorized attempt
     let info = mock_info("addr0000", &[]);
     let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
-    assert_eq!(err, StdError::generic_err("unauthorized"));
+    assert_eq!(err, ContractError::Unauthorized {});
 
     // set correct owner
     let info = mock_info("owner0000", &[]);
@@ -328,13 +602,18 @@ fn create_pair_2() {
         asset_infos: asset_infos.clone(),
         fee_config: Some(FeeConfig {
             total_fee: Decimal::from_str(MAX_TOTAL_FEE).unwrap() + Decimal::one(),
-            protocol_fee: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+            protocol_fee_share: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+            collector: None,
         }),
     };
     let err = execute(deps.as_mut(), mock_env(), info.clone(), invalid_fee_msg).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("The given fee configuration is not valid")
+        ContractError::Std(StdError::generic_err("total fee cannot exceed 0.05"))
+    );
+    assert_eq!(
+        FeeConfig::from_bps(600, 3340).validate(deps.as_ref().api),
+        Err(StdError::generic_err("total fee cannot exceed 0.05"))
     );
 
     // failure - invalid fee config
@@ -342,13 +621,16 @@ fn create_pair_2() {
         asset_infos: asset_infos.clone(),
         fee_config: Some(FeeConfig {
             total_fee: Decimal::from_str(DEFAULT_TOTAL_FEE).unwrap(),
-            protocol_fee: Decimal::from_str(MAX_PROTOCOL_FEE).unwrap() + Decimal::one(),
+            protocol_fee_share: Decimal::from_str(MAX_PROTOCOL_FEE).unwrap() + Decimal::one(),
+            collector: None,
         }),
     };
     let err = execute(deps.as_mut(), mock_env(), info.clone(), invalid_msg).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("The given fee configuration is not valid")
+        ContractError::Std(StdError::generic_err(
+            "protocol fee share cannot exceed 0.8"
+        ))
     );
 
     // failure - invalid token
@@ -363,7 +645,9 @@ fn create_pair_2() {
     let err = execute(deps.as_mut(), mock_env(), info.clone(), invalid_msg).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("Invalid input: human address too short")
+        ContractError::Std(StdError::generic_err(
+            "Invalid input: human address too short"
+        ))
     );
 
     // success
@@ -386,11 +670,12 @@ fn create_pair_2() {
                     factory: Addr::unchecked(MOCK_CONTRACT_ADDR),
                     asset_infos: asset_infos.clone(),
                     token_code_id: 123u64,
+                    fee_config: FeeConfig::default(),
                 })
                 .unwrap(),
                 code_id: 321u64,
                 funds: vec![],
-                label: "".to_string(),
+                label: "PrismSwap pair".to_string(),
                 admin: Some("admin0000".to_string()),
             }
             .into()
@@ -417,7 +702,8 @@ fn create_pair_2() {
 
     let fee_config_default = FeeConfig {
         total_fee: Decimal::from_str(DEFAULT_TOTAL_FEE).unwrap(),
-        protocol_fee: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+        protocol_fee_share: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+        collector: None,
     };
 
     // query pair config, we should get default values
@@ -434,7 +720,7 @@ fn create_pair_2() {
 
     // failure - pair already exists
     let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
-    assert_eq!(err, StdError::generic_err("Pair already exists"));
+    assert_eq!(err, ContractError::PairAlreadyExists {});
 
     // create new pair, this time with a valid FeeConfig
     let asset_infos = [
@@ -443,8 +729,12 @@ fn create_pair_2() {
     ];
     let custom_fee_config = FeeConfig {
         total_fee: Decimal::from_str("0.004").unwrap(),
-        protocol_fee: Decimal::from_str("0.075").unwrap(),
+        protocol_fee_share: Decimal::from_str("0.075").unwrap(),
+        collector: None,
     };
+    assert_eq!(FeeConfig::from_bps(40, 750), custom_fee_config);
+    assert_eq!(custom_fee_config.total_fee_bps(), 40);
+    assert_eq!(custom_fee_config.protocol_fee_share_bps(), 750);
 
     let msg = ExecuteMsg::CreatePair {
         asset_infos: asset_infos.clone(),
@@ -503,7 +793,8 @@ fn test_update_pair_config() {
 
     let fee_config_default = FeeConfig {
         total_fee: Decimal::from_str(DEFAULT_TOTAL_FEE).unwrap(),
-        protocol_fee: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+        protocol_fee_share: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+        collector: None,
     };
 
     let msg = ExecuteMsg::CreatePair {
@@ -541,7 +832,8 @@ fn test_update_pair_config() {
     // update fee config to new values
     let fee_config_updated = FeeConfig {
         total_fee: Decimal::from_str("0.04").unwrap(),
-        protocol_fee: Decimal::from_str("0.75").unwrap(),
+        protocol_fee_share: Decimal::from_str("0.75").unwrap(),
+        collector: None,
     };
     let msg = ExecuteMsg::UpdatePairConfig {
         asset_infos: asset_infos.clone(),
@@ -564,10 +856,60 @@ fn test_update_pair_config() {
     let pair_config: PairConfigResponse = from_binary(&query_res).unwrap();
     assert_eq!(pair_config.fee_config, fee_config_updated);
 
+    // the protocol share goes to the collector of the pair when it sets one
+    let info = mock_info("owner0000", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdatePairConfig {
+            asset_infos: asset_infos.clone(),
+            fee_config: FeeConfig {
+                collector: Some(Addr::unchecked("te")),
+                ..fee_config_updated.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Invalid input: human address too short"
+        ))
+    );
+
+    let fee_config_collector = FeeConfig {
+        collector: Some(Addr::unchecked("collector0001")),
+        ..fee_config_updated.clone()
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdatePairConfig {
+            asset_infos: asset_infos.clone(),
+            fee_config: fee_config_collector.clone(),
+        },
+    )
+    .unwrap();
+    let fee_info: FeeInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::FeeInfo {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(fee_info.fee_config, fee_config_collector);
+    assert_eq!(fee_info.collector, Addr::unchecked("collector0001"));
+
     // failure - unauthorized
     let info = mock_info("addr0000", &[]);
     let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(err, StdError::generic_err("unauthorized"));
+    assert_eq!(err, ContractError::Unauthorized {});
 
     // failure - invalid fee config
     let info = mock_info("owner0000", &[]);
@@ -575,13 +917,16 @@ fn test_update_pair_config() {
         asset_infos: asset_infos.clone(),
         fee_config: Some(FeeConfig {
             total_fee: Decimal::from_str(DEFAULT_TOTAL_FEE).unwrap(),
-            protocol_fee: Decimal::from_str(MAX_PROTOCOL_FEE).unwrap() + Decimal::one(),
+            protocol_fee_share: Decimal::from_str(MAX_PROTOCOL_FEE).unwrap() + Decimal::one(),
+            collector: None,
         }),
     };
     let err = execute(deps.as_mut(), mock_env(), info, invalid_msg).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("The given fee configuration is not valid")
+        ContractError::Std(StdError::generic_err(
+            "protocol fee share cannot exceed 0.8"
+        ))
     );
 
     // failure - no pair exists
@@ -595,10 +940,7 @@ fn test_update_pair_config() {
         fee_config: fee_config_updated.clone(),
     };
     let err = execute(deps.as_mut(), mock_env(), info.clone(), msg_bad).unwrap_err();
-    assert_eq!(
-        err,
-        StdError::generic_err("There is no pair registered with the provided info")
-    );
+    assert_eq!(err, ContractError::InvalidPair {});
 
     // failure - invalid token
     let asset_infos_bad = [
@@ -612,7 +954,9 @@ fn test_update_pair_config() {
     let err = execute(deps.as_mut(), mock_env(), info, msg_bad).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("Invalid input: human address too short")
+        ContractError::Std(StdError::generic_err(
+            "Invalid input: human address too short"
+        ))
     );
 }
 
@@ -676,7 +1020,7 @@ fn test_deregister() {
         asset_infos: asset_infos.clone(),
     };
     let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
-    assert_eq!(err, StdError::generic_err("unauthorized"));
+    assert_eq!(err, ContractError::Unauthorized {});
 
     // failure - no pair exists
     let info = mock_info("owner0000", &[]);
@@ -688,10 +1032,7 @@ fn test_deregister() {
         asset_infos: asset_infos_bad,
     };
     let err = execute(deps.as_mut(), mock_env(), info.clone(), msg_bad).unwrap_err();
-    assert_eq!(
-        err,
-        StdError::generic_err("There is no pair registered with the provided info")
-    );
+    assert_eq!(err, ContractError::InvalidPair {});
 
     // failure - invalid token
     let asset_infos_bad = [
@@ -704,7 +1045,9 @@ fn test_deregister() {
     let err = execute(deps.as_mut(), mock_env(), info.clone(), msg_bad).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("Invalid input: human address too short")
+        ContractError::Std(StdError::generic_err(
+            "Invalid input: human address too short"
+        ))
     );
 
     // success
@@ -757,7 +1100,8 @@ fn test_queries() {
     ];
     let fee_config2 = FeeConfig {
         total_fee: Decimal::from_str("0.004").unwrap(),
-        protocol_fee: Decimal::from_str("0.075").unwrap(),
+        protocol_fee_share: Decimal::from_str("0.075").unwrap(),
+        collector: None,
     };
 
     let msg = ExecuteMsg::CreatePair {
@@ -891,7 +1235,10 @@ fn test_queries() {
         fee_info_response,
         FeeInfoResponse {
             fee_config: FeeConfig::default(),
-            collector: Addr::unchecked("collector0000")
+            collector: Addr::unchecked("collector0000"),
+            withdraw_only: false,
+            param_registry: None,
+            staking_contract: None,
         }
     );
 
@@ -911,7 +1258,10 @@ fn test_queries() {
         fee_info_response,
         FeeInfoResponse {
             fee_config: fee_config2.clone(),
-            collector: Addr::unchecked("collector0000")
+            collector: Addr::unchecked("collector0000"),
+            withdraw_only: false,
+            param_registry: None,
+            staking_contract: None,
         }
     );
 
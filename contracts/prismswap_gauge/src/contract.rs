@@ -0,0 +1,449 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw_storage_plus::U64Key;
+
+use crate::state::{
+    read_all_gauge_weights, read_gauge_weights, Config, EpochVote, VoterInfo, CONFIG, EPOCH_VOTES,
+    EPOCH_WEIGHTS, GAUGE_WEIGHTS, LAST_APPLIED_EPOCH, VOTERS,
+};
+
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::gauge::{
+    ConfigResponse, Cw20HookMsg, EpochResponse, ExecuteMsg, GaugeVote, GaugeWeight,
+    GaugeWeightsResponse, InstantiateMsg, QueryMsg, VoterResponse, VoterWeightResponse,
+};
+use prismswap::staking::{
+    ExecuteMsg as StakingExecuteMsg, PoolAllocPoint, PoolResponse, QueryMsg as StakingQueryMsg,
+};
+
+/// The allocation points shared by the pools of an applied epoch
+const TOTAL_ALLOC_POINT: u128 = 10_000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    if msg.epoch_length == 0 {
+        return Err(StdError::generic_err(
+            "epoch length must be greater than zero",
+        ));
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        xprism_token: deps.api.addr_validate(msg.xprism_token.as_str())?,
+        staking_contract: deps.api.addr_validate(msg.staking_contract.as_str())?,
+        epoch_length: msg.epoch_length,
+        start_time: msg.start_time,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::Withdraw { amount } => execute_withdraw(deps, env, info, amount),
+        ExecuteMsg::Vote { votes } => execute_vote(deps, env, info, votes),
+        ExecuteMsg::Checkpoint {} => execute_checkpoint(deps, env),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Deposit {} => {
+            // only xPRISM counts as voting power
+            if info.sender != config.xprism_token {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            let voter = deps.api.addr_validate(&cw20_msg.sender)?;
+            let mut voter_info = VOTERS.may_load(deps.storage, &voter)?.unwrap_or_default();
+            voter_info.deposit_amount = voter_info.deposit_amount.checked_add(cw20_msg.amount)?;
+            VOTERS.save(deps.storage, &voter, &voter_info)?;
+
+            Ok(Response::new().add_attributes(vec![
+                ("action", "deposit"),
+                ("voter", voter.as_str()),
+                ("amount", &cw20_msg.amount.to_string()),
+            ]))
+        }
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut voter_info = VOTERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+
+    // the deposit backs the vote until the epoch is over
+    if voter_info.vote_epoch.is_some() && voter_info.vote_epoch == current_epoch(&config, &env) {
+        return Err(StdError::generic_err(
+            "xPRISM is locked until the end of the epoch voted in",
+        ));
+    }
+    if amount > voter_info.deposit_amount {
+        return Err(StdError::generic_err("cannot withdraw more than deposited"));
+    }
+
+    voter_info.deposit_amount = voter_info.deposit_amount.checked_sub(amount)?;
+    VOTERS.save(deps.storage, &info.sender, &voter_info)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.xprism_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount,
+            })?,
+        }))
+        .add_attributes(vec![
+            ("action", "withdraw"),
+            ("voter", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+pub fn execute_vote(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    votes: Vec<GaugeVote>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let epoch = current_epoch(&config, &env)
+        .ok_or_else(|| StdError::generic_err("voting has not started"))?;
+
+    let mut total_ratio = Decimal::zero();
+    for (i, vote) in votes.iter().enumerate() {
+        if vote.ratio.is_zero() {
+            return Err(StdError::generic_err(
+                "vote ratio must be greater than zero",
+            ));
+        }
+        if votes[..i].iter().any(|prev| prev.lp_token == vote.lp_token) {
+            return Err(StdError::generic_err(format!(
+                "duplicate vote for {}",
+                vote.lp_token
+            )));
+        }
+        assert_pool(deps.as_ref(), &config, &vote.lp_token)?;
+        total_ratio = total_ratio + vote.ratio;
+    }
+    if total_ratio > Decimal::one() {
+        return Err(StdError::generic_err("vote ratios must not exceed 1"));
+    }
+
+    let mut voter_info = VOTERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if voter_info.deposit_amount.is_zero() {
+        return Err(StdError::generic_err("no voting power"));
+    }
+
+    // a new vote in the same epoch replaces the previous one
+    if voter_info.vote_epoch == Some(epoch) {
+        for vote in voter_info.votes.iter() {
+            remove_weight(
+                deps.storage,
+                epoch,
+                &vote.lp_token,
+                voter_info.voting_power * vote.ratio,
+            )?;
+        }
+    }
+    for vote in votes.iter() {
+        add_weight(
+            deps.storage,
+            epoch,
+            &vote.lp_token,
+            voter_info.deposit_amount * vote.ratio,
+        )?;
+    }
+
+    voter_info.vote_epoch = Some(epoch);
+    voter_info.voting_power = voter_info.deposit_amount;
+    voter_info.votes = votes;
+    VOTERS.save(deps.storage, &info.sender, &voter_info)?;
+    EPOCH_VOTES.save(
+        deps.storage,
+        (U64Key::new(epoch), &info.sender),
+        &EpochVote {
+            voting_power: voter_info.voting_power,
+            votes: voter_info.votes.clone(),
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "vote"),
+        ("voter", info.sender.as_str()),
+        ("epoch", &epoch.to_string()),
+        ("voting_power", &voter_info.voting_power.to_string()),
+    ]))
+}
+
+fn assert_pool(deps: Deps, config: &Config, lp_token: &Addr) -> StdResult<()> {
+    let _pool: PoolResponse = deps
+        .querier
+        .query_wasm_smart(
+            &config.staking_contract,
+            &StakingQueryMsg::Pool {
+                lp_token: lp_token.clone(),
+            },
+        )
+        .map_err(|_| StdError::generic_err(format!("pool {} does not exist", lp_token)))?;
+
+    Ok(())
+}
+
+fn add_weight(
+    storage: &mut dyn Storage,
+    epoch: u64,
+    lp_token: &Addr,
+    weight: Uint128,
+) -> StdResult<()> {
+    GAUGE_WEIGHTS.update(storage, (U64Key::new(epoch), lp_token), |prev| {
+        prev.unwrap_or_default()
+            .checked_add(weight)
+            .map_err(StdError::from)
+    })?;
+    EPOCH_WEIGHTS.update(storage, U64Key::new(epoch), |prev| {
+        prev.unwrap_or_default()
+            .checked_add(weight)
+            .map_err(StdError::from)
+    })?;
+
+    Ok(())
+}
+
+fn remove_weight(
+    storage: &mut dyn Storage,
+    epoch: u64,
+    lp_token: &Addr,
+    weight: Uint128,
+) -> StdResult<()> {
+    let gauge_weight = GAUGE_WEIGHTS
+        .load(storage, (U64Key::new(epoch), lp_token))?
+        .checked_sub(weight)?;
+    if gauge_weight.is_zero() {
+        GAUGE_WEIGHTS.remove(storage, (U64Key::new(epoch), lp_token));
+    } else {
+        GAUGE_WEIGHTS.save(storage, (U64Key::new(epoch), lp_token), &gauge_weight)?;
+    }
+
+    let epoch_weight = EPOCH_WEIGHTS
+        .load(storage, U64Key::new(epoch))?
+        .checked_sub(weight)?;
+    EPOCH_WEIGHTS.save(storage, U64Key::new(epoch), &epoch_weight)?;
+
+    Ok(())
+}
+
+/// Apply the votes of the last finished epoch to the staking contract, the pools getting
+/// allocation points in proportion to their votes. The allocation of an epoch nobody voted in is
+/// left as it was
+pub fn execute_checkpoint(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let epoch = match current_epoch(&config, &env) {
+        Some(current_epoch) if current_epoch > 0 => current_epoch - 1,
+        _ => return Err(StdError::generic_err("no epoch is over yet")),
+    };
+    if let Some(last_applied_epoch) = LAST_APPLIED_EPOCH.may_load(deps.storage)? {
+        if last_applied_epoch >= epoch {
+            return Err(StdError::generic_err(format!(
+                "epoch {} is already applied",
+                epoch
+            )));
+        }
+    }
+    LAST_APPLIED_EPOCH.save(deps.storage, &epoch)?;
+
+    let total_weight = EPOCH_WEIGHTS
+        .may_load(deps.storage, U64Key::new(epoch))?
+        .unwrap_or_default();
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !total_weight.is_zero() {
+        let alloc_points = read_all_gauge_weights(deps.storage, epoch)?
+            .into_iter()
+            .map(|(lp_token, weight)| PoolAllocPoint {
+                lp_token,
+                alloc_point: weight
+                    .multiply_ratio(TOTAL_ALLOC_POINT, total_weight)
+                    .u128() as u64,
+            })
+            .collect::<Vec<PoolAllocPoint>>();
+
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.staking_contract.to_string(),
+            funds: vec![],
+            msg: to_binary(&StakingExecuteMsg::SetAllocPoints { alloc_points })?,
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "checkpoint"),
+        ("epoch", &epoch.to_string()),
+        ("total_weight", &total_weight.to_string()),
+    ]))
+}
+
+/// Returns the epoch of the block, `None` before the first one starts
+fn current_epoch(config: &Config, env: &Env) -> Option<u64> {
+    let time = env.block.time.seconds();
+    if time < config.start_time {
+        return None;
+    }
+
+    Some((time - config.start_time) / config.epoch_length)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Epoch {} => to_binary(&query_epoch(deps, env)?),
+        QueryMsg::Voter { voter } => to_binary(&query_voter(deps, voter)?),
+        QueryMsg::GaugeWeights {
+            epoch,
+            start_after,
+            limit,
+        } => to_binary(&query_gauge_weights(deps, epoch, start_after, limit)?),
+        QueryMsg::VoterWeight {
+            voter,
+            epoch,
+            lp_token,
+        } => to_binary(&query_voter_weight(deps, voter, epoch, lp_token)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        xprism_token: config.xprism_token,
+        staking_contract: config.staking_contract,
+        epoch_length: config.epoch_length,
+        start_time: config.start_time,
+        last_applied_epoch: LAST_APPLIED_EPOCH.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_epoch(deps: Deps, env: Env) -> StdResult<EpochResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let epoch = current_epoch(&config, &env)
+        .ok_or_else(|| StdError::generic_err("voting has not started"))?;
+    let start_time = config.start_time + epoch * config.epoch_length;
+
+    Ok(EpochResponse {
+        epoch,
+        start_time,
+        end_time: start_time + config.epoch_length,
+        total_weight: EPOCH_WEIGHTS
+            .may_load(deps.storage, U64Key::new(epoch))?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn query_voter(deps: Deps, voter: Addr) -> StdResult<VoterResponse> {
+    let voter_info: VoterInfo = VOTERS.may_load(deps.storage, &voter)?.unwrap_or_default();
+
+    Ok(VoterResponse {
+        voter,
+        deposit_amount: voter_info.deposit_amount,
+        vote_epoch: voter_info.vote_epoch,
+        voting_power: voter_info.voting_power,
+        votes: voter_info.votes,
+    })
+}
+
+pub fn query_gauge_weights(
+    deps: Deps,
+    epoch: u64,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<GaugeWeightsResponse> {
+    let weights = read_gauge_weights(deps.storage, epoch, start_after, limit)?
+        .into_iter()
+        .map(|(lp_token, weight)| GaugeWeight { lp_token, weight })
+        .collect();
+
+    Ok(GaugeWeightsResponse {
+        epoch,
+        total_weight: EPOCH_WEIGHTS
+            .may_load(deps.storage, U64Key::new(epoch))?
+            .unwrap_or_default(),
+        weights,
+    })
+}
+
+pub fn query_voter_weight(
+    deps: Deps,
+    voter: Addr,
+    epoch: u64,
+    lp_token: Addr,
+) -> StdResult<VoterWeightResponse> {
+    let weight = match EPOCH_VOTES.may_load(deps.storage, (U64Key::new(epoch), &voter))? {
+        Some(epoch_vote) => epoch_vote
+            .votes
+            .iter()
+            .find(|vote| vote.lp_token == lp_token)
+            .map(|vote| epoch_vote.voting_power * vote.ratio)
+            .unwrap_or_default(),
+        None => Uint128::zero(),
+    };
+
+    Ok(VoterWeightResponse {
+        weight,
+        gauge_weight: GAUGE_WEIGHTS
+            .may_load(deps.storage, (U64Key::new(epoch), &lp_token))?
+            .unwrap_or_default(),
+    })
+}
@@ -0,0 +1,94 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, Empty, OwnedDeps,
+    Querier, QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+
+use prismswap::staking::{PoolResponse, QueryMsg as StakingQueryMsg};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    staking_pools: Vec<String>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) => match from_binary(msg).unwrap() {
+                StakingQueryMsg::Pool { lp_token } => {
+                    if !self.staking_pools.contains(&lp_token.to_string()) {
+                        return SystemResult::Ok(ContractResult::Err(format!(
+                            "pool {} does not exist",
+                            lp_token
+                        )));
+                    }
+
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&PoolResponse {
+                            lp_token,
+                            alloc_point: 0,
+                            total_bond: Uint128::zero(),
+                            total_working_amount: Uint128::zero(),
+                            reward_index: Decimal::zero(),
+                            last_reward_block: 0,
+                            reward_proxy: None,
+                            proxy_reward_index: Decimal::zero(),
+                            unbonding_period: 0,
+                            early_unstake_penalty: None,
+                            pending_penalty: Uint128::zero(),
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => panic!("DO NOT ENTER HERE"),
+            },
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            staking_pools: vec![],
+        }
+    }
+
+    pub fn with_staking_pools(&mut self, lp_tokens: &[&Addr]) {
+        self.staking_pools = lp_tokens
+            .iter()
+            .map(|lp_token| lp_token.to_string())
+            .collect();
+    }
+}
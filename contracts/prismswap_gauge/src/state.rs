@@ -0,0 +1,79 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::gauge::GaugeVote;
+use prismswap::pagination::{calc_limit, start_after_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub xprism_token: Addr,
+    pub staking_contract: Addr,
+    pub epoch_length: u64,
+    pub start_time: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// the last epoch whose votes were applied to the staking contract
+pub const LAST_APPLIED_EPOCH: Item<u64> = Item::new("last_applied_epoch");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct VoterInfo {
+    pub deposit_amount: Uint128,
+    pub vote_epoch: Option<u64>,
+    /// the deposit the vote of `vote_epoch` was cast with
+    pub voting_power: Uint128,
+    pub votes: Vec<GaugeVote>,
+}
+
+pub const VOTERS: Map<&Addr, VoterInfo> = Map::new("voters");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochVote {
+    pub voting_power: Uint128,
+    pub votes: Vec<GaugeVote>,
+}
+
+/// the votes of the voters in every epoch, kept for what is paid out by epoch
+pub const EPOCH_VOTES: Map<(U64Key, &Addr), EpochVote> = Map::new("epoch_votes");
+
+/// the voting power cast for the pools of an epoch
+pub const GAUGE_WEIGHTS: Map<(U64Key, &Addr), Uint128> = Map::new("gauge_weights");
+
+/// the voting power cast in an epoch
+pub const EPOCH_WEIGHTS: Map<U64Key, Uint128> = Map::new("epoch_weights");
+
+pub fn read_all_gauge_weights(
+    storage: &dyn Storage,
+    epoch: u64,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    GAUGE_WEIGHTS
+        .prefix(U64Key::new(epoch))
+        .range(storage, None, None, Order::Ascending)
+        .map(gauge_weight_item)
+        .collect()
+}
+
+fn gauge_weight_item(item: StdResult<(Vec<u8>, Uint128)>) -> StdResult<(Addr, Uint128)> {
+    let (k, v) = item?;
+    Ok((Addr::unchecked(String::from_utf8(k)?), v))
+}
+
+pub fn read_gauge_weights(
+    storage: &dyn Storage,
+    epoch: u64,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Uint128)>> {
+    let start = start_after_bound(start_after.map(|lp_token| lp_token.as_bytes().to_vec()));
+
+    GAUGE_WEIGHTS
+        .prefix(U64Key::new(epoch))
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(gauge_weight_item)
+        .collect()
+}
@@ -0,0 +1,388 @@
+use std::str::FromStr;
+
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Decimal, Env, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::gauge::{
+    ConfigResponse, Cw20HookMsg, EpochResponse, ExecuteMsg, GaugeVote, GaugeWeight,
+    GaugeWeightsResponse, InstantiateMsg, QueryMsg, VoterResponse, VoterWeightResponse,
+};
+use prismswap::staking::{ExecuteMsg as StakingExecuteMsg, PoolAllocPoint};
+
+const EPOCH_LENGTH: u64 = 604800;
+
+fn mock_env_at_epoch(epoch: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(epoch * EPOCH_LENGTH);
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        xprism_token: Addr::unchecked("xprism0000"),
+        staking_contract: Addr::unchecked("staking0000"),
+        epoch_length: EPOCH_LENGTH,
+        start_time: mock_env().block.time.seconds(),
+    }
+}
+
+fn deposit_msg(voter: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: voter.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+    })
+}
+
+fn vote(lp_token: &str, ratio: &str) -> GaugeVote {
+    GaugeVote {
+        lp_token: Addr::unchecked(lp_token),
+        ratio: Decimal::from_str(ratio).unwrap(),
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        epoch_length: 0,
+        ..instantiate_msg()
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("epoch length must be greater than zero")
+    );
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            xprism_token: Addr::unchecked("xprism0000"),
+            staking_contract: Addr::unchecked("staking0000"),
+            epoch_length: EPOCH_LENGTH,
+            start_time: mock_env().block.time.seconds(),
+            last_applied_epoch: None,
+        }
+    );
+
+    let epoch: EpochResponse =
+        from_binary(&query(deps.as_ref(), mock_env_at_epoch(2), QueryMsg::Epoch {}).unwrap())
+            .unwrap();
+    assert_eq!(epoch.epoch, 2);
+    assert_eq!(
+        epoch.start_time,
+        mock_env().block.time.seconds() + 2 * EPOCH_LENGTH
+    );
+    assert_eq!(epoch.end_time, epoch.start_time + EPOCH_LENGTH);
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+}
+
+#[test]
+fn vote_and_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .with_staking_pools(&[&Addr::unchecked("lp0000"), &Addr::unchecked("lp0001")]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    // only xPRISM counts as voting power
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        deposit_msg("addr0000", 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let msg = ExecuteMsg::Vote {
+        votes: vec![vote("lp0000", "1")],
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("no voting power"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("xprism0000", &[]),
+        deposit_msg("addr0000", 100),
+    )
+    .unwrap();
+
+    for (votes, err) in [
+        (vec![vote("lp0002", "1")], "pool lp0002 does not exist"),
+        (
+            vec![vote("lp0000", "0.5"), vote("lp0000", "0.5")],
+            "duplicate vote for lp0000",
+        ),
+        (
+            vec![vote("lp0000", "0.6"), vote("lp0001", "0.6")],
+            "vote ratios must not exceed 1",
+        ),
+    ] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("addr0000", &[]),
+            ExecuteMsg::Vote { votes },
+        );
+        assert_eq!(res.unwrap_err(), StdError::generic_err(err));
+    }
+
+    let msg = ExecuteMsg::Vote {
+        votes: vec![vote("lp0000", "1")],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // voting again in the epoch replaces the previous vote
+    let msg = ExecuteMsg::Vote {
+        votes: vec![vote("lp0000", "0.25"), vote("lp0001", "0.75")],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let res: GaugeWeightsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::GaugeWeights {
+                epoch: 0,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        GaugeWeightsResponse {
+            epoch: 0,
+            total_weight: Uint128::from(100u128),
+            weights: vec![
+                GaugeWeight {
+                    lp_token: Addr::unchecked("lp0000"),
+                    weight: Uint128::from(25u128),
+                },
+                GaugeWeight {
+                    lp_token: Addr::unchecked("lp0001"),
+                    weight: Uint128::from(75u128),
+                },
+            ],
+        }
+    );
+
+    // the deposit backs the vote until the epoch is over
+    let msg = ExecuteMsg::Withdraw {
+        amount: Uint128::from(100u128),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("xPRISM is locked until the end of the epoch voted in")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at_epoch(1),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Withdraw {
+            amount: Uint128::from(101u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("cannot withdraw more than deposited")
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_epoch(1),
+        mock_info("addr0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "xprism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let voter: VoterResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Voter {
+                voter: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(voter.deposit_amount, Uint128::zero());
+    assert_eq!(voter.vote_epoch, Some(0));
+    assert_eq!(voter.voting_power, Uint128::from(100u128));
+
+    // the votes of past epochs are kept
+    let res: VoterWeightResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at_epoch(1),
+            QueryMsg::VoterWeight {
+                voter: Addr::unchecked("addr0000"),
+                epoch: 0,
+                lp_token: Addr::unchecked("lp0001"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        VoterWeightResponse {
+            weight: Uint128::from(75u128),
+            gauge_weight: Uint128::from(75u128),
+        }
+    );
+}
+
+#[test]
+fn checkpoint() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .with_staking_pools(&[&Addr::unchecked("lp0000"), &Addr::unchecked("lp0001")]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    for (voter, amount) in [("addr0000", 300), ("addr0001", 100)] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("xprism0000", &[]),
+            deposit_msg(voter, amount),
+        )
+        .unwrap();
+    }
+
+    let msg = ExecuteMsg::Vote {
+        votes: vec![vote("lp0000", "1")],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Vote {
+        votes: vec![vote("lp0001", "1")],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0001", &[]), msg).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::Checkpoint {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no epoch is over yet"));
+
+    // anyone applies the votes once the epoch is over
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_epoch(1),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::Checkpoint {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "staking0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&StakingExecuteMsg::SetAllocPoints {
+                alloc_points: vec![
+                    PoolAllocPoint {
+                        lp_token: Addr::unchecked("lp0000"),
+                        alloc_point: 7500,
+                    },
+                    PoolAllocPoint {
+                        lp_token: Addr::unchecked("lp0001"),
+                        alloc_point: 2500,
+                    },
+                ],
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at_epoch(1),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::Checkpoint {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("epoch 0 is already applied"));
+
+    // nobody voted in epoch 1, the allocation stays
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at_epoch(2),
+        mock_info("addr0002", &[]),
+        ExecuteMsg::Checkpoint {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.last_applied_epoch, Some(1));
+}
@@ -0,0 +1,219 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_storage_plus::U64Key;
+
+use crate::state::{read_payouts, Config, Payout, CONFIG, NEXT_PAYOUT_ID, PAYOUTS};
+
+use prismswap::asset::Asset;
+use prismswap::insurance::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PayoutResponse, PayoutsResponse, QueryMsg,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        timelock: msg.timelock,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_PAYOUT_ID.save(deps.storage, &1)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::ProposePayout { recipient, asset } => {
+            execute_propose_payout(deps, env, info, recipient, asset)
+        }
+        ExecuteMsg::ExecutePayout { payout_id } => {
+            execute_execute_payout(deps, env, info, payout_id)
+        }
+        ExecuteMsg::CancelPayout { payout_id } => execute_cancel_payout(deps, info, payout_id),
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner can execute it
+pub fn execute_propose_payout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+    asset: Asset,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    deps.api.addr_validate(recipient.as_str())?;
+    if asset.amount.is_zero() {
+        return Err(StdError::generic_err("payout must be greater than zero"));
+    }
+
+    let payout_id = NEXT_PAYOUT_ID.load(deps.storage)?;
+    NEXT_PAYOUT_ID.save(deps.storage, &(payout_id + 1))?;
+    let executable_at = env.block.time.seconds() + config.timelock;
+    PAYOUTS.save(
+        deps.storage,
+        U64Key::new(payout_id),
+        &Payout {
+            recipient: recipient.clone(),
+            asset: asset.clone(),
+            executable_at,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose_payout"),
+        ("payout_id", &payout_id.to_string()),
+        ("recipient", recipient.as_str()),
+        ("asset", &asset.to_string()),
+        ("executable_at", &executable_at.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_execute_payout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    payout_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let payout = load_payout(deps.as_ref(), payout_id)?;
+    if env.block.time.seconds() < payout.executable_at {
+        return Err(StdError::generic_err(format!(
+            "payout {} is timelocked until {}",
+            payout_id, payout.executable_at
+        )));
+    }
+    PAYOUTS.remove(deps.storage, U64Key::new(payout_id));
+
+    Ok(Response::new()
+        .add_message(payout.asset.transfer_msg(&payout.recipient)?)
+        .add_attributes(vec![
+            ("action", "execute_payout"),
+            ("payout_id", &payout_id.to_string()),
+            ("recipient", payout.recipient.as_str()),
+            ("asset", &payout.asset.to_string()),
+        ]))
+}
+
+// Only owner can execute it
+pub fn execute_cancel_payout(
+    deps: DepsMut,
+    info: MessageInfo,
+    payout_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    load_payout(deps.as_ref(), payout_id)?;
+    PAYOUTS.remove(deps.storage, U64Key::new(payout_id));
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "cancel_payout"),
+        ("payout_id", &payout_id.to_string()),
+    ]))
+}
+
+fn load_payout(deps: Deps, payout_id: u64) -> StdResult<Payout> {
+    PAYOUTS
+        .may_load(deps.storage, U64Key::new(payout_id))?
+        .ok_or_else(|| StdError::generic_err(format!("payout {} does not exist", payout_id)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Payout { payout_id } => to_binary(&query_payout(deps, payout_id)?),
+        QueryMsg::Payouts { start_after, limit } => {
+            to_binary(&query_payouts(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        timelock: config.timelock,
+    })
+}
+
+pub fn query_payout(deps: Deps, payout_id: u64) -> StdResult<PayoutResponse> {
+    let payout = load_payout(deps, payout_id)?;
+
+    Ok(payout_response(payout_id, payout))
+}
+
+pub fn query_payouts(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PayoutsResponse> {
+    let payouts = read_payouts(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(payout_id, payout)| payout_response(payout_id, payout))
+        .collect();
+
+    Ok(PayoutsResponse { payouts })
+}
+
+fn payout_response(payout_id: u64, payout: Payout) -> PayoutResponse {
+    PayoutResponse {
+        payout_id,
+        recipient: payout.recipient,
+        asset: payout.asset,
+        executable_at: payout.executable_at,
+    }
+}
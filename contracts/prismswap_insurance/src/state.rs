@@ -0,0 +1,47 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::asset::Asset;
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub timelock: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Payout {
+    pub recipient: Addr,
+    pub asset: Asset,
+    pub executable_at: u64,
+}
+
+/// the payouts proposed and neither executed nor cancelled yet
+pub const PAYOUTS: Map<U64Key, Payout> = Map::new("payouts");
+
+/// the id of the next payout proposed
+pub const NEXT_PAYOUT_ID: Item<u64> = Item::new("next_payout_id");
+
+pub fn read_payouts(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Payout)>> {
+    let start = start_after_id_bound(start_after);
+
+    PAYOUTS
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .collect()
+}
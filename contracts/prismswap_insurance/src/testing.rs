@@ -0,0 +1,222 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{coin, from_binary, Addr, BankMsg, CosmosMsg, Env, StdError, SubMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::insurance::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PayoutResponse, PayoutsResponse, QueryMsg,
+};
+
+const TIMELOCK: u64 = 259200;
+
+fn mock_env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        timelock: TIMELOCK,
+    }
+}
+
+fn propose_payout_msg(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::ProposePayout {
+        recipient: Addr::unchecked("victim0000"),
+        asset: Asset::new(AssetInfo::Native("uusd".to_string()), amount),
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            timelock: TIMELOCK,
+        }
+    );
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+}
+
+#[test]
+fn timelocked_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        propose_payout_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        propose_payout_msg(0),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("payout must be greater than zero")
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        propose_payout_msg(1000),
+    )
+    .unwrap();
+
+    let executable_at = mock_env().block.time.seconds() + TIMELOCK;
+    let payout: PayoutResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Payout { payout_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        payout,
+        PayoutResponse {
+            payout_id: 1,
+            recipient: Addr::unchecked("victim0000"),
+            asset: Asset::new(AssetInfo::Native("uusd".to_string()), 1000u128),
+            executable_at,
+        }
+    );
+
+    let msg = ExecuteMsg::ExecutePayout { payout_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(TIMELOCK - 1),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!("payout 1 is timelocked until {}", executable_at))
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(TIMELOCK),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(TIMELOCK),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "victim0000".to_string(),
+            amount: vec![coin(1000, "uusd")],
+        }))]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(TIMELOCK),
+        mock_info("owner0000", &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("payout 1 does not exist"));
+}
+
+#[test]
+fn cancel_payout() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    for amount in [1000, 2000] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner0000", &[]),
+            propose_payout_msg(amount),
+        )
+        .unwrap();
+    }
+
+    let msg = ExecuteMsg::CancelPayout { payout_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let payouts: PayoutsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Payouts {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        payouts
+            .payouts
+            .iter()
+            .map(|payout| payout.payout_id)
+            .collect::<Vec<u64>>(),
+        vec![2]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(TIMELOCK),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::ExecutePayout { payout_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("payout 1 does not exist"));
+}
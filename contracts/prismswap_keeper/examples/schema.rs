@@ -0,0 +1,25 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use prismswap::keeper::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, JobResponse, JobsResponse, QueryMsg,
+    WorkableResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(JobResponse), &out_dir);
+    export_schema(&schema_for!(JobsResponse), &out_dir);
+    export_schema(&schema_for!(WorkableResponse), &out_dir);
+}
@@ -0,0 +1,348 @@
+use crate::state::{read_all_jobs, read_jobs, Config, Job, CONFIG, JOBS, NEXT_JOB_ID};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::U64Key;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::keeper::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, JobResponse, JobsResponse, QueryMsg,
+    WorkableJob, WorkableResponse,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_JOB_ID.save(deps.storage, &1u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::RegisterJob {
+            name,
+            target,
+            msg,
+            interval,
+            reward,
+        } => execute_register_job(deps, info, name, target, msg, interval, reward),
+        ExecuteMsg::UpdateJob {
+            job_id,
+            msg,
+            interval,
+            reward_amount,
+        } => execute_update_job(deps, info, job_id, msg, interval, reward_amount),
+        ExecuteMsg::RemoveJob { job_id } => execute_remove_job(deps, info, job_id),
+        ExecuteMsg::FundJob { job_id } => {
+            let job = load_job(deps.as_ref(), job_id)?;
+            let amount = match &job.reward.info {
+                AssetInfo::Native(denom) => info
+                    .funds
+                    .iter()
+                    .find(|coin| coin.denom == *denom)
+                    .map(|coin| coin.amount)
+                    .unwrap_or_default(),
+                _ => return Err(StdError::generic_err("invalid reward asset")),
+            };
+
+            execute_fund_job(deps, job_id, job, amount)
+        }
+        ExecuteMsg::Work { job_id } => execute_work(deps, env, info, job_id),
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::FundJob { job_id } => {
+            let job = load_job(deps.as_ref(), job_id)?;
+            if job.reward.info != AssetInfo::Cw20(info.sender) {
+                return Err(StdError::generic_err("invalid reward asset"));
+            }
+
+            execute_fund_job(deps, job_id, job, cw20_msg.amount)
+        }
+    }
+}
+
+// Only owner can execute it
+pub fn execute_register_job(
+    deps: DepsMut,
+    info: MessageInfo,
+    name: String,
+    target: Addr,
+    msg: Binary,
+    interval: u64,
+    reward: Asset,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    assert_interval(interval)?;
+    reward.info.check(deps.api)?;
+
+    let job_id = NEXT_JOB_ID.load(deps.storage)?;
+    NEXT_JOB_ID.save(deps.storage, &(job_id + 1))?;
+    JOBS.save(
+        deps.storage,
+        U64Key::new(job_id),
+        &Job {
+            name: name.clone(),
+            target: deps.api.addr_validate(target.as_str())?,
+            msg,
+            interval,
+            reward,
+            balance: Uint128::zero(),
+            last_worked: None,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_job"),
+        ("job_id", &job_id.to_string()),
+        ("name", &name),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_job(
+    deps: DepsMut,
+    info: MessageInfo,
+    job_id: u64,
+    msg: Option<Binary>,
+    interval: Option<u64>,
+    reward_amount: Option<Uint128>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut job = load_job(deps.as_ref(), job_id)?;
+
+    if let Some(msg) = msg {
+        job.msg = msg;
+    }
+
+    if let Some(interval) = interval {
+        assert_interval(interval)?;
+        job.interval = interval;
+    }
+
+    if let Some(reward_amount) = reward_amount {
+        job.reward.amount = reward_amount;
+    }
+
+    JOBS.save(deps.storage, U64Key::new(job_id), &job)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_job"),
+        ("job_id", &job_id.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_remove_job(deps: DepsMut, info: MessageInfo, job_id: u64) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let job = load_job(deps.as_ref(), job_id)?;
+    JOBS.remove(deps.storage, U64Key::new(job_id));
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !job.balance.is_zero() {
+        messages.push(Asset::new(job.reward.info, job.balance).transfer_msg(&config.owner)?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "remove_job"),
+        ("job_id", &job_id.to_string()),
+    ]))
+}
+
+pub fn execute_fund_job(
+    deps: DepsMut,
+    job_id: u64,
+    mut job: Job,
+    amount: Uint128,
+) -> StdResult<Response> {
+    if amount.is_zero() {
+        return Err(StdError::generic_err("fund amount must be greater than 0"));
+    }
+
+    job.balance += amount;
+    JOBS.save(deps.storage, U64Key::new(job_id), &job)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "fund_job"),
+        ("job_id", &job_id.to_string()),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+pub fn execute_work(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    job_id: u64,
+) -> StdResult<Response> {
+    let mut job = load_job(deps.as_ref(), job_id)?;
+
+    if !job.is_workable(&env.block) {
+        return Err(StdError::generic_err("job not workable"));
+    }
+
+    job.balance -= job.reward.amount;
+    job.last_worked = Some(env.block.time.seconds());
+    JOBS.save(deps.storage, U64Key::new(job_id), &job)?;
+
+    // the reward is only paid when the job executes successfully
+    let mut messages: Vec<CosmosMsg> = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: job.target.to_string(),
+        funds: vec![],
+        msg: job.msg,
+    })];
+    if !job.reward.amount.is_zero() {
+        messages.push(job.reward.transfer_msg(&info.sender)?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "work"),
+        ("job_id", &job_id.to_string()),
+        ("keeper", info.sender.as_str()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_interval(interval: u64) -> StdResult<()> {
+    if interval == 0 {
+        return Err(StdError::generic_err("interval must be greater than 0"));
+    }
+
+    Ok(())
+}
+
+fn load_job(deps: Deps, job_id: u64) -> StdResult<Job> {
+    JOBS.may_load(deps.storage, U64Key::new(job_id))?
+        .ok_or_else(|| StdError::generic_err("job not found"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Job { job_id } => to_binary(&query_job(deps, env, job_id)?),
+        QueryMsg::Jobs { start_after, limit } => {
+            to_binary(&query_jobs(deps, env, start_after, limit)?)
+        }
+        QueryMsg::Workable {} => to_binary(&query_workable(deps, env)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+    })
+}
+
+pub fn query_job(deps: Deps, env: Env, job_id: u64) -> StdResult<JobResponse> {
+    let job = load_job(deps, job_id)?;
+
+    Ok(job_response(&env, job_id, job))
+}
+
+pub fn query_jobs(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<JobsResponse> {
+    let jobs = read_jobs(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(job_id, job)| job_response(&env, job_id, job))
+        .collect();
+
+    Ok(JobsResponse { jobs })
+}
+
+pub fn query_workable(deps: Deps, env: Env) -> StdResult<WorkableResponse> {
+    let jobs = read_all_jobs(deps.storage)?
+        .into_iter()
+        .filter(|(_, job)| job.is_workable(&env.block))
+        .map(|(job_id, job)| WorkableJob {
+            job_id,
+            name: job.name,
+            reward: job.reward,
+        })
+        .collect();
+
+    Ok(WorkableResponse { jobs })
+}
+
+fn job_response(env: &Env, job_id: u64, job: Job) -> JobResponse {
+    JobResponse {
+        job_id,
+        workable: job.is_workable(&env.block),
+        name: job.name,
+        target: job.target,
+        msg: job.msg,
+        interval: job.interval,
+        reward: job.reward,
+        balance: job.balance,
+        last_worked: job.last_worked,
+    }
+}
@@ -0,0 +1,76 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Binary, BlockInfo, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map, U64Key};
+use prismswap::asset::Asset;
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Job {
+    pub name: String,
+    pub target: Addr,
+    /// the execute message sent to the target every time the job is worked
+    pub msg: Binary,
+    pub interval: u64,
+    pub reward: Asset,
+    /// the funds of the reward asset left to pay the keepers
+    pub balance: Uint128,
+    pub last_worked: Option<u64>,
+}
+
+impl Job {
+    /// Returns whether the job is due and funded enough to pay its reward
+    pub fn is_workable(&self, block: &BlockInfo) -> bool {
+        let due = match self.last_worked {
+            Some(last_worked) => last_worked + self.interval <= block.time.seconds(),
+            None => true,
+        };
+
+        due && self.balance >= self.reward.amount
+    }
+}
+
+pub const JOBS: Map<U64Key, Job> = Map::new("jobs");
+
+/// the id of the next job registered
+pub const NEXT_JOB_ID: Item<u64> = Item::new("next_job_id");
+
+pub fn read_all_jobs(storage: &dyn Storage) -> StdResult<Vec<(u64, Job)>> {
+    read_job_range(storage, None, usize::MAX)
+}
+
+pub fn read_jobs(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Job)>> {
+    read_job_range(
+        storage,
+        start_after_id_bound(start_after),
+        calc_limit(limit),
+    )
+}
+
+fn read_job_range(
+    storage: &dyn Storage,
+    start: Option<Bound>,
+    limit: usize,
+) -> StdResult<Vec<(u64, Job)>> {
+    JOBS.range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .collect()
+}
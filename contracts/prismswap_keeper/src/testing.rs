@@ -0,0 +1,327 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Deps, DepsMut, Env, StdError, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::keeper::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, JobResponse, QueryMsg, WorkableJob,
+    WorkableResponse,
+};
+use prismswap::oracle::ExecuteMsg as OracleExecuteMsg;
+
+fn instantiate_keeper(deps: DepsMut) {
+    let _res = instantiate(
+        deps,
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+        },
+    )
+    .unwrap();
+}
+
+/// Registers a job snapshotting the oracle every 600 seconds for 10 uusd
+fn register_job(deps: DepsMut) {
+    let _res = execute(
+        deps,
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RegisterJob {
+            name: "oracle snapshots".to_string(),
+            target: Addr::unchecked("oracle0000"),
+            msg: to_binary(&OracleExecuteMsg::Update {}).unwrap(),
+            interval: 600,
+            reward: Asset::new(AssetInfo::Native("uusd".to_string()), 10u128),
+        },
+    )
+    .unwrap();
+}
+
+fn query_job(deps: Deps, env: Env, job_id: u64) -> JobResponse {
+    from_binary(&query(deps, env, QueryMsg::Job { job_id }).unwrap()).unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_keeper(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: Some(Addr::unchecked("owner0001")),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: Some(Addr::unchecked("owner0001")),
+        },
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0001"),
+        }
+    );
+}
+
+#[test]
+fn register_and_update_job() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_keeper(deps.as_mut());
+
+    let msg = ExecuteMsg::RegisterJob {
+        name: "collector harvests".to_string(),
+        target: Addr::unchecked("collector0000"),
+        msg: Default::default(),
+        interval: 0,
+        reward: Asset::new(AssetInfo::Native("uusd".to_string()), 10u128),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("interval must be greater than 0")
+    );
+
+    register_job(deps.as_mut());
+    assert_eq!(
+        query_job(deps.as_ref(), mock_env(), 1),
+        JobResponse {
+            job_id: 1,
+            name: "oracle snapshots".to_string(),
+            target: Addr::unchecked("oracle0000"),
+            msg: to_binary(&OracleExecuteMsg::Update {}).unwrap(),
+            interval: 600,
+            reward: Asset::new(AssetInfo::Native("uusd".to_string()), 10u128),
+            balance: Uint128::zero(),
+            last_worked: None,
+            workable: false,
+        }
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateJob {
+            job_id: 1,
+            msg: None,
+            interval: Some(3600),
+            reward_amount: Some(Uint128::zero()),
+        },
+    )
+    .unwrap();
+
+    // a job without reward needs no funds
+    let job = query_job(deps.as_ref(), mock_env(), 1);
+    assert_eq!(job.interval, 3600);
+    assert_eq!(job.reward.amount, Uint128::zero());
+    assert!(job.workable);
+}
+
+#[test]
+fn fund_and_remove_job() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_keeper(deps.as_mut());
+    register_job(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::FundJob { job_id: 1 }).unwrap(),
+        }),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid reward asset"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(100, "uluna")]),
+        ExecuteMsg::FundJob { job_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("fund amount must be greater than 0")
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(100, "uusd")]),
+        ExecuteMsg::FundJob { job_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        query_job(deps.as_ref(), mock_env(), 1).balance,
+        Uint128::from(100u128)
+    );
+
+    // jobs rewarded in tokens are funded through their hook
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RegisterJob {
+            name: "vault compounds".to_string(),
+            target: Addr::unchecked("vault0000"),
+            msg: Default::default(),
+            interval: 86400,
+            reward: Asset::new(AssetInfo::Cw20(Addr::unchecked("prism0000")), 50u128),
+        },
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(500u128),
+            msg: to_binary(&Cw20HookMsg::FundJob { job_id: 2 }).unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::RemoveJob { job_id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RemoveJob { job_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "owner0000".to_string(),
+                amount: Uint128::from(500u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::Job { job_id: 2 }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("job not found"));
+}
+
+#[test]
+fn work_job() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_keeper(deps.as_mut());
+    register_job(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Work { job_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("job not workable"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(15, "uusd")]),
+        ExecuteMsg::FundJob { job_id: 1 },
+    )
+    .unwrap();
+
+    let workable: WorkableResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Workable {}).unwrap()).unwrap();
+    assert_eq!(
+        workable.jobs,
+        vec![WorkableJob {
+            job_id: 1,
+            name: "oracle snapshots".to_string(),
+            reward: Asset::new(AssetInfo::Native("uusd".to_string()), 10u128),
+        }]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Work { job_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "oracle0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&OracleExecuteMsg::Update {}).unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "keeper0000".to_string(),
+                amount: vec![coin(10, "uusd")],
+            })),
+        ]
+    );
+
+    // the job is not due again before its interval
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Work { job_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("job not workable"));
+
+    // and no longer workable once its funds cannot pay the reward
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(600);
+    let job = query_job(deps.as_ref(), env.clone(), 1);
+    assert_eq!(job.balance, Uint128::from(5u128));
+    assert_eq!(job.last_worked, Some(mock_env().block.time.seconds()));
+    assert!(!job.workable);
+
+    let workable: WorkableResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::Workable {}).unwrap()).unwrap();
+    assert_eq!(workable.jobs, vec![]);
+}
@@ -0,0 +1,781 @@
+use crate::error::ContractError;
+use crate::math::pow;
+use crate::state::{Config, CONFIG, GRADUATED_PAIR, PURCHASED, REGISTERED};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg,
+};
+
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::PairInfo;
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::events::SwapEvent;
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::fee::{SwapBreakdown, MAX_TOTAL_FEE};
+use prismswap::lbp::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg,
+};
+use prismswap::pair::{ExecuteMsg as PairExecuteMsg, SimulationResponse};
+use prismswap::querier::{query_pair_info, query_supply};
+use prismswap::sale::{ParticipantResponse, Whitelist};
+use prismswap::tax::TaxInfo;
+use std::str::FromStr;
+
+const GRADUATE_REPLY_ID: u64 = 1;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    msg.asset_infos[0].check(deps.api)?;
+    msg.asset_infos[1].check(deps.api)?;
+
+    if msg.asset_infos[0] == msg.asset_infos[1] {
+        return Err(ContractError::DoublingAssets {});
+    }
+
+    if msg
+        .start_weights
+        .iter()
+        .chain(msg.end_weights.iter())
+        .any(|weight| weight.is_zero())
+    {
+        return Err(ContractError::InvalidWeights {});
+    }
+
+    if msg.start_time >= msg.end_time {
+        return Err(ContractError::InvalidSchedule {});
+    }
+
+    if msg.commission_rate > Decimal::from_str(MAX_TOTAL_FEE)? {
+        return Err(ContractError::InvalidCommissionRate {});
+    }
+
+    let whitelist = match msg.whitelist {
+        Some(Whitelist::Addresses { addresses }) => Some(Whitelist::Addresses {
+            addresses: addresses
+                .iter()
+                .map(|address| deps.api.addr_validate(address.as_str()))
+                .collect::<StdResult<Vec<Addr>>>()?,
+        }),
+        Some(whitelist) => {
+            whitelist.validate()?;
+            Some(whitelist)
+        }
+        None => None,
+    };
+
+    if let Some(purchase_cap) = &msg.purchase_cap {
+        if !msg.asset_infos.contains(&purchase_cap.info) {
+            return Err(ContractError::AssetMismatch {});
+        }
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        asset_infos: msg.asset_infos,
+        start_weights: msg.start_weights,
+        end_weights: msg.end_weights,
+        start_time: msg.start_time,
+        end_time: msg.end_time,
+        commission_rate: msg.commission_rate,
+        whitelist,
+        purchase_cap: msg.purchase_cap,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::ProvideLiquidity { assets } => {
+            assets[0].info.check(deps.api)?;
+            assets[1].info.check(deps.api)?;
+            provide_liquidity(deps, env, info, assets)
+        }
+        ExecuteMsg::Swap {
+            offer_asset,
+            belief_price,
+            max_spread,
+            to,
+        } => {
+            offer_asset.info.check(deps.api)?;
+            if !offer_asset.info.is_native_token() {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(deps.api.addr_validate(&to_addr)?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info.clone(),
+                info.sender,
+                offer_asset,
+                belief_price,
+                max_spread,
+                to_addr,
+            )
+        }
+        ExecuteMsg::WithdrawLiquidity {} => withdraw_liquidity(deps, env, info),
+        ExecuteMsg::Graduate {} => graduate(deps, env, info),
+        ExecuteMsg::Register { proof } => register(deps, info, proof),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let contract_addr = info.sender.clone();
+
+    match from_binary(&cw20_msg.msg) {
+        Ok(Cw20HookMsg::Swap {
+            belief_price,
+            max_spread,
+            to,
+        }) => {
+            // only asset contract can execute this message
+            let config: Config = CONFIG.load(deps.storage)?;
+            if !config
+                .asset_infos
+                .contains(&AssetInfo::Cw20(contract_addr.clone()))
+            {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            let to_addr = if let Some(to_addr) = to {
+                Some(deps.api.addr_validate(to_addr.as_str())?)
+            } else {
+                None
+            };
+
+            swap(
+                deps,
+                env,
+                info,
+                Addr::unchecked(cw20_msg.sender),
+                Asset {
+                    info: AssetInfo::Cw20(contract_addr),
+                    amount: cw20_msg.amount,
+                },
+                belief_price,
+                max_spread,
+                to_addr,
+            )
+        }
+        Err(err) => Err(ContractError::Std(err)),
+    }
+}
+
+// Only owner can execute it
+/// CONTRACT - should approve contract to use the amount of token
+pub fn provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: [Asset; 2],
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() >= config.start_time {
+        return Err(ContractError::AlreadyStarted {});
+    }
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for asset in assets.iter() {
+        if !config.asset_infos.contains(&asset.info) {
+            return Err(ContractError::AssetMismatch {});
+        }
+        asset.assert_sent_native_token_balance(&info)?;
+
+        // If the pool is token contract, then we need to execute TransferFrom msg to receive funds
+        if let AssetInfo::Cw20(contract_addr) = &asset.info {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: info.sender.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: asset.amount,
+                })?,
+                funds: vec![],
+            }));
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "provide_liquidity"),
+        ("sender", info.sender.as_str()),
+        ("assets", &format!("{}, {}", assets[0], assets[1])),
+    ]))
+}
+
+// Only owner can execute it
+pub fn withdraw_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() < config.end_time {
+        return Err(ContractError::NotOver {});
+    }
+
+    let pools = query_pools(deps.as_ref(), &config, &env.contract.address)?;
+    let mut messages: Vec<CosmosMsg> = vec![];
+    for pool in pools.iter() {
+        if !pool.amount.is_zero() {
+            messages.push(pool.transfer_msg(&config.owner)?);
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "withdraw_liquidity"),
+        ("sender", info.sender.as_str()),
+        ("refund_assets", &format!("{}, {}", pools[0], pools[1])),
+    ]))
+}
+
+// Only owner can execute it
+pub fn graduate(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if env.block.time.seconds() < config.end_time {
+        return Err(ContractError::NotOver {});
+    }
+
+    if GRADUATED_PAIR.may_load(deps.storage)?.is_some() {
+        return Err(ContractError::AlreadyGraduated {});
+    }
+
+    match query_pair_info(&deps.querier, &config.factory, &config.asset_infos) {
+        Ok(pair_info) => seed_pair(deps, env, config, pair_info),
+        // create the pair first, seeding it once created
+        Err(_) => Ok(Response::new()
+            .add_submessage(SubMsg::reply_on_success(
+                WasmMsg::Execute {
+                    contract_addr: config.factory.to_string(),
+                    msg: to_binary(&FactoryExecuteMsg::CreatePair {
+                        asset_infos: config.asset_infos.clone(),
+                        fee_config: None,
+                    })?,
+                    funds: vec![],
+                },
+                GRADUATE_REPLY_ID,
+            ))
+            .add_attribute("action", "create_graduation_pair")),
+    }
+}
+
+/// Seeds the pair with the whole pool at its price by the end weights, the part of the pool
+/// exceeding that price being sent to the owner with the LP tokens
+fn seed_pair(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    pair_info: PairInfo,
+) -> Result<Response, ContractError> {
+    // the first provide sets the price of the pair
+    if !query_supply(&deps.querier, &pair_info.liquidity_token)?.is_zero() {
+        return Err(ContractError::PairNotEmpty {});
+    }
+
+    let pools = query_pools(deps.as_ref(), &config, &env.contract.address)?;
+    let weights: [Decimal256; 2] = [config.end_weights[0].into(), config.end_weights[1].into()];
+
+    // price0 = (pool1 / weight1) / (pool0 / weight0), so the pool 1 deposit is pool0 * price0
+    let (amount0, amount1): (Uint128, Uint128) = if weights[0] <= weights[1] {
+        (
+            pools[0].amount,
+            (Uint256::from(pools[1].amount) * weights[0] / weights[1]).into(),
+        )
+    } else {
+        (
+            (Uint256::from(pools[0].amount) * weights[1] / weights[0]).into(),
+            pools[1].amount,
+        )
+    };
+    let mut amounts = [amount0, amount1];
+
+    // the tax of a native deposit is charged on top of it, both deposits being scaled down at
+    // the same price until each fits in its pool net of the tax
+    let denoms: Vec<String> = pools
+        .iter()
+        .filter_map(|pool| match &pool.info {
+            AssetInfo::Native(denom) => Some(denom.to_string()),
+            AssetInfo::Cw20(_) => None,
+        })
+        .collect();
+    let tax_info = TaxInfo::query(&deps.querier, &denoms)?;
+    for i in 0..2 {
+        if let AssetInfo::Native(denom) = &pools[i].info {
+            let net_amount = tax_info.deduct_tax(&Coin {
+                denom: denom.to_string(),
+                amount: pools[i].amount,
+            })?;
+            if amounts[i] > net_amount {
+                amounts[1 - i] = amounts[1 - i].multiply_ratio(net_amount, amounts[i]);
+                amounts[i] = net_amount;
+            }
+        }
+    }
+    let deposits = [
+        Asset::new(pools[0].info.clone(), amounts[0]),
+        Asset::new(pools[1].info.clone(), amounts[1]),
+    ];
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for deposit in deposits.iter() {
+        match &deposit.info {
+            AssetInfo::Cw20(contract_addr) => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: pair_info.contract_addr.to_string(),
+                    amount: deposit.amount,
+                    expires: None,
+                })?,
+                funds: vec![],
+            })),
+            AssetInfo::Native(denom) => funds.push(Coin {
+                denom: denom.to_string(),
+                amount: deposit.amount,
+            }),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair_info.contract_addr.to_string(),
+        msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets: deposits.clone(),
+            slippage_tolerance: None,
+            receiver: Some(config.owner.to_string()),
+            auto_stake: None,
+        })?,
+        funds,
+    }));
+
+    // the rest of a native pool pays the tax of its deposit and of its own transfer
+    for (pool, deposit) in pools.iter().zip(deposits.iter()) {
+        let spent = match &deposit.info {
+            AssetInfo::Native(denom) => tax_info.add_tax(&Coin {
+                denom: denom.to_string(),
+                amount: deposit.amount,
+            })?,
+            AssetInfo::Cw20(_) => deposit.amount,
+        };
+        messages.extend(
+            Asset::new(pool.info.clone(), pool.amount.saturating_sub(spent))
+                .into_transfer_msg_with_tax(&deps.querier, &config.owner)?,
+        );
+    }
+
+    GRADUATED_PAIR.save(deps.storage, &pair_info.contract_addr)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "graduate"),
+        ("pair", pair_info.contract_addr.as_str()),
+        ("assets", &format!("{}, {}", deposits[0], deposits[1])),
+    ]))
+}
+
+/// Seeds the pair created by the factory for the graduation
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    if msg.id != GRADUATE_REPLY_ID {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pair_info = query_pair_info(&deps.querier, &config.factory, &config.asset_infos)?;
+
+    seed_pair(deps, env, config, pair_info)
+}
+
+pub fn register(
+    deps: DepsMut,
+    info: MessageInfo,
+    proof: Vec<String>,
+) -> Result<Response, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    match &config.whitelist {
+        Some(whitelist @ Whitelist::MerkleRoot { .. }) => {
+            if !whitelist.verify(&info.sender, &proof)? {
+                return Err(ContractError::InvalidProof {});
+            }
+        }
+        _ => return Err(ContractError::NoMerkleRoot {}),
+    }
+
+    REGISTERED.save(deps.storage, &info.sender, &true)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register"),
+        ("address", info.sender.as_str()),
+    ]))
+}
+
+fn is_whitelisted(deps: Deps, config: &Config, address: &Addr) -> bool {
+    match &config.whitelist {
+        None => true,
+        Some(Whitelist::Addresses { addresses }) => addresses.contains(address),
+        Some(Whitelist::MerkleRoot { .. }) => REGISTERED.has(deps.storage, address),
+    }
+}
+
+/// Adds `asset` to the purchases of `buyer`, if it is the capped asset
+fn record_purchase(
+    deps: DepsMut,
+    config: &Config,
+    buyer: &Addr,
+    asset: &Asset,
+) -> Result<(), ContractError> {
+    if let Some(purchase_cap) = &config.purchase_cap {
+        if purchase_cap.info == asset.info {
+            let purchased = PURCHASED
+                .may_load(deps.storage, buyer)?
+                .unwrap_or_default()
+                .checked_add(asset.amount)?;
+            if purchased > purchase_cap.amount {
+                return Err(ContractError::PurchaseCapExceeded {});
+            }
+            PURCHASED.save(deps.storage, buyer, &purchased)?;
+        }
+    }
+    Ok(())
+}
+
+// CONTRACT - a user must do token approval
+#[allow(clippy::too_many_arguments)]
+pub fn swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    offer_asset: Asset,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<Addr>,
+) -> Result<Response, ContractError> {
+    offer_asset.assert_sent_native_token_balance(&info)?;
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    if now < config.start_time || now >= config.end_time {
+        return Err(ContractError::NotActive {});
+    }
+
+    if !is_whitelisted(deps.as_ref(), &config, &sender) {
+        return Err(ContractError::NotWhitelisted {});
+    }
+
+    let pools = query_pools(deps.as_ref(), &config, &env.contract.address)?;
+    let weights = config.weights(now);
+
+    // If the asset balance is already increased
+    // To calculated properly we should subtract user deposit from the pool
+    let (offer_pool, offer_weight, ask_pool, ask_weight) = if offer_asset.info.eq(&pools[0].info) {
+        (
+            pools[0].amount.checked_sub(offer_asset.amount)?,
+            weights[0],
+            pools[1].clone(),
+            weights[1],
+        )
+    } else if offer_asset.info.eq(&pools[1].info) {
+        (
+            pools[1].amount.checked_sub(offer_asset.amount)?,
+            weights[1],
+            pools[0].clone(),
+            weights[0],
+        )
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    };
+
+    let offer_amount = offer_asset.amount;
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        offer_pool,
+        offer_weight,
+        ask_pool.amount,
+        ask_weight,
+        offer_amount,
+        config.commission_rate,
+    )?;
+
+    // check max spread limit if exist
+    assert_max_spread(
+        belief_price,
+        max_spread,
+        offer_amount,
+        return_amount + commission_amount,
+        spread_amount,
+    )?;
+
+    let return_asset = Asset {
+        info: ask_pool.info.clone(),
+        amount: return_amount,
+    };
+    record_purchase(deps, &config, &sender, &return_asset)?;
+    let receiver = to.unwrap_or_else(|| sender.clone());
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !return_amount.is_zero() {
+        messages.push(return_asset.transfer_msg(&receiver)?);
+    }
+
+    let event = SwapEvent {
+        sender: sender.as_str(),
+        receiver: receiver.as_str(),
+        offer_asset: &offer_asset.info,
+        ask_asset: &ask_pool.info,
+        offer_amount,
+        return_amount,
+        spread_amount,
+        commission_amount,
+    };
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(event.attributes()))
+}
+
+fn query_pools(deps: Deps, config: &Config, contract_addr: &Addr) -> StdResult<[Asset; 2]> {
+    Ok([
+        Asset {
+            amount: config.asset_infos[0].query_pool(&deps.querier, contract_addr)?,
+            info: config.asset_infos[0].clone(),
+        },
+        Asset {
+            amount: config.asset_infos[1].query_pool(&deps.querier, contract_addr)?,
+            info: config.asset_infos[1].clone(),
+        },
+    ])
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    match msg {
+        QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
+        QueryMsg::Participant { address } => Ok(to_binary(&query_participant(deps, address)?)?),
+        QueryMsg::Pool {} => Ok(to_binary(&query_pool(deps, env)?)?),
+        QueryMsg::Simulation { offer_asset } => {
+            Ok(to_binary(&query_simulation(deps, env, offer_asset)?)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        asset_infos: config.asset_infos,
+        start_weights: config.start_weights,
+        end_weights: config.end_weights,
+        start_time: config.start_time,
+        end_time: config.end_time,
+        commission_rate: config.commission_rate,
+        whitelist: config.whitelist,
+        purchase_cap: config.purchase_cap,
+        graduated_pair: GRADUATED_PAIR.may_load(deps.storage)?,
+    })
+}
+
+pub fn query_participant(
+    deps: Deps,
+    address: String,
+) -> Result<ParticipantResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(ParticipantResponse {
+        whitelisted: is_whitelisted(deps, &config, &address),
+        purchased: PURCHASED
+            .may_load(deps.storage, &address)?
+            .unwrap_or_default(),
+        address,
+    })
+}
+
+pub fn query_pool(deps: Deps, env: Env) -> Result<PoolResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(PoolResponse {
+        assets: query_pools(deps, &config, &env.contract.address)?,
+        weights: config.weights(env.block.time.seconds()),
+    })
+}
+
+pub fn query_simulation(
+    deps: Deps,
+    env: Env,
+    offer_asset: Asset,
+) -> Result<SimulationResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let pools = query_pools(deps, &config, &env.contract.address)?;
+    let weights = config.weights(env.block.time.seconds());
+
+    let (offer_index, ask_index) = if offer_asset.info.eq(&pools[0].info) {
+        (0, 1)
+    } else if offer_asset.info.eq(&pools[1].info) {
+        (1, 0)
+    } else {
+        return Err(ContractError::AssetMismatch {});
+    };
+
+    let (return_amount, spread_amount, commission_amount) = compute_swap(
+        pools[offer_index].amount,
+        weights[offer_index],
+        pools[ask_index].amount,
+        weights[ask_index],
+        offer_asset.amount,
+        config.commission_rate,
+    )?;
+
+    Ok(SimulationResponse {
+        return_amount,
+        spread_amount,
+        commission_amount,
+        breakdown: SwapBreakdown {
+            return_amount,
+            spread_amount,
+            commission_amount,
+            ..SwapBreakdown::default()
+        },
+    })
+}
+
+fn compute_swap(
+    offer_pool: Uint128,
+    offer_weight: Decimal,
+    ask_pool: Uint128,
+    ask_weight: Decimal,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> Result<(Uint128, Uint128, Uint128), ContractError> {
+    // the pool price is undefined
+    if offer_pool.is_zero() {
+        return Err(ContractError::InsufficientLiquidity {});
+    }
+
+    // keep the base of the power close enough to 1 for its approximation
+    if offer_amount > offer_pool.multiply_ratio(1u128, 2u128) {
+        return Err(ContractError::MaxInRatio {});
+    }
+
+    let offer_pool: Uint256 = offer_pool.into();
+    let ask_pool: Uint256 = ask_pool.into();
+    let offer_amount: Uint256 = offer_amount.into();
+    let offer_weight: Decimal256 = offer_weight.into();
+    let ask_weight: Decimal256 = ask_weight.into();
+    let commission_rate: Decimal256 = commission_rate.into();
+
+    // offer => ask
+    // ask_amount = ask_pool * (1 - (offer_pool / (offer_pool + offer_amount)) ^ (offer_weight / ask_weight))
+    let ratio = Decimal256::from_ratio(offer_pool, offer_pool + offer_amount);
+    let return_amount: Uint256 =
+        ask_pool * (Decimal256::one() - pow(ratio, offer_weight / ask_weight));
+
+    // calculate spread & commission, against the spot price of the weighted pool
+    // the offer ratio is at most a half, keeping the products within 256 bits
+    let spot_return: Uint256 =
+        ask_pool * Decimal256::from_ratio(offer_amount, offer_pool) * offer_weight / ask_weight;
+    let spread_amount: Uint256 = if spot_return > return_amount {
+        spot_return - return_amount
+    } else {
+        Uint256::zero()
+    };
+    let commission_amount: Uint256 = return_amount * commission_rate;
+
+    // commission will be absorbed to pool
+    let return_amount: Uint256 = return_amount - commission_amount;
+    Ok((
+        return_amount.into(),
+        // the spread of lopsided weights can outgrow the pools
+        std::cmp::min(spread_amount, Uint256::from(u128::MAX)).into(),
+        commission_amount.into(),
+    ))
+}
+
+/// If `belief_price` and `max_spread` both are given,
+/// we compute new spread else we just use swap
+/// spread to check `max_spread`
+pub fn assert_max_spread(
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    offer_amount: Uint128,
+    return_amount: Uint128,
+    spread_amount: Uint128,
+) -> Result<(), ContractError> {
+    let offer_amount: Uint256 = offer_amount.into();
+    let return_amount: Uint256 = return_amount.into();
+    let spread_amount: Uint256 = spread_amount.into();
+
+    if let (Some(max_spread), Some(belief_price)) = (max_spread, belief_price) {
+        let belief_price: Decimal256 = belief_price.into();
+        let max_spread: Decimal256 = max_spread.into();
+
+        let expected_return = offer_amount / belief_price;
+        let spread_amount = if expected_return > return_amount {
+            expected_return - return_amount
+        } else {
+            Uint256::zero()
+        };
+
+        if return_amount < expected_return
+            && Decimal256::from_ratio(spread_amount, expected_return) > max_spread
+        {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    } else if let Some(max_spread) = max_spread {
+        let max_spread: Decimal256 = max_spread.into();
+        if Decimal256::from_ratio(spread_amount, return_amount + spread_amount) > max_spread {
+            return Err(ContractError::MaxSpreadAssertion {});
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use cosmwasm_std::{OverflowError, StdError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Sender is not whitelisted")]
+    NotWhitelisted {},
+
+    #[error("Whitelist is not a merkle root")]
+    NoMerkleRoot {},
+
+    #[error("Invalid merkle proof")]
+    InvalidProof {},
+
+    #[error("Purchase exceeds the purchase cap")]
+    PurchaseCapExceeded {},
+
+    #[error("Max spread assertion")]
+    MaxSpreadAssertion {},
+
+    #[error("Asset mismatch")]
+    AssetMismatch {},
+
+    #[error("Doubling assets in asset infos")]
+    DoublingAssets {},
+
+    #[error("Weights must be greater than zero")]
+    InvalidWeights {},
+
+    #[error("LBP must end after it starts")]
+    InvalidSchedule {},
+
+    #[error("Commission rate must not exceed the maximum total fee")]
+    InvalidCommissionRate {},
+
+    #[error("LBP has already started")]
+    AlreadyStarted {},
+
+    #[error("LBP is not active")]
+    NotActive {},
+
+    #[error("LBP is not over")]
+    NotOver {},
+
+    #[error("Offer amount must not exceed half the offer pool")]
+    MaxInRatio {},
+
+    #[error("Not enough liquidity in the pool")]
+    InsufficientLiquidity {},
+
+    #[error("LBP has already graduated")]
+    AlreadyGraduated {},
+
+    #[error("Pair to graduate into already has liquidity")]
+    PairNotEmpty {},
+}
@@ -0,0 +1,11 @@
+pub mod contract;
+pub mod state;
+
+mod error;
+mod math;
+
+#[cfg(test)]
+mod testing;
+
+#[cfg(test)]
+mod mock_querier;
@@ -0,0 +1,77 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+
+/// the error tolerated on the fractional part of a power
+const POW_PRECISION: u64 = 10_000_000_000;
+/// the most terms of the binomial series summed for the fractional part of a power
+const POW_MAX_ITERATIONS: u64 = 100;
+
+/// `base` raised to `exp`, the integer part of the exponent computed exactly and the
+/// fractional part approximated with the binomial series, accurate for a base around 1
+pub fn pow(base: Decimal256, exp: Decimal256) -> Decimal256 {
+    let whole: Uint256 = Uint256::one() * exp;
+    let remain = exp - Decimal256::from_uint256(whole);
+
+    let whole_pow = powi(base, whole);
+    if remain.is_zero() {
+        return whole_pow;
+    }
+
+    whole_pow * pow_approx(base, remain)
+}
+
+fn powi(base: Decimal256, exp: Uint256) -> Decimal256 {
+    let mut exp = u128::from(exp);
+    let mut base = base;
+    let mut result = Decimal256::one();
+    while exp > 0 {
+        if exp % 2 == 1 {
+            result = result * base;
+        }
+        base = base * base;
+        exp /= 2;
+    }
+    result
+}
+
+/// (1 + x)^exp = 1 + exp x + exp (exp - 1) / 2! x^2 + ...
+fn pow_approx(base: Decimal256, exp: Decimal256) -> Decimal256 {
+    let precision = Decimal256::from_ratio(1, POW_PRECISION);
+    let (x, x_neg) = if base >= Decimal256::one() {
+        (base - Decimal256::one(), false)
+    } else {
+        (Decimal256::one() - base, true)
+    };
+
+    let mut term = Decimal256::one();
+    let mut sum = Decimal256::one();
+    let mut negative = false;
+    for k in 1..=POW_MAX_ITERATIONS {
+        let k_minus_one = Decimal256::from_uint256(k - 1);
+        let (c, c_neg) = if exp >= k_minus_one {
+            (exp - k_minus_one, false)
+        } else {
+            (k_minus_one - exp, true)
+        };
+        term = term * c * x / Decimal256::from_uint256(k);
+        if term.is_zero() {
+            break;
+        }
+
+        if x_neg {
+            negative = !negative;
+        }
+        if c_neg {
+            negative = !negative;
+        }
+        if negative {
+            sum = sum - term;
+        } else {
+            sum += term;
+        }
+
+        if term < precision {
+            break;
+        }
+    }
+    sum
+}
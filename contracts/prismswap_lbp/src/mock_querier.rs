@@ -0,0 +1,161 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use prismswap::asset::{AssetInfo, PairInfo};
+use prismswap::factory::QueryMsg as FactoryQueryMsg;
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    token_supplies: HashMap<String, Uint128>,
+    pairs: Vec<[AssetInfo; 2]>,
+    tax_rate: Decimal,
+    tax_caps: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match &request {
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data })
+                if route == &TerraRoute::Treasury =>
+            {
+                match query_data {
+                    TerraQuery::TaxRate {} => {
+                        let res = TaxRateResponse {
+                            rate: self.tax_rate,
+                        };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    TerraQuery::TaxCap { denom } => {
+                        let cap = self.tax_caps.get(denom).copied().unwrap_or_default();
+                        let res = TaxCapResponse { cap };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<FactoryQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::Pair { asset_infos } => {
+                        let exists = self.pairs.iter().any(|pair| {
+                            pair[0] == asset_infos[0] && pair[1] == asset_infos[1]
+                                || pair[0] == asset_infos[1] && pair[1] == asset_infos[0]
+                        });
+                        if !exists {
+                            return SystemResult::Ok(ContractResult::Err(
+                                "No pair info exists".to_string(),
+                            ));
+                        }
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&PairInfo {
+                                asset_infos,
+                                contract_addr: Addr::unchecked("pair0000"),
+                                liquidity_token: Addr::unchecked("liquidity0000"),
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => {
+                        let total_supply = self
+                            .token_supplies
+                            .get(contract_addr)
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&TokenInfoResponse {
+                                name: "LP Token".to_string(),
+                                symbol: "uLP".to_string(),
+                                decimals: 6,
+                                total_supply,
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_supplies: HashMap::new(),
+            pairs: vec![],
+            tax_rate: Decimal::zero(),
+            tax_caps: HashMap::new(),
+        }
+    }
+
+    pub fn with_balance(&mut self, balances: &[(&String, Vec<Coin>)]) {
+        for (addr, balance) in balances {
+            self.base.update_balance(addr.to_string(), balance.clone());
+        }
+    }
+
+    pub fn with_token_supplies(&mut self, supplies: &[(&String, &Uint128)]) {
+        self.token_supplies = supplies
+            .iter()
+            .map(|(contract_addr, supply)| (contract_addr.to_string(), **supply))
+            .collect();
+    }
+
+    pub fn with_pairs(&mut self, pairs: &[[AssetInfo; 2]]) {
+        self.pairs = pairs.to_vec();
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&str, Uint128)]) {
+        self.tax_rate = rate;
+        self.tax_caps = caps
+            .iter()
+            .map(|(denom, cap)| (denom.to_string(), *cap))
+            .collect();
+    }
+}
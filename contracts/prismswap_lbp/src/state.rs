@@ -0,0 +1,62 @@
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw_storage_plus::{Item, Map};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::sale::Whitelist;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// the pair the pool graduated into
+pub const GRADUATED_PAIR: Item<Addr> = Item::new("graduated_pair");
+
+/// the addresses that proved their leaf in the merkle tree of the whitelist
+pub const REGISTERED: Map<&Addr, bool> = Map::new("registered");
+
+/// the amount of the capped asset bought by every address
+pub const PURCHASED: Map<&Addr, Uint128> = Map::new("purchased");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    /// the project providing the liquidity and withdrawing the proceeds
+    pub owner: Addr,
+    /// the factory the pool graduates into a pair of
+    pub factory: Addr,
+    /// the type of asset infos available in [`AssetInfo`]
+    pub asset_infos: [AssetInfo; 2],
+    pub start_weights: [Decimal; 2],
+    pub end_weights: [Decimal; 2],
+    pub start_time: u64,
+    pub end_time: u64,
+    pub commission_rate: Decimal,
+    pub whitelist: Option<Whitelist>,
+    pub purchase_cap: Option<Asset>,
+}
+
+impl Config {
+    /// the weights of the assets at `time`, shifting linearly from the start weights to the end
+    /// weights
+    pub fn weights(&self, time: u64) -> [Decimal; 2] {
+        if time <= self.start_time {
+            return self.start_weights;
+        }
+        if time >= self.end_time {
+            return self.end_weights;
+        }
+
+        let progress =
+            Decimal256::from_ratio(time - self.start_time, self.end_time - self.start_time);
+        let weight = |i: usize| {
+            let start: Decimal256 = self.start_weights[i].into();
+            let end: Decimal256 = self.end_weights[i].into();
+            let weight = if end >= start {
+                start + (end - start) * progress
+            } else {
+                start - (start - end) * progress
+            };
+            weight.into()
+        };
+        [weight(0), weight(1)]
+    }
+}
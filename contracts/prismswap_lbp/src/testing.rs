@@ -0,0 +1,699 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use crate::math::pow;
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_bignumber::Decimal256;
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coin, from_binary, to_binary, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Decimal,
+    DepsMut, Env, Reply, StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::factory::ExecuteMsg as FactoryExecuteMsg;
+use prismswap::fee::SwapBreakdown;
+use prismswap::lbp::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg,
+};
+use prismswap::pair::{ExecuteMsg as PairExecuteMsg, SimulationResponse};
+use prismswap::sale::{ParticipantResponse, Whitelist};
+use std::str::FromStr;
+
+const START: u64 = 100;
+const END: u64 = 1100;
+
+fn mock_env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    let now = mock_env().block.time.seconds();
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        factory: Addr::unchecked("factory0000"),
+        asset_infos: [
+            AssetInfo::Native("ulaunch".to_string()),
+            AssetInfo::Native("uusd".to_string()),
+        ],
+        start_weights: [
+            Decimal::from_str("0.96").unwrap(),
+            Decimal::from_str("0.04").unwrap(),
+        ],
+        end_weights: [
+            Decimal::from_str("0.5").unwrap(),
+            Decimal::from_str("0.5").unwrap(),
+        ],
+        start_time: now + START,
+        end_time: now + END,
+        commission_rate: Decimal::from_str("0.003").unwrap(),
+        whitelist: None,
+        purchase_cap: None,
+    }
+}
+
+fn swap_msg(amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Swap {
+        offer_asset: Asset::new(AssetInfo::Native("uusd".to_string()), amount),
+        belief_price: None,
+        max_spread: None,
+        to: None,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.asset_infos[1] = AssetInfo::Native("ulaunch".to_string());
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::DoublingAssets {});
+
+    let mut msg = instantiate_msg();
+    msg.end_weights[0] = Decimal::zero();
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidWeights {});
+
+    let mut msg = instantiate_msg();
+    msg.end_time = msg.start_time;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidSchedule {});
+
+    let mut msg = instantiate_msg();
+    msg.commission_rate = Decimal::from_str("0.06").unwrap();
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidCommissionRate {});
+
+    let msg = instantiate_msg();
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: msg.owner,
+            factory: msg.factory,
+            asset_infos: msg.asset_infos,
+            start_weights: msg.start_weights,
+            end_weights: msg.end_weights,
+            start_time: msg.start_time,
+            end_time: msg.end_time,
+            commission_rate: msg.commission_rate,
+            whitelist: msg.whitelist,
+            purchase_cap: msg.purchase_cap,
+            graduated_pair: None,
+        }
+    );
+}
+
+#[test]
+fn provide_liquidity() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.asset_infos[0] = AssetInfo::Cw20(Addr::unchecked("launch0000"));
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: [
+            Asset::new(
+                AssetInfo::Cw20(Addr::unchecked("launch0000")),
+                96_000_000u128,
+            ),
+            Asset::new(AssetInfo::Native("uusd".to_string()), 4_000_000u128),
+        ],
+    };
+    let funds = [coin(4_000_000, "uusd")];
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &funds),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(START),
+        mock_info("owner0000", &funds),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyStarted {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        ))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &funds),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "launch0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: "owner0000".to_string(),
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::from(96_000_000u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // only the assets of the pool can be swapped
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::Swap {
+            belief_price: None,
+            max_spread: None,
+            to: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(START),
+        mock_info("token0000", &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn swap() {
+    let mut deps = mock_dependencies(&[coin(96_000_000, "ulaunch"), Coin::new(4_001_000, "uusd")]);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let funds = [coin(1000, "uusd")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(START - 1),
+        mock_info("addr0000", &funds),
+        swap_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotActive {});
+
+    // at the start, 1 ulaunch is worth 1 uusd
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(START),
+        mock_info("addr0000", &funds),
+        swap_msg(1000),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(997, "ulaunch")],
+        }))]
+    );
+    assert_eq!(res.attributes[7], attr("spread_amount", "1"));
+    assert_eq!(res.attributes[8], attr("commission_amount", "2"));
+
+    // halfway, the weights moved and the price of ulaunch dropped
+    deps.querier.with_balance(&[(
+        &MOCK_CONTRACT_ADDR.to_string(),
+        vec![coin(96_000_000, "ulaunch"), coin(4_000_000, "uusd")],
+    )]);
+    let pool: PoolResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(START + 500),
+            QueryMsg::Pool {},
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        pool.weights,
+        [
+            Decimal::from_str("0.73").unwrap(),
+            Decimal::from_str("0.27").unwrap(),
+        ]
+    );
+
+    let simulation: SimulationResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(START + 500),
+            QueryMsg::Simulation {
+                offer_asset: Asset::new(AssetInfo::Native("uusd".to_string()), 1000u128),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::from(8849u128),
+            spread_amount: Uint128::from(1u128),
+            commission_amount: Uint128::from(26u128),
+            breakdown: SwapBreakdown {
+                return_amount: Uint128::from(8849u128),
+                spread_amount: Uint128::from(1u128),
+                commission_amount: Uint128::from(26u128),
+                ..SwapBreakdown::default()
+            },
+        }
+    );
+
+    let err = query(
+        deps.as_ref(),
+        mock_env_after(START + 500),
+        QueryMsg::Simulation {
+            offer_asset: Asset::new(AssetInfo::Native("uusd".to_string()), 2_000_001u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MaxInRatio {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &funds),
+        swap_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotActive {});
+}
+
+#[test]
+fn swap_restrictions() {
+    let mut deps = mock_dependencies(&[coin(96_000_000, "ulaunch"), Coin::new(4_001_000, "uusd")]);
+
+    let mut msg = instantiate_msg();
+    msg.purchase_cap = Some(Asset::new(AssetInfo::Native("uluna".to_string()), 1500u128));
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, ContractError::AssetMismatch {});
+
+    let mut msg = instantiate_msg();
+    msg.whitelist = Some(Whitelist::Addresses {
+        addresses: vec![Addr::unchecked("addr0000")],
+    });
+    msg.purchase_cap = Some(Asset::new(
+        AssetInfo::Native("ulaunch".to_string()),
+        1500u128,
+    ));
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Register { proof: vec![] },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoMerkleRoot {});
+
+    let funds = [coin(1000, "uusd")];
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(START),
+        mock_info("addr0001", &funds),
+        swap_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotWhitelisted {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(START),
+        mock_info("addr0000", &funds),
+        swap_msg(1000),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[6], attr("return_amount", "997"));
+
+    // a second swap would take the purchases of ulaunch past the cap
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(START),
+        mock_info("addr0000", &funds),
+        swap_msg(1000),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PurchaseCapExceeded {});
+
+    let participant: ParticipantResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Participant {
+                address: "addr0000".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        participant,
+        ParticipantResponse {
+            address: Addr::unchecked("addr0000"),
+            whitelisted: true,
+            purchased: Uint128::from(997u128),
+        }
+    );
+}
+
+#[test]
+fn withdraw_liquidity() {
+    let mut deps = mock_dependencies(&[coin(50_000_000, "ulaunch"), coin(9_000_000, "uusd")]);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END - 1),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::WithdrawLiquidity {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOver {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::WithdrawLiquidity {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(END),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::WithdrawLiquidity {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner0000".to_string(),
+                amount: vec![coin(50_000_000, "ulaunch")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner0000".to_string(),
+                amount: vec![coin(9_000_000, "uusd")],
+            })),
+        ]
+    );
+}
+
+fn graduation_env() -> Env {
+    mock_env_after(END)
+}
+
+fn instantiate_graduating_lbp(deps: DepsMut) {
+    let mut msg = instantiate_msg();
+    msg.end_weights = [
+        Decimal::from_str("0.4").unwrap(),
+        Decimal::from_str("0.6").unwrap(),
+    ];
+    let _res = instantiate(deps, mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+}
+
+fn seed_messages() -> Vec<SubMsg> {
+    // at the end, 1 ulaunch is worth (9_000_000 / 0.6) / (60_000_000 / 0.4) = 0.1 uusd
+    vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset::new(AssetInfo::Native("ulaunch".to_string()), 60_000_000u128),
+                    Asset::new(AssetInfo::Native("uusd".to_string()), 6_000_000u128),
+                ],
+                slippage_tolerance: None,
+                receiver: Some("owner0000".to_string()),
+                auto_stake: None,
+            })
+            .unwrap(),
+            funds: vec![coin(60_000_000, "ulaunch"), coin(6_000_000, "uusd")],
+        })),
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "owner0000".to_string(),
+            amount: vec![coin(3_000_000, "uusd")],
+        })),
+    ]
+}
+
+#[test]
+fn graduate() {
+    let mut deps = mock_dependencies(&[coin(60_000_000, "ulaunch"), coin(9_000_000, "uusd")]);
+    instantiate_graduating_lbp(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(END - 1),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotOver {});
+
+    let err = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the factory has no pair yet, create it first
+    let res = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            WasmMsg::Execute {
+                contract_addr: "factory0000".to_string(),
+                msg: to_binary(&FactoryExecuteMsg::CreatePair {
+                    asset_infos: instantiate_msg().asset_infos,
+                    fee_config: None,
+                })
+                .unwrap(),
+                funds: vec![],
+            },
+            1
+        )]
+    );
+
+    deps.querier.with_pairs(&[instantiate_msg().asset_infos]);
+    let res = reply(
+        deps.as_mut(),
+        graduation_env(),
+        Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: None,
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, seed_messages());
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.graduated_pair, Some(Addr::unchecked("pair0000")));
+
+    let err = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::AlreadyGraduated {});
+}
+
+#[test]
+fn graduate_into_existing_pair() {
+    let mut deps = mock_dependencies(&[coin(60_000_000, "ulaunch"), coin(9_000_000, "uusd")]);
+    instantiate_graduating_lbp(deps.as_mut());
+
+    deps.querier.with_pairs(&[instantiate_msg().asset_infos]);
+    deps.querier
+        .with_token_supplies(&[(&"liquidity0000".to_string(), &Uint128::from(1000u128))]);
+    let err = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::PairNotEmpty {});
+
+    // an empty pair is seeded right away
+    deps.querier
+        .with_token_supplies(&[(&"liquidity0000".to_string(), &Uint128::zero())]);
+    let res = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, seed_messages());
+}
+
+#[test]
+fn graduate_with_tax() {
+    let mut deps = mock_dependencies(&[coin(60_000_000, "ulaunch"), coin(9_000_000, "uusd")]);
+    instantiate_graduating_lbp(deps.as_mut());
+    deps.querier.with_pairs(&[instantiate_msg().asset_infos]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[("uusd", Uint128::from(1_000_000u128))],
+    );
+
+    // the 6_000_000 uusd deposit pays 60_000 uusd of tax, the remaining 2_939_999 uusd paying
+    // the tax of their own transfer
+    let res = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap();
+    let mut messages = seed_messages();
+    messages[1] = SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+        to_address: "owner0000".to_string(),
+        amount: vec![coin(2_910_890, "uusd")],
+    }));
+    assert_eq!(res.messages, messages);
+
+    // the whole uusd pool is deposited at these end weights, both deposits being scaled down
+    // to leave the tax of the uusd one
+    let mut deps = mock_dependencies(&[coin(60_000_000, "ulaunch"), coin(9_000_000, "uusd")]);
+    let mut msg = instantiate_msg();
+    msg.end_weights = [
+        Decimal::from_str("0.6").unwrap(),
+        Decimal::from_str("0.4").unwrap(),
+    ];
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    deps.querier.with_pairs(&[instantiate_msg().asset_infos]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[("uusd", Uint128::from(1_000_000u128))],
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        graduation_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::Graduate {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        Asset::new(AssetInfo::Native("ulaunch".to_string()), 39_603_960u128),
+                        Asset::new(AssetInfo::Native("uusd".to_string()), 8_910_891u128),
+                    ],
+                    slippage_tolerance: None,
+                    receiver: Some("owner0000".to_string()),
+                    auto_stake: None,
+                })
+                .unwrap(),
+                funds: vec![coin(39_603_960, "ulaunch"), coin(8_910_891, "uusd")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner0000".to_string(),
+                amount: vec![coin(20_396_040, "ulaunch")],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn weighted_pow() {
+    let assert_close = |value: Decimal256, expected: &str| {
+        let expected = Decimal256::from_str(expected).unwrap();
+        let diff = if value > expected {
+            value - expected
+        } else {
+            expected - value
+        };
+        assert!(diff < Decimal256::from_str("0.000000001").unwrap());
+    };
+
+    assert_close(
+        pow(
+            Decimal256::from_str("0.5").unwrap(),
+            Decimal256::from_str("0.5").unwrap(),
+        ),
+        "0.707106781186547524",
+    );
+    assert_close(
+        pow(
+            Decimal256::from_str("0.9").unwrap(),
+            Decimal256::from_str("2.5").unwrap(),
+        ),
+        "0.768433471420916177",
+    );
+    assert_eq!(
+        pow(
+            Decimal256::from_str("0.9").unwrap(),
+            Decimal256::from_str("2").unwrap(),
+        ),
+        Decimal256::from_str("0.81").unwrap()
+    );
+}
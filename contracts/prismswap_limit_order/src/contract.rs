@@ -0,0 +1,471 @@
+use crate::state::{read_orders, Config, Order, CONFIG, NEXT_ORDER_ID, ORDERS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use std::str::FromStr;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::limit_order::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse,
+    OrdersResponse, QueryMsg, SimulationResponse, MAX_KEEPER_FEE,
+};
+use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+use prismswap::querier::{query_pair_info, simulate};
+use prismswap::tax::TaxInfo;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_keeper_fee(msg.keeper_fee)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        keeper_fee: msg.keeper_fee,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_ORDER_ID.save(deps.storage, &1u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::SubmitOrder {
+            offer_asset,
+            ask_asset_info,
+            price,
+            expires_at,
+        } => {
+            if !offer_asset.info.is_native_token() {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            offer_asset.assert_sent_native_token_balance(&info)?;
+
+            execute_submit_order(
+                deps,
+                env,
+                info.sender,
+                offer_asset,
+                ask_asset_info,
+                price,
+                expires_at,
+            )
+        }
+        ExecuteMsg::CancelOrder { order_id } => {
+            execute_cancel_orders(deps, env, info, vec![order_id])
+        }
+        ExecuteMsg::CancelOrders { order_ids } => execute_cancel_orders(deps, env, info, order_ids),
+        ExecuteMsg::ExecuteOrder { order_id } => execute_execute_order(deps, env, info, order_id),
+        ExecuteMsg::UpdateConfig { owner, keeper_fee } => {
+            execute_update_config(deps, info, owner, keeper_fee)
+        }
+        ExecuteMsg::Callback(msg) => {
+            if env.contract.address != info.sender {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match msg {
+                CallbackMsg::SettleOrder {
+                    order_id,
+                    keeper,
+                    prev_balance,
+                } => execute_settle_order(deps, env, order_id, keeper, prev_balance),
+            }
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::SubmitOrder {
+            ask_asset_info,
+            price,
+            expires_at,
+        } => {
+            let maker = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_submit_order(
+                deps,
+                env,
+                maker,
+                Asset::new(AssetInfo::Cw20(info.sender), cw20_msg.amount),
+                ask_asset_info,
+                price,
+                expires_at,
+            )
+        }
+    }
+}
+
+pub fn execute_submit_order(
+    deps: DepsMut,
+    env: Env,
+    maker: Addr,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    price: Decimal,
+    expires_at: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    if offer_asset.amount.is_zero() {
+        return Err(StdError::generic_err("offer amount must be greater than 0"));
+    }
+
+    if price.is_zero() {
+        return Err(StdError::generic_err("price must be greater than 0"));
+    }
+
+    if matches!(expires_at, Some(expires_at) if expires_at <= env.block.time.seconds()) {
+        return Err(StdError::generic_err("expiration must be in the future"));
+    }
+
+    ask_asset_info.check(deps.api)?;
+    if ask_asset_info == offer_asset.info {
+        return Err(StdError::generic_err(
+            "offer and ask assets must be different",
+        ));
+    }
+
+    // the order is settled against the pair of the factory, which must exist
+    let pair_info = query_pair_info(
+        &deps.querier,
+        &config.factory,
+        &[offer_asset.info.clone(), ask_asset_info.clone()],
+    )?;
+
+    let order_id = NEXT_ORDER_ID.load(deps.storage)?;
+    NEXT_ORDER_ID.save(deps.storage, &(order_id + 1))?;
+    ORDERS.save(
+        deps.storage,
+        U64Key::new(order_id),
+        &Order {
+            maker: maker.clone(),
+            pair: pair_info.contract_addr,
+            offer_asset: offer_asset.clone(),
+            ask_asset_info,
+            price,
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "submit_order"),
+        ("order_id", &order_id.to_string()),
+        ("maker", maker.as_str()),
+        ("offer_asset", &offer_asset.to_string_legacy()),
+        ("price", &price.to_string()),
+    ]))
+}
+
+// Only maker can execute it, or anyone on expired orders
+pub fn execute_cancel_orders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_ids: Vec<u64>,
+) -> StdResult<Response> {
+    let mut messages = vec![];
+    for order_id in order_ids.iter() {
+        let order = load_order(deps.as_ref(), *order_id)?;
+
+        // permission check
+        if info.sender != order.maker && !order.is_expired(&env.block) {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+
+        ORDERS.remove(deps.storage, U64Key::new(*order_id));
+        // the tax of a native refund is charged on top of it against the escrowed amount
+        messages.extend(
+            order
+                .offer_asset
+                .into_transfer_msg_with_tax(&deps.querier, &order.maker)?,
+        );
+    }
+
+    let order_ids: Vec<String> = order_ids
+        .iter()
+        .map(|order_id| order_id.to_string())
+        .collect();
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "cancel_orders"),
+        ("order_ids", &order_ids.join(",")),
+    ]))
+}
+
+pub fn execute_execute_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let order = load_order(deps.as_ref(), order_id)?;
+
+    if order.is_expired(&env.block) {
+        return Err(StdError::generic_err("order expired"));
+    }
+
+    let simulation = simulate_order(deps.as_ref(), &env, &config, &order)?;
+    if !simulation.marketable {
+        return Err(StdError::generic_err("order not marketable"));
+    }
+
+    // the limit price is checked again on the asset actually received
+    let prev_balance = order
+        .ask_asset_info
+        .query_pool(&deps.querier, &env.contract.address)?;
+
+    Ok(Response::new()
+        .add_messages(vec![
+            swap_msg(&order, taxed_offer_asset(deps.as_ref(), &order)?)?,
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::SettleOrder {
+                    order_id,
+                    keeper: info.sender.clone(),
+                    prev_balance,
+                }))?,
+            }),
+        ])
+        .add_attributes(vec![
+            ("action", "execute_order"),
+            ("order_id", &order_id.to_string()),
+            ("keeper", info.sender.as_str()),
+        ]))
+}
+
+pub fn execute_settle_order(
+    deps: DepsMut,
+    env: Env,
+    order_id: u64,
+    keeper: Addr,
+    prev_balance: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let order = load_order(deps.as_ref(), order_id)?;
+
+    let return_amount = order
+        .ask_asset_info
+        .query_pool(&deps.querier, &env.contract.address)?
+        .checked_sub(prev_balance)?;
+    let keeper_fee_amount = return_amount * config.keeper_fee;
+    let maker_amount = return_amount - keeper_fee_amount;
+    if maker_amount < order.offer_asset.amount * order.price {
+        return Err(StdError::generic_err("order not marketable"));
+    }
+
+    ORDERS.remove(deps.storage, U64Key::new(order_id));
+
+    // the tax of a native return is charged on top of each transfer
+    let mut messages: Vec<CosmosMsg> = vec![];
+    messages.extend(
+        Asset::new(order.ask_asset_info.clone(), maker_amount)
+            .into_transfer_msg_with_tax(&deps.querier, &order.maker)?,
+    );
+    messages.extend(
+        Asset::new(order.ask_asset_info.clone(), keeper_fee_amount)
+            .into_transfer_msg_with_tax(&deps.querier, &keeper)?,
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "settle_order"),
+        ("order_id", &order_id.to_string()),
+        ("maker_amount", &maker_amount.to_string()),
+        ("keeper_fee_amount", &keeper_fee_amount.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    keeper_fee: Option<Decimal>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(keeper_fee) = keeper_fee {
+        assert_keeper_fee(keeper_fee)?;
+        config.keeper_fee = keeper_fee;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_keeper_fee(keeper_fee: Decimal) -> StdResult<()> {
+    if keeper_fee > Decimal::from_str(MAX_KEEPER_FEE).unwrap() {
+        return Err(StdError::generic_err(format!(
+            "keeper fee must not exceed {}",
+            MAX_KEEPER_FEE
+        )));
+    }
+
+    Ok(())
+}
+
+fn load_order(deps: Deps, order_id: u64) -> StdResult<Order> {
+    ORDERS
+        .may_load(deps.storage, U64Key::new(order_id))?
+        .ok_or_else(|| StdError::generic_err("order not found"))
+}
+
+/// Returns the offer asset the pair receives, the tax of a native offer being charged on top
+/// of it against the escrowed amount
+fn taxed_offer_asset(deps: Deps, order: &Order) -> StdResult<Asset> {
+    let amount = match &order.offer_asset.info {
+        AssetInfo::Native(denom) => TaxInfo::query(&deps.querier, &[denom.to_string()])?
+            .deduct_tax(&Coin {
+                denom: denom.to_string(),
+                amount: order.offer_asset.amount,
+            })?,
+        AssetInfo::Cw20(_) => order.offer_asset.amount,
+    };
+
+    Ok(Asset::new(order.offer_asset.info.clone(), amount))
+}
+
+/// Swap the offer asset in the pair, the ask asset coming back to the contract
+fn swap_msg(order: &Order, offer_asset: Asset) -> StdResult<CosmosMsg> {
+    let swap_msg = PairExecuteMsg::Swap {
+        offer_asset: offer_asset.clone(),
+        belief_price: None,
+        max_spread: None,
+        to: None,
+    };
+
+    Ok(match &offer_asset.info {
+        AssetInfo::Native(denom) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: order.pair.to_string(),
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: offer_asset.amount,
+            }],
+            msg: to_binary(&swap_msg)?,
+        }),
+        AssetInfo::Cw20(contract_addr) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: order.pair.to_string(),
+                amount: offer_asset.amount,
+                msg: to_binary(&swap_msg)?,
+            })?,
+        }),
+    })
+}
+
+fn simulate_order(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    order: &Order,
+) -> StdResult<SimulationResponse> {
+    let offer_asset = taxed_offer_asset(deps, order)?;
+    let return_amount = simulate(&deps.querier, &order.pair, &offer_asset)?.return_amount;
+    let keeper_fee_amount = return_amount * config.keeper_fee;
+    let maker_amount = return_amount - keeper_fee_amount;
+    let min_maker_amount = order.offer_asset.amount * order.price;
+
+    Ok(SimulationResponse {
+        return_amount,
+        keeper_fee_amount,
+        maker_amount,
+        min_maker_amount,
+        marketable: !order.is_expired(&env.block) && maker_amount >= min_maker_amount,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Order { order_id } => to_binary(&query_order(deps, order_id)?),
+        QueryMsg::Orders {
+            maker,
+            start_after,
+            limit,
+        } => to_binary(&query_orders(deps, maker, start_after, limit)?),
+        QueryMsg::Simulation { order_id } => to_binary(&query_simulation(deps, env, order_id)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        keeper_fee: config.keeper_fee,
+    })
+}
+
+pub fn query_order(deps: Deps, order_id: u64) -> StdResult<OrderResponse> {
+    let order = load_order(deps, order_id)?;
+
+    Ok(order_response(order_id, order))
+}
+
+pub fn query_orders(
+    deps: Deps,
+    maker: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let orders = read_orders(deps.storage, maker, start_after, limit)?
+        .into_iter()
+        .map(|(order_id, order)| order_response(order_id, order))
+        .collect();
+
+    Ok(OrdersResponse { orders })
+}
+
+pub fn query_simulation(deps: Deps, env: Env, order_id: u64) -> StdResult<SimulationResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let order = load_order(deps, order_id)?;
+
+    simulate_order(deps, &env, &config, &order)
+}
+
+fn order_response(order_id: u64, order: Order) -> OrderResponse {
+    OrderResponse {
+        order_id,
+        maker: order.maker,
+        pair: order.pair,
+        offer_asset: order.offer_asset,
+        ask_asset_info: order.ask_asset_info,
+        price: order.price,
+        expires_at: order.expires_at,
+    }
+}
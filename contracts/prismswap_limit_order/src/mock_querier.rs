@@ -0,0 +1,167 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use prismswap::asset::PairInfo;
+use prismswap::factory::QueryMsg as FactoryQueryMsg;
+use prismswap::fee::SwapBreakdown;
+use prismswap::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    return_amount: Uint128,
+    tax_rate: Decimal,
+    tax_caps: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match &request {
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data })
+                if route == &TerraRoute::Treasury =>
+            {
+                match query_data {
+                    TerraQuery::TaxRate {} => {
+                        let res = TaxRateResponse {
+                            rate: self.tax_rate,
+                        };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    TerraQuery::TaxCap { denom } => {
+                        let cap = self.tax_caps.get(denom).copied().unwrap_or_default();
+                        let res = TaxCapResponse { cap };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<FactoryQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::Pair { asset_infos } => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&PairInfo {
+                            asset_infos,
+                            contract_addr: Addr::unchecked("pair0000"),
+                            liquidity_token: Addr::unchecked("liquidity0000"),
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<PairQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    PairQueryMsg::Simulation { .. } => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&SimulationResponse {
+                            return_amount: self.return_amount,
+                            spread_amount: Uint128::zero(),
+                            commission_amount: Uint128::zero(),
+                            breakdown: SwapBreakdown {
+                                return_amount: self.return_amount,
+                                ..SwapBreakdown::default()
+                            },
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            return_amount: Uint128::zero(),
+            tax_rate: Decimal::zero(),
+            tax_caps: HashMap::new(),
+        }
+    }
+
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_balances = balances
+            .iter()
+            .map(|(contract_addr, balances)| {
+                (
+                    contract_addr.to_string(),
+                    balances
+                        .iter()
+                        .map(|(addr, balance)| (addr.to_string(), **balance))
+                        .collect(),
+                )
+            })
+            .collect();
+    }
+
+    pub fn with_return_amount(&mut self, return_amount: Uint128) {
+        self.return_amount = return_amount;
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&str, Uint128)]) {
+        self.tax_rate = rate;
+        self.tax_caps = caps
+            .iter()
+            .map(|(denom, cap)| (denom.to_string(), *cap))
+            .collect();
+    }
+}
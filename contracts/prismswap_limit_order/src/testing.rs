@@ -0,0 +1,622 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, DepsMut, StdError,
+    SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::limit_order::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse,
+    OrdersResponse, QueryMsg, SimulationResponse,
+};
+use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        factory: Addr::unchecked("factory0000"),
+        keeper_fee: Decimal::percent(1),
+    }
+}
+
+fn uusd(amount: u128) -> Asset {
+    Asset::new(AssetInfo::Native("uusd".to_string()), amount)
+}
+
+/// Submits an order offering uusd for PRISM
+fn submit_order(deps: DepsMut, maker: &str, amount: u128, price: Decimal, expires_at: Option<u64>) {
+    let _res = execute(
+        deps,
+        mock_env(),
+        mock_info(maker, &[coin(amount, "uusd")]),
+        ExecuteMsg::SubmitOrder {
+            offer_asset: uusd(amount),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            price,
+            expires_at,
+        },
+    )
+    .unwrap();
+}
+
+fn prism_transfer(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "prism0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.keeper_fee = Decimal::percent(6);
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("keeper fee must not exceed 0.05")
+    );
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            factory: Addr::unchecked("factory0000"),
+            keeper_fee: Decimal::percent(1),
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        keeper_fee: Some(Decimal::percent(2)),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            keeper_fee: Some(Decimal::percent(10)),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("keeper fee must not exceed 0.05")
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.keeper_fee, Decimal::percent(2));
+}
+
+#[test]
+fn submit_and_cancel_order() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let submit_msg = |price: Decimal| ExecuteMsg::SubmitOrder {
+        offer_asset: uusd(1000),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        price,
+        expires_at: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(500, "uusd")]),
+        submit_msg(Decimal::percent(200)),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "Native token balance mismatch between the argument and the transferred"
+        )
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        submit_msg(Decimal::zero()),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("price must be greater than 0"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        submit_msg(Decimal::percent(200)),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("order_id", "1"));
+
+    // CW20 tokens are offered through their hook
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0001".to_string(),
+            amount: Uint128::from(300u128),
+            msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                price: Decimal::percent(50),
+                expires_at: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("order_id", "2"));
+
+    let order: OrderResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id: 2 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        order,
+        OrderResponse {
+            order_id: 2,
+            maker: Addr::unchecked("addr0001"),
+            pair: Addr::unchecked("pair0000"),
+            offer_asset: Asset::new(AssetInfo::Cw20(Addr::unchecked("prism0000")), 300u128),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            price: Decimal::percent(50),
+            expires_at: None,
+        }
+    );
+
+    let orders: OrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Orders {
+                maker: Some(Addr::unchecked("addr0000")),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orders.orders.len(), 1);
+    assert_eq!(orders.orders[0].order_id, 1);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(1000, "uusd")],
+        }))]
+    );
+
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id: 1 }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("order not found"));
+}
+
+#[test]
+fn execute_order() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    // 1000 uusd for at least 2000 PRISM net of the keeper fee
+    submit_order(deps.as_mut(), "addr0000", 1000, Decimal::percent(200), None);
+
+    deps.querier.with_return_amount(Uint128::from(2000u128));
+    let simulation: SimulationResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Simulation { order_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::from(2000u128),
+            keeper_fee_amount: Uint128::from(20u128),
+            maker_amount: Uint128::from(1980u128),
+            min_maker_amount: Uint128::from(2000u128),
+            marketable: false,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("order not marketable"));
+
+    deps.querier.with_return_amount(Uint128::from(2100u128));
+    deps.querier.with_token_balances(&[(
+        &"prism0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(5000u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                funds: vec![coin(1000, "uusd")],
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: uusd(1000),
+                    belief_price: None,
+                    max_spread: None,
+                    to: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::SettleOrder {
+                    order_id: 1,
+                    keeper: Addr::unchecked("keeper0000"),
+                    prev_balance: Uint128::from(5000u128),
+                }))
+                .unwrap(),
+            })),
+        ]
+    );
+
+    let settle_msg = ExecuteMsg::Callback(CallbackMsg::SettleOrder {
+        order_id: 1,
+        keeper: Addr::unchecked("keeper0000"),
+        prev_balance: Uint128::from(5000u128),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        settle_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // the swap returned less than the limit price
+    deps.querier.with_token_balances(&[(
+        &"prism0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(6900u128))],
+    )]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        settle_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("order not marketable"));
+
+    deps.querier.with_token_balances(&[(
+        &"prism0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(7100u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        settle_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            prism_transfer("addr0000", 2079),
+            prism_transfer("keeper0000", 21),
+        ]
+    );
+
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id: 1 }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("order not found"));
+}
+
+#[test]
+fn order_expiry() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let now = mock_env().block.time.seconds();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        ExecuteMsg::SubmitOrder {
+            offer_asset: uusd(1000),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            price: Decimal::percent(200),
+            expires_at: Some(now),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("expiration must be in the future")
+    );
+
+    submit_order(
+        deps.as_mut(),
+        "addr0000",
+        1000,
+        Decimal::percent(200),
+        Some(now + 3600),
+    );
+    submit_order(deps.as_mut(), "addr0000", 500, Decimal::percent(200), None);
+    submit_order(deps.as_mut(), "addr0001", 200, Decimal::percent(200), None);
+
+    // only the maker cancels an order still live
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // an expired order is neither marketable nor executable
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(3600);
+    deps.querier.with_return_amount(Uint128::from(3000u128));
+    let simulation: SimulationResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Simulation { order_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(!simulation.marketable);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("order expired"));
+
+    // anyone cancels it then, refunding the maker, but no live order of another maker
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrders {
+            order_ids: vec![2, 1],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrders {
+            order_ids: vec![1, 3],
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![coin(1000, "uusd")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0001".to_string(),
+                amount: vec![coin(200, "uusd")],
+            })),
+        ]
+    );
+    assert_eq!(res.attributes[1], attr("order_ids", "1,3"));
+
+    let orders: OrdersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Orders {
+                maker: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(orders.orders.len(), 1);
+    assert_eq!(orders.orders[0].order_id, 2);
+}
+
+#[test]
+fn execute_order_with_tax() {
+    // the escrow of two 1000 uusd orders
+    let mut deps = mock_dependencies(&[coin(2000, "uusd")]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(1000000u128))]);
+
+    submit_order(deps.as_mut(), "addr0000", 1000, Decimal::percent(200), None);
+    submit_order(deps.as_mut(), "addr0001", 1000, Decimal::percent(200), None);
+
+    // the tax of the refund is charged against the escrowed amount, 990 + 10
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(990, "uusd")],
+        }))]
+    );
+
+    // and so is the tax of the swap, leaving the escrow solvent once both orders are out
+    deps.querier.with_return_amount(Uint128::from(2100u128));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![coin(990, "uusd")],
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: uusd(990),
+                belief_price: None,
+                max_spread: None,
+                to: None,
+            })
+            .unwrap(),
+        }))
+    );
+
+    // a native return pays the tax of the maker and the keeper transfers
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0002".to_string(),
+            amount: Uint128::from(300u128),
+            msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                price: Decimal::percent(50),
+                expires_at: None,
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::SettleOrder {
+            order_id: 3,
+            keeper: Addr::unchecked("keeper0000"),
+            prev_balance: Uint128::from(1798u128),
+        }),
+    )
+    .unwrap();
+    // 198 + 2 and 1 + 1 out of the 202 uusd returned
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0002".to_string(),
+                amount: vec![coin(198, "uusd")],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "keeper0000".to_string(),
+                amount: vec![coin(1, "uusd")],
+            })),
+        ]
+    );
+}
@@ -0,0 +1,295 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw_storage_plus::U64Key;
+
+use crate::state::{read_lockups, Config, UserInfo, CONFIG, LOCKUPS, TOTAL_WEIGHT, USERS};
+
+use cw20::Cw20ReceiveMsg;
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::lockdrop::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupDuration, LockupResponse,
+    QueryMsg, StateResponse, UserResponse,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    if msg.window_start >= msg.window_end {
+        return Err(StdError::generic_err(
+            "deposit window must end after it starts",
+        ));
+    }
+    if msg.lockup_durations.is_empty() || !msg.lockup_durations.iter().all(|d| d.is_valid()) {
+        return Err(StdError::generic_err(
+            "lockup durations must have a non-zero multiplier and a penalty lower than 1",
+        ));
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        prism_token: deps.api.addr_validate(msg.prism_token.as_str())?,
+        lp_token: deps.api.addr_validate(msg.lp_token.as_str())?,
+        window_start: msg.window_start,
+        window_end: msg.window_end,
+        total_allocation: msg.total_allocation,
+        lockup_durations: msg.lockup_durations,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    TOTAL_WEIGHT.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::Unlock { duration } => execute_unlock(deps, env, info, duration),
+        ExecuteMsg::Claim {} => execute_claim(deps, env, info),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // only the LP token can be locked
+    if info.sender != config.lp_token {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Lock { duration } => lock(
+            deps,
+            env,
+            config,
+            Addr::unchecked(cw20_msg.sender),
+            duration,
+            cw20_msg.amount,
+        ),
+    }
+}
+
+pub fn lock(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    user: Addr,
+    duration: u64,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let now = env.block.time.seconds();
+    if now < config.window_start || now >= config.window_end {
+        return Err(StdError::generic_err("deposit window is closed"));
+    }
+    let lockup_duration = find_lockup_duration(&config, duration)?;
+
+    LOCKUPS.update(
+        deps.storage,
+        (&user, U64Key::new(duration)),
+        |lockup| -> StdResult<_> { Ok(lockup.unwrap_or_default() + amount) },
+    )?;
+
+    let weight = amount * lockup_duration.multiplier;
+    let mut user_info = USERS.may_load(deps.storage, &user)?.unwrap_or_default();
+    user_info.weight += weight;
+    USERS.save(deps.storage, &user, &user_info)?;
+    TOTAL_WEIGHT.update(deps.storage, |total_weight| -> StdResult<_> {
+        Ok(total_weight + weight)
+    })?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "lock"),
+        ("user", user.as_str()),
+        ("duration", &duration.to_string()),
+        ("amount", &amount.to_string()),
+        ("weight", &weight.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn execute_unlock(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    duration: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let lockup_duration = find_lockup_duration(&config, duration)?;
+    let key = (&info.sender, U64Key::new(duration));
+    let amount = LOCKUPS
+        .may_load(deps.storage, key.clone())?
+        .ok_or_else(|| StdError::generic_err(format!("no lockup of {} seconds", duration)))?;
+    LOCKUPS.remove(deps.storage, key);
+
+    let now = env.block.time.seconds();
+    let mut penalty_amount = Uint128::zero();
+    if now < config.window_end {
+        // withdrawing during the deposit window drops the weight
+        let weight = amount * lockup_duration.multiplier;
+        let mut user_info = USERS.load(deps.storage, &info.sender)?;
+        user_info.weight = user_info.weight.checked_sub(weight)?;
+        USERS.save(deps.storage, &info.sender, &user_info)?;
+        TOTAL_WEIGHT.update(deps.storage, |total_weight| -> StdResult<_> {
+            Ok(total_weight.checked_sub(weight)?)
+        })?;
+    } else if now < config.window_end + duration {
+        penalty_amount = amount * lockup_duration.penalty;
+    }
+
+    let lp_token = AssetInfo::Cw20(config.lp_token);
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let return_amount = amount.checked_sub(penalty_amount)?;
+    if !return_amount.is_zero() {
+        messages.push(Asset::new(lp_token.clone(), return_amount).transfer_msg(&info.sender)?);
+    }
+    if !penalty_amount.is_zero() {
+        messages.push(Asset::new(lp_token, penalty_amount).transfer_msg(&config.owner)?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "unlock"),
+        ("user", info.sender.as_str()),
+        ("duration", &duration.to_string()),
+        ("amount", &return_amount.to_string()),
+        ("penalty_amount", &penalty_amount.to_string()),
+    ]))
+}
+
+pub fn execute_claim(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if env.block.time.seconds() < config.window_end {
+        return Err(StdError::generic_err("deposit window is not over"));
+    }
+
+    let mut user_info = USERS
+        .may_load(deps.storage, &info.sender)?
+        .unwrap_or_default();
+    if user_info.claimed {
+        return Err(StdError::generic_err("already claimed"));
+    }
+    let total_weight = TOTAL_WEIGHT.load(deps.storage)?;
+    let amount = user_allocation(&config, &user_info, total_weight);
+    if amount.is_zero() {
+        return Err(StdError::generic_err("nothing to claim"));
+    }
+
+    user_info.claimed = true;
+    USERS.save(deps.storage, &info.sender, &user_info)?;
+
+    Ok(Response::new()
+        .add_message(
+            Asset::new(AssetInfo::Cw20(config.prism_token), amount).transfer_msg(&info.sender)?,
+        )
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("user", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn find_lockup_duration(config: &Config, duration: u64) -> StdResult<LockupDuration> {
+    config
+        .lockup_durations
+        .iter()
+        .find(|lockup_duration| lockup_duration.duration == duration)
+        .cloned()
+        .ok_or_else(|| StdError::generic_err(format!("no lockup duration of {} seconds", duration)))
+}
+
+fn user_allocation(config: &Config, user_info: &UserInfo, total_weight: Uint128) -> Uint128 {
+    if total_weight.is_zero() {
+        Uint128::zero()
+    } else {
+        config
+            .total_allocation
+            .multiply_ratio(user_info.weight, total_weight)
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps)?),
+        QueryMsg::User { address } => to_binary(&query_user(deps, address)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        prism_token: config.prism_token,
+        lp_token: config.lp_token,
+        window_start: config.window_start,
+        window_end: config.window_end,
+        total_allocation: config.total_allocation,
+        lockup_durations: config.lockup_durations,
+    })
+}
+
+pub fn query_state(deps: Deps) -> StdResult<StateResponse> {
+    Ok(StateResponse {
+        total_weight: TOTAL_WEIGHT.load(deps.storage)?,
+    })
+}
+
+pub fn query_user(deps: Deps, address: Addr) -> StdResult<UserResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let user_info = USERS.may_load(deps.storage, &address)?.unwrap_or_default();
+    let total_weight = TOTAL_WEIGHT.load(deps.storage)?;
+    let lockups = read_lockups(deps.storage, &address)?
+        .into_iter()
+        .map(|(duration, amount)| LockupResponse {
+            duration,
+            amount,
+            unlock_time: config.window_end + duration,
+        })
+        .collect();
+
+    Ok(UserResponse {
+        address,
+        weight: user_info.weight,
+        allocation: user_allocation(&config, &user_info, total_weight),
+        claimed: user_info.claimed,
+        lockups,
+    })
+}
@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::lockdrop::LockupDuration;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub prism_token: Addr,
+    pub lp_token: Addr,
+    pub window_start: u64,
+    pub window_end: u64,
+    pub total_allocation: Uint128,
+    pub lockup_durations: Vec<LockupDuration>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// the total weight of the lockups
+pub const TOTAL_WEIGHT: Item<Uint128> = Item::new("total_weight");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct UserInfo {
+    pub weight: Uint128,
+    pub claimed: bool,
+}
+
+pub const USERS: Map<&Addr, UserInfo> = Map::new("users");
+
+/// the LP tokens locked by each user, by lockup duration
+pub const LOCKUPS: Map<(&Addr, U64Key), Uint128> = Map::new("lockups");
+
+pub fn read_lockups(storage: &dyn Storage, user: &Addr) -> StdResult<Vec<(u64, Uint128)>> {
+    LOCKUPS
+        .prefix(user)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut duration = [0u8; 8];
+            duration.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(duration), v))
+        })
+        .collect()
+}
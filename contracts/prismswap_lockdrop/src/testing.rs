@@ -0,0 +1,305 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, StdError, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::lockdrop::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupDuration, LockupResponse,
+    QueryMsg, StateResponse, UserResponse,
+};
+use std::str::FromStr;
+
+const WINDOW: u64 = 1000;
+
+fn mock_env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn lockup_durations() -> Vec<LockupDuration> {
+    vec![
+        LockupDuration {
+            duration: 1000,
+            multiplier: Decimal::one(),
+            penalty: Decimal::from_str("0.1").unwrap(),
+        },
+        LockupDuration {
+            duration: 3000,
+            multiplier: Decimal::from_str("2").unwrap(),
+            penalty: Decimal::from_str("0.3").unwrap(),
+        },
+    ]
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    let now = mock_env().block.time.seconds();
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        prism_token: Addr::unchecked("prism0000"),
+        lp_token: Addr::unchecked("lptoken0000"),
+        window_start: now,
+        window_end: now + WINDOW,
+        total_allocation: Uint128::from(9000u128),
+        lockup_durations: lockup_durations(),
+    }
+}
+
+fn lock_msg(user: &str, duration: u64, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: user.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&Cw20HookMsg::Lock { duration }).unwrap(),
+    })
+}
+
+fn transfer(token: &str, recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+fn init(deps: DepsMut) {
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps, mock_env(), info, instantiate_msg()).unwrap();
+}
+
+fn query_user(deps: Deps, user: &str) -> UserResponse {
+    from_binary(
+        &query(
+            deps,
+            mock_env(),
+            QueryMsg::User {
+                address: Addr::unchecked(user),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.lockup_durations[1].penalty = Decimal::one();
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "lockup durations must have a non-zero multiplier and a penalty lower than 1"
+        )
+    );
+
+    init(deps.as_mut());
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    let now = mock_env().block.time.seconds();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            prism_token: Addr::unchecked("prism0000"),
+            lp_token: Addr::unchecked("lptoken0000"),
+            window_start: now,
+            window_end: now + WINDOW,
+            total_allocation: Uint128::from(9000u128),
+            lockup_durations: lockup_durations(),
+        }
+    );
+}
+
+#[test]
+fn lock() {
+    let mut deps = mock_dependencies(&[]);
+    init(deps.as_mut());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("token0000", &[]),
+        lock_msg("user0000", 1000, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lptoken0000", &[]),
+        lock_msg("user0000", 2000, 100),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("no lockup duration of 2000 seconds")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW),
+        mock_info("lptoken0000", &[]),
+        lock_msg("user0000", 1000, 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("deposit window is closed"));
+
+    for (user, duration, amount) in [
+        ("user0000", 1000, 100),
+        ("user0000", 3000, 100),
+        ("user0000", 3000, 50),
+        ("user0001", 1000, 300),
+    ] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lptoken0000", &[]),
+            lock_msg(user, duration, amount),
+        )
+        .unwrap();
+    }
+
+    let state: StateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(state.total_weight, Uint128::from(700u128));
+
+    let now = mock_env().block.time.seconds();
+    assert_eq!(
+        query_user(deps.as_ref(), "user0000"),
+        UserResponse {
+            address: Addr::unchecked("user0000"),
+            weight: Uint128::from(400u128),
+            allocation: Uint128::from(5142u128),
+            claimed: false,
+            lockups: vec![
+                LockupResponse {
+                    duration: 1000,
+                    amount: Uint128::from(100u128),
+                    unlock_time: now + WINDOW + 1000,
+                },
+                LockupResponse {
+                    duration: 3000,
+                    amount: Uint128::from(150u128),
+                    unlock_time: now + WINDOW + 3000,
+                },
+            ],
+        }
+    );
+}
+
+#[test]
+fn unlock_and_claim() {
+    let mut deps = mock_dependencies(&[]);
+    init(deps.as_mut());
+
+    for (user, duration, amount) in [
+        ("user0000", 1000, 100),
+        ("user0000", 3000, 100),
+        ("user0001", 1000, 300),
+        ("user0002", 3000, 200),
+    ] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("lptoken0000", &[]),
+            lock_msg(user, duration, amount),
+        )
+        .unwrap();
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user0000", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("deposit window is not over"));
+
+    // withdrawing during the window is free and drops the weight
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("user0002", &[]),
+        ExecuteMsg::Unlock { duration: 3000 },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer("lptoken0000", "user0002", 200)]);
+    assert_eq!(
+        query_user(deps.as_ref(), "user0002").weight,
+        Uint128::zero()
+    );
+
+    // exiting early after the window costs the penalty of the duration
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW + 2999),
+        mock_info("user0000", &[]),
+        ExecuteMsg::Unlock { duration: 3000 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer("lptoken0000", "user0000", 70),
+            transfer("lptoken0000", "owner0000", 30),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW + 1000),
+        mock_info("user0000", &[]),
+        ExecuteMsg::Unlock { duration: 1000 },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer("lptoken0000", "user0000", 100)]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW + 1000),
+        mock_info("user0000", &[]),
+        ExecuteMsg::Unlock { duration: 1000 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no lockup of 1000 seconds"));
+
+    // the weight is kept after exiting
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW),
+        mock_info("user0000", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer("prism0000", "user0000", 4500)]);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW),
+        mock_info("user0000", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("already claimed"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(WINDOW),
+        mock_info("user0002", &[]),
+        ExecuteMsg::Claim {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("nothing to claim"));
+}
@@ -0,0 +1,301 @@
+use crate::state::{Config, Stage, CLAIMED, CONFIG, LATEST_STAGE, STAGES};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::U8Key;
+use sha2::{Digest, Sha256};
+
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::merkle_airdrop::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, IsClaimedResponse, QueryMsg,
+    StageResponse,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: deps.api.addr_validate(msg.owner.as_str())?,
+            token: deps.api.addr_validate(msg.token.as_str())?,
+        },
+    )?;
+    LATEST_STAGE.save(deps.storage, &0)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::Claim {
+            stage,
+            amount,
+            proof,
+        } => execute_claim(deps, env, info, stage, amount, proof),
+        ExecuteMsg::Clawback { stage, recipient } => {
+            execute_clawback(deps, env, info, stage, recipient)
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.token {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::RegisterMerkleRoot {
+            merkle_root,
+            deadline,
+        } => {
+            // Only owner can execute it
+            let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+            if sender != config.owner {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            register_merkle_root(deps, env, merkle_root, deadline, cw20_msg.amount)
+        }
+    }
+}
+
+pub fn register_merkle_root(
+    deps: DepsMut,
+    env: Env,
+    merkle_root: String,
+    deadline: u64,
+    total_amount: Uint128,
+) -> StdResult<Response> {
+    let mut root = [0u8; 32];
+    hex::decode_to_slice(&merkle_root, &mut root)
+        .map_err(|_| StdError::generic_err("invalid merkle root"))?;
+
+    if deadline <= env.block.time.seconds() {
+        return Err(StdError::generic_err("deadline must be in the future"));
+    }
+
+    if total_amount.is_zero() {
+        return Err(StdError::generic_err("stage must be funded"));
+    }
+
+    let stage = LATEST_STAGE
+        .load(deps.storage)?
+        .checked_add(1)
+        .ok_or_else(|| StdError::generic_err("too many stages"))?;
+    LATEST_STAGE.save(deps.storage, &stage)?;
+
+    STAGES.save(
+        deps.storage,
+        U8Key::new(stage),
+        &Stage {
+            merkle_root: merkle_root.clone(),
+            deadline,
+            total_amount,
+            claimed_amount: Uint128::zero(),
+            clawed_back: false,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_merkle_root"),
+        ("stage", &stage.to_string()),
+        ("merkle_root", &merkle_root),
+        ("total_amount", &total_amount.to_string()),
+    ]))
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    amount: Uint128,
+    proof: Vec<String>,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut stage_info = load_stage(deps.as_ref(), stage)?;
+
+    if env.block.time.seconds() >= stage_info.deadline {
+        return Err(StdError::generic_err("stage expired"));
+    }
+
+    if CLAIMED.has(deps.storage, (U8Key::new(stage), &info.sender)) {
+        return Err(StdError::generic_err("already claimed"));
+    }
+
+    verify_proof(&stage_info.merkle_root, &info.sender, amount, &proof)?;
+
+    stage_info.claimed_amount += amount;
+    if stage_info.claimed_amount > stage_info.total_amount {
+        return Err(StdError::generic_err("claims exceed the stage funds"));
+    }
+
+    CLAIMED.save(deps.storage, (U8Key::new(stage), &info.sender), &true)?;
+    STAGES.save(deps.storage, U8Key::new(stage), &stage_info)?;
+
+    let asset = Asset::new(AssetInfo::Cw20(config.token), amount);
+
+    Ok(Response::new()
+        .add_message(asset.transfer_msg(&info.sender)?)
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("stage", &stage.to_string()),
+            ("address", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+pub fn execute_clawback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stage: u8,
+    recipient: Addr,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut stage_info = load_stage(deps.as_ref(), stage)?;
+    if env.block.time.seconds() < stage_info.deadline {
+        return Err(StdError::generic_err("stage not expired"));
+    }
+
+    if stage_info.clawed_back {
+        return Err(StdError::generic_err("already clawed back"));
+    }
+
+    stage_info.clawed_back = true;
+    STAGES.save(deps.storage, U8Key::new(stage), &stage_info)?;
+
+    let amount = stage_info.total_amount - stage_info.claimed_amount;
+    let mut response = Response::new();
+    if !amount.is_zero() {
+        let asset = Asset::new(AssetInfo::Cw20(config.token), amount);
+        response = response.add_message(asset.transfer_msg(&recipient)?);
+    }
+
+    Ok(response.add_attributes(vec![
+        ("action", "clawback"),
+        ("stage", &stage.to_string()),
+        ("recipient", recipient.as_str()),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+fn load_stage(deps: Deps, stage: u8) -> StdResult<Stage> {
+    STAGES
+        .may_load(deps.storage, U8Key::new(stage))?
+        .ok_or_else(|| StdError::generic_err("stage not found"))
+}
+
+/// Verifies the leaf of the address and the amount against the merkle root, hashing every
+/// pair of nodes in ascending order
+fn verify_proof(
+    merkle_root: &str,
+    address: &Addr,
+    amount: Uint128,
+    proof: &[String],
+) -> StdResult<()> {
+    let leaf = format!("{}{}", address, amount);
+    let mut hash: [u8; 32] = Sha256::digest(leaf.as_bytes()).into();
+
+    for node in proof {
+        let mut sibling = [0u8; 32];
+        hex::decode_to_slice(node, &mut sibling)
+            .map_err(|_| StdError::generic_err("invalid proof"))?;
+
+        let mut pair = [hash, sibling];
+        pair.sort_unstable();
+        hash = Sha256::digest(&pair.concat()).into();
+    }
+
+    let mut root = [0u8; 32];
+    hex::decode_to_slice(merkle_root, &mut root)
+        .map_err(|_| StdError::generic_err("invalid merkle root"))?;
+    if hash != root {
+        return Err(StdError::generic_err("verification failed"));
+    }
+
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Stage { stage } => to_binary(&query_stage(deps, stage)?),
+        QueryMsg::IsClaimed { stage, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            to_binary(&IsClaimedResponse {
+                is_claimed: CLAIMED.has(deps.storage, (U8Key::new(stage), &address)),
+            })
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        token: config.token,
+        latest_stage: LATEST_STAGE.load(deps.storage)?,
+    })
+}
+
+pub fn query_stage(deps: Deps, stage: u8) -> StdResult<StageResponse> {
+    let stage_info = load_stage(deps, stage)?;
+
+    Ok(StageResponse {
+        stage,
+        merkle_root: stage_info.merkle_root,
+        deadline: stage_info.deadline,
+        total_amount: stage_info.total_amount,
+        claimed_amount: stage_info.claimed_amount,
+        clawed_back: stage_info.clawed_back,
+    })
+}
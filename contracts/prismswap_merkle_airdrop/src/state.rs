@@ -0,0 +1,29 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U8Key};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub token: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// the last stage registered
+pub const LATEST_STAGE: Item<u8> = Item::new("latest_stage");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stage {
+    pub merkle_root: String,
+    pub deadline: u64,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub clawed_back: bool,
+}
+
+pub const STAGES: Map<U8Key, Stage> = Map::new("stages");
+
+/// the addresses which claimed their rewards in every stage
+pub const CLAIMED: Map<(U8Key, &Addr), bool> = Map::new("claimed");
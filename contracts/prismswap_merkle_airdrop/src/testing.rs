@@ -0,0 +1,268 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, CosmosMsg, Env, OwnedDeps, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::merkle_airdrop::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, IsClaimedResponse, QueryMsg,
+    StageResponse,
+};
+use sha2::{Digest, Sha256};
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn leaf(address: &str, amount: u128) -> [u8; 32] {
+    Sha256::digest(format!("{}{}", address, amount).as_bytes()).into()
+}
+
+/// The root of the tree of addr0000 claiming 100 and addr0001 claiming 200
+fn merkle_root() -> String {
+    let mut pair = [leaf("addr0000", 100), leaf("addr0001", 200)];
+    pair.sort_unstable();
+    hex::encode(Sha256::digest(&pair.concat()))
+}
+
+/// Instantiates the contract with a first stage funded with 1000 prism, the deadline of which
+/// is in 100 seconds
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+            token: Addr::unchecked("prism0000"),
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "owner0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::RegisterMerkleRoot {
+            merkle_root: merkle_root(),
+            deadline: mock_env().block.time.seconds() + 100,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+
+    deps
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("ab"),
+            token: Addr::unchecked("prism0000"),
+        },
+    )
+    .unwrap_err();
+    assert!(matches!(err, StdError::GenericErr { .. }));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("ab")),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert!(matches!(err, StdError::GenericErr { .. }));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+}
+
+#[test]
+fn register_merkle_root() {
+    let mut deps = setup();
+
+    let res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.latest_stage, 1);
+
+    // only the owner funds the stages, with the reward token
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::RegisterMerkleRoot {
+            merkle_root: merkle_root(),
+            deadline: mock_env().block.time.seconds() + 100,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("token0000", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "owner0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::RegisterMerkleRoot {
+            merkle_root: "root".to_string(),
+            deadline: mock_env().block.time.seconds() + 100,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("invalid merkle root")));
+}
+
+#[test]
+fn claim() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(100u128),
+        proof: vec![hex::encode(leaf("addr0001", 200))],
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0000".to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res: IsClaimedResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::IsClaimed {
+                stage: 1,
+                address: "addr0000".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(res.is_claimed);
+
+    let res = execute(deps.as_mut(), env_at(10), mock_info("addr0000", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("already claimed")));
+
+    // another amount than the one in the tree
+    let msg = ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(300u128),
+        proof: vec![hex::encode(leaf("addr0000", 100))],
+    };
+    let res = execute(deps.as_mut(), env_at(10), mock_info("addr0001", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("verification failed")));
+
+    let msg = ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(200u128),
+        proof: vec![hex::encode(leaf("addr0000", 100))],
+    };
+    let res = execute(deps.as_mut(), env_at(100), mock_info("addr0001", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("stage expired")));
+}
+
+#[test]
+fn clawback() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::Claim {
+        stage: 1,
+        amount: Uint128::from(200u128),
+        proof: vec![hex::encode(leaf("addr0000", 100))],
+    };
+    let _res = execute(deps.as_mut(), env_at(10), mock_info("addr0001", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Clawback {
+        stage: 1,
+        recipient: Addr::unchecked("treasury0000"),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(99),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("stage not expired")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury0000".to_string(),
+                amount: Uint128::from(800u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    let res: StageResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Stage { stage: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        StageResponse {
+            stage: 1,
+            merkle_root: merkle_root(),
+            deadline: mock_env().block.time.seconds() + 100,
+            total_amount: Uint128::from(1000u128),
+            claimed_amount: Uint128::from(200u128),
+            clawed_back: true,
+        }
+    );
+
+    let res = execute(deps.as_mut(), env_at(100), mock_info("owner0000", &[]), msg);
+    assert_eq!(res, Err(StdError::generic_err("already clawed back")));
+}
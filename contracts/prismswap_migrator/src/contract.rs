@@ -0,0 +1,361 @@
+use crate::state::{Config, CONFIG};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::migrator::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LegacyPairCw20HookMsg,
+    LegacyPairInfo, LegacyPairQueryMsg, QueryMsg,
+};
+use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+use prismswap::querier::{query_pair_info, query_token_balance};
+use prismswap::staking::Cw20HookMsg as StakingCw20HookMsg;
+use prismswap::tax::TaxInfo;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        staking: msg
+            .staking
+            .map(|staking| deps.api.addr_validate(staking.as_str()))
+            .transpose()?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { owner, staking } => {
+            execute_update_config(deps, info, owner, staking)
+        }
+        ExecuteMsg::Callback(msg) => {
+            if env.contract.address != info.sender {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match msg {
+                CallbackMsg::ProvideLiquidity {
+                    receiver,
+                    asset_infos,
+                    prev_balances,
+                    slippage_tolerance,
+                    stake,
+                } => execute_provide_liquidity(
+                    deps,
+                    env,
+                    receiver,
+                    asset_infos,
+                    prev_balances,
+                    slippage_tolerance,
+                    stake,
+                ),
+                CallbackMsg::StakeLiquidity {
+                    receiver,
+                    lp_token,
+                    prev_balance,
+                } => execute_stake_liquidity(deps, env, receiver, lp_token, prev_balance),
+            }
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Migrate {
+            source_pair,
+            slippage_tolerance,
+            stake,
+        } => execute_migrate(
+            deps,
+            env,
+            info.sender,
+            sender,
+            cw20_msg.amount,
+            source_pair,
+            slippage_tolerance,
+            stake,
+        ),
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    staking: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(staking) = staking {
+        config.staking = Some(deps.api.addr_validate(staking.as_str())?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_migrate(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    sender: Addr,
+    amount: Uint128,
+    source_pair: Addr,
+    slippage_tolerance: Option<Decimal>,
+    stake: bool,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if stake && config.staking.is_none() {
+        return Err(StdError::generic_err("staking not enabled"));
+    }
+
+    // the sent LP token must be the one issued by the source pair
+    let source_pair_info: LegacyPairInfo =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: source_pair.to_string(),
+            msg: to_binary(&LegacyPairQueryMsg::Pair {})?,
+        }))?;
+    if source_pair_info.liquidity_token != lp_token.as_str() {
+        return Err(StdError::generic_err("invalid source pair"));
+    }
+
+    let asset_infos: [AssetInfo; 2] = [
+        source_pair_info.asset_infos[0].clone().into(),
+        source_pair_info.asset_infos[1].clone().into(),
+    ];
+
+    // fails if PrismSwap has no pair of the same assets
+    query_pair_info(&deps.querier, &config.factory, &asset_infos)?;
+
+    let prev_balances = [
+        asset_infos[0].query_pool(&deps.querier, &env.contract.address)?,
+        asset_infos[1].query_pool(&deps.querier, &env.contract.address)?,
+    ];
+
+    Ok(Response::new()
+        .add_messages(vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: lp_token.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: source_pair.to_string(),
+                    amount,
+                    msg: to_binary(&LegacyPairCw20HookMsg::WithdrawLiquidity {})?,
+                })?,
+            }),
+            callback_msg(
+                &env,
+                CallbackMsg::ProvideLiquidity {
+                    receiver: sender.clone(),
+                    asset_infos,
+                    prev_balances,
+                    slippage_tolerance,
+                    stake,
+                },
+            )?,
+        ])
+        .add_attributes(vec![
+            ("action", "migrate"),
+            ("sender", sender.as_str()),
+            ("source_pair", source_pair.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+pub fn execute_provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    receiver: Addr,
+    asset_infos: [AssetInfo; 2],
+    prev_balances: [Uint128; 2],
+    slippage_tolerance: Option<Decimal>,
+    stake: bool,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pair_info = query_pair_info(&deps.querier, &config.factory, &asset_infos)?;
+
+    let denoms: Vec<String> = asset_infos
+        .iter()
+        .filter_map(|asset_info| match asset_info {
+            AssetInfo::Native(denom) if denom != "uluna" => Some(denom.to_string()),
+            _ => None,
+        })
+        .collect();
+    let tax_info = if denoms.is_empty() {
+        None
+    } else {
+        Some(TaxInfo::query(&deps.querier, &denoms)?)
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    let mut assets: Vec<Asset> = vec![];
+    for (asset_info, prev_balance) in asset_infos.iter().zip(prev_balances.iter()) {
+        let balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+        let amount = balance.checked_sub(*prev_balance)?;
+
+        match asset_info {
+            AssetInfo::Native(denom) => {
+                // the tax is charged on top of the funds sent to the pair
+                let mut coin = Coin {
+                    denom: denom.to_string(),
+                    amount,
+                };
+                if let Some(tax_info) = &tax_info {
+                    coin.amount = tax_info.deduct_tax(&coin)?;
+                }
+                let amount = coin.amount;
+                funds.push(coin);
+                assets.push(Asset::new(asset_info.clone(), amount));
+            }
+            AssetInfo::Cw20(contract_addr) => {
+                messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: contract_addr.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                        spender: pair_info.contract_addr.to_string(),
+                        amount,
+                        expires: None,
+                    })?,
+                }));
+                assets.push(Asset::new(asset_info.clone(), amount));
+            }
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    let provide_receiver = if stake {
+        env.contract.address.clone()
+    } else {
+        receiver.clone()
+    };
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair_info.contract_addr.to_string(),
+        funds,
+        msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets: [assets[0].clone(), assets[1].clone()],
+            slippage_tolerance,
+            receiver: Some(provide_receiver.to_string()),
+            auto_stake: None,
+        })?,
+    }));
+
+    if stake {
+        let prev_balance = query_token_balance(
+            &deps.querier,
+            &pair_info.liquidity_token,
+            &env.contract.address,
+        )?;
+        messages.push(callback_msg(
+            &env,
+            CallbackMsg::StakeLiquidity {
+                receiver: receiver.clone(),
+                lp_token: pair_info.liquidity_token,
+                prev_balance,
+            },
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "provide_liquidity"),
+        ("receiver", receiver.as_str()),
+        ("pair", pair_info.contract_addr.as_str()),
+        ("assets", &format!("{}, {}", assets[0], assets[1])),
+    ]))
+}
+
+pub fn execute_stake_liquidity(
+    deps: DepsMut,
+    env: Env,
+    receiver: Addr,
+    lp_token: Addr,
+    prev_balance: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let staking = config
+        .staking
+        .ok_or_else(|| StdError::generic_err("staking not enabled"))?;
+
+    let amount = query_token_balance(&deps.querier, &lp_token, &env.contract.address)?
+        .checked_sub(prev_balance)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: lp_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: staking.to_string(),
+                amount,
+                msg: to_binary(&StakingCw20HookMsg::Bond {
+                    staker_addr: Some(receiver.clone()),
+                })?,
+            })?,
+        }))
+        .add_attributes(vec![
+            ("action", "stake_liquidity"),
+            ("receiver", receiver.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        staking: config.staking,
+    })
+}
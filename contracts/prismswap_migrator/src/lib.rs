@@ -0,0 +1,7 @@
+pub mod contract;
+pub mod state;
+
+#[cfg(test)]
+mod mock_querier;
+#[cfg(test)]
+mod testing;
@@ -0,0 +1,172 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use prismswap::asset::{pair_key, AssetInfo, PairInfo};
+use prismswap::migrator::LegacyPairInfo;
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FactoryQueryMsg {
+    Pair { asset_infos: [AssetInfo; 2] },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PairQueryMsg {
+    Pair {},
+}
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<TerraQueryWrapper>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    // the PrismSwap pairs of the factory, keyed by their sorted asset infos
+    pairs: HashMap<Vec<u8>, PairInfo>,
+    legacy_pairs: HashMap<String, LegacyPairInfo>,
+    tax_rate: Decimal,
+    tax_caps: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        // MockQuerier doesn't support Custom, so we ignore it completely here
+        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+        match &request {
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data })
+                if route == &TerraRoute::Treasury =>
+            {
+                match query_data {
+                    TerraQuery::TaxRate {} => {
+                        let res = TaxRateResponse {
+                            rate: self.tax_rate,
+                        };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    TerraQuery::TaxCap { denom } => {
+                        let cap = self.tax_caps.get(denom).copied().unwrap_or_default();
+                        let res = TaxCapResponse { cap };
+                        SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if from_binary::<PairQueryMsg>(msg).is_ok() =>
+            {
+                match self.legacy_pairs.get(contract_addr) {
+                    Some(v) => SystemResult::Ok(ContractResult::from(to_binary(v))),
+                    None => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    }),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg) {
+                Ok(FactoryQueryMsg::Pair { asset_infos }) => {
+                    match self.pairs.get(&pair_key(&asset_infos)) {
+                        Some(v) => SystemResult::Ok(ContractResult::from(to_binary(v))),
+                        None => SystemResult::Err(SystemError::InvalidRequest {
+                            error: "No pair info exists".to_string(),
+                            request: msg.as_slice().into(),
+                        }),
+                    }
+                }
+                _ => match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                },
+            },
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            pairs: HashMap::new(),
+            legacy_pairs: HashMap::new(),
+            tax_rate: Decimal::zero(),
+            tax_caps: HashMap::new(),
+        }
+    }
+
+    pub fn with_balance(&mut self, balances: &[(String, &[Coin])]) {
+        for (addr, balance) in balances {
+            self.base.update_balance(addr, balance.to_vec());
+        }
+    }
+
+    pub fn with_token_balance(&mut self, token: &str, address: &str, balance: Uint128) {
+        self.token_balances
+            .entry(token.to_string())
+            .or_default()
+            .insert(address.to_string(), balance);
+    }
+
+    pub fn with_pair(&mut self, pair_info: PairInfo) {
+        self.pairs
+            .insert(pair_key(&pair_info.asset_infos), pair_info);
+    }
+
+    pub fn with_legacy_pair(&mut self, pair_info: LegacyPairInfo) {
+        self.legacy_pairs
+            .insert(pair_info.contract_addr.clone(), pair_info);
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&str, Uint128)]) {
+        self.tax_rate = rate;
+        self.tax_caps = caps
+            .iter()
+            .map(|(denom, cap)| (denom.to_string(), *cap))
+            .collect();
+    }
+}
@@ -0,0 +1,15 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+use cw_storage_plus::Item;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub factory: Addr,
+    /// the staking contract the migrated LP tokens are bonded to, none to disable staking
+    pub staking: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
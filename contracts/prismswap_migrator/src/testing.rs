@@ -0,0 +1,333 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Decimal, OwnedDeps, StdError, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo, PairInfo};
+use prismswap::migrator::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LegacyAssetInfo,
+    LegacyPairCw20HookMsg, LegacyPairInfo, QueryMsg,
+};
+use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+use prismswap::staking::Cw20HookMsg as StakingCw20HookMsg;
+
+fn asset_infos() -> [AssetInfo; 2] {
+    [
+        AssetInfo::Native("uusd".to_string()),
+        AssetInfo::Cw20(Addr::unchecked("token0000")),
+    ]
+}
+
+/// Instantiates the migrator with a Terraswap uusd-token0000 pair and its PrismSwap counterpart
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_legacy_pair(LegacyPairInfo {
+        asset_infos: [
+            LegacyAssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            LegacyAssetInfo::Token {
+                contract_addr: "token0000".to_string(),
+            },
+        ],
+        contract_addr: "terraswap0000".to_string(),
+        liquidity_token: "terraswaplp0000".to_string(),
+    });
+    deps.querier.with_pair(PairInfo {
+        asset_infos: asset_infos(),
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+    });
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+            factory: Addr::unchecked("factory0000"),
+            staking: None,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn migrate_msg(stake: bool) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Migrate {
+            source_pair: Addr::unchecked("terraswap0000"),
+            slippage_tolerance: Some(Decimal::percent(1)),
+            stake,
+        })
+        .unwrap(),
+    })
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            staking: Some(Addr::unchecked("staking0000")),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: Some(Addr::unchecked("owner0001")),
+            staking: Some(Addr::unchecked("staking0000")),
+        },
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0001"),
+            factory: Addr::unchecked("factory0000"),
+            staking: Some(Addr::unchecked("staking0000")),
+        }
+    );
+}
+
+#[test]
+fn migrate() {
+    let mut deps = setup();
+    deps.querier
+        .with_balance(&[(MOCK_CONTRACT_ADDR.to_string(), &[coin(10u128, "uusd")])]);
+
+    // only the LP token of the source pair can be migrated
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("token0000", &[]),
+        migrate_msg(false),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid source pair"));
+
+    // staking is not enabled
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("terraswaplp0000", &[]),
+        migrate_msg(true),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("staking not enabled"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("terraswaplp0000", &[]),
+        migrate_msg(false),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "terraswaplp0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "terraswap0000".to_string(),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&LegacyPairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::ProvideLiquidity {
+                    receiver: Addr::unchecked("addr0000"),
+                    asset_infos: asset_infos(),
+                    prev_balances: [Uint128::from(10u128), Uint128::zero()],
+                    slippage_tolerance: Some(Decimal::percent(1)),
+                    stake: false,
+                }))
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the source pair sends 1010 uusd and 500 token0000 back
+    deps.querier
+        .with_balance(&[(MOCK_CONTRACT_ADDR.to_string(), &[coin(1020u128, "uusd")])]);
+    deps.querier
+        .with_token_balance("token0000", MOCK_CONTRACT_ADDR, Uint128::from(500u128));
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(1000u128))]);
+
+    let provide_msg = ExecuteMsg::Callback(CallbackMsg::ProvideLiquidity {
+        receiver: Addr::unchecked("addr0000"),
+        asset_infos: asset_infos(),
+        prev_balances: [Uint128::from(10u128), Uint128::zero()],
+        slippage_tolerance: Some(Decimal::percent(1)),
+        stake: false,
+    });
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        provide_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        provide_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: "pair0000".to_string(),
+                    amount: Uint128::from(500u128),
+                    expires: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                // 1010 uusd less the 10 uusd tax
+                funds: vec![coin(1000u128, "uusd")],
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        Asset::new(AssetInfo::Native("uusd".to_string()), 1000u128),
+                        Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 500u128),
+                    ],
+                    slippage_tolerance: Some(Decimal::percent(1)),
+                    receiver: Some("addr0000".to_string()),
+                    auto_stake: None,
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn migrate_and_stake() {
+    let mut deps = setup();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            staking: Some(Addr::unchecked("staking0000")),
+        },
+    )
+    .unwrap();
+
+    deps.querier
+        .with_balance(&[(MOCK_CONTRACT_ADDR.to_string(), &[coin(1000u128, "uusd")])]);
+    deps.querier
+        .with_token_balance("token0000", MOCK_CONTRACT_ADDR, Uint128::from(500u128));
+    deps.querier
+        .with_token_balance("liquidity0000", MOCK_CONTRACT_ADDR, Uint128::from(5u128));
+
+    // the PrismSwap LP tokens are minted to the migrator, which bonds them for the sender
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProvideLiquidity {
+            receiver: Addr::unchecked("addr0000"),
+            asset_infos: asset_infos(),
+            prev_balances: [Uint128::zero(), Uint128::zero()],
+            slippage_tolerance: None,
+            stake: true,
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![coin(1000u128, "uusd")],
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset::new(AssetInfo::Native("uusd".to_string()), 1000u128),
+                    Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 500u128),
+                ],
+                slippage_tolerance: None,
+                receiver: Some(MOCK_CONTRACT_ADDR.to_string()),
+                auto_stake: None,
+            })
+            .unwrap(),
+        }))
+    );
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::Callback(CallbackMsg::StakeLiquidity {
+                receiver: Addr::unchecked("addr0000"),
+                lp_token: Addr::unchecked("liquidity0000"),
+                prev_balance: Uint128::from(5u128),
+            }))
+            .unwrap(),
+        }))
+    );
+
+    deps.querier
+        .with_token_balance("liquidity0000", MOCK_CONTRACT_ADDR, Uint128::from(705u128));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::StakeLiquidity {
+            receiver: Addr::unchecked("addr0000"),
+            lp_token: Addr::unchecked("liquidity0000"),
+            prev_balance: Uint128::from(5u128),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "liquidity0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking0000".to_string(),
+                amount: Uint128::from(700u128),
+                msg: to_binary(&StakingCw20HookMsg::Bond {
+                    staker_addr: Some(Addr::unchecked("addr0000")),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+        }))]
+    );
+}
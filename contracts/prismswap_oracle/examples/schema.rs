@@ -0,0 +1,30 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use prismswap::oracle::{
+    AggregatePriceResponse, ConfigResponse, DecimalsResponse, EmaResponse, ExecuteMsg,
+    FeedResponse, InstantiateMsg, PairResponse, PairsResponse, PriceResponse, QueryMsg,
+    TwapResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(EmaResponse), &out_dir);
+    export_schema(&schema_for!(FeedResponse), &out_dir);
+    export_schema(&schema_for!(AggregatePriceResponse), &out_dir);
+    export_schema(&schema_for!(PairResponse), &out_dir);
+    export_schema(&schema_for!(PairsResponse), &out_dir);
+    export_schema(&schema_for!(PriceResponse), &out_dir);
+    export_schema(&schema_for!(DecimalsResponse), &out_dir);
+    export_schema(&schema_for!(TwapResponse), &out_dir);
+}
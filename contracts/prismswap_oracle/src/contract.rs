@@ -0,0 +1,799 @@
+use crate::state::{
+    read_all_pairs, read_pairs, Config, Ema, Feed, Observation, TrackedPair, CONFIG, FEEDS,
+    OBSERVATIONS, PAIRS,
+};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Storage, Uint128,
+};
+use cw_storage_plus::U64Key;
+
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use prismswap::asset::{AssetInfo, PrismSwapAssetInfo};
+use prismswap::oracle::{
+    AggregatePriceResponse, Aggregation, ConfigResponse, DecimalsResponse, EmaResponse, ExecuteMsg,
+    FeedResponse, InstantiateMsg, PairResponse, PairsResponse, PriceResponse, QueryMsg, RoutePrice,
+    TwapResponse, MAX_ROUTES,
+};
+use prismswap::pair::CumulativePricesResponse;
+use prismswap::querier::{query_pair_info, PrismSwapQuerier};
+
+/// prices are accumulated by the pairs with 18 decimals, same as [`Decimal`]
+const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000u128;
+/// the decimals of the rates answered, those of [`Decimal`]
+const RATE_DECIMALS: u32 = 18;
+/// the number of observations kept per pair
+pub const MAX_OBSERVATIONS: u64 = 144;
+/// 2^(-1/2^k) for k from 1 to 16, scaled by [`PRICE_PRECISION`]
+const HALVING_ROOTS: [u128; 16] = [
+    707106781186547524,
+    840896415253714543,
+    917004043204671231,
+    957603280698573646,
+    978572062087700134,
+    989228013193975484,
+    994599423483633175,
+    997296056085470126,
+    998647112890970173,
+    999323327502650752,
+    999661606496243683,
+    999830788931929063,
+    999915390886613497,
+    999957694548431132,
+    999978847050491929,
+    999989423469314464,
+];
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_observation_period(msg.observation_period)?;
+    assert_ema_half_lives(&msg.ema_half_lives)?;
+    assert_price_window(msg.price_window)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        observation_period: msg.observation_period,
+        ema_half_lives: msg.ema_half_lives,
+        price_window: msg.price_window,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::AddPair { asset_infos } => execute_add_pair(deps, info, asset_infos),
+        ExecuteMsg::RemovePair { asset_infos } => execute_remove_pair(deps, info, asset_infos),
+        ExecuteMsg::SetFeed {
+            base,
+            quote,
+            routes,
+            aggregation,
+        } => execute_set_feed(deps, info, base, quote, routes, aggregation),
+        ExecuteMsg::RemoveFeed { base, quote } => execute_remove_feed(deps, info, base, quote),
+        ExecuteMsg::Update {} => execute_update(deps),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            observation_period,
+            ema_half_lives,
+            price_window,
+        } => execute_update_config(
+            deps,
+            info,
+            owner,
+            observation_period,
+            ema_half_lives,
+            price_window,
+        ),
+    }
+}
+
+// Only owner can execute it
+pub fn execute_add_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    asset_infos[0].check(deps.api)?;
+    asset_infos[1].check(deps.api)?;
+
+    let pair_info = query_pair_info(&deps.querier, &config.factory, &asset_infos)?;
+    if PAIRS.has(deps.storage, &pair_info.contract_addr) {
+        return Err(StdError::generic_err("pair already tracked"));
+    }
+
+    // the first observation is recorded at once, the price history starting from it
+    let mut pair = TrackedPair {
+        asset_infos: pair_info.asset_infos,
+        observation_count: 0,
+        emas: vec![],
+    };
+    let cumulative_prices = query_cumulative_prices(deps.as_ref(), &pair_info.contract_addr)?;
+    record_observation(
+        deps.storage,
+        &config,
+        &pair_info.contract_addr,
+        &mut pair,
+        &cumulative_prices,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_pair"),
+        ("pair", pair_info.contract_addr.as_str()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_remove_pair(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: [AssetInfo; 2],
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let pair_info = query_pair_info(&deps.querier, &config.factory, &asset_infos)?;
+    let pair = load_pair(deps.as_ref(), &pair_info.contract_addr)?;
+
+    for i in 0..pair.observation_count.min(MAX_OBSERVATIONS) {
+        OBSERVATIONS.remove(deps.storage, (&pair_info.contract_addr, U64Key::new(i)));
+    }
+    PAIRS.remove(deps.storage, &pair_info.contract_addr);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_pair"),
+        ("pair", pair_info.contract_addr.as_str()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_set_feed(
+    deps: DepsMut,
+    info: MessageInfo,
+    base: AssetInfo,
+    quote: AssetInfo,
+    routes: Vec<Vec<AssetInfo>>,
+    aggregation: Aggregation,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if routes.is_empty() {
+        return Err(StdError::generic_err("feed must have at least one route"));
+    }
+
+    if routes.len() > MAX_ROUTES {
+        return Err(StdError::generic_err(format!(
+            "routes must not exceed {}",
+            MAX_ROUTES
+        )));
+    }
+
+    // every hop of a route must be priced by a tracked pair
+    for route in routes.iter() {
+        if route.len() < 2 || route[0] != base || route[route.len() - 1] != quote {
+            return Err(StdError::generic_err(
+                "route must start with the base and end with the quote asset",
+            ));
+        }
+
+        for hop in route.windows(2) {
+            let pair_info = query_pair_info(
+                &deps.querier,
+                &config.factory,
+                &[hop[0].clone(), hop[1].clone()],
+            )?;
+            load_pair(deps.as_ref(), &pair_info.contract_addr)?;
+        }
+    }
+
+    FEEDS.save(
+        deps.storage,
+        (base.as_bytes(), quote.as_bytes()),
+        &Feed {
+            routes,
+            aggregation,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_feed"),
+        ("base", &base.to_string()),
+        ("quote", &quote.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_remove_feed(
+    deps: DepsMut,
+    info: MessageInfo,
+    base: AssetInfo,
+    quote: AssetInfo,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    load_feed(deps.as_ref(), base.as_bytes(), quote.as_bytes())?;
+    FEEDS.remove(deps.storage, (base.as_bytes(), quote.as_bytes()));
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_feed"),
+        ("base", &base.to_string()),
+        ("quote", &quote.to_string()),
+    ]))
+}
+
+pub fn execute_update(deps: DepsMut) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let mut recorded: Vec<String> = vec![];
+    for (pair_addr, mut pair) in read_all_pairs(deps.storage)? {
+        let cumulative_prices = query_cumulative_prices(deps.as_ref(), &pair_addr)?;
+        let latest = latest_observation(deps.storage, &pair_addr, &pair)?;
+        if cumulative_prices.timestamp < latest.timestamp + config.observation_period {
+            continue;
+        }
+
+        record_observation(
+            deps.storage,
+            &config,
+            &pair_addr,
+            &mut pair,
+            &cumulative_prices,
+        )?;
+        recorded.push(pair_addr.to_string());
+    }
+
+    Ok(Response::new().add_attributes(vec![("action", "update"), ("pairs", &recorded.join(","))]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    observation_period: Option<u64>,
+    ema_half_lives: Option<Vec<u64>>,
+    price_window: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(observation_period) = observation_period {
+        assert_observation_period(observation_period)?;
+        config.observation_period = observation_period;
+    }
+
+    // the averages of new half-lives start at the next observation of every pair
+    if let Some(ema_half_lives) = ema_half_lives {
+        assert_ema_half_lives(&ema_half_lives)?;
+        config.ema_half_lives = ema_half_lives;
+    }
+
+    if let Some(price_window) = price_window {
+        assert_price_window(price_window)?;
+        config.price_window = price_window;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_observation_period(observation_period: u64) -> StdResult<()> {
+    if observation_period == 0 {
+        return Err(StdError::generic_err(
+            "observation period must be greater than 0",
+        ));
+    }
+
+    Ok(())
+}
+
+fn assert_ema_half_lives(ema_half_lives: &[u64]) -> StdResult<()> {
+    for (i, half_life) in ema_half_lives.iter().enumerate() {
+        if *half_life == 0 {
+            return Err(StdError::generic_err("half life must be greater than 0"));
+        }
+
+        if ema_half_lives[..i].contains(half_life) {
+            return Err(StdError::generic_err("duplicate half life"));
+        }
+    }
+
+    Ok(())
+}
+
+fn assert_price_window(price_window: u64) -> StdResult<()> {
+    if price_window == 0 {
+        return Err(StdError::generic_err("price window must be greater than 0"));
+    }
+
+    Ok(())
+}
+
+/// Loads the feed of the assets of the given denoms or token addresses
+fn load_feed(deps: Deps, base: &[u8], quote: &[u8]) -> StdResult<Feed> {
+    FEEDS
+        .may_load(deps.storage, (base, quote))?
+        .ok_or_else(|| StdError::generic_err("feed not found"))
+}
+
+fn load_pair(deps: Deps, pair_addr: &Addr) -> StdResult<TrackedPair> {
+    PAIRS
+        .may_load(deps.storage, pair_addr)?
+        .ok_or_else(|| StdError::generic_err("pair not tracked"))
+}
+
+fn query_cumulative_prices(deps: Deps, pair_addr: &Addr) -> StdResult<CumulativePricesResponse> {
+    PrismSwapQuerier::new(&deps.querier).cumulative_prices(pair_addr)
+}
+
+fn latest_observation(
+    storage: &dyn Storage,
+    pair_addr: &Addr,
+    pair: &TrackedPair,
+) -> StdResult<Observation> {
+    OBSERVATIONS.load(
+        storage,
+        (
+            pair_addr,
+            U64Key::new((pair.observation_count - 1) % MAX_OBSERVATIONS),
+        ),
+    )
+}
+
+/// Returns the average price between two accumulator values `elapsed` seconds apart
+fn average_price(end: Uint128, start: Uint128, elapsed: u64) -> Decimal256 {
+    Decimal256::from_ratio(
+        Uint256::from(end.wrapping_sub(start).u128() / elapsed as u128),
+        Uint256::from(PRICE_PRECISION),
+    )
+}
+
+/// Returns the weight an average keeps after `elapsed` seconds, 2^(-elapsed/half_life), with the
+/// fraction of a half-life rounded down to 16 bits
+fn decay(elapsed: u64, half_life: u64) -> Decimal256 {
+    let halvings = elapsed / half_life;
+    if halvings >= 64 {
+        return Decimal256::zero();
+    }
+
+    let mut decay = Decimal256::from_ratio(Uint256::one(), Uint256::from(1u128 << halvings));
+    let fraction = (((elapsed % half_life) as u128) << 16) / half_life as u128;
+    for (k, root) in HALVING_ROOTS.iter().enumerate() {
+        if fraction & (1 << (15 - k)) != 0 {
+            decay = decay
+                * Decimal256::from_ratio(Uint256::from(*root), Uint256::from(PRICE_PRECISION));
+        }
+    }
+
+    decay
+}
+
+/// Moves the averages of the pair towards the average prices since the previous observation,
+/// the averages of half-lives no longer configured being dropped
+fn update_emas(config: &Config, pair: &mut TrackedPair, prev: &Observation, next: &Observation) {
+    let elapsed = next.timestamp - prev.timestamp;
+    if elapsed == 0 {
+        return;
+    }
+
+    let price0 = average_price(next.price0_cumulative, prev.price0_cumulative, elapsed);
+    let price1 = average_price(next.price1_cumulative, prev.price1_cumulative, elapsed);
+
+    pair.emas = config
+        .ema_half_lives
+        .iter()
+        .map(
+            |half_life| match pair.emas.iter().find(|ema| ema.half_life == *half_life) {
+                Some(ema) => {
+                    let decay = decay(elapsed, *half_life);
+                    let average = |ema_price: Decimal, price: Decimal256| -> Decimal {
+                        (Decimal256::from(ema_price) * decay + price * (Decimal256::one() - decay))
+                            .into()
+                    };
+
+                    Ema {
+                        half_life: *half_life,
+                        price0: average(ema.price0, price0),
+                        price1: average(ema.price1, price1),
+                    }
+                }
+                None => Ema {
+                    half_life: *half_life,
+                    price0: price0.into(),
+                    price1: price1.into(),
+                },
+            },
+        )
+        .collect();
+}
+
+/// Records the accumulators in the ring buffer of the pair, overwriting the oldest observation
+/// once [`MAX_OBSERVATIONS`] are kept, and updates the averages of the pair
+fn record_observation(
+    storage: &mut dyn Storage,
+    config: &Config,
+    pair_addr: &Addr,
+    pair: &mut TrackedPair,
+    cumulative_prices: &CumulativePricesResponse,
+) -> StdResult<()> {
+    let observation = Observation {
+        timestamp: cumulative_prices.timestamp,
+        price0_cumulative: cumulative_prices.price0_cumulative,
+        price1_cumulative: cumulative_prices.price1_cumulative,
+    };
+    if pair.observation_count > 0 {
+        let prev = latest_observation(storage, pair_addr, pair)?;
+        update_emas(config, pair, &prev, &observation);
+    }
+
+    OBSERVATIONS.save(
+        storage,
+        (
+            pair_addr,
+            U64Key::new(pair.observation_count % MAX_OBSERVATIONS),
+        ),
+        &observation,
+    )?;
+    pair.observation_count += 1;
+    PAIRS.save(storage, pair_addr, pair)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Pairs { start_after, limit } => {
+            to_binary(&query_pairs(deps, start_after, limit)?)
+        }
+        QueryMsg::Price { base, quote } => to_binary(&query_price(deps, base, quote)?),
+        QueryMsg::Decimals {} => to_binary(&DecimalsResponse {
+            decimals: RATE_DECIMALS,
+        }),
+        QueryMsg::Twap {
+            base,
+            quote,
+            window,
+        } => to_binary(&query_twap(deps, base, quote, window)?),
+        QueryMsg::Ema {
+            base,
+            quote,
+            half_life,
+        } => to_binary(&query_ema(deps, base, quote, half_life)?),
+        QueryMsg::Feed { base, quote } => to_binary(&query_feed(deps, base, quote)?),
+        QueryMsg::AggregatePrice {
+            base,
+            quote,
+            window,
+        } => to_binary(&query_aggregate_price(deps, base, quote, window)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        observation_period: config.observation_period,
+        ema_half_lives: config.ema_half_lives,
+        price_window: config.price_window,
+    })
+}
+
+pub fn query_pairs(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<PairsResponse> {
+    let pairs = read_pairs(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(pair_addr, pair)| {
+            let last_observation_time = match pair.observation_count {
+                0 => None,
+                _ => Some(latest_observation(deps.storage, &pair_addr, &pair)?.timestamp),
+            };
+
+            Ok(PairResponse {
+                pair: pair_addr,
+                asset_infos: pair.asset_infos,
+                observation_count: pair.observation_count,
+                last_observation_time,
+            })
+        })
+        .collect::<StdResult<Vec<PairResponse>>>()?;
+
+    Ok(PairsResponse { pairs })
+}
+
+/// The time-weighted average price of an asset in another over their pair, with the live depth
+/// of the pool of the latter and the time the pair was last observed
+struct PairTwap {
+    price: Decimal256,
+    window: u64,
+    ask_pool: Uint128,
+    last_updated: u64,
+}
+
+/// Returns the average price of `offer` in `ask` between the most recent observation taken at
+/// least `window` seconds ago and the live accumulators of their pair
+fn query_pair_twap(
+    deps: Deps,
+    config: &Config,
+    offer: &AssetInfo,
+    ask: &AssetInfo,
+    window: u64,
+) -> StdResult<PairTwap> {
+    if window == 0 {
+        return Err(StdError::generic_err("window must be greater than 0"));
+    }
+
+    let pair_info = query_pair_info(
+        &deps.querier,
+        &config.factory,
+        &[offer.clone(), ask.clone()],
+    )?;
+    let pair = load_pair(deps, &pair_info.contract_addr)?;
+    let cumulative_prices = query_cumulative_prices(deps, &pair_info.contract_addr)?;
+
+    let mut start: Option<Observation> = None;
+    let mut last_updated = 0u64;
+    for i in pair.observation_count.saturating_sub(MAX_OBSERVATIONS)..pair.observation_count {
+        let observation = OBSERVATIONS.load(
+            deps.storage,
+            (&pair_info.contract_addr, U64Key::new(i % MAX_OBSERVATIONS)),
+        )?;
+        last_updated = observation.timestamp;
+        if observation.timestamp + window <= cumulative_prices.timestamp {
+            start = Some(observation);
+        }
+    }
+    let start = start.ok_or_else(|| StdError::generic_err("not enough price history"))?;
+
+    let elapsed = cumulative_prices.timestamp - start.timestamp;
+    let (end_cumulative, start_cumulative, ask_pool) = if *offer == cumulative_prices.assets[0].info
+    {
+        (
+            cumulative_prices.price0_cumulative,
+            start.price0_cumulative,
+            cumulative_prices.assets[1].amount,
+        )
+    } else {
+        (
+            cumulative_prices.price1_cumulative,
+            start.price1_cumulative,
+            cumulative_prices.assets[0].amount,
+        )
+    };
+
+    Ok(PairTwap {
+        price: average_price(end_cumulative, start_cumulative, elapsed),
+        window: elapsed,
+        ask_pool,
+        last_updated,
+    })
+}
+
+/// Returns the rate of `base` in `quote` from their feed over the price window, the assets
+/// being given as denoms or token addresses as lending protocols query them
+pub fn query_price(deps: Deps, base: String, quote: String) -> StdResult<PriceResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let feed = load_feed(deps, base.as_bytes(), quote.as_bytes())?;
+    let aggregate_price = aggregate_feed_price(deps, &config, feed, config.price_window)?;
+
+    let last_updated = aggregate_price
+        .route_prices
+        .iter()
+        .map(|route| route.last_updated)
+        .min()
+        .unwrap_or_default();
+
+    Ok(PriceResponse {
+        rate: aggregate_price.price,
+        last_updated_base: last_updated,
+        last_updated_quote: last_updated,
+    })
+}
+
+pub fn query_twap(
+    deps: Deps,
+    base: AssetInfo,
+    quote: AssetInfo,
+    window: u64,
+) -> StdResult<TwapResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let twap = query_pair_twap(deps, &config, &base, &quote, window)?;
+
+    Ok(TwapResponse {
+        price: twap.price.into(),
+        window: twap.window,
+    })
+}
+
+pub fn query_ema(
+    deps: Deps,
+    base: AssetInfo,
+    quote: AssetInfo,
+    half_life: u64,
+) -> StdResult<EmaResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if !config.ema_half_lives.contains(&half_life) {
+        return Err(StdError::generic_err("half life not tracked"));
+    }
+
+    let pair_info = query_pair_info(&deps.querier, &config.factory, &[base.clone(), quote])?;
+    let pair = load_pair(deps, &pair_info.contract_addr)?;
+    let ema = pair
+        .emas
+        .iter()
+        .find(|ema| ema.half_life == half_life)
+        .ok_or_else(|| StdError::generic_err("not enough price history"))?;
+    let last_updated = latest_observation(deps.storage, &pair_info.contract_addr, &pair)?.timestamp;
+
+    Ok(EmaResponse {
+        price: if base == pair.asset_infos[0] {
+            ema.price0
+        } else {
+            ema.price1
+        },
+        half_life,
+        last_updated,
+    })
+}
+
+pub fn query_feed(deps: Deps, base: AssetInfo, quote: AssetInfo) -> StdResult<FeedResponse> {
+    let feed = load_feed(deps, base.as_bytes(), quote.as_bytes())?;
+
+    Ok(FeedResponse {
+        base,
+        quote,
+        routes: feed.routes,
+        aggregation: feed.aggregation,
+    })
+}
+
+/// Returns the price of a route, the product of the TWAPs along it, and the depth of its
+/// shallowest pool valued in the quote asset
+fn query_route_price(
+    deps: Deps,
+    config: &Config,
+    route: &[AssetInfo],
+    window: u64,
+) -> StdResult<RoutePrice> {
+    let twaps = route
+        .windows(2)
+        .map(|hop| query_pair_twap(deps, config, &hop[0], &hop[1], window))
+        .collect::<StdResult<Vec<PairTwap>>>()?;
+
+    // the pools are valued from the last hop backwards, the value of an asset in the quote
+    // asset being the product of the prices of the hops after it
+    let mut quote_value = Decimal256::one();
+    let mut liquidity: Option<Uint128> = None;
+    for twap in twaps.iter().rev() {
+        let value: Decimal = quote_value.into();
+        let depth = twap.ask_pool * value;
+        liquidity = Some(liquidity.map_or(depth, |liquidity| liquidity.min(depth)));
+        quote_value = quote_value * twap.price;
+    }
+
+    Ok(RoutePrice {
+        route: route.to_vec(),
+        price: quote_value.into(),
+        liquidity: liquidity.unwrap_or_default(),
+        last_updated: twaps
+            .iter()
+            .map(|twap| twap.last_updated)
+            .min()
+            .unwrap_or_default(),
+    })
+}
+
+pub fn query_aggregate_price(
+    deps: Deps,
+    base: AssetInfo,
+    quote: AssetInfo,
+    window: u64,
+) -> StdResult<AggregatePriceResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let feed = load_feed(deps, base.as_bytes(), quote.as_bytes())?;
+
+    aggregate_feed_price(deps, &config, feed, window)
+}
+
+/// Combines the prices of the routes of the feed according to its aggregation
+fn aggregate_feed_price(
+    deps: Deps,
+    config: &Config,
+    feed: Feed,
+    window: u64,
+) -> StdResult<AggregatePriceResponse> {
+    let route_prices = feed
+        .routes
+        .iter()
+        .map(|route| query_route_price(deps, config, route, window))
+        .collect::<StdResult<Vec<RoutePrice>>>()?;
+
+    let price = match feed.aggregation {
+        Aggregation::Median => {
+            let mut prices: Vec<Decimal> = route_prices.iter().map(|route| route.price).collect();
+            prices.sort();
+
+            let mid = prices.len() / 2;
+            match prices.len() % 2 {
+                0 => (prices[mid - 1] + prices[mid]) / Uint128::from(2u128),
+                _ => prices[mid],
+            }
+        }
+        Aggregation::LiquidityWeighted => {
+            let total_liquidity = route_prices
+                .iter()
+                .fold(Uint128::zero(), |total, route| total + route.liquidity);
+            if total_liquidity.is_zero() {
+                return Err(StdError::generic_err("routes have no liquidity"));
+            }
+
+            route_prices
+                .iter()
+                .fold(Decimal256::zero(), |price, route| {
+                    price
+                        + Decimal256::from(route.price)
+                            * Decimal256::from_ratio(
+                                Uint256::from(route.liquidity),
+                                Uint256::from(total_liquidity),
+                            )
+                })
+                .into()
+        }
+    };
+
+    Ok(AggregatePriceResponse {
+        price,
+        route_prices,
+    })
+}
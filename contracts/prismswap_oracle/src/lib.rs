@@ -0,0 +1,8 @@
+pub mod contract;
+pub mod state;
+
+#[cfg(test)]
+mod testing;
+
+#[cfg(test)]
+mod mock_querier;
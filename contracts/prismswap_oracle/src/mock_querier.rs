@@ -0,0 +1,171 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use prismswap::asset::{Asset, AssetInfo, PairInfo};
+use prismswap::factory::QueryMsg as FactoryQueryMsg;
+use prismswap::pair::{CumulativePricesResponse, QueryMsg as PairQueryMsg};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct MockPair {
+    pub asset_infos: Vec<AssetInfo>,
+    pub pools: [Uint128; 2],
+    pub price0_cumulative: Uint128,
+    pub price1_cumulative: Uint128,
+    pub timestamp: u64,
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    pairs: HashMap<String, MockPair>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<FactoryQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    FactoryQueryMsg::Pair { asset_infos } => {
+                        let pair = self.pairs.iter().find(|(_, pair)| {
+                            pair.asset_infos.contains(&asset_infos[0])
+                                && pair.asset_infos.contains(&asset_infos[1])
+                        });
+                        match pair {
+                            Some((contract_addr, pair)) => SystemResult::Ok(ContractResult::Ok(
+                                to_binary(&PairInfo {
+                                    asset_infos: [
+                                        pair.asset_infos[0].clone(),
+                                        pair.asset_infos[1].clone(),
+                                    ],
+                                    contract_addr: Addr::unchecked(contract_addr),
+                                    liquidity_token: Addr::unchecked("liquidity0000"),
+                                })
+                                .unwrap(),
+                            )),
+                            None => {
+                                SystemResult::Ok(ContractResult::Err("pair not found".to_string()))
+                            }
+                        }
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if from_binary::<PairQueryMsg>(msg).is_ok() =>
+            {
+                let pair = self.pairs.get(contract_addr).unwrap();
+                match from_binary(msg).unwrap() {
+                    PairQueryMsg::CumulativePrices {} => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&CumulativePricesResponse {
+                            assets: [
+                                Asset::new(pair.asset_infos[0].clone(), pair.pools[0]),
+                                Asset::new(pair.asset_infos[1].clone(), pair.pools[1]),
+                            ],
+                            price0_cumulative: pair.price0_cumulative,
+                            price1_cumulative: pair.price1_cumulative,
+                            timestamp: pair.timestamp,
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        let mut pairs = HashMap::new();
+        pairs.insert(
+            "pair0000".to_string(),
+            MockPair {
+                asset_infos: vec![
+                    AssetInfo::Native("uusd".to_string()),
+                    AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                ],
+                pools: [Uint128::from(1_000_000u128), Uint128::from(1_000_000u128)],
+                ..MockPair::default()
+            },
+        );
+
+        WasmMockQuerier { base, pairs }
+    }
+
+    // configure a pair of the factory, in the order the pair stores its assets
+    pub fn with_pair(&mut self, pair: &str, asset_infos: [AssetInfo; 2], pools: [Uint128; 2]) {
+        self.pairs.insert(
+            pair.to_string(),
+            MockPair {
+                asset_infos: asset_infos.to_vec(),
+                pools,
+                ..MockPair::default()
+            },
+        );
+    }
+
+    // configure the accumulators of a pair at the given time
+    pub fn with_pair_cumulative_prices(
+        &mut self,
+        pair: &str,
+        price0_cumulative: Uint128,
+        price1_cumulative: Uint128,
+        timestamp: u64,
+    ) {
+        let pair = self.pairs.get_mut(pair).unwrap();
+        pair.price0_cumulative = price0_cumulative;
+        pair.price1_cumulative = price1_cumulative;
+        pair.timestamp = timestamp;
+    }
+
+    // configure the accumulators of the default pair at the given time
+    pub fn with_cumulative_prices(
+        &mut self,
+        price0_cumulative: Uint128,
+        price1_cumulative: Uint128,
+        timestamp: u64,
+    ) {
+        self.with_pair_cumulative_prices(
+            "pair0000",
+            price0_cumulative,
+            price1_cumulative,
+            timestamp,
+        )
+    }
+}
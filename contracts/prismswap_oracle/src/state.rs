@@ -0,0 +1,89 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::asset::AssetInfo;
+use prismswap::oracle::Aggregation;
+use prismswap::pagination::{calc_limit, start_after_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub observation_period: u64,
+    pub ema_half_lives: Vec<u64>,
+    pub price_window: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TrackedPair {
+    pub asset_infos: [AssetInfo; 2],
+    /// the number of observations recorded, the next one stored at this index modulo the
+    /// history kept
+    pub observation_count: u64,
+    /// the moving averages of the configured half-lives, started at the second observation
+    pub emas: Vec<Ema>,
+}
+
+/// ## Description
+/// The exponential moving averages of the pair prices, ordered as the pair assets
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Ema {
+    pub half_life: u64,
+    pub price0: Decimal,
+    pub price1: Decimal,
+}
+
+/// the tracked pairs, keyed by pair address
+pub const PAIRS: Map<&Addr, TrackedPair> = Map::new("pairs");
+
+/// ## Description
+/// The price accumulators of a pair, ordered as the pair assets, at the time observed
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Observation {
+    pub timestamp: u64,
+    pub price0_cumulative: Uint128,
+    pub price1_cumulative: Uint128,
+}
+
+/// the observations of the pairs, keyed by pair address then index in the ring buffer
+pub const OBSERVATIONS: Map<(&Addr, U64Key), Observation> = Map::new("observations");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Feed {
+    /// the paths of assets from the base to the quote asset
+    pub routes: Vec<Vec<AssetInfo>>,
+    pub aggregation: Aggregation,
+}
+
+/// the feeds, keyed by base then quote asset
+pub const FEEDS: Map<(&[u8], &[u8]), Feed> = Map::new("feeds");
+
+pub fn read_all_pairs(storage: &dyn Storage) -> StdResult<Vec<(Addr, TrackedPair)>> {
+    PAIRS
+        .range(storage, None, None, Order::Ascending)
+        .map(pair_item)
+        .collect()
+}
+
+fn pair_item(item: StdResult<(Vec<u8>, TrackedPair)>) -> StdResult<(Addr, TrackedPair)> {
+    let (k, v) = item?;
+    Ok((Addr::unchecked(String::from_utf8(k)?), v))
+}
+
+pub fn read_pairs(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, TrackedPair)>> {
+    let start = start_after_bound(start_after.map(|pair| pair.as_bytes().to_vec()));
+
+    PAIRS
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(pair_item)
+        .collect()
+}
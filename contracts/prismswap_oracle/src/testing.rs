@@ -0,0 +1,654 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{attr, from_binary, Addr, Decimal, Deps, StdError, StdResult, Uint128};
+use prismswap::asset::AssetInfo;
+use prismswap::oracle::{
+    AggregatePriceResponse, Aggregation, ConfigResponse, DecimalsResponse, EmaResponse, ExecuteMsg,
+    InstantiateMsg, PairResponse, PairsResponse, PriceResponse, QueryMsg, RoutePrice, TwapResponse,
+};
+
+/// prices are accumulated with 18 decimals
+const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000u128;
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        factory: Addr::unchecked("factory0000"),
+        observation_period: 600,
+        ema_half_lives: vec![600, 3600],
+        price_window: 600,
+    }
+}
+
+fn asset_infos() -> [AssetInfo; 2] {
+    [
+        AssetInfo::Native("uusd".to_string()),
+        AssetInfo::Cw20(Addr::unchecked("prism0000")),
+    ]
+}
+
+fn query_twap(
+    deps: Deps,
+    base: AssetInfo,
+    quote: AssetInfo,
+    window: u64,
+) -> StdResult<TwapResponse> {
+    let res = query(
+        deps,
+        mock_env(),
+        QueryMsg::Twap {
+            base,
+            quote,
+            window,
+        },
+    )?;
+    from_binary(&res)
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.observation_period = 0;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("observation period must be greater than 0")
+    );
+
+    let mut msg = instantiate_msg();
+    msg.ema_half_lives = vec![600, 600];
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("duplicate half life"));
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            factory: Addr::unchecked("factory0000"),
+            observation_period: 600,
+            ema_half_lives: vec![600, 3600],
+            price_window: 600,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            observation_period: Some(300),
+            ema_half_lives: None,
+            price_window: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: Some(Addr::unchecked("owner0001")),
+            observation_period: Some(300),
+            ema_half_lives: Some(vec![60]),
+            price_window: Some(1200),
+        },
+    )
+    .unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.observation_period, 300);
+    assert_eq!(config.ema_half_lives, vec![60]);
+    assert_eq!(config.price_window, 1200);
+}
+
+#[test]
+fn add_and_remove_pair() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::AddPair {
+        asset_infos: asset_infos(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    deps.querier
+        .with_cumulative_prices(Uint128::zero(), Uint128::zero(), 1000);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![attr("action", "add_pair"), attr("pair", "pair0000")]
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("pair already tracked"));
+
+    let pairs: PairsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pairs {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        pairs.pairs,
+        vec![PairResponse {
+            pair: Addr::unchecked("pair0000"),
+            asset_infos: asset_infos(),
+            observation_count: 1,
+            last_observation_time: Some(1000),
+        }]
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RemovePair {
+            asset_infos: asset_infos(),
+        },
+    )
+    .unwrap();
+
+    let err = query_twap(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        600,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("pair not tracked"));
+}
+
+#[test]
+fn update_and_query_price() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    deps.querier
+        .with_cumulative_prices(Uint128::zero(), Uint128::zero(), 1000);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::AddPair {
+            asset_infos: asset_infos(),
+        },
+    )
+    .unwrap();
+
+    // uusd is worth 2 PRISM for the first 600 seconds
+    deps.querier.with_cumulative_prices(
+        Uint128::from(300 * 2 * PRICE_PRECISION),
+        Uint128::from(300 * PRICE_PRECISION / 2),
+        1300,
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Update {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("pairs", ""));
+
+    let err = query_twap(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        600,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("not enough price history"));
+
+    deps.querier.with_cumulative_prices(
+        Uint128::from(600 * 2 * PRICE_PRECISION),
+        Uint128::from(600 * PRICE_PRECISION / 2),
+        1600,
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Update {},
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("pairs", "pair0000"));
+
+    // then 4 PRISM for the next 600 seconds
+    deps.querier.with_cumulative_prices(
+        Uint128::from(600 * 2 * PRICE_PRECISION + 600 * 4 * PRICE_PRECISION),
+        Uint128::from(600 * PRICE_PRECISION / 2 + 600 * PRICE_PRECISION / 4),
+        2200,
+    );
+
+    let price = query_twap(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        600,
+    )
+    .unwrap();
+    assert_eq!(
+        price,
+        TwapResponse {
+            price: Decimal::from_ratio(4u128, 1u128),
+            window: 600,
+        }
+    );
+
+    let price = query_twap(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        1200,
+    )
+    .unwrap();
+    assert_eq!(
+        price,
+        TwapResponse {
+            price: Decimal::from_ratio(3u128, 1u128),
+            window: 1200,
+        }
+    );
+
+    // the inverse price is read from the other accumulator
+    let price = query_twap(
+        deps.as_ref(),
+        asset_infos()[1].clone(),
+        asset_infos()[0].clone(),
+        1200,
+    )
+    .unwrap();
+    assert_eq!(price.price, Decimal::from_ratio(3u128, 8u128));
+
+    let err = query_twap(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        1500,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("not enough price history"));
+
+    let err = query_twap(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        0,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("window must be greater than 0"));
+}
+
+fn query_ema(
+    deps: Deps,
+    base: AssetInfo,
+    quote: AssetInfo,
+    half_life: u64,
+) -> StdResult<EmaResponse> {
+    let res = query(
+        deps,
+        mock_env(),
+        QueryMsg::Ema {
+            base,
+            quote,
+            half_life,
+        },
+    )?;
+    from_binary(&res)
+}
+
+#[test]
+fn ema_prices() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    deps.querier
+        .with_cumulative_prices(Uint128::zero(), Uint128::zero(), 1000);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::AddPair {
+            asset_infos: asset_infos(),
+        },
+    )
+    .unwrap();
+
+    let err = query_ema(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        600,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("not enough price history"));
+
+    let err = query_ema(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        60,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("half life not tracked"));
+
+    // the averages start at the price of the first interval, 2 PRISM
+    deps.querier.with_cumulative_prices(
+        Uint128::from(600 * 2 * PRICE_PRECISION),
+        Uint128::from(600 * PRICE_PRECISION / 2),
+        1600,
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Update {},
+    )
+    .unwrap();
+
+    let ema = query_ema(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        600,
+    )
+    .unwrap();
+    assert_eq!(
+        ema,
+        EmaResponse {
+            price: Decimal::from_ratio(2u128, 1u128),
+            half_life: 600,
+            last_updated: 1600,
+        }
+    );
+
+    // one half-life at 4 PRISM moves the short average halfway, the inverse alike
+    deps.querier.with_cumulative_prices(
+        Uint128::from(600 * 2 * PRICE_PRECISION + 600 * 4 * PRICE_PRECISION),
+        Uint128::from(600 * PRICE_PRECISION / 2 + 600 * PRICE_PRECISION / 4),
+        2200,
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Update {},
+    )
+    .unwrap();
+
+    let ema = query_ema(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        600,
+    )
+    .unwrap();
+    assert_eq!(ema.price, Decimal::from_ratio(3u128, 1u128));
+    let ema = query_ema(
+        deps.as_ref(),
+        asset_infos()[1].clone(),
+        asset_infos()[0].clone(),
+        600,
+    )
+    .unwrap();
+    assert_eq!(ema.price, Decimal::from_ratio(3u128, 8u128));
+
+    // a sixth of the long half-life keeps 2^(-1/6) of the average
+    let ema = query_ema(
+        deps.as_ref(),
+        asset_infos()[0].clone(),
+        asset_infos()[1].clone(),
+        3600,
+    )
+    .unwrap();
+    assert!(ema.price > Decimal::from_ratio(2218u128, 1000u128));
+    assert!(ema.price < Decimal::from_ratio(2219u128, 1000u128));
+}
+
+#[test]
+fn aggregate_prices() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let uusd = AssetInfo::Native("uusd".to_string());
+    let uluna = AssetInfo::Native("uluna".to_string());
+    let prism = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+
+    deps.querier.with_pair(
+        "pair0001",
+        [prism.clone(), uluna.clone()],
+        [Uint128::from(10_000_000u128), Uint128::from(100_000u128)],
+    );
+    deps.querier.with_pair(
+        "pair0002",
+        [uluna.clone(), uusd.clone()],
+        [Uint128::from(100_000u128), Uint128::from(6_000_000u128)],
+    );
+    for pair in ["pair0000", "pair0001", "pair0002"] {
+        deps.querier
+            .with_pair_cumulative_prices(pair, Uint128::zero(), Uint128::zero(), 1000);
+    }
+    for asset_infos in [
+        [uusd.clone(), prism.clone()],
+        [prism.clone(), uluna.clone()],
+    ] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner0000", &[]),
+            ExecuteMsg::AddPair { asset_infos },
+        )
+        .unwrap();
+    }
+
+    let direct = vec![prism.clone(), uusd.clone()];
+    let via_luna = vec![prism.clone(), uluna.clone(), uusd.clone()];
+    let set_feed = |routes: Vec<Vec<AssetInfo>>, aggregation: Aggregation| ExecuteMsg::SetFeed {
+        base: prism.clone(),
+        quote: uusd.clone(),
+        routes,
+        aggregation,
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        set_feed(
+            vec![vec![prism.clone(), uluna.clone()]],
+            Aggregation::Median,
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("route must start with the base and end with the quote asset")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        set_feed(vec![direct.clone(), via_luna.clone()], Aggregation::Median),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("pair not tracked"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::AddPair {
+            asset_infos: [uluna.clone(), uusd.clone()],
+        },
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        set_feed(vec![direct.clone(), via_luna.clone()], Aggregation::Median),
+    )
+    .unwrap();
+
+    // PRISM is worth 0.5 uusd directly and 0.01 uluna of 60 uusd through LUNA
+    deps.querier.with_pair_cumulative_prices(
+        "pair0000",
+        Uint128::from(600 * 2 * PRICE_PRECISION),
+        Uint128::from(600 * PRICE_PRECISION / 2),
+        1600,
+    );
+    deps.querier.with_pair_cumulative_prices(
+        "pair0001",
+        Uint128::from(600 * PRICE_PRECISION / 100),
+        Uint128::from(600 * 100 * PRICE_PRECISION),
+        1600,
+    );
+    deps.querier.with_pair_cumulative_prices(
+        "pair0002",
+        Uint128::from(600 * 60 * PRICE_PRECISION),
+        Uint128::from(600 * PRICE_PRECISION / 60),
+        1600,
+    );
+
+    let query_msg = QueryMsg::AggregatePrice {
+        base: prism.clone(),
+        quote: uusd.clone(),
+        window: 600,
+    };
+    let res: AggregatePriceResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        AggregatePriceResponse {
+            price: Decimal::from_ratio(55u128, 100u128),
+            route_prices: vec![
+                RoutePrice {
+                    route: direct.clone(),
+                    price: Decimal::percent(50),
+                    liquidity: Uint128::from(1_000_000u128),
+                    last_updated: 1000,
+                },
+                RoutePrice {
+                    route: via_luna.clone(),
+                    price: Decimal::percent(60),
+                    liquidity: Uint128::from(6_000_000u128),
+                    last_updated: 1000,
+                },
+            ],
+        }
+    );
+
+    // lending protocols query the feeds by denom or token address
+    let res: PriceResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Price {
+                base: "prism0000".to_string(),
+                quote: "uusd".to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        PriceResponse {
+            rate: Decimal::from_ratio(55u128, 100u128),
+            last_updated_base: 1000,
+            last_updated_quote: 1000,
+        }
+    );
+
+    let res: DecimalsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Decimals {}).unwrap()).unwrap();
+    assert_eq!(res.decimals, 18);
+
+    // the deeper route through LUNA weighs six times the direct one
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        set_feed(vec![direct, via_luna], Aggregation::LiquidityWeighted),
+    )
+    .unwrap();
+    let res: AggregatePriceResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), query_msg.clone()).unwrap()).unwrap();
+    assert!(res.price > Decimal::from_ratio(5857u128, 10000u128));
+    assert!(res.price < Decimal::from_ratio(5858u128, 10000u128));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RemoveFeed {
+            base: prism,
+            quote: uusd,
+        },
+    )
+    .unwrap();
+    let err = query(deps.as_ref(), mock_env(), query_msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("feed not found"));
+}
@@ -0,0 +1,249 @@
+use crate::state::{read_offers, Offer, NEXT_OFFER_ID, OFFERS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::U64Key;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset};
+use prismswap::otc::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, OfferResponse, OffersResponse, QueryMsg,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    NEXT_OFFER_ID.save(deps.storage, &1)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::CreateOffer {
+            ask,
+            counterparty,
+            expiry,
+        } => {
+            let offer = native_asset(&info)?;
+            execute_create_offer(deps, env, info.sender, offer, ask, counterparty, expiry)
+        }
+        ExecuteMsg::Fill { offer_id } => {
+            let asset = native_asset(&info)?;
+            execute_fill(deps, env, info.sender, asset, offer_id)
+        }
+        ExecuteMsg::Reclaim { offer_id } => execute_reclaim(deps, env, info, offer_id),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+    let asset = Asset::new(AssetInfo::Cw20(info.sender), cw20_msg.amount);
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateOffer {
+            ask,
+            counterparty,
+            expiry,
+        } => execute_create_offer(deps, env, sender, asset, ask, counterparty, expiry),
+        Cw20HookMsg::Fill { offer_id } => execute_fill(deps, env, sender, asset, offer_id),
+    }
+}
+
+fn native_asset(info: &MessageInfo) -> StdResult<Asset> {
+    if info.funds.len() != 1 {
+        return Err(StdError::generic_err("must send exactly one native asset"));
+    }
+
+    Ok(Asset::from(info.funds[0].clone()))
+}
+
+pub fn execute_create_offer(
+    deps: DepsMut,
+    env: Env,
+    maker: Addr,
+    offer: Asset,
+    ask: Asset,
+    counterparty: Option<Addr>,
+    expiry: u64,
+) -> StdResult<Response> {
+    if offer.amount.is_zero() || ask.amount.is_zero() {
+        return Err(StdError::generic_err(
+            "offer and ask amounts must be greater than 0",
+        ));
+    }
+
+    if offer.info == ask.info {
+        return Err(StdError::generic_err("offer and ask assets must differ"));
+    }
+
+    if expiry <= env.block.time.seconds() {
+        return Err(StdError::generic_err("expiry must be in the future"));
+    }
+
+    let offer_id = NEXT_OFFER_ID.load(deps.storage)?;
+    NEXT_OFFER_ID.save(deps.storage, &(offer_id + 1))?;
+
+    OFFERS.save(
+        deps.storage,
+        U64Key::new(offer_id),
+        &Offer {
+            maker: maker.clone(),
+            offer: offer.clone(),
+            ask: ask.clone(),
+            counterparty,
+            expiry,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "create_offer"),
+        ("offer_id", &offer_id.to_string()),
+        ("maker", maker.as_str()),
+        ("offer", &offer.to_string()),
+        ("ask", &ask.to_string()),
+    ]))
+}
+
+pub fn execute_fill(
+    deps: DepsMut,
+    env: Env,
+    taker: Addr,
+    asset: Asset,
+    offer_id: u64,
+) -> StdResult<Response> {
+    let offer = OFFERS
+        .may_load(deps.storage, U64Key::new(offer_id))?
+        .ok_or_else(|| StdError::generic_err("offer not found"))?;
+
+    if let Some(counterparty) = &offer.counterparty {
+        if *counterparty != taker {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+    }
+
+    if env.block.time.seconds() >= offer.expiry {
+        return Err(StdError::generic_err("offer expired"));
+    }
+
+    if asset != offer.ask {
+        return Err(StdError::generic_err("must send the exact ask asset"));
+    }
+
+    OFFERS.remove(deps.storage, U64Key::new(offer_id));
+
+    // the tax of the native legs is charged on top of them against the amounts swapped
+    Ok(Response::new()
+        .add_messages(
+            offer
+                .ask
+                .into_transfer_msg_with_tax(&deps.querier, &offer.maker)?,
+        )
+        .add_messages(
+            offer
+                .offer
+                .into_transfer_msg_with_tax(&deps.querier, &taker)?,
+        )
+        .add_attributes(vec![
+            ("action", "fill"),
+            ("offer_id", &offer_id.to_string()),
+            ("maker", offer.maker.as_str()),
+            ("taker", taker.as_str()),
+        ]))
+}
+
+pub fn execute_reclaim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    offer_id: u64,
+) -> StdResult<Response> {
+    let offer = OFFERS
+        .may_load(deps.storage, U64Key::new(offer_id))?
+        .ok_or_else(|| StdError::generic_err("offer not found"))?;
+
+    // permission check
+    if info.sender != offer.maker {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // the counterparty can rely on the offer until it expires
+    if env.block.time.seconds() < offer.expiry {
+        return Err(StdError::generic_err("offer not expired"));
+    }
+
+    OFFERS.remove(deps.storage, U64Key::new(offer_id));
+
+    // the tax of a native refund is charged on top of it against the escrowed amount
+    Ok(Response::new()
+        .add_messages(
+            offer
+                .offer
+                .into_transfer_msg_with_tax(&deps.querier, &offer.maker)?,
+        )
+        .add_attributes(vec![
+            ("action", "reclaim"),
+            ("offer_id", &offer_id.to_string()),
+            ("maker", offer.maker.as_str()),
+        ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Offer { offer_id } => to_binary(&query_offer(deps, offer_id)?),
+        QueryMsg::Offers {
+            maker,
+            start_after,
+            limit,
+        } => to_binary(&query_offers(deps, maker, start_after, limit)?),
+    }
+}
+
+pub fn query_offer(deps: Deps, offer_id: u64) -> StdResult<OfferResponse> {
+    let offer = OFFERS
+        .may_load(deps.storage, U64Key::new(offer_id))?
+        .ok_or_else(|| StdError::generic_err("offer not found"))?;
+
+    Ok(offer_response(offer_id, offer))
+}
+
+pub fn query_offers(
+    deps: Deps,
+    maker: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OffersResponse> {
+    let offers = read_offers(deps.storage, maker, start_after, limit)?
+        .into_iter()
+        .map(|(offer_id, offer)| offer_response(offer_id, offer))
+        .collect();
+
+    Ok(OffersResponse { offers })
+}
+
+fn offer_response(offer_id: u64, offer: Offer) -> OfferResponse {
+    OfferResponse {
+        offer_id,
+        maker: offer.maker,
+        offer: offer.offer,
+        ask: offer.ask,
+        counterparty: offer.counterparty,
+        expiry: offer.expiry,
+    }
+}
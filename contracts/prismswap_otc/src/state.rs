@@ -0,0 +1,46 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::asset::Asset;
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offer {
+    pub maker: Addr,
+    pub offer: Asset,
+    pub ask: Asset,
+    pub counterparty: Option<Addr>,
+    pub expiry: u64,
+}
+
+/// the open offers, removed once filled or reclaimed
+pub const OFFERS: Map<U64Key, Offer> = Map::new("offers");
+
+/// the id of the next offer created
+pub const NEXT_OFFER_ID: Item<u64> = Item::new("next_offer_id");
+
+pub fn read_offers(
+    storage: &dyn Storage,
+    maker: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Offer)>> {
+    let start = start_after_id_bound(start_after);
+
+    OFFERS
+        .range(storage, start, None, Order::Ascending)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .filter(|item| match (&maker, item) {
+            (Some(maker), Ok((_, offer))) => offer.maker == *maker,
+            _ => true,
+        })
+        .take(calc_limit(limit))
+        .collect()
+}
@@ -0,0 +1,305 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, Env, OwnedDeps, StdError,
+    SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::otc::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, OfferResponse, OffersResponse, QueryMsg,
+};
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+/// Instantiates the contract with an offer of 1000 prism for 2000 uusd to treasury0001,
+/// expiring in 100 seconds
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "treasury0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::CreateOffer {
+            ask: Asset::new(
+                AssetInfo::Native("uusd".to_string()),
+                Uint128::from(2000u128),
+            ),
+            counterparty: Some(Addr::unchecked("treasury0001")),
+            expiry: mock_env().block.time.seconds() + 100,
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+
+    deps
+}
+
+#[test]
+fn fill() {
+    let mut deps = setup();
+
+    let res: OfferResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Offer { offer_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        OfferResponse {
+            offer_id: 1,
+            maker: Addr::unchecked("treasury0000"),
+            offer: Asset::new(
+                AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                Uint128::from(1000u128),
+            ),
+            ask: Asset::new(
+                AssetInfo::Native("uusd".to_string()),
+                Uint128::from(2000u128),
+            ),
+            counterparty: Some(Addr::unchecked("treasury0001")),
+            expiry: mock_env().block.time.seconds() + 100,
+        }
+    );
+
+    let msg = ExecuteMsg::Fill { offer_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("treasury0001", &coins(2000u128, "uusd")),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "treasury0000".to_string(),
+                amount: coins(2000u128, "uusd"),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "treasury0001".to_string(),
+                    amount: Uint128::from(1000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    // an offer is filled once
+    let res = execute(
+        deps.as_mut(),
+        env_at(20),
+        mock_info("treasury0001", &coins(2000u128, "uusd")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("offer not found")));
+}
+
+#[test]
+fn fill_invalid() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::Fill { offer_id: 1 };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("addr0000", &coins(2000u128, "uusd")),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("treasury0001", &coins(1999u128, "uusd")),
+        msg.clone(),
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("must send the exact ask asset"))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("treasury0001", &coins(2000u128, "uusd")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("offer expired")));
+}
+
+#[test]
+fn reclaim() {
+    let mut deps = setup();
+
+    // a native offer open to anyone
+    let msg = ExecuteMsg::CreateOffer {
+        ask: Asset::new(
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            Uint128::from(500u128),
+        ),
+        counterparty: None,
+        expiry: mock_env().block.time.seconds() + 50,
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("treasury0001", &coins(1000u128, "uusd")),
+        msg,
+    )
+    .unwrap();
+
+    let res: OffersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Offers {
+                maker: Some(Addr::unchecked("treasury0001")),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.offers.iter().map(|o| o.offer_id).collect::<Vec<u64>>(),
+        vec![2]
+    );
+
+    let msg = ExecuteMsg::Reclaim { offer_id: 2 };
+    let res = execute(
+        deps.as_mut(),
+        env_at(49),
+        mock_info("treasury0001", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("offer not expired")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(50),
+        mock_info("treasury0000", &[]),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(50),
+        mock_info("treasury0001", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury0001".to_string(),
+            amount: coins(1000u128, "uusd"),
+        }))]
+    );
+
+    let res: OffersResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Offers {
+                maker: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.offers.iter().map(|o| o.offer_id).collect::<Vec<u64>>(),
+        vec![1]
+    );
+}
+
+#[test]
+fn fill_and_reclaim_with_tax() {
+    let mut deps = setup();
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(1000000u128))]);
+
+    for _ in 0..2 {
+        let msg = ExecuteMsg::CreateOffer {
+            ask: Asset::new(
+                AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                Uint128::from(500u128),
+            ),
+            counterparty: None,
+            expiry: mock_env().block.time.seconds() + 50,
+        };
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("treasury0001", &coins(1000u128, "uusd")),
+            msg,
+        )
+        .unwrap();
+    }
+
+    // the tax of the native legs is charged against them, 1980 + 20 and 990 + 10
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("treasury0001", &coins(2000u128, "uusd")),
+        ExecuteMsg::Fill { offer_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury0000".to_string(),
+            amount: coins(1980u128, "uusd"),
+        }))
+    );
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0001".to_string(),
+        amount: Uint128::from(500u128),
+        msg: to_binary(&Cw20HookMsg::Fill { offer_id: 2 }).unwrap(),
+    });
+    let res = execute(deps.as_mut(), env_at(10), mock_info("prism0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: coins(990u128, "uusd"),
+        }))
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(50),
+        mock_info("treasury0001", &[]),
+        ExecuteMsg::Reclaim { offer_id: 3 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury0001".to_string(),
+            amount: coins(990u128, "uusd"),
+        }))]
+    );
+}
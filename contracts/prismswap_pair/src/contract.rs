@@ -1,25 +1,33 @@
 use crate::error::ContractError;
 use crate::parse_reply::parse_reply_instantiate_data;
 use crate::state::{Config, CONFIG};
+use crate::twap::{accumulate_prices, query_cumulative_prices, query_twap};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
-    QuerierWrapper, Reply, ReplyOn, Response, StdError, SubMsg, Uint128, WasmMsg,
+    QuerierWrapper, Reply, ReplyOn, Response, SubMsg, Uint128, WasmMsg,
 };
 
 use cosmwasm_bignumber::{Decimal256, Uint256};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
-use integer_sqrt::IntegerSquareRoot;
 use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::events::{
+    provide_liquidity_attributes, withdraw_liquidity_attributes, SwapEvent, LIQUIDITY_TOKEN_ADDR,
+    PROTOCOL_FEE_AMOUNT,
+};
 use prismswap::factory::FeeInfoResponse;
+use prismswap::fee::SwapBreakdown;
+use prismswap::hooks::AutoStakeHookMsg;
+use prismswap::math::{self, decimal_atomics};
 use prismswap::pair::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg,
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, PoolResponse, QueryMsg,
     ReverseSimulationResponse, SimulationResponse,
 };
-use prismswap::querier::{query_fee_info, query_supply, query_token_symbol};
+use prismswap::params::{DEFAULT_MAX_SPREAD_KEY, PAUSED_KEY};
+use prismswap::querier::{query_fee_info, query_param, query_supply, query_token_symbol};
 use prismswap::token::InstantiateMsg as TokenInstantiateMsg;
 
 const INSTANTIATE_REPLY_ID: u64 = 1;
@@ -37,6 +45,7 @@ pub fn instantiate(
     if msg.asset_infos[0] == msg.asset_infos[1] {
         return Err(ContractError::DoublingAssets {});
     }
+    msg.fee_config.validate(deps.api)?;
 
     let config = Config {
         pair_info: PairInfo {
@@ -51,28 +60,36 @@ pub fn instantiate(
 
     let token_name = format_lp_token_name(&msg.asset_infos, &deps.querier)?;
 
-    Ok(Response::new().add_submessage(SubMsg {
-        msg: WasmMsg::Instantiate {
-            code_id: msg.token_code_id,
-            msg: to_binary(&TokenInstantiateMsg {
-                name: token_name,
-                symbol: "uLP".to_string(),
-                decimals: 6,
-                initial_balances: vec![],
-                mint: Some(MinterResponse {
-                    minter: env.contract.address.to_string(),
-                    cap: None,
-                }),
-            })?,
-            funds: vec![],
-            admin: None,
-            label: "".to_string(),
-        }
-        .into(),
-        id: INSTANTIATE_REPLY_ID,
-        gas_limit: None,
-        reply_on: ReplyOn::Success,
-    }))
+    Ok(Response::new()
+        .add_attributes(vec![
+            ("total_fee", msg.fee_config.total_fee.to_string()),
+            (
+                "protocol_fee_share",
+                msg.fee_config.protocol_fee_share.to_string(),
+            ),
+        ])
+        .add_submessage(SubMsg {
+            msg: WasmMsg::Instantiate {
+                code_id: msg.token_code_id,
+                msg: to_binary(&TokenInstantiateMsg {
+                    name: token_name,
+                    symbol: "uLP".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(MinterResponse {
+                        minter: env.contract.address.to_string(),
+                        cap: None,
+                    }),
+                })?,
+                funds: vec![],
+                admin: None,
+                label: "PrismSwap liquidity token".to_string(),
+            }
+            .into(),
+            id: INSTANTIATE_REPLY_ID,
+            gas_limit: None,
+            reply_on: ReplyOn::Success,
+        }))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -88,10 +105,19 @@ pub fn execute(
             assets,
             slippage_tolerance,
             receiver,
+            auto_stake,
         } => {
             assets[0].info.check(deps.api)?;
             assets[1].info.check(deps.api)?;
-            provide_liquidity(deps, env, info, assets, slippage_tolerance, receiver)
+            provide_liquidity(
+                deps,
+                env,
+                info,
+                assets,
+                slippage_tolerance,
+                receiver,
+                auto_stake.unwrap_or(false),
+            )
         }
         ExecuteMsg::Swap {
             offer_asset,
@@ -200,7 +226,7 @@ pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractE
     config.pair_info.liquidity_token = deps.api.addr_validate(&liquidity_token)?;
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new().add_attribute("liquidity_token_addr", liquidity_token))
+    Ok(Response::new().add_attribute(LIQUIDITY_TOKEN_ADDR, liquidity_token))
 }
 
 /// CONTRACT - should approve contract to use the amount of token
@@ -211,12 +237,33 @@ pub fn provide_liquidity(
     assets: [Asset; 2],
     slippage_tolerance: Option<Decimal>,
     receiver: Option<String>,
+    auto_stake: bool,
 ) -> Result<Response, ContractError> {
     for asset in assets.iter() {
         asset.assert_sent_native_token_balance(&info)?;
     }
 
     let config: Config = CONFIG.load(deps.storage)?;
+
+    // Liquidity is only withdrawn while the factory is in withdraw-only mode
+    let fee_info: FeeInfoResponse = query_fee_info(
+        &deps.querier,
+        &config.factory,
+        &config.pair_info.asset_infos,
+    )?;
+    if fee_info.withdraw_only {
+        return Err(ContractError::WithdrawOnly {});
+    }
+    let staking_contract = if auto_stake {
+        Some(
+            fee_info
+                .staking_contract
+                .ok_or(ContractError::AutoStakeDisabled {})?,
+        )
+    } else {
+        None
+    };
+
     let mut pools: [Asset; 2] = config
         .pair_info
         .query_pools(&deps.querier, &env.contract.address)?;
@@ -253,48 +300,66 @@ pub fn provide_liquidity(
         }
     }
 
+    accumulate_prices(deps.storage, &env, [pools[0].amount, pools[1].amount])?;
+
     let total_share = query_supply(&deps.querier, &config.pair_info.liquidity_token)?;
-    let share = if total_share.is_zero() {
-        // Initial share = collateral amount
-        Uint128::from((deposits[0].u128() * deposits[1].u128()).integer_sqrt())
-    } else {
+    if !total_share.is_zero() {
         // assert slippage tolerance
         assert_slippage_tolerance(&slippage_tolerance, &deposits, &pools)?;
+    }
 
-        // min(1, 2)
-        // 1. sqrt(deposit_0 * exchange_rate_0_to_1 * deposit_0) * (total_share / sqrt(pool_0 * pool_1))
-        // == deposit_0 * total_share / pool_0
-        // 2. sqrt(deposit_1 * exchange_rate_1_to_0 * deposit_1) * (total_share / sqrt(pool_1 * pool_1))
-        // == deposit_1 * total_share / pool_1
-        std::cmp::min(
-            deposits[0].multiply_ratio(total_share, pools[0].amount),
-            deposits[1].multiply_ratio(total_share, pools[1].amount),
-        )
-    };
+    let share = Uint128::from(math::compute_share(
+        [deposits[0].u128(), deposits[1].u128()],
+        [pools[0].amount.u128(), pools[1].amount.u128()],
+        total_share.u128(),
+    ));
 
     // prevent providing free token
     if share.is_zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
 
-    // mint LP token to sender
+    // mint LP token to sender, or to the pair to bond them on behalf of the receiver
     let receiver = receiver.unwrap_or_else(|| info.sender.to_string());
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: config.pair_info.liquidity_token.to_string(),
-        msg: to_binary(&Cw20ExecuteMsg::Mint {
-            recipient: receiver.to_string(),
-            amount: share,
-        })?,
-        funds: vec![],
-    }));
-
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("action", "provide_liquidity"),
-        ("sender", info.sender.as_str()),
-        ("receiver", receiver.as_str()),
-        ("assets", &format!("{}, {}", assets[0], assets[1])),
-        ("share", &share.to_string()),
-    ]))
+    match staking_contract {
+        Some(staking_contract) => {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.pair_info.liquidity_token.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: env.contract.address.to_string(),
+                    amount: share,
+                })?,
+                funds: vec![],
+            }));
+            messages.push(
+                AutoStakeHookMsg::Bond {
+                    staker_addr: Some(deps.api.addr_validate(&receiver)?),
+                }
+                .into_send_msg(
+                    &config.pair_info.liquidity_token,
+                    &staking_contract,
+                    share,
+                )?,
+            );
+        }
+        None => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.pair_info.liquidity_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: receiver.to_string(),
+                amount: share,
+            })?,
+            funds: vec![],
+        })),
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(provide_liquidity_attributes(
+            info.sender.as_str(),
+            &receiver,
+            &assets,
+            share,
+        )))
 }
 
 pub fn withdraw_liquidity(
@@ -315,12 +380,19 @@ pub fn withdraw_liquidity(
         .query_pools(&deps.querier, &env.contract.address)?;
     let total_share: Uint128 = query_supply(&deps.querier, &config.pair_info.liquidity_token)?;
 
-    let share_ratio: Decimal = Decimal::from_ratio(amount, total_share);
+    accumulate_prices(deps.storage, &env, [pools[0].amount, pools[1].amount])?;
+
+    let refund_amounts = math::compute_withdraw_amounts(
+        [pools[0].amount.u128(), pools[1].amount.u128()],
+        amount.u128(),
+        total_share.u128(),
+    );
     let refund_assets: Vec<Asset> = pools
         .iter()
-        .map(|a| Asset {
+        .zip(refund_amounts.iter())
+        .map(|(a, refund_amount)| Asset {
             info: a.info.clone(),
-            amount: a.amount * share_ratio,
+            amount: Uint128::from(*refund_amount),
         })
         .collect();
 
@@ -336,15 +408,11 @@ pub fn withdraw_liquidity(
                 funds: vec![],
             }),
         ])
-        .add_attributes(vec![
-            ("action", "withdraw_liquidity"),
-            ("sender", sender.as_str()),
-            ("withdrawn_share", &amount.to_string()),
-            (
-                "refund_assets",
-                &format!("{}, {}", refund_assets[0], refund_assets[1]),
-            ),
-        ]))
+        .add_attributes(withdraw_liquidity_attributes(
+            sender.as_str(),
+            amount,
+            &refund_assets,
+        )))
 }
 
 // CONTRACT - a user must do token approval
@@ -388,12 +456,36 @@ pub fn swap(
         return Err(ContractError::AssetMismatch {});
     }
 
+    if offer_pool.info == pools[0].info {
+        accumulate_prices(deps.storage, &env, [offer_pool.amount, ask_pool.amount])?;
+    } else {
+        accumulate_prices(deps.storage, &env, [ask_pool.amount, offer_pool.amount])?;
+    }
+
     // Get pool fee configuration from factory
     let fee_info: FeeInfoResponse = query_fee_info(
         &deps.querier,
         &config.factory,
         &config.pair_info.asset_infos,
     )?;
+    if fee_info.withdraw_only {
+        return Err(ContractError::WithdrawOnly {});
+    }
+
+    // Read the protocol-wide tunables, the swap settings taking precedence
+    let mut max_spread = max_spread;
+    if let Some(param_registry) = &fee_info.param_registry {
+        if let Some(paused) = query_param(&deps.querier, param_registry, PAUSED_KEY)? {
+            if paused.as_bool()? {
+                return Err(ContractError::Paused {});
+            }
+        }
+        if max_spread.is_none() {
+            max_spread = query_param(&deps.querier, param_registry, DEFAULT_MAX_SPREAD_KEY)?
+                .map(|value| value.as_decimal())
+                .transpose()?;
+        }
+    }
 
     let offer_amount = offer_asset.amount;
     let (return_amount, spread_amount, commission_amount) = compute_swap(
@@ -401,7 +493,7 @@ pub fn swap(
         ask_pool.amount,
         offer_amount,
         fee_info.fee_config.total_fee,
-    );
+    )?;
 
     // check max spread limit if exist
     assert_max_spread(
@@ -425,7 +517,7 @@ pub fn swap(
 
     let protocol_fee_asset = Asset {
         info: ask_pool.info.clone(),
-        amount: commission_amount * fee_info.fee_config.protocol_fee,
+        amount: fee_info.fee_config.protocol_fee_amount(commission_amount),
     };
     if !protocol_fee_asset.amount.is_zero() {
         messages.push(protocol_fee_asset.transfer_msg(fee_info.collector)?);
@@ -433,25 +525,24 @@ pub fn swap(
 
     // 1. send collateral token from the contract to a user
     // 2. send inactive commission to collector
-    Ok(Response::new().add_messages(messages).add_attributes(vec![
-        ("action", "swap"),
-        ("sender", sender.as_str()),
-        ("receiver", receiver.as_str()),
-        ("offer_asset", &offer_asset.info.to_string()),
-        ("ask_asset", &ask_pool.info.to_string()),
-        ("offer_amount", &offer_amount.to_string()),
-        ("return_amount", &return_amount.to_string()),
-        ("spread_amount", &spread_amount.to_string()),
-        ("commission_amount", &commission_amount.to_string()),
-        (
-            "protocol_fee_amount",
-            &protocol_fee_asset.amount.to_string(),
-        ),
-    ]))
+    let event = SwapEvent {
+        sender: sender.as_str(),
+        receiver: receiver.as_str(),
+        offer_asset: &offer_asset.info,
+        ask_asset: &ask_pool.info,
+        offer_amount,
+        return_amount,
+        spread_amount,
+        commission_amount,
+    };
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(event.attributes())
+        .add_attribute(PROTOCOL_FEE_AMOUNT, protocol_fee_asset.amount))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
     match msg {
         QueryMsg::Config {} => Ok(to_binary(&query_config(deps)?)?),
         QueryMsg::Pair {} => Ok(to_binary(&query_pair_info(deps)?)?),
@@ -462,9 +553,20 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> Result<Binary, ContractErr
         QueryMsg::ReverseSimulation { ask_asset } => {
             Ok(to_binary(&query_reverse_simulation(deps, ask_asset)?)?)
         }
+        QueryMsg::CumulativePrices {} => Ok(to_binary(&query_cumulative_prices(deps, env)?)?),
+        QueryMsg::Twap { window } => Ok(to_binary(&query_twap(deps, env, window)?)?),
     }
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(_deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::V1_0_0 {} => {}
+    }
+
+    Ok(Response::default())
+}
+
 pub fn query_config(deps: Deps) -> Result<ConfigResponse, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
 
@@ -532,12 +634,19 @@ pub fn query_simulation(
         ask_pool.amount,
         offer_asset.amount,
         fee_info.fee_config.total_fee,
-    );
+    )?;
 
     Ok(SimulationResponse {
         return_amount,
         spread_amount,
         commission_amount,
+        breakdown: SwapBreakdown {
+            return_amount,
+            spread_amount,
+            commission_amount,
+            protocol_fee: fee_info.fee_config.protocol_fee_amount(commission_amount),
+            tax: Uint128::zero(),
+        },
     })
 }
 
@@ -589,31 +698,19 @@ fn compute_swap(
     ask_pool: Uint128,
     offer_amount: Uint128,
     commission_rate: Decimal,
-) -> (Uint128, Uint128, Uint128) {
-    let offer_pool: Uint256 = offer_pool.into();
-    let ask_pool: Uint256 = ask_pool.into();
-    let offer_amount: Uint256 = offer_amount.into();
-    let commission_rate: Decimal256 = commission_rate.into();
-
-    // offer => ask
-    // ask_amount = (ask_pool - cp / (offer_pool + offer_amount)) * (1 - commission_rate)
-    let cp: Uint256 = offer_pool * ask_pool;
-    let return_amount: Uint256 = (Decimal256::from_uint256(ask_pool)
-        - Decimal256::from_ratio(cp, offer_pool + offer_amount))
-        * Uint256::one();
-
-    // calculate spread & commission
-    let spread_amount: Uint256 =
-        (offer_amount * Decimal256::from_ratio(ask_pool, offer_pool)) - return_amount;
-    let commission_amount: Uint256 = return_amount * commission_rate;
-
-    // commission will be absorbed to pool
-    let return_amount: Uint256 = return_amount - commission_amount;
-    (
+) -> Result<(Uint128, Uint128, Uint128), ContractError> {
+    let (return_amount, spread_amount, commission_amount) = math::compute_swap(
+        offer_pool.u128(),
+        ask_pool.u128(),
+        offer_amount.u128(),
+        decimal_atomics(commission_rate),
+    )
+    .ok_or(ContractError::InsufficientLiquidity {})?;
+    Ok((
         return_amount.into(),
         spread_amount.into(),
         commission_amount.into(),
-    )
+    ))
 }
 
 fn compute_offer_amount(
@@ -622,24 +719,18 @@ fn compute_offer_amount(
     ask_amount: Uint128,
     commission_rate: Decimal,
 ) -> Result<(Uint128, Uint128, Uint128), ContractError> {
-    // ask => offer
-    // offer_amount = cp / (ask_pool - ask_amount / (1 - commission_rate)) - offer_pool
-    let cp = Uint256::from(offer_pool) * Uint256::from(ask_pool);
-    let one_minus_commission = Decimal256::one() - Decimal256::from(commission_rate);
-    let inv_one_minus_commission: Decimal = (Decimal256::one() / one_minus_commission).into();
-
-    let offer_amount: Uint128 = Uint128::from(cp.multiply_ratio(
-        Uint256::one(),
-        Uint256::from(ask_pool.checked_sub(ask_amount * inv_one_minus_commission)?),
+    let (offer_amount, spread_amount, commission_amount) = math::compute_offer_amount(
+        offer_pool.u128(),
+        ask_pool.u128(),
+        ask_amount.u128(),
+        decimal_atomics(commission_rate),
+    )
+    .ok_or(ContractError::InsufficientLiquidity {})?;
+    Ok((
+        offer_amount.into(),
+        spread_amount.into(),
+        commission_amount.into(),
     ))
-    .checked_sub(offer_pool)?;
-
-    let before_commission_deduction = ask_amount * inv_one_minus_commission;
-    let spread_amount = (offer_amount * Decimal::from_ratio(ask_pool, offer_pool))
-        .checked_sub(before_commission_deduction)
-        .unwrap_or_else(|_| Uint128::zero());
-    let commission_amount = before_commission_deduction * commission_rate;
-    Ok((offer_amount, spread_amount, commission_amount))
 }
 
 /// If `belief_price` and `max_spread` both are given,
@@ -690,7 +781,7 @@ fn assert_slippage_tolerance(
     if let Some(slippage_tolerance) = *slippage_tolerance {
         let slippage_tolerance: Decimal256 = slippage_tolerance.into();
         if slippage_tolerance > Decimal256::one() {
-            return Err(StdError::generic_err("slippage_tolerance cannot bigger than 1").into());
+            return Err(ContractError::InvalidSlippageTolerance {});
         }
 
         let one_minus_slippage_tolerance = Decimal256::one() - slippage_tolerance;
@@ -12,6 +12,9 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("The factory has no staking contract to auto-stake in")]
+    AutoStakeDisabled {},
+
     #[error("Invalid zero amount")]
     InvalidZeroAmount {},
 
@@ -21,9 +24,15 @@ pub enum ContractError {
     #[error("Max slippage assertion")]
     MaxSlippageAssertion {},
 
+    #[error("Slippage tolerance cannot be bigger than 1")]
+    InvalidSlippageTolerance {},
+
     #[error("Asset mismatch")]
     AssetMismatch {},
 
+    #[error("Not enough liquidity in the pool")]
+    InsufficientLiquidity {},
+
     #[error("Too small offer amount")]
     TooSmallOfferAmount {},
 
@@ -32,4 +41,16 @@ pub enum ContractError {
 
     #[error("ParseReplyError")]
     ParseReplyError {},
+
+    #[error("Twap window must be greater than zero")]
+    InvalidTwapWindow {},
+
+    #[error("Not enough price history for the twap window")]
+    NotEnoughPriceHistory {},
+
+    #[error("Pair is in withdraw-only mode")]
+    WithdrawOnly {},
+
+    #[error("Swaps are paused")]
+    Paused {},
 }
@@ -1,8 +1,9 @@
 pub mod contract;
+pub mod error;
 pub mod state;
 
-mod error;
 mod parse_reply;
+mod twap;
 
 #[cfg(test)]
 mod testing;
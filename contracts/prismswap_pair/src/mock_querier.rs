@@ -9,6 +9,7 @@ use std::str::FromStr;
 
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 use prismswap::factory::QueryMsg::FeeInfo;
+use prismswap::params::{ParamResponse, ParamValue, QueryMsg as ParamsQueryMsg};
 use terra_cosmwasm::TerraQueryWrapper;
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
@@ -29,6 +30,9 @@ pub fn mock_dependencies(
 pub struct WasmMockQuerier {
     base: MockQuerier<TerraQueryWrapper>,
     token_querier: TokenQuerier,
+    withdraw_only: bool,
+    staking_contract: Option<Addr>,
+    params: HashMap<String, ParamValue>,
 }
 
 #[derive(Clone, Default)]
@@ -87,8 +91,28 @@ impl WasmMockQuerier {
                                 collector: Addr::unchecked("collector"),
                                 fee_config: FeeConfig {
                                     total_fee: Decimal::from_str("0.003").unwrap(),
-                                    protocol_fee: Decimal::percent(50),
+                                    protocol_fee_share: Decimal::percent(50),
+                                    collector: None,
                                 },
+                                withdraw_only: self.withdraw_only,
+                                param_registry: if self.params.is_empty() {
+                                    None
+                                } else {
+                                    Some(Addr::unchecked("params0000"))
+                                },
+                                staking_contract: self.staking_contract.clone(),
+                            })
+                            .into(),
+                        ),
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
+                } else if contract_addr == "params0000" {
+                    match from_binary(msg).unwrap() {
+                        ParamsQueryMsg::Param { key } => SystemResult::Ok(
+                            to_binary(&ParamResponse {
+                                value: self.params.get(&key).cloned(),
+                                key,
+                                bounds: None,
                             })
                             .into(),
                         ),
@@ -172,6 +196,9 @@ impl WasmMockQuerier {
         WasmMockQuerier {
             base,
             token_querier: TokenQuerier::default(),
+            withdraw_only: false,
+            staking_contract: None,
+            params: HashMap::new(),
         }
     }
 
@@ -180,6 +207,21 @@ impl WasmMockQuerier {
         self.token_querier = TokenQuerier::new(balances);
     }
 
+    pub fn with_withdraw_only(&mut self, withdraw_only: bool) {
+        self.withdraw_only = withdraw_only;
+    }
+
+    pub fn with_staking_contract(&mut self, staking_contract: &str) {
+        self.staking_contract = Some(Addr::unchecked(staking_contract));
+    }
+
+    pub fn with_params(&mut self, params: &[(&str, ParamValue)]) {
+        self.params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+    }
+
     pub fn with_balance(&mut self, balances: &[(&String, Vec<Coin>)]) {
         for (addr, balance) in balances {
             self.base.update_balance(addr.to_string(), balance.clone());
@@ -17,6 +17,7 @@ pub struct MsgInstantiateContractResponse {
     pub data: Option<Binary>,
 }
 
+#[allow(dead_code)]
 #[derive(Clone, Debug, PartialEq)]
 pub struct MsgExecuteContractResponse {
     pub data: Option<Binary>,
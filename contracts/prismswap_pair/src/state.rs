@@ -1,5 +1,5 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
 use prismswap::asset::PairInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -13,3 +13,20 @@ pub struct Config {
     /// the factory contract address
     pub factory: Addr,
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PriceCumulative {
+    /// the sum of the asset 0 price in asset 1 over every second, scaled by 1e18
+    pub price0_cumulative: Uint128,
+    /// the sum of the asset 1 price in asset 0 over every second, scaled by 1e18
+    pub price1_cumulative: Uint128,
+    /// the block time the accumulators were last updated at
+    pub timestamp: u64,
+}
+
+/// the price accumulators as of the last pool change
+pub const PRICE_CUMULATIVE_LAST: Item<PriceCumulative> = Item::new("price_cumulative_last");
+/// ring buffer of accumulator snapshots used to compute TWAPs
+pub const OBSERVATIONS: Map<U64Key, PriceCumulative> = Map::new("observations");
+/// the number of snapshots recorded so far
+pub const OBSERVATION_COUNT: Item<u64> = Item::new("observation_count");
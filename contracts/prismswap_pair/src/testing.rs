@@ -1,18 +1,23 @@
 use crate::contract::{assert_max_spread, execute, instantiate, query, reply};
 use crate::error::ContractError;
 use crate::mock_querier::mock_dependencies;
+use crate::state::OBSERVATION_COUNT;
+use crate::twap::{accumulate_prices, MAX_OBSERVATIONS, OBSERVATION_PERIOD};
 
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
     attr, from_binary, to_binary, Addr, BankMsg, Coin, ContractResult, CosmosMsg, Decimal, Reply,
-    ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+    ReplyOn, Response, StdError, SubMsg, SubMsgExecutionResponse, Timestamp, Uint128, WasmMsg,
 };
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
 use prismswap::asset::{Asset, AssetInfo, PairInfo};
+use prismswap::fee::{FeeConfig, SwapBreakdown};
 use prismswap::pair::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg,
-    ReverseSimulationResponse, SimulationResponse,
+    ConfigResponse, CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+    PoolResponse, QueryMsg, ReverseSimulationResponse, SimulationResponse, TwapResponse,
 };
+use prismswap::params::{ParamValue, DEFAULT_MAX_SPREAD_KEY, PAUSED_KEY};
+use prismswap::staking::Cw20HookMsg as StakingCw20HookMsg;
 use prismswap::token::InstantiateMsg as TokenInstantiateMsg;
 
 #[test]
@@ -32,6 +37,7 @@ fn proper_initialization() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
     let env = mock_env();
     let info = mock_info("addr0000", &[]);
@@ -51,12 +57,29 @@ fn proper_initialization() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
     let env = mock_env();
     let info = mock_info("addr0000", &[]);
     let err = instantiate(deps.as_mut(), env, info, msg).unwrap_err();
     assert_eq!(err, ContractError::DoublingAssets {});
 
+    // failure - fees out of bounds
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        token_code_id: 10u64,
+        factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::from_bps(600, 3340),
+    };
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err("total fee cannot exceed 0.05"))
+    );
+
     // success
     let msg = InstantiateMsg {
         asset_infos: [
@@ -65,6 +88,7 @@ fn proper_initialization() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
 
     // we can just call .unwrap() to assert this was a success
@@ -88,7 +112,7 @@ fn proper_initialization() {
                 })
                 .unwrap(),
                 funds: vec![],
-                label: "".to_string(),
+                label: "PrismSwap liquidity token".to_string(),
                 admin: None,
             }
             .into(),
@@ -161,6 +185,7 @@ fn provide_liquidity() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
     let env = mock_env();
     let info = mock_info("addr0000", &[]);
@@ -179,6 +204,7 @@ fn provide_liquidity() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
 
     let env = mock_env();
@@ -216,6 +242,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -227,7 +254,7 @@ fn provide_liquidity() {
         }],
     );
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
-    let transfer_from_msg = res.messages.get(0).expect("no message");
+    let transfer_from_msg = res.messages.first().expect("no message");
     let mint_msg = res.messages.get(1).expect("no message");
     assert_eq!(
         transfer_from_msg,
@@ -291,6 +318,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: Some("staking0000".to_string()), // try changing receiver
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -304,7 +332,7 @@ fn provide_liquidity() {
 
     // only accept 100, then 50 share will be generated with 100 * (100 / 200)
     let res: Response = execute(deps.as_mut(), env, info, msg).unwrap();
-    let transfer_from_msg = res.messages.get(0).expect("no message");
+    let transfer_from_msg = res.messages.first().expect("no message");
     let mint_msg = res.messages.get(1).expect("no message");
     assert_eq!(
         transfer_from_msg,
@@ -346,6 +374,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: None,
         receiver: None,
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -401,6 +430,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -440,6 +470,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -481,6 +512,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -516,6 +548,7 @@ fn provide_liquidity() {
         ],
         slippage_tolerance: Some(Decimal::percent(1)),
         receiver: None,
+        auto_stake: None,
     };
 
     let env = mock_env();
@@ -553,6 +586,7 @@ fn withdraw_liquidity() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
 
     let env = mock_env();
@@ -588,7 +622,7 @@ fn withdraw_liquidity() {
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
     let log_withdrawn_share = res.attributes.get(2).expect("no log");
     let log_refund_assets = res.attributes.get(3).expect("no log");
-    let msg_refund_0 = res.messages.get(0).expect("no message");
+    let msg_refund_0 = res.messages.first().expect("no message");
     let msg_refund_1 = res.messages.get(1).expect("no message");
     let msg_burn_liquidity = res.messages.get(2).expect("no message");
     assert_eq!(
@@ -635,6 +669,179 @@ fn withdraw_liquidity() {
     );
 }
 
+#[test]
+fn withdraw_only() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(200u128),
+    }]);
+    deps.querier.with_token_balances(&[
+        (
+            &"liquidity0000".to_string(),
+            &[(&"addr0000".to_string(), &Uint128::from(100u128))],
+        ),
+        (
+            &"asset0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        token_code_id: 10u64,
+        factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // store liquidity token
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(
+                vec![
+                    10, 13, 108, 105, 113, 117, 105, 100, 105, 116, 121, 48, 48, 48, 48,
+                ]
+                .into(),
+            ),
+        }),
+    };
+    let _res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+    deps.querier.with_withdraw_only(true);
+
+    let msg = ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::Native("uusd".to_string()),
+            amount: Uint128::from(100u128),
+        },
+        belief_price: None,
+        max_spread: None,
+        to: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap_err();
+    assert_eq!(err, ContractError::WithdrawOnly {});
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: [
+            Asset {
+                info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                amount: Uint128::from(100u128),
+            },
+            Asset {
+                info: AssetInfo::Native("uusd".to_string()),
+                amount: Uint128::from(100u128),
+            },
+        ],
+        slippage_tolerance: None,
+        receiver: None,
+        auto_stake: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::WithdrawOnly {});
+
+    // liquidity can still be withdrawn
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidity {}).unwrap(),
+        amount: Uint128::from(100u128),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 3);
+}
+
+#[test]
+fn param_registry() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(200u128),
+    }]);
+    deps.querier.with_token_balances(&[
+        (
+            &"liquidity0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+        ),
+        (
+            &"asset0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+        ),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        token_code_id: 10u64,
+        factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let swap = |max_spread: Option<Decimal>| ExecuteMsg::Swap {
+        offer_asset: Asset {
+            info: AssetInfo::Native("uusd".to_string()),
+            amount: Uint128::from(100u128),
+        },
+        belief_price: None,
+        max_spread,
+        to: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+
+    deps.querier.with_params(&[
+        (PAUSED_KEY, ParamValue::Bool(true)),
+        (
+            DEFAULT_MAX_SPREAD_KEY,
+            ParamValue::Decimal(Decimal::percent(1)),
+        ),
+    ]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), swap(None)).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    // the default max spread applies to the swaps setting none
+    deps.querier.with_params(&[
+        (PAUSED_KEY, ParamValue::Bool(false)),
+        (
+            DEFAULT_MAX_SPREAD_KEY,
+            ParamValue::Decimal(Decimal::percent(1)),
+        ),
+    ]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), swap(None)).unwrap_err();
+    assert_eq!(err, ContractError::MaxSpreadAssertion {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        swap(Some(Decimal::percent(60))),
+    )
+    .unwrap();
+}
+
 #[test]
 fn try_native_to_token() {
     let total_share = Uint128::from(30000000000u128);
@@ -666,6 +873,7 @@ fn try_native_to_token() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
 
     let env = mock_env();
@@ -709,7 +917,7 @@ fn try_native_to_token() {
     );
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
     assert_eq!(res.messages.len(), 2);
-    let msg_transfer = res.messages.get(0).expect("no message");
+    let msg_transfer = res.messages.first().expect("no message");
     let msg_collector = res.messages.get(1).expect("no message");
 
     // current price is 1.5, so expected return without spread is 1000
@@ -751,6 +959,16 @@ fn try_native_to_token() {
     assert_eq!(expected_return_amount, simulation_res.return_amount);
     assert_eq!(expected_commission_amount, simulation_res.commission_amount);
     assert_eq!(expected_spread_amount, simulation_res.spread_amount);
+    assert_eq!(
+        simulation_res.breakdown,
+        SwapBreakdown {
+            return_amount: expected_return_amount,
+            spread_amount: expected_spread_amount,
+            commission_amount: expected_commission_amount,
+            protocol_fee: expected_protocol_fee,
+            tax: Uint128::zero(),
+        }
+    );
 
     // check reverse simulation res
     let reverse_simulation_res: ReverseSimulationResponse = from_binary(
@@ -860,6 +1078,7 @@ fn try_token_to_native() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
 
     let env = mock_env();
@@ -937,7 +1156,7 @@ fn try_token_to_native() {
 
     let res = execute(deps.as_mut(), env, info, msg).unwrap();
     assert_eq!(res.messages.len(), 2);
-    let msg_transfer = res.messages.get(0).expect("no message");
+    let msg_transfer = res.messages.first().expect("no message");
     let msg_collector = res.messages.get(1).expect("no message");
 
     // current price is 1.5, so expected return without spread is 1000
@@ -1142,6 +1361,7 @@ fn test_query_pool() {
         ],
         token_code_id: 10u64,
         factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
     };
 
     let env = mock_env();
@@ -1182,3 +1402,233 @@ fn test_query_pool() {
     );
     assert_eq!(res.total_share, total_share_amount);
 }
+
+#[test]
+fn test_query_twap() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(30000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(20000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        token_code_id: 10u64,
+        factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    let start = mock_env().block.time.seconds();
+    let mut env = mock_env();
+    let pools = [Uint128::from(30000u128), Uint128::from(20000u128)];
+    for elapsed in [0u64, 1000u64, 1300u64] {
+        env.block.time = Timestamp::from_seconds(start + elapsed);
+        accumulate_prices(deps.as_mut().storage, &env, pools).unwrap();
+    }
+    // the last accumulation is too close to the previous snapshot to be recorded
+    assert_eq!(OBSERVATION_COUNT.load(deps.as_ref().storage).unwrap(), 2u64);
+
+    env.block.time = Timestamp::from_seconds(start + 2000);
+    let res: CumulativePricesResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::CumulativePrices {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        res.price0_cumulative,
+        Uint128::from(666666666666666666u128 * 2000u128)
+    );
+    assert_eq!(
+        res.price1_cumulative,
+        Uint128::from(1500000000000000000u128 * 2000u128)
+    );
+    assert_eq!(res.timestamp, start + 2000);
+
+    let res: TwapResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Twap { window: 1000 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        res.asset_infos,
+        [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ]
+    );
+    assert_eq!(res.price0, Decimal::from_ratio(2u128, 3u128));
+    assert_eq!(res.price1, Decimal::from_ratio(3u128, 2u128));
+    assert_eq!(res.window, 1000u64);
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Twap { window: 3000 });
+    assert_eq!(res.unwrap_err(), ContractError::NotEnoughPriceHistory {});
+
+    let res = query(deps.as_ref(), env, QueryMsg::Twap { window: 0 });
+    assert_eq!(res.unwrap_err(), ContractError::InvalidTwapWindow {});
+}
+
+#[test]
+fn test_twap_observations_wrap() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(30000u128),
+    }]);
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(30000u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        token_code_id: 10u64,
+        factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
+    };
+
+    let env = mock_env();
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), env, info, msg).unwrap();
+
+    let start = mock_env().block.time.seconds();
+    let mut env = mock_env();
+    let pools = [Uint128::from(30000u128), Uint128::from(30000u128)];
+    for i in 0..MAX_OBSERVATIONS + 5 {
+        env.block.time = Timestamp::from_seconds(start + i * OBSERVATION_PERIOD);
+        accumulate_prices(deps.as_mut().storage, &env, pools).unwrap();
+    }
+
+    // the oldest snapshots were overwritten
+    let now = start + (MAX_OBSERVATIONS + 4) * OBSERVATION_PERIOD;
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Twap {
+            window: now - start,
+        },
+    );
+    assert_eq!(res.unwrap_err(), ContractError::NotEnoughPriceHistory {});
+
+    let res: TwapResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env,
+            QueryMsg::Twap {
+                window: (MAX_OBSERVATIONS - 1) * OBSERVATION_PERIOD,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.price0, Decimal::one());
+    assert_eq!(res.window, (MAX_OBSERVATIONS - 1) * OBSERVATION_PERIOD);
+}
+
+#[test]
+fn provide_liquidity_auto_stake() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(100u128),
+    }]);
+    deps.querier.with_token_balances(&[
+        (
+            &"liquidity0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::zero())],
+        ),
+        (&"asset0000".to_string(), &[]),
+    ]);
+
+    let msg = InstantiateMsg {
+        asset_infos: [
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        token_code_id: 10u64,
+        factory: Addr::unchecked("factory0000"),
+        fee_config: FeeConfig::default(),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // store liquidity token
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(
+                vec![
+                    10, 13, 108, 105, 113, 117, 105, 100, 105, 116, 121, 48, 48, 48, 48,
+                ]
+                .into(),
+            ),
+        }),
+    };
+    let _res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+
+    let msg = ExecuteMsg::ProvideLiquidity {
+        assets: [
+            Asset {
+                info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                amount: Uint128::from(100u128),
+            },
+            Asset {
+                info: AssetInfo::Native("uusd".to_string()),
+                amount: Uint128::from(100u128),
+            },
+        ],
+        slippage_tolerance: None,
+        receiver: Some("addr0001".to_string()),
+        auto_stake: Some(true),
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(100u128),
+        }],
+    );
+
+    // failure - the factory has no staking contract
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::AutoStakeDisabled {});
+
+    // the liquidity tokens are minted to the pair and bonded on behalf of the receiver
+    deps.querier.with_staking_contract("staking0000");
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1..],
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "liquidity0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::from(100u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "liquidity0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "staking0000".to_string(),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&StakingCw20HookMsg::Bond {
+                        staker_addr: Some(Addr::unchecked("addr0001")),
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+}
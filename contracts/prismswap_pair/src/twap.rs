@@ -0,0 +1,142 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{Decimal, Deps, Env, StdResult, Storage, Uint128};
+use cw_storage_plus::U64Key;
+
+use crate::error::ContractError;
+use crate::state::{
+    Config, PriceCumulative, CONFIG, OBSERVATIONS, OBSERVATION_COUNT, PRICE_CUMULATIVE_LAST,
+};
+
+use prismswap::asset::Asset;
+use prismswap::pair::{CumulativePricesResponse, TwapResponse};
+
+/// prices are accumulated with 18 decimals, same as [`Decimal`]
+const PRICE_PRECISION: u128 = 1_000_000_000_000_000_000u128;
+/// the minimum number of seconds between two snapshots
+pub const OBSERVATION_PERIOD: u64 = 600;
+/// the number of snapshots kept, 24 hours at one snapshot per period
+pub const MAX_OBSERVATIONS: u64 = 144;
+
+/// Returns the accumulators advanced to `timestamp` with the given pools
+fn cumulate(last: &PriceCumulative, pools: [Uint128; 2], timestamp: u64) -> PriceCumulative {
+    let elapsed = Uint128::from(timestamp.saturating_sub(last.timestamp));
+    if elapsed.is_zero() || pools[0].is_zero() || pools[1].is_zero() {
+        return PriceCumulative {
+            timestamp: std::cmp::max(timestamp, last.timestamp),
+            ..last.clone()
+        };
+    }
+
+    let price0 = Uint128::from(PRICE_PRECISION).multiply_ratio(pools[1], pools[0]);
+    let price1 = Uint128::from(PRICE_PRECISION).multiply_ratio(pools[0], pools[1]);
+    PriceCumulative {
+        price0_cumulative: last
+            .price0_cumulative
+            .wrapping_add(price0.wrapping_mul(elapsed)),
+        price1_cumulative: last
+            .price1_cumulative
+            .wrapping_add(price1.wrapping_mul(elapsed)),
+        timestamp,
+    }
+}
+
+/// Accumulates the prices of the pools before they change, recording a snapshot at most once
+/// per [`OBSERVATION_PERIOD`]
+pub fn accumulate_prices(
+    storage: &mut dyn Storage,
+    env: &Env,
+    pools: [Uint128; 2],
+) -> StdResult<()> {
+    let now = env.block.time.seconds();
+    let price_cumulative = match PRICE_CUMULATIVE_LAST.may_load(storage)? {
+        Some(last) => cumulate(&last, pools, now),
+        None => PriceCumulative {
+            timestamp: now,
+            ..PriceCumulative::default()
+        },
+    };
+    PRICE_CUMULATIVE_LAST.save(storage, &price_cumulative)?;
+
+    let count = OBSERVATION_COUNT.may_load(storage)?.unwrap_or(0);
+    let record = match count {
+        0 => true,
+        _ => {
+            let latest = OBSERVATIONS.load(storage, U64Key::new((count - 1) % MAX_OBSERVATIONS))?;
+            now >= latest.timestamp + OBSERVATION_PERIOD
+        }
+    };
+    if record {
+        OBSERVATIONS.save(
+            storage,
+            U64Key::new(count % MAX_OBSERVATIONS),
+            &price_cumulative,
+        )?;
+        OBSERVATION_COUNT.save(storage, &(count + 1))?;
+    }
+
+    Ok(())
+}
+
+fn pool_amounts(pools: &[Asset; 2]) -> [Uint128; 2] {
+    [pools[0].amount, pools[1].amount]
+}
+
+pub fn query_cumulative_prices(
+    deps: Deps,
+    env: Env,
+) -> Result<CumulativePricesResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let assets: [Asset; 2] = config
+        .pair_info
+        .query_pools(&deps.querier, &config.pair_info.contract_addr)?;
+
+    let last = PRICE_CUMULATIVE_LAST
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let price_cumulative = cumulate(&last, pool_amounts(&assets), env.block.time.seconds());
+
+    Ok(CumulativePricesResponse {
+        assets,
+        price0_cumulative: price_cumulative.price0_cumulative,
+        price1_cumulative: price_cumulative.price1_cumulative,
+        timestamp: price_cumulative.timestamp,
+    })
+}
+
+pub fn query_twap(deps: Deps, env: Env, window: u64) -> Result<TwapResponse, ContractError> {
+    if window == 0 {
+        return Err(ContractError::InvalidTwapWindow {});
+    }
+
+    let cumulative_prices = query_cumulative_prices(deps, env)?;
+    let count = OBSERVATION_COUNT.may_load(deps.storage)?.unwrap_or(0);
+
+    // the most recent snapshot taken at least `window` seconds ago
+    let mut start: Option<PriceCumulative> = None;
+    for i in count.saturating_sub(MAX_OBSERVATIONS)..count {
+        let observation = OBSERVATIONS.load(deps.storage, U64Key::new(i % MAX_OBSERVATIONS))?;
+        if observation.timestamp + window <= cumulative_prices.timestamp {
+            start = Some(observation);
+        }
+    }
+    let start = start.ok_or(ContractError::NotEnoughPriceHistory {})?;
+
+    let elapsed = cumulative_prices.timestamp - start.timestamp;
+    let average = |end: Uint128, start: Uint128| -> Decimal {
+        Decimal256::from_ratio(
+            Uint256::from(end.wrapping_sub(start).u128() / elapsed as u128),
+            Uint256::from(PRICE_PRECISION),
+        )
+        .into()
+    };
+
+    Ok(TwapResponse {
+        asset_infos: [
+            cumulative_prices.assets[0].info.clone(),
+            cumulative_prices.assets[1].info.clone(),
+        ],
+        price0: average(cumulative_prices.price0_cumulative, start.price0_cumulative),
+        price1: average(cumulative_prices.price1_cumulative, start.price1_cumulative),
+        window: elapsed,
+    })
+}
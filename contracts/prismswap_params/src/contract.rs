@@ -0,0 +1,163 @@
+use crate::state::{read_params, Config, Param, CONFIG, PARAMS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
+
+use prismswap::params::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, ParamBounds, ParamResponse, ParamValue,
+    ParamsResponse, QueryMsg,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match msg {
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, config, owner),
+        ExecuteMsg::RegisterParam { key, value, bounds } => {
+            execute_register_param(deps, key, value, bounds)
+        }
+        ExecuteMsg::SetParam { key, value } => execute_set_param(deps, key, value),
+        ExecuteMsg::RemoveParam { key } => execute_remove_param(deps, key),
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    mut config: Config,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner can execute it
+pub fn execute_register_param(
+    deps: DepsMut,
+    key: String,
+    value: ParamValue,
+    bounds: Option<ParamBounds>,
+) -> StdResult<Response> {
+    if key.is_empty() {
+        return Err(StdError::generic_err("key must not be empty"));
+    }
+
+    if let Some(bounds) = &bounds {
+        bounds.check(&value)?;
+    }
+
+    PARAMS.save(deps.storage, &key, &Param { value, bounds })?;
+
+    Ok(Response::new().add_attributes(vec![("action", "register_param"), ("key", &key)]))
+}
+
+// Only owner can execute it
+pub fn execute_set_param(deps: DepsMut, key: String, value: ParamValue) -> StdResult<Response> {
+    let mut param: Param = PARAMS
+        .may_load(deps.storage, &key)?
+        .ok_or_else(|| StdError::generic_err("parameter is not registered"))?;
+
+    if !param.value.same_type(&value) {
+        return Err(StdError::generic_err("value must match the parameter type"));
+    }
+    if let Some(bounds) = &param.bounds {
+        bounds.check(&value)?;
+    }
+
+    param.value = value;
+    PARAMS.save(deps.storage, &key, &param)?;
+
+    Ok(Response::new().add_attributes(vec![("action", "set_param"), ("key", &key)]))
+}
+
+// Only owner can execute it
+pub fn execute_remove_param(deps: DepsMut, key: String) -> StdResult<Response> {
+    if PARAMS.may_load(deps.storage, &key)?.is_none() {
+        return Err(StdError::generic_err("parameter is not registered"));
+    }
+
+    PARAMS.remove(deps.storage, &key);
+
+    Ok(Response::new().add_attributes(vec![("action", "remove_param"), ("key", &key)]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Param { key } => to_binary(&query_param(deps, key)?),
+        QueryMsg::Params { start_after, limit } => {
+            to_binary(&query_params(deps, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+    })
+}
+
+pub fn query_param(deps: Deps, key: String) -> StdResult<ParamResponse> {
+    let param: Option<Param> = PARAMS.may_load(deps.storage, &key)?;
+
+    Ok(ParamResponse {
+        key,
+        value: param.as_ref().map(|param| param.value.clone()),
+        bounds: param.and_then(|param| param.bounds),
+    })
+}
+
+pub fn query_params(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<ParamsResponse> {
+    let params = read_params(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(key, param)| ParamResponse {
+            key,
+            value: Some(param.value),
+            bounds: param.bounds,
+        })
+        .collect();
+
+    Ok(ParamsResponse { params })
+}
@@ -0,0 +1,39 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage};
+use cw_storage_plus::{Item, Map};
+use prismswap::pagination::{calc_limit, start_after_bound};
+use prismswap::params::{ParamBounds, ParamValue};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Param {
+    pub value: ParamValue,
+    pub bounds: Option<ParamBounds>,
+}
+
+pub const PARAMS: Map<&str, Param> = Map::new("params");
+
+pub fn read_params(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(String, Param)>> {
+    let start = start_after_bound(start_after.map(|key| key.as_bytes().to_vec()));
+
+    PARAMS
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((String::from_utf8(k)?, v))
+        })
+        .collect()
+}
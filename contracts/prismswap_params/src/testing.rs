@@ -0,0 +1,172 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, Addr, Decimal, OwnedDeps, StdError};
+use prismswap::params::{
+    ExecuteMsg, InstantiateMsg, ParamBounds, ParamResponse, ParamValue, ParamsResponse, QueryMsg,
+    DEFAULT_MAX_SPREAD_KEY, PAUSED_KEY,
+};
+
+/// Instantiates the contract with a default max spread of 1% bounded between 0.1% and 10%
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("gov0000"),
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::RegisterParam {
+        key: DEFAULT_MAX_SPREAD_KEY.to_string(),
+        value: ParamValue::Decimal(Decimal::percent(1)),
+        bounds: Some(ParamBounds {
+            min: Some(ParamValue::Decimal(Decimal::permille(1))),
+            max: Some(ParamValue::Decimal(Decimal::percent(10))),
+        }),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+
+    deps
+}
+
+fn query_param(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, key: &str) -> ParamResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Param {
+                key: key.to_string(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn register_param() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::RegisterParam {
+        key: PAUSED_KEY.to_string(),
+        value: ParamValue::Bool(false),
+        bounds: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+
+    // bounds of another type are rejected
+    let msg = ExecuteMsg::RegisterParam {
+        key: PAUSED_KEY.to_string(),
+        value: ParamValue::Bool(false),
+        bounds: Some(ParamBounds {
+            min: Some(ParamValue::Decimal(Decimal::zero())),
+            max: None,
+        }),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("bounds must match the value type")
+    );
+
+    assert_eq!(
+        query_param(&deps, PAUSED_KEY),
+        ParamResponse {
+            key: PAUSED_KEY.to_string(),
+            value: Some(ParamValue::Bool(false)),
+            bounds: None,
+        }
+    );
+    assert_eq!(
+        query_param(&deps, "unknown"),
+        ParamResponse {
+            key: "unknown".to_string(),
+            value: None,
+            bounds: None,
+        }
+    );
+
+    let res: ParamsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Params {
+                start_after: Some(DEFAULT_MAX_SPREAD_KEY.to_string()),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.params.len(), 1);
+    assert_eq!(res.params[0].key, PAUSED_KEY.to_string());
+}
+
+#[test]
+fn set_param() {
+    let mut deps = setup();
+
+    let set_param = |value: ParamValue| ExecuteMsg::SetParam {
+        key: DEFAULT_MAX_SPREAD_KEY.to_string(),
+        value,
+    };
+
+    let msg = set_param(ParamValue::Decimal(Decimal::percent(11)));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("value is above the maximum"));
+
+    let msg = set_param(ParamValue::Decimal(Decimal::zero()));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("value is below the minimum"));
+
+    let msg = set_param(ParamValue::Bool(true));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("value must match the parameter type")
+    );
+
+    let msg = set_param(ParamValue::Decimal(Decimal::percent(5)));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap();
+    assert_eq!(
+        query_param(&deps, DEFAULT_MAX_SPREAD_KEY).value,
+        Some(ParamValue::Decimal(Decimal::percent(5)))
+    );
+
+    let msg = ExecuteMsg::SetParam {
+        key: PAUSED_KEY.to_string(),
+        value: ParamValue::Bool(true),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("parameter is not registered"));
+}
+
+#[test]
+fn remove_param() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::RemoveParam {
+        key: DEFAULT_MAX_SPREAD_KEY.to_string(),
+    };
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gov0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(query_param(&deps, DEFAULT_MAX_SPREAD_KEY).value, None);
+
+    let err = execute(deps.as_mut(), mock_env(), mock_info("gov0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("parameter is not registered"));
+}
@@ -0,0 +1,284 @@
+use crate::state::{
+    leaderboard_key, read_leaderboard, Config, EpochTotal, CONFIG, EPOCHS, LEADERBOARD,
+    TRADER_VOLUMES,
+};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128,
+};
+use cw_storage_plus::U64Key;
+
+use prismswap::asset::{Asset, PrismSwapAssetInfo};
+use prismswap::points::{
+    ConfigResponse, EpochResponse, ExecuteMsg, InstantiateMsg, LeaderboardEntry,
+    LeaderboardResponse, QueryMsg, TraderVolumeResponse, VolumeAsset,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    if msg.epoch_length == 0 {
+        return Err(StdError::generic_err("epoch length must be greater than 0"));
+    }
+
+    assert_volume_assets(deps.as_ref(), &msg.volume_assets)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        reporters: validate_addresses(deps.as_ref(), &msg.reporters)?,
+        volume_assets: msg.volume_assets,
+        start_time: msg.start_time,
+        epoch_length: msg.epoch_length,
+    };
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            reporters,
+            volume_assets,
+        } => execute_update_config(deps, info, owner, reporters, volume_assets),
+        ExecuteMsg::ReportSwap {
+            trader,
+            offer_asset,
+            return_asset,
+        } => execute_report_swap(deps, env, info, trader, offer_asset, return_asset),
+    }
+}
+
+fn validate_addresses(deps: Deps, addresses: &[Addr]) -> StdResult<Vec<Addr>> {
+    addresses
+        .iter()
+        .map(|address| deps.api.addr_validate(address.as_str()))
+        .collect()
+}
+
+fn assert_volume_assets(deps: Deps, volume_assets: &[VolumeAsset]) -> StdResult<()> {
+    for (i, volume_asset) in volume_assets.iter().enumerate() {
+        volume_asset.asset_info.check(deps.api)?;
+
+        if volume_assets[..i]
+            .iter()
+            .any(|other| other.asset_info == volume_asset.asset_info)
+        {
+            return Err(StdError::generic_err("duplicate volume asset"));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    reporters: Option<Vec<Addr>>,
+    volume_assets: Option<Vec<VolumeAsset>>,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(reporters) = reporters {
+        config.reporters = validate_addresses(deps.as_ref(), &reporters)?;
+    }
+
+    if let Some(volume_assets) = volume_assets {
+        assert_volume_assets(deps.as_ref(), &volume_assets)?;
+        config.volume_assets = volume_assets;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+/// Returns the epoch of the block time, none before the first one starts
+fn epoch_at(config: &Config, time: u64) -> Option<u64> {
+    time.checked_sub(config.start_time)
+        .map(|elapsed| elapsed / config.epoch_length)
+}
+
+/// Returns the volume of a swap, measured on the offer asset if it is a volume asset, else on
+/// the return asset
+fn swap_volume(config: &Config, offer_asset: &Asset, return_asset: &Asset) -> Uint128 {
+    [offer_asset, return_asset]
+        .iter()
+        .find_map(|asset| {
+            config
+                .volume_assets
+                .iter()
+                .find(|volume_asset| volume_asset.asset_info == asset.info)
+                .map(|volume_asset| asset.amount * volume_asset.weight)
+        })
+        .unwrap_or_default()
+}
+
+// Only reporters can execute it
+pub fn execute_report_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    trader: Addr,
+    offer_asset: Asset,
+    return_asset: Asset,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if !config.reporters.contains(&info.sender) {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // swaps before the first epoch or in no volume asset are not counted, but never make the
+    // reporter fail
+    let volume = swap_volume(&config, &offer_asset, &return_asset);
+    let epoch = match epoch_at(&config, env.block.time.seconds()) {
+        Some(epoch) if !volume.is_zero() => epoch,
+        _ => return Ok(Response::new().add_attribute("action", "report_swap")),
+    };
+
+    let mut epoch_total = EPOCHS
+        .may_load(deps.storage, U64Key::new(epoch))?
+        .unwrap_or_default();
+    let prev_volume = TRADER_VOLUMES.may_load(deps.storage, (U64Key::new(epoch), &trader))?;
+    let trader_volume = match prev_volume {
+        Some(prev_volume) => {
+            LEADERBOARD.remove(
+                deps.storage,
+                (U64Key::new(epoch), &leaderboard_key(&trader, prev_volume)),
+            );
+            prev_volume + volume
+        }
+        None => {
+            epoch_total.trader_count += 1;
+            volume
+        }
+    };
+
+    epoch_total.total_volume += volume;
+    epoch_total.swap_count += 1;
+    EPOCHS.save(deps.storage, U64Key::new(epoch), &epoch_total)?;
+    TRADER_VOLUMES.save(deps.storage, (U64Key::new(epoch), &trader), &trader_volume)?;
+    LEADERBOARD.save(
+        deps.storage,
+        (U64Key::new(epoch), &leaderboard_key(&trader, trader_volume)),
+        &true,
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "report_swap"),
+        ("trader", trader.as_str()),
+        ("epoch", &epoch.to_string()),
+        ("volume", &volume.to_string()),
+    ]))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Epoch { epoch } => to_binary(&query_epoch(deps, env, epoch)?),
+        QueryMsg::TraderVolume { trader, epoch } => {
+            to_binary(&query_trader_volume(deps, env, trader, epoch)?)
+        }
+        QueryMsg::Leaderboard {
+            epoch,
+            start_after,
+            limit,
+        } => to_binary(&query_leaderboard(deps, env, epoch, start_after, limit)?),
+    }
+}
+
+/// Returns the given epoch, the current one if not set
+fn resolve_epoch(config: &Config, env: &Env, epoch: Option<u64>) -> StdResult<u64> {
+    match epoch {
+        Some(epoch) => Ok(epoch),
+        None => epoch_at(config, env.block.time.seconds())
+            .ok_or_else(|| StdError::generic_err("epochs not started")),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        reporters: config.reporters,
+        volume_assets: config.volume_assets,
+        start_time: config.start_time,
+        epoch_length: config.epoch_length,
+    })
+}
+
+pub fn query_epoch(deps: Deps, env: Env, epoch: Option<u64>) -> StdResult<EpochResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let epoch = resolve_epoch(&config, &env, epoch)?;
+    let epoch_total: EpochTotal = EPOCHS
+        .may_load(deps.storage, U64Key::new(epoch))?
+        .unwrap_or_default();
+
+    let start_time = config.start_time + epoch * config.epoch_length;
+    Ok(EpochResponse {
+        epoch,
+        start_time,
+        end_time: start_time + config.epoch_length,
+        total_volume: epoch_total.total_volume,
+        swap_count: epoch_total.swap_count,
+        trader_count: epoch_total.trader_count,
+    })
+}
+
+pub fn query_trader_volume(
+    deps: Deps,
+    env: Env,
+    trader: Addr,
+    epoch: Option<u64>,
+) -> StdResult<TraderVolumeResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let epoch = resolve_epoch(&config, &env, epoch)?;
+    let volume = TRADER_VOLUMES
+        .may_load(deps.storage, (U64Key::new(epoch), &trader))?
+        .unwrap_or_default();
+
+    Ok(TraderVolumeResponse {
+        trader,
+        epoch,
+        volume,
+    })
+}
+
+pub fn query_leaderboard(
+    deps: Deps,
+    env: Env,
+    epoch: Option<u64>,
+    start_after: Option<LeaderboardEntry>,
+    limit: Option<u32>,
+) -> StdResult<LeaderboardResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let epoch = resolve_epoch(&config, &env, epoch)?;
+
+    Ok(LeaderboardResponse {
+        epoch,
+        entries: read_leaderboard(deps.storage, epoch, start_after, limit)?,
+    })
+}
@@ -0,0 +1,65 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdError, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map, U64Key};
+use prismswap::pagination::calc_limit;
+use prismswap::points::{LeaderboardEntry, VolumeAsset};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub reporters: Vec<Addr>,
+    pub volume_assets: Vec<VolumeAsset>,
+    pub start_time: u64,
+    pub epoch_length: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct EpochTotal {
+    pub total_volume: Uint128,
+    pub swap_count: u64,
+    pub trader_count: u64,
+}
+
+pub const EPOCHS: Map<U64Key, EpochTotal> = Map::new("epochs");
+
+pub const TRADER_VOLUMES: Map<(U64Key, &Addr), Uint128> = Map::new("trader_volumes");
+
+/// the traders of every epoch keyed by their volume followed by their address, for
+/// reading them by descending volume
+pub const LEADERBOARD: Map<(U64Key, &[u8]), bool> = Map::new("leaderboard");
+
+pub fn leaderboard_key(trader: &Addr, volume: Uint128) -> Vec<u8> {
+    [&volume.u128().to_be_bytes()[..], trader.as_bytes()].concat()
+}
+
+pub fn read_leaderboard(
+    storage: &dyn Storage,
+    epoch: u64,
+    start_after: Option<LeaderboardEntry>,
+    limit: Option<u32>,
+) -> StdResult<Vec<LeaderboardEntry>> {
+    let end =
+        start_after.map(|entry| Bound::exclusive(leaderboard_key(&entry.trader, entry.volume)));
+
+    LEADERBOARD
+        .prefix(U64Key::new(epoch))
+        .range(storage, None, end, Order::Descending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, _) = item?;
+            let mut volume = [0u8; 16];
+            volume.copy_from_slice(&k[..16]);
+            let trader = String::from_utf8(k[16..].to_vec())
+                .map_err(|_| StdError::generic_err("invalid leaderboard key"))?;
+
+            Ok(LeaderboardEntry {
+                trader: Addr::unchecked(trader),
+                volume: Uint128::from(u128::from_be_bytes(volume)),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,309 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{from_binary, Addr, Decimal, Env, OwnedDeps, StdError, Uint128};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::points::{
+    EpochResponse, ExecuteMsg, InstantiateMsg, LeaderboardEntry, LeaderboardResponse, QueryMsg,
+    TraderVolumeResponse, VolumeAsset,
+};
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+/// A swap of the offer amount of uusd into prism
+fn report(trader: &str, uusd_amount: u128) -> ExecuteMsg {
+    ExecuteMsg::ReportSwap {
+        trader: Addr::unchecked(trader),
+        offer_asset: Asset::new(
+            AssetInfo::Native("uusd".to_string()),
+            Uint128::from(uusd_amount),
+        ),
+        return_asset: Asset::new(
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            Uint128::from(uusd_amount * 2),
+        ),
+    }
+}
+
+/// Instantiates the contract with daily epochs starting now, measuring swaps in uusd and
+/// in uluna worth 50 uusd
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+            reporters: vec![Addr::unchecked("router0000")],
+            volume_assets: vec![
+                VolumeAsset {
+                    asset_info: AssetInfo::Native("uusd".to_string()),
+                    weight: Decimal::one(),
+                },
+                VolumeAsset {
+                    asset_info: AssetInfo::Native("uluna".to_string()),
+                    weight: Decimal::from_ratio(50u128, 1u128),
+                },
+            ],
+            start_time: mock_env().block.time.seconds(),
+            epoch_length: 86400,
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+#[test]
+fn update_config() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("ab")),
+        reporters: None,
+        volume_assets: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert!(matches!(err, StdError::GenericErr { .. }));
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        reporters: Some(vec![Addr::unchecked("router0001"), Addr::unchecked("ab")]),
+        volume_assets: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert!(matches!(err, StdError::GenericErr { .. }));
+
+    // the reporters are replaced
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        reporters: Some(vec![Addr::unchecked("router0001")]),
+        volume_assets: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("router0000", &[]),
+        report("addr0000", 1_000_000),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("router0001", &[]),
+        report("addr0000", 1_000_000),
+    )
+    .unwrap();
+}
+
+#[test]
+fn report_swap() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        report("trader0000", 100),
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+
+    let router = mock_info("router0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        router.clone(),
+        report("trader0000", 100),
+    )
+    .unwrap();
+
+    // measured on the return asset when the offer asset is not a volume asset
+    let msg = ExecuteMsg::ReportSwap {
+        trader: Addr::unchecked("trader0000"),
+        offer_asset: Asset::new(
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            Uint128::from(1000u128),
+        ),
+        return_asset: Asset::new(
+            AssetInfo::Native("uluna".to_string()),
+            Uint128::from(10u128),
+        ),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), router.clone(), msg).unwrap();
+
+    // swaps in no volume asset are not counted
+    let msg = ExecuteMsg::ReportSwap {
+        trader: Addr::unchecked("trader0000"),
+        offer_asset: Asset::new(
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            Uint128::from(1000u128),
+        ),
+        return_asset: Asset::new(
+            AssetInfo::Cw20(Addr::unchecked("token0000")),
+            Uint128::from(1000u128),
+        ),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), router, msg).unwrap();
+
+    let res: TraderVolumeResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::TraderVolume {
+                trader: Addr::unchecked("trader0000"),
+                epoch: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.volume, Uint128::from(600u128));
+
+    let res: EpochResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Epoch { epoch: None }).unwrap())
+            .unwrap();
+    assert_eq!(
+        res,
+        EpochResponse {
+            epoch: 0,
+            start_time: mock_env().block.time.seconds(),
+            end_time: mock_env().block.time.seconds() + 86400,
+            total_volume: Uint128::from(600u128),
+            swap_count: 2,
+            trader_count: 1,
+        }
+    );
+}
+
+#[test]
+fn leaderboard() {
+    let mut deps = setup();
+
+    let router = mock_info("router0000", &[]);
+    for (trader, amount) in [
+        ("trader0000", 100u128),
+        ("trader0001", 300u128),
+        ("trader0002", 200u128),
+        ("trader0000", 250u128),
+    ] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            router.clone(),
+            report(trader, amount),
+        )
+        .unwrap();
+    }
+
+    let res: LeaderboardResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Leaderboard {
+                epoch: None,
+                start_after: None,
+                limit: Some(2),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.entries,
+        vec![
+            LeaderboardEntry {
+                trader: Addr::unchecked("trader0000"),
+                volume: Uint128::from(350u128),
+            },
+            LeaderboardEntry {
+                trader: Addr::unchecked("trader0001"),
+                volume: Uint128::from(300u128),
+            },
+        ]
+    );
+
+    let res: LeaderboardResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Leaderboard {
+                epoch: None,
+                start_after: res.entries.last().cloned(),
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.entries,
+        vec![LeaderboardEntry {
+            trader: Addr::unchecked("trader0002"),
+            volume: Uint128::from(200u128),
+        }]
+    );
+}
+
+#[test]
+fn epochs() {
+    let mut deps = setup();
+
+    let router = mock_info("router0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        router.clone(),
+        report("trader0000", 100),
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        env_at(86400),
+        router,
+        report("trader0001", 500),
+    )
+    .unwrap();
+
+    let res: EpochResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(86400),
+            QueryMsg::Epoch { epoch: None },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.epoch, 1);
+    assert_eq!(res.total_volume, Uint128::from(500u128));
+
+    let res: LeaderboardResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env_at(86400),
+            QueryMsg::Leaderboard {
+                epoch: Some(0),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.entries,
+        vec![LeaderboardEntry {
+            trader: Addr::unchecked("trader0000"),
+            volume: Uint128::from(100u128),
+        }]
+    );
+}
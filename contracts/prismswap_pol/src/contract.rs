@@ -0,0 +1,453 @@
+use crate::state::{read_positions, Config, Position, CONFIG, POSITIONS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use prismswap::pol::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PositionResponse, PositionsResponse, QueryMsg,
+    TargetRange,
+};
+use prismswap::querier::{query_pair_info_from_pair, query_supply, query_token_balance};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        operator: msg
+            .operator
+            .map(|operator| deps.api.addr_validate(operator.as_str()))
+            .transpose()?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig { owner, operator } => {
+            execute_update_config(deps, info, owner, operator)
+        }
+        ExecuteMsg::SetPosition { pair, target_range } => {
+            execute_set_position(deps, info, pair, target_range)
+        }
+        ExecuteMsg::RemovePosition { pair } => execute_remove_position(deps, env, info, pair),
+        ExecuteMsg::ProvideLiquidity {
+            pair,
+            assets,
+            slippage_tolerance,
+        } => execute_provide_liquidity(deps, env, info, pair, assets, slippage_tolerance),
+        ExecuteMsg::WithdrawLiquidity { pair, amount } => {
+            execute_withdraw_liquidity(deps, env, info, pair, amount)
+        }
+        ExecuteMsg::Transfer { asset, recipient } => execute_transfer(deps, info, asset, recipient),
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    operator: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(operator) = operator {
+        config.operator = Some(deps.api.addr_validate(operator.as_str())?);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner can execute it
+pub fn execute_set_position(
+    deps: DepsMut,
+    info: MessageInfo,
+    pair: Addr,
+    target_range: TargetRange,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if target_range.min_share > target_range.max_share {
+        return Err(StdError::generic_err("min share must not exceed max share"));
+    }
+
+    if target_range.max_share > Decimal::one() {
+        return Err(StdError::generic_err("max share must not exceed 1"));
+    }
+
+    let pair = deps.api.addr_validate(pair.as_str())?;
+    let position = match POSITIONS.may_load(deps.storage, &pair)? {
+        Some(position) => Position {
+            target_range,
+            ..position
+        },
+        None => {
+            let pair_info = query_pair_info_from_pair(&deps.querier, &pair)?;
+            Position {
+                asset_infos: pair_info.asset_infos,
+                lp_token: pair_info.liquidity_token,
+                target_range,
+                provided: [Uint128::zero(); 2],
+                withdrawn: [Uint128::zero(); 2],
+            }
+        }
+    };
+
+    POSITIONS.save(deps.storage, &pair, &position)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_position"),
+        ("pair", pair.as_str()),
+        ("min_share", &position.target_range.min_share.to_string()),
+        ("max_share", &position.target_range.max_share.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_remove_position(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Addr,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let position = load_position(deps.as_ref(), &pair)?;
+    if !query_token_balance(&deps.querier, &position.lp_token, &env.contract.address)?.is_zero() {
+        return Err(StdError::generic_err("position still holds liquidity"));
+    }
+
+    POSITIONS.remove(deps.storage, &pair);
+
+    Ok(
+        Response::new()
+            .add_attributes(vec![("action", "remove_position"), ("pair", pair.as_str())]),
+    )
+}
+
+// Only owner or operator can execute it
+pub fn execute_provide_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Addr,
+    assets: [Asset; 2],
+    slippage_tolerance: Option<Decimal>,
+) -> StdResult<Response> {
+    assert_owner_or_operator(deps.as_ref(), &info.sender)?;
+
+    let mut position = load_position(deps.as_ref(), &pair)?;
+
+    // the assets in the order of the pair
+    let amounts = if assets[0].info == position.asset_infos[0]
+        && assets[1].info == position.asset_infos[1]
+    {
+        [assets[0].amount, assets[1].amount]
+    } else if assets[0].info == position.asset_infos[1] && assets[1].info == position.asset_infos[0]
+    {
+        [assets[1].amount, assets[0].amount]
+    } else {
+        return Err(StdError::generic_err("invalid assets"));
+    };
+
+    let pools = query_pools(deps.as_ref(), &pair, &position)?;
+    let supply = query_supply(&deps.querier, &position.lp_token)?;
+    let held = query_token_balance(&deps.querier, &position.lp_token, &env.contract.address)?;
+
+    // the share owned once the LP tokens the provision mints at most are added
+    let share = if supply.is_zero() || pools[0].is_zero() || pools[1].is_zero() {
+        Decimal::one()
+    } else {
+        let minted = std::cmp::min(
+            amounts[0].multiply_ratio(supply, pools[0]),
+            amounts[1].multiply_ratio(supply, pools[1]),
+        );
+        Decimal::from_ratio(held + minted, supply + minted)
+    };
+    if share > position.target_range.max_share {
+        return Err(StdError::generic_err("share exceeds the target range"));
+    }
+
+    position.provided = [
+        position.provided[0] + amounts[0],
+        position.provided[1] + amounts[1],
+    ];
+    POSITIONS.save(deps.storage, &pair, &position)?;
+
+    let mut messages: Vec<CosmosMsg> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for (asset_info, amount) in position.asset_infos.iter().zip(amounts.iter()) {
+        match asset_info {
+            AssetInfo::Native(denom) => funds.push(Coin {
+                denom: denom.to_string(),
+                amount: *amount,
+            }),
+            AssetInfo::Cw20(contract_addr) => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: pair.to_string(),
+                    amount: *amount,
+                    expires: None,
+                })?,
+            })),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair.to_string(),
+        funds,
+        msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets: [
+                Asset::new(position.asset_infos[0].clone(), amounts[0]),
+                Asset::new(position.asset_infos[1].clone(), amounts[1]),
+            ],
+            slippage_tolerance,
+            receiver: None,
+            auto_stake: None,
+        })?,
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "provide_liquidity"),
+        ("pair", pair.as_str()),
+        ("share", &share.to_string()),
+    ]))
+}
+
+// Only owner or operator can execute it
+pub fn execute_withdraw_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    assert_owner_or_operator(deps.as_ref(), &info.sender)?;
+
+    let mut position = load_position(deps.as_ref(), &pair)?;
+    let pools = query_pools(deps.as_ref(), &pair, &position)?;
+    let supply = query_supply(&deps.querier, &position.lp_token)?;
+    let held = query_token_balance(&deps.querier, &position.lp_token, &env.contract.address)?;
+    if amount.is_zero() || amount > held {
+        return Err(StdError::generic_err("invalid withdraw amount"));
+    }
+
+    let share = if supply == amount {
+        Decimal::zero()
+    } else {
+        Decimal::from_ratio(held - amount, supply - amount)
+    };
+    if share < position.target_range.min_share {
+        return Err(StdError::generic_err("share falls below the target range"));
+    }
+
+    position.withdrawn = [
+        position.withdrawn[0] + pools[0].multiply_ratio(amount, supply),
+        position.withdrawn[1] + pools[1].multiply_ratio(amount, supply),
+    ];
+    POSITIONS.save(deps.storage, &pair, &position)?;
+
+    Ok(Response::new()
+        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: position.lp_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount,
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {})?,
+            })?,
+        }))
+        .add_attributes(vec![
+            ("action", "withdraw_liquidity"),
+            ("pair", pair.as_str()),
+            ("amount", &amount.to_string()),
+            ("share", &share.to_string()),
+        ]))
+}
+
+// Only owner can execute it
+pub fn execute_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset: Asset,
+    recipient: Addr,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let recipient = deps.api.addr_validate(recipient.as_str())?;
+
+    Ok(Response::new()
+        .add_message(asset.transfer_msg(&recipient)?)
+        .add_attributes(vec![
+            ("action", "transfer"),
+            ("asset", &asset.to_string()),
+            ("recipient", recipient.as_str()),
+        ]))
+}
+
+fn assert_owner_or_operator(deps: Deps, sender: &Addr) -> StdResult<()> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    if *sender != config.owner && Some(sender) != config.operator.as_ref() {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    Ok(())
+}
+
+fn load_position(deps: Deps, pair: &Addr) -> StdResult<Position> {
+    POSITIONS
+        .may_load(deps.storage, pair)?
+        .ok_or_else(|| StdError::generic_err("position not found"))
+}
+
+fn query_pools(deps: Deps, pair: &Addr, position: &Position) -> StdResult<[Uint128; 2]> {
+    Ok([
+        position.asset_infos[0].query_pool(&deps.querier, pair)?,
+        position.asset_infos[1].query_pool(&deps.querier, pair)?,
+    ])
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Position { pair } => to_binary(&query_position(deps, env, pair)?),
+        QueryMsg::Positions { start_after, limit } => {
+            to_binary(&query_positions(deps, env, start_after, limit)?)
+        }
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        operator: config.operator,
+    })
+}
+
+pub fn query_position(deps: Deps, env: Env, pair: Addr) -> StdResult<PositionResponse> {
+    let position = load_position(deps, &pair)?;
+    position_response(deps, &env, pair, position)
+}
+
+pub fn query_positions(
+    deps: Deps,
+    env: Env,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<PositionsResponse> {
+    let positions = read_positions(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(pair, position)| position_response(deps, &env, pair, position))
+        .collect::<StdResult<Vec<PositionResponse>>>()?;
+
+    Ok(PositionsResponse { positions })
+}
+
+fn position_response(
+    deps: Deps,
+    env: &Env,
+    pair: Addr,
+    position: Position,
+) -> StdResult<PositionResponse> {
+    let pools = query_pools(deps, &pair, &position)?;
+    let supply = query_supply(&deps.querier, &position.lp_token)?;
+    let lp_amount = query_token_balance(&deps.querier, &position.lp_token, &env.contract.address)?;
+
+    let (share, underlying) = if supply.is_zero() {
+        (Decimal::zero(), [Uint128::zero(); 2])
+    } else {
+        (
+            Decimal::from_ratio(lp_amount, supply),
+            [
+                pools[0].multiply_ratio(lp_amount, supply),
+                pools[1].multiply_ratio(lp_amount, supply),
+            ],
+        )
+    };
+
+    // the amounts valued in the second asset at the pool price
+    let value_of = |amounts: [Uint128; 2]| -> Uint128 {
+        if pools[0].is_zero() {
+            amounts[1]
+        } else {
+            amounts[1] + amounts[0].multiply_ratio(pools[1], pools[0])
+        }
+    };
+    let value = value_of(underlying) + value_of(position.withdrawn);
+    let cost = value_of(position.provided);
+    let (pnl, is_loss) = if value < cost {
+        (cost - value, true)
+    } else {
+        (value - cost, false)
+    };
+
+    let assets = |amounts: [Uint128; 2]| -> [Asset; 2] {
+        [
+            Asset::new(position.asset_infos[0].clone(), amounts[0]),
+            Asset::new(position.asset_infos[1].clone(), amounts[1]),
+        ]
+    };
+
+    Ok(PositionResponse {
+        pair,
+        asset_infos: position.asset_infos.clone(),
+        lp_token: position.lp_token.clone(),
+        target_range: position.target_range.clone(),
+        lp_amount,
+        share,
+        underlying: assets(underlying),
+        provided: assets(position.provided),
+        withdrawn: assets(position.withdrawn),
+        value,
+        cost,
+        pnl,
+        is_loss,
+    })
+}
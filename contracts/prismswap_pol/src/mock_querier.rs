@@ -0,0 +1,139 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, ContractResult, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use prismswap::asset::PairInfo;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PairQueryMsg {
+    Pair {},
+}
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    pair_infos: HashMap<String, PairInfo>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    token_supplies: HashMap<String, Uint128>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if from_binary::<PairQueryMsg>(msg).is_ok() =>
+            {
+                match self.pair_infos.get(contract_addr) {
+                    Some(v) => SystemResult::Ok(ContractResult::from(to_binary(v))),
+                    None => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    }),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    Cw20QueryMsg::TokenInfo {} => {
+                        let total_supply = self
+                            .token_supplies
+                            .get(contract_addr)
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&TokenInfoResponse {
+                                name: "LP Token".to_string(),
+                                symbol: "uLP".to_string(),
+                                decimals: 6,
+                                total_supply,
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            pair_infos: HashMap::new(),
+            token_balances: HashMap::new(),
+            token_supplies: HashMap::new(),
+        }
+    }
+
+    pub fn with_balance(&mut self, balances: &[(String, &[Coin])]) {
+        for (addr, balance) in balances {
+            self.base.update_balance(addr, balance.to_vec());
+        }
+    }
+
+    pub fn with_pair_info(&mut self, pair_info: PairInfo) {
+        self.pair_infos
+            .insert(pair_info.contract_addr.to_string(), pair_info);
+    }
+
+    pub fn with_token_balance(&mut self, token: &str, address: &str, balance: Uint128) {
+        self.token_balances
+            .entry(token.to_string())
+            .or_default()
+            .insert(address.to_string(), balance);
+    }
+
+    pub fn with_token_supply(&mut self, token: &str, supply: Uint128) {
+        self.token_supplies.insert(token.to_string(), supply);
+    }
+}
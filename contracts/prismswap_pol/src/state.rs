@@ -0,0 +1,47 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use prismswap::asset::AssetInfo;
+use prismswap::pagination::{calc_limit, start_after_bound};
+use prismswap::pol::TargetRange;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub operator: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Position {
+    pub asset_infos: [AssetInfo; 2],
+    pub lp_token: Addr,
+    pub target_range: TargetRange,
+    /// the amounts of each asset provided so far
+    pub provided: [Uint128; 2],
+    /// the amounts of each asset withdrawn so far
+    pub withdrawn: [Uint128; 2],
+}
+
+/// the designated pairs, keyed by the pair contract
+pub const POSITIONS: Map<&Addr, Position> = Map::new("positions");
+
+pub fn read_positions(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, Position)>> {
+    let start = start_after_bound(start_after.map(|pair| pair.as_bytes().to_vec()));
+
+    POSITIONS
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((Addr::unchecked(String::from_utf8(k)?), v))
+        })
+        .collect()
+}
@@ -0,0 +1,418 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, OwnedDeps, StdError, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use prismswap::asset::{Asset, AssetInfo, PairInfo};
+use prismswap::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use prismswap::pol::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, PositionResponse, PositionsResponse, QueryMsg,
+    TargetRange,
+};
+
+fn asset_infos() -> [AssetInfo; 2] {
+    [
+        AssetInfo::Native("uusd".to_string()),
+        AssetInfo::Cw20(Addr::unchecked("token0000")),
+    ]
+}
+
+/// Sets the pools of the uusd-token0000 pair and the LP supply
+fn with_pools(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    uusd: u128,
+    token: u128,
+    supply: u128,
+) {
+    deps.querier
+        .with_balance(&[("pair0000".to_string(), &[coin(uusd, "uusd")])]);
+    deps.querier
+        .with_token_balance("token0000", "pair0000", Uint128::from(token));
+    deps.querier
+        .with_token_supply("liquidity0000", Uint128::from(supply));
+}
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_pair_info(PairInfo {
+        asset_infos: asset_infos(),
+        contract_addr: Addr::unchecked("pair0000"),
+        liquidity_token: Addr::unchecked("liquidity0000"),
+    });
+    with_pools(&mut deps, 1000, 2000, 1000);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+            operator: Some(Addr::unchecked("operator0000")),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn set_position(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    min_share: Decimal,
+    max_share: Decimal,
+) {
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::SetPosition {
+            pair: Addr::unchecked("pair0000"),
+            target_range: TargetRange {
+                min_share,
+                max_share,
+            },
+        },
+    )
+    .unwrap();
+}
+
+fn query_position(deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) -> PositionResponse {
+    from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Position {
+                pair: Addr::unchecked("pair0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = setup();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            operator: Some(Addr::unchecked("operator0000")),
+        }
+    );
+
+    let position_msg = |min_share: u64, max_share: u64| ExecuteMsg::SetPosition {
+        pair: Addr::unchecked("pair0000"),
+        target_range: TargetRange {
+            min_share: Decimal::percent(min_share),
+            max_share: Decimal::percent(max_share),
+        },
+    };
+
+    // the operator cannot set the target ranges
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        position_msg(5, 10),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        position_msg(10, 5),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("min share must not exceed max share")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        position_msg(5, 110),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("max share must not exceed 1"));
+
+    set_position(&mut deps, Decimal::percent(5), Decimal::percent(10));
+
+    let res: PositionsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Positions {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.positions.len(), 1);
+    assert_eq!(res.positions[0].lp_token, Addr::unchecked("liquidity0000"));
+    assert_eq!(res.positions[0].lp_amount, Uint128::zero());
+
+    // the treasury assets only move out through the owner
+    let transfer_msg = ExecuteMsg::Transfer {
+        asset: Asset::new(AssetInfo::Native("uusd".to_string()), 100u128),
+        recipient: Addr::unchecked("addr0000"),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        transfer_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        transfer_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(100u128, "uusd")],
+        }))]
+    );
+}
+
+#[test]
+fn provide_liquidity() {
+    let mut deps = setup();
+    set_position(&mut deps, Decimal::zero(), Decimal::percent(5));
+
+    // the assets can be given in either order
+    let provide_msg = ExecuteMsg::ProvideLiquidity {
+        pair: Addr::unchecked("pair0000"),
+        assets: [
+            Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 200u128),
+            Asset::new(AssetInfo::Native("uusd".to_string()), 100u128),
+        ],
+        slippage_tolerance: Some(Decimal::percent(1)),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        provide_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        ExecuteMsg::ProvideLiquidity {
+            pair: Addr::unchecked("pair0000"),
+            assets: [
+                Asset::new(AssetInfo::Native("uluna".to_string()), 100u128),
+                Asset::new(AssetInfo::Native("uusd".to_string()), 100u128),
+            ],
+            slippage_tolerance: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid assets"));
+
+    // 100 LP tokens out of 1100 exceed the 5% range
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        provide_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("share exceeds the target range"));
+
+    set_position(&mut deps, Decimal::zero(), Decimal::percent(10));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        provide_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: "pair0000".to_string(),
+                    amount: Uint128::from(200u128),
+                    expires: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                funds: vec![coin(100u128, "uusd")],
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        Asset::new(AssetInfo::Native("uusd".to_string()), 100u128),
+                        Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 200u128),
+                    ],
+                    slippage_tolerance: Some(Decimal::percent(1)),
+                    receiver: None,
+                    auto_stake: None,
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    with_pools(&mut deps, 1100, 2200, 1100);
+    deps.querier
+        .with_token_balance("liquidity0000", MOCK_CONTRACT_ADDR, Uint128::from(100u128));
+
+    let position = query_position(&deps);
+    assert_eq!(position.share, Decimal::from_ratio(1u128, 11u128));
+    assert_eq!(
+        position.provided,
+        [
+            Asset::new(AssetInfo::Native("uusd".to_string()), 100u128),
+            Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 200u128),
+        ]
+    );
+    assert_eq!(position.value, Uint128::from(400u128));
+    assert_eq!(position.cost, Uint128::from(400u128));
+    assert_eq!(position.pnl, Uint128::zero());
+}
+
+#[test]
+fn withdraw_liquidity_and_pnl() {
+    let mut deps = setup();
+    set_position(&mut deps, Decimal::percent(5), Decimal::percent(10));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::ProvideLiquidity {
+            pair: Addr::unchecked("pair0000"),
+            assets: [
+                Asset::new(AssetInfo::Native("uusd".to_string()), 100u128),
+                Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 200u128),
+            ],
+            slippage_tolerance: None,
+        },
+    )
+    .unwrap();
+    deps.querier
+        .with_token_balance("liquidity0000", MOCK_CONTRACT_ADDR, Uint128::from(100u128));
+
+    // the uusd price rises, the pool holding less uusd
+    with_pools(&mut deps, 1000, 2420, 1100);
+    let position = query_position(&deps);
+    assert_eq!(
+        position.underlying,
+        [
+            Asset::new(AssetInfo::Native("uusd".to_string()), 90u128),
+            Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 220u128),
+        ]
+    );
+    // 220 + 90 * 2.42 against 200 + 100 * 2.42
+    assert_eq!(position.value, Uint128::from(437u128));
+    assert_eq!(position.cost, Uint128::from(442u128));
+    assert_eq!(position.pnl, Uint128::from(5u128));
+    assert!(position.is_loss);
+
+    // 40 LP tokens out of 1040 fall below the 5% range
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        ExecuteMsg::WithdrawLiquidity {
+            pair: Addr::unchecked("pair0000"),
+            amount: Uint128::from(60u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("share falls below the target range")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        ExecuteMsg::WithdrawLiquidity {
+            pair: Addr::unchecked("pair0000"),
+            amount: Uint128::from(101u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid withdraw amount"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("operator0000", &[]),
+        ExecuteMsg::WithdrawLiquidity {
+            pair: Addr::unchecked("pair0000"),
+            amount: Uint128::from(44u128),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "liquidity0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "pair0000".to_string(),
+                amount: Uint128::from(44u128),
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // the position cannot be removed while holding LP tokens
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::RemovePosition {
+            pair: Addr::unchecked("pair0000"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("position still holds liquidity"));
+
+    // the withdrawn assets keep counting towards the value
+    with_pools(&mut deps, 960, 2324, 1056);
+    deps.querier
+        .with_token_balance("liquidity0000", MOCK_CONTRACT_ADDR, Uint128::from(56u128));
+    let position = query_position(&deps);
+    assert_eq!(
+        position.withdrawn,
+        [
+            Asset::new(AssetInfo::Native("uusd".to_string()), 40u128),
+            Asset::new(AssetInfo::Cw20(Addr::unchecked("token0000")), 96u128),
+        ]
+    );
+    assert_eq!(position.share, Decimal::from_ratio(56u128, 1056u128));
+}
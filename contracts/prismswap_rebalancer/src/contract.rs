@@ -0,0 +1,378 @@
+use crate::state::{Config, CONFIG, LAST_REBALANCE_TIME, TARGETS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::Cw20ExecuteMsg;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::oracle::{QueryMsg as OracleQueryMsg, TwapResponse};
+use prismswap::price::divide_by_price;
+use prismswap::rebalancer::{
+    ConfigResponse, ExecuteMsg, Holding, HoldingsResponse, InstantiateMsg, QueryMsg, Target,
+};
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation,
+};
+use prismswap::signed_decimal::SignedDecimal;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    msg.base_asset.check(deps.api)?;
+    assert_max_slippage(msg.max_slippage)?;
+    if let Some(keeper_reward) = &msg.keeper_reward {
+        keeper_reward.info.check(deps.api)?;
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+        oracle: deps.api.addr_validate(msg.oracle.as_str())?,
+        base_asset: msg.base_asset,
+        drift_threshold: msg.drift_threshold,
+        max_slippage: msg.max_slippage,
+        twap_window: msg.twap_window,
+        cooldown: msg.cooldown,
+        keeper_reward: msg.keeper_reward,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    TARGETS.save(deps.storage, &vec![])?;
+    LAST_REBALANCE_TIME.save(deps.storage, &0u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig {
+            owner,
+            drift_threshold,
+            max_slippage,
+            twap_window,
+            cooldown,
+            keeper_reward,
+        } => execute_update_config(
+            deps,
+            info,
+            owner,
+            drift_threshold,
+            max_slippage,
+            twap_window,
+            cooldown,
+            keeper_reward,
+        ),
+        ExecuteMsg::SetTargets { targets } => execute_set_targets(deps, info, targets),
+        ExecuteMsg::Rebalance {} => execute_rebalance(deps, env, info),
+    }
+}
+
+// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    drift_threshold: Option<Decimal>,
+    max_slippage: Option<Decimal>,
+    twap_window: Option<u64>,
+    cooldown: Option<u64>,
+    keeper_reward: Option<Asset>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(drift_threshold) = drift_threshold {
+        config.drift_threshold = drift_threshold;
+    }
+
+    if let Some(max_slippage) = max_slippage {
+        assert_max_slippage(max_slippage)?;
+        config.max_slippage = max_slippage;
+    }
+
+    if let Some(twap_window) = twap_window {
+        config.twap_window = twap_window;
+    }
+
+    if let Some(cooldown) = cooldown {
+        config.cooldown = cooldown;
+    }
+
+    if let Some(keeper_reward) = keeper_reward {
+        keeper_reward.info.check(deps.api)?;
+        config.keeper_reward = Some(keeper_reward);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_max_slippage(max_slippage: Decimal) -> StdResult<()> {
+    if max_slippage >= Decimal::one() {
+        return Err(StdError::generic_err("max slippage must be below 1"));
+    }
+
+    Ok(())
+}
+
+// Only owner can execute it
+pub fn execute_set_targets(
+    deps: DepsMut,
+    info: MessageInfo,
+    targets: Vec<Target>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut total_weight = Decimal::zero();
+    for (i, target) in targets.iter().enumerate() {
+        target.asset_info.check(deps.api)?;
+        if targets[..i]
+            .iter()
+            .any(|other| other.asset_info == target.asset_info)
+        {
+            return Err(StdError::generic_err("duplicate target asset"));
+        }
+
+        total_weight = total_weight + target.weight;
+    }
+
+    if total_weight != Decimal::one() {
+        return Err(StdError::generic_err("target weights must sum to 1"));
+    }
+
+    TARGETS.save(deps.storage, &targets)?;
+
+    Ok(Response::new().add_attribute("action", "set_targets"))
+}
+
+pub fn execute_rebalance(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+
+    let last_rebalance_time = LAST_REBALANCE_TIME.load(deps.storage)?;
+    if now < last_rebalance_time + config.cooldown {
+        return Err(StdError::generic_err("rebalance cooldown not elapsed"));
+    }
+
+    let (holdings, total_value) = compute_holdings(deps.as_ref(), &env, &config)?;
+
+    // the most overweight holding is swapped into the most underweight one
+    let over = holdings
+        .iter()
+        .filter(|holding| holding.drift > SignedDecimal::zero())
+        .max_by_key(|holding| holding.drift);
+    let under = holdings
+        .iter()
+        .filter(|holding| holding.drift < SignedDecimal::zero())
+        .max_by_key(|holding| -holding.drift);
+    let (over, under) = match (over, under) {
+        (Some(over), Some(under))
+            if max_drift(&holdings) > config.drift_threshold && !over.price.is_zero() =>
+        {
+            (over, under)
+        }
+        _ => return Err(StdError::generic_err("drift within threshold")),
+    };
+
+    let excess = over.value - total_value * over.target_weight;
+    let deficit = total_value * under.target_weight - under.value;
+    let swap_value = std::cmp::min(excess, deficit);
+
+    let offer_amount = divide_by_price(swap_value, over.price)?;
+    let minimum_receive = if under.price.is_zero() {
+        Uint128::zero()
+    } else {
+        divide_by_price(swap_value, under.price)? * (Decimal::one() - config.max_slippage)
+    };
+
+    // swaps between other assets go through the base asset
+    let operations =
+        if over.asset_info == config.base_asset || under.asset_info == config.base_asset {
+            vec![swap_operation(&over.asset_info, &under.asset_info)]
+        } else {
+            vec![
+                swap_operation(&over.asset_info, &config.base_asset),
+                swap_operation(&config.base_asset, &under.asset_info),
+            ]
+        };
+    let swap_msg = ExecuteSwapOperationsMsg {
+        operations,
+        minimum_receive: Some(minimum_receive),
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    };
+
+    let mut messages: Vec<CosmosMsg> = vec![match &over.asset_info {
+        AssetInfo::Native(denom) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.router.to_string(),
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: offer_amount,
+            }],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap_msg))?,
+        }),
+        AssetInfo::Cw20(contract_addr) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: config.router.to_string(),
+                amount: offer_amount,
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap_msg))?,
+            })?,
+        }),
+    }];
+
+    if let Some(keeper_reward) = &config.keeper_reward {
+        messages.push(keeper_reward.transfer_msg(&info.sender)?);
+    }
+
+    LAST_REBALANCE_TIME.save(deps.storage, &now)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "rebalance"),
+        (
+            "offer_asset",
+            &Asset::new(over.asset_info.clone(), offer_amount).to_string(),
+        ),
+        ("ask_asset_info", &under.asset_info.to_string()),
+        ("minimum_receive", &minimum_receive.to_string()),
+    ]))
+}
+
+fn swap_operation(offer_asset_info: &AssetInfo, ask_asset_info: &AssetInfo) -> SwapOperation {
+    SwapOperation::PrismSwap {
+        offer_asset_info: offer_asset_info.clone(),
+        ask_asset_info: ask_asset_info.clone(),
+        pair: None,
+        max_spread: None,
+    }
+}
+
+/// Returns the target holdings valued in the base asset at the oracle TWAPs, with their total
+fn compute_holdings(deps: Deps, env: &Env, config: &Config) -> StdResult<(Vec<Holding>, Uint128)> {
+    let targets = TARGETS.load(deps.storage)?;
+
+    let mut holdings: Vec<Holding> = vec![];
+    for target in targets {
+        let amount = target
+            .asset_info
+            .query_pool(&deps.querier, &env.contract.address)?;
+        let price = if target.asset_info == config.base_asset {
+            Decimal::one()
+        } else {
+            let res: TwapResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: config.oracle.to_string(),
+                msg: to_binary(&OracleQueryMsg::Twap {
+                    base: target.asset_info.clone(),
+                    quote: config.base_asset.clone(),
+                    window: config.twap_window,
+                })?,
+            }))?;
+            res.price
+        };
+
+        holdings.push(Holding {
+            asset_info: target.asset_info,
+            amount,
+            price,
+            value: amount * price,
+            weight: Decimal::zero(),
+            target_weight: target.weight,
+            drift: SignedDecimal::negative(target.weight),
+        });
+    }
+
+    let total_value = holdings
+        .iter()
+        .fold(Uint128::zero(), |total, holding| total + holding.value);
+    if !total_value.is_zero() {
+        for holding in holdings.iter_mut() {
+            holding.weight = Decimal::from_ratio(holding.value, total_value);
+            holding.drift = SignedDecimal::diff(holding.weight, holding.target_weight);
+        }
+    }
+
+    Ok((holdings, total_value))
+}
+
+fn max_drift(holdings: &[Holding]) -> Decimal {
+    holdings
+        .iter()
+        .map(|holding| holding.drift.abs())
+        .max()
+        .unwrap_or_default()
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Holdings {} => to_binary(&query_holdings(deps, env)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        router: config.router,
+        oracle: config.oracle,
+        base_asset: config.base_asset,
+        drift_threshold: config.drift_threshold,
+        max_slippage: config.max_slippage,
+        twap_window: config.twap_window,
+        cooldown: config.cooldown,
+        keeper_reward: config.keeper_reward,
+        targets: TARGETS.load(deps.storage)?,
+        last_rebalance_time: LAST_REBALANCE_TIME.load(deps.storage)?,
+    })
+}
+
+pub fn query_holdings(deps: Deps, env: Env) -> StdResult<HoldingsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let (holdings, total_value) = compute_holdings(deps, &env, &config)?;
+
+    let max_drift = max_drift(&holdings);
+    let last_rebalance_time = LAST_REBALANCE_TIME.load(deps.storage)?;
+    let workable = max_drift > config.drift_threshold
+        && env.block.time.seconds() >= last_rebalance_time + config.cooldown;
+
+    Ok(HoldingsResponse {
+        holdings,
+        total_value,
+        max_drift,
+        workable,
+    })
+}
@@ -0,0 +1,120 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse, Cw20QueryMsg};
+use prismswap::asset::AssetInfo;
+use prismswap::oracle::{QueryMsg as OracleQueryMsg, TwapResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    // the oracle TWAPs of the assets in the base asset
+    prices: HashMap<String, Decimal>,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if contract_addr == "oracle0000" =>
+            {
+                match from_binary(msg).unwrap() {
+                    OracleQueryMsg::Twap { base, window, .. } => {
+                        match self.prices.get(&base.to_string()) {
+                            Some(price) => {
+                                SystemResult::Ok(ContractResult::from(to_binary(&TwapResponse {
+                                    price: *price,
+                                    window,
+                                })))
+                            }
+                            None => SystemResult::Err(SystemError::InvalidRequest {
+                                error: "No price exists".to_string(),
+                                request: msg.as_slice().into(),
+                            }),
+                        }
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            prices: HashMap::new(),
+        }
+    }
+
+    pub fn with_balance(&mut self, balances: &[(String, &[Coin])]) {
+        for (addr, balance) in balances {
+            self.base.update_balance(addr, balance.to_vec());
+        }
+    }
+
+    pub fn with_token_balance(&mut self, token: &str, address: &str, balance: Uint128) {
+        self.token_balances
+            .entry(token.to_string())
+            .or_default()
+            .insert(address.to_string(), balance);
+    }
+
+    pub fn with_price(&mut self, asset_info: &AssetInfo, price: Decimal) {
+        self.prices.insert(asset_info.to_string(), price);
+    }
+}
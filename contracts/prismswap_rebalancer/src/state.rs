@@ -0,0 +1,24 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::Item;
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::rebalancer::Target;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub router: Addr,
+    pub oracle: Addr,
+    pub base_asset: AssetInfo,
+    pub drift_threshold: Decimal,
+    pub max_slippage: Decimal,
+    pub twap_window: u64,
+    pub cooldown: u64,
+    pub keeper_reward: Option<Asset>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+pub const TARGETS: Item<Vec<Target>> = Item::new("targets");
+pub const LAST_REBALANCE_TIME: Item<u64> = Item::new("last_rebalance_time");
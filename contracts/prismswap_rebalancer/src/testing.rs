@@ -0,0 +1,298 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, Env, OwnedDeps, StdError,
+    SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::rebalancer::{
+    ConfigResponse, ExecuteMsg, HoldingsResponse, InstantiateMsg, QueryMsg, Target,
+};
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation,
+};
+use prismswap::signed_decimal::SignedDecimal;
+
+fn uusd() -> AssetInfo {
+    AssetInfo::Native("uusd".to_string())
+}
+
+fn uluna() -> AssetInfo {
+    AssetInfo::Native("uluna".to_string())
+}
+
+fn token() -> AssetInfo {
+    AssetInfo::Cw20(Addr::unchecked("token0000"))
+}
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+/// Instantiates the rebalancer valuing its holdings in uusd, token0000 being worth 5 uusd and
+/// uluna 50 uusd
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier
+        .with_price(&token(), Decimal::from_ratio(5u128, 1u128));
+    deps.querier
+        .with_price(&uluna(), Decimal::from_ratio(50u128, 1u128));
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            owner: Addr::unchecked("owner0000"),
+            router: Addr::unchecked("router0000"),
+            oracle: Addr::unchecked("oracle0000"),
+            base_asset: uusd(),
+            drift_threshold: Decimal::percent(5),
+            max_slippage: Decimal::percent(1),
+            twap_window: 3600,
+            cooldown: 600,
+            keeper_reward: Some(Asset::new(uusd(), 10u128)),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+fn set_targets(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, targets: Vec<Target>) {
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::SetTargets { targets },
+    )
+    .unwrap();
+}
+
+fn target(asset_info: AssetInfo, weight: u64) -> Target {
+    Target {
+        asset_info,
+        weight: Decimal::percent(weight),
+    }
+}
+
+fn swap_msg(operations: Vec<SwapOperation>, minimum_receive: u128) -> ExecuteSwapOperationsMsg {
+    ExecuteSwapOperationsMsg {
+        operations,
+        minimum_receive: Some(Uint128::from(minimum_receive)),
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = setup();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SetTargets {
+            targets: vec![target(uusd(), 50), target(token(), 50)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::SetTargets {
+            targets: vec![target(uusd(), 50), target(token(), 40)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("target weights must sum to 1"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::SetTargets {
+            targets: vec![target(uusd(), 50), target(uusd(), 50)],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("duplicate target asset"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            drift_threshold: None,
+            max_slippage: Some(Decimal::one()),
+            twap_window: None,
+            cooldown: None,
+            keeper_reward: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("max slippage must be below 1"));
+
+    set_targets(&mut deps, vec![target(uusd(), 50), target(token(), 50)]);
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.targets,
+        vec![target(uusd(), 50), target(token(), 50)]
+    );
+    assert_eq!(config.last_rebalance_time, 0);
+}
+
+#[test]
+fn rebalance_into_base_asset() {
+    let mut deps = setup();
+    set_targets(&mut deps, vec![target(uusd(), 50), target(token(), 50)]);
+
+    // 1000 uusd and 300 tokens worth 1500 uusd weigh 40% and 60%
+    deps.querier
+        .with_balance(&[(MOCK_CONTRACT_ADDR.to_string(), &[coin(1000u128, "uusd")])]);
+    deps.querier
+        .with_token_balance("token0000", MOCK_CONTRACT_ADDR, Uint128::from(300u128));
+
+    let holdings: HoldingsResponse =
+        from_binary(&query(deps.as_ref(), env_at(1000), QueryMsg::Holdings {}).unwrap()).unwrap();
+    assert_eq!(holdings.total_value, Uint128::from(2500u128));
+    assert_eq!(holdings.holdings[1].value, Uint128::from(1500u128));
+    assert_eq!(holdings.holdings[1].weight, Decimal::percent(60));
+    assert_eq!(
+        holdings.holdings[0].drift,
+        SignedDecimal::negative(Decimal::percent(10))
+    );
+    assert_eq!(holdings.holdings[1].drift, Decimal::percent(10).into());
+    assert_eq!(holdings.max_drift, Decimal::percent(10));
+    assert!(holdings.workable);
+
+    // the 250 uusd of excess tokens are swapped for at least 247 uusd
+    let res = execute(
+        deps.as_mut(),
+        env_at(1000),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Rebalance {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "token0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "router0000".to_string(),
+                    amount: Uint128::from(50u128),
+                    msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap_msg(
+                        vec![SwapOperation::PrismSwap {
+                            offer_asset_info: token(),
+                            ask_asset_info: uusd(),
+                            pair: None,
+                            max_spread: None,
+                        }],
+                        247,
+                    )))
+                    .unwrap(),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "keeper0000".to_string(),
+                amount: vec![coin(10u128, "uusd")],
+            })),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        env_at(1599),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Rebalance {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("rebalance cooldown not elapsed"));
+
+    // once rebalanced, the drift stays within the threshold
+    deps.querier
+        .with_balance(&[(MOCK_CONTRACT_ADDR.to_string(), &[coin(1240u128, "uusd")])]);
+    deps.querier
+        .with_token_balance("token0000", MOCK_CONTRACT_ADDR, Uint128::from(250u128));
+    let err = execute(
+        deps.as_mut(),
+        env_at(1600),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Rebalance {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("drift within threshold"));
+}
+
+#[test]
+fn rebalance_through_base_asset() {
+    let mut deps = setup();
+    set_targets(
+        &mut deps,
+        vec![target(uusd(), 20), target(uluna(), 40), target(token(), 40)],
+    );
+
+    // 200 uusd, 10 uluna worth 500 uusd and 60 tokens worth 300 uusd
+    deps.querier.with_balance(&[(
+        MOCK_CONTRACT_ADDR.to_string(),
+        &[coin(200u128, "uusd"), coin(10u128, "uluna")],
+    )]);
+    deps.querier
+        .with_token_balance("token0000", MOCK_CONTRACT_ADDR, Uint128::from(60u128));
+
+    // the 100 uusd of excess uluna are swapped into tokens through uusd
+    let res = execute(
+        deps.as_mut(),
+        env_at(1000),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::Rebalance {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router0000".to_string(),
+            funds: vec![coin(2u128, "uluna")],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap_msg(
+                vec![
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: uluna(),
+                        ask_asset_info: uusd(),
+                        pair: None,
+                        max_spread: None,
+                    },
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: uusd(),
+                        ask_asset_info: token(),
+                        pair: None,
+                        max_spread: None,
+                    },
+                ],
+                19,
+            )))
+            .unwrap(),
+        }))
+    );
+}
@@ -0,0 +1,310 @@
+use crate::state::{read_inventory, INVENTORY, MAKERS, USED_NONCES};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, to_vec, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response,
+    StdError, StdResult, Uint128,
+};
+use cw20::Cw20ReceiveMsg;
+use cw_storage_plus::U64Key;
+use sha2::{Digest, Sha256};
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::asset_list::{AssetList, PrismSwapAssetList};
+use prismswap::rfq::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MakerResponse, NonceUsedResponse, QueryMsg, Quote,
+    QuoteDigestResponse, QuotePayload,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    _deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: InstantiateMsg,
+) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::RegisterMaker { public_key } => execute_register_maker(deps, info, public_key),
+        ExecuteMsg::Deposit {} => {
+            let assets: Vec<Asset> = info
+                .funds
+                .iter()
+                .map(|coin| Asset::from(coin.clone()))
+                .collect();
+            execute_deposit(deps, info.sender, assets)
+        }
+        ExecuteMsg::Withdraw { asset } => execute_withdraw(deps, info, asset),
+        ExecuteMsg::WithdrawMany { assets } => execute_withdraw_many(deps, info, assets),
+        ExecuteMsg::Fill { quote, signature } => {
+            if info.funds.len() != 1 {
+                return Err(StdError::generic_err("must send exactly one native asset"));
+            }
+
+            let offer_asset = Asset::from(info.funds[0].clone());
+            execute_fill(deps, env, info.sender, offer_asset, quote, signature)
+        }
+        ExecuteMsg::CancelNonce { nonce } => execute_cancel_nonce(deps, info, nonce),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+    let asset = Asset::new(AssetInfo::Cw20(info.sender), cw20_msg.amount);
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Deposit {} => execute_deposit(deps, sender, vec![asset]),
+        Cw20HookMsg::Fill { quote, signature } => {
+            execute_fill(deps, env, sender, asset, quote, signature)
+        }
+    }
+}
+
+pub fn execute_register_maker(
+    deps: DepsMut,
+    info: MessageInfo,
+    public_key: Binary,
+) -> StdResult<Response> {
+    // compressed or uncompressed secp256k1 public keys
+    if public_key.len() != 33 && public_key.len() != 65 {
+        return Err(StdError::generic_err("invalid public key"));
+    }
+
+    MAKERS.save(deps.storage, &info.sender, &public_key)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "register_maker"),
+        ("maker", info.sender.as_str()),
+    ]))
+}
+
+pub fn execute_deposit(deps: DepsMut, maker: Addr, assets: Vec<Asset>) -> StdResult<Response> {
+    if !MAKERS.has(deps.storage, &maker) {
+        return Err(StdError::generic_err("maker not registered"));
+    }
+
+    if assets.is_empty() || assets.iter().any(|asset| asset.amount.is_zero()) {
+        return Err(StdError::generic_err(
+            "deposit amount must be greater than 0",
+        ));
+    }
+
+    for asset in assets.iter() {
+        add_inventory(deps.storage, &maker, &asset.info, asset.amount)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "deposit"),
+        ("maker", maker.as_str()),
+        (
+            "assets",
+            &assets
+                .iter()
+                .map(|asset| asset.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+        ),
+    ]))
+}
+
+pub fn execute_withdraw(deps: DepsMut, info: MessageInfo, asset: Asset) -> StdResult<Response> {
+    sub_inventory(deps.storage, &info.sender, &asset.info, asset.amount)?;
+
+    Ok(Response::new()
+        .add_message(asset.transfer_msg(&info.sender)?)
+        .add_attributes(vec![
+            ("action", "withdraw"),
+            ("maker", info.sender.as_str()),
+            ("asset", &asset.to_string()),
+        ]))
+}
+
+pub fn execute_withdraw_many(
+    deps: DepsMut,
+    info: MessageInfo,
+    assets: AssetList,
+) -> StdResult<Response> {
+    assets.validate(deps.api)?;
+    for asset in assets.to_vec() {
+        sub_inventory(deps.storage, &info.sender, &asset.info, asset.amount)?;
+    }
+
+    Ok(Response::new()
+        .add_messages(assets.transfer_msgs(&info.sender)?)
+        .add_attributes(vec![
+            ("action", "withdraw_many"),
+            ("maker", info.sender.as_str()),
+            ("assets", &assets.to_string()),
+        ]))
+}
+
+pub fn execute_fill(
+    deps: DepsMut,
+    env: Env,
+    taker: Addr,
+    offer_asset: Asset,
+    quote: Quote,
+    signature: Binary,
+) -> StdResult<Response> {
+    if let Some(quote_taker) = &quote.taker {
+        if *quote_taker != taker {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+    }
+
+    if env.block.time.seconds() >= quote.expiry {
+        return Err(StdError::generic_err("quote expired"));
+    }
+
+    if offer_asset.info != quote.offer_asset_info {
+        return Err(StdError::generic_err("invalid offer asset"));
+    }
+
+    if offer_asset.amount > quote.size {
+        return Err(StdError::generic_err("offer exceeds quote size"));
+    }
+
+    let nonce_key = (&quote.maker, U64Key::new(quote.nonce));
+    if USED_NONCES.has(deps.storage, nonce_key.clone()) {
+        return Err(StdError::generic_err("quote already used"));
+    }
+
+    let public_key = MAKERS
+        .may_load(deps.storage, &quote.maker)?
+        .ok_or_else(|| StdError::generic_err("maker not registered"))?;
+    let digest = quote_digest(&env.contract.address, &quote)?;
+    if !deps
+        .api
+        .secp256k1_verify(&digest, &signature, &public_key)
+        .unwrap_or(false)
+    {
+        return Err(StdError::generic_err("invalid signature"));
+    }
+
+    let ask_amount = offer_asset.amount * quote.price;
+    if ask_amount.is_zero() {
+        return Err(StdError::generic_err("fill too small"));
+    }
+
+    USED_NONCES.save(deps.storage, nonce_key, &true)?;
+    sub_inventory(
+        deps.storage,
+        &quote.maker,
+        &quote.ask_asset_info,
+        ask_amount,
+    )?;
+    add_inventory(
+        deps.storage,
+        &quote.maker,
+        &offer_asset.info,
+        offer_asset.amount,
+    )?;
+
+    let ask_asset = Asset::new(quote.ask_asset_info.clone(), ask_amount);
+
+    Ok(Response::new()
+        .add_message(ask_asset.transfer_msg(&taker)?)
+        .add_attributes(vec![
+            ("action", "fill"),
+            ("maker", quote.maker.as_str()),
+            ("taker", taker.as_str()),
+            ("nonce", &quote.nonce.to_string()),
+            ("offer_asset", &offer_asset.to_string()),
+            ("ask_asset", &ask_asset.to_string()),
+        ]))
+}
+
+pub fn execute_cancel_nonce(deps: DepsMut, info: MessageInfo, nonce: u64) -> StdResult<Response> {
+    USED_NONCES.save(deps.storage, (&info.sender, U64Key::new(nonce)), &true)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "cancel_nonce"),
+        ("maker", info.sender.as_str()),
+        ("nonce", &nonce.to_string()),
+    ]))
+}
+
+/// Returns the SHA-256 hash of the quote payload, which the maker signs
+fn quote_digest(contract: &Addr, quote: &Quote) -> StdResult<Vec<u8>> {
+    let payload = to_vec(&QuotePayload {
+        contract: contract.clone(),
+        quote: quote.clone(),
+    })?;
+
+    Ok(Sha256::digest(&payload).to_vec())
+}
+
+fn add_inventory(
+    storage: &mut dyn cosmwasm_std::Storage,
+    maker: &Addr,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<()> {
+    INVENTORY.update(
+        storage,
+        (maker, asset_info.as_bytes()),
+        |asset| -> StdResult<Asset> {
+            let balance = asset.map(|asset| asset.amount).unwrap_or_default();
+            Ok(Asset::new(asset_info.clone(), balance + amount))
+        },
+    )?;
+
+    Ok(())
+}
+
+fn sub_inventory(
+    storage: &mut dyn cosmwasm_std::Storage,
+    maker: &Addr,
+    asset_info: &AssetInfo,
+    amount: Uint128,
+) -> StdResult<()> {
+    let balance = INVENTORY
+        .may_load(storage, (maker, asset_info.as_bytes()))?
+        .map(|asset| asset.amount)
+        .unwrap_or_default();
+    if balance < amount {
+        return Err(StdError::generic_err("insufficient maker inventory"));
+    }
+
+    INVENTORY.save(
+        storage,
+        (maker, asset_info.as_bytes()),
+        &Asset::new(asset_info.clone(), balance - amount),
+    )
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Maker { maker } => to_binary(&query_maker(deps, maker)?),
+        QueryMsg::QuoteDigest { quote } => to_binary(&QuoteDigestResponse {
+            digest: Binary::from(quote_digest(&env.contract.address, &quote)?),
+        }),
+        QueryMsg::NonceUsed { maker, nonce } => to_binary(&NonceUsedResponse {
+            used: USED_NONCES.has(deps.storage, (&maker, U64Key::new(nonce))),
+        }),
+    }
+}
+
+pub fn query_maker(deps: Deps, maker: Addr) -> StdResult<MakerResponse> {
+    let public_key = MAKERS
+        .may_load(deps.storage, &maker)?
+        .ok_or_else(|| StdError::generic_err("maker not registered"))?;
+
+    Ok(MakerResponse {
+        inventory: read_inventory(deps.storage, &maker)?,
+        maker,
+        public_key,
+    })
+}
@@ -0,0 +1,24 @@
+use cosmwasm_std::{Addr, Binary, Order, StdResult, Storage};
+use cw_storage_plus::{Map, U64Key};
+use prismswap::asset::Asset;
+
+/// the public keys the quotes of the market makers are signed with
+pub const MAKERS: Map<&Addr, Binary> = Map::new("makers");
+
+/// the inventories of the market makers, keyed by the maker and the asset bytes
+pub const INVENTORY: Map<(&Addr, &[u8]), Asset> = Map::new("inventory");
+
+/// the nonces of the quotes filled or cancelled by every market maker
+pub const USED_NONCES: Map<(&Addr, U64Key), bool> = Map::new("used_nonces");
+
+pub fn read_inventory(storage: &dyn Storage, maker: &Addr) -> StdResult<Vec<Asset>> {
+    INVENTORY
+        .prefix(maker)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| Ok(item?.1))
+        .filter(|item: &StdResult<Asset>| match item {
+            Ok(asset) => !asset.amount.is_zero(),
+            Err(_) => true,
+        })
+        .collect()
+}
@@ -0,0 +1,407 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR,
+};
+use cosmwasm_std::{
+    coins, from_binary, to_binary, to_vec, Addr, BankMsg, Binary, CosmosMsg, Decimal, Env,
+    OwnedDeps, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use k256::ecdsa::signature::Signer;
+use k256::ecdsa::{Signature, SigningKey};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::asset_list::AssetList;
+use prismswap::rfq::{
+    Cw20HookMsg, ExecuteMsg, InstantiateMsg, MakerResponse, NonceUsedResponse, QueryMsg, Quote,
+    QuoteDigestResponse, QuotePayload,
+};
+use sha2::{Digest, Sha256};
+
+fn env_at(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn signing_key() -> SigningKey {
+    SigningKey::from_bytes(&[1u8; 32]).unwrap()
+}
+
+fn sign(quote: &Quote) -> Binary {
+    let payload = to_vec(&QuotePayload {
+        contract: Addr::unchecked(MOCK_CONTRACT_ADDR),
+        quote: quote.clone(),
+    })
+    .unwrap();
+    let signature: Signature = signing_key().sign(&payload);
+    Binary::from(signature.as_ref())
+}
+
+/// A quote of the maker buying up to 1000 uusd at 2 prism each, expiring in 100 seconds
+fn quote(nonce: u64) -> Quote {
+    Quote {
+        maker: Addr::unchecked("maker0000"),
+        taker: None,
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        price: Decimal::from_ratio(2u128, 1u128),
+        size: Uint128::from(1000u128),
+        expiry: mock_env().block.time.seconds() + 100,
+        nonce,
+    }
+}
+
+/// Instantiates the contract with a maker holding 10000 prism
+fn setup() -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {},
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::RegisterMaker {
+        public_key: Binary::from(signing_key().verifying_key().to_bytes().as_slice()),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "maker0000".to_string(),
+        amount: Uint128::from(10000u128),
+        msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+
+    deps
+}
+
+#[test]
+fn fill() {
+    let mut deps = setup();
+
+    let quote = quote(1);
+    let signature = sign(&quote);
+    let msg = ExecuteMsg::Fill {
+        quote: quote.clone(),
+        signature,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(400u128, "uusd")),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "taker0000".to_string(),
+                amount: Uint128::from(800u128),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))]
+    );
+
+    // the digest queried is the one signed
+    let res: QuoteDigestResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::QuoteDigest {
+                quote: quote.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let payload = to_vec(&QuotePayload {
+        contract: Addr::unchecked(MOCK_CONTRACT_ADDR),
+        quote,
+    })
+    .unwrap();
+    assert_eq!(res.digest.as_slice(), Sha256::digest(&payload).as_slice());
+
+    let res: MakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Maker {
+                maker: Addr::unchecked("maker0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.inventory.len(), 2);
+    assert!(res.inventory.contains(&Asset::new(
+        AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        Uint128::from(9200u128),
+    )));
+    assert!(res.inventory.contains(&Asset::new(
+        AssetInfo::Native("uusd".to_string()),
+        Uint128::from(400u128),
+    )));
+
+    // a quote is filled once
+    let res = execute(
+        deps.as_mut(),
+        env_at(20),
+        mock_info("taker0001", &coins(400u128, "uusd")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("quote already used")));
+
+    // the maker sells the uusd back
+    let msg = ExecuteMsg::Withdraw {
+        asset: Asset::new(
+            AssetInfo::Native("uusd".to_string()),
+            Uint128::from(400u128),
+        ),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "maker0000".to_string(),
+            amount: coins(400u128, "uusd"),
+        }))]
+    );
+}
+
+#[test]
+fn fill_invalid_quote() {
+    let mut deps = setup();
+
+    // signed over another quote
+    let mut tampered = quote(1);
+    let signature = sign(&tampered);
+    tampered.price = Decimal::from_ratio(3u128, 1u128);
+    let msg = ExecuteMsg::Fill {
+        quote: tampered,
+        signature,
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(400u128, "uusd")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("invalid signature")));
+
+    let quote = quote(1);
+    let msg = ExecuteMsg::Fill {
+        quote: quote.clone(),
+        signature: sign(&quote),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(100),
+        mock_info("taker0000", &coins(400u128, "uusd")),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("quote expired")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(1001u128, "uusd")),
+        msg.clone(),
+    );
+    assert_eq!(res, Err(StdError::generic_err("offer exceeds quote size")));
+
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(400u128, "uluna")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("invalid offer asset")));
+
+    // a quote for another taker
+    let mut quote = quote;
+    quote.taker = Some(Addr::unchecked("taker0001"));
+    let msg = ExecuteMsg::Fill {
+        quote: quote.clone(),
+        signature: sign(&quote),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(400u128, "uusd")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("unauthorized")));
+}
+
+#[test]
+fn cancel_nonce_and_inventory() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::CancelNonce { nonce: 1 };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg).unwrap();
+
+    let res: NonceUsedResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::NonceUsed {
+                maker: Addr::unchecked("maker0000"),
+                nonce: 1,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(res.used);
+
+    let quote1 = quote(1);
+    let msg = ExecuteMsg::Fill {
+        quote: quote1.clone(),
+        signature: sign(&quote1),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(400u128, "uusd")),
+        msg,
+    );
+    assert_eq!(res, Err(StdError::generic_err("quote already used")));
+
+    // the inventory cannot cover the fill
+    let mut quote2 = quote(2);
+    quote2.price = Decimal::from_ratio(20u128, 1u128);
+    let msg = ExecuteMsg::Fill {
+        quote: quote2.clone(),
+        signature: sign(&quote2),
+    };
+    let res = execute(
+        deps.as_mut(),
+        env_at(10),
+        mock_info("taker0000", &coins(600u128, "uusd")),
+        msg,
+    );
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("insufficient maker inventory"))
+    );
+
+    // cw20 fills go through the hook
+    let mut quote3 = quote(3);
+    quote3.offer_asset_info = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+    quote3.ask_asset_info = AssetInfo::Native("uusd".to_string());
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "taker0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Fill {
+            quote: quote3.clone(),
+            signature: sign(&quote3),
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), env_at(10), mock_info("prism0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("insufficient maker inventory"))
+    );
+
+    let msg = ExecuteMsg::Withdraw {
+        asset: Asset::new(
+            AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            Uint128::from(10001u128),
+        ),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("insufficient maker inventory"))
+    );
+}
+
+#[test]
+fn withdraw_many() {
+    let mut deps = setup();
+
+    let msg = ExecuteMsg::Deposit {};
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("maker0000", &coins(500u128, "uusd")),
+        msg,
+    )
+    .unwrap();
+
+    let prism = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+    let uusd = AssetInfo::Native("uusd".to_string());
+
+    // an asset cannot be listed twice
+    let msg = ExecuteMsg::WithdrawMany {
+        assets: AssetList::from(vec![
+            Asset::new(uusd.clone(), Uint128::from(100u128)),
+            Asset::new(uusd.clone(), Uint128::from(100u128)),
+        ]),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("asset list holds native:uusd twice"))
+    );
+
+    let msg = ExecuteMsg::WithdrawMany {
+        assets: AssetList::from(vec![
+            Asset::new(prism.clone(), Uint128::from(1000u128)),
+            Asset::new(uusd.clone(), Uint128::from(500u128)),
+        ]),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "maker0000".to_string(),
+                    amount: Uint128::from(1000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "maker0000".to_string(),
+                amount: coins(500u128, "uusd"),
+            })),
+        ]
+    );
+
+    let res: MakerResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Maker {
+                maker: Addr::unchecked("maker0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.inventory,
+        vec![Asset::new(prism, Uint128::from(9000u128))]
+    );
+
+    // the whole list fails when the inventory cannot cover one asset
+    let msg = ExecuteMsg::WithdrawMany {
+        assets: AssetList::from(vec![Asset::new(uusd, Uint128::from(1u128))]),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("maker0000", &[]), msg);
+    assert_eq!(
+        res,
+        Err(StdError::generic_err("insufficient maker inventory"))
+    );
+}
@@ -2,22 +2,46 @@
 use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
-    QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+    from_binary, to_binary, Addr, Api, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, QueryRequest, Reply, Response, StdResult, SubMsg, Uint128, WasmMsg, WasmQuery,
 };
 
-use crate::operations::execute_swap_operation;
-use crate::state::{Config, CONFIG};
+use crate::error::ContractError;
+use crate::fee::{
+    assert_fee_config, assert_referral, assert_relayer_config, compute_swap_fees,
+    execute_distribute_swap_output, has_swap_fees, load_relayer_fee, load_simulated_relayer_fee,
+};
+use crate::migration::migrate_config;
+use crate::operations::{execute_refund_dust, execute_swap_operation};
+use crate::receipt::{execute_emit_swap_receipt, reply_swap_receipt, SWAP_RECEIPT_REPLY_ID};
+use crate::route::query_estimate_required_offer;
+use crate::stake::{execute_swap_received_balance, execute_unstake_and_swap};
+use crate::state::{
+    assert_ask_asset_allowed, assert_hop_allowed, assert_staking_contract, clear_pair_cache,
+    is_fee_exempt, pair_key, read_asset_volumes, read_pair_info, Config, ASK_ASSET_WHITELIST,
+    CONFIG, FEE_CONFIG, FEE_EXEMPTIONS, GUARDIAN, PAIR_CACHE, PARAM_REGISTRY, PAUSED,
+    POINTS_TRACKER, RELAYER_CONFIG, RELAY_ORDERS, ROUTE_BLACKLIST, SLIPPAGE_CONFIG,
+    STAKING_CONTRACTS, SWAP_RECEIPT, SWAP_ROUTES, SWAP_TRADER, TWAP_CONFIG,
+};
+use crate::tax::taxed_denoms;
+use crate::twap::{assert_twap_config, query_twap_return_amount};
+use crate::zap::{
+    execute_provide_liquidity_from_single_asset, execute_provide_swapped_liquidity,
+    execute_transfer_balance, execute_withdraw_liquidity_to_single_asset,
+};
 
-use cw20::Cw20ReceiveMsg;
-use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAssetInfo};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::fee::SwapBreakdown;
 use prismswap::pair::{QueryMsg as PairQueryMsg, SimulationResponse};
-use prismswap::querier::query_pair_info;
+use prismswap::params::PAUSED_KEY;
+use prismswap::querier::query_param;
 use prismswap::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-    SimulateSwapOperationsResponse, SwapOperation, MAX_SWAP_OPERATIONS,
+    AssetVolumesResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, ExecuteSplitSwapOperationsMsg,
+    ExecuteSwapOperationsMsg, FeeConfig, InstantiateMsg, MigrateMsg, QueryMsg, Referral,
+    RelayOrder, RelayerConfig, RouteBlacklist, SimulateSwapOperationsResponse, SlippageConfig,
+    StakingContract, SwapAmount, SwapOperation, TaxInfo, TwapConfig, MAX_SWAP_OPERATIONS,
 };
-use std::collections::HashMap;
 use terra_cosmwasm::{SwapResponse, TerraMsgWrapper, TerraQuerier};
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -26,11 +50,12 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     CONFIG.save(
         deps.storage,
         &Config {
             factory: msg.factory,
+            owner: deps.api.addr_validate(msg.owner.as_str())?,
         },
     )?;
 
@@ -43,29 +68,145 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
-) -> StdResult<Response<TerraMsgWrapper>> {
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
-        ExecuteMsg::ExecuteSwapOperations {
-            operations,
-            minimum_receive,
+        ExecuteMsg::UpdateConfig { owner, guardian } => {
+            execute_update_config(deps, info, owner, guardian)
+        }
+        ExecuteMsg::Pause {} => execute_pause(deps, info),
+        ExecuteMsg::Unpause {} => execute_unpause(deps, info),
+        ExecuteMsg::Sweep { assets, to } => {
+            for asset_info in &assets {
+                asset_info.check(deps.api)?;
+            }
+            execute_sweep(deps, env, info, assets, to)
+        }
+        ExecuteMsg::UpdateFeeConfig { fee_config } => {
+            execute_update_fee_config(deps, info, fee_config)
+        }
+        ExecuteMsg::UpdateFeeExemptions { addresses } => {
+            execute_update_fee_exemptions(deps, info, addresses)
+        }
+        ExecuteMsg::UpdateRelayerConfig { relayer_config } => {
+            execute_update_relayer_config(deps, info, relayer_config)
+        }
+        ExecuteMsg::UpdateAskAssetWhitelist { ask_asset_infos } => {
+            if let Some(ask_asset_infos) = &ask_asset_infos {
+                for asset_info in ask_asset_infos {
+                    asset_info.check(deps.api)?;
+                }
+            }
+            execute_update_ask_asset_whitelist(deps, info, ask_asset_infos)
+        }
+        ExecuteMsg::UpdateSlippageConfig { slippage_config } => {
+            execute_update_slippage_config(deps, info, slippage_config)
+        }
+        ExecuteMsg::UpdateRouteBlacklist { route_blacklist } => {
+            if let Some(route_blacklist) = &route_blacklist {
+                for hop in &route_blacklist.hops {
+                    hop[0].check(deps.api)?;
+                    hop[1].check(deps.api)?;
+                }
+            }
+            execute_update_route_blacklist(deps, info, route_blacklist)
+        }
+        ExecuteMsg::UpdateStakingContracts { staking_contracts } => {
+            for staking_contract in &staking_contracts {
+                staking_contract.asset_info.check(deps.api)?;
+            }
+            execute_update_staking_contracts(deps, info, staking_contracts)
+        }
+        ExecuteMsg::UpdateTwapConfig { twap_config } => {
+            execute_update_twap_config(deps, info, twap_config)
+        }
+        ExecuteMsg::UpdatePointsTracker { points_tracker } => {
+            execute_update_points_tracker(deps, info, points_tracker)
+        }
+        ExecuteMsg::UpdateParamRegistry { param_registry } => {
+            execute_update_param_registry(deps, info, param_registry)
+        }
+        ExecuteMsg::InvalidatePairCache { asset_infos } => {
+            execute_invalidate_pair_cache(deps, info, asset_infos)
+        }
+        ExecuteMsg::ExecuteSwapOperations(msg) => {
+            assert_not_paused(deps.as_ref())?;
+            check_operations(deps.api, &msg.operations)?;
+
+            if info.funds.len() != 1 {
+                return Err(ContractError::InvalidNativeFunds {});
+            }
+
+            let offer_asset = Asset::from(info.funds[0].clone());
+            execute_swap_operations(deps, env, info.sender, offer_asset, msg, None)
+        }
+        ExecuteMsg::ExecuteSplitSwapOperations(msg) => {
+            assert_not_paused(deps.as_ref())?;
+            for route in &msg.routes {
+                check_operations(deps.api, &route.operations)?;
+            }
+
+            if info.funds.len() != 1 {
+                return Err(ContractError::InvalidNativeFunds {});
+            }
+
+            let offer_asset = Asset::from(info.funds[0].clone());
+            execute_split_swap_operations(deps, env, info.sender, offer_asset, msg)
+        }
+        ExecuteMsg::SubmitRelayOrder { order } => {
+            order.offer_asset.info.check(deps.api)?;
+            check_operations(deps.api, &order.operations)?;
+
+            execute_submit_relay_order(deps, env, info, order)
+        }
+        ExecuteMsg::CancelRelayOrder {} => execute_cancel_relay_order(deps, info),
+        ExecuteMsg::ExecuteRelayedSwapOperations { user } => {
+            assert_not_paused(deps.as_ref())?;
+            execute_relayed_swap_operations(deps, env, info, user)
+        }
+        ExecuteMsg::ProvideLiquidityFromSingleAsset {
+            pair,
+            min_lp,
+            max_spread,
             to,
+            staking_contract,
         } => {
-            for operation in &operations {
-                if let SwapOperation::PrismSwap {
-                    offer_asset_info,
-                    ask_asset_info,
-                } = &operation
-                {
-                    offer_asset_info.check(deps.api)?;
-                    ask_asset_info.check(deps.api)?;
-                };
+            assert_not_paused(deps.as_ref())?;
+            if info.funds.len() != 1 {
+                return Err(ContractError::InvalidNativeFunds {});
             }
-            execute_swap_operations(deps, env, info.sender, operations, minimum_receive, to)
+
+            let offer_asset = Asset::from(info.funds[0].clone());
+            execute_provide_liquidity_from_single_asset(
+                deps,
+                env,
+                info.sender,
+                offer_asset,
+                pair,
+                min_lp,
+                max_spread,
+                to,
+                staking_contract,
+            )
         }
-        ExecuteMsg::ExecuteSwapOperation { operation, to } => {
+        ExecuteMsg::ExecuteSwapOperation {
+            operation,
+            to,
+            amount,
+            tax_info,
+            max_spread,
+        } => {
             // this can only be called internally, no need to validate AssetInfo
-            execute_swap_operation(deps, env, info, operation, to.map(|v| v.to_string()))
+            execute_swap_operation(
+                deps,
+                env,
+                info,
+                operation,
+                to.map(|v| v.to_string()),
+                amount,
+                tax_info,
+                max_spread,
+            )
         }
         ExecuteMsg::AssertMinimumReceive {
             asset_info,
@@ -82,97 +223,1241 @@ pub fn execute(
                 receiver,
             )
         }
+        ExecuteMsg::ProvideSwappedLiquidity {
+            pair,
+            offer_asset,
+            ask_asset_info,
+            prev_ask_balance,
+            receiver,
+        } => execute_provide_swapped_liquidity(
+            deps,
+            env,
+            info,
+            pair,
+            offer_asset,
+            ask_asset_info,
+            prev_ask_balance,
+            receiver,
+        ),
+        ExecuteMsg::TransferBalance {
+            asset_info,
+            prev_balance,
+            receiver,
+        } => execute_transfer_balance(deps, env, info, asset_info, prev_balance, receiver),
+        ExecuteMsg::RefundDust {
+            prev_balances,
+            receiver,
+        } => execute_refund_dust(deps, env, info, prev_balances, receiver),
+        ExecuteMsg::DistributeSwapOutput {
+            asset_info,
+            prev_balance,
+            receiver,
+            referral,
+            relayer,
+            fee_exempt,
+            staking_contract,
+            minimum_receive,
+        } => execute_distribute_swap_output(
+            deps,
+            env,
+            info,
+            asset_info,
+            prev_balance,
+            receiver,
+            referral,
+            relayer,
+            fee_exempt,
+            staking_contract,
+            minimum_receive,
+        ),
+        ExecuteMsg::EmitSwapReceipt {} => execute_emit_swap_receipt(deps, env, info),
+        ExecuteMsg::RelaySwapOperations {
+            user,
+            offer_asset,
+            operations,
+            minimum_receive,
+            relayer,
+        } => {
+            if env.contract.address != info.sender {
+                return Err(ContractError::Unauthorized {});
+            }
+
+            execute_swap_operations(
+                deps,
+                env,
+                user.clone(),
+                offer_asset,
+                ExecuteSwapOperationsMsg {
+                    operations,
+                    minimum_receive: Some(minimum_receive),
+                    max_spread: None,
+                    to: Some(user),
+                    deadline: None,
+                    referral: None,
+                    swap_all: None,
+                    twap_check: None,
+                    twap_config: None,
+                    staking_contract: None,
+                },
+                Some(relayer),
+            )
+        }
+        ExecuteMsg::SwapReceivedBalance {
+            user,
+            asset_info,
+            prev_balance,
+            msg,
+        } => execute_swap_received_balance(deps, env, info, user, asset_info, prev_balance, msg),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    _deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    match msg.id {
+        SWAP_RECEIPT_REPLY_ID => reply_swap_receipt(msg),
+        _ => Err(ContractError::InvalidReplyId {}),
     }
 }
 
 pub fn receive_cw20(
     deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> StdResult<Response<TerraMsgWrapper>> {
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    assert_not_paused(deps.as_ref())?;
+
     let sender = deps.api.addr_validate(&cw20_msg.sender)?;
     match from_binary(&cw20_msg.msg)? {
-        Cw20HookMsg::ExecuteSwapOperations {
-            operations,
+        Cw20HookMsg::ExecuteSwapOperations(msg) => {
+            check_operations(deps.api, &msg.operations)?;
+
+            let offer_asset = Asset {
+                info: AssetInfo::Cw20(info.sender),
+                amount: cw20_msg.amount,
+            };
+            execute_swap_operations(deps, env, sender, offer_asset, msg, None)
+        }
+        Cw20HookMsg::ExecuteSplitSwapOperations(msg) => {
+            for route in &msg.routes {
+                check_operations(deps.api, &route.operations)?;
+            }
+
+            let offer_asset = Asset {
+                info: AssetInfo::Cw20(info.sender),
+                amount: cw20_msg.amount,
+            };
+            execute_split_swap_operations(deps, env, sender, offer_asset, msg)
+        }
+        Cw20HookMsg::ProvideLiquidityFromSingleAsset {
+            pair,
+            min_lp,
+            max_spread,
+            to,
+            staking_contract,
+        } => {
+            let offer_asset = Asset {
+                info: AssetInfo::Cw20(info.sender),
+                amount: cw20_msg.amount,
+            };
+            execute_provide_liquidity_from_single_asset(
+                deps,
+                env,
+                sender,
+                offer_asset,
+                pair,
+                min_lp,
+                max_spread,
+                to,
+                staking_contract,
+            )
+        }
+        Cw20HookMsg::WithdrawLiquidityToSingleAsset {
+            pair,
+            ask_asset_info,
             minimum_receive,
             to,
         } => {
-            for operation in &operations {
-                if let SwapOperation::PrismSwap {
-                    offer_asset_info,
-                    ask_asset_info,
-                } = &operation
-                {
-                    offer_asset_info.check(deps.api)?;
-                    ask_asset_info.check(deps.api)?;
-                };
+            ask_asset_info.check(deps.api)?;
+            execute_withdraw_liquidity_to_single_asset(
+                deps,
+                env,
+                info,
+                sender,
+                cw20_msg.amount,
+                pair,
+                ask_asset_info,
+                minimum_receive,
+                to,
+            )
+        }
+        Cw20HookMsg::UnstakeAndSwap {
+            staking_contract,
+            swap,
+        } => {
+            check_operations(deps.api, &swap.operations)?;
+            execute_unstake_and_swap(
+                deps,
+                env,
+                info,
+                sender,
+                cw20_msg.amount,
+                staking_contract,
+                swap,
+            )
+        }
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    guardian: Option<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    if let Some(guardian) = guardian {
+        deps.api.addr_validate(guardian.as_str())?;
+        GUARDIAN.save(deps.storage, &guardian)?;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner or guardian can execute it
+pub fn execute_pause(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let guardian: Option<Addr> = GUARDIAN.may_load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner && Some(&info.sender) != guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &true)?;
+
+    Ok(Response::new().add_attribute("action", "pause"))
+}
+
+// Only owner or guardian can execute it
+pub fn execute_unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let guardian: Option<Addr> = GUARDIAN.may_load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner && Some(&info.sender) != guardian.as_ref() {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    PAUSED.save(deps.storage, &false)?;
+
+    Ok(Response::new().add_attribute("action", "unpause"))
+}
+
+fn assert_not_paused(deps: Deps) -> Result<(), ContractError> {
+    if PAUSED.may_load(deps.storage)?.unwrap_or(false) {
+        return Err(ContractError::Paused {});
+    }
+
+    if let Some(param_registry) = PARAM_REGISTRY.may_load(deps.storage)? {
+        if let Some(paused) = query_param(&deps.querier, &param_registry, PAUSED_KEY)? {
+            if paused.as_bool()? {
+                return Err(ContractError::Paused {});
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// Only owner can execute it
+pub fn execute_sweep(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    assets: Vec<AssetInfo>,
+    to: Addr,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let to = deps.api.addr_validate(to.as_str())?;
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![];
+    for asset_info in assets {
+        let amount = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+        // the whole balance is swept, the tax being charged on top of it
+        messages.extend(
+            Asset {
+                info: asset_info,
+                amount,
+            }
+            .into_transfer_msg_with_tax(&deps.querier, &to)?,
+        );
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attributes(vec![("action", "sweep"), ("to", to.as_str())]))
+}
+
+// Only owner can execute it
+pub fn execute_update_fee_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_config: Option<FeeConfig>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match fee_config {
+        Some(fee_config) => {
+            assert_fee_config(deps.as_ref(), &fee_config)?;
+            FEE_CONFIG.save(deps.storage, &fee_config)?;
+        }
+        None => FEE_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_fee_config"))
+}
+
+// Only owner can execute it
+pub fn execute_update_fee_exemptions(
+    deps: DepsMut,
+    info: MessageInfo,
+    addresses: Vec<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in &addresses {
+        deps.api.addr_validate(address.as_str())?;
+    }
+    FEE_EXEMPTIONS.save(deps.storage, &addresses)?;
+
+    Ok(Response::new().add_attribute("action", "update_fee_exemptions"))
+}
+
+// Only owner can execute it
+pub fn execute_update_relayer_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    relayer_config: Option<RelayerConfig>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match relayer_config {
+        Some(relayer_config) => {
+            assert_relayer_config(deps.as_ref(), &relayer_config)?;
+            RELAYER_CONFIG.save(deps.storage, &relayer_config)?;
+        }
+        None => RELAYER_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_relayer_config"))
+}
+
+// Only owner can execute it
+pub fn execute_update_ask_asset_whitelist(
+    deps: DepsMut,
+    info: MessageInfo,
+    ask_asset_infos: Option<Vec<AssetInfo>>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match ask_asset_infos {
+        Some(ask_asset_infos) => ASK_ASSET_WHITELIST.save(deps.storage, &ask_asset_infos)?,
+        None => ASK_ASSET_WHITELIST.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_ask_asset_whitelist"))
+}
+
+// Only owner can execute it
+pub fn execute_invalidate_pair_cache(
+    deps: DepsMut,
+    info: MessageInfo,
+    asset_infos: Option<Vec<[AssetInfo; 2]>>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match asset_infos {
+        Some(asset_infos) => {
+            for asset_infos in asset_infos {
+                PAIR_CACHE.remove(deps.storage, &pair_key(&asset_infos));
             }
-            execute_swap_operations(deps, env, sender, operations, minimum_receive, to)
         }
+        None => clear_pair_cache(deps.storage),
     }
+
+    Ok(Response::new().add_attribute("action", "invalidate_pair_cache"))
+}
+
+// Only owner can execute it
+pub fn execute_update_slippage_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    slippage_config: Option<SlippageConfig>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match slippage_config {
+        Some(slippage_config) => {
+            if slippage_config.max_spread >= Decimal::one() {
+                return Err(ContractError::InvalidMaxSpread {});
+            }
+            if slippage_config.minimum_receive_tolerance >= Decimal::one() {
+                return Err(ContractError::InvalidMinimumReceiveTolerance {});
+            }
+            SLIPPAGE_CONFIG.save(deps.storage, &slippage_config)?;
+        }
+        None => SLIPPAGE_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_slippage_config"))
+}
+
+// Only owner can execute it
+pub fn execute_update_route_blacklist(
+    deps: DepsMut,
+    info: MessageInfo,
+    route_blacklist: Option<RouteBlacklist>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match route_blacklist {
+        Some(route_blacklist) => {
+            for pair in &route_blacklist.pairs {
+                deps.api.addr_validate(pair.as_str())?;
+            }
+            ROUTE_BLACKLIST.save(deps.storage, &route_blacklist)?;
+        }
+        None => ROUTE_BLACKLIST.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_route_blacklist"))
+}
+
+// Only owner can execute it
+pub fn execute_update_staking_contracts(
+    deps: DepsMut,
+    info: MessageInfo,
+    staking_contracts: Vec<StakingContract>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for staking_contract in &staking_contracts {
+        deps.api
+            .addr_validate(staking_contract.contract_addr.as_str())?;
+        if let Some(share_token) = &staking_contract.share_token {
+            deps.api.addr_validate(share_token.as_str())?;
+        }
+    }
+    STAKING_CONTRACTS.save(deps.storage, &staking_contracts)?;
+
+    Ok(Response::new().add_attribute("action", "update_staking_contracts"))
+}
+
+// Only owner can execute it
+pub fn execute_update_twap_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    twap_config: Option<TwapConfig>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match twap_config {
+        Some(twap_config) => {
+            assert_twap_config(&twap_config)?;
+            TWAP_CONFIG.save(deps.storage, &twap_config)?;
+        }
+        None => TWAP_CONFIG.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_twap_config"))
+}
+
+// Only owner can execute it
+pub fn execute_update_points_tracker(
+    deps: DepsMut,
+    info: MessageInfo,
+    points_tracker: Option<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match points_tracker {
+        Some(points_tracker) => {
+            let points_tracker = deps.api.addr_validate(points_tracker.as_str())?;
+            POINTS_TRACKER.save(deps.storage, &points_tracker)?;
+        }
+        None => POINTS_TRACKER.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_points_tracker"))
+}
+
+// Only owner can execute it
+pub fn execute_update_param_registry(
+    deps: DepsMut,
+    info: MessageInfo,
+    param_registry: Option<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match param_registry {
+        Some(param_registry) => {
+            let param_registry = deps.api.addr_validate(param_registry.as_str())?;
+            PARAM_REGISTRY.save(deps.storage, &param_registry)?;
+        }
+        None => PARAM_REGISTRY.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_param_registry"))
+}
+
+pub fn execute_submit_relay_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order: RelayOrder,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    assert_deadline(&env, order.deadline)?;
+
+    if let AssetInfo::Native(_) = &order.offer_asset.info {
+        return Err(ContractError::RelayedNativeOffer {});
+    }
+
+    if order.operations.is_empty() {
+        return Err(ContractError::NoOperations {});
+    }
+
+    if order.operations.len() > MAX_SWAP_OPERATIONS {
+        return Err(ContractError::TooManyOperations {});
+    }
+
+    assert_operations(&order.offer_asset.info, &order.operations)?;
+
+    RELAY_ORDERS.save(deps.storage, &info.sender, &order)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "submit_relay_order"),
+        ("user", info.sender.as_str()),
+    ]))
+}
+
+pub fn execute_cancel_relay_order(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if RELAY_ORDERS.may_load(deps.storage, &info.sender)?.is_none() {
+        return Err(ContractError::RelayOrderNotFound {});
+    }
+
+    RELAY_ORDERS.remove(deps.storage, &info.sender);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "cancel_relay_order"),
+        ("user", info.sender.as_str()),
+    ]))
+}
+
+// Only allowed relayers can execute it
+pub fn execute_relayed_swap_operations(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: Addr,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    // permission check
+    load_relayer_fee(deps.as_ref(), &info.sender)?;
+
+    // the relayer only executes the swap the user authorized, once
+    let user = deps.api.addr_validate(user.as_str())?;
+    let RelayOrder {
+        offer_asset,
+        operations,
+        minimum_receive,
+        deadline,
+    } = RELAY_ORDERS
+        .may_load(deps.storage, &user)?
+        .ok_or(ContractError::RelayOrderNotFound {})?;
+    assert_deadline(&env, deadline)?;
+    RELAY_ORDERS.remove(deps.storage, &user);
+
+    let token = match &offer_asset.info {
+        AssetInfo::Cw20(contract_addr) => contract_addr.to_string(),
+        AssetInfo::Native(_) => return Err(ContractError::RelayedNativeOffer {}),
+    };
+
+    Ok(Response::new()
+        .add_messages(vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: token,
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: user.to_string(),
+                    recipient: env.contract.address.to_string(),
+                    amount: offer_asset.amount,
+                })?,
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::RelaySwapOperations {
+                    user: user.clone(),
+                    offer_asset,
+                    operations,
+                    minimum_receive,
+                    relayer: info.sender.clone(),
+                })?,
+            }),
+        ])
+        .add_attributes(vec![
+            ("action", "relayed_swap"),
+            ("user", user.as_str()),
+            ("relayer", info.sender.as_str()),
+        ]))
 }
 
 pub fn execute_swap_operations(
     deps: DepsMut,
     env: Env,
     sender: Addr,
-    operations: Vec<SwapOperation>,
-    minimum_receive: Option<Uint128>,
-    to: Option<Addr>,
-) -> StdResult<Response<TerraMsgWrapper>> {
+    offer_asset: Asset,
+    msg: ExecuteSwapOperationsMsg,
+    relayer: Option<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let ExecuteSwapOperationsMsg {
+        operations,
+        minimum_receive,
+        max_spread,
+        to,
+        deadline,
+        referral,
+        swap_all,
+        twap_check,
+        twap_config,
+        staking_contract,
+    } = msg;
+
+    assert_deadline(&env, deadline)?;
+
     let operations_len = operations.len();
     if operations_len == 0 {
-        return Err(StdError::generic_err("must provide operations"));
+        return Err(ContractError::NoOperations {});
     }
 
     if operations_len > MAX_SWAP_OPERATIONS {
-        return Err(StdError::generic_err("exceeded swap operations limit"));
+        return Err(ContractError::TooManyOperations {});
     }
 
     // Assert the operations are properly set
-    assert_operations(&operations)?;
+    assert_operations(&offer_asset.info, &operations)?;
 
-    let to = to.unwrap_or(sender);
+    if let Some(referral) = &referral {
+        assert_referral(deps.as_ref(), referral)?;
+    }
+
+    let fee_exempt = is_fee_exempt(deps.storage, &sender)?;
+    let to = to.unwrap_or_else(|| sender.clone());
     let target_asset_info = operations.last().unwrap().get_target_asset_info();
+    assert_ask_asset_allowed(deps.storage, &target_asset_info)?;
+    if let Some(staking_contract) = &staking_contract {
+        assert_staking_contract(deps.storage, staking_contract, &target_asset_info)?;
+    }
+
+    // Route the output through the router when fees have to be taken from it or it is bonded
+    let fee_config: Option<FeeConfig> = if fee_exempt {
+        None
+    } else {
+        FEE_CONFIG.may_load(deps.storage)?
+    };
+    let distribute_output =
+        staking_contract.is_some() || has_swap_fees(&fee_config, &referral, &relayer);
+    let prev_target_balance = if distribute_output {
+        query_prev_balance(deps.as_ref(), &env, &offer_asset, &target_asset_info)?
+    } else {
+        Uint128::zero()
+    };
+
+    // Query the tax once for every hop sending native assets to a pair
+    let tax_info = query_route_tax_info(deps.as_ref(), &operations)?;
+
+    // Fall back to the configured slippage protection when the swap sets none
+    let slippage_config: Option<SlippageConfig> = SLIPPAGE_CONFIG.may_load(deps.storage)?;
+    let max_spread = max_spread.or_else(|| {
+        slippage_config
+            .as_ref()
+            .map(|slippage_config| slippage_config.max_spread)
+    });
+    let minimum_receive = match (minimum_receive, &slippage_config) {
+        (None, Some(slippage_config)) => {
+            let res = simulate_swap_operations(
+                deps.as_ref(),
+                offer_asset.amount,
+                operations.clone(),
+                referral.clone(),
+                relayer.is_some(),
+            )?;
+            Some(res.amount * (Decimal::one() - slippage_config.minimum_receive_tolerance))
+        }
+        _ => minimum_receive,
+    };
+
+    // Raise the minimum receive to the output at the pair TWAPs, less the allowed deviation
+    // A bound set by the swap itself does not depend on the router configuration
+    let twap_config: Option<TwapConfig> = match twap_config {
+        Some(twap_config) => {
+            assert_twap_config(&twap_config)?;
+            Some(twap_config)
+        }
+        None if twap_check.unwrap_or(false) => Some(
+            TWAP_CONFIG
+                .may_load(deps.storage)?
+                .ok_or(ContractError::TwapCheckNotConfigured {})?,
+        ),
+        None => None,
+    };
+    let minimum_receive = if let Some(twap_config) = twap_config {
+        let config: Config = CONFIG.load(deps.storage)?;
+        let twap_amount = query_twap_return_amount(
+            deps.as_ref(),
+            &config.factory,
+            &twap_config,
+            &tax_info,
+            offer_asset.amount,
+            &operations,
+        )?;
+
+        let relayer_fee = match &relayer {
+            Some(relayer) => Some(load_relayer_fee(deps.as_ref(), relayer)?),
+            None => None,
+        };
+        let fees = compute_swap_fees(&fee_config, &referral, relayer_fee, twap_amount);
+        let twap_minimum =
+            twap_amount.checked_sub(fees.total())? * (Decimal::one() - twap_config.max_deviation);
+
+        Some(std::cmp::max(
+            minimum_receive.unwrap_or_default(),
+            twap_minimum,
+        ))
+    } else {
+        minimum_receive
+    };
+
+    // Every operation records its realized amounts for the receipt
+    SWAP_RECEIPT.save(deps.storage, &vec![])?;
+    if POINTS_TRACKER.may_load(deps.storage)?.is_some() {
+        SWAP_TRADER.save(deps.storage, &sender)?;
+    }
+
+    let refund_msg = if swap_all.unwrap_or(false) {
+        None
+    } else {
+        refund_dust_msg(
+            deps.as_ref(),
+            &env,
+            &offer_asset,
+            &operations,
+            &target_asset_info,
+            &tax_info,
+            sender,
+        )?
+    };
+
+    let mut messages = swap_operation_msgs(
+        deps.as_ref(),
+        &env,
+        &offer_asset,
+        offer_asset.amount,
+        operations,
+        if distribute_output {
+            None
+        } else {
+            Some(to.clone())
+        },
+        swap_all.unwrap_or(false),
+        &tax_info,
+        max_spread,
+    )?;
+
+    // The receipt data is forwarded as the data of the swap on reply
+    messages.push(SubMsg::reply_on_success(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::EmitSwapReceipt {})?,
+        }),
+        SWAP_RECEIPT_REPLY_ID,
+    ));
+
+    if distribute_output {
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::DistributeSwapOutput {
+                asset_info: target_asset_info.clone(),
+                prev_balance: prev_target_balance,
+                receiver: to.clone(),
+                referral,
+                relayer,
+                fee_exempt,
+                staking_contract: staking_contract.clone(),
+                minimum_receive: if staking_contract.is_some() {
+                    minimum_receive
+                } else {
+                    None
+                },
+            })?,
+        })));
+    }
+
+    // Execute minimum amount assertion, bonded outputs being checked when distributed
+    if let (Some(minimum_receive), None) = (minimum_receive, &staking_contract) {
+        let receiver_balance = target_asset_info.query_pool(&deps.querier, &to)?;
+
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: target_asset_info,
+                prev_balance: receiver_balance,
+                minimum_receive,
+                receiver: to,
+            })?,
+        })))
+    }
+
+    messages.extend(refund_msg);
+
+    Ok(Response::new().add_submessages(messages))
+}
+
+pub fn execute_split_swap_operations(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer_asset: Asset,
+    msg: ExecuteSplitSwapOperationsMsg,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let ExecuteSplitSwapOperationsMsg {
+        routes,
+        minimum_receive,
+        max_spread,
+        to,
+        deadline,
+        referral,
+    } = msg;
+
+    assert_deadline(&env, deadline)?;
+
+    if routes.is_empty() {
+        return Err(ContractError::NoRoutes {});
+    }
+
+    let operations_len: usize = routes.iter().map(|route| route.operations.len()).sum();
+    if operations_len > MAX_SWAP_OPERATIONS {
+        return Err(ContractError::TooManyOperations {});
+    }
+
+    // Assert every route offers its part of the sent asset and ends in the same asset
+    let mut offer_amount = Uint128::zero();
+    for route in &routes {
+        assert_operations(&offer_asset.info, &route.operations)?;
+        offer_amount = offer_amount.checked_add(route.offer_amount)?;
+    }
+    if offer_amount != offer_asset.amount {
+        return Err(ContractError::RouteAmountMismatch {});
+    }
+
+    let target_asset_info = routes[0].operations.last().unwrap().get_target_asset_info();
+    if routes
+        .iter()
+        .any(|route| route.operations.last().unwrap().get_target_asset_info() != target_asset_info)
+    {
+        return Err(ContractError::RouteTargetMismatch {});
+    }
+
+    if let Some(referral) = &referral {
+        assert_referral(deps.as_ref(), referral)?;
+    }
+
+    let fee_exempt = is_fee_exempt(deps.storage, &sender)?;
+    let to = to.unwrap_or_else(|| sender.clone());
+    assert_ask_asset_allowed(deps.storage, &target_asset_info)?;
+
+    // Route the output through the router when fees have to be taken from it
+    let fee_config: Option<FeeConfig> = if fee_exempt {
+        None
+    } else {
+        FEE_CONFIG.may_load(deps.storage)?
+    };
+    let distribute_output = has_swap_fees(&fee_config, &referral, &None);
+    let prev_target_balance = if distribute_output {
+        query_prev_balance(deps.as_ref(), &env, &offer_asset, &target_asset_info)?
+    } else {
+        Uint128::zero()
+    };
+
+    // Query the tax once for every hop of every route
+    let operations: Vec<SwapOperation> = routes
+        .iter()
+        .flat_map(|route| route.operations.clone())
+        .collect();
+    let tax_info = query_route_tax_info(deps.as_ref(), &operations)?;
+
+    // Fall back to the configured slippage protection when the swap sets none
+    let slippage_config: Option<SlippageConfig> = SLIPPAGE_CONFIG.may_load(deps.storage)?;
+    let max_spread = max_spread.or_else(|| {
+        slippage_config
+            .as_ref()
+            .map(|slippage_config| slippage_config.max_spread)
+    });
+    let minimum_receive = match (minimum_receive, &slippage_config) {
+        (None, Some(slippage_config)) => {
+            let mut amount = Uint128::zero();
+            for route in &routes {
+                let res = simulate_swap_operations(
+                    deps.as_ref(),
+                    route.offer_amount,
+                    route.operations.clone(),
+                    referral.clone(),
+                    false,
+                )?;
+                amount = amount.checked_add(res.amount)?;
+            }
+            Some(amount * (Decimal::one() - slippage_config.minimum_receive_tolerance))
+        }
+        _ => minimum_receive,
+    };
+
+    // Every operation records its realized amounts for the receipt of all the routes
+    SWAP_RECEIPT.save(deps.storage, &vec![])?;
+    SWAP_ROUTES.save(
+        deps.storage,
+        &routes
+            .iter()
+            .map(|route| route.operations.len() as u64)
+            .collect(),
+    )?;
+    if POINTS_TRACKER.may_load(deps.storage)?.is_some() {
+        SWAP_TRADER.save(deps.storage, &sender)?;
+    }
+
+    let refund_msg = refund_dust_msg(
+        deps.as_ref(),
+        &env,
+        &offer_asset,
+        &operations,
+        &target_asset_info,
+        &tax_info,
+        sender,
+    )?;
+
+    // The routes run one after the other, each offering only what the previous one left
+    let mut messages: Vec<SubMsg<TerraMsgWrapper>> = vec![];
+    for route in routes {
+        messages.extend(swap_operation_msgs(
+            deps.as_ref(),
+            &env,
+            &offer_asset,
+            route.offer_amount,
+            route.operations,
+            if distribute_output {
+                None
+            } else {
+                Some(to.clone())
+            },
+            false,
+            &tax_info,
+            max_spread,
+        )?);
+    }
 
+    // The receipt data is forwarded as the data of the swap on reply
+    messages.push(SubMsg::reply_on_success(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::EmitSwapReceipt {})?,
+        }),
+        SWAP_RECEIPT_REPLY_ID,
+    ));
+
+    if distribute_output {
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::DistributeSwapOutput {
+                asset_info: target_asset_info.clone(),
+                prev_balance: prev_target_balance,
+                receiver: to.clone(),
+                referral,
+                relayer: None,
+                fee_exempt,
+                staking_contract: None,
+                minimum_receive: None,
+            })?,
+        })));
+    }
+
+    // Execute minimum amount assertion on the output of all the routes
+    if let Some(minimum_receive) = minimum_receive {
+        let receiver_balance = target_asset_info.query_pool(&deps.querier, &to)?;
+
+        messages.push(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: target_asset_info,
+                prev_balance: receiver_balance,
+                minimum_receive,
+                receiver: to,
+            })?,
+        })))
+    }
+
+    messages.extend(refund_msg);
+
+    Ok(Response::new().add_submessages(messages))
+}
+
+fn check_operations(api: &dyn Api, operations: &[SwapOperation]) -> Result<(), ContractError> {
+    for operation in operations {
+        if let SwapOperation::PrismSwap {
+            offer_asset_info,
+            ask_asset_info,
+            pair,
+            max_spread,
+        } = operation
+        {
+            offer_asset_info.check(api)?;
+            ask_asset_info.check(api)?;
+            if let Some(pair) = pair {
+                api.addr_validate(pair.as_str())?;
+            }
+            if max_spread.is_some_and(|max_spread| max_spread >= Decimal::one()) {
+                return Err(ContractError::InvalidMaxSpread {});
+            }
+        };
+    }
+
+    Ok(())
+}
+
+fn assert_deadline(env: &Env, deadline: Option<u64>) -> Result<(), ContractError> {
+    if let Some(deadline) = deadline {
+        if env.block.time.seconds() > deadline {
+            return Err(ContractError::DeadlineExpired {});
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the messages executing the operations of a route offering `offer_amount` of the
+/// swap offer asset, sending the output of the last one to the receiver or keeping it in the
+/// router when `None`
+#[allow(clippy::too_many_arguments)]
+fn swap_operation_msgs(
+    deps: Deps,
+    env: &Env,
+    offer_asset: &Asset,
+    offer_amount: Uint128,
+    operations: Vec<SwapOperation>,
+    receiver: Option<Addr>,
+    swap_all: bool,
+    tax_info: &Option<TaxInfo>,
+    max_spread: Option<Decimal>,
+) -> Result<Vec<SubMsg<TerraMsgWrapper>>, ContractError> {
+    let operations_len = operations.len();
     let mut operation_index = 0;
-    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = operations
+    operations
         .into_iter()
         .map(|op| {
             operation_index += 1;
-            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+
+            // Offer only what this swap brought to the router, unless swapping everything
+            let amount = if swap_all {
+                Some(SwapAmount::All)
+            } else if operation_index == 1 {
+                Some(SwapAmount::Exact(offer_amount))
+            } else {
+                let prev_balance =
+                    query_prev_balance(deps, env, offer_asset, &op.get_offer_asset_info())?;
+                Some(SwapAmount::ReceivedSince(prev_balance))
+            };
+
+            Ok(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: env.contract.address.to_string(),
                 funds: vec![],
                 msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
                     operation: op,
                     to: if operation_index == operations_len {
-                        Some(to.clone())
+                        receiver.clone()
                     } else {
                         None
                     },
+                    amount,
+                    tax_info: tax_info.clone(),
+                    max_spread,
                 })?,
-            }))
+            })))
         })
-        .collect::<StdResult<Vec<CosmosMsg<TerraMsgWrapper>>>>()?;
+        .collect()
+}
 
-    // Execute minimum amount assertion
-    if let Some(minimum_receive) = minimum_receive {
-        let receiver_balance = target_asset_info.query_pool(&deps.querier, &to)?;
+fn query_route_tax_info(deps: Deps, operations: &[SwapOperation]) -> StdResult<Option<TaxInfo>> {
+    let denoms = taxed_denoms(operations);
+    if denoms.is_empty() {
+        return Ok(None);
+    }
 
-        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: env.contract.address.to_string(),
-            funds: vec![],
-            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
-                asset_info: target_asset_info,
-                prev_balance: receiver_balance,
-                minimum_receive,
-                receiver: to,
-            })?,
-        }))
+    Ok(Some(TaxInfo::query(&deps.querier, &denoms)?))
+}
+
+/// Returns the message refunding the sender the dust left by the hops deducting a non-zero tax
+/// from their native offer, the target asset being left to the output distribution
+fn refund_dust_msg(
+    deps: Deps,
+    env: &Env,
+    offer_asset: &Asset,
+    operations: &[SwapOperation],
+    target_asset_info: &AssetInfo,
+    tax_info: &Option<TaxInfo>,
+    sender: Addr,
+) -> Result<Option<SubMsg<TerraMsgWrapper>>, ContractError> {
+    let tax_info = match tax_info {
+        Some(tax_info) if !tax_info.rate.is_zero() => tax_info,
+        _ => return Ok(None),
+    };
+
+    let mut prev_balances: Vec<Asset> = vec![];
+    for denom in taxed_denoms(operations) {
+        let asset_info = AssetInfo::Native(denom.to_string());
+        let has_tax = tax_info
+            .caps
+            .iter()
+            .any(|cap| cap.denom == denom && !cap.amount.is_zero());
+        if !has_tax || asset_info == *target_asset_info {
+            continue;
+        }
+
+        prev_balances.push(Asset {
+            amount: query_prev_balance(deps, env, offer_asset, &asset_info)?,
+            info: asset_info,
+        });
+    }
+
+    if prev_balances.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::RefundDust {
+            prev_balances,
+            receiver: sender,
+        })?,
+    }))))
+}
+
+/// Returns the router balance of the asset before this swap, leaving out the offered amount
+/// which is already held by the router but spent by the first operation
+fn query_prev_balance(
+    deps: Deps,
+    env: &Env,
+    offer_asset: &Asset,
+    asset_info: &AssetInfo,
+) -> Result<Uint128, ContractError> {
+    let balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    if *asset_info == offer_asset.info {
+        return Ok(balance.checked_sub(offer_asset.amount)?);
     }
 
-    Ok(Response::new().add_messages(messages))
+    Ok(balance)
 }
 
 fn assert_minimum_receive(
@@ -181,15 +1466,15 @@ fn assert_minimum_receive(
     prev_balance: Uint128,
     minium_receive: Uint128,
     receiver: Addr,
-) -> StdResult<Response<TerraMsgWrapper>> {
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
     let receiver_balance = asset_info.query_pool(&deps.querier, &receiver)?;
     let swap_amount = receiver_balance.checked_sub(prev_balance)?;
 
     if swap_amount < minium_receive {
-        return Err(StdError::generic_err(format!(
-            "assertion failed; minimum receive amount: {}, swap amount: {}",
-            minium_receive, swap_amount
-        )));
+        return Err(ContractError::MinimumReceiveAssertion {
+            minimum_receive: minium_receive,
+            swap_amount,
+        });
     }
 
     Ok(Response::default())
@@ -199,10 +1484,39 @@ fn assert_minimum_receive(
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::CachedPair { asset_infos } => {
+            to_binary(&PAIR_CACHE.may_load(deps.storage, &pair_key(&asset_infos))?)
+        }
+        QueryMsg::RelayOrder { user } => to_binary(&RELAY_ORDERS.may_load(deps.storage, &user)?),
         QueryMsg::SimulateSwapOperations {
             offer_amount,
             operations,
-        } => to_binary(&simulate_swap_operations(deps, offer_amount, operations)?),
+            referral,
+            relayed,
+        } => to_binary(&simulate_swap_operations(
+            deps,
+            offer_amount,
+            operations,
+            referral,
+            relayed.unwrap_or(false),
+        )?),
+        QueryMsg::EstimateRequiredOffer {
+            offer_asset_info,
+            ask_asset,
+            intermediate_assets,
+            referral,
+            relayed,
+        } => to_binary(&query_estimate_required_offer(
+            deps,
+            offer_asset_info,
+            ask_asset,
+            intermediate_assets.unwrap_or_default(),
+            referral,
+            relayed.unwrap_or(false),
+        )?),
+        QueryMsg::AssetVolumes { start_after, limit } => to_binary(&AssetVolumesResponse {
+            volumes: read_asset_volumes(deps.storage, start_after, limit)?,
+        }),
     }
 }
 
@@ -210,36 +1524,80 @@ pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let state = CONFIG.load(deps.storage)?;
     let resp = ConfigResponse {
         factory: state.factory,
+        owner: state.owner,
+        guardian: GUARDIAN.may_load(deps.storage)?,
+        paused: PAUSED.may_load(deps.storage)?.unwrap_or(false),
+        fee_config: FEE_CONFIG.may_load(deps.storage)?,
+        fee_exemptions: FEE_EXEMPTIONS.may_load(deps.storage)?.unwrap_or_default(),
+        ask_asset_whitelist: ASK_ASSET_WHITELIST.may_load(deps.storage)?,
+        relayer_config: RELAYER_CONFIG.may_load(deps.storage)?,
+        twap_config: TWAP_CONFIG.may_load(deps.storage)?,
+        slippage_config: SLIPPAGE_CONFIG.may_load(deps.storage)?,
+        route_blacklist: ROUTE_BLACKLIST.may_load(deps.storage)?,
+        staking_contracts: STAKING_CONTRACTS
+            .may_load(deps.storage)?
+            .unwrap_or_default(),
+        points_tracker: POINTS_TRACKER.may_load(deps.storage)?,
+        param_registry: PARAM_REGISTRY.may_load(deps.storage)?,
     };
 
     Ok(resp)
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    match msg {
+        MigrateMsg::V1_0_0 { owner } => {
+            let owner: Addr = deps.api.addr_validate(owner.as_str())?;
+            migrate_config(deps.storage, owner)?;
+        }
+    }
+
+    Ok(Response::default())
+}
+
 fn simulate_swap_operations(
     deps: Deps,
     offer_amount: Uint128,
     operations: Vec<SwapOperation>,
-) -> StdResult<SimulateSwapOperationsResponse> {
+    referral: Option<Referral>,
+    relayed: bool,
+) -> Result<SimulateSwapOperationsResponse, ContractError> {
     let config: Config = CONFIG.load(deps.storage)?;
     let prismswap_factory = config.factory;
     let terra_querier = TerraQuerier::new(&deps.querier);
 
     let operations_len = operations.len();
     if operations_len == 0 {
-        return Err(StdError::generic_err("must provide operations"));
+        return Err(ContractError::NoOperations {});
     }
 
     if operations_len > MAX_SWAP_OPERATIONS {
-        return Err(StdError::generic_err("exceeded swap operations limit"));
+        return Err(ContractError::TooManyOperations {});
+    }
+
+    if let Some(referral) = &referral {
+        assert_referral(deps, referral)?;
     }
 
+    let tax_info = query_route_tax_info(deps, &operations)?;
+
     let mut offer_amount = offer_amount;
+    let mut breakdowns: Vec<SwapBreakdown> = vec![];
     for operation in operations.into_iter() {
         match operation {
             SwapOperation::NativeSwap {
                 offer_denom,
                 ask_denom,
             } => {
+                assert_hop_allowed(
+                    deps.storage,
+                    &[
+                        AssetInfo::Native(offer_denom.to_string()),
+                        AssetInfo::Native(ask_denom.to_string()),
+                    ],
+                    None,
+                )?;
                 let res: SwapResponse = terra_querier.query_swap(
                     Coin {
                         denom: offer_denom,
@@ -249,17 +1607,40 @@ fn simulate_swap_operations(
                 )?;
 
                 offer_amount = res.receive.amount;
+                breakdowns.push(SwapBreakdown {
+                    return_amount: offer_amount,
+                    ..SwapBreakdown::default()
+                });
             }
             SwapOperation::PrismSwap {
                 offer_asset_info,
                 ask_asset_info,
+                pair,
+                ..
             } => {
-                let pair_info: PairInfo = query_pair_info(
+                let pair_info: PairInfo = read_pair_info(
+                    deps.storage,
                     &deps.querier,
                     &prismswap_factory,
                     &[offer_asset_info.clone(), ask_asset_info.clone()],
+                    pair.as_ref(),
+                )?;
+                assert_hop_allowed(
+                    deps.storage,
+                    &[offer_asset_info.clone(), ask_asset_info.clone()],
+                    Some(&pair_info.contract_addr),
                 )?;
 
+                let mut tax = Uint128::zero();
+                if let (AssetInfo::Native(denom), Some(tax_info)) = (&offer_asset_info, &tax_info) {
+                    let taxed_amount = tax_info.deduct_tax(&Coin {
+                        denom: denom.to_string(),
+                        amount: offer_amount,
+                    })?;
+                    tax = offer_amount.checked_sub(taxed_amount)?;
+                    offer_amount = taxed_amount;
+                }
+
                 let res: SimulationResponse =
                     deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
                         contract_addr: pair_info.contract_addr.to_string(),
@@ -272,40 +1653,40 @@ fn simulate_swap_operations(
                     }))?;
 
                 offer_amount = res.return_amount;
+                breakdowns.push(SwapBreakdown {
+                    tax,
+                    ..res.breakdown
+                });
             }
         }
     }
 
+    let fee_config: Option<FeeConfig> = FEE_CONFIG.may_load(deps.storage)?;
+    let relayer_fee = load_simulated_relayer_fee(deps, relayed)?;
+    let fees = compute_swap_fees(&fee_config, &referral, relayer_fee, offer_amount);
+
     Ok(SimulateSwapOperationsResponse {
-        amount: offer_amount,
+        amount: offer_amount.checked_sub(fees.total())?,
+        fee_amount: fees.fee_amount,
+        referral_amount: fees.referral_amount,
+        relayer_amount: fees.relayer_amount,
+        breakdowns,
     })
 }
 
-fn assert_operations(operations: &[SwapOperation]) -> StdResult<()> {
-    let mut ask_asset_map: HashMap<String, bool> = HashMap::new();
-    for operation in operations.iter() {
-        let (offer_asset, ask_asset) = match operation {
-            SwapOperation::NativeSwap {
-                offer_denom,
-                ask_denom,
-            } => (
-                AssetInfo::Native(offer_denom.to_string()),
-                AssetInfo::Native(ask_denom.to_string()),
-            ),
-            SwapOperation::PrismSwap {
-                offer_asset_info,
-                ask_asset_info,
-            } => (offer_asset_info.clone(), ask_asset_info.clone()),
-        };
-
-        ask_asset_map.remove(&offer_asset.to_string());
-        ask_asset_map.insert(ask_asset.to_string(), true);
+fn assert_operations(
+    offer_asset_info: &AssetInfo,
+    operations: &[SwapOperation],
+) -> Result<(), ContractError> {
+    let first_operation = operations.first().ok_or(ContractError::NoOperations {})?;
+    if first_operation.get_offer_asset_info() != *offer_asset_info {
+        return Err(ContractError::InvalidFirstOperation {});
     }
 
-    if ask_asset_map.keys().len() != 1 {
-        return Err(StdError::generic_err(
-            "invalid operations; multiple output token",
-        ));
+    for window in operations.windows(2) {
+        if window[0].get_target_asset_info() != window[1].get_offer_asset_info() {
+            return Err(ContractError::BrokenOperationChain {});
+        }
     }
 
     Ok(())
@@ -313,65 +1694,124 @@ fn assert_operations(operations: &[SwapOperation]) -> StdResult<()> {
 
 #[test]
 fn test_invalid_operations() {
+    let uusd = AssetInfo::Native("uusd".to_string());
+
     // empty error
-    assert!(assert_operations(&[]).is_err());
+    assert!(assert_operations(&uusd, &[]).is_err());
 
     // uluna output
-    assert!(assert_operations(&vec![
-        SwapOperation::NativeSwap {
-            offer_denom: "uusd".to_string(),
-            ask_denom: "uluna".to_string(),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Native("ukrw".to_string()),
-            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-            ask_asset_info: AssetInfo::Native("uluna".to_string()),
-        }
-    ])
+    assert!(assert_operations(
+        &uusd,
+        &[
+            SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                pair: None,
+                max_spread: None,
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                pair: None,
+                max_spread: None,
+            }
+        ]
+    )
     .is_ok());
 
     // asset0002 output
-    assert!(assert_operations(&vec![
-        SwapOperation::NativeSwap {
-            offer_denom: "uusd".to_string(),
-            ask_denom: "uluna".to_string(),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Native("ukrw".to_string()),
-            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-            ask_asset_info: AssetInfo::Native("uluna".to_string()),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Native("uluna".to_string()),
-            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
-        },
-    ])
+    assert!(assert_operations(
+        &uusd,
+        &[
+            SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                pair: None,
+                max_spread: None,
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                pair: None,
+                max_spread: None,
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("uluna".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                pair: None,
+                max_spread: None,
+            },
+        ]
+    )
     .is_ok());
 
+    // first operation does not offer the sent asset
+    assert_eq!(
+        assert_operations(
+            &AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            &[SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            }]
+        ),
+        Err(ContractError::InvalidFirstOperation {})
+    );
+
+    // broken chain
+    assert_eq!(
+        assert_operations(
+            &uusd,
+            &[
+                SwapOperation::NativeSwap {
+                    offer_denom: "uusd".to_string(),
+                    ask_denom: "uluna".to_string(),
+                },
+                SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                    pair: None,
+                    max_spread: None,
+                },
+            ]
+        ),
+        Err(ContractError::BrokenOperationChain {})
+    );
+
     // multiple output token types error
-    assert!(assert_operations(&vec![
-        SwapOperation::NativeSwap {
-            offer_denom: "uusd".to_string(),
-            ask_denom: "ukrw".to_string(),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Native("ukrw".to_string()),
-            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-            ask_asset_info: AssetInfo::Native("uaud".to_string()),
-        },
-        SwapOperation::PrismSwap {
-            offer_asset_info: AssetInfo::Native("uluna".to_string()),
-            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
-        },
-    ])
+    assert!(assert_operations(
+        &uusd,
+        &[
+            SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                pair: None,
+                max_spread: None,
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                ask_asset_info: AssetInfo::Native("uaud".to_string()),
+                pair: None,
+                max_spread: None,
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("uluna".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                pair: None,
+                max_spread: None,
+            },
+        ]
+    )
     .is_err());
 }
@@ -0,0 +1,153 @@
+use cosmwasm_std::{Addr, Decimal, OverflowError, StdError, Uint128};
+use prismswap::asset::AssetInfo;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    OverflowError(#[from] OverflowError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("router is paused")]
+    Paused {},
+
+    #[error("must send exactly one native asset")]
+    InvalidNativeFunds {},
+
+    #[error("invalid reply id")]
+    InvalidReplyId {},
+
+    #[error("{0}")]
+    SubMsgFailure(String),
+
+    #[error("max spread must be lower than 1")]
+    InvalidMaxSpread {},
+
+    #[error("minimum receive tolerance must be lower than 1")]
+    InvalidMinimumReceiveTolerance {},
+
+    #[error("twap window must be greater than zero")]
+    InvalidTwapWindow {},
+
+    #[error("twap max deviation must be lower than 1")]
+    InvalidTwapMaxDeviation {},
+
+    #[error("twap check is not configured")]
+    TwapCheckNotConfigured {},
+
+    #[error("router fee and max referral fee cannot exceed {max_percent}%")]
+    FeeTooHigh { max_percent: u64 },
+
+    #[error("relayer fee cannot exceed {max_percent}%")]
+    RelayerFeeTooHigh { max_percent: u64 },
+
+    #[error("referral fee cannot exceed {max_referral_fee}")]
+    ReferralFeeTooHigh { max_referral_fee: Decimal },
+
+    #[error("relayed swaps are disabled")]
+    RelayingDisabled {},
+
+    #[error("relayed swaps can only offer cw20 tokens")]
+    RelayedNativeOffer {},
+
+    #[error("no relay order")]
+    RelayOrderNotFound {},
+
+    #[error("must provide operations")]
+    NoOperations {},
+
+    #[error("exceeded swap operations limit")]
+    TooManyOperations {},
+
+    #[error("invalid operations; first operation must offer the sent asset")]
+    InvalidFirstOperation {},
+
+    #[error("invalid operations; each operation must offer the previous ask asset")]
+    BrokenOperationChain {},
+
+    #[error("must provide routes")]
+    NoRoutes {},
+
+    #[error("route offer amounts must add up to the offer amount")]
+    RouteAmountMismatch {},
+
+    #[error("all routes must end in the same asset")]
+    RouteTargetMismatch {},
+
+    #[error("no route found")]
+    NoRouteFound {},
+
+    #[error("fees take the whole swap output")]
+    FeesExceedOutput {},
+
+    #[error("swap deadline expired")]
+    DeadlineExpired {},
+
+    #[error(
+        "assertion failed; minimum receive amount: {minimum_receive}, swap amount: {swap_amount}"
+    )]
+    MinimumReceiveAssertion {
+        minimum_receive: Uint128,
+        swap_amount: Uint128,
+    },
+
+    #[error("no swap operation executed")]
+    NoSwapExecuted {},
+
+    #[error("native swaps cannot be reverse simulated")]
+    NativeReverseSimulation {},
+
+    #[error("ask asset {asset_info} is not whitelisted")]
+    AskAssetNotWhitelisted { asset_info: AssetInfo },
+
+    #[error("hop {offer_asset_info} -> {ask_asset_info} is blacklisted")]
+    HopBlacklisted {
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+
+    #[error("pair {pair} is blacklisted")]
+    PairBlacklisted { pair: Addr },
+
+    #[error("pair {pair} is not registered in the factory")]
+    PairNotRegistered { pair: Addr },
+
+    #[error("staking contract {contract_addr} is not registered")]
+    StakingContractNotRegistered { contract_addr: Addr },
+
+    #[error("staking contract {contract_addr} does not bond {asset_info}")]
+    StakingAssetMismatch {
+        contract_addr: Addr,
+        asset_info: AssetInfo,
+    },
+
+    #[error("staking contract {contract_addr} does not issue {token}")]
+    StakingShareMismatch { contract_addr: Addr, token: Addr },
+
+    #[error("offer amount must be greater than zero")]
+    InvalidZeroAmount {},
+
+    #[error("offer asset does not belong to the pair")]
+    OfferAssetMismatch {},
+
+    #[error("ask asset does not belong to the pair")]
+    AskAssetMismatch {},
+
+    #[error("cannot provide to an empty pool")]
+    EmptyPool {},
+}
+
+/// Lets the queries sharing the swap checks return their failures as standard errors
+impl From<ContractError> for StdError {
+    fn from(err: ContractError) -> Self {
+        match err {
+            ContractError::Std(err) => err,
+            err => StdError::generic_err(err.to_string()),
+        }
+    }
+}
@@ -0,0 +1,214 @@
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, Uint128};
+
+use crate::error::ContractError;
+use crate::stake::into_bond_msg;
+use crate::state::{FEE_CONFIG, RELAYER_CONFIG};
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::router::{FeeConfig, Referral, RelayerConfig};
+use terra_cosmwasm::TerraMsgWrapper;
+
+/// the router fee and the referral fee can never take more than this share of the output
+const MAX_TOTAL_FEE_PERCENT: u64 = 10;
+
+pub fn assert_fee_config(deps: Deps, fee_config: &FeeConfig) -> Result<(), ContractError> {
+    deps.api.addr_validate(fee_config.fee_collector.as_str())?;
+
+    if fee_config.fee_rate + fee_config.max_referral_fee > Decimal::percent(MAX_TOTAL_FEE_PERCENT) {
+        return Err(ContractError::FeeTooHigh {
+            max_percent: MAX_TOTAL_FEE_PERCENT,
+        });
+    }
+
+    Ok(())
+}
+
+pub fn assert_relayer_config(
+    deps: Deps,
+    relayer_config: &RelayerConfig,
+) -> Result<(), ContractError> {
+    for relayer in &relayer_config.relayers {
+        deps.api.addr_validate(relayer.as_str())?;
+    }
+
+    if relayer_config.fee_rate > Decimal::percent(MAX_TOTAL_FEE_PERCENT) {
+        return Err(ContractError::RelayerFeeTooHigh {
+            max_percent: MAX_TOTAL_FEE_PERCENT,
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the relayer fee rate, failing if the address is not an allowed relayer
+pub fn load_relayer_fee(deps: Deps, relayer: &Addr) -> Result<Decimal, ContractError> {
+    match RELAYER_CONFIG.may_load(deps.storage)? {
+        Some(relayer_config) if relayer_config.relayers.contains(relayer) => {
+            Ok(relayer_config.fee_rate)
+        }
+        _ => Err(ContractError::Unauthorized {}),
+    }
+}
+
+/// Returns the relayer fee rate simulations of relayed swaps take, failing if relaying is disabled
+pub fn load_simulated_relayer_fee(
+    deps: Deps,
+    relayed: bool,
+) -> Result<Option<Decimal>, ContractError> {
+    if !relayed {
+        return Ok(None);
+    }
+
+    let relayer_config = RELAYER_CONFIG
+        .may_load(deps.storage)?
+        .ok_or(ContractError::RelayingDisabled {})?;
+
+    Ok(Some(relayer_config.fee_rate))
+}
+
+pub fn assert_referral(deps: Deps, referral: &Referral) -> Result<(), ContractError> {
+    deps.api.addr_validate(referral.address.as_str())?;
+
+    let max_referral_fee = FEE_CONFIG
+        .may_load(deps.storage)?
+        .map(|fee_config| fee_config.max_referral_fee)
+        .unwrap_or_else(Decimal::zero);
+    if referral.fee > max_referral_fee {
+        return Err(ContractError::ReferralFeeTooHigh { max_referral_fee });
+    }
+
+    Ok(())
+}
+
+/// Returns whether the swap output has to go through the router to pay fees
+pub fn has_swap_fees(
+    fee_config: &Option<FeeConfig>,
+    referral: &Option<Referral>,
+    relayer: &Option<Addr>,
+) -> bool {
+    fee_config
+        .as_ref()
+        .is_some_and(|fee_config| !fee_config.fee_rate.is_zero())
+        || referral
+            .as_ref()
+            .is_some_and(|referral| !referral.fee.is_zero())
+        || relayer.is_some()
+}
+
+/// The fees taken from a swap output
+pub struct SwapFees {
+    pub fee_amount: Uint128,
+    pub referral_amount: Uint128,
+    pub relayer_amount: Uint128,
+}
+
+impl SwapFees {
+    pub fn total(&self) -> Uint128 {
+        self.fee_amount + self.referral_amount + self.relayer_amount
+    }
+}
+
+/// Returns the router, referral and relayer fees taken from the swap output
+pub fn compute_swap_fees(
+    fee_config: &Option<FeeConfig>,
+    referral: &Option<Referral>,
+    relayer_fee: Option<Decimal>,
+    amount: Uint128,
+) -> SwapFees {
+    SwapFees {
+        fee_amount: fee_config
+            .as_ref()
+            .map_or_else(Uint128::zero, |fee_config| amount * fee_config.fee_rate),
+        referral_amount: referral
+            .as_ref()
+            .map_or_else(Uint128::zero, |referral| amount * referral.fee),
+        relayer_amount: relayer_fee.map_or_else(Uint128::zero, |relayer_fee| amount * relayer_fee),
+    }
+}
+
+/// Take the fees from the output received since `prev_balance` and send the rest to the receiver,
+/// or bond it on behalf of the receiver when a staking contract is given
+#[allow(clippy::too_many_arguments)]
+pub fn execute_distribute_swap_output(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    receiver: Addr,
+    referral: Option<Referral>,
+    relayer: Option<Addr>,
+    fee_exempt: bool,
+    staking_contract: Option<Addr>,
+    minimum_receive: Option<Uint128>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let fee_config: Option<FeeConfig> = if fee_exempt {
+        None
+    } else {
+        FEE_CONFIG.may_load(deps.storage)?
+    };
+    let relayer_fee = match &relayer {
+        Some(relayer) => Some(load_relayer_fee(deps.as_ref(), relayer)?),
+        None => None,
+    };
+    let balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let amount = balance.checked_sub(prev_balance)?;
+    let fees = compute_swap_fees(&fee_config, &referral, relayer_fee, amount);
+    let return_amount = amount.checked_sub(fees.total())?;
+
+    if let Some(minimum_receive) = minimum_receive {
+        if return_amount < minimum_receive {
+            return Err(ContractError::MinimumReceiveAssertion {
+                minimum_receive,
+                swap_amount: return_amount,
+            });
+        }
+    }
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![];
+    let mut transfers: Vec<(Uint128, Addr)> = vec![];
+    match staking_contract {
+        Some(staking_contract) => messages.push(into_bond_msg(
+            &deps.querier,
+            Asset {
+                info: asset_info.clone(),
+                amount: return_amount,
+            },
+            &staking_contract,
+            &receiver,
+        )?),
+        None => transfers.push((return_amount, receiver)),
+    }
+    if let Some(fee_config) = fee_config {
+        transfers.push((fees.fee_amount, fee_config.fee_collector));
+    }
+    if let Some(referral) = referral {
+        transfers.push((fees.referral_amount, referral.address));
+    }
+    if let Some(relayer) = relayer {
+        transfers.push((fees.relayer_amount, relayer));
+    }
+    // Native transfers leave the tax to the recipients
+    for (amount, recipient) in transfers {
+        if let Some(msg) = (Asset {
+            info: asset_info.clone(),
+            amount,
+        })
+        .into_transfer_msg_with_tax(&deps.querier, &recipient)?
+        {
+            messages.push(msg);
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "distribute_swap_output"),
+        ("return_amount", &return_amount.to_string()),
+        ("fee_amount", &fees.fee_amount.to_string()),
+        ("referral_amount", &fees.referral_amount.to_string()),
+        ("relayer_amount", &fees.relayer_amount.to_string()),
+    ]))
+}
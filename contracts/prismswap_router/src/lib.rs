@@ -1,7 +1,16 @@
 pub mod contract;
+pub mod error;
+pub mod migration;
 pub mod state;
 
+mod fee;
 mod operations;
+mod receipt;
+mod route;
+mod stake;
+mod tax;
+mod twap;
+mod zap;
 
 #[cfg(test)]
 mod testing;
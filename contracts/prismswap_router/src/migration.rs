@@ -0,0 +1,63 @@
+use cosmwasm_std::{Addr, StdResult, Storage};
+use cw_storage_plus::Item;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{Config, CONFIG};
+
+pub const LEGACY: Item<LegacyConfig> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub factory: Addr,
+}
+
+pub fn migrate_config(storage: &mut dyn Storage, owner: Addr) -> StdResult<()> {
+    let legacy_config: LegacyConfig = LEGACY.load(storage)?;
+    let config = Config {
+        factory: legacy_config.factory,
+        owner,
+    };
+
+    CONFIG.save(storage, &config)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod migrate_tests {
+    use cosmwasm_std::{testing::mock_dependencies, Api};
+
+    use crate::{
+        migration::{migrate_config, LegacyConfig, LEGACY},
+        state::{Config, CONFIG},
+    };
+
+    #[test]
+    fn test_config_migration() {
+        let mut deps = mock_dependencies(&[]);
+
+        LEGACY
+            .save(
+                &mut deps.storage,
+                &LegacyConfig {
+                    factory: deps.api.addr_validate("factory0000").unwrap(),
+                },
+            )
+            .unwrap();
+
+        migrate_config(
+            &mut deps.storage,
+            deps.api.addr_validate("owner0000").unwrap(),
+        )
+        .unwrap();
+
+        let config: Config = CONFIG.load(&deps.storage).unwrap();
+        assert_eq!(
+            config,
+            Config {
+                factory: deps.api.addr_validate("factory0000").unwrap(),
+                owner: deps.api.addr_validate("owner0000").unwrap(),
+            }
+        )
+    }
+}
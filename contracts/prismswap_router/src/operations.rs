@@ -1,34 +1,68 @@
-use cosmwasm_std::{Coin, CosmosMsg, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use cosmwasm_std::{Addr, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response};
 
-use crate::state::{Config, CONFIG};
+use crate::error::ContractError;
+use crate::receipt::record_hop;
+use crate::state::{assert_hop_allowed, load_pair_info, Config, CONFIG};
 
-use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAsset};
-use prismswap::querier::{query_balance, query_pair_info, query_token_balance};
-use prismswap::router::SwapOperation;
+use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::router::{SwapAmount, SwapOperation, TaxInfo};
 use terra_cosmwasm::{create_swap_msg, create_swap_send_msg, TerraMsgWrapper};
 
 /// Execute swap operation
-/// swap all offer asset to ask asset
+/// swap the given amount of offer asset, or all of it, to ask asset
+/// native assets sent to a pair are reduced by the tax paid on top of them
+#[allow(clippy::too_many_arguments)]
 pub fn execute_swap_operation(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     operation: SwapOperation,
     to: Option<String>,
-) -> StdResult<Response<TerraMsgWrapper>> {
+    amount: Option<SwapAmount>,
+    tax_info: Option<TaxInfo>,
+    max_spread: Option<Decimal>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
     if env.contract.address != info.sender {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
+    let amount = match amount.unwrap_or(SwapAmount::All) {
+        SwapAmount::Exact(amount) => amount,
+        SwapAmount::All => operation
+            .get_offer_asset_info()
+            .query_pool(&deps.querier, &env.contract.address)?,
+        SwapAmount::ReceivedSince(prev_balance) => operation
+            .get_offer_asset_info()
+            .query_pool(&deps.querier, &env.contract.address)?
+            .checked_sub(prev_balance)?,
+    };
+
+    let receiver = match &to {
+        Some(to) => deps.api.addr_validate(to)?,
+        None => env.contract.address.clone(),
+    };
+
     let messages: Vec<CosmosMsg<TerraMsgWrapper>> = match operation {
         SwapOperation::NativeSwap {
             offer_denom,
             ask_denom,
         } => {
-            let amount = query_balance(
-                &deps.querier,
-                &env.contract.address,
-                offer_denom.to_string(),
+            assert_hop_allowed(
+                deps.storage,
+                &[
+                    AssetInfo::Native(offer_denom.to_string()),
+                    AssetInfo::Native(ask_denom.to_string()),
+                ],
+                None,
+            )?;
+            record_hop(
+                deps,
+                Asset {
+                    info: AssetInfo::Native(offer_denom.to_string()),
+                    amount,
+                },
+                AssetInfo::Native(ask_denom.to_string()),
+                receiver,
             )?;
 
             if let Some(to) = to {
@@ -53,31 +87,81 @@ pub fn execute_swap_operation(
         SwapOperation::PrismSwap {
             offer_asset_info,
             ask_asset_info,
+            pair,
+            max_spread: hop_max_spread,
         } => {
-            let config: Config = CONFIG.load(deps.as_ref().storage)?;
+            let config: Config = CONFIG.load(deps.storage)?;
             let prismswap_factory = config.factory;
-            let pair_info: PairInfo = query_pair_info(
+            let pair_info: PairInfo = load_pair_info(
+                deps.storage,
                 &deps.querier,
                 &prismswap_factory,
-                &[offer_asset_info.clone(), ask_asset_info],
+                &[offer_asset_info.clone(), ask_asset_info.clone()],
+                pair.as_ref(),
+            )?;
+            assert_hop_allowed(
+                deps.storage,
+                &[offer_asset_info.clone(), ask_asset_info.clone()],
+                Some(&pair_info.contract_addr),
             )?;
 
-            let amount = match offer_asset_info.clone() {
+            let amount = match &offer_asset_info {
                 AssetInfo::Native(denom) => {
-                    query_balance(&deps.querier, &env.contract.address, denom)?
-                }
-                AssetInfo::Cw20(contract_addr) => {
-                    query_token_balance(&deps.querier, &contract_addr, &env.contract.address)?
+                    let tax_info = match tax_info {
+                        Some(tax_info) => tax_info,
+                        None => TaxInfo::query(&deps.querier, &[denom.to_string()])?,
+                    };
+                    tax_info.deduct_tax(&Coin {
+                        denom: denom.to_string(),
+                        amount,
+                    })?
                 }
+                AssetInfo::Cw20(_) => amount,
             };
+
             let offer_asset: Asset = Asset {
                 info: offer_asset_info,
                 amount,
             };
+            record_hop(deps, offer_asset.clone(), ask_asset_info, receiver)?;
 
-            vec![offer_asset.into_swap_msg(&pair_info.contract_addr, None, to)?]
+            // the spread of the hop overrides the one of the swap
+            let max_spread = hop_max_spread.or(max_spread);
+            vec![offer_asset.into_swap_msg(&pair_info.contract_addr, max_spread, to)?]
         }
     };
 
     Ok(Response::new().add_messages(messages))
 }
+
+/// Refund the balances left above `prev_balances` by the swap, such as the rounding of the tax
+/// deducted from native offers, native refunds paying their own tax
+pub fn execute_refund_dust(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prev_balances: Vec<Asset>,
+    receiver: Addr,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![];
+    for prev_balance in prev_balances {
+        let balance = prev_balance
+            .info
+            .query_pool(&deps.querier, &env.contract.address)?;
+        let amount = balance.checked_sub(prev_balance.amount)?;
+
+        messages.extend(
+            Asset {
+                info: prev_balance.info,
+                amount,
+            }
+            .into_transfer_msg_with_tax(&deps.querier, &receiver)?,
+        );
+    }
+
+    Ok(Response::new().add_messages(messages))
+}
@@ -0,0 +1,193 @@
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cosmwasm_std::{
+    to_binary, Addr, CosmosMsg, Decimal, Deps, DepsMut, Env, Event, MessageInfo, Reply, Response,
+    StdResult, Uint128, WasmMsg,
+};
+
+use crate::error::ContractError;
+use crate::state::{
+    record_volume, HopRecord, POINTS_TRACKER, SWAP_RECEIPT, SWAP_ROUTES, SWAP_TRADER,
+};
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::points::ExecuteMsg as PointsExecuteMsg;
+use prismswap::router::{SwapHopReceipt, SwapReceipt};
+use terra_cosmwasm::TerraMsgWrapper;
+
+/// the reply id of the receipt message, whose data is returned as the swap data
+pub const SWAP_RECEIPT_REPLY_ID: u64 = 1;
+
+/// Records the realized offer of an operation, when it is part of a swap with a receipt
+pub fn record_hop(
+    deps: DepsMut,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    receiver: Addr,
+) -> StdResult<()> {
+    let mut hops: Vec<HopRecord> = match SWAP_RECEIPT.may_load(deps.storage)? {
+        Some(hops) => hops,
+        None => return Ok(()),
+    };
+
+    // the previous operation has fully executed by now
+    if let Some(prev) = hops.last_mut() {
+        prev.return_amount = Some(query_return_amount(deps.as_ref(), prev)?);
+    }
+
+    let prev_ask_balance = ask_asset_info.query_pool(&deps.querier, &receiver)?;
+    hops.push(HopRecord {
+        offer_asset,
+        ask_asset_info,
+        receiver,
+        prev_ask_balance,
+        return_amount: None,
+    });
+
+    SWAP_RECEIPT.save(deps.storage, &hops)
+}
+
+fn query_return_amount(deps: Deps, record: &HopRecord) -> StdResult<Uint128> {
+    let balance = record
+        .ask_asset_info
+        .query_pool(&deps.querier, &record.receiver)?;
+
+    Ok(balance.checked_sub(record.prev_ask_balance)?)
+}
+
+fn ratio(numerator: Uint128, denominator: Uint128) -> Decimal {
+    if denominator.is_zero() {
+        return Decimal::zero();
+    }
+
+    Decimal256::from_ratio(Uint256::from(numerator), Uint256::from(denominator)).into()
+}
+
+fn add_asset(total: Option<Asset>, asset: &Asset) -> StdResult<Asset> {
+    match total {
+        Some(total) => Ok(Asset {
+            amount: total.amount.checked_add(asset.amount)?,
+            info: total.info,
+        }),
+        None => Ok(asset.clone()),
+    }
+}
+
+/// Emit the realized amounts of every hop of the swap and return them as data
+pub fn execute_emit_swap_receipt(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let records: Vec<HopRecord> = SWAP_RECEIPT.load(deps.storage)?;
+    SWAP_RECEIPT.remove(deps.storage);
+
+    let mut hops: Vec<SwapHopReceipt> = vec![];
+    for record in records {
+        let return_amount = match record.return_amount {
+            Some(return_amount) => return_amount,
+            None => query_return_amount(deps.as_ref(), &record)?,
+        };
+
+        hops.push(SwapHopReceipt {
+            price: ratio(return_amount, record.offer_asset.amount),
+            offer_asset: record.offer_asset,
+            return_asset: Asset {
+                info: record.ask_asset_info,
+                amount: return_amount,
+            },
+        });
+    }
+
+    let events: Vec<Event> = hops
+        .iter()
+        .enumerate()
+        .map(|(index, hop)| {
+            Event::new("swap_hop")
+                .add_attribute("hop", index.to_string())
+                .add_attribute("offer_asset", hop.offer_asset.to_string())
+                .add_attribute("return_asset", hop.return_asset.to_string())
+                .add_attribute("price", hop.price.to_string())
+        })
+        .collect();
+
+    // a split swap offers and returns the sum of its routes
+    let route_lengths: Vec<u64> = match SWAP_ROUTES.may_load(deps.storage)? {
+        Some(route_lengths) => {
+            SWAP_ROUTES.remove(deps.storage);
+            route_lengths
+        }
+        None => vec![hops.len() as u64],
+    };
+    let mut offer_asset: Option<Asset> = None;
+    let mut return_asset: Option<Asset> = None;
+    let mut start = 0usize;
+    for route_length in route_lengths {
+        let end = start + route_length as usize;
+        let route = hops
+            .get(start..end)
+            .filter(|route| !route.is_empty())
+            .ok_or(ContractError::NoSwapExecuted {})?;
+        start = end;
+
+        let (first, last) = (&route[0], &route[route.len() - 1]);
+        offer_asset = Some(add_asset(offer_asset, &first.offer_asset)?);
+        return_asset = Some(add_asset(return_asset, &last.return_asset)?);
+    }
+    let offer_asset = offer_asset.ok_or(ContractError::NoSwapExecuted {})?;
+    let return_asset = return_asset.ok_or(ContractError::NoSwapExecuted {})?;
+    let receipt = SwapReceipt {
+        price: ratio(return_asset.amount, offer_asset.amount),
+        offer_asset,
+        return_asset,
+        hops,
+    };
+    record_volume(deps.storage, &receipt.offer_asset, &receipt.return_asset)?;
+
+    // Report the completed swap to the points tracker
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![];
+    if let Some(trader) = SWAP_TRADER.may_load(deps.storage)? {
+        SWAP_TRADER.remove(deps.storage);
+
+        if let Some(points_tracker) = POINTS_TRACKER.may_load(deps.storage)? {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: points_tracker.to_string(),
+                funds: vec![],
+                msg: to_binary(&PointsExecuteMsg::ReportSwap {
+                    trader,
+                    offer_asset: receipt.offer_asset.clone(),
+                    return_asset: receipt.return_asset.clone(),
+                })?,
+            }));
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_events(events)
+        .add_attributes(vec![
+            ("action", "swap_receipt"),
+            ("offer_asset", &receipt.offer_asset.to_string()),
+            ("return_asset", &receipt.return_asset.to_string()),
+            ("price", &receipt.price.to_string()),
+        ])
+        .set_data(to_binary(&receipt)?))
+}
+
+/// Forward the receipt data of the receipt message as the data of the swap
+pub fn reply_swap_receipt(msg: Reply) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let res = msg
+        .result
+        .into_result()
+        .map_err(ContractError::SubMsgFailure)?;
+
+    let mut response = Response::new();
+    if let Some(data) = res.data {
+        response = response.set_data(data);
+    }
+
+    Ok(response)
+}
@@ -0,0 +1,189 @@
+use cosmwasm_std::{Coin, Decimal, Deps, Uint128};
+
+use crate::error::ContractError;
+use crate::fee::{assert_referral, compute_swap_fees, load_simulated_relayer_fee};
+use crate::state::{assert_hop_allowed, read_pair_info, Config, CONFIG, FEE_CONFIG};
+use crate::tax::taxed_denoms;
+
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::querier::reverse_simulate;
+use prismswap::router::{
+    EstimateRequiredOfferResponse, FeeConfig, Referral, SwapOperation, TaxInfo,
+};
+
+const DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
+
+/// Returns the direct route and the routes through one of the intermediate assets
+fn candidate_routes(
+    offer_asset_info: &AssetInfo,
+    ask_asset_info: &AssetInfo,
+    intermediate_assets: &[AssetInfo],
+) -> Vec<Vec<SwapOperation>> {
+    let mut routes = vec![vec![SwapOperation::PrismSwap {
+        offer_asset_info: offer_asset_info.clone(),
+        ask_asset_info: ask_asset_info.clone(),
+        pair: None,
+        max_spread: None,
+    }]];
+
+    for intermediate_asset in intermediate_assets {
+        if intermediate_asset == offer_asset_info || intermediate_asset == ask_asset_info {
+            continue;
+        }
+
+        routes.push(vec![
+            SwapOperation::PrismSwap {
+                offer_asset_info: offer_asset_info.clone(),
+                ask_asset_info: intermediate_asset.clone(),
+                pair: None,
+                max_spread: None,
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: intermediate_asset.clone(),
+                ask_asset_info: ask_asset_info.clone(),
+                pair: None,
+                max_spread: None,
+            },
+        ]);
+    }
+
+    routes
+}
+
+/// Returns the offer amount the operations need to return `ask_amount` before router fees,
+/// walking the route backwards and adding the tax of every native hop
+fn reverse_simulate_route(
+    deps: Deps,
+    config: &Config,
+    tax_info: &Option<TaxInfo>,
+    ask_amount: Uint128,
+    operations: &[SwapOperation],
+) -> Result<Uint128, ContractError> {
+    let mut ask_amount = ask_amount;
+    for operation in operations.iter().rev() {
+        let (offer_asset_info, ask_asset_info, pair) = match operation {
+            SwapOperation::PrismSwap {
+                offer_asset_info,
+                ask_asset_info,
+                pair,
+                ..
+            } => (offer_asset_info, ask_asset_info, pair),
+            SwapOperation::NativeSwap { .. } => {
+                return Err(ContractError::NativeReverseSimulation {})
+            }
+        };
+
+        let pair_info = read_pair_info(
+            deps.storage,
+            &deps.querier,
+            &config.factory,
+            &[offer_asset_info.clone(), ask_asset_info.clone()],
+            pair.as_ref(),
+        )?;
+        assert_hop_allowed(
+            deps.storage,
+            &[offer_asset_info.clone(), ask_asset_info.clone()],
+            Some(&pair_info.contract_addr),
+        )?;
+        let res = reverse_simulate(
+            &deps.querier,
+            &pair_info.contract_addr,
+            &Asset {
+                info: ask_asset_info.clone(),
+                amount: ask_amount,
+            },
+        )?;
+
+        ask_amount = match (offer_asset_info, tax_info) {
+            (AssetInfo::Native(denom), Some(tax_info)) => tax_info.add_tax(&Coin {
+                denom: denom.to_string(),
+                amount: res.offer_amount,
+            })?,
+            _ => res.offer_amount,
+        };
+    }
+
+    Ok(ask_amount)
+}
+
+/// Returns the swap output needed to leave `amount` once the fees are taken from it
+fn add_swap_fees(
+    fee_config: &Option<FeeConfig>,
+    referral: &Option<Referral>,
+    relayer_fee: Option<Decimal>,
+    amount: Uint128,
+) -> Result<Uint128, ContractError> {
+    let fee_rate = fee_config
+        .as_ref()
+        .map_or_else(Decimal::zero, |fee_config| fee_config.fee_rate)
+        + referral
+            .as_ref()
+            .map_or_else(Decimal::zero, |referral| referral.fee)
+        + relayer_fee.unwrap_or_else(Decimal::zero);
+    if fee_rate >= Decimal::one() {
+        return Err(ContractError::FeesExceedOutput {});
+    }
+
+    let output = amount.multiply_ratio(
+        DECIMAL_FRACTION,
+        DECIMAL_FRACTION * (Decimal::one() - fee_rate),
+    );
+    let fees = compute_swap_fees(fee_config, referral, relayer_fee, output);
+    if output.checked_sub(fees.total())? < amount {
+        return Ok(output + Uint128::new(1));
+    }
+
+    Ok(output)
+}
+
+pub fn query_estimate_required_offer(
+    deps: Deps,
+    offer_asset_info: AssetInfo,
+    ask_asset: Asset,
+    intermediate_assets: Vec<AssetInfo>,
+    referral: Option<Referral>,
+    relayed: bool,
+) -> Result<EstimateRequiredOfferResponse, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    if let Some(referral) = &referral {
+        assert_referral(deps, referral)?;
+    }
+
+    let fee_config: Option<FeeConfig> = FEE_CONFIG.may_load(deps.storage)?;
+    let relayer_fee = load_simulated_relayer_fee(deps, relayed)?;
+    let ask_amount = add_swap_fees(&fee_config, &referral, relayer_fee, ask_asset.amount)?;
+
+    let routes = candidate_routes(&offer_asset_info, &ask_asset.info, &intermediate_assets);
+
+    // Query the tax once for the denoms of every route
+    let denoms = taxed_denoms(&routes.concat());
+    let tax_info = if denoms.is_empty() {
+        None
+    } else {
+        Some(TaxInfo::query(&deps.querier, &denoms)?)
+    };
+
+    // Routes without a pair, through a blacklisted hop or without the liquidity for the ask
+    // amount are skipped
+    let mut best: Option<EstimateRequiredOfferResponse> = None;
+    for operations in routes {
+        let offer_amount =
+            match reverse_simulate_route(deps, &config, &tax_info, ask_amount, &operations) {
+                Ok(offer_amount) => offer_amount,
+                Err(_) => continue,
+            };
+
+        if best
+            .as_ref()
+            .is_none_or(|best| offer_amount < best.offer_amount)
+        {
+            best = Some(EstimateRequiredOfferResponse {
+                operations,
+                offer_amount,
+            });
+        }
+    }
+
+    best.ok_or(ContractError::NoRouteFound {})
+}
@@ -0,0 +1,128 @@
+use cosmwasm_std::{
+    to_binary, Addr, Coin, CosmosMsg, DepsMut, Env, MessageInfo, QuerierWrapper, Response,
+    StdResult, Uint128, WasmMsg,
+};
+
+use crate::contract::execute_swap_operations;
+use crate::error::ContractError;
+use crate::state::load_staking_contract;
+
+use cw20::Cw20ExecuteMsg;
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::router::{ExecuteMsg, ExecuteSwapOperationsMsg, StakingHookMsg};
+use prismswap::tax::TaxInfo;
+use terra_cosmwasm::TerraMsgWrapper;
+
+/// Returns the message bonding the asset in the staking contract on behalf of the staker,
+/// native assets being reduced by the tax paid on top of them
+pub fn into_bond_msg(
+    querier: &QuerierWrapper,
+    asset: Asset,
+    staking_contract: &Addr,
+    staker: &Addr,
+) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    let msg = to_binary(&StakingHookMsg::Bond {
+        staker_addr: Some(staker.clone()),
+    })?;
+
+    match asset.info {
+        AssetInfo::Native(denom) => {
+            let coin = Coin {
+                denom: denom.to_string(),
+                amount: asset.amount,
+            };
+            let amount = TaxInfo::query(querier, &[denom.to_string()])?.deduct_tax(&coin)?;
+
+            Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: staking_contract.to_string(),
+                funds: vec![Coin { denom, amount }],
+                msg,
+            }))
+        }
+        AssetInfo::Cw20(contract_addr) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: staking_contract.to_string(),
+                amount: asset.amount,
+                msg,
+            })?,
+        })),
+    }
+}
+
+/// Redeem the sent share tokens in the staking contract, then swap the unbonded asset
+/// on behalf of the sender
+#[allow(clippy::too_many_arguments)]
+pub fn execute_unstake_and_swap(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+    staking_contract: Addr,
+    msg: ExecuteSwapOperationsMsg,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let staking_contract = load_staking_contract(deps.storage, &staking_contract)?;
+    if staking_contract.share_token.as_ref() != Some(&info.sender) {
+        return Err(ContractError::StakingShareMismatch {
+            contract_addr: staking_contract.contract_addr,
+            token: info.sender,
+        });
+    }
+
+    let prev_balance = staking_contract
+        .asset_info
+        .query_pool(&deps.querier, &env.contract.address)?;
+
+    Ok(Response::new()
+        .add_messages(vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: info.sender.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: staking_contract.contract_addr.to_string(),
+                    amount,
+                    msg: to_binary(&StakingHookMsg::Unbond {})?,
+                })?,
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: env.contract.address.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::SwapReceivedBalance {
+                    user: sender,
+                    asset_info: staking_contract.asset_info,
+                    prev_balance,
+                    msg,
+                })?,
+            }),
+        ])
+        .add_attributes(vec![
+            ("action", "unstake_and_swap"),
+            ("staking_contract", staking_contract.contract_addr.as_str()),
+            ("unstaked_share", &amount.to_string()),
+        ]))
+}
+
+/// Swap the balance received since `prev_balance` as if the user had sent it
+pub fn execute_swap_received_balance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    user: Addr,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    msg: ExecuteSwapOperationsMsg,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let offer_asset = Asset {
+        info: asset_info,
+        amount: balance.checked_sub(prev_balance)?,
+    };
+
+    execute_swap_operations(deps, env, user, offer_asset, msg, None)
+}
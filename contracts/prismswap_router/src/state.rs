@@ -1,12 +1,294 @@
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Order, QuerierWrapper, StdError, StdResult, Storage, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cw_storage_plus::Item;
+use cw_storage_plus::{Item, Map};
+use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAssetInfo};
+use prismswap::pagination::{calc_limit, start_after_bound};
+use prismswap::querier::{query_pair_info, query_pair_info_from_pair};
+use prismswap::router::{
+    AssetVolume, FeeConfig, RelayOrder, RelayerConfig, RouteBlacklist, SlippageConfig,
+    StakingContract, TwapConfig,
+};
+
+use crate::error::ContractError;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub factory: Addr,
+    pub owner: Addr,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const FEE_CONFIG: Item<FeeConfig> = Item::new("fee_config");
+
+/// the addresses whose swaps pay no router fee, like the protocol owned contracts
+pub const FEE_EXEMPTIONS: Item<Vec<Addr>> = Item::new("fee_exemptions");
+
+pub fn is_fee_exempt(storage: &dyn Storage, address: &Addr) -> StdResult<bool> {
+    Ok(FEE_EXEMPTIONS
+        .may_load(storage)?
+        .is_some_and(|fee_exemptions| fee_exemptions.contains(address)))
+}
+
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+pub const PAUSED: Item<bool> = Item::new("paused");
+
+pub const RELAYER_CONFIG: Item<RelayerConfig> = Item::new("relayer_config");
+/// the swap each user authorized the relayers to execute
+pub const RELAY_ORDERS: Map<&Addr, RelayOrder> = Map::new("relay_orders");
+
+pub const TWAP_CONFIG: Item<TwapConfig> = Item::new("twap_config");
+
+pub const SLIPPAGE_CONFIG: Item<SlippageConfig> = Item::new("slippage_config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HopRecord {
+    /// the asset sent by the operation
+    pub offer_asset: Asset,
+    /// the asset the operation returns
+    pub ask_asset_info: AssetInfo,
+    /// the account receiving the return asset
+    pub receiver: Addr,
+    /// the receiver balance of the return asset before the operation
+    pub prev_ask_balance: Uint128,
+    /// the realized return, measured when the next operation starts
+    pub return_amount: Option<Uint128>,
+}
+
+/// the hops of the swap being executed, only set between the first operation and the receipt
+pub const SWAP_RECEIPT: Item<Vec<HopRecord>> = Item::new("swap_receipt");
+/// the number of operations of every route of the split swap being executed, unset for a
+/// single route
+pub const SWAP_ROUTES: Item<Vec<u64>> = Item::new("swap_routes");
+
+/// the contract completed swaps are reported to when set
+pub const POINTS_TRACKER: Item<Addr> = Item::new("points_tracker");
+/// the parameter registry whose pause flag halts the swaps when set
+pub const PARAM_REGISTRY: Item<Addr> = Item::new("param_registry");
+/// the trader of the swap being executed, only set while a points tracker is
+pub const SWAP_TRADER: Item<Addr> = Item::new("swap_trader");
+
+/// the only final ask assets allowed when set
+pub const ASK_ASSET_WHITELIST: Item<Vec<AssetInfo>> = Item::new("ask_asset_whitelist");
+
+pub fn assert_ask_asset_allowed(
+    storage: &dyn Storage,
+    ask_asset_info: &AssetInfo,
+) -> Result<(), ContractError> {
+    if let Some(whitelist) = ASK_ASSET_WHITELIST.may_load(storage)? {
+        if !whitelist.contains(ask_asset_info) {
+            return Err(ContractError::AskAssetNotWhitelisted {
+                asset_info: ask_asset_info.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// the staking contracts swap outputs can be bonded in
+pub const STAKING_CONTRACTS: Item<Vec<StakingContract>> = Item::new("staking_contracts");
+
+/// Returns the registered staking contract, failing if it is not registered
+pub fn load_staking_contract(
+    storage: &dyn Storage,
+    contract_addr: &Addr,
+) -> Result<StakingContract, ContractError> {
+    STAKING_CONTRACTS
+        .may_load(storage)?
+        .unwrap_or_default()
+        .into_iter()
+        .find(|staking_contract| staking_contract.contract_addr == *contract_addr)
+        .ok_or_else(|| ContractError::StakingContractNotRegistered {
+            contract_addr: contract_addr.clone(),
+        })
+}
+
+/// Fails unless the staking contract is registered for the asset
+pub fn assert_staking_contract(
+    storage: &dyn Storage,
+    contract_addr: &Addr,
+    asset_info: &AssetInfo,
+) -> Result<(), ContractError> {
+    let staking_contract = load_staking_contract(storage, contract_addr)?;
+
+    if staking_contract.asset_info != *asset_info {
+        return Err(ContractError::StakingAssetMismatch {
+            contract_addr: contract_addr.clone(),
+            asset_info: asset_info.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+/// the pairs and the hops swaps cannot go through when set
+pub const ROUTE_BLACKLIST: Item<RouteBlacklist> = Item::new("route_blacklist");
+
+/// Fails when the hop between the asset infos or its pair is blacklisted
+pub fn assert_hop_allowed(
+    storage: &dyn Storage,
+    asset_infos: &[AssetInfo; 2],
+    pair: Option<&Addr>,
+) -> Result<(), ContractError> {
+    if let Some(route_blacklist) = ROUTE_BLACKLIST.may_load(storage)? {
+        if route_blacklist.hops.iter().any(|hop| {
+            (hop[0] == asset_infos[0] && hop[1] == asset_infos[1])
+                || (hop[0] == asset_infos[1] && hop[1] == asset_infos[0])
+        }) {
+            return Err(ContractError::HopBlacklisted {
+                offer_asset_info: asset_infos[0].clone(),
+                ask_asset_info: asset_infos[1].clone(),
+            });
+        }
+
+        if let Some(pair) = pair {
+            if route_blacklist.pairs.contains(pair) {
+                return Err(ContractError::PairBlacklisted { pair: pair.clone() });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// pair infos resolved from the factory, keyed by the sorted asset infos
+pub const PAIR_CACHE: Map<&[u8], PairInfo> = Map::new("pair_cache");
+
+pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
+    let mut asset_infos = asset_infos.to_vec();
+    asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+
+    [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat()
+}
+
+/// Returns the info of the pair set on an operation, checked to swap the asset infos
+fn query_operation_pair(
+    querier: &QuerierWrapper,
+    pair: &Addr,
+    asset_infos: &[AssetInfo; 2],
+) -> StdResult<PairInfo> {
+    let pair_info = query_pair_info_from_pair(querier, pair)?;
+    if pair_key(&pair_info.asset_infos) != pair_key(asset_infos) {
+        return Err(StdError::generic_err(format!(
+            "pair {} does not swap {} and {}",
+            pair, asset_infos[0], asset_infos[1]
+        )));
+    }
+
+    Ok(pair_info)
+}
+
+/// Returns the info of the pair, failing unless it is the pair the factory created for its assets
+pub fn query_registered_pair(
+    querier: &QuerierWrapper,
+    factory: &Addr,
+    pair: &Addr,
+) -> Result<PairInfo, ContractError> {
+    let pair_info = query_pair_info_from_pair(querier, pair)?;
+    match query_pair_info(querier, factory, &pair_info.asset_infos) {
+        Ok(registered) if registered.contract_addr == *pair => Ok(pair_info),
+        _ => Err(ContractError::PairNotRegistered { pair: pair.clone() }),
+    }
+}
+
+/// Returns the pair set on the operation, or the cached pair info falling back to the factory
+/// on a cache miss
+pub fn read_pair_info(
+    storage: &dyn Storage,
+    querier: &QuerierWrapper,
+    factory: &Addr,
+    asset_infos: &[AssetInfo; 2],
+    pair: Option<&Addr>,
+) -> StdResult<PairInfo> {
+    if let Some(pair) = pair {
+        return query_operation_pair(querier, pair, asset_infos);
+    }
+
+    match PAIR_CACHE.may_load(storage, &pair_key(asset_infos))? {
+        Some(pair_info) => Ok(pair_info),
+        None => query_pair_info(querier, factory, asset_infos),
+    }
+}
+
+/// Returns the pair set on the operation, or the cached pair info querying the factory and
+/// caching the result on a cache miss
+pub fn load_pair_info(
+    storage: &mut dyn Storage,
+    querier: &QuerierWrapper,
+    factory: &Addr,
+    asset_infos: &[AssetInfo; 2],
+    pair: Option<&Addr>,
+) -> StdResult<PairInfo> {
+    if let Some(pair) = pair {
+        return query_operation_pair(querier, pair, asset_infos);
+    }
+
+    let key = pair_key(asset_infos);
+    if let Some(pair_info) = PAIR_CACHE.may_load(storage, &key)? {
+        return Ok(pair_info);
+    }
+
+    let pair_info = query_pair_info(querier, factory, asset_infos)?;
+    PAIR_CACHE.save(storage, &key, &pair_info)?;
+
+    Ok(pair_info)
+}
+
+pub fn clear_pair_cache(storage: &mut dyn Storage) {
+    let keys: Vec<Vec<u8>> = PAIR_CACHE
+        .keys(storage, None, None, Order::Ascending)
+        .collect();
+    for key in keys {
+        PAIR_CACHE.remove(storage, &key);
+    }
+}
+
+/// the cumulative volume swapped from and into each asset, keyed by the asset bytes
+pub const ASSET_VOLUMES: Map<&[u8], AssetVolume> = Map::new("asset_volumes");
+
+/// Adds the offer and the return of a swap to the volumes of their assets
+pub fn record_volume(
+    storage: &mut dyn Storage,
+    offer_asset: &Asset,
+    return_asset: &Asset,
+) -> StdResult<()> {
+    for (asset, is_offer) in [(offer_asset, true), (return_asset, false)] {
+        let key = asset.info.as_bytes();
+        let mut volume = ASSET_VOLUMES
+            .may_load(storage, key)?
+            .unwrap_or_else(|| AssetVolume {
+                asset_info: asset.info.clone(),
+                offer_volume: Uint128::zero(),
+                ask_volume: Uint128::zero(),
+            });
+        if is_offer {
+            volume.offer_volume = volume.offer_volume.checked_add(asset.amount)?;
+        } else {
+            volume.ask_volume = volume.ask_volume.checked_add(asset.amount)?;
+        }
+
+        ASSET_VOLUMES.save(storage, key, &volume)?;
+    }
+
+    Ok(())
+}
+
+pub fn read_asset_volumes(
+    storage: &dyn Storage,
+    start_after: Option<AssetInfo>,
+    limit: Option<u32>,
+) -> StdResult<Vec<AssetVolume>> {
+    let start = start_after_bound(start_after.map(|asset_info| asset_info.as_bytes().to_vec()));
+
+    ASSET_VOLUMES
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (_, v) = item?;
+            Ok(v)
+        })
+        .collect::<StdResult<Vec<AssetVolume>>>()
+}
@@ -0,0 +1,20 @@
+use prismswap::asset::AssetInfo;
+use prismswap::router::SwapOperation;
+
+/// Returns the native denoms the operations send to pairs, which pay tax on every hop
+pub fn taxed_denoms(operations: &[SwapOperation]) -> Vec<String> {
+    let mut denoms: Vec<String> = vec![];
+    for operation in operations {
+        if let SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Native(denom),
+            ..
+        } = operation
+        {
+            if denom != "uluna" && !denoms.contains(denom) {
+                denoms.push(denom.to_string());
+            }
+        }
+    }
+
+    denoms
+}
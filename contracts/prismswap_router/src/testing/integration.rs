@@ -0,0 +1,216 @@
+use cosmwasm_std::{Addr, Decimal, DepsMut, Env, MessageInfo, Response, Uint128};
+use cw_multi_test::{ContractWrapper, Executor};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::builder::RouterSwapBuilder;
+use prismswap::router::{
+    InstantiateMsg, QueryMsg, SimulateSwapOperationsResponse, SwapOperation, TwapConfig,
+};
+use prismswap::testing::{PrismSwapApp, PrismSwapContracts};
+
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
+use terra_cosmwasm::TerraMsgWrapper;
+
+// the router instantiates with no terra message, unlike its other entry points
+fn instantiate_router(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let res = instantiate(deps, env, info, msg)?;
+    Ok(Response::new().add_attributes(res.attributes))
+}
+
+fn contracts() -> PrismSwapContracts {
+    PrismSwapContracts {
+        factory: Box::new(
+            ContractWrapper::new_with_empty(
+                prismswap_factory::contract::execute,
+                prismswap_factory::contract::instantiate,
+                prismswap_factory::contract::query,
+            )
+            .with_reply_empty(prismswap_factory::contract::reply),
+        ),
+        pair: Box::new(
+            ContractWrapper::new_with_empty(
+                prismswap_pair::contract::execute,
+                prismswap_pair::contract::instantiate,
+                prismswap_pair::contract::query,
+            )
+            .with_reply_empty(prismswap_pair::contract::reply),
+        ),
+        router: Box::new(
+            ContractWrapper::new(execute, instantiate_router, query).with_reply(reply),
+        ),
+    }
+}
+
+#[test]
+fn swap_through_real_contracts() {
+    let mut suite = PrismSwapApp::new(contracts());
+
+    let token_a = suite.create_token("AAA", &[("provider", 1_000_000), ("trader", 1_000)]);
+    let token_b = suite.create_token("BBB", &[("provider", 1_000_000)]);
+    let asset_a = AssetInfo::Cw20(token_a);
+    let asset_b = AssetInfo::Cw20(token_b);
+    let pair = suite.create_pair([asset_a.clone(), asset_b.clone()]);
+
+    suite
+        .provide(
+            "provider",
+            &pair,
+            [
+                Asset::new(asset_a.clone(), 1_000_000u128),
+                Asset::new(asset_b.clone(), 1_000_000u128),
+            ],
+        )
+        .unwrap();
+    suite.assert_balance(
+        "provider",
+        &AssetInfo::Cw20(pair.liquidity_token.clone()),
+        1_000_000,
+    );
+    suite.assert_balance(pair.contract_addr.as_str(), &asset_a, 1_000_000);
+
+    let operations = vec![SwapOperation::PrismSwap {
+        offer_asset_info: asset_a.clone(),
+        ask_asset_info: asset_b.clone(),
+        pair: None,
+        max_spread: None,
+    }];
+    let simulation: SimulateSwapOperationsResponse = suite
+        .app
+        .wrap()
+        .query_wasm_smart(
+            &suite.router,
+            &QueryMsg::SimulateSwapOperations {
+                offer_amount: Uint128::from(1_000u128),
+                operations: operations.clone(),
+                referral: None,
+                relayed: None,
+            },
+        )
+        .unwrap();
+    assert!(!simulation.amount.is_zero());
+
+    // the minimum receive is checked against the real pair
+    let err = suite
+        .swap_operations(
+            "trader",
+            Asset::new(asset_a.clone(), 1_000u128),
+            operations.clone(),
+            Some(simulation.amount + Uint128::from(1u128)),
+        )
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("assertion failed; minimum receive amount"));
+
+    suite
+        .swap_operations(
+            "trader",
+            Asset::new(asset_a.clone(), 1_000u128),
+            operations,
+            Some(simulation.amount),
+        )
+        .unwrap();
+    suite.assert_balance("trader", &asset_a, 0);
+    suite.assert_balance("trader", &asset_b, simulation.amount.u128());
+    suite.assert_balance(suite.router.as_str(), &asset_b, 0);
+
+    // a direct swap back through the pair
+    suite
+        .swap(
+            "trader",
+            &pair,
+            Asset::new(asset_b.clone(), simulation.amount),
+            Some(Decimal::percent(50)),
+        )
+        .unwrap();
+    suite.assert_balance("trader", &asset_b, 0);
+    assert!(!suite.query_balance("trader", &asset_a).is_zero());
+}
+
+#[test]
+fn twap_bound_rejects_manipulated_pool() {
+    let mut suite = PrismSwapApp::new(contracts());
+
+    let token_a = suite.create_token(
+        "AAA",
+        &[
+            ("provider", 1_000_000),
+            ("attacker", 500_000),
+            ("collector", 1_000),
+        ],
+    );
+    let token_b = suite.create_token("BBB", &[("provider", 1_000_000)]);
+    let asset_a = AssetInfo::Cw20(token_a);
+    let asset_b = AssetInfo::Cw20(token_b);
+    let pair = suite.create_pair([asset_a.clone(), asset_b.clone()]);
+
+    suite
+        .provide(
+            "provider",
+            &pair,
+            [
+                Asset::new(asset_a.clone(), 1_000_000u128),
+                Asset::new(asset_b.clone(), 1_000_000u128),
+            ],
+        )
+        .unwrap();
+
+    // an hour at a 1:1 price, then the pool is pushed right before the conversion
+    suite
+        .app
+        .update_block(|block| block.time = block.time.plus_seconds(3600));
+    suite
+        .swap(
+            "attacker",
+            &pair,
+            Asset::new(asset_a.clone(), 500_000u128),
+            Some(Decimal::percent(50)),
+        )
+        .unwrap();
+
+    let operations = vec![SwapOperation::PrismSwap {
+        offer_asset_info: asset_a.clone(),
+        ask_asset_info: asset_b.clone(),
+        pair: None,
+        max_spread: None,
+    }];
+    let twap_config = TwapConfig {
+        window: 3600,
+        max_deviation: Decimal::percent(2),
+    };
+
+    // the router has no TWAP config, the bound of the swap alone rejects it
+    let msg = RouterSwapBuilder::new(
+        suite.router.clone(),
+        Asset::new(asset_a.clone(), 1_000u128),
+        operations.clone(),
+    )
+    .twap_config(twap_config)
+    .build()
+    .unwrap();
+    let err = suite
+        .app
+        .execute(Addr::unchecked("collector"), msg)
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("assertion failed; minimum receive amount"));
+    suite.assert_balance("collector", &asset_a, 1_000);
+
+    // without the bound the manipulated price is taken
+    suite
+        .swap_operations(
+            "collector",
+            Asset::new(asset_a.clone(), 1_000u128),
+            operations,
+            None,
+        )
+        .unwrap();
+    // the output at the TWAP is 1000, the bound accepting no less than 980
+    assert!(suite.query_balance("collector", &asset_b) < Uint128::from(980u128));
+}
@@ -1,6 +1,6 @@
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, OwnedDeps, Querier,
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Decimal, OwnedDeps, Querier,
     QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
 use schemars::JsonSchema;
@@ -9,14 +9,28 @@ use std::collections::HashMap;
 
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
 use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAssetInfo};
-use prismswap::pair::SimulationResponse;
-use terra_cosmwasm::{SwapResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
+use prismswap::factory::{FeeConfig, FeeInfoResponse};
+use prismswap::fee::SwapBreakdown;
+use prismswap::pair::{ReverseSimulationResponse, SimulationResponse, TwapResponse};
+use prismswap::params::{ParamResponse, ParamValue, QueryMsg as ParamsQueryMsg};
+use terra_cosmwasm::{
+    SwapResponse, TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Pair { asset_infos: [AssetInfo; 2] },
+    FeeInfo { asset_infos: [AssetInfo; 2] },
     Simulation { offer_asset: Asset },
+    ReverseSimulation { ask_asset: Asset },
+    Twap { window: u64 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum PairQueryMsg {
+    Pair {},
 }
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
@@ -38,6 +52,31 @@ pub struct WasmMockQuerier {
     base: MockQuerier<TerraQueryWrapper>,
     token_querier: TokenQuerier,
     factory_querier: FactoryQuerier,
+    pair_querier: PairQuerier,
+    twap_querier: TwapQuerier,
+    reverse_simulation_querier: ReverseSimulationQuerier,
+    tax_querier: TaxQuerier,
+    params: HashMap<String, ParamValue>,
+}
+
+#[derive(Clone, Default)]
+pub struct TaxQuerier {
+    rate: Decimal,
+    caps: HashMap<String, Uint128>,
+}
+
+impl TaxQuerier {
+    pub fn new(rate: Decimal, caps: &[(&String, &Uint128)]) -> Self {
+        let mut caps_map: HashMap<String, Uint128> = HashMap::new();
+        for (denom, cap) in caps.iter() {
+            caps_map.insert(denom.to_string(), **cap);
+        }
+
+        TaxQuerier {
+            rate,
+            caps: caps_map,
+        }
+    }
 }
 
 #[derive(Clone, Default)]
@@ -90,6 +129,57 @@ pub(crate) fn pairs_to_map(pairs: &[(&String, &String)]) -> HashMap<String, Stri
     pairs_map
 }
 
+#[derive(Clone, Default)]
+pub struct PairQuerier {
+    pair_infos: HashMap<String, PairInfo>,
+}
+
+impl PairQuerier {
+    pub fn new(pair_infos: &[(&String, &PairInfo)]) -> Self {
+        let mut pair_infos_map: HashMap<String, PairInfo> = HashMap::new();
+        for (pair, pair_info) in pair_infos.iter() {
+            pair_infos_map.insert(pair.to_string(), (*pair_info).clone());
+        }
+
+        PairQuerier {
+            pair_infos: pair_infos_map,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct TwapQuerier {
+    twaps: HashMap<String, TwapResponse>,
+}
+
+impl TwapQuerier {
+    pub fn new(twaps: &[(&String, &TwapResponse)]) -> Self {
+        let mut twaps_map: HashMap<String, TwapResponse> = HashMap::new();
+        for (pair, twap) in twaps.iter() {
+            twaps_map.insert(pair.to_string(), (*twap).clone());
+        }
+
+        TwapQuerier { twaps: twaps_map }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct ReverseSimulationQuerier {
+    // the offer amount per ask unit of each pair, one when missing
+    prices: HashMap<String, Decimal>,
+}
+
+impl ReverseSimulationQuerier {
+    pub fn new(prices: &[(&String, &Decimal)]) -> Self {
+        let mut prices_map: HashMap<String, Decimal> = HashMap::new();
+        for (pair, price) in prices.iter() {
+            prices_map.insert(pair.to_string(), **price);
+        }
+
+        ReverseSimulationQuerier { prices: prices_map }
+    }
+}
+
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         // MockQuerier doesn't support Custom, so we ignore it completely here
@@ -106,12 +196,6 @@ impl Querier for WasmMockQuerier {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum MockQueryMsg {
-    Price {},
-}
-
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
         match &request {
@@ -129,10 +213,54 @@ impl WasmMockQuerier {
                         }
                         _ => panic!("DO NOT ENTER HERE"),
                     }
+                } else if route == &TerraRoute::Treasury {
+                    match query_data {
+                        TerraQuery::TaxRate {} => {
+                            let res = TaxRateResponse {
+                                rate: self.tax_querier.rate,
+                            };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        TerraQuery::TaxCap { denom } => {
+                            let cap = self
+                                .tax_querier
+                                .caps
+                                .get(denom)
+                                .copied()
+                                .unwrap_or_default();
+                            let res = TaxCapResponse { cap };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
                 } else {
                     panic!("DO NOT ENTER HERE")
                 }
             }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if matches!(from_binary(msg), Ok(ParamsQueryMsg::Param { .. })) =>
+            {
+                match from_binary(msg).unwrap() {
+                    ParamsQueryMsg::Param { key } => {
+                        SystemResult::Ok(ContractResult::from(to_binary(&ParamResponse {
+                            value: self.params.get(&key).cloned(),
+                            key,
+                            bounds: None,
+                        })))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if from_binary::<PairQueryMsg>(msg).is_ok() =>
+            {
+                match self.pair_querier.pair_infos.get(contract_addr) {
+                    Some(v) => SystemResult::Ok(ContractResult::from(to_binary(v))),
+                    None => SystemResult::Err(SystemError::NoSuchContract {
+                        addr: contract_addr.clone(),
+                    }),
+                }
+            }
             QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg) {
                 Ok(QueryMsg::Pair { asset_infos }) => {
                     let key = String::from_utf8(pair_key(&asset_infos)).unwrap();
@@ -151,13 +279,52 @@ impl WasmMockQuerier {
                         }),
                     }
                 }
+                Ok(QueryMsg::FeeInfo { .. }) => {
+                    SystemResult::Ok(ContractResult::from(to_binary(&FeeInfoResponse {
+                        fee_config: FeeConfig {
+                            total_fee: Decimal::permille(3),
+                            protocol_fee_share: Decimal::zero(),
+                            collector: None,
+                        },
+                        collector: Addr::unchecked("collector"),
+                        withdraw_only: false,
+                        param_registry: None,
+                        staking_contract: None,
+                    })))
+                }
                 Ok(QueryMsg::Simulation { offer_asset }) => {
                     SystemResult::Ok(ContractResult::from(to_binary(&SimulationResponse {
                         return_amount: offer_asset.amount,
                         commission_amount: Uint128::zero(),
                         spread_amount: Uint128::zero(),
+                        breakdown: SwapBreakdown {
+                            return_amount: offer_asset.amount,
+                            ..SwapBreakdown::default()
+                        },
                     })))
                 }
+                Ok(QueryMsg::ReverseSimulation { ask_asset }) => {
+                    let price = self
+                        .reverse_simulation_querier
+                        .prices
+                        .get(contract_addr)
+                        .cloned()
+                        .unwrap_or_else(Decimal::one);
+                    SystemResult::Ok(ContractResult::from(to_binary(
+                        &ReverseSimulationResponse {
+                            offer_amount: ask_asset.amount * price,
+                            commission_amount: Uint128::zero(),
+                            spread_amount: Uint128::zero(),
+                        },
+                    )))
+                }
+                Ok(QueryMsg::Twap { .. }) => match self.twap_querier.twaps.get(contract_addr) {
+                    Some(v) => SystemResult::Ok(ContractResult::from(to_binary(v))),
+                    None => SystemResult::Err(SystemError::InvalidRequest {
+                        error: "Not enough price history for the twap window".to_string(),
+                        request: msg.as_slice().into(),
+                    }),
+                },
                 _ => match from_binary(msg).unwrap() {
                     Cw20QueryMsg::Balance { address } => {
                         let balances: &HashMap<String, Uint128> =
@@ -204,6 +371,11 @@ impl WasmMockQuerier {
             base,
             token_querier: TokenQuerier::default(),
             factory_querier: FactoryQuerier::default(),
+            pair_querier: PairQuerier::default(),
+            twap_querier: TwapQuerier::default(),
+            reverse_simulation_querier: ReverseSimulationQuerier::default(),
+            tax_querier: TaxQuerier::default(),
+            params: HashMap::new(),
         }
     }
 
@@ -213,6 +385,13 @@ impl WasmMockQuerier {
         }
     }
 
+    pub fn with_params(&mut self, params: &[(&str, ParamValue)]) {
+        self.params = params
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.clone()))
+            .collect();
+    }
+
     pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
         self.token_querier = TokenQuerier::new(balances);
     }
@@ -220,6 +399,22 @@ impl WasmMockQuerier {
     pub fn with_pairs(&mut self, pairs: &[(&String, &String)]) {
         self.factory_querier = FactoryQuerier::new(pairs);
     }
+
+    pub fn with_pair_infos(&mut self, pair_infos: &[(&String, &PairInfo)]) {
+        self.pair_querier = PairQuerier::new(pair_infos);
+    }
+
+    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&String, &Uint128)]) {
+        self.tax_querier = TaxQuerier::new(rate, caps);
+    }
+
+    pub fn with_twaps(&mut self, twaps: &[(&String, &TwapResponse)]) {
+        self.twap_querier = TwapQuerier::new(twaps);
+    }
+
+    pub fn with_reverse_prices(&mut self, prices: &[(&String, &Decimal)]) {
+        self.reverse_simulation_querier = ReverseSimulationQuerier::new(prices);
+    }
 }
 
 fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
@@ -1,2 +1,3 @@
+mod integration;
 mod mock_querier;
 mod tests;
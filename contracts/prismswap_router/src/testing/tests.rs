@@ -1,19 +1,39 @@
 use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, Coin, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg,
+    from_binary, to_binary, Addr, BankMsg, Binary, Coin, ContractResult, CosmosMsg, Decimal, Event,
+    Reply, Response, StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
 };
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, query, reply};
+use crate::error::ContractError;
 use crate::testing::mock_querier::mock_dependencies;
+use crate::zap::compute_zap_swap_amount;
 
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
-use prismswap::asset::{Asset, AssetInfo};
-use prismswap::pair::ExecuteMsg as PairExecuteMsg;
+use prismswap::asset::{Asset, AssetInfo, PairInfo};
+use prismswap::fee::SwapBreakdown;
+use prismswap::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg, TwapResponse};
+use prismswap::params::{ParamValue, PAUSED_KEY};
+use prismswap::points::ExecuteMsg as PointsExecuteMsg;
 use prismswap::router::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
-    SimulateSwapOperationsResponse, SwapOperation,
+    AssetVolume, AssetVolumesResponse, ConfigResponse, Cw20HookMsg, EstimateRequiredOfferResponse,
+    ExecuteMsg, ExecuteSplitSwapOperationsMsg, ExecuteSwapOperationsMsg, FeeConfig, InstantiateMsg,
+    QueryMsg, Referral, RelayOrder, RelayerConfig, RouteBlacklist, SimulateSwapOperationsResponse,
+    SlippageConfig, StakingContract, StakingHookMsg, SwapAmount, SwapHopReceipt, SwapOperation,
+    SwapReceipt, SwapRoute, TaxInfo, TwapConfig,
 };
-use terra_cosmwasm::{create_swap_msg, create_swap_send_msg};
+use terra_cosmwasm::{create_swap_msg, create_swap_send_msg, TerraMsgWrapper};
+
+fn swap_receipt_msg() -> SubMsg<TerraMsgWrapper> {
+    SubMsg::reply_on_success(
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::EmitSwapReceipt {}).unwrap(),
+        }),
+        1,
+    )
+}
 
 #[test]
 fn proper_initialization() {
@@ -21,6 +41,7 @@ fn proper_initialization() {
 
     let msg = InstantiateMsg {
         factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
     };
 
     let info = mock_info("addr0000", &[]);
@@ -32,18 +53,43 @@ fn proper_initialization() {
     let config: ConfigResponse =
         from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
     assert_eq!("factory", config.factory.as_str());
+    assert_eq!("owner0000", config.owner.as_str());
 }
 
 #[test]
 fn execute_swap_operations() {
-    let mut deps = mock_dependencies(&[]);
-    deps.querier.with_token_balances(&[(
-        &"asset0002".to_string(),
-        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
-    )]);
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::from(50u128),
+        },
+    ]);
+    deps.querier.with_token_balances(&[
+        (
+            &"asset0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+        ),
+        (
+            &"asset0001".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+        ),
+        (
+            &"asset0002".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+        ),
+    ]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"ukrw".to_string(), &Uint128::from(1000000u128))],
+    );
 
     let msg = InstantiateMsg {
         factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
     };
 
     let info = mock_info("addr0000", &[]);
@@ -51,20 +97,42 @@ fn execute_swap_operations() {
     // we can just call .unwrap() to assert this was a success
     let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
 
-    let msg = ExecuteMsg::ExecuteSwapOperations {
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
         operations: vec![],
         minimum_receive: None,
+        max_spread: None,
         to: None,
-    };
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
 
-    let info = mock_info("addr0000", &[]);
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "must provide operations"),
+        Err(ContractError::NoOperations {}) => {}
         _ => panic!("DO NOT ENTER HERE"),
     }
 
-    let msg = ExecuteMsg::ExecuteSwapOperations {
+    // the tax is queried once for the ukrw hop and passed to every operation
+    let tax_info = Some(TaxInfo {
+        rate: Decimal::percent(1),
+        caps: vec![Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    });
+
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
         operations: vec![
             SwapOperation::NativeSwap {
                 offer_denom: "uusd".to_string(),
@@ -73,21 +141,40 @@ fn execute_swap_operations() {
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Native("ukrw".to_string()),
                 ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                pair: None,
+                max_spread: None,
             },
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
                 ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                pair: None,
+                max_spread: None,
             },
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Native("uluna".to_string()),
                 ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                pair: None,
+                max_spread: None,
             },
         ],
         minimum_receive: Some(Uint128::from(1000000u128)),
+        max_spread: None,
         to: None,
-    };
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
 
-    let info = mock_info("addr0000", &[]);
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
     assert_eq!(
         res.messages,
@@ -101,6 +188,9 @@ fn execute_swap_operations() {
                         ask_denom: "ukrw".to_string(),
                     },
                     to: None,
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
@@ -111,8 +201,13 @@ fn execute_swap_operations() {
                     operation: SwapOperation::PrismSwap {
                         offer_asset_info: AssetInfo::Native("ukrw".to_string()),
                         ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: None,
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::from(50u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
@@ -123,8 +218,13 @@ fn execute_swap_operations() {
                     operation: SwapOperation::PrismSwap {
                         offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
                         ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: None,
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::from(100u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
@@ -135,11 +235,17 @@ fn execute_swap_operations() {
                     operation: SwapOperation::PrismSwap {
                         offer_asset_info: AssetInfo::Native("uluna".to_string()),
                         ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: Some(Addr::unchecked("addr0000")),
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::zero())),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
+            swap_receipt_msg(),
             SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: MOCK_CONTRACT_ADDR.to_string(),
                 funds: vec![],
@@ -151,34 +257,63 @@ fn execute_swap_operations() {
                 })
                 .unwrap(),
             })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::RefundDust {
+                    prev_balances: vec![Asset {
+                        info: AssetInfo::Native("ukrw".to_string()),
+                        amount: Uint128::from(50u128),
+                    }],
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
         ]
     );
 
     let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
         sender: "addr0000".to_string(),
         amount: Uint128::from(1000000u128),
-        msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations {
-            operations: vec![
-                SwapOperation::NativeSwap {
-                    offer_denom: "uusd".to_string(),
-                    ask_denom: "ukrw".to_string(),
-                },
-                SwapOperation::PrismSwap {
-                    offer_asset_info: AssetInfo::Native("ukrw".to_string()),
-                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-                },
-                SwapOperation::PrismSwap {
-                    offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
-                    ask_asset_info: AssetInfo::Native("uluna".to_string()),
-                },
-                SwapOperation::PrismSwap {
-                    offer_asset_info: AssetInfo::Native("uluna".to_string()),
-                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
-                },
-            ],
-            minimum_receive: None,
-            to: Some(Addr::unchecked("addr0002")),
-        })
+        msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations(
+            ExecuteSwapOperationsMsg {
+                operations: vec![
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        ask_asset_info: AssetInfo::Native("ukrw".to_string()),
+                        pair: None,
+                        max_spread: None,
+                    },
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                        pair: None,
+                        max_spread: None,
+                    },
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                        ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                        pair: None,
+                        max_spread: None,
+                    },
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Native("uluna".to_string()),
+                        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                        pair: None,
+                        max_spread: None,
+                    },
+                ],
+                minimum_receive: None,
+                max_spread: None,
+                to: Some(Addr::unchecked("addr0002")),
+                deadline: None,
+                referral: None,
+                swap_all: None,
+                twap_check: None,
+                twap_config: None,
+                staking_contract: None,
+            },
+        ))
         .unwrap(),
     });
 
@@ -191,11 +326,16 @@ fn execute_swap_operations() {
                 contract_addr: MOCK_CONTRACT_ADDR.to_string(),
                 funds: vec![],
                 msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
-                    operation: SwapOperation::NativeSwap {
-                        offer_denom: "uusd".to_string(),
-                        ask_denom: "ukrw".to_string(),
+                    operation: SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        ask_asset_info: AssetInfo::Native("ukrw".to_string()),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: None,
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
@@ -206,8 +346,13 @@ fn execute_swap_operations() {
                     operation: SwapOperation::PrismSwap {
                         offer_asset_info: AssetInfo::Native("ukrw".to_string()),
                         ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: None,
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::from(50u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
@@ -218,8 +363,13 @@ fn execute_swap_operations() {
                     operation: SwapOperation::PrismSwap {
                         offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
                         ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: None,
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::from(100u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
                 })
                 .unwrap(),
             })),
@@ -230,16 +380,35 @@ fn execute_swap_operations() {
                     operation: SwapOperation::PrismSwap {
                         offer_asset_info: AssetInfo::Native("uluna".to_string()),
                         ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                        pair: None,
+                        max_spread: None,
                     },
                     to: Some(Addr::unchecked("addr0002"),),
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::zero())),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+            // the ukrw dust goes back to the sender rather than the recipient
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::RefundDust {
+                    prev_balances: vec![Asset {
+                        info: AssetInfo::Native("ukrw".to_string()),
+                        amount: Uint128::from(50u128),
+                    }],
+                    receiver: Addr::unchecked("addr0000"),
                 })
                 .unwrap(),
-            }))
+            })),
         ]
     );
 
     // failure - invalid token addr
-    let msg = ExecuteMsg::ExecuteSwapOperations {
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
         operations: vec![
             SwapOperation::NativeSwap {
                 offer_denom: "uusd".to_string(),
@@ -248,26 +417,124 @@ fn execute_swap_operations() {
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Native("ukrw".to_string()),
                 ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                pair: None,
+                max_spread: None,
             },
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
                 ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                pair: None,
+                max_spread: None,
             },
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Native("uluna".to_string()),
                 ask_asset_info: AssetInfo::Cw20(Addr::unchecked("te")),
+                pair: None,
+                max_spread: None,
             },
         ],
         minimum_receive: Some(Uint128::from(1000000u128)),
+        max_spread: None,
         to: None,
-    };
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
 
     let info = mock_info("addr0000", &[]);
     let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("Invalid input: human address too short")
+        ContractError::Std(StdError::generic_err(
+            "Invalid input: human address too short"
+        ))
+    );
+
+    // failure - sent asset is not offered by the first operation
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uluna".to_string(),
+            ask_denom: "ukrw".to_string(),
+        }],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
     );
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidFirstOperation {});
+
+    // failure - operations are not chained
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000000u128),
+        msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations(
+            ExecuteSwapOperationsMsg {
+                operations: vec![
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        ask_asset_info: AssetInfo::Native("ukrw".to_string()),
+                        pair: None,
+                        max_spread: None,
+                    },
+                    SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Native("uluna".to_string()),
+                        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0002")),
+                        pair: None,
+                        max_spread: None,
+                    },
+                ],
+                minimum_receive: None,
+                max_spread: None,
+                to: None,
+                deadline: None,
+                referral: None,
+                swap_all: None,
+                twap_check: None,
+                twap_config: None,
+                staking_contract: None,
+            },
+        ))
+        .unwrap(),
+    });
+    let info = mock_info("asset0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::BrokenOperationChain {});
+
+    // failure - no native asset sent
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "ukrw".to_string(),
+        }],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNativeFunds {});
 }
 
 #[test]
@@ -275,6 +542,7 @@ fn execute_swap_operation() {
     let mut deps = mock_dependencies(&[]);
     let msg = InstantiateMsg {
         factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
     };
 
     let info = mock_info("addr0000", &[]);
@@ -298,11 +566,14 @@ fn execute_swap_operation() {
             ask_denom: "uluna".to_string(),
         },
         to: None,
+        amount: None,
+        tax_info: None,
+        max_spread: None,
     };
     let info = mock_info("addr0000", &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
     match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        Err(ContractError::Unauthorized {}) => {}
         _ => panic!("DO NOT ENTER HERE"),
     }
 
@@ -327,6 +598,9 @@ fn execute_swap_operation() {
             ask_denom: "uluna".to_string(),
         },
         to: Some(Addr::unchecked("addr0000")),
+        amount: None,
+        tax_info: None,
+        max_spread: None,
     };
     let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
     let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
@@ -352,8 +626,13 @@ fn execute_swap_operation() {
         operation: SwapOperation::PrismSwap {
             offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
             ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: None,
+            max_spread: None,
         },
         to: Some(Addr::unchecked("addr0000")),
+        amount: None,
+        tax_info: None,
+        max_spread: None,
     };
 
     let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
@@ -382,12 +661,129 @@ fn execute_swap_operation() {
     );
 }
 
+#[test]
+fn execute_swap_operation_through_pair() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"assetuusd".to_string(), &"pair".to_string())]);
+    deps.querier.with_pair_infos(&[
+        (
+            &"stable_pair".to_string(),
+            &PairInfo {
+                asset_infos: [
+                    AssetInfo::Native("uusd".to_string()),
+                    AssetInfo::Cw20(Addr::unchecked("asset")),
+                ],
+                contract_addr: Addr::unchecked("stable_pair"),
+                liquidity_token: Addr::unchecked("liquidity"),
+            },
+        ),
+        (
+            &"other_pair".to_string(),
+            &PairInfo {
+                asset_infos: [
+                    AssetInfo::Native("uluna".to_string()),
+                    AssetInfo::Cw20(Addr::unchecked("asset")),
+                ],
+                contract_addr: Addr::unchecked("other_pair"),
+                liquidity_token: Addr::unchecked("liquidity"),
+            },
+        ),
+    ]);
+    deps.querier.with_token_balances(&[(
+        &"asset".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+    )]);
+
+    // the hop swaps in its pair with its own max spread
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: Some(Addr::unchecked("stable_pair")),
+            max_spread: Some(Decimal::percent(1)),
+        },
+        to: None,
+        amount: None,
+        tax_info: None,
+        max_spread: Some(Decimal::percent(5)),
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "asset".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "stable_pair".to_string(),
+                amount: Uint128::from(1000000u128),
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::Cw20(Addr::unchecked("asset")),
+                        amount: Uint128::from(1000000u128),
+                    },
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(1)),
+                    to: None,
+                })
+                .unwrap()
+            })
+            .unwrap()
+        }))]
+    );
+
+    // the pair must swap the assets of the hop
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: Some(Addr::unchecked("other_pair")),
+            max_spread: None,
+        },
+        to: None,
+        amount: None,
+        tax_info: None,
+        max_spread: None,
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::Std(StdError::generic_err(
+            "pair other_pair does not swap cw20:asset and native:uusd"
+        ))
+    );
+
+    // operations without the hop settings keep their former encoding
+    assert_eq!(
+        to_binary(&SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: None,
+            max_spread: None,
+        })
+        .unwrap(),
+        Binary::from(
+            br#"{"prism_swap":{"offer_asset_info":{"cw20":"asset"},"ask_asset_info":{"native":"uusd"}}}"#
+        )
+    );
+}
+
 #[test]
 fn query_buy_with_routes() {
     let mut deps = mock_dependencies(&[]);
 
     let msg = InstantiateMsg {
         factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
     };
 
     let info = mock_info("addr0000", &[]);
@@ -405,12 +801,18 @@ fn query_buy_with_routes() {
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Native("ukrw".to_string()),
                 ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                pair: None,
+                max_spread: None,
             },
             SwapOperation::PrismSwap {
                 offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
                 ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                pair: None,
+                max_spread: None,
             },
         ],
+        referral: None,
+        relayed: None,
     };
 
     deps.querier.with_pairs(&[
@@ -424,6 +826,23 @@ fn query_buy_with_routes() {
         res,
         SimulateSwapOperationsResponse {
             amount: Uint128::from(1000000u128),
+            fee_amount: Uint128::zero(),
+            referral_amount: Uint128::zero(),
+            relayer_amount: Uint128::zero(),
+            breakdowns: vec![
+                SwapBreakdown {
+                    return_amount: Uint128::from(1000000u128),
+                    ..SwapBreakdown::default()
+                },
+                SwapBreakdown {
+                    return_amount: Uint128::from(1000000u128),
+                    ..SwapBreakdown::default()
+                },
+                SwapBreakdown {
+                    return_amount: Uint128::from(1000000u128),
+                    ..SwapBreakdown::default()
+                },
+            ],
         }
     );
 
@@ -439,6 +858,8 @@ fn query_buy_with_routes() {
                 ask_denom: "uluna".to_string(),
             },
         ],
+        referral: None,
+        relayed: None,
     };
 
     let res: SimulateSwapOperationsResponse =
@@ -447,6 +868,19 @@ fn query_buy_with_routes() {
         res,
         SimulateSwapOperationsResponse {
             amount: Uint128::from(1000000u128),
+            fee_amount: Uint128::zero(),
+            referral_amount: Uint128::zero(),
+            relayer_amount: Uint128::zero(),
+            breakdowns: vec![
+                SwapBreakdown {
+                    return_amount: Uint128::from(1000000u128),
+                    ..SwapBreakdown::default()
+                },
+                SwapBreakdown {
+                    return_amount: Uint128::from(1000000u128),
+                    ..SwapBreakdown::default()
+                },
+            ],
         }
     );
 }
@@ -481,9 +915,12 @@ fn assert_minimum_receive_native_token() {
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
-            msg,
-            "assertion failed; minimum receive amount: 1000001, swap amount: 1000000"
+        Err(err) => assert_eq!(
+            err,
+            ContractError::MinimumReceiveAssertion {
+                minimum_receive: Uint128::from(1000001u128),
+                swap_amount: Uint128::from(1000000u128),
+            }
         ),
         _ => panic!("DO NOT ENTER HERE"),
     }
@@ -516,9 +953,12 @@ fn assert_minimum_receive_token() {
     };
     let res = execute(deps.as_mut(), mock_env(), info, msg);
     match res {
-        Err(StdError::GenericErr { msg, .. }) => assert_eq!(
-            msg,
-            "assertion failed; minimum receive amount: 1000001, swap amount: 1000000"
+        Err(err) => assert_eq!(
+            err,
+            ContractError::MinimumReceiveAssertion {
+                minimum_receive: Uint128::from(1000001u128),
+                swap_amount: Uint128::from(1000000u128),
+            }
         ),
         _ => panic!("DO NOT ENTER HERE"),
     }
@@ -534,6 +974,3924 @@ fn assert_minimum_receive_token() {
     let err = execute(deps.as_mut(), mock_env(), info, msg).unwrap_err();
     assert_eq!(
         err,
-        StdError::generic_err("Invalid input: human address too short")
+        ContractError::Std(StdError::generic_err(
+            "Invalid input: human address too short"
+        ))
+    );
+}
+
+#[test]
+fn compute_zap_amount() {
+    // half of the offer minus the price impact and commission
+    let swap_amount = compute_zap_swap_amount(
+        Uint128::from(1000000u128),
+        Uint128::from(1000u128),
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert_eq!(swap_amount, Uint128::from(500u128));
+
+    // swap amount never exceeds the offer amount
+    let swap_amount = compute_zap_swap_amount(
+        Uint128::from(1u128),
+        Uint128::from(1000000u128),
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert!(swap_amount <= Uint128::from(1000000u128));
+}
+
+#[test]
+fn compute_zap_amount_large_pool() {
+    // the square of the pool overflows 128 bits
+    let swap_amount = compute_zap_swap_amount(
+        Uint128::from(100_000_000_000_000_000_000u128),
+        Uint128::from(1_000_000_000_000_000u128),
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert_eq!(swap_amount, Uint128::from(500_749_878_576_909u128));
+
+    let swap_amount = compute_zap_swap_amount(
+        Uint128::from(100_000_000_000_000_000_000u128),
+        Uint128::from(100_000_000_000_000_000_000u128),
+        Decimal::permille(3),
+    )
+    .unwrap();
+    assert!(swap_amount > Uint128::from(41_000_000_000_000_000_000u128));
+    assert!(swap_amount < Uint128::from(42_000_000_000_000_000_000u128));
+}
+
+#[test]
+fn provide_liquidity_from_single_asset() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_pair_infos(&[(
+        &"pair0000".to_string(),
+        &PairInfo {
+            asset_infos: [
+                AssetInfo::Native("uusd".to_string()),
+                AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            ],
+            contract_addr: Addr::unchecked("pair0000"),
+            liquidity_token: Addr::unchecked("liquidity0000"),
+        },
+    )]);
+    deps.querier.with_balance(&[(
+        "pair0000".to_string(),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[
+        (
+            &"asset0000".to_string(),
+            &[
+                (&"pair0000".to_string(), &Uint128::from(1000000u128)),
+                (&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(10u128)),
+            ],
+        ),
+        (
+            &"liquidity0000".to_string(),
+            &[(&"addr0000".to_string(), &Uint128::from(7u128))],
+        ),
+    ]);
+
+    // failure - no funds
+    let msg = ExecuteMsg::ProvideLiquidityFromSingleAsset {
+        pair: Addr::unchecked("pair0000"),
+        min_lp: Some(Uint128::from(100u128)),
+        max_spread: Some(Decimal::percent(1)),
+        to: None,
+        staking_contract: None,
+    };
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::InvalidNativeFunds {});
+
+    // failure - pair not created by the factory
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PairNotRegistered {
+            pair: Addr::unchecked("pair0000"),
+        }
+    );
+    deps.querier
+        .with_pairs(&[(&"asset0000uusd".to_string(), &"pair0000".to_string())]);
+
+    // failure - asset not in pair
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uluna".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::OfferAssetMismatch {});
+
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                funds: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(500u128),
+                }],
+                msg: to_binary(&PairExecuteMsg::Swap {
+                    offer_asset: Asset {
+                        info: AssetInfo::Native("uusd".to_string()),
+                        amount: Uint128::from(500u128),
+                    },
+                    belief_price: None,
+                    max_spread: Some(Decimal::percent(1)),
+                    to: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ProvideSwappedLiquidity {
+                    pair: Addr::unchecked("pair0000"),
+                    offer_asset: Asset {
+                        info: AssetInfo::Native("uusd".to_string()),
+                        amount: Uint128::from(500u128),
+                    },
+                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    prev_ask_balance: Uint128::from(10u128),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                    asset_info: AssetInfo::Cw20(Addr::unchecked("liquidity0000")),
+                    prev_balance: Uint128::from(7u128),
+                    minimum_receive: Uint128::from(100u128),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // cw20 offer through the receive hook
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::ProvideLiquidityFromSingleAsset {
+            pair: Addr::unchecked("pair0000"),
+            min_lp: None,
+            max_spread: None,
+            to: Some(Addr::unchecked("addr0001")),
+            staking_contract: None,
+        })
+        .unwrap(),
+    });
+    let info = mock_info("asset0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "pair0000".to_string(),
+                    amount: Uint128::from(500u128),
+                    msg: to_binary(&PairExecuteMsg::Swap {
+                        offer_asset: Asset {
+                            info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                            amount: Uint128::from(500u128),
+                        },
+                        belief_price: None,
+                        max_spread: None,
+                        to: None,
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ProvideSwappedLiquidity {
+                    pair: Addr::unchecked("pair0000"),
+                    offer_asset: Asset {
+                        info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        amount: Uint128::from(500u128),
+                    },
+                    ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                    prev_ask_balance: Uint128::zero(),
+                    receiver: Addr::unchecked("addr0001"),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the LP tokens are provided to the router and bonded on behalf of the recipient
+    let msg = ExecuteMsg::ProvideLiquidityFromSingleAsset {
+        pair: Addr::unchecked("pair0000"),
+        min_lp: Some(Uint128::from(100u128)),
+        max_spread: Some(Decimal::percent(1)),
+        to: None,
+        staking_contract: Some(Addr::unchecked("staking0000")),
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StakingContractNotRegistered {
+            contract_addr: Addr::unchecked("staking0000"),
+        }
+    );
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateStakingContracts {
+            staking_contracts: vec![StakingContract {
+                contract_addr: Addr::unchecked("staking0000"),
+                asset_info: AssetInfo::Cw20(Addr::unchecked("liquidity0000")),
+                share_token: None,
+            }],
+        },
+    )
+    .unwrap();
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1..],
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ProvideSwappedLiquidity {
+                    pair: Addr::unchecked("pair0000"),
+                    offer_asset: Asset {
+                        info: AssetInfo::Native("uusd".to_string()),
+                        amount: Uint128::from(500u128),
+                    },
+                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    prev_ask_balance: Uint128::from(10u128),
+                    receiver: Addr::unchecked(MOCK_CONTRACT_ADDR),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::DistributeSwapOutput {
+                    asset_info: AssetInfo::Cw20(Addr::unchecked("liquidity0000")),
+                    prev_balance: Uint128::zero(),
+                    receiver: Addr::unchecked("addr0000"),
+                    referral: None,
+                    relayer: None,
+                    fee_exempt: true,
+                    staking_contract: Some(Addr::unchecked("staking0000")),
+                    minimum_receive: Some(Uint128::from(100u128)),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn provide_liquidity_from_single_asset_with_tax() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1500u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_pair_infos(&[(
+        &"pair0000".to_string(),
+        &PairInfo {
+            asset_infos: [
+                AssetInfo::Native("uusd".to_string()),
+                AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            ],
+            contract_addr: Addr::unchecked("pair0000"),
+            liquidity_token: Addr::unchecked("liquidity0000"),
+        },
+    )]);
+    deps.querier
+        .with_pairs(&[(&"asset0000uusd".to_string(), &"pair0000".to_string())]);
+    deps.querier.with_balance(&[(
+        "pair0000".to_string(),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[
+            (&"pair0000".to_string(), &Uint128::from(1000000u128)),
+            (&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(510u128)),
+        ],
+    )]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    // the tax of the swapped portion is charged on top of it
+    let msg = ExecuteMsg::ProvideLiquidityFromSingleAsset {
+        pair: Addr::unchecked("pair0000"),
+        min_lp: None,
+        max_spread: Some(Decimal::percent(1)),
+        to: None,
+        staking_contract: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(495u128),
+            }],
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset {
+                    info: AssetInfo::Native("uusd".to_string()),
+                    amount: Uint128::from(495u128),
+                },
+                belief_price: None,
+                max_spread: Some(Decimal::percent(1)),
+                to: None,
+            })
+            .unwrap(),
+        }))
+    );
+
+    // and so is the tax of the provided native asset
+    let msg = ExecuteMsg::ProvideSwappedLiquidity {
+        pair: Addr::unchecked("pair0000"),
+        offer_asset: Asset {
+            info: AssetInfo::Native("uusd".to_string()),
+            amount: Uint128::from(500u128),
+        },
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        prev_ask_balance: Uint128::from(10u128),
+        receiver: Addr::unchecked("addr0000"),
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(495u128),
+            }],
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: AssetInfo::Native("uusd".to_string()),
+                        amount: Uint128::from(495u128),
+                    },
+                    Asset {
+                        info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        amount: Uint128::from(500u128),
+                    },
+                ],
+                slippage_tolerance: None,
+                receiver: Some("addr0000".to_string()),
+                auto_stake: None,
+            })
+            .unwrap(),
+        }))
+    );
+
+    // the native swap return of a cw20 offer is taxed when provided
+    let msg = ExecuteMsg::ProvideSwappedLiquidity {
+        pair: Addr::unchecked("pair0000"),
+        offer_asset: Asset {
+            info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            amount: Uint128::from(500u128),
+        },
+        ask_asset_info: AssetInfo::Native("uusd".to_string()),
+        prev_ask_balance: Uint128::from(1000u128),
+        receiver: Addr::unchecked("addr0000"),
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(495u128),
+            }],
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: [
+                    Asset {
+                        info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        amount: Uint128::from(500u128),
+                    },
+                    Asset {
+                        info: AssetInfo::Native("uusd".to_string()),
+                        amount: Uint128::from(495u128),
+                    },
+                ],
+                slippage_tolerance: None,
+                receiver: Some("addr0000".to_string()),
+                auto_stake: None,
+            })
+            .unwrap(),
+        }))
+    );
+}
+
+#[test]
+fn provide_swapped_liquidity() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1500u128),
+    }]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(510u128))],
+    )]);
+
+    let msg = ExecuteMsg::ProvideSwappedLiquidity {
+        pair: Addr::unchecked("pair0000"),
+        offer_asset: Asset {
+            info: AssetInfo::Native("uusd".to_string()),
+            amount: Uint128::from(500u128),
+        },
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        prev_ask_balance: Uint128::from(10u128),
+        receiver: Addr::unchecked("addr0000"),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: "pair0000".to_string(),
+                    amount: Uint128::from(500u128),
+                    expires: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "pair0000".to_string(),
+                funds: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(500u128),
+                }],
+                msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                    assets: [
+                        Asset {
+                            info: AssetInfo::Native("uusd".to_string()),
+                            amount: Uint128::from(500u128),
+                        },
+                        Asset {
+                            info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                            amount: Uint128::from(500u128),
+                        },
+                    ],
+                    slippage_tolerance: None,
+                    receiver: Some("addr0000".to_string()),
+                    auto_stake: None,
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn withdraw_liquidity_to_single_asset() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_pair_infos(&[(
+        &"pair0000".to_string(),
+        &PairInfo {
+            asset_infos: [
+                AssetInfo::Native("uusd".to_string()),
+                AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            ],
+            contract_addr: Addr::unchecked("pair0000"),
+            liquidity_token: Addr::unchecked("liquidity0000"),
+        },
+    )]);
+    deps.querier.with_balance(&[(
+        "addr0000".to_string(),
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(20u128),
+        }],
+    )]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(10u128))],
+    )]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::WithdrawLiquidityToSingleAsset {
+            pair: Addr::unchecked("pair0000"),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            minimum_receive: Some(Uint128::from(900u128)),
+            to: None,
+        })
+        .unwrap(),
+    });
+
+    // failure - pair not created by the factory
+    let info = mock_info("liquidity0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PairNotRegistered {
+            pair: Addr::unchecked("pair0000"),
+        }
+    );
+    deps.querier
+        .with_pairs(&[(&"asset0000uusd".to_string(), &"pair0000".to_string())]);
+
+    // failure - not the liquidity token of the pair
+    let info = mock_info("asset0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // failure - blacklisted pair
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateRouteBlacklist {
+            route_blacklist: Some(RouteBlacklist {
+                pairs: vec![Addr::unchecked("pair0000")],
+                hops: vec![],
+            }),
+        },
+    )
+    .unwrap();
+    let info = mock_info("liquidity0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PairBlacklisted {
+            pair: Addr::unchecked("pair0000"),
+        }
+    );
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateRouteBlacklist {
+            route_blacklist: None,
+        },
+    )
+    .unwrap();
+
+    let info = mock_info("liquidity0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "liquidity0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "pair0000".to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {}).unwrap(),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: SwapOperation::PrismSwap {
+                        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                        ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                        pair: None,
+                        max_spread: None,
+                    },
+                    to: Some(Addr::unchecked("addr0000")),
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::from(10u128))),
+                    tax_info: None,
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::TransferBalance {
+                    asset_info: AssetInfo::Native("uusd".to_string()),
+                    prev_balance: Uint128::zero(),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                    asset_info: AssetInfo::Native("uusd".to_string()),
+                    prev_balance: Uint128::from(20u128),
+                    minimum_receive: Uint128::from(900u128),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn transfer_balance() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1500u128),
+    }]);
+
+    let msg = ExecuteMsg::TransferBalance {
+        asset_info: AssetInfo::Native("uusd".to_string()),
+        prev_balance: Uint128::from(500u128),
+        receiver: Addr::unchecked("addr0000"),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(1000u128),
+            }],
+        }))]
+    );
+
+    // the tax is deducted from the received balance
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(990u128),
+            }],
+        }))]
+    );
+
+    // nothing is sent when the whole balance goes to the tax
+    let msg = ExecuteMsg::TransferBalance {
+        asset_info: AssetInfo::Native("uusd".to_string()),
+        prev_balance: Uint128::from(1499u128),
+        receiver: Addr::unchecked("addr0000"),
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // unauthorized
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        guardian: Some(Addr::unchecked("guardian0000")),
+    };
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info("owner0000", &[]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!("owner0001", config.owner.as_str());
+    assert_eq!(config.guardian, Some(Addr::unchecked("guardian0000")));
+}
+
+#[test]
+fn sweep() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(100u128),
+    }]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(200u128))],
+    )]);
+
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::Sweep {
+        assets: vec![
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Native("uluna".to_string()),
+            AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ],
+        to: Addr::unchecked("addr0001"),
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // empty balances are skipped
+    let info = mock_info("owner0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0001".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(100u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0001".to_string(),
+                    amount: Uint128::from(200u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the tax is charged on top of the swept native balance
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(1000000u128))],
+    );
+    let msg = ExecuteMsg::Sweep {
+        assets: vec![AssetInfo::Native("uusd".to_string())],
+        to: Addr::unchecked("addr0001"),
+    };
+    let info = mock_info("owner0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(99u128),
+            }],
+        }))]
+    );
+}
+
+#[test]
+fn update_fee_config() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let fee_config = FeeConfig {
+        fee_rate: Decimal::permille(3),
+        fee_collector: Addr::unchecked("collector0000"),
+        max_referral_fee: Decimal::percent(1),
+    };
+
+    // unauthorized
+    let msg = ExecuteMsg::UpdateFeeConfig {
+        fee_config: Some(fee_config.clone()),
+    };
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // fees too high
+    let info = mock_info("owner0000", &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::UpdateFeeConfig {
+            fee_config: Some(FeeConfig {
+                fee_rate: Decimal::percent(6),
+                max_referral_fee: Decimal::percent(5),
+                ..fee_config.clone()
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::FeeTooHigh { max_percent: 10 });
+
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.fee_config, Some(fee_config));
+
+    // disable fees
+    let msg = ExecuteMsg::UpdateFeeConfig { fee_config: None };
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.fee_config, None);
+}
+
+#[test]
+fn execute_swap_operations_with_fees() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let operation = SwapOperation::NativeSwap {
+        offer_denom: "uusd".to_string(),
+        ask_denom: "ukrw".to_string(),
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    // referral fee above the allowed maximum
+    let referral = Referral {
+        address: Addr::unchecked("referrer0000"),
+        fee: Decimal::permille(5),
+    };
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![operation.clone()],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: Some(referral.clone()),
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::ReferralFeeTooHigh {
+            max_referral_fee: Decimal::zero()
+        }
+    );
+
+    let owner_info = mock_info("owner0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        ExecuteMsg::UpdateFeeConfig {
+            fee_config: Some(FeeConfig {
+                fee_rate: Decimal::permille(3),
+                fee_collector: Addr::unchecked("collector0000"),
+                max_referral_fee: Decimal::percent(1),
+            }),
+        },
+    )
+    .unwrap();
+
+    // the output is kept by the router and distributed after the last operation
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation,
+                    to: None,
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: None,
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::DistributeSwapOutput {
+                    asset_info: AssetInfo::Native("ukrw".to_string()),
+                    prev_balance: Uint128::zero(),
+                    receiver: Addr::unchecked("addr0000"),
+                    referral: Some(referral),
+                    relayer: None,
+                    fee_exempt: false,
+                    staking_contract: None,
+                    minimum_receive: None,
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn execute_split_swap_operations() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::zero())],
+    )]);
+
+    let krw_route = vec![
+        SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "ukrw".to_string(),
+        },
+        SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            pair: None,
+            max_spread: None,
+        },
+    ];
+    let direct_route = vec![SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        pair: None,
+        max_spread: None,
+    }];
+    let split_msg = |offer_amounts: [u128; 2], direct_route: Vec<SwapOperation>| {
+        ExecuteMsg::ExecuteSplitSwapOperations(ExecuteSplitSwapOperationsMsg {
+            routes: vec![
+                SwapRoute {
+                    offer_amount: Uint128::from(offer_amounts[0]),
+                    operations: krw_route.clone(),
+                },
+                SwapRoute {
+                    offer_amount: Uint128::from(offer_amounts[1]),
+                    operations: direct_route,
+                },
+            ],
+            minimum_receive: Some(Uint128::from(900000u128)),
+            max_spread: None,
+            to: None,
+            deadline: None,
+            referral: None,
+        })
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        split_msg([600000, 300000], direct_route.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RouteAmountMismatch {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        split_msg(
+            [600000, 400000],
+            vec![SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            }],
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RouteTargetMismatch {});
+
+    // the routes run one after the other and only their total output is checked
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        split_msg([600000, 400000], direct_route.clone()),
+    )
+    .unwrap();
+    let tax_info = Some(TaxInfo {
+        rate: Decimal::zero(),
+        caps: vec![
+            Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::zero(),
+            },
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::zero(),
+            },
+        ],
+    });
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: krw_route[0].clone(),
+                    to: None,
+                    amount: Some(SwapAmount::Exact(Uint128::from(600000u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: krw_route[1].clone(),
+                    to: Some(Addr::unchecked("addr0000")),
+                    amount: Some(SwapAmount::ReceivedSince(Uint128::zero())),
+                    tax_info: tax_info.clone(),
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: direct_route[0].clone(),
+                    to: Some(Addr::unchecked("addr0000")),
+                    amount: Some(SwapAmount::Exact(Uint128::from(400000u128))),
+                    tax_info,
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                    asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    prev_balance: Uint128::zero(),
+                    minimum_receive: Uint128::from(900000u128),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // run the operations of both routes, updating the balances they change
+    deps.querier.with_pairs(&[
+        (&"asset0000ukrw".to_string(), &"pair0000".to_string()),
+        (&"asset0000uusd".to_string(), &"pair0001".to_string()),
+    ]);
+    let contract_info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    for (index, msg) in res.messages[..3].iter().enumerate() {
+        let msg = match &msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => from_binary(msg).unwrap(),
+            _ => panic!("DO NOT ENTER HERE"),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), contract_info.clone(), msg).unwrap();
+
+        match index {
+            0 => deps.querier.with_balance(&[(
+                MOCK_CONTRACT_ADDR.to_string(),
+                &[Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(594000u128),
+                }],
+            )]),
+            1 => deps.querier.with_token_balances(&[(
+                &"asset0000".to_string(),
+                &[(&"addr0000".to_string(), &Uint128::from(297000u128))],
+            )]),
+            _ => deps.querier.with_token_balances(&[(
+                &"asset0000".to_string(),
+                &[(&"addr0000".to_string(), &Uint128::from(497000u128))],
+            )]),
+        }
+    }
+
+    // the receipt and the volumes cover the sum of the routes
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info,
+        ExecuteMsg::EmitSwapReceipt {},
+    )
+    .unwrap();
+    let receipt: SwapReceipt = from_binary(&res.data.unwrap()).unwrap();
+    assert_eq!(receipt.hops.len(), 3);
+    assert_eq!(
+        receipt.offer_asset,
+        Asset {
+            info: AssetInfo::Native("uusd".to_string()),
+            amount: Uint128::from(1000000u128),
+        }
+    );
+    assert_eq!(
+        receipt.return_asset,
+        Asset {
+            info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            amount: Uint128::from(497000u128),
+        }
+    );
+    assert_eq!(receipt.price, Decimal::permille(497));
+
+    let res: AssetVolumesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AssetVolumes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.volumes,
+        vec![
+            AssetVolume {
+                asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                offer_volume: Uint128::zero(),
+                ask_volume: Uint128::from(497000u128),
+            },
+            AssetVolume {
+                asset_info: AssetInfo::Native("uusd".to_string()),
+                offer_volume: Uint128::from(1000000u128),
+                ask_volume: Uint128::zero(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn distribute_swap_output() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "ukrw".to_string(),
+        amount: Uint128::from(1100000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("owner0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateFeeConfig {
+            fee_config: Some(FeeConfig {
+                fee_rate: Decimal::permille(3),
+                fee_collector: Addr::unchecked("collector0000"),
+                max_referral_fee: Decimal::percent(1),
+            }),
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::DistributeSwapOutput {
+        asset_info: AssetInfo::Native("ukrw".to_string()),
+        prev_balance: Uint128::from(100000u128),
+        receiver: Addr::unchecked("addr0000"),
+        referral: Some(Referral {
+            address: Addr::unchecked("referrer0000"),
+            fee: Decimal::permille(2),
+        }),
+        relayer: None,
+        fee_exempt: false,
+        staking_contract: None,
+        minimum_receive: None,
+    };
+
+    let info = mock_info("addr0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), info, msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(995000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "collector0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(3000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referrer0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(2000u128),
+                }],
+            })),
+        ]
+    );
+
+    // the tax of native transfers is taken from every recipient's share
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"ukrw".to_string(), &Uint128::from(1000000u128))],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(985148u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "collector0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(2970u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referrer0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(1980u128),
+                }],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn fee_exemptions() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "ukrw".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let owner_info = mock_info("owner0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::UpdateFeeConfig {
+            fee_config: Some(FeeConfig {
+                fee_rate: Decimal::permille(3),
+                fee_collector: Addr::unchecked("collector0000"),
+                max_referral_fee: Decimal::percent(1),
+            }),
+        },
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateFeeExemptions {
+        addresses: vec![Addr::unchecked("collector0000")],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.fee_exemptions,
+        vec![Addr::unchecked("collector0000")]
+    );
+
+    // the output of an exempt sender goes straight to the recipient
+    let operation = SwapOperation::NativeSwap {
+        offer_denom: "uusd".to_string(),
+        ask_denom: "ukrw".to_string(),
+    };
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![operation.clone()],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "collector0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation,
+                    to: Some(Addr::unchecked("collector0000")),
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: None,
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+        ]
+    );
+
+    // an exempt output only pays the referral fee
+    let msg = ExecuteMsg::DistributeSwapOutput {
+        asset_info: AssetInfo::Native("ukrw".to_string()),
+        prev_balance: Uint128::zero(),
+        receiver: Addr::unchecked("collector0000"),
+        referral: Some(Referral {
+            address: Addr::unchecked("referrer0000"),
+            fee: Decimal::permille(2),
+        }),
+        relayer: None,
+        fee_exempt: true,
+        staking_contract: None,
+        minimum_receive: None,
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "collector0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(998000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "referrer0000".to_string(),
+                amount: vec![Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(2000u128),
+                }],
+            })),
+        ]
+    );
+}
+
+#[test]
+fn query_simulation_with_fees() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let info = mock_info("owner0000", &[]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::UpdateFeeConfig {
+            fee_config: Some(FeeConfig {
+                fee_rate: Decimal::permille(3),
+                fee_collector: Addr::unchecked("collector0000"),
+                max_referral_fee: Decimal::percent(1),
+            }),
+        },
+    )
+    .unwrap();
+
+    let msg = QueryMsg::SimulateSwapOperations {
+        offer_amount: Uint128::from(1000000u128),
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "ukrw".to_string(),
+        }],
+        referral: Some(Referral {
+            address: Addr::unchecked("referrer0000"),
+            fee: Decimal::permille(2),
+        }),
+        relayed: None,
+    };
+    let res: SimulateSwapOperationsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        SimulateSwapOperationsResponse {
+            amount: Uint128::from(995000u128),
+            fee_amount: Uint128::from(3000u128),
+            referral_amount: Uint128::from(2000u128),
+            relayer_amount: Uint128::zero(),
+            breakdowns: vec![SwapBreakdown {
+                return_amount: Uint128::from(1000000u128),
+                ..SwapBreakdown::default()
+            },],
+        }
+    );
+}
+
+#[test]
+fn estimate_required_offer() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_pairs(&[
+        (&"asset0000uusd".to_string(), &"pair0000".to_string()),
+        (&"ukrwuusd".to_string(), &"pair0001".to_string()),
+        (&"asset0000ukrw".to_string(), &"pair0002".to_string()),
+    ]);
+    deps.querier.with_reverse_prices(&[
+        (&"pair0000".to_string(), &Decimal::percent(200)),
+        (&"pair0002".to_string(), &Decimal::percent(150)),
+    ]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[
+            (&"uusd".to_string(), &Uint128::from(1000000u128)),
+            (&"ukrw".to_string(), &Uint128::from(2000u128)),
+        ],
+    );
+
+    let msg = QueryMsg::EstimateRequiredOffer {
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset: Asset {
+            info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            amount: Uint128::from(1000000u128),
+        },
+        intermediate_assets: Some(vec![
+            AssetInfo::Native("uluna".to_string()),
+            AssetInfo::Native("ukrw".to_string()),
+        ]),
+        referral: None,
+        relayed: None,
+    };
+
+    // the direct route needs 2000000 uusd plus tax, the krw route 1502000 ukrw then
+    // 1502000 uusd plus tax and the missing luna pairs are skipped
+    let res: EstimateRequiredOfferResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(
+        res,
+        EstimateRequiredOfferResponse {
+            operations: vec![
+                SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Native("uusd".to_string()),
+                    ask_asset_info: AssetInfo::Native("ukrw".to_string()),
+                    pair: None,
+                    max_spread: None,
+                },
+                SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    pair: None,
+                    max_spread: None,
+                },
+            ],
+            offer_amount: Uint128::from(1517021u128),
+        }
+    );
+
+    // the router fee is added back before simulating the route
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateFeeConfig {
+            fee_config: Some(FeeConfig {
+                fee_rate: Decimal::percent(1),
+                fee_collector: Addr::unchecked("collector0000"),
+                max_referral_fee: Decimal::percent(1),
+            }),
+        },
+    )
+    .unwrap();
+    let res: EstimateRequiredOfferResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.offer_amount, Uint128::from(1532323u128));
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::EstimateRequiredOffer {
+            offer_asset_info: AssetInfo::Native("uusd".to_string()),
+            ask_asset: Asset {
+                info: AssetInfo::Native("uluna".to_string()),
+                amount: Uint128::from(1000000u128),
+            },
+            intermediate_assets: None,
+            referral: None,
+            relayed: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no route found"));
+}
+
+#[test]
+fn pair_cache() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"assetuusd".to_string(), &"pair0000".to_string())]);
+    deps.querier.with_token_balances(&[(
+        &"asset".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+    )]);
+
+    let asset_infos = [
+        AssetInfo::Native("uusd".to_string()),
+        AssetInfo::Cw20(Addr::unchecked("asset")),
+    ];
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: None,
+            max_spread: None,
+        },
+        to: None,
+        amount: None,
+        tax_info: None,
+        max_spread: None,
+    };
+    let swap_contract = |res: Response<TerraMsgWrapper>| match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => {
+            match from_binary::<Cw20ExecuteMsg>(msg).unwrap() {
+                Cw20ExecuteMsg::Send { contract, .. } => contract,
+                _ => panic!("unexpected cw20 message"),
+            }
+        }
+        _ => panic!("unexpected message"),
+    };
+
+    // the first swap resolves the pair from the factory and caches it
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(swap_contract(res), "pair0000");
+
+    let cached: Option<PairInfo> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CachedPair {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        cached.map(|pair_info| pair_info.contract_addr),
+        Some(Addr::unchecked("pair0000"))
+    );
+
+    // later swaps keep using the cached pair
+    deps.querier
+        .with_pairs(&[(&"assetuusd".to_string(), &"pair0001".to_string())]);
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(swap_contract(res), "pair0000");
+
+    // unauthorized
+    let invalidate_msg = ExecuteMsg::InvalidatePairCache {
+        asset_infos: Some(vec![asset_infos.clone()]),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        invalidate_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        invalidate_msg,
+    )
+    .unwrap();
+    let cached: Option<PairInfo> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CachedPair {
+                asset_infos: asset_infos.clone(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(cached, None);
+
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(swap_contract(res), "pair0001");
+
+    // drop every cached pair
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::InvalidatePairCache { asset_infos: None },
+    )
+    .unwrap();
+    let cached: Option<PairInfo> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::CachedPair { asset_infos },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(cached, None);
+}
+
+#[test]
+fn execute_swap_operation_amounts() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let operation = SwapOperation::NativeSwap {
+        offer_denom: "uusd".to_string(),
+        ask_denom: "uluna".to_string(),
+    };
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+
+    // exact amount
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: operation.clone(),
+        to: None,
+        amount: Some(SwapAmount::Exact(Uint128::from(300000u128))),
+        tax_info: None,
+        max_spread: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(create_swap_msg(
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(300000u128),
+            },
+            "uluna".to_string()
+        ))],
+    );
+
+    // only the amount received on top of the previous balance
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: operation.clone(),
+        to: None,
+        amount: Some(SwapAmount::ReceivedSince(Uint128::from(400000u128))),
+        tax_info: None,
+        max_spread: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(create_swap_msg(
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(600000u128),
+            },
+            "uluna".to_string()
+        ))],
+    );
+
+    // the whole balance
+    let msg = ExecuteMsg::ExecuteSwapOperation {
+        operation: operation.clone(),
+        to: None,
+        amount: Some(SwapAmount::All),
+        tax_info: None,
+        max_spread: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(create_swap_msg(
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(1000000u128),
+            },
+            "uluna".to_string()
+        ))],
+    );
+
+    // swap_all opts every operation into swapping the whole balance
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![operation.clone()],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: Some(true),
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(500000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation,
+                    to: Some(Addr::unchecked("addr0000")),
+                    amount: Some(SwapAmount::All),
+                    tax_info: None,
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+        ]
+    );
+}
+
+#[test]
+fn pause() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        guardian: Some(Addr::unchecked("guardian0000")),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    // unauthorized
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Pause {},
+    )
+    .unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert!(config.paused);
+
+    let operations = vec![SwapOperation::NativeSwap {
+        offer_denom: "uusd".to_string(),
+        ask_denom: "uluna".to_string(),
+    }];
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: operations.clone(),
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    let receive_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(1000000u128),
+        msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations(
+            ExecuteSwapOperationsMsg {
+                operations: vec![SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                    pair: None,
+                    max_spread: None,
+                }],
+                minimum_receive: None,
+                max_spread: None,
+                to: None,
+                deadline: None,
+                referral: None,
+                swap_all: None,
+                twap_check: None,
+                twap_config: None,
+                staking_contract: None,
+            },
+        ))
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("asset0000", &[]),
+        receive_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    // simulations still work
+    let _res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateSwapOperations {
+            offer_amount: Uint128::from(1000000u128),
+            operations,
+            referral: None,
+            relayed: None,
+        },
+    )
+    .unwrap();
+
+    // only the owner or the guardian can unpause
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::Unpause {},
+    )
+    .unwrap();
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn param_registry_pause() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateParamRegistry {
+        param_registry: Some(Addr::unchecked("params0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.param_registry, Some(Addr::unchecked("params0000")));
+
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "uluna".to_string(),
+        }],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    // the registry pause flag halts the swaps
+    deps.querier
+        .with_params(&[(PAUSED_KEY, ParamValue::Bool(true))]);
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Paused {});
+
+    deps.querier
+        .with_params(&[(PAUSED_KEY, ParamValue::Bool(false))]);
+    let _res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+}
+
+#[test]
+fn ask_asset_whitelist() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateAskAssetWhitelist {
+        ask_asset_infos: Some(vec![AssetInfo::Native("uluna".to_string())]),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.ask_asset_whitelist,
+        Some(vec![AssetInfo::Native("uluna".to_string())])
+    );
+
+    let swap_msg = |ask_denom: &str| {
+        ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+            operations: vec![SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: ask_denom.to_string(),
+            }],
+            minimum_receive: None,
+            max_spread: None,
+            to: None,
+            deadline: None,
+            referral: None,
+            swap_all: None,
+            twap_check: None,
+            twap_config: None,
+            staking_contract: None,
+        })
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), swap_msg("ukrw")).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::AskAssetNotWhitelisted {
+            asset_info: AssetInfo::Native("ukrw".to_string())
+        }
+    );
+    let _res = execute(deps.as_mut(), mock_env(), info.clone(), swap_msg("uluna")).unwrap();
+
+    // lifting the restriction allows any ask asset again
+    let msg = ExecuteMsg::UpdateAskAssetWhitelist {
+        ask_asset_infos: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let _res = execute(deps.as_mut(), mock_env(), info, swap_msg("ukrw")).unwrap();
+}
+
+#[test]
+fn relayed_swap_operations() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+    )]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let operations = vec![SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        ask_asset_info: AssetInfo::Native("uusd".to_string()),
+        pair: None,
+        max_spread: None,
+    }];
+    let offer_asset = Asset {
+        info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        amount: Uint128::from(1000000u128),
+    };
+    let order = RelayOrder {
+        offer_asset: offer_asset.clone(),
+        operations: operations.clone(),
+        minimum_receive: Uint128::from(900000u128),
+        deadline: None,
+    };
+    let msg = ExecuteMsg::ExecuteRelayedSwapOperations {
+        user: Addr::unchecked("addr0000"),
+    };
+
+    // relayed swaps are disabled
+    let relayer_info = mock_info("relayer0000", &[]);
+    let err = execute(deps.as_mut(), mock_env(), relayer_info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let relayer_config = RelayerConfig {
+        fee_rate: Decimal::permille(5),
+        relayers: vec![Addr::unchecked("relayer0000")],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::UpdateRelayerConfig {
+            relayer_config: Some(relayer_config.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateRelayerConfig {
+            relayer_config: Some(relayer_config.clone()),
+        },
+    )
+    .unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.relayer_config, Some(relayer_config));
+
+    // not an allowed relayer
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer0001", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the relayer can only execute a swap the user authorized
+    let err = execute(deps.as_mut(), mock_env(), relayer_info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::RelayOrderNotFound {});
+
+    // native assets cannot be pulled from the user
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SubmitRelayOrder {
+            order: RelayOrder {
+                offer_asset: Asset {
+                    info: AssetInfo::Native("uusd".to_string()),
+                    amount: Uint128::from(1000000u128),
+                },
+                operations: vec![SwapOperation::NativeSwap {
+                    offer_denom: "uusd".to_string(),
+                    ask_denom: "uluna".to_string(),
+                }],
+                minimum_receive: Uint128::from(900000u128),
+                deadline: None,
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RelayedNativeOffer {});
+
+    // the operations must swap the offer asset
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SubmitRelayOrder {
+            order: RelayOrder {
+                offer_asset: Asset {
+                    info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                    amount: Uint128::from(1000000u128),
+                },
+                ..order.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidFirstOperation {});
+
+    // an expired order cannot be submitted
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SubmitRelayOrder {
+            order: RelayOrder {
+                deadline: Some(mock_env().block.time.seconds() - 1),
+                ..order.clone()
+            },
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DeadlineExpired {});
+
+    // nor executed once expired
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SubmitRelayOrder {
+            order: RelayOrder {
+                deadline: Some(mock_env().block.time.seconds() + 10),
+                ..order.clone()
+            },
+        },
+    )
+    .unwrap();
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(11);
+    let err = execute(deps.as_mut(), env, relayer_info.clone(), msg.clone()).unwrap_err();
+    assert_eq!(err, ContractError::DeadlineExpired {});
+
+    // only the user can cancel the order
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelRelayOrder {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RelayOrderNotFound {});
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelRelayOrder {},
+    )
+    .unwrap();
+    let res: Option<RelayOrder> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RelayOrder {
+                user: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, None);
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::SubmitRelayOrder {
+            order: order.clone(),
+        },
+    )
+    .unwrap();
+    let res: Option<RelayOrder> = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::RelayOrder {
+                user: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res, Some(order));
+
+    let res = execute(deps.as_mut(), mock_env(), relayer_info.clone(), msg.clone()).unwrap();
+    let relay_msg = ExecuteMsg::RelaySwapOperations {
+        user: Addr::unchecked("addr0000"),
+        offer_asset,
+        operations: operations.clone(),
+        minimum_receive: Uint128::from(900000u128),
+        relayer: Addr::unchecked("relayer0000"),
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: "addr0000".to_string(),
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::from(1000000u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&relay_msg).unwrap(),
+            })),
+        ]
+    );
+
+    // the order is executed once
+    let err = execute(deps.as_mut(), mock_env(), relayer_info, msg).unwrap_err();
+    assert_eq!(err, ContractError::RelayOrderNotFound {});
+
+    // internal use only
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("relayer0000", &[]),
+        relay_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the output is distributed with the relayer fee before checking the minimum receive
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        relay_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: operations[0].clone(),
+                    to: None,
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: None,
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::DistributeSwapOutput {
+                    asset_info: AssetInfo::Native("uusd".to_string()),
+                    prev_balance: Uint128::from(1000000u128),
+                    receiver: Addr::unchecked("addr0000"),
+                    referral: None,
+                    relayer: Some(Addr::unchecked("relayer0000")),
+                    fee_exempt: false,
+                    staking_contract: None,
+                    minimum_receive: None,
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                    asset_info: AssetInfo::Native("uusd".to_string()),
+                    prev_balance: Uint128::zero(),
+                    minimum_receive: Uint128::from(900000u128),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the relayer fee is paid from what the router received since the previous balance
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::DistributeSwapOutput {
+            asset_info: AssetInfo::Native("uusd".to_string()),
+            prev_balance: Uint128::zero(),
+            receiver: Addr::unchecked("addr0000"),
+            referral: None,
+            relayer: Some(Addr::unchecked("relayer0000")),
+            fee_exempt: false,
+            staking_contract: None,
+            minimum_receive: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "addr0000".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(995000u128),
+                }],
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "relayer0000".to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(5000u128),
+                }],
+            })),
+        ]
+    );
+
+    let res: SimulateSwapOperationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SimulateSwapOperations {
+                offer_amount: Uint128::from(1000000u128),
+                operations: vec![SwapOperation::NativeSwap {
+                    offer_denom: "uusd".to_string(),
+                    ask_denom: "uluna".to_string(),
+                }],
+                referral: None,
+                relayed: Some(true),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        SimulateSwapOperationsResponse {
+            amount: Uint128::from(995000u128),
+            fee_amount: Uint128::zero(),
+            referral_amount: Uint128::zero(),
+            relayer_amount: Uint128::from(5000u128),
+            breakdowns: vec![SwapBreakdown {
+                return_amount: Uint128::from(1000000u128),
+                ..SwapBreakdown::default()
+            },],
+        }
+    );
+}
+
+#[test]
+fn twap_check() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"assetuusd".to_string(), &"pair0000".to_string())]);
+    deps.querier.with_token_balances(&[(
+        &"asset".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::zero())],
+    )]);
+    deps.querier.with_twaps(&[(
+        &"pair0000".to_string(),
+        &TwapResponse {
+            asset_infos: [
+                AssetInfo::Native("uusd".to_string()),
+                AssetInfo::Cw20(Addr::unchecked("asset")),
+            ],
+            price0: Decimal::percent(50),
+            price1: Decimal::percent(200),
+            window: 3600u64,
+        },
+    )]);
+
+    let swap_msg = |minimum_receive: Option<Uint128>| {
+        ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+            operations: vec![SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("uusd".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+                pair: None,
+                max_spread: None,
+            }],
+            minimum_receive,
+            max_spread: None,
+            to: None,
+            deadline: None,
+            referral: None,
+            swap_all: None,
+            twap_check: Some(true),
+            twap_config: None,
+            staking_contract: None,
+        })
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), swap_msg(None)).unwrap_err();
+    assert_eq!(err, ContractError::TwapCheckNotConfigured {});
+
+    let msg = ExecuteMsg::UpdateTwapConfig {
+        twap_config: Some(TwapConfig {
+            window: 3600u64,
+            max_deviation: Decimal::percent(2),
+        }),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateTwapConfig {
+            twap_config: Some(TwapConfig {
+                window: 0u64,
+                max_deviation: Decimal::percent(2),
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTwapWindow {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateTwapConfig {
+            twap_config: Some(TwapConfig {
+                window: 3600u64,
+                max_deviation: Decimal::one(),
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidTwapMaxDeviation {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config.twap_config,
+        Some(TwapConfig {
+            window: 3600u64,
+            max_deviation: Decimal::percent(2),
+        })
+    );
+
+    // the output at the twap is 500000, so at most 2% less is accepted
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), swap_msg(None)).unwrap();
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+                prev_balance: Uint128::zero(),
+                minimum_receive: Uint128::from(490000u128),
+                receiver: Addr::unchecked("addr0000"),
+            })
+            .unwrap(),
+        }))
+    );
+
+    // a stricter minimum receive is kept
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        swap_msg(Some(Uint128::from(495000u128))),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+                prev_balance: Uint128::zero(),
+                minimum_receive: Uint128::from(495000u128),
+                receiver: Addr::unchecked("addr0000"),
+            })
+            .unwrap(),
+        }))
+    );
+}
+
+#[test]
+fn execute_swap_operation_with_tax() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"assetuusd".to_string(), &"pair".to_string())]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"uusd".to_string(), &Uint128::from(100u128))],
+    );
+
+    let swap_msg = |amount: u128, tax_info: Option<TaxInfo>| ExecuteMsg::ExecuteSwapOperation {
+        operation: SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Native("uusd".to_string()),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+            pair: None,
+            max_spread: None,
+        },
+        to: None,
+        amount: Some(SwapAmount::Exact(Uint128::from(amount))),
+        tax_info,
+        max_spread: None,
+    };
+    let pair_swap_msg = |amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair".to_string(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(amount),
+            }],
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset {
+                    info: AssetInfo::Native("uusd".to_string()),
+                    amount: Uint128::from(amount),
+                },
+                belief_price: None,
+                max_spread: None,
+                to: None,
+            })
+            .unwrap(),
+        }))
+    };
+
+    // below the cap the tax is the rate of the sent amount
+    let info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        swap_msg(1010, None),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![pair_swap_msg(1000)]);
+
+    // above the cap only the cap is deducted
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        swap_msg(1000000, None),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![pair_swap_msg(999900)]);
+
+    // the tax info passed from the start of the swap is used instead of querying it again
+    let tax_info = TaxInfo {
+        rate: Decimal::percent(1),
+        caps: vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000u128),
+        }],
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        swap_msg(1000000, Some(tax_info)),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![pair_swap_msg(999000)]);
+
+    // simulations deduct the same tax
+    let res: SimulateSwapOperationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SimulateSwapOperations {
+                offer_amount: Uint128::from(1000000u128),
+                operations: vec![SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Native("uusd".to_string()),
+                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+                    pair: None,
+                    max_spread: None,
+                }],
+                referral: None,
+                relayed: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.amount, Uint128::from(999900u128));
+}
+
+#[test]
+fn swap_deadline() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let now = mock_env().block.time.seconds();
+    let swap_msg = |deadline: u64| ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "ukrw".to_string(),
+        }],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: Some(deadline),
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        ExecuteMsg::ExecuteSwapOperations(swap_msg(now - 1)),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DeadlineExpired {});
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::ExecuteSwapOperations(swap_msg(now)),
+    )
+    .unwrap();
+
+    // the cw20 hook carries the same fields
+    let cw20_swap_msg = |deadline: u64| ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: None,
+            max_spread: None,
+        }],
+        ..swap_msg(deadline)
+    };
+    let receive_msg = |deadline: u64| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(1000000u128),
+            msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations(cw20_swap_msg(deadline))).unwrap(),
+        })
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("asset0000", &[]),
+        receive_msg(now - 1),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::DeadlineExpired {});
+}
+
+#[test]
+fn swap_receipt() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"asset0000ukrw".to_string(), &"pair0000".to_string())]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::zero())],
+    )]);
+
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![
+            SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                pair: None,
+                max_spread: None,
+            },
+        ],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages[2], swap_receipt_msg());
+
+    // run the operations, updating the balances they change
+    let contract_info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    for (index, msg) in res.messages[..2].iter().enumerate() {
+        let msg = match &msg.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => from_binary(msg).unwrap(),
+            _ => panic!("DO NOT ENTER HERE"),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), contract_info.clone(), msg).unwrap();
+
+        if index == 0 {
+            deps.querier.with_balance(&[(
+                MOCK_CONTRACT_ADDR.to_string(),
+                &[Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(990000u128),
+                }],
+            )]);
+        } else {
+            deps.querier.with_token_balances(&[(
+                &"asset0000".to_string(),
+                &[(&"addr0000".to_string(), &Uint128::from(495000u128))],
+            )]);
+        }
+    }
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::EmitSwapReceipt {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info.clone(),
+        ExecuteMsg::EmitSwapReceipt {},
+    )
+    .unwrap();
+    let receipt = SwapReceipt {
+        hops: vec![
+            SwapHopReceipt {
+                offer_asset: Asset {
+                    info: AssetInfo::Native("uusd".to_string()),
+                    amount: Uint128::from(1000000u128),
+                },
+                return_asset: Asset {
+                    info: AssetInfo::Native("ukrw".to_string()),
+                    amount: Uint128::from(990000u128),
+                },
+                price: Decimal::percent(99),
+            },
+            SwapHopReceipt {
+                offer_asset: Asset {
+                    info: AssetInfo::Native("ukrw".to_string()),
+                    amount: Uint128::from(990000u128),
+                },
+                return_asset: Asset {
+                    info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    amount: Uint128::from(495000u128),
+                },
+                price: Decimal::percent(50),
+            },
+        ],
+        offer_asset: Asset {
+            info: AssetInfo::Native("uusd".to_string()),
+            amount: Uint128::from(1000000u128),
+        },
+        return_asset: Asset {
+            info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            amount: Uint128::from(495000u128),
+        },
+        price: Decimal::permille(495),
+    };
+    assert_eq!(res.data, Some(to_binary(&receipt).unwrap()));
+    assert_eq!(res.events.len(), 2);
+    assert_eq!(
+        res.events[1],
+        Event::new("swap_hop")
+            .add_attribute("hop", "1")
+            .add_attribute("offer_asset", "native:ukrw:990000")
+            .add_attribute("return_asset", "cw20:asset0000:495000")
+            .add_attribute("price", "0.5")
+    );
+
+    // the swap volume is added to its offer and return assets
+    let res: AssetVolumesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AssetVolumes {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.volumes,
+        vec![
+            AssetVolume {
+                asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                offer_volume: Uint128::zero(),
+                ask_volume: Uint128::from(495000u128),
+            },
+            AssetVolume {
+                asset_info: AssetInfo::Native("uusd".to_string()),
+                offer_volume: Uint128::from(1000000u128),
+                ask_volume: Uint128::zero(),
+            },
+        ]
+    );
+
+    let res: AssetVolumesResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::AssetVolumes {
+                start_after: Some(AssetInfo::Cw20(Addr::unchecked("asset0000"))),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.volumes.len(), 1);
+    assert_eq!(
+        res.volumes[0].asset_info,
+        AssetInfo::Native("uusd".to_string())
+    );
+
+    // the receipt is only emitted once
+    let _err = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info,
+        ExecuteMsg::EmitSwapReceipt {},
+    )
+    .unwrap_err();
+
+    // the reply forwards the receipt as the data of the swap
+    let res = reply(
+        deps.as_mut(),
+        mock_env(),
+        Reply {
+            id: 1,
+            result: ContractResult::Ok(SubMsgExecutionResponse {
+                events: vec![],
+                data: Some(to_binary(&receipt).unwrap()),
+            }),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.data, Some(to_binary(&receipt).unwrap()));
+}
+
+#[test]
+fn swap_receipt_reports_points() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"asset0000uusd".to_string(), &"pair0000".to_string())]);
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::zero())],
+    )]);
+
+    let msg = ExecuteMsg::UpdatePointsTracker {
+        points_tracker: Some(Addr::unchecked("points0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let res: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(res.points_tracker, Some(Addr::unchecked("points0000")));
+
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Native("uusd".to_string()),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            pair: None,
+            max_spread: None,
+        }],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(res.messages[1], swap_receipt_msg());
+
+    // run the operation, updating the balance it changes
+    let contract_info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let msg = match &res.messages[0].msg {
+        CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => from_binary(msg).unwrap(),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), contract_info.clone(), msg).unwrap();
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(500000u128))],
+    )]);
+
+    // the completed swap is reported to the points tracker on behalf of the sender
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info,
+        ExecuteMsg::EmitSwapReceipt {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "points0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&PointsExecuteMsg::ReportSwap {
+                trader: Addr::unchecked("addr0000"),
+                offer_asset: Asset {
+                    info: AssetInfo::Native("uusd".to_string()),
+                    amount: Uint128::from(1000000u128),
+                },
+                return_asset: Asset {
+                    info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    amount: Uint128::from(500000u128),
+                },
+            })
+            .unwrap(),
+        }))]
+    );
+}
+
+#[test]
+fn slippage_config() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier
+        .with_pairs(&[(&"assetuusd".to_string(), &"pair0000".to_string())]);
+    deps.querier.with_token_balances(&[(
+        &"asset".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::zero())],
+    )]);
+
+    let slippage_config = SlippageConfig {
+        max_spread: Decimal::percent(1),
+        minimum_receive_tolerance: Decimal::percent(2),
+    };
+    let msg = ExecuteMsg::UpdateSlippageConfig {
+        slippage_config: Some(slippage_config.clone()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateSlippageConfig {
+            slippage_config: Some(SlippageConfig {
+                max_spread: Decimal::one(),
+                minimum_receive_tolerance: Decimal::percent(2),
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidMaxSpread {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.slippage_config, Some(slippage_config));
+
+    // a swap without slippage protection gets the configured one
+    let operation = SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+        pair: None,
+        max_spread: None,
+    };
+    let msg = ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+        operations: vec![operation.clone()],
+        minimum_receive: None,
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    });
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let tax_info = Some(TaxInfo {
+        rate: Decimal::zero(),
+        caps: vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::zero(),
+        }],
+    });
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation: operation.clone(),
+                    to: Some(Addr::unchecked("addr0000")),
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: tax_info.clone(),
+                    max_spread: Some(Decimal::percent(1)),
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                    asset_info: AssetInfo::Cw20(Addr::unchecked("asset")),
+                    prev_balance: Uint128::zero(),
+                    minimum_receive: Uint128::from(980000u128),
+                    receiver: Addr::unchecked("addr0000"),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+
+    // the operation passes the max spread to the pair
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::ExecuteSwapOperation {
+            operation,
+            to: Some(Addr::unchecked("addr0000")),
+            amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+            tax_info,
+            max_spread: Some(Decimal::percent(1)),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "pair0000".to_string(),
+            funds: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::from(1000000u128),
+            }],
+            msg: to_binary(&PairExecuteMsg::Swap {
+                offer_asset: Asset {
+                    info: AssetInfo::Native("uusd".to_string()),
+                    amount: Uint128::from(1000000u128),
+                },
+                belief_price: None,
+                max_spread: Some(Decimal::percent(1)),
+                to: Some("addr0000".to_string()),
+            })
+            .unwrap(),
+        }))]
+    );
+}
+
+#[test]
+fn route_blacklist() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_pairs(&[
+        (&"asset0000uusd".to_string(), &"pair0000".to_string()),
+        (&"ulunauusd".to_string(), &"pair0001".to_string()),
+        (&"asset0000uluna".to_string(), &"pair0002".to_string()),
+    ]);
+
+    let route_blacklist = RouteBlacklist {
+        pairs: vec![Addr::unchecked("pair0000")],
+        hops: vec![[
+            AssetInfo::Native("uusd".to_string()),
+            AssetInfo::Native("ukrw".to_string()),
+        ]],
+    };
+    let msg = ExecuteMsg::UpdateRouteBlacklist {
+        route_blacklist: Some(route_blacklist.clone()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.route_blacklist, Some(route_blacklist));
+
+    // blacklisted hops are rejected in either direction
+    let contract_info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info.clone(),
+        ExecuteMsg::ExecuteSwapOperation {
+            operation: SwapOperation::NativeSwap {
+                offer_denom: "ukrw".to_string(),
+                ask_denom: "uusd".to_string(),
+            },
+            to: None,
+            amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+            tax_info: None,
+            max_spread: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::HopBlacklisted {
+            offer_asset_info: AssetInfo::Native("ukrw".to_string()),
+            ask_asset_info: AssetInfo::Native("uusd".to_string())
+        }
+    );
+
+    let direct_operation = SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        pair: None,
+        max_spread: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info,
+        ExecuteMsg::ExecuteSwapOperation {
+            operation: direct_operation.clone(),
+            to: None,
+            amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+            tax_info: None,
+            max_spread: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::PairBlacklisted {
+            pair: Addr::unchecked("pair0000")
+        }
+    );
+
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateSwapOperations {
+            offer_amount: Uint128::from(1000000u128),
+            operations: vec![direct_operation],
+            referral: None,
+            relayed: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("pair pair0000 is blacklisted"));
+
+    // route finding skips the blacklisted pair
+    let res: EstimateRequiredOfferResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::EstimateRequiredOffer {
+                offer_asset_info: AssetInfo::Native("uusd".to_string()),
+                ask_asset: Asset {
+                    info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    amount: Uint128::from(1000000u128),
+                },
+                intermediate_assets: Some(vec![AssetInfo::Native("uluna".to_string())]),
+                referral: None,
+                relayed: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        EstimateRequiredOfferResponse {
+            operations: vec![
+                SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Native("uusd".to_string()),
+                    ask_asset_info: AssetInfo::Native("uluna".to_string()),
+                    pair: None,
+                    max_spread: None,
+                },
+                SwapOperation::PrismSwap {
+                    offer_asset_info: AssetInfo::Native("uluna".to_string()),
+                    ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                    pair: None,
+                    max_spread: None,
+                },
+            ],
+            offer_amount: Uint128::from(1000000u128),
+        }
+    );
+}
+
+#[test]
+fn swap_and_stake() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::from(1000000u128),
+    }]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::zero())],
+    )]);
+
+    let staking_contract = StakingContract {
+        contract_addr: Addr::unchecked("staking0000"),
+        asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        share_token: None,
+    };
+    let msg = ExecuteMsg::UpdateStakingContracts {
+        staking_contracts: vec![staking_contract.clone()],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.staking_contracts, vec![staking_contract]);
+
+    let swap_msg = |operation: SwapOperation, staking_contract: &str| {
+        ExecuteMsg::ExecuteSwapOperations(ExecuteSwapOperationsMsg {
+            operations: vec![operation],
+            minimum_receive: Some(Uint128::from(900000u128)),
+            max_spread: None,
+            to: None,
+            deadline: None,
+            referral: None,
+            swap_all: None,
+            twap_check: None,
+            twap_config: None,
+            staking_contract: Some(Addr::unchecked(staking_contract)),
+        })
+    };
+    let operation = SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Native("uusd".to_string()),
+        ask_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        pair: None,
+        max_spread: None,
+    };
+    let info = mock_info(
+        "addr0000",
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        }],
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        swap_msg(operation.clone(), "staking0001"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StakingContractNotRegistered {
+            contract_addr: Addr::unchecked("staking0001")
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        swap_msg(
+            SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "ukrw".to_string(),
+            },
+            "staking0000",
+        ),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StakingAssetMismatch {
+            contract_addr: Addr::unchecked("staking0000"),
+            asset_info: AssetInfo::Native("ukrw".to_string())
+        }
+    );
+
+    // the output is kept by the router and bonded once the fees are taken
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        swap_msg(operation.clone(), "staking0000"),
+    )
+    .unwrap();
+    let distribute_msg = ExecuteMsg::DistributeSwapOutput {
+        asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        prev_balance: Uint128::zero(),
+        receiver: Addr::unchecked("addr0000"),
+        referral: None,
+        relayer: None,
+        fee_exempt: false,
+        staking_contract: Some(Addr::unchecked("staking0000")),
+        minimum_receive: Some(Uint128::from(900000u128)),
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                    operation,
+                    to: None,
+                    amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                    tax_info: Some(TaxInfo {
+                        rate: Decimal::zero(),
+                        caps: vec![Coin {
+                            denom: "uusd".to_string(),
+                            amount: Uint128::zero(),
+                        }],
+                    }),
+                    max_spread: None,
+                })
+                .unwrap(),
+            })),
+            swap_receipt_msg(),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&distribute_msg).unwrap(),
+            })),
+        ]
+    );
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(890000u128))],
+    )]);
+    let contract_info = mock_info(MOCK_CONTRACT_ADDR, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        contract_info.clone(),
+        distribute_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::MinimumReceiveAssertion {
+            minimum_receive: Uint128::from(900000u128),
+            swap_amount: Uint128::from(890000u128)
+        }
+    );
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+    )]);
+    let res = execute(deps.as_mut(), mock_env(), contract_info, distribute_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "asset0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking0000".to_string(),
+                amount: Uint128::from(1000000u128),
+                msg: to_binary(&StakingHookMsg::Bond {
+                    staker_addr: Some(Addr::unchecked("addr0000")),
+                })
+                .unwrap(),
+            })
+            .unwrap(),
+        }))]
+    );
+}
+
+#[test]
+fn unstake_and_swap() {
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg {
+        factory: Addr::unchecked("factory"),
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::zero())],
+    )]);
+
+    let msg = ExecuteMsg::UpdateStakingContracts {
+        staking_contracts: vec![StakingContract {
+            contract_addr: Addr::unchecked("staking0000"),
+            asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            share_token: Some(Addr::unchecked("share0000")),
+        }],
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let swap = ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::PrismSwap {
+            offer_asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            pair: None,
+            max_spread: None,
+        }],
+        minimum_receive: Some(Uint128::from(900000u128)),
+        max_spread: None,
+        to: None,
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    };
+    let receive_msg = |staking_contract: &str| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::UnstakeAndSwap {
+                staking_contract: Addr::unchecked(staking_contract),
+                swap: swap.clone(),
+            })
+            .unwrap(),
+        })
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        receive_msg("staking0001"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StakingContractNotRegistered {
+            contract_addr: Addr::unchecked("staking0001")
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("asset0000", &[]),
+        receive_msg("staking0000"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::StakingShareMismatch {
+            contract_addr: Addr::unchecked("staking0000"),
+            token: Addr::unchecked("asset0000")
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        receive_msg("staking0000"),
+    )
+    .unwrap();
+    let swap_received_msg = ExecuteMsg::SwapReceivedBalance {
+        user: Addr::unchecked("addr0000"),
+        asset_info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+        prev_balance: Uint128::zero(),
+        msg: swap.clone(),
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "share0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "staking0000".to_string(),
+                    amount: Uint128::from(100u128),
+                    msg: to_binary(&StakingHookMsg::Unbond {}).unwrap(),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                funds: vec![],
+                msg: to_binary(&swap_received_msg).unwrap(),
+            })),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        swap_received_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the unbonded amount is swapped as if the user had sent it
+    deps.querier.with_token_balances(&[(
+        &"asset0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000000u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        swap_received_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                operation: swap.operations[0].clone(),
+                to: Some(Addr::unchecked("addr0000")),
+                amount: Some(SwapAmount::Exact(Uint128::from(1000000u128))),
+                tax_info: None,
+                max_spread: None,
+            })
+            .unwrap(),
+        }))
+    );
+    assert_eq!(res.messages.len(), 3);
+}
+
+#[test]
+fn refund_dust() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::from(1000000u128),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::from(1000100u128),
+        },
+    ]);
+    deps.querier.with_tax(
+        Decimal::percent(1),
+        &[(&"ukrw".to_string(), &Uint128::from(1000000u128))],
+    );
+
+    let msg = ExecuteMsg::RefundDust {
+        prev_balances: vec![
+            Asset {
+                info: AssetInfo::Native("uusd".to_string()),
+                amount: Uint128::from(1000000u128),
+            },
+            Asset {
+                info: AssetInfo::Native("ukrw".to_string()),
+                amount: Uint128::from(1000000u128),
+            },
+        ],
+        receiver: Addr::unchecked("addr0000"),
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // only the balance above the previous one is refunded, less the tax of the transfer
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::from(99u128),
+            }],
+        }))]
     );
 }
@@ -0,0 +1,88 @@
+use cosmwasm_std::{Addr, Coin, Decimal, Deps, StdResult, Uint128};
+
+use crate::error::ContractError;
+use crate::state::read_pair_info;
+
+use prismswap::asset::{AssetInfo, PairInfo};
+use prismswap::pair::TwapResponse;
+use prismswap::querier::PrismSwapQuerier;
+use prismswap::router::{SwapOperation, TaxInfo, TwapConfig};
+use terra_cosmwasm::{SwapResponse, TerraQuerier};
+
+pub fn assert_twap_config(twap_config: &TwapConfig) -> Result<(), ContractError> {
+    if twap_config.window == 0 {
+        return Err(ContractError::InvalidTwapWindow {});
+    }
+
+    if twap_config.max_deviation >= Decimal::one() {
+        return Err(ContractError::InvalidTwapMaxDeviation {});
+    }
+
+    Ok(())
+}
+
+/// Returns the output of the operations when every prismswap hop executes at the pair TWAP.
+/// Native swaps are priced by the market module, which already follows the oracle.
+pub fn query_twap_return_amount(
+    deps: Deps,
+    factory: &Addr,
+    twap_config: &TwapConfig,
+    tax_info: &Option<TaxInfo>,
+    offer_amount: Uint128,
+    operations: &[SwapOperation],
+) -> StdResult<Uint128> {
+    let terra_querier = TerraQuerier::new(&deps.querier);
+
+    let mut amount = offer_amount;
+    for operation in operations {
+        match operation {
+            SwapOperation::NativeSwap {
+                offer_denom,
+                ask_denom,
+            } => {
+                let res: SwapResponse = terra_querier.query_swap(
+                    Coin {
+                        denom: offer_denom.to_string(),
+                        amount,
+                    },
+                    ask_denom.to_string(),
+                )?;
+
+                amount = res.receive.amount;
+            }
+            SwapOperation::PrismSwap {
+                offer_asset_info,
+                ask_asset_info,
+                pair,
+                ..
+            } => {
+                let pair_info: PairInfo = read_pair_info(
+                    deps.storage,
+                    &deps.querier,
+                    factory,
+                    &[offer_asset_info.clone(), ask_asset_info.clone()],
+                    pair.as_ref(),
+                )?;
+
+                let res: TwapResponse = PrismSwapQuerier::new(&deps.querier)
+                    .twap(&pair_info.contract_addr, twap_config.window)?;
+
+                if let (AssetInfo::Native(denom), Some(tax_info)) = (offer_asset_info, tax_info) {
+                    amount = tax_info.deduct_tax(&Coin {
+                        denom: denom.to_string(),
+                        amount,
+                    })?;
+                }
+
+                let price = if *offer_asset_info == res.asset_infos[0] {
+                    res.price0
+                } else {
+                    res.price1
+                };
+                amount = amount * price;
+            }
+        }
+    }
+
+    Ok(amount)
+}
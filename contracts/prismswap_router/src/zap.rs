@@ -0,0 +1,397 @@
+use cosmwasm_std::{
+    to_binary, Addr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128, WasmMsg,
+};
+
+use crate::error::ContractError;
+use crate::state::{
+    assert_ask_asset_allowed, assert_hop_allowed, assert_staking_contract, query_registered_pair,
+    Config, CONFIG,
+};
+
+use cw20::Cw20ExecuteMsg;
+use prismswap::asset::{Asset, AssetInfo, PairInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::math::{decimal_atomics, DECIMAL_FRACTIONAL, U256};
+use prismswap::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use prismswap::querier::query_fee_info;
+use prismswap::router::{ExecuteMsg, SwapAmount, SwapOperation, TaxInfo};
+use terra_cosmwasm::TerraMsgWrapper;
+
+/// Swap the optimal portion of the offer asset through the pair, then provide
+/// the remainder together with the swap return as liquidity, bonding the LP tokens
+/// when a staking contract is given
+#[allow(clippy::too_many_arguments)]
+pub fn execute_provide_liquidity_from_single_asset(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    offer_asset: Asset,
+    pair: Addr,
+    min_lp: Option<Uint128>,
+    max_spread: Option<Decimal>,
+    to: Option<Addr>,
+    staking_contract: Option<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if offer_asset.amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pair_info: PairInfo = query_registered_pair(&deps.querier, &config.factory, &pair)?;
+    assert_hop_allowed(deps.storage, &pair_info.asset_infos, Some(&pair))?;
+    let ask_asset_info = if offer_asset.info == pair_info.asset_infos[0] {
+        pair_info.asset_infos[1].clone()
+    } else if offer_asset.info == pair_info.asset_infos[1] {
+        pair_info.asset_infos[0].clone()
+    } else {
+        return Err(ContractError::OfferAssetMismatch {});
+    };
+
+    let lp_token_info = AssetInfo::Cw20(pair_info.liquidity_token.clone());
+    if let Some(staking_contract) = &staking_contract {
+        assert_staking_contract(deps.storage, staking_contract, &lp_token_info)?;
+    }
+
+    let swap_amount =
+        query_zap_swap_amount(deps.as_ref(), &config.factory, &pair_info, &offer_asset)?;
+    // the tax of a native offer is charged on top of the swapped amount
+    let swap_offer_amount = match &offer_asset.info {
+        AssetInfo::Native(denom) => TaxInfo::query(&deps.querier, &[denom.to_string()])?
+            .deduct_tax(&Coin {
+                denom: denom.to_string(),
+                amount: swap_amount,
+            })?,
+        AssetInfo::Cw20(_) => swap_amount,
+    };
+    let receiver = to.unwrap_or(sender);
+    // bonded LP tokens are provided to the router first
+    let lp_receiver = match &staking_contract {
+        Some(_) => env.contract.address.clone(),
+        None => receiver.clone(),
+    };
+
+    let prev_ask_balance = ask_asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let prev_lp_balance = lp_token_info.query_pool(&deps.querier, &lp_receiver)?;
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![
+        Asset {
+            info: offer_asset.info.clone(),
+            amount: swap_offer_amount,
+        }
+        .into_swap_msg(&pair, max_spread, None)?,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ProvideSwappedLiquidity {
+                pair,
+                offer_asset: Asset {
+                    info: offer_asset.info.clone(),
+                    amount: offer_asset.amount.checked_sub(swap_amount)?,
+                },
+                ask_asset_info,
+                prev_ask_balance,
+                receiver: lp_receiver,
+            })?,
+        }),
+    ];
+
+    if staking_contract.is_some() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::DistributeSwapOutput {
+                asset_info: lp_token_info,
+                prev_balance: prev_lp_balance,
+                receiver,
+                referral: None,
+                relayer: None,
+                fee_exempt: true,
+                staking_contract,
+                minimum_receive: min_lp,
+            })?,
+        }));
+    } else if let Some(min_lp) = min_lp {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: lp_token_info,
+                prev_balance: prev_lp_balance,
+                minimum_receive: min_lp,
+                receiver,
+            })?,
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "provide_liquidity_from_single_asset"),
+        ("offer_asset", &offer_asset.to_string()),
+        ("swap_amount", &swap_amount.to_string()),
+    ]))
+}
+
+/// Provide the given offer asset and everything received from the previous swap
+#[allow(clippy::too_many_arguments)]
+pub fn execute_provide_swapped_liquidity(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pair: Addr,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    prev_ask_balance: Uint128,
+    receiver: Addr,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let ask_balance = ask_asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let mut ask_asset = Asset {
+        info: ask_asset_info,
+        amount: ask_balance.checked_sub(prev_ask_balance)?,
+    };
+
+    // the tax of the native assets is charged on top of the provided amounts
+    let mut offer_asset = offer_asset;
+    let denoms: Vec<String> = [&offer_asset.info, &ask_asset.info]
+        .iter()
+        .filter_map(|asset_info| match asset_info {
+            AssetInfo::Native(denom) => Some(denom.to_string()),
+            AssetInfo::Cw20(_) => None,
+        })
+        .collect();
+    if !denoms.is_empty() {
+        let tax_info = TaxInfo::query(&deps.querier, &denoms)?;
+        for asset in [&mut offer_asset, &mut ask_asset] {
+            if let AssetInfo::Native(denom) = &asset.info {
+                asset.amount = tax_info.deduct_tax(&Coin {
+                    denom: denom.to_string(),
+                    amount: asset.amount,
+                })?;
+            }
+        }
+    }
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![];
+    let mut funds: Vec<Coin> = vec![];
+    for asset in [&offer_asset, &ask_asset] {
+        match &asset.info {
+            AssetInfo::Native(denom) => funds.push(Coin {
+                denom: denom.to_string(),
+                amount: asset.amount,
+            }),
+            AssetInfo::Cw20(contract_addr) => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: pair.to_string(),
+                    amount: asset.amount,
+                    expires: None,
+                })?,
+            })),
+        }
+    }
+    funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: pair.to_string(),
+        funds,
+        msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+            assets: [offer_asset, ask_asset],
+            slippage_tolerance: None,
+            receiver: Some(receiver.to_string()),
+            auto_stake: None,
+        })?,
+    }));
+
+    Ok(Response::new().add_messages(messages))
+}
+
+/// Withdraw the LP tokens from the pair, swap the other leg into the ask asset
+/// and send everything to the recipient
+#[allow(clippy::too_many_arguments)]
+pub fn execute_withdraw_liquidity_to_single_asset(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+    pair: Addr,
+    ask_asset_info: AssetInfo,
+    minimum_receive: Option<Uint128>,
+    to: Option<Addr>,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pair_info: PairInfo = query_registered_pair(&deps.querier, &config.factory, &pair)?;
+    assert_hop_allowed(deps.storage, &pair_info.asset_infos, Some(&pair))?;
+    if pair_info.liquidity_token != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    assert_ask_asset_allowed(deps.storage, &ask_asset_info)?;
+
+    let offer_asset_info = if ask_asset_info == pair_info.asset_infos[0] {
+        pair_info.asset_infos[1].clone()
+    } else if ask_asset_info == pair_info.asset_infos[1] {
+        pair_info.asset_infos[0].clone()
+    } else {
+        return Err(ContractError::AskAssetMismatch {});
+    };
+
+    let receiver = to.unwrap_or(sender);
+    let prev_ask_balance = ask_asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let prev_offer_balance = offer_asset_info.query_pool(&deps.querier, &env.contract.address)?;
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: pair_info.liquidity_token.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: pair.to_string(),
+                amount,
+                msg: to_binary(&PairCw20HookMsg::WithdrawLiquidity {})?,
+            })?,
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::ExecuteSwapOperation {
+                operation: SwapOperation::PrismSwap {
+                    offer_asset_info,
+                    ask_asset_info: ask_asset_info.clone(),
+                    pair: None,
+                    max_spread: None,
+                },
+                to: Some(receiver.clone()),
+                amount: Some(SwapAmount::ReceivedSince(prev_offer_balance)),
+                tax_info: None,
+                max_spread: None,
+            })?,
+        }),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::TransferBalance {
+                asset_info: ask_asset_info.clone(),
+                prev_balance: prev_ask_balance,
+                receiver: receiver.clone(),
+            })?,
+        }),
+    ];
+
+    if let Some(minimum_receive) = minimum_receive {
+        let receiver_balance = ask_asset_info.query_pool(&deps.querier, &receiver)?;
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            funds: vec![],
+            msg: to_binary(&ExecuteMsg::AssertMinimumReceive {
+                asset_info: ask_asset_info,
+                prev_balance: receiver_balance,
+                minimum_receive,
+                receiver,
+            })?,
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "withdraw_liquidity_to_single_asset"),
+        ("withdrawn_share", &amount.to_string()),
+    ]))
+}
+
+/// Transfer the balance received since `prev_balance` to the receiver
+pub fn execute_transfer_balance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+    receiver: Addr,
+) -> Result<Response<TerraMsgWrapper>, ContractError> {
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let asset = Asset {
+        info: asset_info,
+        amount: balance.checked_sub(prev_balance)?,
+    };
+
+    // the tax of a native transfer is charged on top of the sent amount
+    let messages: Vec<CosmosMsg<TerraMsgWrapper>> = asset
+        .into_transfer_msg_with_tax(&deps.querier, &receiver)?
+        .into_iter()
+        .collect();
+
+    Ok(Response::new().add_messages(messages))
+}
+
+fn query_zap_swap_amount(
+    deps: Deps,
+    factory: &Addr,
+    pair_info: &PairInfo,
+    offer_asset: &Asset,
+) -> Result<Uint128, ContractError> {
+    let fee_info = query_fee_info(&deps.querier, factory, &pair_info.asset_infos)?;
+
+    let offer_pool = offer_asset
+        .info
+        .query_pool(&deps.querier, &pair_info.contract_addr)?;
+    if offer_pool.is_zero() {
+        return Err(ContractError::EmptyPool {});
+    }
+
+    Ok(compute_zap_swap_amount(
+        offer_pool,
+        offer_asset.amount,
+        fee_info.fee_config.total_fee,
+    )?)
+}
+
+/// Returns the part of `offer_amount` to swap so that the remainder and the swap return
+/// match the pool ratio after the swap.
+///
+/// With the commission taken from the return amount, the swapped amount `s` solves
+/// `s^2 + s * (offer_pool * (2 - fee) - offer_amount * fee) - offer_amount * offer_pool = 0`
+pub fn compute_zap_swap_amount(
+    offer_pool: Uint128,
+    offer_amount: Uint128,
+    commission_rate: Decimal,
+) -> StdResult<Uint128> {
+    // the square of b and the product of the amounts do not fit in 128 bits for large pools
+    let fractional = U256::from(DECIMAL_FRACTIONAL);
+    let commission_rate = U256::from(decimal_atomics(commission_rate));
+    let offer_pool = U256::from(offer_pool.u128());
+    let offer_amount = U256::from(offer_amount.u128());
+
+    let b_pos = offer_pool * U256::from(2u8) - offer_pool * commission_rate / fractional;
+    let b_neg = offer_amount * commission_rate / fractional;
+
+    let b_abs = if b_pos > b_neg {
+        b_pos - b_neg
+    } else {
+        b_neg - b_pos
+    };
+    let discriminant = b_abs
+        .checked_mul(b_abs)
+        .and_then(|b_square| {
+            offer_amount
+                .checked_mul(offer_pool)?
+                .checked_mul(U256::from(4u8))?
+                .checked_add(b_square)
+        })
+        .ok_or_else(|| StdError::generic_err("zap swap amount overflow"))?;
+    let root = discriminant.integer_sqrt();
+
+    let swap_amount = if b_pos > b_neg {
+        root.checked_sub(b_abs)
+            .ok_or_else(|| StdError::generic_err("zap swap amount underflow"))?
+    } else {
+        root + b_abs
+    };
+
+    Ok(Uint128::from(
+        std::cmp::min(swap_amount / U256::from(2u8), offer_amount).as_u128(),
+    ))
+}
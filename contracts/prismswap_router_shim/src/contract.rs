@@ -0,0 +1,183 @@
+use crate::state::{Config, CONFIG};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Deps, DepsMut, Env, MessageInfo,
+    QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use prismswap::router::{
+    ConfigResponse as RouterConfigResponse, Cw20HookMsg as RouterCw20HookMsg,
+    ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg, QueryMsg as RouterQueryMsg,
+    SimulateSwapOperationsResponse as RouterSimulateSwapOperationsResponse, SwapOperation,
+};
+use prismswap::router_shim::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    SimulateSwapOperationsResponse, TerraswapSwapOperation,
+};
+use prismswap::tax::TaxInfo;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        router: deps.api.addr_validate(msg.router.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, info, msg),
+        ExecuteMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive,
+            to,
+        } => {
+            if info.funds.len() != 1 {
+                return Err(StdError::generic_err("must send exactly one native asset"));
+            }
+
+            let config: Config = CONFIG.load(deps.storage)?;
+            let to = to
+                .map(|to| deps.api.addr_validate(&to))
+                .transpose()?
+                .unwrap_or(info.sender);
+
+            // forwarding the funds to the router pays the tax once more
+            let offer_coin = Coin {
+                denom: info.funds[0].denom.clone(),
+                amount: TaxInfo::query(&deps.querier, &[info.funds[0].denom.clone()])?
+                    .deduct_tax(&info.funds[0])?,
+            };
+
+            Ok(Response::new()
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: config.router.to_string(),
+                    funds: vec![offer_coin],
+                    msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(
+                        swap_operations_msg(operations, minimum_receive, to.clone()),
+                    ))?,
+                }))
+                .add_attributes(vec![
+                    ("action", "execute_swap_operations"),
+                    ("to", to.as_str()),
+                ]))
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive,
+            to,
+        } => {
+            let config: Config = CONFIG.load(deps.storage)?;
+            let to = match to {
+                Some(to) => deps.api.addr_validate(&to)?,
+                None => deps.api.addr_validate(&cw20_msg.sender)?,
+            };
+
+            Ok(Response::new()
+                .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: info.sender.to_string(),
+                    funds: vec![],
+                    msg: to_binary(&Cw20ExecuteMsg::Send {
+                        contract: config.router.to_string(),
+                        amount: cw20_msg.amount,
+                        msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(
+                            swap_operations_msg(operations, minimum_receive, to.clone()),
+                        ))?,
+                    })?,
+                }))
+                .add_attributes(vec![
+                    ("action", "execute_swap_operations"),
+                    ("to", to.as_str()),
+                ]))
+        }
+    }
+}
+
+fn swap_operations_msg(
+    operations: Vec<TerraswapSwapOperation>,
+    minimum_receive: Option<Uint128>,
+    to: Addr,
+) -> ExecuteSwapOperationsMsg {
+    ExecuteSwapOperationsMsg {
+        operations: operations.into_iter().map(SwapOperation::from).collect(),
+        minimum_receive,
+        max_spread: None,
+        to: Some(to),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::SimulateSwapOperations {
+            offer_amount,
+            operations,
+        } => to_binary(&simulate_swap_operations(deps, offer_amount, operations)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let router_config: RouterConfigResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.router.to_string(),
+            msg: to_binary(&RouterQueryMsg::Config {})?,
+        }))?;
+
+    Ok(ConfigResponse {
+        terraswap_factory: router_config.factory.to_string(),
+    })
+}
+
+pub fn simulate_swap_operations(
+    deps: Deps,
+    offer_amount: Uint128,
+    operations: Vec<TerraswapSwapOperation>,
+) -> StdResult<SimulateSwapOperationsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let res: RouterSimulateSwapOperationsResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.router.to_string(),
+            msg: to_binary(&RouterQueryMsg::SimulateSwapOperations {
+                offer_amount,
+                operations: operations.into_iter().map(SwapOperation::from).collect(),
+                referral: None,
+                relayed: None,
+            })?,
+        }))?;
+
+    Ok(SimulateSwapOperationsResponse { amount: res.amount })
+}
@@ -0,0 +1,184 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::{mock_dependencies, WasmMockQuerier};
+
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Decimal, OwnedDeps, StdError, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::AssetInfo;
+use prismswap::migrator::LegacyAssetInfo;
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation,
+};
+use prismswap::router_shim::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    SimulateSwapOperationsResponse, TerraswapSwapOperation,
+};
+
+fn setup() -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        InstantiateMsg {
+            router: Addr::unchecked("router0000"),
+        },
+    )
+    .unwrap();
+
+    deps
+}
+
+/// Swaps ukrw into uusd natively, then uusd into token0000 in the Terraswap format
+fn terraswap_operations() -> Vec<TerraswapSwapOperation> {
+    vec![
+        TerraswapSwapOperation::NativeSwap {
+            offer_denom: "ukrw".to_string(),
+            ask_denom: "uusd".to_string(),
+        },
+        TerraswapSwapOperation::TerraSwap {
+            offer_asset_info: LegacyAssetInfo::NativeToken {
+                denom: "uusd".to_string(),
+            },
+            ask_asset_info: LegacyAssetInfo::Token {
+                contract_addr: "token0000".to_string(),
+            },
+        },
+    ]
+}
+
+fn swap_operations_msg(to: &str) -> ExecuteSwapOperationsMsg {
+    ExecuteSwapOperationsMsg {
+        operations: vec![
+            SwapOperation::NativeSwap {
+                offer_denom: "ukrw".to_string(),
+                ask_denom: "uusd".to_string(),
+            },
+            SwapOperation::PrismSwap {
+                offer_asset_info: AssetInfo::Native("uusd".to_string()),
+                ask_asset_info: AssetInfo::Cw20(Addr::unchecked("token0000")),
+                pair: None,
+                max_spread: None,
+            },
+        ],
+        minimum_receive: Some(Uint128::from(900u128)),
+        max_spread: None,
+        to: Some(Addr::unchecked(to)),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    }
+}
+
+#[test]
+fn queries() {
+    let deps = setup();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.terraswap_factory, "factory0000");
+
+    let res: SimulateSwapOperationsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::SimulateSwapOperations {
+                offer_amount: Uint128::from(10000u128),
+                operations: terraswap_operations(),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.amount, Uint128::from(9801u128));
+}
+
+#[test]
+fn execute_native_swap_operations() {
+    let mut deps = setup();
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("ukrw", Uint128::from(1000u128))]);
+
+    let msg = ExecuteMsg::ExecuteSwapOperations {
+        operations: terraswap_operations(),
+        minimum_receive: Some(Uint128::from(900u128)),
+        to: None,
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("must send exactly one native asset")
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1010u128, "ukrw")]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "router0000".to_string(),
+            // 1010 ukrw less the 10 ukrw tax of forwarding them
+            funds: vec![coin(1000u128, "ukrw")],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(
+                swap_operations_msg("addr0000")
+            ))
+            .unwrap(),
+        }))]
+    );
+}
+
+#[test]
+fn execute_cw20_swap_operations() {
+    let mut deps = setup();
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("token0001", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::ExecuteSwapOperations {
+                operations: terraswap_operations(),
+                minimum_receive: Some(Uint128::from(900u128)),
+                to: Some("addr0001".to_string()),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "token0001".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "router0000".to_string(),
+                amount: Uint128::from(1000u128),
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(
+                    swap_operations_msg("addr0001")
+                ))
+                .unwrap(),
+            })
+            .unwrap(),
+        }))]
+    );
+}
@@ -0,0 +1,29 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use prismswap::staking::{
+    AllPendingRewardsResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+    PendingRewardsResponse, PoolResponse, PoolsResponse, QueryMsg, StakerInfoResponse,
+    UnbondingClaimsResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(PoolResponse), &out_dir);
+    export_schema(&schema_for!(PoolsResponse), &out_dir);
+    export_schema(&schema_for!(StakerInfoResponse), &out_dir);
+    export_schema(&schema_for!(UnbondingClaimsResponse), &out_dir);
+    export_schema(&schema_for!(PendingRewardsResponse), &out_dir);
+    export_schema(&schema_for!(AllPendingRewardsResponse), &out_dir);
+}
@@ -0,0 +1,1283 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
+};
+
+use crate::state::{
+    is_withdraw_only, read_all_pools, read_pools, read_staker_pools, Config, PoolInfo, StakerInfo,
+    CONFIG, GUARDIAN, POOLS, STAKERS, UNBONDING_CLAIMS, WITHDRAW_ONLY,
+};
+
+use cosmwasm_bignumber::{Decimal256, Uint256};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::querier::{query_supply, query_token_balance};
+use prismswap::staking::{
+    AllPendingRewardsResponse, CallbackMsg, ConfigResponse, Cw20HookMsg, EarlyUnstakePenalty,
+    EmissionPeriod, ExecuteMsg, InstantiateMsg, PendingRewardsResponse, PoolAllocPoint,
+    PoolResponse, PoolsResponse, ProxyExecuteMsg, QueryMsg, RewardProxy, StakerInfoResponse,
+    UnbondingClaim, UnbondingClaimsResponse,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    if let Some(xprism_token) = &msg.xprism_token {
+        deps.api.addr_validate(xprism_token.as_str())?;
+    }
+    assert_max_boost(msg.max_boost)?;
+    assert_emission_schedule(&msg.emission_schedule)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        prism_token: deps.api.addr_validate(msg.prism_token.as_str())?,
+        tokens_per_block: msg.tokens_per_block,
+        total_alloc_point: 0,
+        start_block: msg.start_block,
+        xprism_token: msg.xprism_token,
+        max_boost: msg.max_boost,
+        emission_schedule: msg.emission_schedule,
+        gauge: None,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            tokens_per_block,
+            xprism_token,
+            max_boost,
+            emission_schedule,
+            gauge,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            owner,
+            tokens_per_block,
+            xprism_token,
+            max_boost,
+            emission_schedule,
+            gauge,
+        ),
+        ExecuteMsg::AddPool {
+            lp_token,
+            alloc_point,
+            reward_proxy,
+            unbonding_period,
+        } => execute_add_pool(
+            deps,
+            env,
+            info,
+            lp_token,
+            alloc_point,
+            reward_proxy,
+            unbonding_period,
+        ),
+        ExecuteMsg::SetPool {
+            lp_token,
+            alloc_point,
+            unbonding_period,
+        } => execute_set_pool(deps, env, info, lp_token, alloc_point, unbonding_period),
+        ExecuteMsg::SetAllocPoints { alloc_points } => {
+            execute_set_alloc_points(deps, env, info, alloc_points)
+        }
+        ExecuteMsg::SetRewardProxy {
+            lp_token,
+            reward_proxy,
+        } => execute_set_reward_proxy(deps, info, lp_token, reward_proxy),
+        ExecuteMsg::SetEarlyUnstakePenalty {
+            lp_token,
+            early_unstake_penalty,
+        } => execute_set_early_unstake_penalty(deps, info, lp_token, early_unstake_penalty),
+        ExecuteMsg::Deposit { lp_token, amount } => {
+            execute_deposit(deps, env, info, lp_token, amount)
+        }
+        ExecuteMsg::Withdraw { lp_token, amount } => {
+            execute_withdraw(deps, env, info, lp_token, amount)
+        }
+        ExecuteMsg::WithdrawUnbonded { lp_token } => {
+            execute_withdraw_unbonded(deps, env, info, lp_token)
+        }
+        ExecuteMsg::Claim { lp_token } => execute_claim(deps, env, info, lp_token),
+        ExecuteMsg::EmergencyWithdraw { lp_token } => {
+            execute_emergency_withdraw(deps, env, info, lp_token)
+        }
+        ExecuteMsg::UpdateGuardian { guardian } => execute_update_guardian(deps, info, guardian),
+        ExecuteMsg::SetWithdrawOnly { withdraw_only } => {
+            execute_set_withdraw_only(deps, info, withdraw_only)
+        }
+        ExecuteMsg::Callback(msg) => {
+            if env.contract.address != info.sender {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            execute_callback(deps, env, msg)
+        }
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_guardian(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardian: Option<Addr>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match guardian {
+        Some(guardian) => {
+            deps.api.addr_validate(guardian.as_str())?;
+            GUARDIAN.save(deps.storage, &guardian)?;
+        }
+        None => GUARDIAN.remove(deps.storage),
+    }
+
+    Ok(Response::new().add_attribute("action", "update_guardian"))
+}
+
+// Only owner or guardian can execute it
+pub fn execute_set_withdraw_only(
+    deps: DepsMut,
+    info: MessageInfo,
+    withdraw_only: bool,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let guardian: Option<Addr> = GUARDIAN.may_load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner && Some(&info.sender) != guardian.as_ref() {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    WITHDRAW_ONLY.save(deps.storage, &withdraw_only)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_withdraw_only"),
+        ("withdraw_only", &withdraw_only.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    tokens_per_block: Option<Uint128>,
+    xprism_token: Option<Addr>,
+    max_boost: Option<Decimal>,
+    emission_schedule: Option<Vec<EmissionPeriod>>,
+    gauge: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    if tokens_per_block.is_some() || emission_schedule.is_some() {
+        // the rewards accrued so far are accounted for at the previous rates
+        update_all_pools(deps.storage, &config, env.block.height)?;
+    }
+
+    if let Some(tokens_per_block) = tokens_per_block {
+        config.tokens_per_block = tokens_per_block;
+    }
+
+    if let Some(emission_schedule) = emission_schedule {
+        assert_emission_schedule(&emission_schedule)?;
+        config.emission_schedule = emission_schedule;
+    }
+
+    if let Some(xprism_token) = xprism_token {
+        deps.api.addr_validate(xprism_token.as_str())?;
+        config.xprism_token = Some(xprism_token);
+    }
+
+    if let Some(max_boost) = max_boost {
+        assert_max_boost(max_boost)?;
+        config.max_boost = max_boost;
+    }
+
+    if let Some(gauge) = gauge {
+        deps.api.addr_validate(gauge.as_str())?;
+        config.gauge = Some(gauge);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_max_boost(max_boost: Decimal) -> StdResult<()> {
+    if max_boost < Decimal::one() {
+        return Err(StdError::generic_err("max boost must be at least 1"));
+    }
+
+    Ok(())
+}
+
+/// The periods must be ordered and must not overlap
+fn assert_emission_schedule(emission_schedule: &[EmissionPeriod]) -> StdResult<()> {
+    let mut prev_end_block = 0u64;
+    for period in emission_schedule {
+        if period.start_block >= period.end_block || period.start_block < prev_end_block {
+            return Err(StdError::generic_err("invalid emission schedule"));
+        }
+        prev_end_block = period.end_block;
+    }
+
+    Ok(())
+}
+
+// Only owner can execute it
+pub fn execute_add_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+    alloc_point: u64,
+    reward_proxy: Option<RewardProxy>,
+    unbonding_period: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    deps.api.addr_validate(lp_token.as_str())?;
+    if let Some(reward_proxy) = &reward_proxy {
+        assert_reward_proxy(deps.as_ref(), reward_proxy)?;
+    }
+    if POOLS.has(deps.storage, &lp_token) {
+        return Err(StdError::generic_err("pool already exists"));
+    }
+
+    update_all_pools(deps.storage, &config, env.block.height)?;
+    POOLS.save(
+        deps.storage,
+        &lp_token,
+        &PoolInfo {
+            alloc_point,
+            total_bond: Uint128::zero(),
+            total_working_amount: Uint128::zero(),
+            reward_index: Decimal::zero(),
+            last_reward_block: std::cmp::max(env.block.height, config.start_block),
+            reward_proxy,
+            proxy_reward_index: Decimal::zero(),
+            unbonding_period: unbonding_period.unwrap_or_default(),
+            early_unstake_penalty: None,
+            pending_penalty: Uint128::zero(),
+        },
+    )?;
+
+    config.total_alloc_point += alloc_point;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_pool"),
+        ("lp_token", lp_token.as_str()),
+        ("alloc_point", &alloc_point.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_set_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+    alloc_point: u64,
+    unbonding_period: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // the rewards accrued so far are accounted for at the previous allocation
+    update_all_pools(deps.storage, &config, env.block.height)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    config.total_alloc_point = config.total_alloc_point - pool.alloc_point + alloc_point;
+    pool.alloc_point = alloc_point;
+    if let Some(unbonding_period) = unbonding_period {
+        pool.unbonding_period = unbonding_period;
+    }
+
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_pool"),
+        ("lp_token", lp_token.as_str()),
+        ("alloc_point", &alloc_point.to_string()),
+    ]))
+}
+
+// Only owner or gauge can execute it
+pub fn execute_set_alloc_points(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    alloc_points: Vec<PoolAllocPoint>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner && Some(&info.sender) != config.gauge.as_ref() {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    for pool_alloc_point in alloc_points.iter() {
+        load_pool(deps.storage, &pool_alloc_point.lp_token)?;
+    }
+
+    // the rewards accrued so far are accounted for at the previous allocations
+    update_all_pools(deps.storage, &config, env.block.height)?;
+    config.total_alloc_point = 0;
+    for (lp_token, mut pool) in read_all_pools(deps.storage)? {
+        pool.alloc_point = alloc_points
+            .iter()
+            .find(|pool_alloc_point| pool_alloc_point.lp_token == lp_token)
+            .map(|pool_alloc_point| pool_alloc_point.alloc_point)
+            .unwrap_or_default();
+        config.total_alloc_point += pool.alloc_point;
+        POOLS.save(deps.storage, &lp_token, &pool)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_alloc_points"),
+        ("total_alloc_point", &config.total_alloc_point.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_set_reward_proxy(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: Addr,
+    reward_proxy: RewardProxy,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // the accrued proxy rewards are only meaningful for the token they were paid in
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    if pool.reward_proxy.is_some() {
+        return Err(StdError::generic_err("pool already has a reward proxy"));
+    }
+
+    assert_reward_proxy(deps.as_ref(), &reward_proxy)?;
+    pool.reward_proxy = Some(reward_proxy.clone());
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_reward_proxy"),
+        ("lp_token", lp_token.as_str()),
+        ("reward_proxy", reward_proxy.contract_addr.as_str()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_set_early_unstake_penalty(
+    deps: DepsMut,
+    info: MessageInfo,
+    lp_token: Addr,
+    early_unstake_penalty: Option<EarlyUnstakePenalty>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(early_unstake_penalty) = &early_unstake_penalty {
+        if early_unstake_penalty.max_penalty > Decimal::one() {
+            return Err(StdError::generic_err("max penalty must not exceed 1"));
+        }
+    }
+
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    pool.early_unstake_penalty = early_unstake_penalty;
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_early_unstake_penalty"),
+        ("lp_token", lp_token.as_str()),
+    ]))
+}
+
+fn assert_reward_proxy(deps: Deps, reward_proxy: &RewardProxy) -> StdResult<()> {
+    deps.api
+        .addr_validate(reward_proxy.contract_addr.as_str())?;
+    deps.api.addr_validate(reward_proxy.reward_token.as_str())?;
+
+    Ok(())
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Bond { staker_addr } => {
+            let staker = match staker_addr {
+                Some(staker_addr) => deps.api.addr_validate(staker_addr.as_str())?,
+                None => deps.api.addr_validate(&cw20_msg.sender)?,
+            };
+
+            execute_bond(deps, env, info.sender, staker, cw20_msg.amount)
+        }
+    }
+}
+
+pub fn execute_deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let transfer_msg = CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: lp_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+            owner: info.sender.to_string(),
+            recipient: env.contract.address.to_string(),
+            amount,
+        })?,
+    });
+
+    let mut res = execute_bond(deps, env, lp_token, info.sender, amount)?;
+    res.messages.insert(0, SubMsg::new(transfer_msg));
+    Ok(res)
+}
+
+/// Bond LP tokens already held by the contract
+fn execute_bond(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    staker: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "deposit amount must be greater than zero",
+        ));
+    }
+
+    if is_withdraw_only(deps.storage)? {
+        return Err(StdError::generic_err("staking is in withdraw-only mode"));
+    }
+
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let action = CallbackMsg::Bond {
+        lp_token: lp_token.clone(),
+        staker,
+        amount,
+    };
+    if let Some(messages) = proxy_claim_msgs(deps.as_ref(), &env, &lp_token, &pool, action.clone())?
+    {
+        return Ok(Response::new().add_messages(messages));
+    }
+
+    execute_callback(deps, env, action)
+}
+
+pub fn execute_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let action = CallbackMsg::Unbond {
+        lp_token: lp_token.clone(),
+        staker: info.sender,
+        amount,
+    };
+    if let Some(messages) = proxy_claim_msgs(deps.as_ref(), &env, &lp_token, &pool, action.clone())?
+    {
+        return Ok(Response::new().add_messages(messages));
+    }
+
+    execute_callback(deps, env, action)
+}
+
+pub fn execute_withdraw_unbonded(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+) -> StdResult<Response> {
+    let claims = UNBONDING_CLAIMS
+        .may_load(deps.storage, (&lp_token, &info.sender))?
+        .unwrap_or_default();
+    let (released, pending): (Vec<UnbondingClaim>, Vec<UnbondingClaim>) = claims
+        .into_iter()
+        .partition(|claim| claim.release_at <= env.block.time.seconds());
+
+    let amount = released
+        .iter()
+        .try_fold(Uint128::zero(), |acc, claim| acc.checked_add(claim.amount))?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("no unbonded LP tokens to withdraw"));
+    }
+
+    if pending.is_empty() {
+        UNBONDING_CLAIMS.remove(deps.storage, (&lp_token, &info.sender));
+    } else {
+        UNBONDING_CLAIMS.save(deps.storage, (&lp_token, &info.sender), &pending)?;
+    }
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&lp_token, &info.sender, amount)?)
+        .add_attributes(vec![
+            ("action", "withdraw_unbonded"),
+            ("lp_token", lp_token.as_str()),
+            ("staker", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+) -> StdResult<Response> {
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let action = CallbackMsg::Claim {
+        lp_token: lp_token.clone(),
+        staker: info.sender,
+    };
+    if let Some(messages) = proxy_claim_msgs(deps.as_ref(), &env, &lp_token, &pool, action.clone())?
+    {
+        return Ok(Response::new().add_messages(messages));
+    }
+
+    execute_callback(deps, env, action)
+}
+
+pub fn execute_emergency_withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    lp_token: Addr,
+) -> StdResult<Response> {
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    let staker = STAKERS
+        .may_load(deps.storage, (&info.sender, &lp_token))?
+        .ok_or_else(|| StdError::generic_err("nothing bonded"))?;
+
+    // neither the emissions nor the proxy are touched, in case they are what is broken
+    pool.total_bond = pool.total_bond.checked_sub(staker.bond_amount)?;
+    pool.total_working_amount = pool
+        .total_working_amount
+        .checked_sub(staker.working_amount)?;
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+    STAKERS.remove(deps.storage, (&info.sender, &lp_token));
+
+    // the LP tokens still go through the unbonding period of the pool
+    Ok(Response::new()
+        .add_messages(release_msg(
+            deps.storage,
+            &env,
+            &lp_token,
+            &pool,
+            &info.sender,
+            staker.bond_amount,
+        )?)
+        .add_attributes(vec![
+            ("action", "emergency_withdraw"),
+            ("lp_token", lp_token.as_str()),
+            ("staker", info.sender.as_str()),
+            ("amount", &staker.bond_amount.to_string()),
+        ]))
+}
+
+/// Returns the messages claiming the rewards of the pool proxy and accruing them, before running
+/// the action so that it pays out the proxy rewards up to now, `None` when the pool has no proxy
+fn proxy_claim_msgs(
+    deps: Deps,
+    env: &Env,
+    lp_token: &Addr,
+    pool: &PoolInfo,
+    action: CallbackMsg,
+) -> StdResult<Option<Vec<CosmosMsg>>> {
+    let reward_proxy = match &pool.reward_proxy {
+        Some(reward_proxy) => reward_proxy,
+        None => return Ok(None),
+    };
+    let prev_balance = query_token_balance(
+        &deps.querier,
+        &reward_proxy.reward_token,
+        &env.contract.address,
+    )?;
+
+    Ok(Some(vec![
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: reward_proxy.contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&ProxyExecuteMsg::Claim {})?,
+        }),
+        callback_msg(
+            env,
+            CallbackMsg::UpdateProxyReward {
+                lp_token: lp_token.clone(),
+                prev_balance,
+            },
+        )?,
+        callback_msg(env, action)?,
+    ]))
+}
+
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+    }))
+}
+
+pub fn execute_callback(deps: DepsMut, env: Env, msg: CallbackMsg) -> StdResult<Response> {
+    match msg {
+        CallbackMsg::UpdateProxyReward {
+            lp_token,
+            prev_balance,
+        } => update_proxy_reward(deps, env, lp_token, prev_balance),
+        CallbackMsg::Bond {
+            lp_token,
+            staker,
+            amount,
+        } => bond(deps, env, lp_token, staker, amount),
+        CallbackMsg::Unbond {
+            lp_token,
+            staker,
+            amount,
+        } => unbond(deps, env, lp_token, staker, amount),
+        CallbackMsg::Claim { lp_token, staker } => claim(deps, env, lp_token, staker),
+    }
+}
+
+/// Accrue the proxy rewards received since `prev_balance` to the bonded LP tokens
+fn update_proxy_reward(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    prev_balance: Uint128,
+) -> StdResult<Response> {
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    let reward_proxy = pool
+        .reward_proxy
+        .clone()
+        .ok_or_else(|| StdError::generic_err("pool has no reward proxy"))?;
+
+    let balance = query_token_balance(
+        &deps.querier,
+        &reward_proxy.reward_token,
+        &env.contract.address,
+    )?;
+    let reward = balance.checked_sub(prev_balance)?;
+    if !reward.is_zero() && !pool.total_bond.is_zero() {
+        pool.proxy_reward_index =
+            pool.proxy_reward_index + reward_per_share(reward, pool.total_bond);
+        POOLS.save(deps.storage, &lp_token, &pool)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_proxy_reward"),
+        ("lp_token", lp_token.as_str()),
+        ("proxy_reward_amount", &reward.to_string()),
+    ]))
+}
+
+fn bond(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    staker_addr: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    update_pool(&config, &mut pool, env.block.height)?;
+
+    let mut staker = STAKERS
+        .may_load(deps.storage, (&staker_addr, &lp_token))?
+        .unwrap_or_default();
+    update_staker(&pool, &mut staker)?;
+    let (reward, proxy_reward) = take_rewards(&mut staker);
+
+    // a top-up moves the deposit time only by its share of the bond, so that it cannot restart
+    // the early unstake penalty of an aged bond
+    staker.last_deposit_time = deposit_time(&staker, env.block.time.seconds(), amount)?;
+    staker.bond_amount = staker.bond_amount.checked_add(amount)?;
+    pool.total_bond = pool.total_bond.checked_add(amount)?;
+    update_working_amount(deps.as_ref(), &config, &mut pool, &staker_addr, &mut staker)?;
+
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+    STAKERS.save(deps.storage, (&staker_addr, &lp_token), &staker)?;
+
+    Ok(Response::new()
+        .add_messages(reward_msgs(
+            &config,
+            &pool,
+            &staker_addr,
+            reward,
+            proxy_reward,
+        )?)
+        .add_attributes(vec![
+            ("action", "deposit"),
+            ("lp_token", lp_token.as_str()),
+            ("staker", staker_addr.as_str()),
+            ("amount", &amount.to_string()),
+            ("reward_amount", &reward.to_string()),
+            ("proxy_reward_amount", &proxy_reward.to_string()),
+        ]))
+}
+
+fn unbond(
+    deps: DepsMut,
+    env: Env,
+    lp_token: Addr,
+    staker_addr: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    update_pool(&config, &mut pool, env.block.height)?;
+
+    let mut staker = STAKERS
+        .may_load(deps.storage, (&staker_addr, &lp_token))?
+        .unwrap_or_default();
+    if amount > staker.bond_amount {
+        return Err(StdError::generic_err("cannot withdraw more than bonded"));
+    }
+    update_staker(&pool, &mut staker)?;
+    let (reward, proxy_reward) = take_rewards(&mut staker);
+    let (reward, penalty) = cut_penalty(&mut pool, &staker, env.block.time.seconds(), reward)?;
+
+    staker.bond_amount = staker.bond_amount.checked_sub(amount)?;
+    pool.total_bond = pool.total_bond.checked_sub(amount)?;
+    update_working_amount(deps.as_ref(), &config, &mut pool, &staker_addr, &mut staker)?;
+
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+    if staker.bond_amount.is_zero() {
+        STAKERS.remove(deps.storage, (&staker_addr, &lp_token));
+    } else {
+        STAKERS.save(deps.storage, (&staker_addr, &lp_token), &staker)?;
+    }
+
+    let mut messages = reward_msgs(&config, &pool, &staker_addr, reward, proxy_reward)?;
+    messages.extend(release_msg(
+        deps.storage,
+        &env,
+        &lp_token,
+        &pool,
+        &staker_addr,
+        amount,
+    )?);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "withdraw"),
+        ("lp_token", lp_token.as_str()),
+        ("staker", staker_addr.as_str()),
+        ("amount", &amount.to_string()),
+        ("reward_amount", &reward.to_string()),
+        ("penalty_amount", &penalty.to_string()),
+        ("proxy_reward_amount", &proxy_reward.to_string()),
+    ]))
+}
+
+/// Returns the message sending the unbonded LP tokens to the staker, `None` when the pool queues
+/// them for its unbonding period instead
+fn release_msg(
+    storage: &mut dyn Storage,
+    env: &Env,
+    lp_token: &Addr,
+    pool: &PoolInfo,
+    staker_addr: &Addr,
+    amount: Uint128,
+) -> StdResult<Option<CosmosMsg>> {
+    if amount.is_zero() {
+        return Ok(None);
+    }
+
+    if pool.unbonding_period == 0 {
+        return Ok(Some(transfer_msg(lp_token, staker_addr, amount)?));
+    }
+
+    let mut claims = UNBONDING_CLAIMS
+        .may_load(storage, (lp_token, staker_addr))?
+        .unwrap_or_default();
+    claims.push(UnbondingClaim {
+        amount,
+        release_at: env.block.time.seconds() + pool.unbonding_period,
+    });
+    UNBONDING_CLAIMS.save(storage, (lp_token, staker_addr), &claims)?;
+
+    Ok(None)
+}
+
+fn claim(deps: DepsMut, env: Env, lp_token: Addr, staker_addr: Addr) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let mut pool = load_pool(deps.storage, &lp_token)?;
+    update_pool(&config, &mut pool, env.block.height)?;
+
+    let mut staker = STAKERS
+        .may_load(deps.storage, (&staker_addr, &lp_token))?
+        .ok_or_else(|| StdError::generic_err("nothing bonded"))?;
+    update_staker(&pool, &mut staker)?;
+    let (reward, proxy_reward) = take_rewards(&mut staker);
+    update_working_amount(deps.as_ref(), &config, &mut pool, &staker_addr, &mut staker)?;
+
+    POOLS.save(deps.storage, &lp_token, &pool)?;
+    STAKERS.save(deps.storage, (&staker_addr, &lp_token), &staker)?;
+
+    Ok(Response::new()
+        .add_messages(reward_msgs(
+            &config,
+            &pool,
+            &staker_addr,
+            reward,
+            proxy_reward,
+        )?)
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("lp_token", lp_token.as_str()),
+            ("staker", staker_addr.as_str()),
+            ("reward_amount", &reward.to_string()),
+            ("proxy_reward_amount", &proxy_reward.to_string()),
+        ]))
+}
+
+fn load_pool(storage: &dyn Storage, lp_token: &Addr) -> StdResult<PoolInfo> {
+    POOLS
+        .may_load(storage, lp_token)?
+        .ok_or_else(|| StdError::generic_err(format!("pool {} does not exist", lp_token)))
+}
+
+/// Accrue the emissions of the pool since its last reward block
+fn update_pool(config: &Config, pool: &mut PoolInfo, height: u64) -> StdResult<()> {
+    if height <= pool.last_reward_block {
+        return Ok(());
+    }
+
+    if !pool.total_working_amount.is_zero() {
+        let mut reward = std::mem::take(&mut pool.pending_penalty);
+        if config.total_alloc_point != 0 {
+            reward = reward.checked_add(
+                emissions(config, pool.last_reward_block, height)?
+                    .multiply_ratio(pool.alloc_point, config.total_alloc_point),
+            )?;
+        }
+        pool.reward_index = pool.reward_index + reward_per_share(reward, pool.total_working_amount);
+    }
+    pool.last_reward_block = height;
+
+    Ok(())
+}
+
+/// Returns the PRISM emitted from `from_block` included to `to_block` excluded, at the rate of the
+/// schedule periods covering the blocks, `tokens_per_block` otherwise
+fn emissions(config: &Config, from_block: u64, to_block: u64) -> StdResult<Uint128> {
+    let from_block = std::cmp::max(from_block, config.start_block);
+    if to_block <= from_block {
+        return Ok(Uint128::zero());
+    }
+
+    let mut emissions = Uint128::zero();
+    let mut scheduled_blocks = 0u64;
+    for period in config.emission_schedule.iter() {
+        let start_block = std::cmp::max(period.start_block, from_block);
+        let end_block = std::cmp::min(period.end_block, to_block);
+        if end_block > start_block {
+            emissions = emissions.checked_add(
+                period
+                    .tokens_per_block
+                    .checked_mul(Uint128::from(end_block - start_block))?,
+            )?;
+            scheduled_blocks += end_block - start_block;
+        }
+    }
+
+    Ok(emissions.checked_add(
+        config
+            .tokens_per_block
+            .checked_mul(Uint128::from(to_block - from_block - scheduled_blocks))?,
+    )?)
+}
+
+/// Accrue the emissions of every pool, before the emission rate or the allocations change
+fn update_all_pools(storage: &mut dyn Storage, config: &Config, height: u64) -> StdResult<()> {
+    for (lp_token, mut pool) in read_all_pools(storage)? {
+        update_pool(config, &mut pool, height)?;
+        POOLS.save(storage, &lp_token, &pool)?;
+    }
+
+    Ok(())
+}
+
+/// Accrue the rewards of the staker since its index snapshots, and move the snapshots to the
+/// current indexes of the pool
+fn update_staker(pool: &PoolInfo, staker: &mut StakerInfo) -> StdResult<()> {
+    staker.pending_reward = staker.pending_reward.checked_add(accrued_reward(
+        staker.working_amount,
+        pool.reward_index,
+        staker.reward_index,
+    ))?;
+    staker.reward_index = pool.reward_index;
+
+    staker.pending_proxy_reward = staker.pending_proxy_reward.checked_add(accrued_reward(
+        staker.bond_amount,
+        pool.proxy_reward_index,
+        staker.proxy_reward_index,
+    ))?;
+    staker.proxy_reward_index = pool.proxy_reward_index;
+
+    Ok(())
+}
+
+fn accrued_reward(amount: Uint128, index: Decimal, snapshot: Decimal) -> Uint128 {
+    (Uint256::from(amount) * (Decimal256::from(index) - Decimal256::from(snapshot))).into()
+}
+
+/// Returns the deposit time of the bond of the staker topped up with `amount` at `time`, the
+/// deposit times averaged weighted by their amounts
+fn deposit_time(staker: &StakerInfo, time: u64, amount: Uint128) -> StdResult<u64> {
+    let total_amount = staker.bond_amount.checked_add(amount)?;
+    if total_amount.is_zero() {
+        return Ok(time);
+    }
+
+    let elapsed = time.saturating_sub(staker.last_deposit_time);
+    Ok(time
+        - Uint128::from(elapsed)
+            .multiply_ratio(staker.bond_amount, total_amount)
+            .u128() as u64)
+}
+
+/// Returns the PRISM reward a withdrawal leaves to the staker after the early unstake penalty of
+/// the pool and the penalty, queued for the other stakers
+fn cut_penalty(
+    pool: &mut PoolInfo,
+    staker: &StakerInfo,
+    time: u64,
+    reward: Uint128,
+) -> StdResult<(Uint128, Uint128)> {
+    let elapsed = time.saturating_sub(staker.last_deposit_time);
+    let penalty = match &pool.early_unstake_penalty {
+        Some(early_unstake_penalty) if elapsed < early_unstake_penalty.period => {
+            reward.multiply_ratio(
+                early_unstake_penalty.period - elapsed,
+                early_unstake_penalty.period,
+            ) * early_unstake_penalty.max_penalty
+        }
+        _ => Uint128::zero(),
+    };
+    pool.pending_penalty = pool.pending_penalty.checked_add(penalty)?;
+
+    Ok((reward.checked_sub(penalty)?, penalty))
+}
+
+/// Snapshot the working amount of the staker for its current bond and xPRISM holdings.
+///
+/// Without xPRISM the bond works at `1 / max_boost`, and up to the whole bond as the xPRISM share
+/// of the staker grows to its share of the pool:
+/// `min(bond / max_boost + total_bond * xprism / xprism_supply * (1 - 1 / max_boost), bond)`
+fn update_working_amount(
+    deps: Deps,
+    config: &Config,
+    pool: &mut PoolInfo,
+    staker_addr: &Addr,
+    staker: &mut StakerInfo,
+) -> StdResult<()> {
+    let working_amount = match &config.xprism_token {
+        Some(xprism_token) if config.max_boost > Decimal::one() => {
+            let xprism_supply = query_supply(&deps.querier, xprism_token)?;
+            let xprism_share = if xprism_supply.is_zero() {
+                Decimal256::zero()
+            } else {
+                let xprism_balance = query_token_balance(&deps.querier, xprism_token, staker_addr)?;
+                Decimal256::from_ratio(Uint256::from(xprism_balance), Uint256::from(xprism_supply))
+            };
+
+            let base_ratio = Decimal256::one() / Decimal256::from(config.max_boost);
+            let working_amount: Uint128 = (Uint256::from(staker.bond_amount) * base_ratio
+                + Uint256::from(pool.total_bond) * xprism_share * (Decimal256::one() - base_ratio))
+                .into();
+            std::cmp::min(working_amount, staker.bond_amount)
+        }
+        _ => staker.bond_amount,
+    };
+
+    pool.total_working_amount = pool
+        .total_working_amount
+        .checked_sub(staker.working_amount)?
+        .checked_add(working_amount)?;
+    staker.working_amount = working_amount;
+
+    Ok(())
+}
+
+/// Returns the PRISM and proxy rewards pending for the staker, clearing them
+fn take_rewards(staker: &mut StakerInfo) -> (Uint128, Uint128) {
+    (
+        std::mem::take(&mut staker.pending_reward),
+        std::mem::take(&mut staker.pending_proxy_reward),
+    )
+}
+
+fn reward_per_share(reward: Uint128, total_bond: Uint128) -> Decimal {
+    Decimal256::from_ratio(Uint256::from(reward), Uint256::from(total_bond)).into()
+}
+
+fn reward_msgs(
+    config: &Config,
+    pool: &PoolInfo,
+    recipient: &Addr,
+    reward: Uint128,
+    proxy_reward: Uint128,
+) -> StdResult<Vec<CosmosMsg>> {
+    let mut messages: Vec<CosmosMsg> = vec![];
+    if !reward.is_zero() {
+        messages.push(transfer_msg(&config.prism_token, recipient, reward)?);
+    }
+    if let (Some(reward_proxy), false) = (&pool.reward_proxy, proxy_reward.is_zero()) {
+        messages.push(transfer_msg(
+            &reward_proxy.reward_token,
+            recipient,
+            proxy_reward,
+        )?);
+    }
+
+    Ok(messages)
+}
+
+fn transfer_msg(token: &Addr, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+    }))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Pool { lp_token } => to_binary(&query_pool(deps, env, lp_token)?),
+        QueryMsg::Pools { start_after, limit } => {
+            to_binary(&query_pools(deps, env, start_after, limit)?)
+        }
+        QueryMsg::StakerInfo { lp_token, staker } => {
+            to_binary(&query_staker_info(deps, lp_token, staker)?)
+        }
+        QueryMsg::UnbondingClaims { lp_token, staker } => {
+            to_binary(&query_unbonding_claims(deps, lp_token, staker)?)
+        }
+        QueryMsg::PendingRewards { user, lp_token } => {
+            to_binary(&query_pending_rewards(deps, env, user, lp_token)?)
+        }
+        QueryMsg::AllPendingRewards {
+            user,
+            start_after,
+            limit,
+        } => to_binary(&query_all_pending_rewards(
+            deps,
+            env,
+            user,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        prism_token: config.prism_token,
+        tokens_per_block: config.tokens_per_block,
+        total_alloc_point: config.total_alloc_point,
+        start_block: config.start_block,
+        xprism_token: config.xprism_token,
+        max_boost: config.max_boost,
+        emission_schedule: config.emission_schedule,
+        gauge: config.gauge,
+        guardian: GUARDIAN.may_load(deps.storage)?,
+        withdraw_only: is_withdraw_only(deps.storage)?,
+    })
+}
+
+fn pool_response(
+    config: &Config,
+    lp_token: Addr,
+    mut pool: PoolInfo,
+    height: u64,
+) -> StdResult<PoolResponse> {
+    update_pool(config, &mut pool, height)?;
+
+    Ok(PoolResponse {
+        lp_token,
+        alloc_point: pool.alloc_point,
+        total_bond: pool.total_bond,
+        total_working_amount: pool.total_working_amount,
+        reward_index: pool.reward_index,
+        last_reward_block: pool.last_reward_block,
+        reward_proxy: pool.reward_proxy,
+        proxy_reward_index: pool.proxy_reward_index,
+        unbonding_period: pool.unbonding_period,
+        early_unstake_penalty: pool.early_unstake_penalty,
+        pending_penalty: pool.pending_penalty,
+    })
+}
+
+pub fn query_pool(deps: Deps, env: Env, lp_token: Addr) -> StdResult<PoolResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool = load_pool(deps.storage, &lp_token)?;
+
+    pool_response(&config, lp_token, pool, env.block.height)
+}
+
+pub fn query_pools(
+    deps: Deps,
+    env: Env,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<PoolsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pools = read_pools(deps.storage, start_after, limit)?
+        .into_iter()
+        .map(|(lp_token, pool)| pool_response(&config, lp_token, pool, env.block.height))
+        .collect::<StdResult<Vec<PoolResponse>>>()?;
+
+    Ok(PoolsResponse { pools })
+}
+
+pub fn query_staker_info(
+    deps: Deps,
+    lp_token: Addr,
+    staker: Addr,
+) -> StdResult<StakerInfoResponse> {
+    let staker_info = STAKERS
+        .may_load(deps.storage, (&staker, &lp_token))?
+        .unwrap_or_default();
+
+    Ok(StakerInfoResponse {
+        staker,
+        lp_token,
+        bond_amount: staker_info.bond_amount,
+        working_amount: staker_info.working_amount,
+    })
+}
+
+pub fn query_unbonding_claims(
+    deps: Deps,
+    lp_token: Addr,
+    staker: Addr,
+) -> StdResult<UnbondingClaimsResponse> {
+    let claims = UNBONDING_CLAIMS
+        .may_load(deps.storage, (&lp_token, &staker))?
+        .unwrap_or_default();
+
+    Ok(UnbondingClaimsResponse { claims })
+}
+
+fn pending_rewards_response(
+    config: &Config,
+    lp_token: Addr,
+    mut pool: PoolInfo,
+    mut staker: StakerInfo,
+    height: u64,
+) -> StdResult<PendingRewardsResponse> {
+    update_pool(config, &mut pool, height)?;
+    update_staker(&pool, &mut staker)?;
+
+    Ok(PendingRewardsResponse {
+        lp_token,
+        pending_reward: staker.pending_reward,
+        proxy_reward_token: pool
+            .reward_proxy
+            .map(|reward_proxy| reward_proxy.reward_token),
+        pending_proxy_reward: staker.pending_proxy_reward,
+    })
+}
+
+pub fn query_pending_rewards(
+    deps: Deps,
+    env: Env,
+    user: Addr,
+    lp_token: Addr,
+) -> StdResult<PendingRewardsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pool = load_pool(deps.storage, &lp_token)?;
+    let staker = STAKERS
+        .may_load(deps.storage, (&user, &lp_token))?
+        .unwrap_or_default();
+
+    pending_rewards_response(&config, lp_token, pool, staker, env.block.height)
+}
+
+pub fn query_all_pending_rewards(
+    deps: Deps,
+    env: Env,
+    user: Addr,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<AllPendingRewardsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let pending_rewards = read_staker_pools(deps.storage, &user, start_after, limit)?
+        .into_iter()
+        .map(|(lp_token, staker)| {
+            let pool = load_pool(deps.storage, &lp_token)?;
+            pending_rewards_response(&config, lp_token, pool, staker, env.block.height)
+        })
+        .collect::<StdResult<Vec<PendingRewardsResponse>>>()?;
+
+    Ok(AllPendingRewardsResponse { pending_rewards })
+}
@@ -0,0 +1,115 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map};
+use prismswap::pagination::{calc_limit, start_after_bound};
+use prismswap::staking::{EarlyUnstakePenalty, EmissionPeriod, RewardProxy, UnbondingClaim};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub prism_token: Addr,
+    pub tokens_per_block: Uint128,
+    pub total_alloc_point: u64,
+    pub start_block: u64,
+    pub xprism_token: Option<Addr>,
+    pub max_boost: Decimal,
+    pub emission_schedule: Vec<EmissionPeriod>,
+    pub gauge: Option<Addr>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+/// whether deposits were halted by the owner or the guardian
+pub const WITHDRAW_ONLY: Item<bool> = Item::new("withdraw_only");
+
+pub fn is_withdraw_only(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(WITHDRAW_ONLY.may_load(storage)?.unwrap_or(false))
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolInfo {
+    pub alloc_point: u64,
+    pub total_bond: Uint128,
+    pub total_working_amount: Uint128,
+    pub reward_index: Decimal,
+    pub last_reward_block: u64,
+    pub reward_proxy: Option<RewardProxy>,
+    pub proxy_reward_index: Decimal,
+    pub unbonding_period: u64,
+    pub early_unstake_penalty: Option<EarlyUnstakePenalty>,
+    /// the penalties to accrue to the stakers with the next emissions
+    pub pending_penalty: Uint128,
+}
+
+pub const POOLS: Map<&Addr, PoolInfo> = Map::new("pools");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct StakerInfo {
+    pub bond_amount: Uint128,
+    /// the bond boosted by the xPRISM of the staker, accruing the emissions
+    pub working_amount: Uint128,
+    /// the `reward_index` of the pool the pending reward was last accrued at
+    pub reward_index: Decimal,
+    /// the rewards accrued and not claimed yet
+    pub pending_reward: Uint128,
+    /// the same for the proxy rewards, accrued by the bond itself
+    pub proxy_reward_index: Decimal,
+    pub pending_proxy_reward: Uint128,
+    /// the block time in seconds of the deposits averaged by amount, the early unstake penalty
+    /// decays from
+    pub last_deposit_time: u64,
+}
+
+/// the stakers of the pools, keyed by staker then LP token to list the pools of a staker
+pub const STAKERS: Map<(&Addr, &Addr), StakerInfo> = Map::new("stakers");
+
+/// the withdrawals of a staker from a pool waiting for their unbonding period, oldest first
+pub const UNBONDING_CLAIMS: Map<(&Addr, &Addr), Vec<UnbondingClaim>> = Map::new("unbonding_claims");
+
+pub fn read_all_pools(storage: &dyn Storage) -> StdResult<Vec<(Addr, PoolInfo)>> {
+    POOLS
+        .range(storage, None, None, Order::Ascending)
+        .map(pool_item)
+        .collect()
+}
+
+fn pool_item(item: StdResult<(Vec<u8>, PoolInfo)>) -> StdResult<(Addr, PoolInfo)> {
+    let (k, v) = item?;
+    Ok((Addr::unchecked(String::from_utf8(k)?), v))
+}
+
+pub fn read_pools(
+    storage: &dyn Storage,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, PoolInfo)>> {
+    let start = start_after_bound(start_after.map(|lp_token| lp_token.as_bytes().to_vec()));
+
+    POOLS
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(pool_item)
+        .collect()
+}
+
+pub fn read_staker_pools(
+    storage: &dyn Storage,
+    staker: &Addr,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(Addr, StakerInfo)>> {
+    let start = start_after_bound(start_after.map(|lp_token| lp_token.as_bytes().to_vec()));
+
+    STAKERS
+        .prefix(staker)
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, v) = item?;
+            Ok((Addr::unchecked(String::from_utf8(k)?), v))
+        })
+        .collect()
+}
@@ -0,0 +1,1469 @@
+use std::str::FromStr;
+
+use crate::contract::{execute, instantiate, query};
+
+use crate::mock_querier::mock_dependencies;
+use crate::state::STAKERS;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Env, StdError, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::staking::{
+    AllPendingRewardsResponse, CallbackMsg, ConfigResponse, Cw20HookMsg, EarlyUnstakePenalty,
+    EmissionPeriod, ExecuteMsg, InstantiateMsg, PendingRewardsResponse, PoolAllocPoint,
+    PoolResponse, PoolsResponse, ProxyExecuteMsg, QueryMsg, RewardProxy, StakerInfoResponse,
+    UnbondingClaim, UnbondingClaimsResponse,
+};
+
+fn mock_env_at(height: u64) -> Env {
+    let mut env = mock_env();
+    env.block.height = height;
+    env
+}
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        prism_token: Addr::unchecked("prism0000"),
+        tokens_per_block: Uint128::from(100u128),
+        start_block: 12355,
+        xprism_token: None,
+        max_boost: Decimal::one(),
+        emission_schedule: vec![],
+    }
+}
+
+fn transfer_msg(token: &str, recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            prism_token: Addr::unchecked("prism0000"),
+            tokens_per_block: Uint128::from(100u128),
+            total_alloc_point: 0,
+            start_block: 12355,
+            xprism_token: None,
+            max_boost: Decimal::one(),
+            emission_schedule: vec![],
+            gauge: None,
+            guardian: None,
+            withdraw_only: false,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        tokens_per_block: Some(Uint128::from(200u128)),
+        xprism_token: Some(Addr::unchecked("xprism0000")),
+        max_boost: Some(Decimal::from_str("2.5").unwrap()),
+        emission_schedule: None,
+        gauge: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.tokens_per_block, Uint128::from(200u128));
+    assert_eq!(config.xprism_token, Some(Addr::unchecked("xprism0000")));
+    assert_eq!(config.max_boost, Decimal::from_str("2.5").unwrap());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        tokens_per_block: None,
+        xprism_token: None,
+        max_boost: Some(Decimal::from_str("0.5").unwrap()),
+        emission_schedule: None,
+        gauge: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0001", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("max boost must be at least 1"));
+}
+
+#[test]
+fn emission_schedule() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        tokens_per_block: None,
+        xprism_token: None,
+        max_boost: None,
+        emission_schedule: Some(vec![
+            EmissionPeriod {
+                start_block: 12360,
+                end_block: 12370,
+                tokens_per_block: Uint128::from(50u128),
+            },
+            EmissionPeriod {
+                start_block: 12365,
+                end_block: 12375,
+                tokens_per_block: Uint128::from(25u128),
+            },
+        ]),
+        gauge: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("invalid emission schedule"));
+
+    // the rate halves at 12360 and again at 12370, then emissions stop
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        tokens_per_block: Some(Uint128::zero()),
+        xprism_token: None,
+        max_boost: None,
+        emission_schedule: Some(vec![
+            EmissionPeriod {
+                start_block: 12355,
+                end_block: 12360,
+                tokens_per_block: Uint128::from(100u128),
+            },
+            EmissionPeriod {
+                start_block: 12360,
+                end_block: 12370,
+                tokens_per_block: Uint128::from(50u128),
+            },
+            EmissionPeriod {
+                start_block: 12370,
+                end_block: 12380,
+                tokens_per_block: Uint128::from(25u128),
+            },
+        ]),
+        gauge: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // 5 * 100 + 10 * 50 + 5 * 25
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12375),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![transfer_msg("prism0000", "addr0000", 1125)]
+    );
+
+    // 5 * 25, nothing past the schedule
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12400),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![transfer_msg("prism0000", "addr0000", 125)]
+    );
+}
+
+#[test]
+fn add_and_set_pool() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("pool already exists"));
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0001"),
+        alloc_point: 3,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetPool {
+        lp_token: Addr::unchecked("lp0002"),
+        alloc_point: 2,
+        unbonding_period: None,
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("pool lp0002 does not exist"));
+
+    let msg = ExecuteMsg::SetPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 2,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.total_alloc_point, 5);
+
+    // emissions of new pools only start at the start block
+    let res: PoolsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pools {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.pools,
+        vec![
+            PoolResponse {
+                lp_token: Addr::unchecked("lp0000"),
+                alloc_point: 2,
+                total_bond: Uint128::zero(),
+                total_working_amount: Uint128::zero(),
+                reward_index: Decimal::zero(),
+                last_reward_block: 12355,
+                reward_proxy: None,
+                proxy_reward_index: Decimal::zero(),
+                unbonding_period: 0,
+                early_unstake_penalty: None,
+                pending_penalty: Uint128::zero(),
+            },
+            PoolResponse {
+                lp_token: Addr::unchecked("lp0001"),
+                alloc_point: 3,
+                total_bond: Uint128::zero(),
+                total_working_amount: Uint128::zero(),
+                reward_index: Decimal::zero(),
+                last_reward_block: 12355,
+                reward_proxy: None,
+                proxy_reward_index: Decimal::zero(),
+                unbonding_period: 0,
+                early_unstake_penalty: None,
+                pending_penalty: Uint128::zero(),
+            },
+        ]
+    );
+
+    let res: PoolsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pools {
+                start_after: Some(Addr::unchecked("lp0000")),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.pools.len(), 1);
+    assert_eq!(res.pools[0].lp_token, Addr::unchecked("lp0001"));
+}
+
+#[test]
+fn set_alloc_points() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    for (lp_token, alloc_point) in [("lp0000", 1), ("lp0001", 3)] {
+        let msg = ExecuteMsg::AddPool {
+            lp_token: Addr::unchecked(lp_token),
+            alloc_point,
+            reward_proxy: None,
+            unbonding_period: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    }
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: None,
+        tokens_per_block: None,
+        xprism_token: None,
+        max_boost: None,
+        emission_schedule: None,
+        gauge: Some(Addr::unchecked("gauge0000")),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetAllocPoints {
+        alloc_points: vec![PoolAllocPoint {
+            lp_token: Addr::unchecked("lp0001"),
+            alloc_point: 5,
+        }],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("gauge0000", &[]),
+        ExecuteMsg::SetAllocPoints {
+            alloc_points: vec![PoolAllocPoint {
+                lp_token: Addr::unchecked("lp0002"),
+                alloc_point: 5,
+            }],
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("pool lp0002 does not exist"));
+
+    // the pools left out lose their allocation
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("gauge0000", &[]), msg).unwrap();
+    let res: PoolsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Pools {
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.pools
+            .iter()
+            .map(|pool| pool.alloc_point)
+            .collect::<Vec<u64>>(),
+        vec![0, 5]
+    );
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.total_alloc_point, 5);
+}
+
+#[test]
+fn deposit_withdraw_and_claim() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    for (lp_token, alloc_point) in [("lp0000", 1), ("lp0001", 3)] {
+        let msg = ExecuteMsg::AddPool {
+            lp_token: Addr::unchecked(lp_token),
+            alloc_point,
+            reward_proxy: None,
+            unbonding_period: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    }
+
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0002"),
+        amount: Uint128::from(100u128),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("pool lp0002 does not exist"));
+
+    // the deposit is pulled from the sender
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "lp0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: "addr0000".to_string(),
+                recipient: MOCK_CONTRACT_ADDR.to_string(),
+                amount: Uint128::from(100u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // 10 blocks after the start, a quarter of the emissions went to the only staker
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(300u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12365),
+        mock_info("addr0001", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 1);
+
+    let pool: PoolResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::Pool {
+                lp_token: Addr::unchecked("lp0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pool.total_bond, Uint128::from(400u128));
+    assert_eq!(pool.reward_index, Decimal::from_str("2.5").unwrap());
+
+    // the new staker only snapshots the index, it accrues from there
+    let staker = STAKERS
+        .load(
+            &deps.storage,
+            (&Addr::unchecked("addr0001"), &Addr::unchecked("lp0000")),
+        )
+        .unwrap();
+    assert_eq!(staker.reward_index, Decimal::from_str("2.5").unwrap());
+    assert_eq!(staker.pending_reward, Uint128::zero());
+
+    // the next 250 are shared by both stakers
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12375),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![transfer_msg("prism0000", "addr0000", 312)]
+    );
+
+    // claiming again in the same block pays nothing
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12375),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    let msg = ExecuteMsg::Withdraw {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(301u128),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at(12375),
+        mock_info("addr0001", &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("cannot withdraw more than bonded")
+    );
+
+    // withdrawing claims the pending rewards
+    let msg = ExecuteMsg::Withdraw {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(300u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12375),
+        mock_info("addr0001", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer_msg("prism0000", "addr0001", 187),
+            transfer_msg("lp0000", "addr0001", 300),
+        ]
+    );
+
+    let staker_info: StakerInfoResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12375),
+            QueryMsg::StakerInfo {
+                lp_token: Addr::unchecked("lp0000"),
+                staker: Addr::unchecked("addr0001"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(staker_info.bond_amount, Uint128::zero());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at(12375),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("nothing bonded"));
+}
+
+#[test]
+fn xprism_boost() {
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[(
+        &"xprism0000".to_string(),
+        &[(&"addr0000".to_string(), &Uint128::from(1000u128))],
+    )]);
+    deps.querier
+        .with_token_supplies(&[(&"xprism0000".to_string(), &Uint128::from(1000u128))]);
+
+    let msg = InstantiateMsg {
+        xprism_token: Some(Addr::unchecked("xprism0000")),
+        max_boost: Decimal::from_str("2.5").unwrap(),
+        ..instantiate_msg()
+    };
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    for staker in ["addr0000", "addr0001"] {
+        let msg = ExecuteMsg::Deposit {
+            lp_token: Addr::unchecked("lp0000"),
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info(staker, &[]), msg).unwrap();
+    }
+
+    // the xPRISM holder works its whole bond, the other one 1 / 2.5 of it
+    for (staker, working_amount) in [("addr0000", 100u128), ("addr0001", 40u128)] {
+        let staker_info: StakerInfoResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    lp_token: Addr::unchecked("lp0000"),
+                    staker: Addr::unchecked(staker),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(staker_info.working_amount, Uint128::from(working_amount));
+    }
+
+    // 1000 PRISM are emitted to 140 working LP tokens
+    for (staker, reward) in [("addr0000", 714), ("addr0001", 285)] {
+        let res = execute(
+            deps.as_mut(),
+            mock_env_at(12365),
+            mock_info(staker, &[]),
+            ExecuteMsg::Claim {
+                lp_token: Addr::unchecked("lp0000"),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages,
+            vec![transfer_msg("prism0000", staker, reward)]
+        );
+    }
+
+    let pool: PoolResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::Pool {
+                lp_token: Addr::unchecked("lp0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pool.total_working_amount, Uint128::from(140u128));
+}
+
+#[test]
+fn bond_with_cw20_hook() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: Binary::from(br#"{"bond":{}}"#),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lp0002", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("pool lp0002 does not exist"));
+
+    // the LP tokens are already held, nothing is pulled from the sender
+    let res = execute(deps.as_mut(), mock_env(), mock_info("lp0000", &[]), msg).unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "router0000".to_string(),
+        amount: Uint128::from(50u128),
+        msg: to_binary(&Cw20HookMsg::Bond {
+            staker_addr: Some(Addr::unchecked("addr0001")),
+        })
+        .unwrap(),
+    });
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("lp0000", &[]), msg).unwrap();
+
+    for (staker, bond_amount) in [("addr0000", 100u128), ("addr0001", 50u128)] {
+        let staker_info: StakerInfoResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::StakerInfo {
+                    lp_token: Addr::unchecked("lp0000"),
+                    staker: Addr::unchecked(staker),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(staker_info.bond_amount, Uint128::from(bond_amount));
+    }
+}
+
+#[test]
+fn withdraw_only() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let bond_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+        msg: to_binary(&Cw20HookMsg::Bond { staker_addr: None }).unwrap(),
+    });
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lp0000", &[]),
+        bond_msg.clone(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateGuardian {
+        guardian: Some(Addr::unchecked("guardian0000")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetWithdrawOnly {
+        withdraw_only: true,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        msg,
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.guardian, Some(Addr::unchecked("guardian0000")));
+    assert!(config.withdraw_only);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lp0000", &[]),
+        bond_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("staking is in withdraw-only mode")
+    );
+
+    // the bonded LP tokens can still be withdrawn
+    let msg = ExecuteMsg::Withdraw {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("guardian0000", &[]),
+        ExecuteMsg::SetWithdrawOnly {
+            withdraw_only: false,
+        },
+    )
+    .unwrap();
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lp0000", &[]),
+        bond_msg,
+    )
+    .unwrap();
+}
+
+#[test]
+fn early_unstake_penalty() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetEarlyUnstakePenalty {
+        lp_token: Addr::unchecked("lp0000"),
+        early_unstake_penalty: Some(EarlyUnstakePenalty {
+            period: 1000,
+            max_penalty: Decimal::from_str("1.5").unwrap(),
+        }),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("max penalty must not exceed 1"));
+
+    let msg = ExecuteMsg::SetEarlyUnstakePenalty {
+        lp_token: Addr::unchecked("lp0000"),
+        early_unstake_penalty: Some(EarlyUnstakePenalty {
+            period: 1000,
+            max_penalty: Decimal::from_str("0.5").unwrap(),
+        }),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    for staker in ["addr0000", "addr0001"] {
+        let msg = ExecuteMsg::Deposit {
+            lp_token: Addr::unchecked("lp0000"),
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info(staker, &[]), msg).unwrap();
+    }
+
+    // halfway through the period, a quarter of the 500 PRISM earned is cut
+    let mut env = mock_env_at(12365);
+    env.block.time = env.block.time.plus_seconds(500);
+    let msg = ExecuteMsg::Withdraw {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer_msg("prism0000", "addr0000", 375),
+            transfer_msg("lp0000", "addr0000", 100),
+        ]
+    );
+
+    // the penalty goes to the remaining staker with the next emissions
+    let mut env = mock_env_at(12366);
+    env.block.time = env.block.time.plus_seconds(1000);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0001", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![transfer_msg("prism0000", "addr0001", 725)]
+    );
+}
+
+#[test]
+fn emergency_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: Some(100),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // the LP tokens are queued for the unbonding period without the pending rewards
+    let msg = ExecuteMsg::EmergencyWithdraw {
+        lp_token: Addr::unchecked("lp0000"),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12365),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+
+    let res: UnbondingClaimsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UnbondingClaims {
+                lp_token: Addr::unchecked("lp0000"),
+                staker: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.claims,
+        vec![UnbondingClaim {
+            amount: Uint128::from(100u128),
+            release_at: mock_env_at(12365).block.time.seconds() + 100,
+        }]
+    );
+
+    let pool: PoolResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::Pool {
+                lp_token: Addr::unchecked("lp0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(pool.total_bond, Uint128::zero());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_at(12365),
+        mock_info("addr0000", &[]),
+        msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("nothing bonded"));
+
+    let mut env = mock_env_at(12365);
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::WithdrawUnbonded {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("lp0000", "addr0000", 100)]);
+}
+
+#[test]
+fn pending_rewards() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    for (lp_token, alloc_point) in [("lp0000", 1), ("lp0001", 3), ("lp0002", 1)] {
+        let msg = ExecuteMsg::AddPool {
+            lp_token: Addr::unchecked(lp_token),
+            alloc_point,
+            reward_proxy: None,
+            unbonding_period: None,
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    }
+
+    for lp_token in ["lp0000", "lp0001"] {
+        let msg = ExecuteMsg::Deposit {
+            lp_token: Addr::unchecked(lp_token),
+            amount: Uint128::from(100u128),
+        };
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    }
+
+    let res: PendingRewardsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::PendingRewards {
+                user: Addr::unchecked("addr0000"),
+                lp_token: Addr::unchecked("lp0001"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res,
+        PendingRewardsResponse {
+            lp_token: Addr::unchecked("lp0001"),
+            pending_reward: Uint128::from(600u128),
+            proxy_reward_token: None,
+            pending_proxy_reward: Uint128::zero(),
+        }
+    );
+
+    let res: PendingRewardsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::PendingRewards {
+                user: Addr::unchecked("addr0001"),
+                lp_token: Addr::unchecked("lp0001"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.pending_reward, Uint128::zero());
+
+    // only the pools the user is bonded in are listed
+    let res: AllPendingRewardsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::AllPendingRewards {
+                user: Addr::unchecked("addr0000"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.pending_rewards
+            .iter()
+            .map(|pending| (pending.lp_token.to_string(), pending.pending_reward.u128()))
+            .collect::<Vec<(String, u128)>>(),
+        vec![("lp0000".to_string(), 200), ("lp0001".to_string(), 600)]
+    );
+
+    let res: AllPendingRewardsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_at(12365),
+            QueryMsg::AllPendingRewards {
+                user: Addr::unchecked("addr0000"),
+                start_after: Some(Addr::unchecked("lp0000")),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(res.pending_rewards.len(), 1);
+    assert_eq!(res.pending_rewards[0].lp_token, Addr::unchecked("lp0001"));
+}
+
+#[test]
+fn unbonding_period() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: Some(100),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // withdrawals are queued instead of sent
+    let now = mock_env().block.time.seconds();
+    let mut env = mock_env();
+    for amount in [60u128, 40u128] {
+        let msg = ExecuteMsg::Withdraw {
+            lp_token: Addr::unchecked("lp0000"),
+            amount: Uint128::from(amount),
+        };
+        let res = execute(deps.as_mut(), env.clone(), mock_info("addr0000", &[]), msg).unwrap();
+        assert_eq!(res.messages, vec![]);
+        env.block.time = env.block.time.plus_seconds(50);
+    }
+
+    let res: UnbondingClaimsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UnbondingClaims {
+                lp_token: Addr::unchecked("lp0000"),
+                staker: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.claims,
+        vec![
+            UnbondingClaim {
+                amount: Uint128::from(60u128),
+                release_at: now + 100,
+            },
+            UnbondingClaim {
+                amount: Uint128::from(40u128),
+                release_at: now + 150,
+            },
+        ]
+    );
+
+    let msg = ExecuteMsg::WithdrawUnbonded {
+        lp_token: Addr::unchecked("lp0000"),
+    };
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(99);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("no unbonded LP tokens to withdraw")
+    );
+
+    // only the released claims are sent
+    env.block.time = env.block.time.plus_seconds(1);
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(res.messages, vec![transfer_msg("lp0000", "addr0000", 60)]);
+
+    let res: UnbondingClaimsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::UnbondingClaims {
+                lp_token: Addr::unchecked("lp0000"),
+                staker: Addr::unchecked("addr0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.claims,
+        vec![UnbondingClaim {
+            amount: Uint128::from(40u128),
+            release_at: now + 150,
+        }]
+    );
+}
+
+#[test]
+fn reward_proxy() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let reward_proxy = RewardProxy {
+        contract_addr: Addr::unchecked("proxy0000"),
+        reward_token: Addr::unchecked("partner0000"),
+    };
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: Some(reward_proxy.clone()),
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0001"),
+        alloc_point: 3,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetRewardProxy {
+        lp_token: Addr::unchecked("lp0000"),
+        reward_proxy: reward_proxy.clone(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("pool already has a reward proxy")
+    );
+
+    let msg = ExecuteMsg::SetRewardProxy {
+        lp_token: Addr::unchecked("lp0001"),
+        reward_proxy: reward_proxy.clone(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    // the proxy pays out before the deposit is accounted for
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+    let update_msg = CallbackMsg::UpdateProxyReward {
+        lp_token: Addr::unchecked("lp0000"),
+        prev_balance: Uint128::zero(),
+    };
+    let bond_msg = CallbackMsg::Bond {
+        lp_token: Addr::unchecked("lp0000"),
+        staker: Addr::unchecked("addr0000"),
+        amount: Uint128::from(100u128),
+    };
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "lp0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: "addr0000".to_string(),
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::from(100u128),
+                })
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "proxy0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&ProxyExecuteMsg::Claim {}).unwrap(),
+            })),
+            callback_msg(update_msg.clone()),
+            callback_msg(bond_msg.clone()),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Callback(bond_msg.clone()),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    for msg in [update_msg, bond_msg] {
+        let _res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(MOCK_CONTRACT_ADDR, &[]),
+            ExecuteMsg::Callback(msg),
+        )
+        .unwrap();
+    }
+
+    // the proxy sends 300 partner tokens on the next claim
+    deps.querier.with_token_balances(&[(
+        &"partner0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(500u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12365),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    let update_msg = CallbackMsg::UpdateProxyReward {
+        lp_token: Addr::unchecked("lp0000"),
+        prev_balance: Uint128::from(500u128),
+    };
+    let claim_msg = CallbackMsg::Claim {
+        lp_token: Addr::unchecked("lp0000"),
+        staker: Addr::unchecked("addr0000"),
+    };
+    assert_eq!(res.messages[1], callback_msg(update_msg.clone()));
+    assert_eq!(res.messages[2], callback_msg(claim_msg.clone()));
+
+    deps.querier.with_token_balances(&[(
+        &"partner0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(800u128))],
+    )]);
+    let _res = execute(
+        deps.as_mut(),
+        mock_env_at(12365),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(update_msg),
+    )
+    .unwrap();
+
+    // both rewards are paid out in the same claim
+    let res = execute(
+        deps.as_mut(),
+        mock_env_at(12365),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(claim_msg),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer_msg("prism0000", "addr0000", 250),
+            transfer_msg("partner0000", "addr0000", 300),
+        ]
+    );
+}
+
+fn callback_msg(msg: CallbackMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg)).unwrap(),
+    }))
+}
+
+#[test]
+fn early_unstake_penalty_top_up() {
+    let mut deps = mock_dependencies(&[]);
+
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg()).unwrap();
+
+    let msg = ExecuteMsg::AddPool {
+        lp_token: Addr::unchecked("lp0000"),
+        alloc_point: 1,
+        reward_proxy: None,
+        unbonding_period: None,
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::SetEarlyUnstakePenalty {
+        lp_token: Addr::unchecked("lp0000"),
+        early_unstake_penalty: Some(EarlyUnstakePenalty {
+            period: 1000,
+            max_penalty: Decimal::from_str("0.5").unwrap(),
+        }),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    // the top-up pays out the 500 PRISM earned without a cut, and moves the deposit time of the
+    // aged bond halfway only
+    let mut env = mock_env_at(12360);
+    env.block.time = env.block.time.plus_seconds(900);
+    let msg = ExecuteMsg::Deposit {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(100u128),
+    };
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(res.messages[1], transfer_msg("prism0000", "addr0000", 500));
+    let staker = STAKERS
+        .load(
+            &deps.storage,
+            (&Addr::unchecked("addr0000"), &Addr::unchecked("lp0000")),
+        )
+        .unwrap();
+    assert_eq!(
+        staker.last_deposit_time,
+        mock_env().block.time.seconds() + 450
+    );
+
+    // so does a claim
+    let mut env = mock_env_at(12362);
+    env.block.time = env.block.time.plus_seconds(920);
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Claim {
+            lp_token: Addr::unchecked("lp0000"),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![transfer_msg("prism0000", "addr0000", 200)]
+    );
+
+    // 500 seconds after the averaged deposit time, a quarter of the 300 PRISM earned is cut
+    let mut env = mock_env_at(12365);
+    env.block.time = env.block.time.plus_seconds(950);
+    let msg = ExecuteMsg::Withdraw {
+        lp_token: Addr::unchecked("lp0000"),
+        amount: Uint128::from(200u128),
+    };
+    let res = execute(deps.as_mut(), env, mock_info("addr0000", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            transfer_msg("prism0000", "addr0000", 225),
+            transfer_msg("lp0000", "addr0000", 200),
+        ]
+    );
+}
@@ -0,0 +1,508 @@
+use crate::state::{Config, CONFIG, EXITED};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, QueryRequest, Response, StdError, StdResult, Uint128, WasmMsg, WasmQuery,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::querier::query_pair_info_from_pair;
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation,
+};
+use prismswap::staking::{
+    Cw20HookMsg as StakingCw20HookMsg, ExecuteMsg as StakingExecuteMsg,
+    QueryMsg as StakingQueryMsg, StakerInfoResponse,
+};
+use prismswap::staking_strategy::{
+    CallbackMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use prismswap::strategy::{StrategyCw20HookMsg, StrategyInfoResponse, TotalAssetsResponse};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_max_spread(msg.max_spread)?;
+
+    let pair_info = query_pair_info_from_pair(&deps.querier, &msg.pair)?;
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        vault: deps.api.addr_validate(msg.vault.as_str())?,
+        staking: deps.api.addr_validate(msg.staking.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+        prism_token: deps.api.addr_validate(msg.prism_token.as_str())?,
+        pair: pair_info.contract_addr,
+        asset_infos: pair_info.asset_infos,
+        lp_token: pair_info.liquidity_token,
+        compound_operations: msg.compound_operations,
+        max_spread: msg.max_spread,
+    };
+    assert_compound_operations(&config, &config.compound_operations)?;
+
+    CONFIG.save(deps.storage, &config)?;
+    EXITED.save(deps.storage, &false)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::Withdraw { amount } => execute_withdraw(deps, info, amount),
+        ExecuteMsg::Harvest {} => execute_harvest(deps, env, info),
+        ExecuteMsg::EmergencyExit {} => execute_emergency_exit(deps, env, info),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            compound_operations,
+            max_spread,
+        } => execute_update_config(deps, info, owner, compound_operations, max_spread),
+        ExecuteMsg::Callback(msg) => {
+            if env.contract.address != info.sender {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match msg {
+                CallbackMsg::SwapRewards {} => execute_swap_rewards(deps, env),
+                CallbackMsg::ProvideRewards {
+                    asset_info,
+                    prev_balance,
+                } => execute_provide_rewards(deps, env, asset_info, prev_balance),
+                CallbackMsg::BondLiquidity {} => execute_bond_liquidity(deps, env),
+            }
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        StrategyCw20HookMsg::Deposit {} => {
+            // only the vault can deposit the LP tokens
+            if info.sender != config.lp_token || cw20_msg.sender != config.vault.as_str() {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            if EXITED.load(deps.storage)? {
+                return Err(StdError::generic_err("strategy exited"));
+            }
+
+            Ok(Response::new()
+                .add_message(bond_msg(&config, cw20_msg.amount)?)
+                .add_attributes(vec![
+                    ("action", "deposit"),
+                    ("amount", &cw20_msg.amount.to_string()),
+                ]))
+        }
+    }
+}
+
+// Only vault can execute it
+pub fn execute_withdraw(deps: DepsMut, info: MessageInfo, amount: Uint128) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.vault {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    Ok(Response::new()
+        .add_messages(vec![
+            unbond_msg(&config, amount)?,
+            Asset::new(AssetInfo::Cw20(config.lp_token.clone()), amount)
+                .transfer_msg(&config.vault)?,
+        ])
+        .add_attributes(vec![
+            ("action", "withdraw"),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+// Only vault can execute it
+pub fn execute_harvest(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.vault {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if EXITED.load(deps.storage)? {
+        return Err(StdError::generic_err("strategy exited"));
+    }
+
+    Ok(Response::new()
+        .add_messages(vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: config.staking.to_string(),
+                funds: vec![],
+                msg: to_binary(&StakingExecuteMsg::Claim {
+                    lp_token: config.lp_token,
+                })?,
+            }),
+            callback_msg(&env, CallbackMsg::SwapRewards {})?,
+        ])
+        .add_attribute("action", "harvest"))
+}
+
+// Only vault or owner can execute it
+pub fn execute_emergency_exit(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.vault && info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    EXITED.save(deps.storage, &true)?;
+
+    let bond_amount = query_total_bond(deps.as_ref(), &config, &env.contract.address)?;
+    let amount = bond_amount
+        + AssetInfo::Cw20(config.lp_token.clone())
+            .query_pool(&deps.querier, &env.contract.address)?;
+
+    let mut messages = vec![];
+    if !bond_amount.is_zero() {
+        messages.push(unbond_msg(&config, bond_amount)?);
+    }
+    if !amount.is_zero() {
+        messages.push(
+            Asset::new(AssetInfo::Cw20(config.lp_token.clone()), amount)
+                .transfer_msg(&config.vault)?,
+        );
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "emergency_exit"),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    compound_operations: Option<Vec<SwapOperation>>,
+    max_spread: Option<Decimal>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(compound_operations) = compound_operations {
+        assert_compound_operations(&config, &compound_operations)?;
+        config.compound_operations = compound_operations;
+    }
+
+    if let Some(max_spread) = max_spread {
+        assert_max_spread(max_spread)?;
+        config.max_spread = max_spread;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_max_spread(max_spread: Decimal) -> StdResult<()> {
+    if max_spread >= Decimal::one() {
+        return Err(StdError::generic_err("max spread must be lower than 1"));
+    }
+
+    Ok(())
+}
+
+fn assert_compound_operations(config: &Config, operations: &[SwapOperation]) -> StdResult<()> {
+    let prism_info = AssetInfo::Cw20(config.prism_token.clone());
+
+    let (first_operation, last_operation) = match (operations.first(), operations.last()) {
+        (Some(first_operation), Some(last_operation)) => (first_operation, last_operation),
+        _ if config.asset_infos.contains(&prism_info) => return Ok(()),
+        _ => {
+            return Err(StdError::generic_err(
+                "must provide operations; PRISM is not an asset of the pair",
+            ))
+        }
+    };
+
+    if first_operation.get_offer_asset_info() != prism_info {
+        return Err(StdError::generic_err(
+            "invalid operations; first operation must offer PRISM",
+        ));
+    }
+
+    for window in operations.windows(2) {
+        if window[0].get_target_asset_info() != window[1].get_offer_asset_info() {
+            return Err(StdError::generic_err(
+                "invalid operations; each operation must offer the previous ask asset",
+            ));
+        }
+    }
+
+    if !config
+        .asset_infos
+        .contains(&last_operation.get_target_asset_info())
+    {
+        return Err(StdError::generic_err(
+            "invalid operations; last operation must ask an asset of the pair",
+        ));
+    }
+
+    Ok(())
+}
+
+pub fn execute_swap_rewards(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let prism_info = AssetInfo::Cw20(config.prism_token.clone());
+    let amount = prism_info.query_pool(&deps.querier, &env.contract.address)?;
+    if amount.is_zero() {
+        return Ok(Response::new().add_attribute("action", "skip_compound"));
+    }
+
+    let reward = Asset::new(prism_info, amount);
+    let messages = match config.compound_operations.last() {
+        None => vec![
+            provide_msg(&config, reward.clone())?,
+            callback_msg(&env, CallbackMsg::BondLiquidity {})?,
+        ],
+        Some(last_operation) => {
+            let asset_info = last_operation.get_target_asset_info();
+            let prev_balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+            vec![
+                swap_msg(&config, &env, reward.clone())?,
+                callback_msg(
+                    &env,
+                    CallbackMsg::ProvideRewards {
+                        asset_info,
+                        prev_balance,
+                    },
+                )?,
+            ]
+        }
+    };
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "swap_rewards"),
+        ("reward", &reward.to_string()),
+    ]))
+}
+
+pub fn execute_provide_rewards(
+    deps: DepsMut,
+    env: Env,
+    asset_info: AssetInfo,
+    prev_balance: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let balance = asset_info.query_pool(&deps.querier, &env.contract.address)?;
+    let asset = Asset::new(asset_info, balance.checked_sub(prev_balance)?);
+    if asset.amount.is_zero() {
+        return Ok(Response::new().add_attribute("action", "skip_compound"));
+    }
+
+    Ok(Response::new()
+        .add_messages(vec![
+            provide_msg(&config, asset.clone())?,
+            callback_msg(&env, CallbackMsg::BondLiquidity {})?,
+        ])
+        .add_attributes(vec![
+            ("action", "provide_rewards"),
+            ("asset", &asset.to_string()),
+        ]))
+}
+
+pub fn execute_bond_liquidity(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let amount = AssetInfo::Cw20(config.lp_token.clone())
+        .query_pool(&deps.querier, &env.contract.address)?;
+    if amount.is_zero() {
+        return Ok(Response::new().add_attribute("action", "skip_compound"));
+    }
+
+    Ok(Response::new()
+        .add_message(bond_msg(&config, amount)?)
+        .add_attributes(vec![
+            ("action", "bond_liquidity"),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+    }))
+}
+
+/// Bond the LP tokens in the staking contract on behalf of the contract
+fn bond_msg(config: &Config, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.lp_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: config.staking.to_string(),
+            amount,
+            msg: to_binary(&StakingCw20HookMsg::Bond { staker_addr: None })?,
+        })?,
+    }))
+}
+
+/// Unbond the LP tokens from the staking contract
+fn unbond_msg(config: &Config, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.staking.to_string(),
+        funds: vec![],
+        msg: to_binary(&StakingExecuteMsg::Withdraw {
+            lp_token: config.lp_token.clone(),
+            amount,
+        })?,
+    }))
+}
+
+/// Swap the PRISM rewards to an asset of the pair through the router, sending the output back to
+/// the contract
+fn swap_msg(config: &Config, env: &Env, reward: Asset) -> StdResult<CosmosMsg> {
+    let swap = ExecuteSwapOperationsMsg {
+        operations: config.compound_operations.clone(),
+        minimum_receive: None,
+        max_spread: Some(config.max_spread),
+        to: Some(env.contract.address.clone()),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: None,
+        staking_contract: None,
+    };
+
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.prism_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: config.router.to_string(),
+            amount: reward.amount,
+            msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap))?,
+        })?,
+    }))
+}
+
+/// Provide the asset to the pair through the router, the LP tokens being sent back to the
+/// contract
+fn provide_msg(config: &Config, asset: Asset) -> StdResult<CosmosMsg> {
+    match &asset.info {
+        AssetInfo::Cw20(contract_addr) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: config.router.to_string(),
+                amount: asset.amount,
+                msg: to_binary(&RouterCw20HookMsg::ProvideLiquidityFromSingleAsset {
+                    pair: config.pair.clone(),
+                    min_lp: None,
+                    max_spread: Some(config.max_spread),
+                    to: None,
+                    staking_contract: None,
+                })?,
+            })?,
+        })),
+        AssetInfo::Native(denom) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.router.to_string(),
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: asset.amount,
+            }],
+            msg: to_binary(&RouterExecuteMsg::ProvideLiquidityFromSingleAsset {
+                pair: config.pair.clone(),
+                min_lp: None,
+                max_spread: Some(config.max_spread),
+                to: None,
+                staking_contract: None,
+            })?,
+        })),
+    }
+}
+
+fn query_total_bond(deps: Deps, config: &Config, contract_addr: &Addr) -> StdResult<Uint128> {
+    let res: StakerInfoResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: config.staking.to_string(),
+        msg: to_binary(&StakingQueryMsg::StakerInfo {
+            lp_token: config.lp_token.clone(),
+            staker: contract_addr.clone(),
+        })?,
+    }))?;
+
+    Ok(res.bond_amount)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::StrategyInfo {} => to_binary(&query_strategy_info(deps)?),
+        QueryMsg::TotalAssets {} => to_binary(&query_total_assets(deps, env)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+    }
+}
+
+pub fn query_strategy_info(deps: Deps) -> StdResult<StrategyInfoResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(StrategyInfoResponse {
+        vault: config.vault,
+        lp_token: config.lp_token,
+        exited: EXITED.load(deps.storage)?,
+    })
+}
+
+pub fn query_total_assets(deps: Deps, env: Env) -> StdResult<TotalAssetsResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let bond_amount = query_total_bond(deps, &config, &env.contract.address)?;
+    let balance =
+        AssetInfo::Cw20(config.lp_token).query_pool(&deps.querier, &env.contract.address)?;
+
+    Ok(TotalAssetsResponse {
+        amount: bond_amount + balance,
+    })
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        vault: config.vault,
+        staking: config.staking,
+        router: config.router,
+        prism_token: config.prism_token,
+        pair: config.pair,
+        lp_token: config.lp_token,
+        compound_operations: config.compound_operations,
+        max_spread: config.max_spread,
+    })
+}
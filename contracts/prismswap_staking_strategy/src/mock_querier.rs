@@ -0,0 +1,143 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use prismswap::asset::{AssetInfo, PairInfo};
+use prismswap::pair::QueryMsg as PairQueryMsg;
+use prismswap::staking::{QueryMsg as StakingQueryMsg, StakerInfoResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    pair_asset_infos: [AssetInfo; 2],
+    bond_amount: Uint128,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg })
+                if from_binary::<PairQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    PairQueryMsg::Pair {} => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&PairInfo {
+                            asset_infos: self.pair_asset_infos.clone(),
+                            contract_addr: Addr::unchecked(contract_addr),
+                            liquidity_token: Addr::unchecked("liquidity0000"),
+                        })
+                        .unwrap(),
+                    )),
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<StakingQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    StakingQueryMsg::StakerInfo { lp_token, staker } => {
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&StakerInfoResponse {
+                                staker,
+                                lp_token,
+                                bond_amount: self.bond_amount,
+                                working_amount: self.bond_amount,
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            pair_asset_infos: [
+                AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                AssetInfo::Native("uusd".to_string()),
+            ],
+            bond_amount: Uint128::zero(),
+        }
+    }
+
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_balances = balances
+            .iter()
+            .map(|(contract_addr, balances)| {
+                (
+                    contract_addr.to_string(),
+                    balances
+                        .iter()
+                        .map(|(addr, balance)| (addr.to_string(), **balance))
+                        .collect(),
+                )
+            })
+            .collect();
+    }
+
+    pub fn with_pair_asset_infos(&mut self, asset_infos: [AssetInfo; 2]) {
+        self.pair_asset_infos = asset_infos;
+    }
+
+    pub fn with_bond_amount(&mut self, bond_amount: Uint128) {
+        self.bond_amount = bond_amount;
+    }
+}
@@ -0,0 +1,26 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::Item;
+use prismswap::asset::AssetInfo;
+use prismswap::router::SwapOperation;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub vault: Addr,
+    pub staking: Addr,
+    pub router: Addr,
+    pub prism_token: Addr,
+    pub pair: Addr,
+    pub asset_infos: [AssetInfo; 2],
+    pub lp_token: Addr,
+    pub compound_operations: Vec<SwapOperation>,
+    pub max_spread: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+/// whether the strategy exited, refusing deposits
+pub const EXITED: Item<bool> = Item::new("exited");
@@ -0,0 +1,523 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, CosmosMsg, Decimal, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::AssetInfo;
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation,
+};
+use prismswap::staking::{Cw20HookMsg as StakingCw20HookMsg, ExecuteMsg as StakingExecuteMsg};
+use prismswap::staking_strategy::{
+    CallbackMsg, ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg,
+};
+use prismswap::strategy::{StrategyCw20HookMsg, StrategyInfoResponse, TotalAssetsResponse};
+use std::str::FromStr;
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        vault: Addr::unchecked("vault0000"),
+        staking: Addr::unchecked("staking0000"),
+        router: Addr::unchecked("router0000"),
+        prism_token: Addr::unchecked("prism0000"),
+        pair: Addr::unchecked("pair0000"),
+        compound_operations: vec![],
+        max_spread: Decimal::from_str("0.01").unwrap(),
+    }
+}
+
+fn prism_to_uusd() -> Vec<SwapOperation> {
+    vec![SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        ask_asset_info: AssetInfo::Native("uusd".to_string()),
+        pair: None,
+        max_spread: None,
+    }]
+}
+
+fn bond_msg(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "liquidity0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: "staking0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&StakingCw20HookMsg::Bond { staker_addr: None }).unwrap(),
+        })
+        .unwrap(),
+    }))
+}
+
+fn unbond_msg(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "staking0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&StakingExecuteMsg::Withdraw {
+            lp_token: Addr::unchecked("liquidity0000"),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+fn lp_transfer(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "liquidity0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: "vault0000".to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+fn callback_msg(msg: CallbackMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg)).unwrap(),
+    }))
+}
+
+fn deposit_msg(sender: &str, amount: u128) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::from(amount),
+        msg: to_binary(&StrategyCw20HookMsg::Deposit {}).unwrap(),
+    })
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    // PRISM is not an asset of the pair, it needs a route
+    deps.querier.with_pair_asset_infos([
+        AssetInfo::Native("uluna".to_string()),
+        AssetInfo::Native("uusd".to_string()),
+    ]);
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("must provide operations; PRISM is not an asset of the pair")
+    );
+
+    let mut msg = instantiate_msg();
+    msg.compound_operations = vec![SwapOperation::PrismSwap {
+        offer_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+        ask_asset_info: AssetInfo::Native("ukrw".to_string()),
+        pair: None,
+        max_spread: None,
+    }];
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("invalid operations; last operation must ask an asset of the pair")
+    );
+
+    let mut msg = instantiate_msg();
+    msg.compound_operations = prism_to_uusd();
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: msg.owner,
+            vault: msg.vault,
+            staking: msg.staking,
+            router: msg.router,
+            prism_token: msg.prism_token,
+            pair: msg.pair,
+            lp_token: Addr::unchecked("liquidity0000"),
+            compound_operations: msg.compound_operations,
+            max_spread: msg.max_spread,
+        }
+    );
+
+    let info: StrategyInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::StrategyInfo {}).unwrap()).unwrap();
+    assert_eq!(
+        info,
+        StrategyInfoResponse {
+            vault: Addr::unchecked("vault0000"),
+            lp_token: Addr::unchecked("liquidity0000"),
+            exited: false,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        compound_operations: Some(prism_to_uusd()),
+        max_spread: Some(Decimal::from_str("0.02").unwrap()),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            compound_operations: None,
+            max_spread: Some(Decimal::one()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("max spread must be lower than 1")
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.compound_operations, prism_to_uusd());
+    assert_eq!(config.max_spread, Decimal::from_str("0.02").unwrap());
+}
+
+#[test]
+fn deposit_and_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    // only the vault deposits
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        deposit_msg("addr0000", 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        deposit_msg("vault0000", 100),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![bond_msg(100)]);
+
+    deps.querier.with_bond_amount(Uint128::from(100u128));
+    let res: TotalAssetsResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::TotalAssets {}).unwrap()).unwrap();
+    assert_eq!(res.amount, Uint128::from(100u128));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Withdraw {
+            amount: Uint128::from(40u128),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vault0000", &[]),
+        ExecuteMsg::Withdraw {
+            amount: Uint128::from(40u128),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![unbond_msg(40), lp_transfer(40)]);
+}
+
+#[test]
+fn harvest() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Harvest {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vault0000", &[]),
+        ExecuteMsg::Harvest {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "staking0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&StakingExecuteMsg::Claim {
+                    lp_token: Addr::unchecked("liquidity0000"),
+                })
+                .unwrap(),
+            })),
+            callback_msg(CallbackMsg::SwapRewards {}),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Callback(CallbackMsg::SwapRewards {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // PRISM is an asset of the pair, it is provided as it is
+    deps.querier.with_token_balances(&[
+        (
+            &"prism0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000u128))],
+        ),
+        (
+            &"liquidity0000".to_string(),
+            &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(50u128))],
+        ),
+    ]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::SwapRewards {}),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "router0000".to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&RouterCw20HookMsg::ProvideLiquidityFromSingleAsset {
+                        pair: Addr::unchecked("pair0000"),
+                        min_lp: None,
+                        max_spread: Some(Decimal::from_str("0.01").unwrap()),
+                        to: None,
+                        staking_contract: None,
+                    })
+                    .unwrap(),
+                })
+                .unwrap(),
+            })),
+            callback_msg(CallbackMsg::BondLiquidity {}),
+        ]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![bond_msg(50)]);
+}
+
+#[test]
+fn harvest_through_route() {
+    let mut deps = mock_dependencies(&[coin(600, "uusd")]);
+    deps.querier.with_pair_asset_infos([
+        AssetInfo::Native("uluna".to_string()),
+        AssetInfo::Native("uusd".to_string()),
+    ]);
+    let mut msg = instantiate_msg();
+    msg.compound_operations = prism_to_uusd();
+    let _res = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    deps.querier.with_token_balances(&[(
+        &"prism0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(1000u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::SwapRewards {}),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "prism0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: "router0000".to_string(),
+                    amount: Uint128::from(1000u128),
+                    msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(
+                        ExecuteSwapOperationsMsg {
+                            operations: prism_to_uusd(),
+                            minimum_receive: None,
+                            max_spread: Some(Decimal::from_str("0.01").unwrap()),
+                            to: Some(Addr::unchecked(MOCK_CONTRACT_ADDR)),
+                            deadline: None,
+                            referral: None,
+                            swap_all: None,
+                            twap_check: None,
+                            twap_config: None,
+                            staking_contract: None,
+                        }
+                    ))
+                    .unwrap(),
+                })
+                .unwrap(),
+            })),
+            callback_msg(CallbackMsg::ProvideRewards {
+                asset_info: AssetInfo::Native("uusd".to_string()),
+                prev_balance: Uint128::from(600u128),
+            }),
+        ]
+    );
+
+    // the swap brought the uusd balance from 100 to 600
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProvideRewards {
+            asset_info: AssetInfo::Native("uusd".to_string()),
+            prev_balance: Uint128::from(100u128),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "router0000".to_string(),
+                funds: vec![coin(500, "uusd")],
+                msg: to_binary(&RouterExecuteMsg::ProvideLiquidityFromSingleAsset {
+                    pair: Addr::unchecked("pair0000"),
+                    min_lp: None,
+                    max_spread: Some(Decimal::from_str("0.01").unwrap()),
+                    to: None,
+                    staking_contract: None,
+                })
+                .unwrap(),
+            })),
+            callback_msg(CallbackMsg::BondLiquidity {}),
+        ]
+    );
+}
+
+#[test]
+fn emergency_exit() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // every LP token, bonded or held, goes back to the vault
+    deps.querier.with_bond_amount(Uint128::from(100u128));
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(20u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![unbond_msg(100), lp_transfer(120)]);
+
+    let info: StrategyInfoResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::StrategyInfo {}).unwrap()).unwrap();
+    assert!(info.exited);
+
+    // the exited strategy refuses deposits and harvests
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        deposit_msg("vault0000", 100),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("strategy exited"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("vault0000", &[]),
+        ExecuteMsg::Harvest {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("strategy exited"));
+}
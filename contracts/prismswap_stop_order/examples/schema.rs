@@ -0,0 +1,25 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use prismswap::stop_order::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, OrdersResponse,
+    QueryMsg, TriggerResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(OrderResponse), &out_dir);
+    export_schema(&schema_for!(OrdersResponse), &out_dir);
+    export_schema(&schema_for!(TriggerResponse), &out_dir);
+}
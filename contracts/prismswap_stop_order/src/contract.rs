@@ -0,0 +1,465 @@
+use crate::state::{read_orders, Config, Order, CONFIG, NEXT_ORDER_ID, ORDERS};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, QueryRequest, Response, StdError, StdResult, WasmMsg, WasmQuery,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use cw_storage_plus::U64Key;
+use std::str::FromStr;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+use prismswap::pair::{QueryMsg as PairQueryMsg, TwapResponse};
+use prismswap::querier::query_pair_info;
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation, TwapConfig,
+};
+use prismswap::stop_order::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, OrdersResponse,
+    QueryMsg, TriggerResponse, MAX_KEEPER_FEE,
+};
+use prismswap::tax::TaxInfo;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_keeper_fee(msg.keeper_fee)?;
+    assert_twap_window(msg.twap_window)?;
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        factory: deps.api.addr_validate(msg.factory.as_str())?,
+        router: deps.api.addr_validate(msg.router.as_str())?,
+        keeper_fee: msg.keeper_fee,
+        twap_window: msg.twap_window,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_ORDER_ID.save(deps.storage, &1u64)?;
+
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::SubmitOrder {
+            offer_asset,
+            ask_asset_info,
+            trigger_price,
+            max_spread,
+            expires_at,
+        } => {
+            if !offer_asset.info.is_native_token() {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            offer_asset.assert_sent_native_token_balance(&info)?;
+
+            execute_submit_order(
+                deps,
+                env,
+                info.sender,
+                offer_asset,
+                ask_asset_info,
+                trigger_price,
+                max_spread,
+                expires_at,
+            )
+        }
+        ExecuteMsg::CancelOrder { order_id } => {
+            execute_cancel_orders(deps, env, info, vec![order_id])
+        }
+        ExecuteMsg::CancelOrders { order_ids } => execute_cancel_orders(deps, env, info, order_ids),
+        ExecuteMsg::ExecuteOrder { order_id } => execute_execute_order(deps, env, info, order_id),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            keeper_fee,
+            twap_window,
+        } => execute_update_config(deps, info, owner, keeper_fee, twap_window),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::SubmitOrder {
+            ask_asset_info,
+            trigger_price,
+            max_spread,
+            expires_at,
+        } => {
+            let maker = deps.api.addr_validate(&cw20_msg.sender)?;
+            execute_submit_order(
+                deps,
+                env,
+                maker,
+                Asset::new(AssetInfo::Cw20(info.sender), cw20_msg.amount),
+                ask_asset_info,
+                trigger_price,
+                max_spread,
+                expires_at,
+            )
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn execute_submit_order(
+    deps: DepsMut,
+    env: Env,
+    maker: Addr,
+    offer_asset: Asset,
+    ask_asset_info: AssetInfo,
+    trigger_price: Decimal,
+    max_spread: Decimal,
+    expires_at: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    if offer_asset.amount.is_zero() {
+        return Err(StdError::generic_err("offer amount must be greater than 0"));
+    }
+
+    if trigger_price.is_zero() {
+        return Err(StdError::generic_err(
+            "trigger price must be greater than 0",
+        ));
+    }
+
+    if max_spread >= Decimal::one() {
+        return Err(StdError::generic_err("max spread must be lower than 1"));
+    }
+
+    if matches!(expires_at, Some(expires_at) if expires_at <= env.block.time.seconds()) {
+        return Err(StdError::generic_err("expiration must be in the future"));
+    }
+
+    ask_asset_info.check(deps.api)?;
+    if ask_asset_info == offer_asset.info {
+        return Err(StdError::generic_err(
+            "offer and ask assets must be different",
+        ));
+    }
+
+    // the TWAP is read from the pair of the factory, which must exist
+    let pair_info = query_pair_info(
+        &deps.querier,
+        &config.factory,
+        &[offer_asset.info.clone(), ask_asset_info.clone()],
+    )?;
+
+    let order_id = NEXT_ORDER_ID.load(deps.storage)?;
+    NEXT_ORDER_ID.save(deps.storage, &(order_id + 1))?;
+    ORDERS.save(
+        deps.storage,
+        U64Key::new(order_id),
+        &Order {
+            maker: maker.clone(),
+            pair: pair_info.contract_addr,
+            offer_asset: offer_asset.clone(),
+            ask_asset_info,
+            trigger_price,
+            max_spread,
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "submit_order"),
+        ("order_id", &order_id.to_string()),
+        ("maker", maker.as_str()),
+        ("offer_asset", &offer_asset.to_string_legacy()),
+        ("trigger_price", &trigger_price.to_string()),
+    ]))
+}
+
+// Only maker can execute it, or anyone on expired orders
+pub fn execute_cancel_orders(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_ids: Vec<u64>,
+) -> StdResult<Response> {
+    let mut messages = vec![];
+    for order_id in order_ids.iter() {
+        let order = load_order(deps.as_ref(), *order_id)?;
+
+        // permission check
+        if info.sender != order.maker && !order.is_expired(&env.block) {
+            return Err(StdError::generic_err("unauthorized"));
+        }
+
+        ORDERS.remove(deps.storage, U64Key::new(*order_id));
+        // the tax of a native refund is charged on top of it against the escrowed amount
+        messages.extend(
+            order
+                .offer_asset
+                .into_transfer_msg_with_tax(&deps.querier, &order.maker)?,
+        );
+    }
+
+    let order_ids: Vec<String> = order_ids
+        .iter()
+        .map(|order_id| order_id.to_string())
+        .collect();
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "cancel_orders"),
+        ("order_ids", &order_ids.join(",")),
+    ]))
+}
+
+pub fn execute_execute_order(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    order_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let order = load_order(deps.as_ref(), order_id)?;
+
+    if order.is_expired(&env.block) {
+        return Err(StdError::generic_err("order expired"));
+    }
+
+    let trigger = query_order_trigger(deps.as_ref(), &env, &config, &order)?;
+    if !trigger.triggered {
+        return Err(StdError::generic_err("order not triggered"));
+    }
+
+    ORDERS.remove(deps.storage, U64Key::new(order_id));
+
+    // the tax of the native swap and keeper fee transfers is charged on top of them
+    let mut swap_amount = order.offer_asset.amount - trigger.keeper_fee_amount;
+    if let AssetInfo::Native(denom) = &order.offer_asset.info {
+        swap_amount = TaxInfo::query(&deps.querier, &[denom.to_string()])?.deduct_tax(&Coin {
+            denom: denom.to_string(),
+            amount: swap_amount,
+        })?;
+    }
+    let mut messages = vec![swap_msg(
+        &config,
+        &order,
+        Asset::new(order.offer_asset.info.clone(), swap_amount),
+    )?];
+    messages.extend(
+        Asset::new(order.offer_asset.info.clone(), trigger.keeper_fee_amount)
+            .into_transfer_msg_with_tax(&deps.querier, &info.sender)?,
+    );
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "execute_order"),
+        ("order_id", &order_id.to_string()),
+        ("keeper", info.sender.as_str()),
+        ("twap_price", &trigger.twap_price.to_string()),
+        ("swap_amount", &swap_amount.to_string()),
+        ("keeper_fee_amount", &trigger.keeper_fee_amount.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    keeper_fee: Option<Decimal>,
+    twap_window: Option<u64>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(keeper_fee) = keeper_fee {
+        assert_keeper_fee(keeper_fee)?;
+        config.keeper_fee = keeper_fee;
+    }
+
+    if let Some(twap_window) = twap_window {
+        assert_twap_window(twap_window)?;
+        config.twap_window = twap_window;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+fn assert_keeper_fee(keeper_fee: Decimal) -> StdResult<()> {
+    if keeper_fee > Decimal::from_str(MAX_KEEPER_FEE).unwrap() {
+        return Err(StdError::generic_err(format!(
+            "keeper fee must not exceed {}",
+            MAX_KEEPER_FEE
+        )));
+    }
+
+    Ok(())
+}
+
+fn assert_twap_window(twap_window: u64) -> StdResult<()> {
+    if twap_window == 0 {
+        return Err(StdError::generic_err("twap window must be greater than 0"));
+    }
+
+    Ok(())
+}
+
+fn load_order(deps: Deps, order_id: u64) -> StdResult<Order> {
+    ORDERS
+        .may_load(deps.storage, U64Key::new(order_id))?
+        .ok_or_else(|| StdError::generic_err("order not found"))
+}
+
+/// Swap the offer asset through the router, the output going to the maker
+fn swap_msg(config: &Config, order: &Order, offer_asset: Asset) -> StdResult<CosmosMsg> {
+    let swap = ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::PrismSwap {
+            offer_asset_info: order.offer_asset.info.clone(),
+            ask_asset_info: order.ask_asset_info.clone(),
+            pair: None,
+            max_spread: None,
+        }],
+        minimum_receive: None,
+        max_spread: Some(order.max_spread),
+        to: Some(order.maker.clone()),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        // the output is bounded by the pair TWAP the order is triggered at
+        twap_check: None,
+        twap_config: Some(TwapConfig {
+            window: config.twap_window,
+            max_deviation: order.max_spread,
+        }),
+        staking_contract: None,
+    };
+
+    Ok(match &offer_asset.info {
+        AssetInfo::Native(denom) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.router.to_string(),
+            funds: vec![Coin {
+                denom: denom.to_string(),
+                amount: offer_asset.amount,
+            }],
+            msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap))?,
+        }),
+        AssetInfo::Cw20(contract_addr) => CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: config.router.to_string(),
+                amount: offer_asset.amount,
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap))?,
+            })?,
+        }),
+    })
+}
+
+fn query_order_trigger(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    order: &Order,
+) -> StdResult<TriggerResponse> {
+    let twap: TwapResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: order.pair.to_string(),
+        msg: to_binary(&PairQueryMsg::Twap {
+            window: config.twap_window,
+        })?,
+    }))?;
+    let twap_price = if order.offer_asset.info == twap.asset_infos[0] {
+        twap.price0
+    } else {
+        twap.price1
+    };
+
+    Ok(TriggerResponse {
+        twap_price,
+        keeper_fee_amount: order.offer_asset.amount * config.keeper_fee,
+        triggered: !order.is_expired(&env.block) && twap_price <= order.trigger_price,
+    })
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Order { order_id } => to_binary(&query_order(deps, order_id)?),
+        QueryMsg::Orders {
+            maker,
+            start_after,
+            limit,
+        } => to_binary(&query_orders(deps, maker, start_after, limit)?),
+        QueryMsg::Trigger { order_id } => to_binary(&query_trigger(deps, env, order_id)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        factory: config.factory,
+        router: config.router,
+        keeper_fee: config.keeper_fee,
+        twap_window: config.twap_window,
+    })
+}
+
+pub fn query_order(deps: Deps, order_id: u64) -> StdResult<OrderResponse> {
+    let order = load_order(deps, order_id)?;
+
+    Ok(order_response(order_id, order))
+}
+
+pub fn query_orders(
+    deps: Deps,
+    maker: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let orders = read_orders(deps.storage, maker, start_after, limit)?
+        .into_iter()
+        .map(|(order_id, order)| order_response(order_id, order))
+        .collect();
+
+    Ok(OrdersResponse { orders })
+}
+
+pub fn query_trigger(deps: Deps, env: Env, order_id: u64) -> StdResult<TriggerResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let order = load_order(deps, order_id)?;
+
+    query_order_trigger(deps, &env, &config, &order)
+}
+
+fn order_response(order_id: u64, order: Order) -> OrderResponse {
+    OrderResponse {
+        order_id,
+        maker: order.maker,
+        pair: order.pair,
+        offer_asset: order.offer_asset,
+        ask_asset_info: order.ask_asset_info,
+        trigger_price: order.trigger_price,
+        max_spread: order.max_spread,
+        expires_at: order.expires_at,
+    }
+}
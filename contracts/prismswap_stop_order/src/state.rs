@@ -0,0 +1,67 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, BlockInfo, Decimal, Order as OrderBy, StdResult, Storage};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub router: Addr,
+    pub keeper_fee: Decimal,
+    pub twap_window: u64,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Order {
+    pub maker: Addr,
+    /// the pair whose TWAP triggers the order
+    pub pair: Addr,
+    pub offer_asset: Asset,
+    pub ask_asset_info: AssetInfo,
+    /// the TWAP of the offer asset in the ask asset at or below which the order can be executed
+    pub trigger_price: Decimal,
+    pub max_spread: Decimal,
+    /// the time the order can no longer be executed at, when anyone can cancel it
+    pub expires_at: Option<u64>,
+}
+
+impl Order {
+    pub fn is_expired(&self, block: &BlockInfo) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= block.time.seconds())
+    }
+}
+
+pub const ORDERS: Map<U64Key, Order> = Map::new("orders");
+
+/// the id of the next order submitted
+pub const NEXT_ORDER_ID: Item<u64> = Item::new("next_order_id");
+
+pub fn read_orders(
+    storage: &dyn Storage,
+    maker: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Order)>> {
+    let start = start_after_id_bound(start_after);
+
+    ORDERS
+        .range(storage, start, None, OrderBy::Ascending)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .filter(|item| match (&maker, item) {
+            (Some(maker), Ok((_, order))) => order.maker == *maker,
+            _ => true,
+        })
+        .take(calc_limit(limit))
+        .collect()
+}
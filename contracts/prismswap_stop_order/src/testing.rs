@@ -0,0 +1,444 @@
+use crate::contract::{execute, instantiate, query};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info};
+use cosmwasm_std::{
+    attr, coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Decimal, DepsMut, StdError,
+    SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    SwapOperation, TwapConfig,
+};
+use prismswap::stop_order::{
+    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, QueryMsg,
+    TriggerResponse,
+};
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        factory: Addr::unchecked("factory0000"),
+        router: Addr::unchecked("router0000"),
+        keeper_fee: Decimal::percent(1),
+        twap_window: 3600,
+    }
+}
+
+fn uusd(amount: u128) -> Asset {
+    Asset::new(AssetInfo::Native("uusd".to_string()), amount)
+}
+
+fn swap_msg(offer_asset_info: AssetInfo, ask_asset_info: AssetInfo) -> ExecuteSwapOperationsMsg {
+    ExecuteSwapOperationsMsg {
+        operations: vec![SwapOperation::PrismSwap {
+            offer_asset_info,
+            ask_asset_info,
+            pair: None,
+            max_spread: None,
+        }],
+        minimum_receive: None,
+        max_spread: Some(Decimal::percent(2)),
+        to: Some(Addr::unchecked("addr0000")),
+        deadline: None,
+        referral: None,
+        swap_all: None,
+        twap_check: None,
+        twap_config: Some(TwapConfig {
+            window: 3600,
+            max_deviation: Decimal::percent(2),
+        }),
+        staking_contract: None,
+    }
+}
+
+/// Submits an order selling uusd for PRISM once a uusd is worth the trigger price in PRISM
+fn submit_order(deps: DepsMut, amount: u128, trigger_price: Decimal, expires_at: Option<u64>) {
+    let _res = execute(
+        deps,
+        mock_env(),
+        mock_info("addr0000", &[coin(amount, "uusd")]),
+        ExecuteMsg::SubmitOrder {
+            offer_asset: uusd(amount),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            trigger_price,
+            max_spread: Decimal::percent(2),
+            expires_at,
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.twap_window = 0;
+    let err = instantiate(deps.as_mut(), mock_env(), mock_info("addr0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("twap window must be greater than 0")
+    );
+
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            factory: Addr::unchecked("factory0000"),
+            router: Addr::unchecked("router0000"),
+            keeper_fee: Decimal::percent(1),
+            twap_window: 3600,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        keeper_fee: Some(Decimal::percent(2)),
+        twap_window: Some(600),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            keeper_fee: Some(Decimal::percent(10)),
+            twap_window: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("keeper fee must not exceed 0.05")
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.keeper_fee, Decimal::percent(2));
+    assert_eq!(config.twap_window, 600);
+}
+
+#[test]
+fn submit_and_cancel_order() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[coin(1000, "uusd")]),
+        ExecuteMsg::SubmitOrder {
+            offer_asset: uusd(1000),
+            ask_asset_info: AssetInfo::Cw20(Addr::unchecked("prism0000")),
+            trigger_price: Decimal::percent(50),
+            max_spread: Decimal::one(),
+            expires_at: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("max spread must be lower than 1")
+    );
+
+    // CW20 tokens are offered through their hook
+    let expires_at = mock_env().block.time.seconds() + 3600;
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(300u128),
+            msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                trigger_price: Decimal::percent(150),
+                max_spread: Decimal::percent(2),
+                expires_at: Some(expires_at),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[1], attr("order_id", "1"));
+
+    let order: OrderResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        order,
+        OrderResponse {
+            order_id: 1,
+            maker: Addr::unchecked("addr0000"),
+            pair: Addr::unchecked("pair0000"),
+            offer_asset: Asset::new(AssetInfo::Cw20(Addr::unchecked("prism0000")), 300u128),
+            ask_asset_info: AssetInfo::Native("uusd".to_string()),
+            trigger_price: Decimal::percent(150),
+            max_spread: Decimal::percent(2),
+            expires_at: Some(expires_at),
+        }
+    );
+
+    submit_order(deps.as_mut(), 1000, Decimal::percent(50), None);
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // anyone cancels an expired order, the maker cancels the rest
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(3600);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0001", &[]),
+        ExecuteMsg::CancelOrder { order_id: 1 },
+    )
+    .unwrap();
+
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrders { order_ids: vec![2] },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(1000, "uusd")],
+        }))]
+    );
+}
+
+#[test]
+fn execute_order() {
+    let mut deps = mock_dependencies(&[]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+
+    // sell 1000 uusd once uusd falls to 0.5 PRISM
+    submit_order(deps.as_mut(), 1000, Decimal::percent(50), None);
+
+    deps.querier.with_twap_price(Decimal::percent(60));
+    let trigger: TriggerResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Trigger { order_id: 1 }).unwrap())
+            .unwrap();
+    assert_eq!(
+        trigger,
+        TriggerResponse {
+            twap_price: Decimal::percent(60),
+            keeper_fee_amount: Uint128::from(10u128),
+            triggered: false,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("order not triggered"));
+
+    deps.querier.with_twap_price(Decimal::percent(50));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "router0000".to_string(),
+                funds: vec![coin(990, "uusd")],
+                msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap_msg(
+                    AssetInfo::Native("uusd".to_string()),
+                    AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                )))
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "keeper0000".to_string(),
+                amount: vec![coin(10, "uusd")],
+            })),
+        ]
+    );
+
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::Order { order_id: 1 }).unwrap_err();
+    assert_eq!(err, StdError::generic_err("order not found"));
+
+    // PRISM is sold once the TWAP of PRISM in uusd, the inverse, falls to the trigger price
+    let _res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("prism0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(500u128),
+            msg: to_binary(&Cw20HookMsg::SubmitOrder {
+                ask_asset_info: AssetInfo::Native("uusd".to_string()),
+                trigger_price: Decimal::percent(200),
+                max_spread: Decimal::percent(2),
+                expires_at: Some(mock_env().block.time.seconds() + 60),
+            })
+            .unwrap(),
+        }),
+    )
+    .unwrap();
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(60);
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("order expired"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "router0000".to_string(),
+                amount: Uint128::from(495u128),
+                msg: to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(swap_msg(
+                    AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                    AssetInfo::Native("uusd".to_string()),
+                )))
+                .unwrap(),
+            })
+            .unwrap(),
+        }))
+    );
+}
+
+#[test]
+fn execute_order_with_tax() {
+    let mut deps = mock_dependencies(&[coin(3000, "uusd")]);
+    let _res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        instantiate_msg(),
+    )
+    .unwrap();
+    deps.querier
+        .with_tax(Decimal::percent(1), &[("uusd", Uint128::from(1000000u128))]);
+
+    submit_order(deps.as_mut(), 2000, Decimal::percent(50), None);
+    submit_order(deps.as_mut(), 1000, Decimal::percent(50), None);
+
+    // the tax of the swap and of the keeper fee is charged against the escrowed amount,
+    // 1960 + 19 and 19 + 1
+    deps.querier.with_twap_price(Decimal::percent(50));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("keeper0000", &[]),
+        ExecuteMsg::ExecuteOrder { order_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "router0000".to_string(),
+                funds: vec![coin(1960, "uusd")],
+                msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(swap_msg(
+                    AssetInfo::Native("uusd".to_string()),
+                    AssetInfo::Cw20(Addr::unchecked("prism0000")),
+                )))
+                .unwrap(),
+            })),
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: "keeper0000".to_string(),
+                amount: vec![coin(19, "uusd")],
+            })),
+        ]
+    );
+
+    // and so is the tax of the refund, 990 + 10
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::CancelOrder { order_id: 2 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![coin(990, "uusd")],
+        }))]
+    );
+}
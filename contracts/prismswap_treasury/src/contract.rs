@@ -0,0 +1,255 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+    Uint128,
+};
+use cw_storage_plus::U64Key;
+
+use crate::state::{read_streams, Config, Stream, CONFIG, NEXT_STREAM_ID, STREAMS};
+
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::treasury::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StreamResponse, StreamsResponse,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_STREAM_ID.save(deps.storage, &1)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::CreateStream {
+            recipient,
+            asset_info,
+            rate,
+            start_time,
+            end_time,
+        } => execute_create_stream(
+            deps, env, info, recipient, asset_info, rate, start_time, end_time,
+        ),
+        ExecuteMsg::CancelStream { stream_id } => execute_cancel_stream(deps, env, info, stream_id),
+        ExecuteMsg::Claim { stream_id } => execute_claim(deps, env, info, stream_id),
+    }
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_create_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+    asset_info: AssetInfo,
+    rate: Uint128,
+    start_time: u64,
+    end_time: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    deps.api.addr_validate(recipient.as_str())?;
+    if rate.is_zero() {
+        return Err(StdError::generic_err("rate must be greater than zero"));
+    }
+    if start_time >= end_time || end_time <= env.block.time.seconds() {
+        return Err(StdError::generic_err(
+            "stream must end after it starts and in the future",
+        ));
+    }
+
+    let stream_id = NEXT_STREAM_ID.load(deps.storage)?;
+    NEXT_STREAM_ID.save(deps.storage, &(stream_id + 1))?;
+    STREAMS.save(
+        deps.storage,
+        U64Key::new(stream_id),
+        &Stream {
+            recipient: recipient.clone(),
+            asset_info: asset_info.clone(),
+            rate,
+            start_time,
+            end_time,
+            claimed_amount: Uint128::zero(),
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "create_stream"),
+        ("stream_id", &stream_id.to_string()),
+        ("recipient", recipient.as_str()),
+        ("asset_info", &asset_info.to_string()),
+        ("rate", &rate.to_string()),
+        ("start_time", &start_time.to_string()),
+        ("end_time", &end_time.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_cancel_stream(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stream_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut stream = load_stream(deps.as_ref(), stream_id)?;
+    let now = env.block.time.seconds();
+    if stream.end_time <= now {
+        return Err(StdError::generic_err("stream is over"));
+    }
+
+    stream.end_time = now.max(stream.start_time);
+    STREAMS.save(deps.storage, U64Key::new(stream_id), &stream)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "cancel_stream"),
+        ("stream_id", &stream_id.to_string()),
+        ("end_time", &stream.end_time.to_string()),
+    ]))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    stream_id: u64,
+) -> StdResult<Response> {
+    let mut stream = load_stream(deps.as_ref(), stream_id)?;
+    if info.sender != stream.recipient {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let amount = claimable_amount(&stream, env.block.time.seconds())?;
+    if amount.is_zero() {
+        return Err(StdError::generic_err("nothing to claim"));
+    }
+
+    stream.claimed_amount = stream.claimed_amount.checked_add(amount)?;
+    STREAMS.save(deps.storage, U64Key::new(stream_id), &stream)?;
+
+    Ok(Response::new()
+        .add_message(Asset::new(stream.asset_info, amount).transfer_msg(&stream.recipient)?)
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("stream_id", &stream_id.to_string()),
+            ("recipient", stream.recipient.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn load_stream(deps: Deps, stream_id: u64) -> StdResult<Stream> {
+    STREAMS
+        .may_load(deps.storage, U64Key::new(stream_id))?
+        .ok_or_else(|| StdError::generic_err(format!("stream {} does not exist", stream_id)))
+}
+
+/// The amount the stream paid by `time` and not claimed yet
+fn claimable_amount(stream: &Stream, time: u64) -> StdResult<Uint128> {
+    let elapsed = time.min(stream.end_time).saturating_sub(stream.start_time);
+    let paid_amount = stream.rate.checked_mul(Uint128::from(elapsed))?;
+
+    Ok(paid_amount.checked_sub(stream.claimed_amount)?)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Stream { stream_id } => to_binary(&query_stream(deps, env, stream_id)?),
+        QueryMsg::Streams {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query_streams(deps, env, recipient, start_after, limit)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+    })
+}
+
+pub fn query_stream(deps: Deps, env: Env, stream_id: u64) -> StdResult<StreamResponse> {
+    let stream = load_stream(deps, stream_id)?;
+
+    stream_response(&env, stream_id, stream)
+}
+
+pub fn query_streams(
+    deps: Deps,
+    env: Env,
+    recipient: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<StreamsResponse> {
+    let streams = read_streams(deps.storage, recipient, start_after, limit)?
+        .into_iter()
+        .map(|(stream_id, stream)| stream_response(&env, stream_id, stream))
+        .collect::<StdResult<Vec<StreamResponse>>>()?;
+
+    Ok(StreamsResponse { streams })
+}
+
+fn stream_response(env: &Env, stream_id: u64, stream: Stream) -> StdResult<StreamResponse> {
+    Ok(StreamResponse {
+        stream_id,
+        claimable_amount: claimable_amount(&stream, env.block.time.seconds())?,
+        recipient: stream.recipient,
+        asset_info: stream.asset_info,
+        rate: stream.rate,
+        start_time: stream.start_time,
+        end_time: stream.end_time,
+        claimed_amount: stream.claimed_amount,
+    })
+}
@@ -0,0 +1,54 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::asset::AssetInfo;
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Stream {
+    pub recipient: Addr,
+    pub asset_info: AssetInfo,
+    pub rate: Uint128,
+    pub start_time: u64,
+    /// brought forward when the stream is cancelled
+    pub end_time: u64,
+    pub claimed_amount: Uint128,
+}
+
+pub const STREAMS: Map<U64Key, Stream> = Map::new("streams");
+
+/// the id of the next stream created
+pub const NEXT_STREAM_ID: Item<u64> = Item::new("next_stream_id");
+
+pub fn read_streams(
+    storage: &dyn Storage,
+    recipient: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Stream)>> {
+    let start = start_after_id_bound(start_after);
+
+    STREAMS
+        .range(storage, start, None, Order::Ascending)
+        .filter(|item| match (&recipient, item) {
+            (Some(recipient), Ok((_, stream))) => stream.recipient == *recipient,
+            _ => true,
+        })
+        .take(calc_limit(limit))
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+        .collect()
+}
@@ -0,0 +1,302 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    coin, from_binary, to_binary, Addr, BankMsg, CosmosMsg, Env, StdError, SubMsg, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+use prismswap::asset::AssetInfo;
+use prismswap::treasury::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StreamResponse, StreamsResponse,
+};
+
+fn mock_env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn create_stream_msg(recipient: &str, asset_info: AssetInfo) -> ExecuteMsg {
+    let now = mock_env().block.time.seconds();
+    ExecuteMsg::CreateStream {
+        recipient: Addr::unchecked(recipient),
+        asset_info,
+        rate: Uint128::from(10u128),
+        start_time: now + 100,
+        end_time: now + 1100,
+    }
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+        }
+    );
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+}
+
+#[test]
+fn create_and_claim_stream() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let prism = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+    let msg = create_stream_msg("recipient0000", prism.clone());
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let now = mock_env().block.time.seconds();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::CreateStream {
+            recipient: Addr::unchecked("recipient0000"),
+            asset_info: prism.clone(),
+            rate: Uint128::from(10u128),
+            start_time: now - 100,
+            end_time: now,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("stream must end after it starts and in the future")
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+
+    // nothing is paid before the start
+    let claim = ExecuteMsg::Claim { stream_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(100),
+        mock_info("recipient0000", &[]),
+        claim.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("nothing to claim"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(400),
+        mock_info("addr0000", &[]),
+        claim.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let stream: StreamResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(400),
+            QueryMsg::Stream { stream_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        stream,
+        StreamResponse {
+            stream_id: 1,
+            recipient: Addr::unchecked("recipient0000"),
+            asset_info: prism.clone(),
+            rate: Uint128::from(10u128),
+            start_time: now + 100,
+            end_time: now + 1100,
+            claimed_amount: Uint128::zero(),
+            claimable_amount: Uint128::from(3000u128),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(400),
+        mock_info("recipient0000", &[]),
+        claim.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient0000".to_string(),
+                amount: Uint128::from(3000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+
+    // the stream pays nothing past its end
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(2000),
+        mock_info("recipient0000", &[]),
+        claim,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "prism0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "recipient0000".to_string(),
+                amount: Uint128::from(7000u128),
+            })
+            .unwrap(),
+        }))]
+    );
+}
+
+#[test]
+fn cancel_stream() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+    };
+    let info = mock_info("addr0000", &[]);
+    let _res = instantiate(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let uusd = AssetInfo::Native("uusd".to_string());
+    let prism = AssetInfo::Cw20(Addr::unchecked("prism0000"));
+    for msg in [
+        create_stream_msg("recipient0000", uusd.clone()),
+        create_stream_msg("recipient0001", prism),
+        create_stream_msg("recipient0000", uusd),
+    ] {
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    }
+
+    let cancel = ExecuteMsg::CancelStream { stream_id: 1 };
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(300),
+        mock_info("recipient0000", &[]),
+        cancel.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let _res = execute(
+        deps.as_mut(),
+        mock_env_after(300),
+        mock_info("owner0000", &[]),
+        cancel.clone(),
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(300),
+        mock_info("owner0000", &[]),
+        cancel,
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("stream is over"));
+
+    // what was paid before the cancellation stays claimable
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(1000),
+        mock_info("recipient0000", &[]),
+        ExecuteMsg::Claim { stream_id: 1 },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "recipient0000".to_string(),
+            amount: vec![coin(2000, "uusd")],
+        }))]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::CancelStream { stream_id: 4 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("stream 4 does not exist"));
+
+    let streams: StreamsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(1000),
+            QueryMsg::Streams {
+                recipient: Some(Addr::unchecked("recipient0000")),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        streams
+            .streams
+            .iter()
+            .map(|stream| (stream.stream_id, stream.claimable_amount))
+            .collect::<Vec<(u64, Uint128)>>(),
+        vec![(1, Uint128::zero()), (3, Uint128::from(9000u128))]
+    );
+
+    let streams: StreamsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env(),
+            QueryMsg::Streams {
+                recipient: None,
+                start_after: Some(1),
+                limit: Some(1),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(streams.streams.len(), 1);
+    assert_eq!(streams.streams[0].stream_id, 2);
+}
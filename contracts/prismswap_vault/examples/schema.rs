@@ -0,0 +1,28 @@
+use std::env::current_dir;
+use std::fs::create_dir_all;
+
+use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+
+use prismswap::vault::{
+    ConfigResponse, Cw20HookMsg, ExchangeRateResponse, ExecuteMsg, FeesResponse, InstantiateMsg,
+    PositionResponse, QueryMsg, StateResponse, WithdrawalResponse, WithdrawalsResponse,
+};
+
+fn main() {
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+
+    export_schema(&schema_for!(InstantiateMsg), &out_dir);
+    export_schema(&schema_for!(ExecuteMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
+    export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(StateResponse), &out_dir);
+    export_schema(&schema_for!(ExchangeRateResponse), &out_dir);
+    export_schema(&schema_for!(PositionResponse), &out_dir);
+    export_schema(&schema_for!(FeesResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawalResponse), &out_dir);
+    export_schema(&schema_for!(WithdrawalsResponse), &out_dir);
+}
@@ -0,0 +1,883 @@
+use crate::parse_reply::parse_reply_instantiate_data;
+use crate::state::{
+    read_queue, read_withdrawals, Config, Withdrawal, CONFIG, HARVEST_INTERVAL, LAST_CHARGED_TIME,
+    LAST_HARVEST_TIME, NEXT_WITHDRAWAL_ID, TOTAL_QUEUED, WITHDRAWALS,
+};
+
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
+    Reply, Response, StdError, StdResult, SubMsg, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use cw_storage_plus::U64Key;
+use std::str::FromStr;
+
+use prismswap::asset::{Asset, AssetInfo, PrismSwapAssetInfo};
+use prismswap::querier::{
+    query_strategy_info, query_strategy_total_assets, query_supply, query_token_balance,
+};
+use prismswap::strategy::{StrategyCw20HookMsg, StrategyExecuteMsg};
+use prismswap::token::InstantiateMsg as TokenInstantiateMsg;
+use prismswap::vault::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExchangeRateResponse, ExecuteMsg, FeesResponse,
+    InstantiateMsg, PositionResponse, QueryMsg, StateResponse, WithdrawalQueue, WithdrawalResponse,
+    WithdrawalsResponse, MAX_MANAGEMENT_FEE, MAX_PERFORMANCE_FEE,
+};
+
+const INSTANTIATE_REPLY_ID: u64 = 1;
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    assert_fees(msg.performance_fee, msg.management_fee)?;
+    if let Some(withdrawal_queue) = &msg.withdrawal_queue {
+        assert_withdrawal_queue(withdrawal_queue)?;
+    }
+
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        lp_token: deps.api.addr_validate(msg.lp_token.as_str())?,
+        share_token: Addr::unchecked(""),
+        strategy: None,
+        collector: deps.api.addr_validate(msg.collector.as_str())?,
+        performance_fee: msg.performance_fee,
+        management_fee: msg.management_fee,
+        withdrawal_queue: msg.withdrawal_queue,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    LAST_CHARGED_TIME.save(deps.storage, &env.block.time.seconds())?;
+    LAST_HARVEST_TIME.save(deps.storage, &0u64)?;
+    HARVEST_INTERVAL.save(deps.storage, &0u64)?;
+    NEXT_WITHDRAWAL_ID.save(deps.storage, &1u64)?;
+    TOTAL_QUEUED.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new().add_submessage(SubMsg::reply_on_success(
+        WasmMsg::Instantiate {
+            code_id: msg.token_code_id,
+            msg: to_binary(&TokenInstantiateMsg {
+                name: "PrismSwap vault share".to_string(),
+                symbol: "uVS".to_string(),
+                decimals: 6,
+                initial_balances: vec![],
+                mint: Some(MinterResponse {
+                    minter: env.contract.address.to_string(),
+                    cap: None,
+                }),
+            })?,
+            funds: vec![],
+            admin: None,
+            label: "".to_string(),
+        },
+        INSTANTIATE_REPLY_ID,
+    )))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    if msg.id != INSTANTIATE_REPLY_ID || config.share_token != Addr::unchecked("") {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let res = parse_reply_instantiate_data(msg)
+        .map_err(|_| StdError::generic_err("error parsing instantiate reply"))?;
+    config.share_token = deps.api.addr_validate(&res.contract_address)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("share_token_addr", config.share_token.as_str()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig {
+            owner,
+            collector,
+            performance_fee,
+            management_fee,
+            withdrawal_queue,
+        } => execute_update_config(
+            deps,
+            env,
+            info,
+            owner,
+            collector,
+            performance_fee,
+            management_fee,
+            withdrawal_queue,
+        ),
+        ExecuteMsg::UpdateStrategy { strategy } => {
+            execute_update_strategy(deps, env, info, strategy)
+        }
+        ExecuteMsg::EmergencyExit {} => execute_emergency_exit(deps, info),
+        ExecuteMsg::Compound {} => execute_compound(deps, env, info),
+        ExecuteMsg::Callback(msg) => {
+            if env.contract.address != info.sender {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+
+            match msg {
+                CallbackMsg::ProcessHarvest { prev_total_assets } => {
+                    execute_process_harvest(deps, env, prev_total_assets)
+                }
+                CallbackMsg::BondLiquidity {} => execute_bond_liquidity(deps, env),
+            }
+        }
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Deposit {} => {
+            // only the LP token contract can execute this message
+            if info.sender != config.lp_token {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            execute_deposit(deps, env, config, sender, cw20_msg.amount)
+        }
+        Cw20HookMsg::Withdraw {} => {
+            // only the share token contract can execute this message
+            if info.sender != config.share_token {
+                return Err(StdError::generic_err("unauthorized"));
+            }
+            execute_withdraw(deps, env, config, sender, cw20_msg.amount)
+        }
+    }
+}
+
+// Only owner can execute it
+#[allow(clippy::too_many_arguments)]
+pub fn execute_update_config(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: Option<Addr>,
+    collector: Option<Addr>,
+    performance_fee: Option<Decimal>,
+    management_fee: Option<Decimal>,
+    withdrawal_queue: Option<WithdrawalQueue>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // the management fee accrued so far is charged at the previous rate to the previous collector
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    let (_, fee_msg) = charge_management_fee(deps.branch(), &env, &config, total_share)?;
+
+    if let Some(owner) = owner {
+        config.owner = deps.api.addr_validate(owner.as_str())?;
+    }
+
+    if let Some(collector) = collector {
+        config.collector = deps.api.addr_validate(collector.as_str())?;
+    }
+
+    if let Some(performance_fee) = performance_fee {
+        config.performance_fee = performance_fee;
+    }
+
+    if let Some(management_fee) = management_fee {
+        config.management_fee = management_fee;
+    }
+
+    assert_fees(config.performance_fee, config.management_fee)?;
+
+    if let Some(withdrawal_queue) = withdrawal_queue {
+        assert_withdrawal_queue(&withdrawal_queue)?;
+        config.withdrawal_queue = Some(withdrawal_queue);
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_attribute("action", "update_config"))
+}
+
+fn assert_fees(performance_fee: Decimal, management_fee: Decimal) -> StdResult<()> {
+    if performance_fee > Decimal::from_str(MAX_PERFORMANCE_FEE).unwrap() {
+        return Err(StdError::generic_err(format!(
+            "performance fee must not exceed {}",
+            MAX_PERFORMANCE_FEE
+        )));
+    }
+
+    if management_fee > Decimal::from_str(MAX_MANAGEMENT_FEE).unwrap() {
+        return Err(StdError::generic_err(format!(
+            "management fee must not exceed {}",
+            MAX_MANAGEMENT_FEE
+        )));
+    }
+
+    Ok(())
+}
+
+fn assert_withdrawal_queue(withdrawal_queue: &WithdrawalQueue) -> StdResult<()> {
+    if withdrawal_queue.buffer_rate > Decimal::one() {
+        return Err(StdError::generic_err("buffer rate must not exceed 1"));
+    }
+
+    if withdrawal_queue.unbond_rate.is_zero() || withdrawal_queue.unbond_rate > Decimal::one() {
+        return Err(StdError::generic_err(
+            "unbond rate must be greater than 0 and not exceed 1",
+        ));
+    }
+
+    Ok(())
+}
+
+// Only owner can execute it
+pub fn execute_update_strategy(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    strategy: Addr,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let strategy = deps.api.addr_validate(strategy.as_str())?;
+    let strategy_info = query_strategy_info(&deps.querier, &strategy)?;
+    if strategy_info.vault != env.contract.address || strategy_info.lp_token != config.lp_token {
+        return Err(StdError::generic_err(
+            "strategy must manage the LP token of the vault",
+        ));
+    }
+
+    if strategy_info.exited {
+        return Err(StdError::generic_err("strategy exited"));
+    }
+
+    // the current strategy sends its LP tokens back before they go to the new one
+    let mut messages = vec![];
+    if let Some(prev_strategy) = &config.strategy {
+        messages.push(strategy_msg(
+            prev_strategy,
+            &StrategyExecuteMsg::EmergencyExit {},
+        )?);
+    }
+    messages.push(callback_msg(&env, CallbackMsg::BondLiquidity {})?);
+
+    config.strategy = Some(strategy.clone());
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "update_strategy"),
+        ("strategy", strategy.as_str()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_emergency_exit(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let strategy = config
+        .strategy
+        .take()
+        .ok_or_else(|| StdError::generic_err("no strategy set"))?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new()
+        .add_message(strategy_msg(
+            &strategy,
+            &StrategyExecuteMsg::EmergencyExit {},
+        )?)
+        .add_attributes(vec![
+            ("action", "emergency_exit"),
+            ("strategy", strategy.as_str()),
+        ]))
+}
+
+pub fn execute_deposit(
+    deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let strategy_assets = query_strategy_assets(deps.as_ref(), &config)?;
+    // the deposit is already received
+    let liquid =
+        query_liquid(deps.as_ref(), &config, &env.contract.address)?.saturating_sub(amount);
+    let total_queued = TOTAL_QUEUED.load(deps.storage)?;
+    let total_lp = (strategy_assets + liquid).saturating_sub(total_queued);
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    let (fee_share, fee_msg) = charge_management_fee(deps, &env, &config, total_share)?;
+    let total_share = total_share + fee_share;
+
+    // the first deposit sets a share to an LP token
+    let share = if total_lp.is_zero() || total_share.is_zero() {
+        amount
+    } else {
+        amount.multiply_ratio(total_share, total_lp)
+    };
+    if share.is_zero() {
+        return Err(StdError::generic_err("deposit too small to mint any share"));
+    }
+
+    // the buffer is topped up before the rest goes to the strategy
+    let buffer_target = (total_lp + amount) * buffer_rate(&config);
+    let buffer = liquid.saturating_sub(total_queued);
+    let deposit_amount = amount - buffer_target.saturating_sub(buffer).min(amount);
+
+    let mut messages = vec![];
+    if let (Some(strategy), false) = (&config.strategy, deposit_amount.is_zero()) {
+        messages.push(deposit_msg(&config, strategy, deposit_amount)?);
+    }
+    messages.push(mint_msg(&config, &sender, share)?);
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_messages(messages)
+        .add_attributes(vec![
+            ("action", "deposit"),
+            ("sender", sender.as_str()),
+            ("amount", &amount.to_string()),
+            ("share", &share.to_string()),
+        ]))
+}
+
+pub fn execute_withdraw(
+    mut deps: DepsMut,
+    env: Env,
+    config: Config,
+    sender: Addr,
+    share: Uint128,
+) -> StdResult<Response> {
+    let strategy_assets = query_strategy_assets(deps.as_ref(), &config)?;
+    let liquid = query_liquid(deps.as_ref(), &config, &env.contract.address)?;
+    let total_queued = TOTAL_QUEUED.load(deps.storage)?;
+    let total_lp = (strategy_assets + liquid).saturating_sub(total_queued);
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    let (fee_share, fee_msg) = charge_management_fee(deps.branch(), &env, &config, total_share)?;
+    let total_share = total_share + fee_share;
+
+    let amount = share.multiply_ratio(total_lp, total_share);
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "withdrawal too small to unbond any LP token",
+        ));
+    }
+
+    let mut messages = vec![CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.share_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Burn { amount: share })?,
+    })];
+    let mut attributes = vec![
+        ("action", "withdraw".to_string()),
+        ("sender", sender.to_string()),
+        ("share", share.to_string()),
+        ("amount", amount.to_string()),
+    ];
+
+    let buffer = liquid.saturating_sub(total_queued);
+    let lp = Asset::new(AssetInfo::Cw20(config.lp_token.clone()), amount);
+    match (&config.withdrawal_queue, &config.strategy) {
+        // the buffer serves the withdrawal at once
+        _ if amount <= buffer => {
+            messages.push(lp.transfer_msg(&sender)?);
+        }
+        // the strategy serves what the buffer lacks at once
+        (None, strategy) => {
+            if let Some(strategy) = strategy {
+                messages.push(strategy_msg(
+                    strategy,
+                    &StrategyExecuteMsg::Withdraw {
+                        amount: amount - buffer,
+                    },
+                )?);
+            }
+            messages.push(lp.transfer_msg(&sender)?);
+        }
+        // the withdrawal exceeding the buffer waits for the harvests to fulfill it
+        _ => {
+            let withdrawal_id = NEXT_WITHDRAWAL_ID.load(deps.storage)?;
+            NEXT_WITHDRAWAL_ID.save(deps.storage, &(withdrawal_id + 1))?;
+            WITHDRAWALS.save(
+                deps.storage,
+                U64Key::new(withdrawal_id),
+                &Withdrawal {
+                    owner: sender,
+                    amount,
+                },
+            )?;
+            TOTAL_QUEUED.save(deps.storage, &(total_queued + amount))?;
+            attributes.push(("withdrawal_id", withdrawal_id.to_string()));
+        }
+    }
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_messages(messages)
+        .add_attributes(attributes))
+}
+
+pub fn execute_compound(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let strategy = config
+        .strategy
+        .clone()
+        .ok_or_else(|| StdError::generic_err("no strategy set"))?;
+
+    let last_harvest_time = LAST_HARVEST_TIME.load(deps.storage)?;
+    if last_harvest_time != 0 {
+        HARVEST_INTERVAL.save(
+            deps.storage,
+            &(env.block.time.seconds() - last_harvest_time),
+        )?;
+    }
+    LAST_HARVEST_TIME.save(deps.storage, &env.block.time.seconds())?;
+
+    let prev_total_assets = query_strategy_total_assets(&deps.querier, &strategy)?;
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    let (_, fee_msg) = charge_management_fee(deps, &env, &config, total_share)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msg)
+        .add_messages(vec![
+            strategy_msg(&strategy, &StrategyExecuteMsg::Harvest {})?,
+            callback_msg(&env, CallbackMsg::ProcessHarvest { prev_total_assets })?,
+        ])
+        .add_attributes(vec![
+            ("action", "compound"),
+            ("caller", info.sender.as_str()),
+        ]))
+}
+
+pub fn execute_process_harvest(
+    deps: DepsMut,
+    env: Env,
+    prev_total_assets: Uint128,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let strategy_assets = query_strategy_assets(deps.as_ref(), &config)?;
+    let harvested = strategy_assets.saturating_sub(prev_total_assets);
+
+    // the performance fee is charged on the harvest as the shares it is worth
+    let fee_amount = harvested * config.performance_fee;
+    let liquid = query_liquid(deps.as_ref(), &config, &env.contract.address)?;
+    let total_lp = (strategy_assets + liquid).saturating_sub(TOTAL_QUEUED.load(deps.storage)?);
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    let fee_share = if fee_amount.is_zero() || total_share.is_zero() {
+        Uint128::zero()
+    } else {
+        total_share.multiply_ratio(fee_amount, total_lp.checked_sub(fee_amount)?)
+    };
+
+    let mut messages = vec![];
+    if !fee_share.is_zero() {
+        messages.push(mint_msg(&config, &config.collector, fee_share)?);
+    }
+    messages.push(callback_msg(&env, CallbackMsg::BondLiquidity {})?);
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "process_harvest"),
+        ("harvested", &harvested.to_string()),
+        ("performance_fee_share", &fee_share.to_string()),
+    ]))
+}
+
+pub fn execute_bond_liquidity(deps: DepsMut, env: Env) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let liquid = query_liquid(deps.as_ref(), &config, &env.contract.address)?;
+    let strategy_assets = query_strategy_assets(deps.as_ref(), &config)?;
+    let mut total_queued = TOTAL_QUEUED.load(deps.storage)?;
+    let mut messages = vec![];
+
+    // what the liquid LP tokens lack to fulfill the queue is withdrawn from the strategy, up to
+    // the unbond rate
+    let unbond_amount = total_queued
+        .saturating_sub(liquid)
+        .min(strategy_assets * unbond_rate(&config));
+    if let (Some(strategy), false) = (&config.strategy, unbond_amount.is_zero()) {
+        messages.push(strategy_msg(
+            strategy,
+            &StrategyExecuteMsg::Withdraw {
+                amount: unbond_amount,
+            },
+        )?);
+    }
+
+    // the withdrawals are fulfilled in the order they were queued, the last one possibly in part
+    let mut available = liquid + unbond_amount;
+    let mut fulfilled: Vec<(u64, Withdrawal)> = vec![];
+    for item in read_queue(deps.storage, None) {
+        if available.is_zero() {
+            break;
+        }
+
+        let (withdrawal_id, mut withdrawal) = item?;
+        let amount = withdrawal.amount.min(available);
+        messages.push(
+            Asset::new(AssetInfo::Cw20(config.lp_token.clone()), amount)
+                .transfer_msg(&withdrawal.owner)?,
+        );
+        available -= amount;
+        total_queued -= amount;
+        withdrawal.amount -= amount;
+        fulfilled.push((withdrawal_id, withdrawal));
+    }
+
+    for (withdrawal_id, withdrawal) in fulfilled.iter() {
+        if withdrawal.amount.is_zero() {
+            WITHDRAWALS.remove(deps.storage, U64Key::new(*withdrawal_id));
+        } else {
+            WITHDRAWALS.save(deps.storage, U64Key::new(*withdrawal_id), withdrawal)?;
+        }
+    }
+    TOTAL_QUEUED.save(deps.storage, &total_queued)?;
+
+    // what exceeds the buffer goes to the strategy
+    let buffer_target = (strategy_assets - unbond_amount + available) * buffer_rate(&config);
+    let amount = available.saturating_sub(buffer_target);
+    if let (Some(strategy), false) = (&config.strategy, amount.is_zero()) {
+        messages.push(deposit_msg(&config, strategy, amount)?);
+    }
+
+    if messages.is_empty() {
+        return Ok(Response::new().add_attribute("action", "skip_compound"));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "bond_liquidity"),
+        ("amount", &amount.to_string()),
+        ("unbond_amount", &unbond_amount.to_string()),
+        ("fulfilled", &fulfilled.len().to_string()),
+    ]))
+}
+
+fn buffer_rate(config: &Config) -> Decimal {
+    config
+        .withdrawal_queue
+        .as_ref()
+        .map_or(Decimal::zero(), |withdrawal_queue| {
+            withdrawal_queue.buffer_rate
+        })
+}
+
+fn unbond_rate(config: &Config) -> Decimal {
+    config
+        .withdrawal_queue
+        .as_ref()
+        .map_or(Decimal::zero(), |withdrawal_queue| {
+            withdrawal_queue.unbond_rate
+        })
+}
+
+/// Returns the shares diluting the holders by the management fee accrued since it was last
+/// charged
+fn compute_management_fee_share(
+    deps: Deps,
+    env: &Env,
+    config: &Config,
+    total_share: Uint128,
+) -> StdResult<Uint128> {
+    let last_charged_time = LAST_CHARGED_TIME.load(deps.storage)?;
+    let elapsed = env.block.time.seconds() - last_charged_time;
+
+    Ok((total_share * config.management_fee).multiply_ratio(elapsed, SECONDS_PER_YEAR))
+}
+
+/// Charges the management fee accrued, returning the shares minted to the collector along with
+/// the message minting them
+fn charge_management_fee(
+    deps: DepsMut,
+    env: &Env,
+    config: &Config,
+    total_share: Uint128,
+) -> StdResult<(Uint128, Option<CosmosMsg>)> {
+    let fee_share = compute_management_fee_share(deps.as_ref(), env, config, total_share)?;
+
+    // a fee rounding down to zero keeps accruing from the last charge
+    if fee_share.is_zero() && !total_share.is_zero() && !config.management_fee.is_zero() {
+        return Ok((fee_share, None));
+    }
+
+    LAST_CHARGED_TIME.save(deps.storage, &env.block.time.seconds())?;
+    if fee_share.is_zero() {
+        return Ok((fee_share, None));
+    }
+
+    Ok((
+        fee_share,
+        Some(mint_msg(config, &config.collector, fee_share)?),
+    ))
+}
+
+fn mint_msg(config: &Config, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.share_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount,
+        })?,
+    }))
+}
+
+fn callback_msg(env: &Env, msg: CallbackMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg))?,
+    }))
+}
+
+fn strategy_msg(strategy: &Addr, msg: &StrategyExecuteMsg) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: strategy.to_string(),
+        funds: vec![],
+        msg: to_binary(msg)?,
+    }))
+}
+
+/// Deposit the LP tokens in the strategy
+fn deposit_msg(config: &Config, strategy: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: config.lp_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: strategy.to_string(),
+            amount,
+            msg: to_binary(&StrategyCw20HookMsg::Deposit {})?,
+        })?,
+    }))
+}
+
+/// Returns the LP tokens the strategy manages, none without a strategy
+fn query_strategy_assets(deps: Deps, config: &Config) -> StdResult<Uint128> {
+    match &config.strategy {
+        Some(strategy) => query_strategy_total_assets(&deps.querier, strategy),
+        None => Ok(Uint128::zero()),
+    }
+}
+
+fn query_liquid(deps: Deps, config: &Config, contract_addr: &Addr) -> StdResult<Uint128> {
+    AssetInfo::Cw20(config.lp_token.clone()).query_pool(&deps.querier, contract_addr)
+}
+
+/// Returns the LP tokens the shares are worth, those of the strategy and the vault less those
+/// owed to the queue
+fn query_total_lp(deps: Deps, config: &Config, contract_addr: &Addr) -> StdResult<Uint128> {
+    let strategy_assets = query_strategy_assets(deps, config)?;
+    let liquid = query_liquid(deps, config, contract_addr)?;
+    let total_queued = TOTAL_QUEUED.load(deps.storage)?;
+
+    Ok((strategy_assets + liquid).saturating_sub(total_queued))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::State {} => to_binary(&query_state(deps, env)?),
+        QueryMsg::ExchangeRate {} => to_binary(&query_exchange_rate(deps, env)?),
+        QueryMsg::Position { address } => to_binary(&query_position(deps, env, address)?),
+        QueryMsg::Fees {} => to_binary(&query_fees(deps, env)?),
+        QueryMsg::Withdrawal { withdrawal_id } => {
+            to_binary(&query_withdrawal(deps, env, withdrawal_id)?)
+        }
+        QueryMsg::Withdrawals {
+            owner,
+            start_after,
+            limit,
+        } => to_binary(&query_withdrawals(deps, env, owner, start_after, limit)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        lp_token: config.lp_token,
+        share_token: config.share_token,
+        strategy: config.strategy,
+        collector: config.collector,
+        performance_fee: config.performance_fee,
+        management_fee: config.management_fee,
+        withdrawal_queue: config.withdrawal_queue,
+    })
+}
+
+pub fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let total_queued = TOTAL_QUEUED.load(deps.storage)?;
+
+    Ok(StateResponse {
+        strategy_assets: query_strategy_assets(deps, &config)?,
+        total_share: query_supply(&deps.querier, &config.share_token)?,
+        buffer: query_liquid(deps, &config, &env.contract.address)?.saturating_sub(total_queued),
+        total_queued,
+    })
+}
+
+pub fn query_exchange_rate(deps: Deps, env: Env) -> StdResult<ExchangeRateResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let total_lp = query_total_lp(deps, &config, &env.contract.address)?;
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    // net of the management fee accrued
+    let total_share = total_share + compute_management_fee_share(deps, &env, &config, total_share)?;
+    let exchange_rate = if total_share.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(total_lp, total_share)
+    };
+
+    Ok(ExchangeRateResponse {
+        share_token: config.share_token,
+        lp_token: config.lp_token,
+        exchange_rate,
+    })
+}
+
+pub fn query_position(deps: Deps, env: Env, address: String) -> StdResult<PositionResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let address = deps.api.addr_validate(&address)?;
+
+    let share = query_token_balance(&deps.querier, &config.share_token, &address)?;
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+    let total_share = total_share + compute_management_fee_share(deps, &env, &config, total_share)?;
+    // valued as a withdrawal would, rounding down
+    let lp_amount = if total_share.is_zero() {
+        Uint128::zero()
+    } else {
+        let total_lp = query_total_lp(deps, &config, &env.contract.address)?;
+        share.multiply_ratio(total_lp, total_share)
+    };
+
+    Ok(PositionResponse {
+        address,
+        share,
+        lp_amount,
+    })
+}
+
+pub fn query_fees(deps: Deps, env: Env) -> StdResult<FeesResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let total_share = query_supply(&deps.querier, &config.share_token)?;
+
+    Ok(FeesResponse {
+        pending_management_fee_share: compute_management_fee_share(
+            deps,
+            &env,
+            &config,
+            total_share,
+        )?,
+        last_charged_time: LAST_CHARGED_TIME.load(deps.storage)?,
+        collector: config.collector,
+        performance_fee: config.performance_fee,
+        management_fee: config.management_fee,
+    })
+}
+
+pub fn query_withdrawal(deps: Deps, env: Env, withdrawal_id: u64) -> StdResult<WithdrawalResponse> {
+    let withdrawal = WITHDRAWALS
+        .may_load(deps.storage, U64Key::new(withdrawal_id))?
+        .ok_or_else(|| StdError::generic_err("withdrawal not found"))?;
+
+    withdrawal_response(deps, &env, withdrawal_id, withdrawal)
+}
+
+pub fn query_withdrawals(
+    deps: Deps,
+    env: Env,
+    owner: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<WithdrawalsResponse> {
+    let withdrawals = read_withdrawals(deps.storage, owner, start_after, limit)?
+        .into_iter()
+        .map(|(withdrawal_id, withdrawal)| {
+            withdrawal_response(deps, &env, withdrawal_id, withdrawal)
+        })
+        .collect::<StdResult<Vec<WithdrawalResponse>>>()?;
+
+    Ok(WithdrawalsResponse { withdrawals })
+}
+
+fn withdrawal_response(
+    deps: Deps,
+    env: &Env,
+    withdrawal_id: u64,
+    withdrawal: Withdrawal,
+) -> StdResult<WithdrawalResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    let mut queued_ahead = Uint128::zero();
+    for item in read_queue(deps.storage, None) {
+        let (id, ahead) = item?;
+        if id >= withdrawal_id {
+            break;
+        }
+        queued_ahead += ahead.amount;
+    }
+
+    // the next harvest fulfills from the liquid LP tokens, every harvest unbonding up to the
+    // unbond rate of the current bond toward the rest
+    let liquid = query_liquid(deps, &config, &env.contract.address)?;
+    let shortfall = (queued_ahead + withdrawal.amount).saturating_sub(liquid);
+    let unbond_amount = query_strategy_assets(deps, &config)? * unbond_rate(&config);
+    let harvests = if shortfall.is_zero() {
+        Some(1)
+    } else if unbond_amount.is_zero() {
+        None
+    } else {
+        Some(shortfall.u128().div_ceil(unbond_amount.u128()) as u64)
+    };
+
+    let harvest_interval = HARVEST_INTERVAL.load(deps.storage)?;
+    let eta = match harvests {
+        Some(harvests) if harvest_interval != 0 => {
+            Some(LAST_HARVEST_TIME.load(deps.storage)? + harvests * harvest_interval)
+        }
+        _ => None,
+    };
+
+    Ok(WithdrawalResponse {
+        withdrawal_id,
+        owner: withdrawal.owner,
+        amount: withdrawal.amount,
+        queued_ahead,
+        harvests,
+        eta,
+    })
+}
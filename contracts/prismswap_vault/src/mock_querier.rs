@@ -0,0 +1,154 @@
+use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, from_slice, to_binary, Addr, Coin, ContractResult, Empty, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
+};
+use std::collections::HashMap;
+
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use prismswap::strategy::{StrategyInfoResponse, StrategyQueryMsg, TotalAssetsResponse};
+
+/// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
+/// this uses our CustomQuerier.
+pub fn mock_dependencies(
+    contract_balance: &[Coin],
+) -> OwnedDeps<MockStorage, MockApi, WasmMockQuerier> {
+    let custom_querier: WasmMockQuerier =
+        WasmMockQuerier::new(MockQuerier::new(&[(MOCK_CONTRACT_ADDR, contract_balance)]));
+
+    OwnedDeps {
+        storage: MockStorage::default(),
+        api: MockApi::default(),
+        querier: custom_querier,
+    }
+}
+
+pub struct WasmMockQuerier {
+    base: MockQuerier<Empty>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    token_supplies: HashMap<String, Uint128>,
+    total_assets: Uint128,
+    strategy_info: StrategyInfoResponse,
+}
+
+impl Querier for WasmMockQuerier {
+    fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
+            Ok(v) => v,
+            Err(e) => {
+                return SystemResult::Err(SystemError::InvalidRequest {
+                    error: format!("Parsing query request: {}", e),
+                    request: bin_request.into(),
+                })
+            }
+        };
+        self.handle_query(&request)
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
+        match &request {
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. })
+                if from_binary::<StrategyQueryMsg>(msg).is_ok() =>
+            {
+                match from_binary(msg).unwrap() {
+                    StrategyQueryMsg::StrategyInfo {} => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&self.strategy_info).unwrap(),
+                    )),
+                    StrategyQueryMsg::TotalAssets {} => SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&TotalAssetsResponse {
+                            amount: self.total_assets,
+                        })
+                        .unwrap(),
+                    )),
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                        ))
+                    }
+                    Cw20QueryMsg::TokenInfo {} => {
+                        let total_supply = self
+                            .token_supplies
+                            .get(contract_addr)
+                            .copied()
+                            .unwrap_or_default();
+
+                        SystemResult::Ok(ContractResult::Ok(
+                            to_binary(&TokenInfoResponse {
+                                name: "Share Token".to_string(),
+                                symbol: "uVS".to_string(),
+                                decimals: 6,
+                                total_supply,
+                            })
+                            .unwrap(),
+                        ))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
+                }
+            }
+            _ => self.base.handle_query(request),
+        }
+    }
+}
+
+impl WasmMockQuerier {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
+        WasmMockQuerier {
+            base,
+            token_balances: HashMap::new(),
+            token_supplies: HashMap::new(),
+            total_assets: Uint128::zero(),
+            strategy_info: StrategyInfoResponse {
+                vault: Addr::unchecked(MOCK_CONTRACT_ADDR),
+                lp_token: Addr::unchecked("liquidity0000"),
+                exited: false,
+            },
+        }
+    }
+
+    pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
+        self.token_balances = balances
+            .iter()
+            .map(|(contract_addr, balances)| {
+                (
+                    contract_addr.to_string(),
+                    balances
+                        .iter()
+                        .map(|(addr, balance)| (addr.to_string(), **balance))
+                        .collect(),
+                )
+            })
+            .collect();
+    }
+
+    pub fn with_token_supplies(&mut self, supplies: &[(&String, &Uint128)]) {
+        self.token_supplies = supplies
+            .iter()
+            .map(|(contract_addr, supply)| (contract_addr.to_string(), **supply))
+            .collect();
+    }
+
+    pub fn with_strategy_info(&mut self, vault: &str, lp_token: &str, exited: bool) {
+        self.strategy_info = StrategyInfoResponse {
+            vault: Addr::unchecked(vault),
+            lp_token: Addr::unchecked(lp_token),
+            exited,
+        };
+    }
+
+    pub fn with_total_assets(&mut self, total_assets: Uint128) {
+        self.total_assets = total_assets;
+    }
+}
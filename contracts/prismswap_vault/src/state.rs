@@ -0,0 +1,75 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Bound, Item, Map, U64Key};
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+use prismswap::vault::WithdrawalQueue;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub lp_token: Addr,
+    /// the share token, set once instantiated
+    pub share_token: Addr,
+    /// the strategy the LP tokens are put to work in, none to keep them in the vault
+    pub strategy: Option<Addr>,
+    pub collector: Addr,
+    pub performance_fee: Decimal,
+    pub management_fee: Decimal,
+    pub withdrawal_queue: Option<WithdrawalQueue>,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+/// the time the management fee was last charged
+pub const LAST_CHARGED_TIME: Item<u64> = Item::new("last_charged_time");
+
+/// the time of the last harvest and the interval since the one before, zero until known
+pub const LAST_HARVEST_TIME: Item<u64> = Item::new("last_harvest_time");
+pub const HARVEST_INTERVAL: Item<u64> = Item::new("harvest_interval");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Withdrawal {
+    pub owner: Addr,
+    /// the LP tokens still owed, lowered as it is fulfilled in part
+    pub amount: Uint128,
+}
+
+pub const WITHDRAWALS: Map<U64Key, Withdrawal> = Map::new("withdrawals");
+
+/// the id of the next withdrawal queued
+pub const NEXT_WITHDRAWAL_ID: Item<u64> = Item::new("next_withdrawal_id");
+/// the LP tokens owed to the queued withdrawals
+pub const TOTAL_QUEUED: Item<Uint128> = Item::new("total_queued");
+
+pub fn read_withdrawals(
+    storage: &dyn Storage,
+    owner: Option<Addr>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Withdrawal)>> {
+    let start = start_after_id_bound(start_after);
+
+    read_queue(storage, start)
+        .filter(|item| match (&owner, item) {
+            (Some(owner), Ok((_, withdrawal))) => withdrawal.owner == *owner,
+            _ => true,
+        })
+        .take(calc_limit(limit))
+        .collect()
+}
+
+/// Iterates over the queued withdrawals in the order they are fulfilled
+pub fn read_queue<'a>(
+    storage: &'a dyn Storage,
+    start: Option<Bound>,
+) -> impl Iterator<Item = StdResult<(u64, Withdrawal)>> + 'a {
+    WITHDRAWALS
+        .range(storage, start, None, Order::Ascending)
+        .map(|item| {
+            let (k, v) = item?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok((u64::from_be_bytes(id), v))
+        })
+}
@@ -0,0 +1,997 @@
+use crate::contract::{execute, instantiate, query, reply};
+use crate::mock_querier::mock_dependencies;
+
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Addr, ContractResult, CosmosMsg, Decimal, Deps, DepsMut, Reply,
+    StdError, SubMsg, SubMsgExecutionResponse, Uint128, WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, MinterResponse};
+use prismswap::strategy::{StrategyCw20HookMsg, StrategyExecuteMsg};
+use prismswap::token::InstantiateMsg as TokenInstantiateMsg;
+use prismswap::vault::{
+    CallbackMsg, ConfigResponse, Cw20HookMsg, ExchangeRateResponse, ExecuteMsg, FeesResponse,
+    InstantiateMsg, PositionResponse, QueryMsg, StateResponse, WithdrawalQueue, WithdrawalResponse,
+    WithdrawalsResponse,
+};
+
+fn instantiate_msg() -> InstantiateMsg {
+    InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        lp_token: Addr::unchecked("liquidity0000"),
+        token_code_id: 10u64,
+        collector: Addr::unchecked("collector0000"),
+        performance_fee: Decimal::zero(),
+        management_fee: Decimal::zero(),
+        withdrawal_queue: None,
+    }
+}
+
+/// Instantiates the vault, storing share0000 as its share token
+fn instantiate_vault(mut deps: DepsMut, msg: InstantiateMsg) {
+    let _res = instantiate(deps.branch(), mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(vec![10, 9, 115, 104, 97, 114, 101, 48, 48, 48, 48].into()),
+        }),
+    };
+    let _res = reply(deps, mock_env(), reply_msg).unwrap();
+}
+
+/// Sets the strategy the vault puts its LP tokens to work in
+fn set_strategy(deps: DepsMut, strategy: &str) {
+    let _res = execute(
+        deps,
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateStrategy {
+            strategy: Addr::unchecked(strategy),
+        },
+    )
+    .unwrap();
+}
+
+fn deposit_msg(amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "liquidity0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: "strategy0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&StrategyCw20HookMsg::Deposit {}).unwrap(),
+        })
+        .unwrap(),
+    }))
+}
+
+fn strategy_msg(strategy: &str, msg: StrategyExecuteMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: strategy.to_string(),
+        funds: vec![],
+        msg: to_binary(&msg).unwrap(),
+    }))
+}
+
+fn mint_msg(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "share0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Mint {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+fn callback_msg(msg: CallbackMsg) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+        funds: vec![],
+        msg: to_binary(&ExecuteMsg::Callback(msg)).unwrap(),
+    }))
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = instantiate_msg();
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::reply_on_success(
+            WasmMsg::Instantiate {
+                code_id: 10u64,
+                msg: to_binary(&TokenInstantiateMsg {
+                    name: "PrismSwap vault share".to_string(),
+                    symbol: "uVS".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![],
+                    mint: Some(MinterResponse {
+                        minter: MOCK_CONTRACT_ADDR.to_string(),
+                        cap: None,
+                    }),
+                })
+                .unwrap(),
+                funds: vec![],
+                admin: None,
+                label: "".to_string(),
+            },
+            1
+        )]
+    );
+
+    let reply_msg = Reply {
+        id: 1,
+        result: ContractResult::Ok(SubMsgExecutionResponse {
+            events: vec![],
+            data: Some(vec![10, 9, 115, 104, 97, 114, 101, 48, 48, 48, 48].into()),
+        }),
+    };
+    let _res = reply(deps.as_mut(), mock_env(), reply_msg.clone()).unwrap();
+    let err = reply(deps.as_mut(), mock_env(), reply_msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: msg.owner,
+            lp_token: msg.lp_token,
+            share_token: Addr::unchecked("share0000"),
+            strategy: None,
+            collector: msg.collector,
+            performance_fee: msg.performance_fee,
+            management_fee: msg.management_fee,
+            withdrawal_queue: msg.withdrawal_queue,
+        }
+    );
+}
+
+#[test]
+fn update_config() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_vault(deps.as_mut(), instantiate_msg());
+
+    let msg = ExecuteMsg::UpdateConfig {
+        owner: Some(Addr::unchecked("owner0001")),
+        collector: Some(Addr::unchecked("collector0001")),
+        performance_fee: Some(Decimal::percent(10)),
+        management_fee: None,
+        withdrawal_queue: None,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::UpdateConfig {
+            owner: None,
+            collector: None,
+            performance_fee: None,
+            management_fee: Some(Decimal::percent(6)),
+            withdrawal_queue: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("management fee must not exceed 0.05")
+    );
+
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("owner0000", &[]), msg).unwrap();
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("owner0001"));
+    assert_eq!(config.collector, Addr::unchecked("collector0001"));
+    assert_eq!(config.performance_fee, Decimal::percent(10));
+}
+
+#[test]
+fn deposit_and_withdraw() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_vault(deps.as_mut(), instantiate_msg());
+    set_strategy(deps.as_mut(), "strategy0000");
+
+    let receive_msg = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(amount),
+            msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+        })
+    };
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        receive_msg(100),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // the first deposit mints a share per LP token
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        receive_msg(100),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![deposit_msg(100), mint_msg("addr0000", 100)]
+    );
+
+    // compounding raised the strategy to 150 LP tokens for the 100 shares
+    deps.querier.with_total_assets(Uint128::from(150u128));
+    deps.querier
+        .with_token_supplies(&[(&"share0000".to_string(), &Uint128::from(100u128))]);
+    let state: StateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(
+        state,
+        StateResponse {
+            strategy_assets: Uint128::from(150u128),
+            total_share: Uint128::from(100u128),
+            buffer: Uint128::zero(),
+            total_queued: Uint128::zero(),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        receive_msg(30),
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], attr("share", "20"));
+
+    let withdraw_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::from(20u128),
+        msg: to_binary(&Cw20HookMsg::Withdraw {}).unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        withdraw_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        withdraw_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "share0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::from(20u128),
+                })
+                .unwrap(),
+            })),
+            strategy_msg(
+                "strategy0000",
+                StrategyExecuteMsg::Withdraw {
+                    amount: Uint128::from(30u128),
+                }
+            ),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "liquidity0000".to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0000".to_string(),
+                    amount: Uint128::from(30u128),
+                })
+                .unwrap(),
+            })),
+        ]
+    );
+}
+
+#[test]
+fn share_value() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_vault(deps.as_mut(), instantiate_msg());
+
+    let query_rate = |deps: Deps| -> ExchangeRateResponse {
+        from_binary(&query(deps, mock_env(), QueryMsg::ExchangeRate {}).unwrap()).unwrap()
+    };
+    let query_position = |deps: Deps, address: &str| -> PositionResponse {
+        from_binary(
+            &query(
+                deps,
+                mock_env(),
+                QueryMsg::Position {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap()
+    };
+
+    // before the first deposit, a share is worth an LP token
+    assert_eq!(
+        query_rate(deps.as_ref()),
+        ExchangeRateResponse {
+            share_token: Addr::unchecked("share0000"),
+            lp_token: Addr::unchecked("liquidity0000"),
+            exchange_rate: Decimal::one(),
+        }
+    );
+    assert_eq!(
+        query_position(deps.as_ref(), "addr0000").lp_amount,
+        Uint128::zero()
+    );
+
+    // the shares moved to addr0001 are worth the same LP tokens
+    set_strategy(deps.as_mut(), "strategy0000");
+    deps.querier.with_total_assets(Uint128::from(200u128));
+    deps.querier
+        .with_token_supplies(&[(&"share0000".to_string(), &Uint128::from(150u128))]);
+    deps.querier.with_token_balances(&[(
+        &"share0000".to_string(),
+        &[
+            (&"addr0000".to_string(), &Uint128::from(100u128)),
+            (&"addr0001".to_string(), &Uint128::from(50u128)),
+        ],
+    )]);
+    assert_eq!(
+        query_rate(deps.as_ref()).exchange_rate,
+        Decimal::from_ratio(4u128, 3u128)
+    );
+    assert_eq!(
+        query_position(deps.as_ref(), "addr0000"),
+        PositionResponse {
+            address: Addr::unchecked("addr0000"),
+            share: Uint128::from(100u128),
+            lp_amount: Uint128::from(133u128),
+        }
+    );
+    assert_eq!(
+        query_position(deps.as_ref(), "addr0001").lp_amount,
+        Uint128::from(66u128)
+    );
+}
+
+#[test]
+fn compound() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_vault(deps.as_mut(), instantiate_msg());
+    set_strategy(deps.as_mut(), "strategy0000");
+
+    deps.querier.with_total_assets(Uint128::from(1000u128));
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            strategy_msg("strategy0000", StrategyExecuteMsg::Harvest {}),
+            callback_msg(CallbackMsg::ProcessHarvest {
+                prev_total_assets: Uint128::from(1000u128),
+            }),
+        ]
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![]);
+    assert_eq!(res.attributes, vec![attr("action", "skip_compound")]);
+
+    // the harvest raised the strategy by 100 LP tokens, 50 more being left in the vault
+    deps.querier.with_total_assets(Uint128::from(1100u128));
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(50u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProcessHarvest {
+            prev_total_assets: Uint128::from(1000u128),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![callback_msg(CallbackMsg::BondLiquidity {})]
+    );
+    assert_eq!(res.attributes[1], attr("harvested", "100"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![deposit_msg(50)]);
+}
+
+#[test]
+fn fees() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.performance_fee = Decimal::percent(31);
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("performance fee must not exceed 0.3")
+    );
+
+    msg.performance_fee = Decimal::percent(10);
+    msg.management_fee = Decimal::percent(2);
+    instantiate_vault(deps.as_mut(), msg);
+    set_strategy(deps.as_mut(), "strategy0000");
+
+    deps.querier.with_total_assets(Uint128::from(1_000_000u128));
+    deps.querier
+        .with_token_supplies(&[(&"share0000".to_string(), &Uint128::from(1_000_000u128))]);
+
+    // half a year accrues a 1% management fee
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(15_768_000);
+    let fees: FeesResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Fees {}).unwrap()).unwrap();
+    assert_eq!(
+        fees,
+        FeesResponse {
+            collector: Addr::unchecked("collector0000"),
+            performance_fee: Decimal::percent(10),
+            management_fee: Decimal::percent(2),
+            last_charged_time: mock_env().block.time.seconds(),
+            pending_management_fee_share: Uint128::from(10_000u128),
+        }
+    );
+    let rate: ExchangeRateResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::ExchangeRate {}).unwrap())
+            .unwrap();
+    assert_eq!(
+        rate.exchange_rate,
+        Decimal::from_ratio(1_000_000u128, 1_010_000u128)
+    );
+
+    // a deposit charges the fee first, pricing its shares net of it
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("liquidity0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(1000u128),
+            msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            mint_msg("collector0000", 10_000),
+            deposit_msg(1000),
+            mint_msg("addr0000", 1010),
+        ]
+    );
+
+    let fees: FeesResponse =
+        from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::Fees {}).unwrap()).unwrap();
+    assert_eq!(fees.last_charged_time, env.block.time.seconds());
+    assert_eq!(fees.pending_management_fee_share, Uint128::zero());
+
+    // the performance fee is minted as the shares 10% of the harvest is worth
+    deps.querier.with_total_assets(Uint128::from(1_010_000u128));
+    let res = execute(
+        deps.as_mut(),
+        env,
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProcessHarvest {
+            prev_total_assets: Uint128::from(1_000_000u128),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            mint_msg("collector0000", 991),
+            callback_msg(CallbackMsg::BondLiquidity {}),
+        ]
+    );
+}
+
+#[test]
+fn withdrawal_queue() {
+    let mut deps = mock_dependencies(&[]);
+
+    let mut msg = instantiate_msg();
+    msg.withdrawal_queue = Some(WithdrawalQueue {
+        buffer_rate: Decimal::percent(10),
+        unbond_rate: Decimal::zero(),
+    });
+    let err = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("unbond rate must be greater than 0 and not exceed 1")
+    );
+
+    msg.withdrawal_queue = Some(WithdrawalQueue {
+        buffer_rate: Decimal::percent(10),
+        unbond_rate: Decimal::percent(20),
+    });
+    instantiate_vault(deps.as_mut(), msg);
+    set_strategy(deps.as_mut(), "strategy0000");
+
+    let lp_transfer = |recipient: &str, amount: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "liquidity0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount: Uint128::from(amount),
+            })
+            .unwrap(),
+        }))
+    };
+    let withdraw_msg = |sender: &str, share: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::from(share),
+            msg: to_binary(&Cw20HookMsg::Withdraw {}).unwrap(),
+        })
+    };
+    let burn_msg = |share: u128| {
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "share0000".to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::from(share),
+            })
+            .unwrap(),
+        }))
+    };
+
+    // 900 LP tokens in the strategy and 100 in the buffer for 1000 shares
+    deps.querier.with_total_assets(Uint128::from(900u128));
+    deps.querier
+        .with_token_supplies(&[(&"share0000".to_string(), &Uint128::from(1000u128))]);
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(200u128))],
+    )]);
+
+    // a deposit tops up the buffer to its target before depositing the rest in the strategy
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages[0], deposit_msg(90));
+    assert_eq!(res.attributes[3], attr("share", "100"));
+
+    // the buffer serves a withdrawal at once
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        withdraw_msg("addr0000", 50),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![burn_msg(50), lp_transfer("addr0000", 50)]
+    );
+
+    // withdrawals exceeding the buffer are queued
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        withdraw_msg("addr0000", 500),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![burn_msg(500)]);
+    assert_eq!(res.attributes[4], attr("withdrawal_id", "1"));
+
+    deps.querier
+        .with_token_supplies(&[(&"share0000".to_string(), &Uint128::from(500u128))]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        withdraw_msg("addr0001", 100),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![burn_msg(100)]);
+    assert_eq!(res.attributes[3], attr("amount", "100"));
+    assert_eq!(res.attributes[4], attr("withdrawal_id", "2"));
+
+    let state: StateResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(state.buffer, Uint128::zero());
+    assert_eq!(state.total_queued, Uint128::from(600u128));
+
+    // two harvests an hour apart set the interval the fulfillment is expected at
+    let mut env = mock_env();
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap();
+    env.block.time = env.block.time.plus_seconds(3600);
+    let _res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap();
+
+    // 100 liquid LP tokens, then 180 withdrawn from the strategy every harvest
+    let withdrawals: WithdrawalsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Withdrawals {
+                owner: None,
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        withdrawals.withdrawals,
+        vec![
+            WithdrawalResponse {
+                withdrawal_id: 1,
+                owner: Addr::unchecked("addr0000"),
+                amount: Uint128::from(500u128),
+                queued_ahead: Uint128::zero(),
+                harvests: Some(3),
+                eta: Some(env.block.time.seconds() + 3 * 3600),
+            },
+            WithdrawalResponse {
+                withdrawal_id: 2,
+                owner: Addr::unchecked("addr0001"),
+                amount: Uint128::from(100u128),
+                queued_ahead: Uint128::from(500u128),
+                harvests: Some(3),
+                eta: Some(env.block.time.seconds() + 3 * 3600),
+            },
+        ]
+    );
+
+    // a harvest without rewards still fulfills the queue
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::ProcessHarvest {
+            prev_total_assets: Uint128::from(900u128),
+        }),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![callback_msg(CallbackMsg::BondLiquidity {})]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            strategy_msg(
+                "strategy0000",
+                StrategyExecuteMsg::Withdraw {
+                    amount: Uint128::from(180u128),
+                }
+            ),
+            lp_transfer("addr0000", 280),
+        ]
+    );
+
+    let withdrawal: WithdrawalResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            env.clone(),
+            QueryMsg::Withdrawal { withdrawal_id: 1 },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(withdrawal.amount, Uint128::from(220u128));
+
+    // the compounded LP tokens fulfill the rest, the excess over the buffer going to the strategy
+    deps.querier.with_total_assets(Uint128::from(720u128));
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(500u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            lp_transfer("addr0000", 220),
+            lp_transfer("addr0001", 100),
+            deposit_msg(90),
+        ]
+    );
+
+    let err = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::Withdrawal { withdrawal_id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("withdrawal not found"));
+
+    let state: StateResponse =
+        from_binary(&query(deps.as_ref(), env, QueryMsg::State {}).unwrap()).unwrap();
+    assert_eq!(state.total_queued, Uint128::zero());
+}
+
+#[test]
+fn update_strategy() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_vault(deps.as_mut(), instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::Compound {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no strategy set"));
+
+    // without a strategy the deposits stay in the vault
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("liquidity0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(100u128),
+            msg: to_binary(&Cw20HookMsg::Deposit {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![mint_msg("addr0000", 100)]);
+
+    let update_msg = |strategy: &str| ExecuteMsg::UpdateStrategy {
+        strategy: Addr::unchecked(strategy),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        update_msg("strategy0000"),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    deps.querier
+        .with_strategy_info(MOCK_CONTRACT_ADDR, "liquidity0001", false);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        update_msg("strategy0000"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("strategy must manage the LP token of the vault")
+    );
+
+    deps.querier
+        .with_strategy_info(MOCK_CONTRACT_ADDR, "liquidity0000", true);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        update_msg("strategy0000"),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("strategy exited"));
+
+    // the LP tokens held go to the first strategy
+    deps.querier
+        .with_strategy_info(MOCK_CONTRACT_ADDR, "liquidity0000", false);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        update_msg("strategy0000"),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![callback_msg(CallbackMsg::BondLiquidity {})]
+    );
+
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(100u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        ExecuteMsg::Callback(CallbackMsg::BondLiquidity {}),
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![deposit_msg(100)]);
+
+    // the next strategy exits the current one first
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        update_msg("strategy0001"),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            strategy_msg("strategy0000", StrategyExecuteMsg::EmergencyExit {}),
+            callback_msg(CallbackMsg::BondLiquidity {}),
+        ]
+    );
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.strategy, Some(Addr::unchecked("strategy0001")));
+}
+
+#[test]
+fn emergency_exit() {
+    let mut deps = mock_dependencies(&[]);
+    instantiate_vault(deps.as_mut(), instantiate_msg());
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("no strategy set"));
+
+    set_strategy(deps.as_mut(), "strategy0000");
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("owner0000", &[]),
+        ExecuteMsg::EmergencyExit {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![strategy_msg(
+            "strategy0000",
+            StrategyExecuteMsg::EmergencyExit {}
+        )]
+    );
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(config.strategy, None);
+
+    // the LP tokens sent back serve the withdrawals from the vault
+    deps.querier
+        .with_token_supplies(&[(&"share0000".to_string(), &Uint128::from(100u128))]);
+    deps.querier.with_token_balances(&[(
+        &"liquidity0000".to_string(),
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::from(150u128))],
+    )]);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("share0000", &[]),
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::from(20u128),
+            msg: to_binary(&Cw20HookMsg::Withdraw {}).unwrap(),
+        }),
+    )
+    .unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(res.attributes[3], attr("amount", "30"));
+}
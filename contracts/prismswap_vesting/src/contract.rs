@@ -0,0 +1,328 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Storage, Uint128,
+};
+
+use cw_storage_plus::U64Key;
+
+use crate::state::{
+    read_all_vestings, read_vestings, Config, Vesting, CONFIG, NEXT_VESTING_ID, VESTINGS,
+};
+
+use cw20::Cw20ReceiveMsg;
+use prismswap::asset::{Asset, AssetInfo};
+use prismswap::vesting::{
+    ClaimableResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    VestingResponse, VestingSchedule, VestingsResponse,
+};
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let config = Config {
+        owner: deps.api.addr_validate(msg.owner.as_str())?,
+        prism_token: deps.api.addr_validate(msg.prism_token.as_str())?,
+    };
+
+    CONFIG.save(deps.storage, &config)?;
+    NEXT_VESTING_ID.save(deps.storage, &1)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+    match msg {
+        ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
+        ExecuteMsg::UpdateConfig { owner } => execute_update_config(deps, info, owner),
+        ExecuteMsg::Claim { vesting_id } => execute_claim(deps, env, info, vesting_id),
+        ExecuteMsg::Clawback {
+            recipient,
+            vesting_id,
+        } => execute_clawback(deps, env, info, recipient, vesting_id),
+    }
+}
+
+pub fn receive_cw20(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // only the owner can vest PRISM
+    if info.sender != config.prism_token || cw20_msg.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::CreateSchedule {
+            recipient,
+            schedule,
+        } => create_schedule(deps, env, recipient, schedule, cw20_msg.amount),
+    }
+}
+
+pub fn create_schedule(
+    deps: DepsMut,
+    env: Env,
+    recipient: Addr,
+    schedule: VestingSchedule,
+    amount: Uint128,
+) -> StdResult<Response> {
+    deps.api.addr_validate(recipient.as_str())?;
+    if !schedule.is_valid() || schedule.end_time <= env.block.time.seconds() {
+        return Err(StdError::generic_err(
+            "schedule must start before its cliff, end after its cliff and in the future",
+        ));
+    }
+
+    let vesting_id = NEXT_VESTING_ID.load(deps.storage)?;
+    NEXT_VESTING_ID.save(deps.storage, &(vesting_id + 1))?;
+    VESTINGS.save(
+        deps.storage,
+        (&recipient, U64Key::new(vesting_id)),
+        &Vesting {
+            schedule,
+            total_amount: amount,
+            claimed_amount: Uint128::zero(),
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "create_schedule"),
+        ("vesting_id", &vesting_id.to_string()),
+        ("recipient", recipient.as_str()),
+        ("amount", &amount.to_string()),
+    ]))
+}
+
+// Only owner can execute it
+pub fn execute_update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    owner: Option<Addr>,
+) -> StdResult<Response> {
+    let mut config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(owner) = owner {
+        deps.api.addr_validate(owner.as_str())?;
+        config.owner = owner;
+    }
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "update_config"))
+}
+
+pub fn execute_claim(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    vesting_id: Option<u64>,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+    let vestings = match vesting_id {
+        Some(vesting_id) => vec![(
+            vesting_id,
+            load_vesting(deps.as_ref(), &info.sender, vesting_id)?,
+        )],
+        None => read_all_vestings(deps.storage, &info.sender)?,
+    };
+
+    let now = env.block.time.seconds();
+    let mut amount = Uint128::zero();
+    for (vesting_id, mut vesting) in vestings {
+        let claimable_amount = vesting.vested_amount(now) - vesting.claimed_amount;
+        if claimable_amount.is_zero() {
+            continue;
+        }
+
+        vesting.claimed_amount += claimable_amount;
+        amount += claimable_amount;
+        save_vesting(deps.storage, &info.sender, vesting_id, &vesting)?;
+    }
+    if amount.is_zero() {
+        return Err(StdError::generic_err("nothing to claim"));
+    }
+
+    Ok(Response::new()
+        .add_message(
+            Asset::new(AssetInfo::Cw20(config.prism_token), amount).transfer_msg(&info.sender)?,
+        )
+        .add_attributes(vec![
+            ("action", "claim"),
+            ("recipient", info.sender.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+// Only owner can execute it
+pub fn execute_clawback(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+    vesting_id: u64,
+) -> StdResult<Response> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    // permission check
+    if info.sender != config.owner {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let mut vesting = load_vesting(deps.as_ref(), &recipient, vesting_id)?;
+    let now = env.block.time.seconds();
+    if now >= vesting.schedule.end_time {
+        return Err(StdError::generic_err("vesting is over"));
+    }
+
+    // end the schedule now with what is vested so far
+    let vested_amount = vesting.vested_amount(now);
+    let amount = vesting.total_amount - vested_amount;
+    vesting.total_amount = vested_amount;
+    vesting.schedule = VestingSchedule {
+        start_time: vesting.schedule.start_time.min(now),
+        cliff_time: vesting.schedule.cliff_time.min(now),
+        end_time: now,
+    };
+    save_vesting(deps.storage, &recipient, vesting_id, &vesting)?;
+
+    Ok(Response::new()
+        .add_message(
+            Asset::new(AssetInfo::Cw20(config.prism_token), amount).transfer_msg(&config.owner)?,
+        )
+        .add_attributes(vec![
+            ("action", "clawback"),
+            ("vesting_id", &vesting_id.to_string()),
+            ("recipient", recipient.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+fn load_vesting(deps: Deps, recipient: &Addr, vesting_id: u64) -> StdResult<Vesting> {
+    VESTINGS
+        .may_load(deps.storage, (recipient, U64Key::new(vesting_id)))?
+        .ok_or_else(|| {
+            StdError::generic_err(format!("{} has no vesting {}", recipient, vesting_id))
+        })
+}
+
+/// saves the vesting, or removes it once fully claimed
+fn save_vesting(
+    storage: &mut dyn Storage,
+    recipient: &Addr,
+    vesting_id: u64,
+    vesting: &Vesting,
+) -> StdResult<()> {
+    let key = (recipient, U64Key::new(vesting_id));
+    if vesting.claimed_amount == vesting.total_amount {
+        VESTINGS.remove(storage, key);
+        Ok(())
+    } else {
+        VESTINGS.save(storage, key, vesting)
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Vesting {
+            recipient,
+            vesting_id,
+        } => to_binary(&query_vesting(deps, env, recipient, vesting_id)?),
+        QueryMsg::Vestings {
+            recipient,
+            start_after,
+            limit,
+        } => to_binary(&query_vestings(deps, env, recipient, start_after, limit)?),
+        QueryMsg::Claimable { recipient } => to_binary(&query_claimable(deps, env, recipient)?),
+    }
+}
+
+pub fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config: Config = CONFIG.load(deps.storage)?;
+
+    Ok(ConfigResponse {
+        owner: config.owner,
+        prism_token: config.prism_token,
+    })
+}
+
+pub fn query_vesting(
+    deps: Deps,
+    env: Env,
+    recipient: Addr,
+    vesting_id: u64,
+) -> StdResult<VestingResponse> {
+    let vesting = load_vesting(deps, &recipient, vesting_id)?;
+
+    Ok(vesting_response(&env, recipient, vesting_id, vesting))
+}
+
+pub fn query_vestings(
+    deps: Deps,
+    env: Env,
+    recipient: Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<VestingsResponse> {
+    let vestings = read_vestings(deps.storage, &recipient, start_after, limit)?
+        .into_iter()
+        .map(|(vesting_id, vesting)| vesting_response(&env, recipient.clone(), vesting_id, vesting))
+        .collect();
+
+    Ok(VestingsResponse { vestings })
+}
+
+pub fn query_claimable(deps: Deps, env: Env, recipient: Addr) -> StdResult<ClaimableResponse> {
+    let mut res = ClaimableResponse {
+        recipient: recipient.clone(),
+        total_amount: Uint128::zero(),
+        vested_amount: Uint128::zero(),
+        claimed_amount: Uint128::zero(),
+        claimable_amount: Uint128::zero(),
+    };
+    for (vesting_id, vesting) in read_all_vestings(deps.storage, &recipient)? {
+        let vesting = vesting_response(&env, recipient.clone(), vesting_id, vesting);
+        res.total_amount += vesting.total_amount;
+        res.vested_amount += vesting.vested_amount;
+        res.claimed_amount += vesting.claimed_amount;
+        res.claimable_amount += vesting.claimable_amount;
+    }
+
+    Ok(res)
+}
+
+fn vesting_response(
+    env: &Env,
+    recipient: Addr,
+    vesting_id: u64,
+    vesting: Vesting,
+) -> VestingResponse {
+    let vested_amount = vesting.vested_amount(env.block.time.seconds());
+
+    VestingResponse {
+        vesting_id,
+        recipient,
+        schedule: vesting.schedule,
+        total_amount: vesting.total_amount,
+        vested_amount,
+        claimed_amount: vesting.claimed_amount,
+        claimable_amount: vested_amount - vesting.claimed_amount,
+    }
+}
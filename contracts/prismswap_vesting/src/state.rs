@@ -0,0 +1,68 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U64Key};
+use prismswap::pagination::{calc_limit, start_after_id_bound};
+use prismswap::vesting::VestingSchedule;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub owner: Addr,
+    pub prism_token: Addr,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Vesting {
+    pub schedule: VestingSchedule,
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+}
+
+impl Vesting {
+    pub fn vested_amount(&self, time: u64) -> Uint128 {
+        self.schedule.vested_amount(self.total_amount, time)
+    }
+}
+
+/// the vestings of each recipient, by id
+pub const VESTINGS: Map<(&Addr, U64Key), Vesting> = Map::new("vestings");
+
+/// the id of the next vesting created
+pub const NEXT_VESTING_ID: Item<u64> = Item::new("next_vesting_id");
+
+pub fn read_vestings(
+    storage: &dyn Storage,
+    recipient: &Addr,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Vesting)>> {
+    let start = start_after_id_bound(start_after);
+
+    VESTINGS
+        .prefix(recipient)
+        .range(storage, start, None, Order::Ascending)
+        .take(calc_limit(limit))
+        .map(vesting_item)
+        .collect()
+}
+
+pub fn read_all_vestings(
+    storage: &dyn Storage,
+    recipient: &Addr,
+) -> StdResult<Vec<(u64, Vesting)>> {
+    VESTINGS
+        .prefix(recipient)
+        .range(storage, None, None, Order::Ascending)
+        .map(vesting_item)
+        .collect()
+}
+
+fn vesting_item(item: StdResult<(Vec<u8>, Vesting)>) -> StdResult<(u64, Vesting)> {
+    let (k, v) = item?;
+    let mut id = [0u8; 8];
+    id.copy_from_slice(&k);
+    Ok((u64::from_be_bytes(id), v))
+}
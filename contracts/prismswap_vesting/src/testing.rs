@@ -0,0 +1,348 @@
+use crate::contract::{execute, instantiate, query};
+
+use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+use cosmwasm_std::{
+    attr, from_binary, to_binary, Addr, CosmosMsg, Deps, DepsMut, Env, StdError, SubMsg, Uint128,
+    WasmMsg,
+};
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use prismswap::vesting::{
+    ClaimableResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+    VestingResponse, VestingSchedule, VestingsResponse,
+};
+
+fn mock_env_after(seconds: u64) -> Env {
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(seconds);
+    env
+}
+
+fn schedule(cliff: u64, end: u64) -> VestingSchedule {
+    let now = mock_env().block.time.seconds();
+    VestingSchedule {
+        start_time: now,
+        cliff_time: now + cliff,
+        end_time: now + end,
+    }
+}
+
+fn create_schedule_msg(sender: &str, recipient: &str, schedule: VestingSchedule) -> ExecuteMsg {
+    ExecuteMsg::Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::from(1000u128),
+        msg: to_binary(&Cw20HookMsg::CreateSchedule {
+            recipient: Addr::unchecked(recipient),
+            schedule,
+        })
+        .unwrap(),
+    })
+}
+
+fn prism_transfer(recipient: &str, amount: u128) -> SubMsg {
+    SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: "prism0000".to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+            recipient: recipient.to_string(),
+            amount: Uint128::from(amount),
+        })
+        .unwrap(),
+    }))
+}
+
+fn query_vesting(deps: Deps, env: Env, recipient: &str, vesting_id: u64) -> VestingResponse {
+    from_binary(
+        &query(
+            deps,
+            env,
+            QueryMsg::Vesting {
+                recipient: Addr::unchecked(recipient),
+                vesting_id,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap()
+}
+
+fn init(deps: DepsMut) {
+    let msg = InstantiateMsg {
+        owner: Addr::unchecked("owner0000"),
+        prism_token: Addr::unchecked("prism0000"),
+    };
+    let _res = instantiate(deps, mock_env(), mock_info("addr0000", &[]), msg).unwrap();
+}
+
+#[test]
+fn proper_initialization() {
+    let mut deps = mock_dependencies(&[]);
+    init(deps.as_mut());
+
+    let config: ConfigResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap()).unwrap();
+    assert_eq!(
+        config,
+        ConfigResponse {
+            owner: Addr::unchecked("owner0000"),
+            prism_token: Addr::unchecked("prism0000"),
+        }
+    );
+
+    // only the owner can vest PRISM, and only PRISM
+    let msg = create_schedule_msg("addr0000", "team0000", schedule(100, 1000));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let msg = create_schedule_msg("owner0000", "team0000", schedule(100, 1000));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("token0000", &[]), msg).unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let msg = create_schedule_msg("owner0000", "team0000", schedule(1000, 100));
+    let err = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(
+            "schedule must start before its cliff, end after its cliff and in the future"
+        )
+    );
+
+    let msg = create_schedule_msg("owner0000", "team0000", schedule(100, 1000));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+    assert_eq!(res.attributes[1], attr("vesting_id", "1"));
+}
+
+#[test]
+fn claim() {
+    let mut deps = mock_dependencies(&[]);
+    init(deps.as_mut());
+
+    let msg = create_schedule_msg("owner0000", "team0000", schedule(100, 1000));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+
+    // nothing vests before the cliff
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(99),
+        mock_info("team0000", &[]),
+        ExecuteMsg::Claim { vesting_id: None },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("nothing to claim"));
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(100),
+        mock_info("team0000", &[]),
+        ExecuteMsg::Claim { vesting_id: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("team0000", 100)]);
+
+    let vesting = query_vesting(deps.as_ref(), mock_env_after(500), "team0000", 1);
+    assert_eq!(
+        vesting,
+        VestingResponse {
+            vesting_id: 1,
+            recipient: Addr::unchecked("team0000"),
+            schedule: schedule(100, 1000),
+            total_amount: Uint128::from(1000u128),
+            vested_amount: Uint128::from(500u128),
+            claimed_amount: Uint128::from(100u128),
+            claimable_amount: Uint128::from(400u128),
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(2000),
+        mock_info("team0000", &[]),
+        ExecuteMsg::Claim { vesting_id: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("team0000", 900)]);
+
+    // the vesting is gone once fully claimed
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(2000),
+        mock_info("team0000", &[]),
+        ExecuteMsg::Claim {
+            vesting_id: Some(1),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("team0000 has no vesting 1"));
+
+    // a cliff at the end vests everything at once
+    let msg = create_schedule_msg("owner0000", "lp0000", schedule(1000, 1000));
+    let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+    let vesting = query_vesting(deps.as_ref(), mock_env_after(999), "lp0000", 2);
+    assert_eq!(vesting.claimable_amount, Uint128::zero());
+    let vesting = query_vesting(deps.as_ref(), mock_env_after(1000), "lp0000", 2);
+    assert_eq!(vesting.claimable_amount, Uint128::from(1000u128));
+}
+
+#[test]
+fn clawback() {
+    let mut deps = mock_dependencies(&[]);
+    init(deps.as_mut());
+
+    for recipient in ["team0000", "team0001"] {
+        let msg = create_schedule_msg("owner0000", recipient, schedule(100, 1000));
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+    }
+
+    let msg = ExecuteMsg::Clawback {
+        recipient: Addr::unchecked("team0000"),
+        vesting_id: 1,
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env_after(50),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // before the cliff, everything goes back to the owner
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(50),
+        mock_info("owner0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("owner0000", 1000)]);
+
+    // after the cliff, the recipient keeps what is vested
+    let msg = ExecuteMsg::Clawback {
+        recipient: Addr::unchecked("team0001"),
+        vesting_id: 2,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(400),
+        mock_info("owner0000", &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("owner0000", 600)]);
+
+    let vestings: VestingsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(2000),
+            QueryMsg::Vestings {
+                recipient: Addr::unchecked("team0001"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vestings.vestings,
+        vec![VestingResponse {
+            vesting_id: 2,
+            recipient: Addr::unchecked("team0001"),
+            schedule: schedule(100, 400),
+            total_amount: Uint128::from(400u128),
+            vested_amount: Uint128::from(400u128),
+            claimed_amount: Uint128::zero(),
+            claimable_amount: Uint128::from(400u128),
+        }]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(2000),
+        mock_info("team0001", &[]),
+        ExecuteMsg::Claim { vesting_id: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("team0001", 400)]);
+}
+
+#[test]
+fn multiple_vestings() {
+    let mut deps = mock_dependencies(&[]);
+    init(deps.as_mut());
+
+    // a seed vesting, linear, and a team vesting, all at once at its cliff
+    for schedule in [schedule(0, 1000), schedule(500, 500)] {
+        let msg = create_schedule_msg("owner0000", "team0000", schedule);
+        let _res = execute(deps.as_mut(), mock_env(), mock_info("prism0000", &[]), msg).unwrap();
+    }
+
+    let claimable: ClaimableResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(500),
+            QueryMsg::Claimable {
+                recipient: Addr::unchecked("team0000"),
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        claimable,
+        ClaimableResponse {
+            recipient: Addr::unchecked("team0000"),
+            total_amount: Uint128::from(2000u128),
+            vested_amount: Uint128::from(1500u128),
+            claimed_amount: Uint128::zero(),
+            claimable_amount: Uint128::from(1500u128),
+        }
+    );
+
+    // claim the seed vesting only
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(500),
+        mock_info("team0000", &[]),
+        ExecuteMsg::Claim {
+            vesting_id: Some(1),
+        },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("team0000", 500)]);
+    assert_eq!(
+        query_vesting(deps.as_ref(), mock_env_after(500), "team0000", 2).claimable_amount,
+        Uint128::from(1000u128)
+    );
+
+    // then all of them
+    let res = execute(
+        deps.as_mut(),
+        mock_env_after(750),
+        mock_info("team0000", &[]),
+        ExecuteMsg::Claim { vesting_id: None },
+    )
+    .unwrap();
+    assert_eq!(res.messages, vec![prism_transfer("team0000", 1250)]);
+
+    let vestings: VestingsResponse = from_binary(
+        &query(
+            deps.as_ref(),
+            mock_env_after(750),
+            QueryMsg::Vestings {
+                recipient: Addr::unchecked("team0000"),
+                start_after: None,
+                limit: None,
+            },
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(
+        vestings
+            .vestings
+            .iter()
+            .map(|vesting| (vesting.vesting_id, vesting.claimed_amount))
+            .collect::<Vec<(u64, Uint128)>>(),
+        vec![(1, Uint128::from(750u128))]
+    );
+}
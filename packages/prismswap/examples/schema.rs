@@ -0,0 +1,761 @@
+//! Exports the JSON schemas of the messages and responses of every contract into
+//! `schema/v<version>/<contract>` at the root of the workspace, the tree the typescript clients
+//! of the frontends are generated from. Run with `cargo run -p prismswap --example schema`.
+
+use std::fs::create_dir_all;
+use std::path::{Path, PathBuf};
+
+use cosmwasm_schema::{export_schema_with_title, remove_schemas, schema_for};
+use prismswap::querier::QueryResponses;
+
+/// Exports the schemas of the types into the directory, titled after the types since the doc
+/// comment headers would give them all the same title
+macro_rules! export {
+    ($out_dir:expr, $($t:ty),+ $(,)?) => {
+        $(export_schema_with_title(&schema_for!($t), &$out_dir, stringify!($t));)+
+    };
+}
+
+fn main() {
+    let workspace_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .ancestors()
+        .nth(2)
+        .unwrap();
+    let version_dir = workspace_dir
+        .join("schema")
+        .join(format!("v{}", env!("CARGO_PKG_VERSION")));
+
+    export_admin_proxy(&version_dir);
+    export_auction(&version_dir);
+    export_bond(&version_dir);
+    export_bribes(&version_dir);
+    export_collector(&version_dir);
+    export_dca(&version_dir);
+    export_emergency(&version_dir);
+    export_factory(&version_dir);
+    export_gauge(&version_dir);
+    export_hooks(&version_dir);
+    export_insurance(&version_dir);
+    export_keeper(&version_dir);
+    export_lbp(&version_dir);
+    export_limit_order(&version_dir);
+    export_lockdrop(&version_dir);
+    export_merkle_airdrop(&version_dir);
+    export_migrator(&version_dir);
+    export_oracle(&version_dir);
+    export_otc(&version_dir);
+    export_pair(&version_dir);
+    export_params(&version_dir);
+    export_points(&version_dir);
+    export_pol(&version_dir);
+    export_rebalancer(&version_dir);
+    export_rfq(&version_dir);
+    export_router(&version_dir);
+    export_router_shim(&version_dir);
+    export_staking(&version_dir);
+    export_staking_strategy(&version_dir);
+    export_stop_order(&version_dir);
+    export_treasury(&version_dir);
+    export_vault(&version_dir);
+    export_vesting(&version_dir);
+    export_token(&version_dir);
+}
+
+/// Exports the schemas of the responses to the queries, named after the variants
+fn export_responses<M: QueryResponses>(out_dir: &Path) {
+    for (variant, schema) in M::response_schemas() {
+        export_schema_with_title(&schema, out_dir, &format!("response_to_{}", variant));
+    }
+}
+
+/// Returns the emptied schema directory of the contract
+fn contract_dir(version_dir: &Path, contract: &str) -> PathBuf {
+    let out_dir = version_dir.join(contract);
+    create_dir_all(&out_dir).unwrap();
+    remove_schemas(&out_dir).unwrap();
+    out_dir
+}
+
+fn export_admin_proxy(version_dir: &Path) {
+    use prismswap::admin_proxy::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, ProposalResponse, ProposalsResponse, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "admin_proxy");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        ProposalResponse,
+        ProposalsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_auction(version_dir: &Path) {
+    use prismswap::auction::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse,
+    };
+    use prismswap::sale::ParticipantResponse;
+
+    let out_dir = contract_dir(version_dir, "auction");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        ParticipantResponse,
+        StateResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_bond(version_dir: &Path) {
+    use prismswap::bond::{
+        BondResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MarketResponse,
+        QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "bond");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        MarketResponse,
+        BondResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_bribes(version_dir: &Path) {
+    use prismswap::bribes::{
+        BribesResponse, ClaimableResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+        QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "bribes");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        BribesResponse,
+        ClaimableResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_collector(version_dir: &Path) {
+    use prismswap::collector::{
+        ConfigResponse, DistributionResponse, ExecuteMsg, FeeConfigResponse, InstantiateMsg,
+        QueryMsg, RouteResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "collector");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        RouteResponse,
+        DistributionResponse,
+        FeeConfigResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_dca(version_dir: &Path) {
+    use prismswap::dca::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg, ScheduleResponse,
+        SchedulesResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "dca");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        ScheduleResponse,
+        SchedulesResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_emergency(version_dir: &Path) {
+    use prismswap::emergency::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "emergency");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        StateResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_factory(version_dir: &Path) {
+    use prismswap::asset::PairInfo;
+    use prismswap::factory::{
+        ConfigResponse, ExecuteMsg, FeeInfoResponse, InstantiateMsg, MigrateMsg,
+        PairConfigResponse, PairsConfigResponse, PairsResponse, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "factory");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        PairInfo,
+        PairsResponse,
+        ConfigResponse,
+        PairsConfigResponse,
+        MigrateMsg,
+        PairConfigResponse,
+        FeeInfoResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_gauge(version_dir: &Path) {
+    use prismswap::gauge::{
+        ConfigResponse, Cw20HookMsg, EpochResponse, ExecuteMsg, GaugeWeightsResponse,
+        InstantiateMsg, QueryMsg, VoterResponse, VoterWeightResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "gauge");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        EpochResponse,
+        VoterResponse,
+        GaugeWeightsResponse,
+        VoterWeightResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_hooks(version_dir: &Path) {
+    use prismswap::hooks::{AutoStakeHookMsg, HookExecuteMsg};
+
+    let out_dir = contract_dir(version_dir, "hooks");
+    export!(out_dir, AutoStakeHookMsg, HookExecuteMsg);
+}
+
+fn export_insurance(version_dir: &Path) {
+    use prismswap::insurance::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, PayoutResponse, PayoutsResponse, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "insurance");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        PayoutResponse,
+        PayoutsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_keeper(version_dir: &Path) {
+    use prismswap::keeper::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, JobResponse, JobsResponse,
+        QueryMsg, WorkableResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "keeper");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        JobResponse,
+        JobsResponse,
+        WorkableResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_lbp(version_dir: &Path) {
+    use prismswap::lbp::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, PoolResponse, QueryMsg,
+    };
+    use prismswap::pair::SimulationResponse;
+    use prismswap::sale::ParticipantResponse;
+
+    let out_dir = contract_dir(version_dir, "lbp");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        ParticipantResponse,
+        PoolResponse,
+        SimulationResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_limit_order(version_dir: &Path) {
+    use prismswap::limit_order::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, OrdersResponse,
+        QueryMsg, SimulationResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "limit_order");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        OrderResponse,
+        OrdersResponse,
+        SimulationResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_lockdrop(version_dir: &Path) {
+    use prismswap::lockdrop::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, LockupResponse, QueryMsg,
+        StateResponse, UserResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "lockdrop");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        StateResponse,
+        UserResponse,
+        LockupResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_merkle_airdrop(version_dir: &Path) {
+    use prismswap::merkle_airdrop::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, IsClaimedResponse, QueryMsg,
+        StageResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "merkle_airdrop");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        StageResponse,
+        IsClaimedResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_migrator(version_dir: &Path) {
+    use prismswap::migrator::{ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg};
+
+    let out_dir = contract_dir(version_dir, "migrator");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_oracle(version_dir: &Path) {
+    use prismswap::oracle::{
+        AggregatePriceResponse, ConfigResponse, DecimalsResponse, EmaResponse, ExecuteMsg,
+        FeedResponse, InstantiateMsg, PairResponse, PairsResponse, PriceResponse, QueryMsg,
+        TwapResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "oracle");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        EmaResponse,
+        FeedResponse,
+        AggregatePriceResponse,
+        PairResponse,
+        PairsResponse,
+        PriceResponse,
+        DecimalsResponse,
+        TwapResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_otc(version_dir: &Path) {
+    use prismswap::otc::{
+        Cw20HookMsg, ExecuteMsg, InstantiateMsg, OfferResponse, OffersResponse, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "otc");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        OfferResponse,
+        OffersResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_pair(version_dir: &Path) {
+    use prismswap::asset::PairInfo;
+    use prismswap::pair::{
+        ConfigResponse, CumulativePricesResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+        MigrateMsg, PoolResponse, QueryMsg, ReverseSimulationResponse, SimulationResponse,
+        TwapResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "pair");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        MigrateMsg,
+        PairInfo,
+        PoolResponse,
+        ReverseSimulationResponse,
+        SimulationResponse,
+        ConfigResponse,
+        CumulativePricesResponse,
+        TwapResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_params(version_dir: &Path) {
+    use prismswap::params::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, ParamResponse, ParamsResponse, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "params");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        ParamResponse,
+        ParamsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_points(version_dir: &Path) {
+    use prismswap::points::{
+        ConfigResponse, EpochResponse, ExecuteMsg, InstantiateMsg, LeaderboardResponse, QueryMsg,
+        TraderVolumeResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "points");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        EpochResponse,
+        TraderVolumeResponse,
+        LeaderboardResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_pol(version_dir: &Path) {
+    use prismswap::pol::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, PositionResponse, PositionsResponse, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "pol");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        PositionResponse,
+        PositionsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_rebalancer(version_dir: &Path) {
+    use prismswap::rebalancer::{
+        ConfigResponse, ExecuteMsg, HoldingsResponse, InstantiateMsg, QueryMsg,
+    };
+
+    let out_dir = contract_dir(version_dir, "rebalancer");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        HoldingsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_rfq(version_dir: &Path) {
+    use prismswap::rfq::{
+        Cw20HookMsg, ExecuteMsg, InstantiateMsg, MakerResponse, NonceUsedResponse, QueryMsg,
+        QuoteDigestResponse, QuotePayload,
+    };
+
+    let out_dir = contract_dir(version_dir, "rfq");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        QuotePayload,
+        MakerResponse,
+        QuoteDigestResponse,
+        NonceUsedResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_router(version_dir: &Path) {
+    use prismswap::router::{
+        AssetVolumesResponse, ConfigResponse, Cw20HookMsg, EstimateRequiredOfferResponse,
+        ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, SimulateSwapOperationsResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "router");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        MigrateMsg,
+        SimulateSwapOperationsResponse,
+        EstimateRequiredOfferResponse,
+        AssetVolumesResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_router_shim(version_dir: &Path) {
+    use prismswap::router_shim::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+        SimulateSwapOperationsResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "router_shim");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        SimulateSwapOperationsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_staking(version_dir: &Path) {
+    use prismswap::staking::{
+        AllPendingRewardsResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg,
+        PendingRewardsResponse, PoolResponse, PoolsResponse, QueryMsg, StakerInfoResponse,
+        UnbondingClaimsResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "staking");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        PoolResponse,
+        PoolsResponse,
+        StakerInfoResponse,
+        UnbondingClaimsResponse,
+        PendingRewardsResponse,
+        AllPendingRewardsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_staking_strategy(version_dir: &Path) {
+    use prismswap::staking_strategy::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+    use prismswap::strategy::{StrategyCw20HookMsg, StrategyInfoResponse, TotalAssetsResponse};
+
+    let out_dir = contract_dir(version_dir, "staking_strategy");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        StrategyCw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        StrategyInfoResponse,
+        TotalAssetsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_stop_order(version_dir: &Path) {
+    use prismswap::stop_order::{
+        ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, OrderResponse, OrdersResponse,
+        QueryMsg, TriggerResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "stop_order");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        OrderResponse,
+        OrdersResponse,
+        TriggerResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_treasury(version_dir: &Path) {
+    use prismswap::treasury::{
+        ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StreamResponse, StreamsResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "treasury");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        ConfigResponse,
+        StreamResponse,
+        StreamsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_vault(version_dir: &Path) {
+    use prismswap::vault::{
+        ConfigResponse, Cw20HookMsg, ExchangeRateResponse, ExecuteMsg, FeesResponse,
+        InstantiateMsg, PositionResponse, QueryMsg, StateResponse, WithdrawalResponse,
+        WithdrawalsResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "vault");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        StateResponse,
+        ExchangeRateResponse,
+        PositionResponse,
+        FeesResponse,
+        WithdrawalResponse,
+        WithdrawalsResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_vesting(version_dir: &Path) {
+    use prismswap::vesting::{
+        ClaimableResponse, ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, QueryMsg,
+        VestingResponse, VestingsResponse,
+    };
+
+    let out_dir = contract_dir(version_dir, "vesting");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        Cw20HookMsg,
+        QueryMsg,
+        ConfigResponse,
+        VestingResponse,
+        VestingsResponse,
+        ClaimableResponse
+    );
+    export_responses::<QueryMsg>(&out_dir);
+}
+
+fn export_token(version_dir: &Path) {
+    use cw20::{
+        AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse,
+        MinterResponse, TokenInfoResponse,
+    };
+    use cw20_base::msg::{ExecuteMsg, QueryMsg};
+    use prismswap::token::{InstantiateMsg, MigrateMsg};
+
+    let out_dir = contract_dir(version_dir, "token");
+    export!(
+        out_dir,
+        InstantiateMsg,
+        ExecuteMsg,
+        QueryMsg,
+        MigrateMsg,
+        AllowanceResponse,
+        BalanceResponse,
+        TokenInfoResponse,
+        MinterResponse,
+        AllAllowancesResponse,
+        AllAccountsResponse,
+    );
+}
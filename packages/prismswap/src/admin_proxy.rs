@@ -0,0 +1,110 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Binary};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract. The contract must be set
+/// as the owner of the factory and the router.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the addresses allowed to propose and confirm actions
+    pub signers: Vec<Addr>,
+    /// the confirmations a config change needs
+    pub threshold: u64,
+    /// the confirmations a pause needs, at most `threshold`
+    pub pause_threshold: u64,
+    pub factory: Addr,
+    pub router: Addr,
+}
+
+/// ## Description
+/// This enum describes the actions the signers can propose.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Action {
+    /// Pause pauses the router and switches the factory to withdraw-only mode, needing only the
+    /// pause threshold
+    Pause {},
+    /// Execute sends an execute message to the factory or the router, such as a config change
+    Execute { contract_addr: Addr, msg: Binary },
+    /// UpdateSigners replaces the signers and the thresholds
+    UpdateSigners {
+        signers: Vec<Addr>,
+        threshold: u64,
+        pause_threshold: u64,
+    },
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Propose opens a proposal running an action, confirmed by its proposer
+    Propose { action: Action },
+    /// Confirm adds the confirmation of the sender to a proposal
+    Confirm { proposal_id: u64 },
+    /// Revoke withdraws the confirmation of the sender from a proposal
+    Revoke { proposal_id: u64 },
+    /// Execute runs the action of a proposal confirmed by enough of the current signers. Anyone
+    /// can execute it
+    Execute { proposal_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Proposal returns a proposal
+    Proposal { proposal_id: u64 },
+    /// Proposals returns the proposals according to the specified parameters in `start_after` and
+    /// `limit` variables
+    Proposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Proposal => ProposalResponse,
+        Proposals => ProposalsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub signers: Vec<Addr>,
+    pub threshold: u64,
+    pub pause_threshold: u64,
+    pub factory: Addr,
+    pub router: Addr,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the proposal query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalResponse {
+    pub proposal_id: u64,
+    pub proposer: Addr,
+    pub action: Action,
+    /// the current signers that confirmed the proposal
+    pub confirmations: Vec<Addr>,
+    /// the confirmations the action needs
+    pub required: u64,
+    pub executed: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the proposals query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposalsResponse {
+    pub proposals: Vec<ProposalResponse>,
+}
@@ -1,12 +1,14 @@
 use cw20::Cw20ExecuteMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use terra_cosmwasm::TerraMsgWrapper;
 
+use crate::custom_msg::CustomMsg;
 use crate::pair::ExecuteMsg as PairExecuteMsg;
 use crate::querier::{query_balance, query_token_balance};
+#[cfg(feature = "terra")]
+use crate::tax::TaxInfo;
 use cosmwasm_std::{
-    to_binary, Addr, Api, Coin, CosmosMsg, Decimal, MessageInfo, QuerierWrapper, StdError,
+    to_binary, Addr, Api, BankMsg, Coin, CosmosMsg, Decimal, MessageInfo, QuerierWrapper, StdError,
     StdResult, Uint128, WasmMsg,
 };
 
@@ -57,6 +59,7 @@ pub trait PrismSwapAssetInfo {
     fn as_bytes(&self) -> &[u8];
     fn to_string_legacy(&self) -> String;
     fn check(&self, api: &dyn Api) -> StdResult<()>;
+    fn validate(&self, api: &dyn Api) -> StdResult<()>;
 }
 
 impl PrismSwapAssetInfo for AssetInfo {
@@ -112,26 +115,47 @@ impl PrismSwapAssetInfo for AssetInfo {
         }
         Ok(())
     }
+
+    /// ## Description
+    /// Returns an error unless the cw20 address is valid or the native denom is a valid bank,
+    /// IBC or token factory denom, see [`validate_native_denom`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **api** is the object of type [`Api`].
+    fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        match self {
+            AssetInfo::Cw20(addr) => api.addr_validate(addr.as_str()).map(|_| ()),
+            AssetInfo::Native(denom) => validate_native_denom(api, denom),
+        }
+    }
 }
 
 pub trait PrismSwapAsset {
-    fn into_swap_msg(
+    fn into_swap_msg<T: CustomMsg>(
         self,
         pair_contract: &Addr,
         max_spread: Option<Decimal>,
         to: Option<String>,
-    ) -> StdResult<CosmosMsg<TerraMsgWrapper>>;
+    ) -> StdResult<CosmosMsg<T>>;
+    fn into_msg<T: CustomMsg>(self, recipient: &Addr) -> StdResult<CosmosMsg<T>>;
+    #[cfg(feature = "terra")]
+    fn into_transfer_msg_with_tax<T: CustomMsg>(
+        self,
+        querier: &QuerierWrapper,
+        recipient: &Addr,
+    ) -> StdResult<Option<CosmosMsg<T>>>;
     fn assert_sent_native_token_balance(&self, info: &MessageInfo) -> StdResult<()>;
     fn to_string_legacy(&self) -> String;
 }
 
 impl PrismSwapAsset for Asset {
-    fn into_swap_msg(
+    fn into_swap_msg<T: CustomMsg>(
         self,
         pair_contract: &Addr,
         max_spread: Option<Decimal>,
         to: Option<String>,
-    ) -> StdResult<CosmosMsg<TerraMsgWrapper>> {
+    ) -> StdResult<CosmosMsg<T>> {
         match self.info.clone() {
             AssetInfo::Native(denom) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
                 contract_addr: pair_contract.to_string(),
@@ -166,6 +190,70 @@ impl PrismSwapAsset for Asset {
         }
     }
 
+    /// ## Description
+    /// Returns a message transferring the asset to the recipient.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **recipient** is the object of type [`Addr`].
+    fn into_msg<T: CustomMsg>(self, recipient: &Addr) -> StdResult<CosmosMsg<T>> {
+        match self.info {
+            AssetInfo::Native(denom) => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin {
+                    denom,
+                    amount: self.amount,
+                }],
+            })),
+            AssetInfo::Cw20(contract_addr) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                funds: vec![],
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: self.amount,
+                })?,
+            })),
+        }
+    }
+
+    /// ## Description
+    /// Returns a message transferring the asset to the recipient out of a balance of `amount`,
+    /// the native amount being reduced by the capped tax charged on top of the transfer. Returns
+    /// none if nothing is left to transfer once the tax is taken out.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **querier** is the object of type [`QuerierWrapper`].
+    ///
+    /// * **recipient** is the object of type [`Addr`].
+    #[cfg(feature = "terra")]
+    fn into_transfer_msg_with_tax<T: CustomMsg>(
+        self,
+        querier: &QuerierWrapper,
+        recipient: &Addr,
+    ) -> StdResult<Option<CosmosMsg<T>>> {
+        let amount = match &self.info {
+            AssetInfo::Native(denom) => {
+                TaxInfo::query(querier, &[denom.to_string()])?.deduct_tax(&Coin {
+                    denom: denom.to_string(),
+                    amount: self.amount,
+                })?
+            }
+            AssetInfo::Cw20(_) => self.amount,
+        };
+        if amount.is_zero() {
+            return Ok(None);
+        }
+
+        Ok(Some(
+            Asset {
+                info: self.info,
+                amount,
+            }
+            .into_msg(recipient)?,
+        ))
+    }
+
     fn assert_sent_native_token_balance(&self, message_info: &MessageInfo) -> StdResult<()> {
         if let AssetInfo::Native(denom) = &self.info {
             match message_info.funds.iter().find(|x| x.denom == *denom) {
@@ -192,3 +280,79 @@ impl PrismSwapAsset for Asset {
         format!("{}:{}", self.info.to_string_legacy(), self.amount)
     }
 }
+
+/// ## Description
+/// Returns the asset infos in the canonical order of the factory, sorted by their bytes.
+/// ## Params
+/// * **asset_infos** are the asset infos of a pair, in any order.
+pub fn sort_asset_infos(asset_infos: &[AssetInfo; 2]) -> [AssetInfo; 2] {
+    let mut asset_infos = asset_infos.clone();
+    asset_infos.sort_by(|a, b| a.as_bytes().cmp(b.as_bytes()));
+    asset_infos
+}
+
+/// ## Description
+/// Returns the key the factory stores the pair of the asset infos under, the concatenation of
+/// their bytes in the canonical order, so both orders of the assets give the same key.
+/// ## Params
+/// * **asset_infos** are the asset infos of a pair, in any order.
+pub fn pair_key(asset_infos: &[AssetInfo; 2]) -> Vec<u8> {
+    let asset_infos = sort_asset_infos(asset_infos);
+    [asset_infos[0].as_bytes(), asset_infos[1].as_bytes()].concat()
+}
+
+/// ## Description
+/// Returns an error unless the denom is a valid native denom:
+/// * `ibc/{hash}`, the hash being 64 uppercase hexadecimal characters.
+/// * `factory/{creator}/{subdenom}`, the creator being a valid address.
+/// * any other bank denom, 3 to 128 characters starting with a letter followed by letters,
+///   digits or `/:._-`.
+/// ## Params
+/// * **api** is the object of type [`Api`].
+///
+/// * **denom** is the object of type [`&str`].
+pub fn validate_native_denom(api: &dyn Api, denom: &str) -> StdResult<()> {
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        if hash.len() != 64
+            || !hash
+                .chars()
+                .all(|c| c.is_ascii_digit() || ('A'..='F').contains(&c))
+        {
+            return Err(StdError::generic_err(format!(
+                "invalid ibc denom {}: the hash must be 64 uppercase hexadecimal characters",
+                denom
+            )));
+        }
+        return Ok(());
+    }
+
+    if let Some(rest) = denom.strip_prefix("factory/") {
+        let (creator, subdenom) = rest.split_once('/').ok_or_else(|| {
+            StdError::generic_err(format!(
+                "invalid token factory denom {}: expected factory/{{creator}}/{{subdenom}}",
+                denom
+            ))
+        })?;
+        api.addr_validate(creator)?;
+        if subdenom.is_empty() {
+            return Err(StdError::generic_err(format!(
+                "invalid token factory denom {}: the subdenom is empty",
+                denom
+            )));
+        }
+    }
+
+    let mut chars = denom.chars();
+    if denom.len() < 3
+        || denom.len() > 128
+        || !chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        || !chars.all(|c| c.is_ascii_alphanumeric() || "/:._-".contains(c))
+    {
+        return Err(StdError::generic_err(format!(
+            "invalid native denom {}",
+            denom
+        )));
+    }
+
+    Ok(())
+}
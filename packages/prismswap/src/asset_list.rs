@@ -0,0 +1,101 @@
+use cosmwasm_std::{Api, MessageInfo, StdError, StdResult};
+
+use crate::asset::{AssetInfo, PrismSwapAsset, PrismSwapAssetInfo};
+
+pub use cw_asset::AssetList;
+
+/// ## Description
+/// The helpers of the messages carrying any number of assets, such as multi-asset deposits,
+/// withdrawals and sweeps, on top of the [`AssetList`] ones, which also builds their transfer
+/// messages.
+pub trait PrismSwapAssetList {
+    fn validate(&self, api: &dyn Api) -> StdResult<()>;
+    fn merge(&self, other: &AssetList) -> StdResult<AssetList>;
+    fn checked_deduct(&self, other: &AssetList) -> StdResult<AssetList>;
+    fn assert_sent_native_funds(&self, info: &MessageInfo) -> StdResult<()>;
+}
+
+impl PrismSwapAssetList for AssetList {
+    /// ## Description
+    /// Returns an error if the list is empty, holds a zero amount, an asset twice or an invalid
+    /// asset info, see [`PrismSwapAssetInfo::validate`].
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **api** is the object of type [`Api`].
+    fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        let assets = self.to_vec();
+        if assets.is_empty() {
+            return Err(StdError::generic_err("asset list is empty"));
+        }
+
+        for (i, asset) in assets.iter().enumerate() {
+            asset.info.validate(api)?;
+            if asset.amount.is_zero() {
+                return Err(StdError::generic_err(format!(
+                    "asset list holds a zero amount of {}",
+                    asset.info
+                )));
+            }
+            if assets[..i].iter().any(|other| other.info == asset.info) {
+                return Err(StdError::generic_err(format!(
+                    "asset list holds {} twice",
+                    asset.info
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// ## Description
+    /// Returns the list adding the amounts of the other list, the assets missing from the list
+    /// being appended.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **other** is the object of type [`AssetList`] added.
+    fn merge(&self, other: &AssetList) -> StdResult<AssetList> {
+        let mut merged = self.clone();
+        merged.add_many(other)?;
+        Ok(merged)
+    }
+
+    /// ## Description
+    /// Returns the list less the amounts of the other list without the emptied assets, an error
+    /// if the list holds less of one of them.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **other** is the object of type [`AssetList`] deducted.
+    fn checked_deduct(&self, other: &AssetList) -> StdResult<AssetList> {
+        let mut deducted = self.clone();
+        deducted.deduct_many(other)?;
+        Ok(deducted)
+    }
+
+    /// ## Description
+    /// Returns an error unless the funds sent with the message are exactly the native assets of
+    /// the list.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **info** is the object of type [`MessageInfo`].
+    fn assert_sent_native_funds(&self, info: &MessageInfo) -> StdResult<()> {
+        for asset in self {
+            asset.assert_sent_native_token_balance(info)?;
+        }
+
+        if info
+            .funds
+            .iter()
+            .any(|coin| self.find(&AssetInfo::Native(coin.denom.clone())).is_none())
+        {
+            return Err(StdError::generic_err(
+                "sent native funds missing from the asset list",
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use crate::sale::{ParticipantResponse, Whitelist};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the project depositing the sale asset and settling the auction
+    pub owner: Addr,
+    /// the factory the pair seeded with the proceeds is looked up in
+    pub factory: Addr,
+    /// the asset sold
+    pub sale_asset: AssetInfo,
+    /// the asset the purchases are paid in
+    pub quote_asset: AssetInfo,
+    /// the price in quote asset of a unit of sale asset at the start
+    pub start_price: Decimal,
+    /// the price at the end, reached linearly from the start price
+    pub end_price: Decimal,
+    /// the block time in seconds the purchases open at
+    pub start_time: u64,
+    /// the block time in seconds the purchases close at
+    pub end_time: u64,
+    /// where the proceeds go on settlement
+    pub proceeds_target: ProceedsTarget,
+    /// the addresses allowed to buy, anyone if none
+    pub whitelist: Option<Whitelist>,
+    /// the most of the sale asset an address can buy
+    pub purchase_cap: Option<Asset>,
+}
+
+/// ## Description
+/// This enum describes where the proceeds of an auction go on settlement.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProceedsTarget {
+    /// the proceeds are sent to the treasury and the unsold sale asset to the owner
+    Treasury { address: Addr },
+    /// the proceeds seed the factory pair of the sale and quote assets at the clearing price, the
+    /// pair being created if the factory has none. The LP tokens and the rest are sent to the
+    /// owner
+    Pair {},
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// Deposit the owner deposits the sale asset, before the start
+    Deposit { asset: Asset },
+    /// Buy the sale asset with the native quote asset sent, at the current price, between the
+    /// start and the end. The part of the quote asset exceeding the sale asset left is refunded
+    Buy {
+        max_price: Option<Decimal>,
+        to: Option<String>,
+    },
+    /// Settle routes the proceeds to the proceeds target, after the end or once the sale asset
+    /// is sold out. Anyone can execute it
+    Settle {},
+    /// Register whitelists the sender by a proof of its leaf in the merkle tree of the whitelist
+    Register { proof: Vec<String> },
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Buy the sale asset with the cw20 quote asset sent
+    Buy {
+        max_price: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure.
+    Config {},
+    /// Returns the current price and the progress of the sale in a [`StateResponse`] object.
+    State {},
+    /// Returns whether an address can take part and how much it bought in a
+    /// [`crate::sale::ParticipantResponse`] object.
+    Participant { address: String },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        State => StateResponse,
+        Participant => ParticipantResponse,
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub sale_asset: AssetInfo,
+    pub quote_asset: AssetInfo,
+    pub start_price: Decimal,
+    pub end_price: Decimal,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub proceeds_target: ProceedsTarget,
+    pub whitelist: Option<Whitelist>,
+    pub purchase_cap: Option<Asset>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for the state query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    /// the price at the current block time
+    pub price: Decimal,
+    /// the sale asset left
+    pub available: Uint128,
+    pub sold: Uint128,
+    /// the quote asset paid for the sale asset sold
+    pub raised: Uint128,
+    /// the price of the last purchase, the pair being seeded at it
+    pub clearing_price: Option<Decimal>,
+    pub settled: bool,
+    /// the pair the proceeds seeded
+    pub pair: Option<Addr>,
+}
@@ -0,0 +1,122 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration and the markets
+    pub owner: Addr,
+    /// the PRISM token paid out, held by the contract
+    pub prism_token: Addr,
+    /// the protocol-owned liquidity contract the bonded LP tokens are sent to
+    pub treasury: Addr,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving LP tokens
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner and the treasury
+    UpdateConfig {
+        owner: Option<Addr>,
+        treasury: Option<Addr>,
+    },
+    /// SetMarket opens or updates the bond market of the LP token of a PRISM pair
+    SetMarket {
+        lp_token: Addr,
+        pair: Addr,
+        /// the discount of a bond while the market has no outstanding debt
+        max_discount: Decimal,
+        /// the seconds the payout of a bond vests over
+        vesting_term: u64,
+        /// the outstanding PRISM the market can owe
+        max_debt: Uint128,
+        /// the seconds of the pair TWAP the LP tokens are valued at
+        twap_window: u64,
+    },
+    /// RemoveMarket closes the bond market of an LP token, vesting bonds staying claimable
+    RemoveMarket { lp_token: Addr },
+    /// Claim sends the sender the PRISM vested since the last claim of its bond of the LP token
+    Claim { lp_token: Addr },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the LP tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Bond the sent LP tokens for PRISM vesting over the vesting term of the market, reverting
+    /// if the payout falls below `min_payout`
+    Bond { min_payout: Option<Uint128> },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Market returns the bond market of an LP token and its current discount
+    Market { lp_token: Addr },
+    /// Bond returns the bond of an address in the market of an LP token and what it can claim
+    Bond { address: Addr, lp_token: Addr },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Market => MarketResponse,
+        Bond => BondResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub prism_token: Addr,
+    pub treasury: Addr,
+    /// the PRISM owed to the vesting bonds
+    pub total_payout: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the market query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MarketResponse {
+    pub lp_token: Addr,
+    pub pair: Addr,
+    pub max_discount: Decimal,
+    pub vesting_term: u64,
+    pub max_debt: Uint128,
+    pub twap_window: u64,
+    /// the outstanding debt, decayed to the current time
+    pub total_debt: Uint128,
+    /// the discount of a bond at the current debt
+    pub discount: Decimal,
+    /// the PRISM an LP token is bonded for at the current discount
+    pub price: Decimal,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the bond query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondResponse {
+    pub address: Addr,
+    pub lp_token: Addr,
+    /// the PRISM not claimed yet
+    pub payout: Uint128,
+    /// the time the payout is fully vested
+    pub vesting_end: u64,
+    /// the PRISM vested and not claimed yet
+    pub claimable: Uint128,
+}
@@ -0,0 +1,112 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::Asset;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the gauge contract the bribes are paid to the voters of
+    pub gauge: Addr,
+    /// the epochs after its end the bribes of an epoch can be claimed for, before the rest can be
+    /// rolled over
+    pub claim_epochs: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving cw20 bribes
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner and the claim period
+    UpdateConfig {
+        owner: Option<Addr>,
+        claim_epochs: Option<u64>,
+    },
+    /// AddBribe attaches the sent native tokens to the votes for a pool in an epoch not over yet
+    AddBribe {
+        lp_token: Addr,
+        epoch: u64,
+        asset: Asset,
+    },
+    /// Claim sends the sender its share of the bribes of a pool in an epoch that is over, in
+    /// proportion to its votes for the pool
+    Claim { lp_token: Addr, epoch: u64 },
+    /// Rollover moves the unclaimed bribes of a pool in an epoch to the current epoch, once nobody
+    /// can claim them anymore. Anyone can execute it
+    Rollover { lp_token: Addr, epoch: u64 },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the cw20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Attach the sent tokens to the votes for a pool in an epoch not over yet
+    AddBribe { lp_token: Addr, epoch: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Bribes returns the bribes of a pool in an epoch
+    Bribes { lp_token: Addr, epoch: u64 },
+    /// Claimable returns the bribes a voter can claim for a pool in an epoch
+    Claimable {
+        voter: Addr,
+        lp_token: Addr,
+        epoch: u64,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Bribes => BribesResponse,
+        Claimable => ClaimableResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub gauge: Addr,
+    pub claim_epochs: u64,
+}
+
+/// ## Description
+/// This structure describes a bribed asset and how much of it was claimed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Bribe {
+    pub asset: Asset,
+    pub claimed_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the bribes query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BribesResponse {
+    pub bribes: Vec<Bribe>,
+    /// whether the unclaimed bribes were moved to a later epoch
+    pub rolled_over: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the claimable query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableResponse {
+    pub assets: Vec<Asset>,
+}
@@ -0,0 +1,287 @@
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Coin, CosmosMsg, Decimal, StdResult, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+use crate::asset::{Asset, AssetInfo};
+use crate::custom_msg::CustomMsg;
+use crate::pair::{Cw20HookMsg as PairCw20HookMsg, ExecuteMsg as PairExecuteMsg};
+use crate::router::{
+    Cw20HookMsg as RouterCw20HookMsg, ExecuteMsg as RouterExecuteMsg, ExecuteSwapOperationsMsg,
+    Referral, SwapOperation, TwapConfig,
+};
+
+/// ## Description
+/// This structure builds the message swapping an asset in a pair, sending the native asset as
+/// funds or the cw20 one through the pair hook.
+pub struct SwapMsgBuilder {
+    pair_contract: Addr,
+    offer_asset: Asset,
+    belief_price: Option<Decimal>,
+    max_spread: Option<Decimal>,
+    to: Option<String>,
+}
+
+impl SwapMsgBuilder {
+    pub fn new(pair_contract: Addr, offer_asset: Asset) -> Self {
+        SwapMsgBuilder {
+            pair_contract,
+            offer_asset,
+            belief_price: None,
+            max_spread: None,
+            to: None,
+        }
+    }
+
+    pub fn belief_price(mut self, belief_price: Decimal) -> Self {
+        self.belief_price = Some(belief_price);
+        self
+    }
+
+    pub fn max_spread(mut self, max_spread: Decimal) -> Self {
+        self.max_spread = Some(max_spread);
+        self
+    }
+
+    /// Sets the recipient of the return, the sender by default
+    pub fn to(mut self, to: impl Into<String>) -> Self {
+        self.to = Some(to.into());
+        self
+    }
+
+    pub fn build<T>(self) -> StdResult<CosmosMsg<T>>
+    where
+        T: CustomMsg,
+    {
+        match &self.offer_asset.info {
+            AssetInfo::Cw20(contract_addr) => send_msg(
+                contract_addr,
+                &self.pair_contract,
+                self.offer_asset.amount,
+                to_binary(&PairCw20HookMsg::Swap {
+                    belief_price: self.belief_price,
+                    max_spread: self.max_spread,
+                    to: self.to,
+                })?,
+            ),
+            AssetInfo::Native(denom) => {
+                let funds = vec![Coin {
+                    denom: denom.clone(),
+                    amount: self.offer_asset.amount,
+                }];
+                Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr: self.pair_contract.to_string(),
+                    msg: to_binary(&PairExecuteMsg::Swap {
+                        offer_asset: self.offer_asset,
+                        belief_price: self.belief_price,
+                        max_spread: self.max_spread,
+                        to: self.to,
+                    })?,
+                    funds,
+                }))
+            }
+        }
+    }
+}
+
+/// ## Description
+/// This structure builds the messages providing liquidity to a pair: an allowance for each cw20
+/// asset, then the provision sending the native assets as funds.
+pub struct ProvideLiquidityBuilder {
+    pair_contract: Addr,
+    assets: [Asset; 2],
+    slippage_tolerance: Option<Decimal>,
+    receiver: Option<String>,
+    auto_stake: Option<bool>,
+}
+
+impl ProvideLiquidityBuilder {
+    pub fn new(pair_contract: Addr, assets: [Asset; 2]) -> Self {
+        ProvideLiquidityBuilder {
+            pair_contract,
+            assets,
+            slippage_tolerance: None,
+            receiver: None,
+            auto_stake: None,
+        }
+    }
+
+    pub fn slippage_tolerance(mut self, slippage_tolerance: Decimal) -> Self {
+        self.slippage_tolerance = Some(slippage_tolerance);
+        self
+    }
+
+    /// Sets the recipient of the liquidity tokens, the sender by default
+    pub fn receiver(mut self, receiver: impl Into<String>) -> Self {
+        self.receiver = Some(receiver.into());
+        self
+    }
+
+    /// Bonds the liquidity tokens in the factory staking contract on behalf of the recipient
+    pub fn auto_stake(mut self) -> Self {
+        self.auto_stake = Some(true);
+        self
+    }
+
+    pub fn build<T>(self) -> StdResult<Vec<CosmosMsg<T>>>
+    where
+        T: CustomMsg,
+    {
+        let mut messages: Vec<CosmosMsg<T>> = vec![];
+        let mut funds: Vec<Coin> = vec![];
+        for asset in self.assets.iter() {
+            match &asset.info {
+                AssetInfo::Cw20(contract_addr) => {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                            spender: self.pair_contract.to_string(),
+                            amount: asset.amount,
+                            expires: None,
+                        })?,
+                        funds: vec![],
+                    }))
+                }
+                AssetInfo::Native(denom) => funds.push(Coin {
+                    denom: denom.clone(),
+                    amount: asset.amount,
+                }),
+            }
+        }
+        // the funds must be sorted by denom
+        funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: self.pair_contract.to_string(),
+            msg: to_binary(&PairExecuteMsg::ProvideLiquidity {
+                assets: self.assets,
+                slippage_tolerance: self.slippage_tolerance,
+                receiver: self.receiver,
+                auto_stake: self.auto_stake,
+            })?,
+            funds,
+        }));
+
+        Ok(messages)
+    }
+}
+
+/// ## Description
+/// This structure builds the message swapping an asset through the router operations, sending
+/// the native asset as funds or the cw20 one through the router hook.
+pub struct RouterSwapBuilder {
+    router_contract: Addr,
+    offer_asset: Asset,
+    msg: ExecuteSwapOperationsMsg,
+}
+
+impl RouterSwapBuilder {
+    pub fn new(router_contract: Addr, offer_asset: Asset, operations: Vec<SwapOperation>) -> Self {
+        RouterSwapBuilder {
+            router_contract,
+            offer_asset,
+            msg: ExecuteSwapOperationsMsg {
+                operations,
+                minimum_receive: None,
+                max_spread: None,
+                to: None,
+                deadline: None,
+                referral: None,
+                swap_all: None,
+                twap_check: None,
+                twap_config: None,
+                staking_contract: None,
+            },
+        }
+    }
+
+    pub fn minimum_receive(mut self, minimum_receive: Uint128) -> Self {
+        self.msg.minimum_receive = Some(minimum_receive);
+        self
+    }
+
+    pub fn max_spread(mut self, max_spread: Decimal) -> Self {
+        self.msg.max_spread = Some(max_spread);
+        self
+    }
+
+    /// Sets the recipient of the output, the sender by default
+    pub fn to(mut self, to: Addr) -> Self {
+        self.msg.to = Some(to);
+        self
+    }
+
+    pub fn deadline(mut self, deadline: u64) -> Self {
+        self.msg.deadline = Some(deadline);
+        self
+    }
+
+    pub fn referral(mut self, referral: Referral) -> Self {
+        self.msg.referral = Some(referral);
+        self
+    }
+
+    pub fn swap_all(mut self) -> Self {
+        self.msg.swap_all = Some(true);
+        self
+    }
+
+    pub fn twap_check(mut self) -> Self {
+        self.msg.twap_check = Some(true);
+        self
+    }
+
+    /// Checks the output against the given TWAP bound instead of the router one
+    pub fn twap_config(mut self, twap_config: TwapConfig) -> Self {
+        self.msg.twap_config = Some(twap_config);
+        self
+    }
+
+    /// Bonds the output in the registered staking contract on behalf of the recipient
+    pub fn staking_contract(mut self, staking_contract: Addr) -> Self {
+        self.msg.staking_contract = Some(staking_contract);
+        self
+    }
+
+    pub fn build<T>(self) -> StdResult<CosmosMsg<T>>
+    where
+        T: CustomMsg,
+    {
+        match &self.offer_asset.info {
+            AssetInfo::Cw20(contract_addr) => send_msg(
+                contract_addr,
+                &self.router_contract,
+                self.offer_asset.amount,
+                to_binary(&RouterCw20HookMsg::ExecuteSwapOperations(self.msg))?,
+            ),
+            AssetInfo::Native(denom) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: self.router_contract.to_string(),
+                msg: to_binary(&RouterExecuteMsg::ExecuteSwapOperations(self.msg))?,
+                funds: vec![Coin {
+                    denom: denom.clone(),
+                    amount: self.offer_asset.amount,
+                }],
+            })),
+        }
+    }
+}
+
+fn send_msg<T>(
+    token: &Addr,
+    contract: &Addr,
+    amount: Uint128,
+    msg: Binary,
+) -> StdResult<CosmosMsg<T>>
+where
+    T: CustomMsg,
+{
+    Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: token.to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send {
+            contract: contract.to_string(),
+            amount,
+            msg,
+        })?,
+        funds: vec![],
+    }))
+}
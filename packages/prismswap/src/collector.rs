@@ -0,0 +1,190 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::AssetInfo;
+use crate::fee::FeeConfig;
+use crate::router::{SwapOperation, TwapConfig};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+/// the most of the converted PRISM a collect can pay its caller, 100 bps
+pub const MAX_CALLER_INCENTIVE: &str = "0.01";
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the router the fees are swapped to PRISM through
+    pub router: Addr,
+    /// the factory looked up for a direct pair with PRISM, for the assets without a route
+    pub factory: Addr,
+    /// the PRISM token the fees are converted to
+    pub prism_token: Addr,
+    /// how the converted fees are shared out
+    pub distribution: DistributionConfig,
+    /// the share of the PRISM a collect converts paid to its caller, at most
+    /// [`MAX_CALLER_INCENTIVE`]
+    pub caller_incentive: Decimal,
+    /// the maximum spread of every hop of a conversion
+    pub max_spread: Decimal,
+    /// the bound every conversion output is checked against the output at the pair TWAPs with
+    pub twap_config: TwapConfig,
+    /// the seconds of the epochs the PRISM is distributed at most once in, the first epoch starting
+    /// at instantiation
+    pub epoch_length: u64,
+}
+
+/// ## Description
+/// This structure describes how the converted PRISM is shared out: a part is burned, a part is
+/// sent to the xPRISM staking contract, a part to the insurance fund and the rest is sent to the
+/// treasury.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionConfig {
+    pub burn_ratio: Decimal,
+    pub xprism_ratio: Decimal,
+    pub insurance_ratio: Decimal,
+    /// the xPRISM staking contract, its stakers earning the PRISM sent to it
+    pub xprism_contract: Addr,
+    pub insurance_fund: Addr,
+    pub treasury: Addr,
+}
+
+impl DistributionConfig {
+    pub fn is_valid(&self) -> bool {
+        self.burn_ratio + self.xprism_ratio + self.insurance_ratio <= Decimal::one()
+    }
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner, the router, the distribution, the caller incentive and the
+    /// protection of the conversions
+    UpdateConfig {
+        owner: Option<Addr>,
+        router: Option<Addr>,
+        distribution: Option<DistributionConfig>,
+        caller_incentive: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        twap_config: Option<TwapConfig>,
+    },
+    /// SetRoute registers the swap operations converting an asset to PRISM, or removes them to
+    /// fall back to the direct pair with PRISM
+    SetRoute {
+        asset_info: AssetInfo,
+        operations: Option<Vec<SwapOperation>>,
+    },
+    /// UpdateGuardian sets the address allowed to switch the withdraw-only mode besides the owner
+    UpdateGuardian { guardian: Option<Addr> },
+    /// SetWithdrawOnly switches the withdraw-only mode, in which no fees are collected
+    SetWithdrawOnly { withdraw_only: bool },
+    /// UpdateParamRegistry sets the parameter registry whose pause flag halts the fee collection
+    /// and whose caller incentive bound caps the incentive paid, `None` stops reading it
+    UpdateParamRegistry { param_registry: Option<Addr> },
+    /// Collect swaps the whole balance of the fee assets to PRISM, paying the caller incentive on
+    /// the converted PRISM to the sender, and distributes the PRISM balance. The PRISM is kept
+    /// until the next epoch once the current one had its distribution. Anyone can execute it
+    Collect { assets: Vec<AssetInfo> },
+    /// Internal use
+    Callback(CallbackMsg),
+}
+
+/// ## Description
+/// This structure describes the actions run once the fees are swapped.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Pay the caller incentive on the PRISM received since `prev_balance`, then burn, stake,
+    /// insure and send the rest of the balance to the treasury by the distribution ratios, unless
+    /// the current epoch had its distribution
+    Distribute { caller: Addr, prev_balance: Uint128 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Route returns the swap operations an asset is converted to PRISM with
+    Route { asset_info: AssetInfo },
+    /// Distribution returns the distribution of an epoch
+    Distribution { epoch: u64 },
+    /// FeeConfig returns the fees the factory sets for a pair and whether their protocol share
+    /// is sent to this collector
+    FeeConfig { asset_infos: [AssetInfo; 2] },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Route => RouteResponse,
+        Distribution => DistributionResponse,
+        FeeConfig => FeeConfigResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub router: Addr,
+    pub factory: Addr,
+    pub prism_token: Addr,
+    pub distribution: DistributionConfig,
+    pub caller_incentive: Decimal,
+    pub max_spread: Decimal,
+    pub twap_config: TwapConfig,
+    pub epoch_length: u64,
+    pub start_time: u64,
+    /// the last epoch the PRISM was distributed in
+    pub last_distributed_epoch: Option<u64>,
+    /// address allowed to switch the withdraw-only mode besides the owner
+    pub guardian: Option<Addr>,
+    /// whether the fee collection is halted
+    pub withdraw_only: bool,
+    /// the parameter registry read for the pause flag and the caller incentive bound
+    pub param_registry: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the route query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouteResponse {
+    pub asset_info: AssetInfo,
+    pub operations: Vec<SwapOperation>,
+    /// whether the operations are the direct pair with PRISM, no route being registered
+    pub is_default: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the distribution query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionResponse {
+    pub epoch: u64,
+    /// the block time in seconds of the distribution
+    pub time: u64,
+    /// the sender of the collect the distribution ran in
+    pub caller: Addr,
+    pub caller_amount: Uint128,
+    pub burn_amount: Uint128,
+    pub xprism_amount: Uint128,
+    pub insurance_amount: Uint128,
+    pub treasury_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the fee config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeConfigResponse {
+    /// the fees of the pair, its collector resolved to the factory collector if not set
+    pub fee_config: FeeConfig,
+    /// whether the protocol share of the pair is sent to this collector
+    pub is_collected: bool,
+}
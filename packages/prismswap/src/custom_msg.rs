@@ -0,0 +1,19 @@
+use schemars::JsonSchema;
+use std::fmt;
+
+/// ## Description
+/// This trait describes the custom messages a chain adds to [`cosmwasm_std::CosmosMsg`], the
+/// bounds the responses of the contracts require. The helpers building messages are generic over
+/// it so the same code serves the chains with and without the Terra bindings.
+pub trait CustomMsg: Clone + fmt::Debug + PartialEq + JsonSchema {}
+
+impl<T> CustomMsg for T where T: Clone + fmt::Debug + PartialEq + JsonSchema {}
+
+/// the custom message of the chain the package is built for, the Terra one with the `terra`
+/// feature
+#[cfg(feature = "terra")]
+pub type DefaultMsg = terra_cosmwasm::TerraMsgWrapper;
+
+/// the custom message of the chain the package is built for, none without the `terra` feature
+#[cfg(not(feature = "terra"))]
+pub type DefaultMsg = cosmwasm_std::Empty;
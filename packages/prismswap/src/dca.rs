@@ -0,0 +1,129 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::Asset;
+use crate::router::SwapOperation;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+pub const MAX_KEEPER_FEE: &str = "0.05";
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the router the swaps are executed through
+    pub router: Addr,
+    /// the share of every installment paid to the keeper executing it
+    pub keeper_fee: Decimal,
+    /// the seconds the TWAP bounding the installment swaps is averaged over
+    pub twap_window: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving CW20 tokens
+    Receive(Cw20ReceiveMsg),
+    /// CreateSchedule escrows the native budget sent, swapping an installment of it every interval
+    CreateSchedule {
+        budget: Asset,
+        amount_per_interval: Uint128,
+        interval: u64,
+        operations: Vec<SwapOperation>,
+        max_spread: Decimal,
+    },
+    /// CancelSchedule sends the budget left back to the owner. Only the schedule owner can
+    /// execute it
+    CancelSchedule { schedule_id: u64 },
+    /// ExecuteSchedule swaps the installment due, paying the keeper fee to the sender. Anyone can
+    /// execute it
+    ExecuteSchedule { schedule_id: u64 },
+    /// UpdateConfig updates the owner, the keeper fee and the TWAP window
+    UpdateConfig {
+        owner: Option<Addr>,
+        keeper_fee: Option<Decimal>,
+        twap_window: Option<u64>,
+    },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the CW20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// CreateSchedule escrows the sent tokens as the budget, swapping an installment of it every
+    /// interval
+    CreateSchedule {
+        amount_per_interval: Uint128,
+        interval: u64,
+        operations: Vec<SwapOperation>,
+        max_spread: Decimal,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Schedule returns a schedule with budget left
+    Schedule { schedule_id: u64 },
+    /// Schedules returns the schedules, of an owner if set, according to the specified parameters
+    /// in `start_after` and `limit` variables
+    Schedules {
+        owner: Option<Addr>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Schedule => ScheduleResponse,
+        Schedules => SchedulesResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub router: Addr,
+    pub keeper_fee: Decimal,
+    pub twap_window: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the schedule query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduleResponse {
+    pub schedule_id: u64,
+    /// the recipient of the swaps
+    pub owner: Addr,
+    /// the budget left
+    pub budget: Asset,
+    pub amount_per_interval: Uint128,
+    /// the seconds between two installments
+    pub interval: u64,
+    pub operations: Vec<SwapOperation>,
+    /// the maximum spread of every swap operation
+    pub max_spread: Decimal,
+    /// the time the next installment can be executed at
+    pub next_execution_time: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the schedules query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
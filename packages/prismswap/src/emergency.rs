@@ -0,0 +1,86 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract. The contract must be set
+/// as the guardian of the factory, the router, the staking and the collector contracts.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance address allowed to update the configuration and queue a restore
+    pub owner: Addr,
+    /// addresses allowed to shut the protocol down besides the owner
+    pub guardians: Vec<Addr>,
+    /// the factory, its pairs only letting liquidity be withdrawn once shut down
+    pub factory: Addr,
+    pub router: Addr,
+    pub staking: Addr,
+    pub collector: Addr,
+    /// the seconds a queued restore waits before it can be executed
+    pub restore_delay: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner, the guardians and the restore delay
+    UpdateConfig {
+        owner: Option<Addr>,
+        guardians: Option<Vec<Addr>>,
+        restore_delay: Option<u64>,
+    },
+    /// Shutdown switches the factory, the staking and the collector to withdraw-only mode and
+    /// pauses the router, cancelling any queued restore
+    Shutdown {},
+    /// QueueRestore queues the restore of the normal operation, executable once the restore delay
+    /// elapsed
+    QueueRestore {},
+    /// CancelRestore cancels the queued restore
+    CancelRestore {},
+    /// ExecuteRestore switches the withdraw-only mode off and unpauses the router once the queued
+    /// restore is due. Anyone can execute it
+    ExecuteRestore {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// State returns whether the protocol is shut down and when the queued restore is due
+    State {},
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        State => StateResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub guardians: Vec<Addr>,
+    pub factory: Addr,
+    pub router: Addr,
+    pub staking: Addr,
+    pub collector: Addr,
+    pub restore_delay: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the state query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub shutdown: bool,
+    /// the block time in seconds the queued restore can be executed from, none if not queued
+    pub restore_time: Option<u64>,
+}
@@ -0,0 +1,99 @@
+//! The event attribute keys the contracts emit and the attribute sets shared by the contracts
+//! emitting the same action, so indexers can rely on a single spelling of every key.
+
+use cosmwasm_std::{attr, Attribute, Uint128};
+
+use crate::asset::{Asset, AssetInfo};
+
+pub const ACTION: &str = "action";
+pub const SENDER: &str = "sender";
+pub const RECEIVER: &str = "receiver";
+pub const PAIR: &str = "pair";
+pub const ASSETS: &str = "assets";
+pub const SHARE: &str = "share";
+pub const WITHDRAWN_SHARE: &str = "withdrawn_share";
+pub const REFUND_ASSETS: &str = "refund_assets";
+pub const OFFER_ASSET: &str = "offer_asset";
+pub const ASK_ASSET: &str = "ask_asset";
+pub const OFFER_AMOUNT: &str = "offer_amount";
+pub const RETURN_AMOUNT: &str = "return_amount";
+pub const SPREAD_AMOUNT: &str = "spread_amount";
+pub const COMMISSION_AMOUNT: &str = "commission_amount";
+pub const PROTOCOL_FEE_AMOUNT: &str = "protocol_fee_amount";
+pub const LIQUIDITY_TOKEN_ADDR: &str = "liquidity_token_addr";
+
+pub const ACTION_SWAP: &str = "swap";
+pub const ACTION_PROVIDE_LIQUIDITY: &str = "provide_liquidity";
+pub const ACTION_WITHDRAW_LIQUIDITY: &str = "withdraw_liquidity";
+
+/// Returns the action attribute
+pub fn action(action: &str) -> Attribute {
+    attr(ACTION, action)
+}
+
+/// ## Description
+/// This structure describes a swap against a pool, emitted by the pair and the lbp.
+pub struct SwapEvent<'a> {
+    pub sender: &'a str,
+    pub receiver: &'a str,
+    pub offer_asset: &'a AssetInfo,
+    pub ask_asset: &'a AssetInfo,
+    pub offer_amount: Uint128,
+    pub return_amount: Uint128,
+    pub spread_amount: Uint128,
+    pub commission_amount: Uint128,
+}
+
+impl SwapEvent<'_> {
+    pub fn attributes(&self) -> Vec<Attribute> {
+        vec![
+            action(ACTION_SWAP),
+            attr(SENDER, self.sender),
+            attr(RECEIVER, self.receiver),
+            attr(OFFER_ASSET, self.offer_asset.to_string()),
+            attr(ASK_ASSET, self.ask_asset.to_string()),
+            attr(OFFER_AMOUNT, self.offer_amount),
+            attr(RETURN_AMOUNT, self.return_amount),
+            attr(SPREAD_AMOUNT, self.spread_amount),
+            attr(COMMISSION_AMOUNT, self.commission_amount),
+        ]
+    }
+}
+
+/// Returns the attributes of a liquidity provision minting `share` to the receiver
+pub fn provide_liquidity_attributes(
+    sender: &str,
+    receiver: &str,
+    assets: &[Asset],
+    share: Uint128,
+) -> Vec<Attribute> {
+    vec![
+        action(ACTION_PROVIDE_LIQUIDITY),
+        attr(SENDER, sender),
+        attr(RECEIVER, receiver),
+        attr(ASSETS, join_assets(assets)),
+        attr(SHARE, share),
+    ]
+}
+
+/// Returns the attributes of a liquidity withdrawal burning `share` for the refund assets
+pub fn withdraw_liquidity_attributes(
+    sender: &str,
+    share: Uint128,
+    refund_assets: &[Asset],
+) -> Vec<Attribute> {
+    vec![
+        action(ACTION_WITHDRAW_LIQUIDITY),
+        attr(SENDER, sender),
+        attr(WITHDRAWN_SHARE, share),
+        attr(REFUND_ASSETS, join_assets(refund_assets)),
+    ]
+}
+
+fn join_assets(assets: &[Asset]) -> String {
+    assets
+        .iter()
+        .map(|asset| asset.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
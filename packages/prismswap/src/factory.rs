@@ -1,39 +1,12 @@
-use std::str::FromStr;
-
 use crate::asset::PairInfo;
-use cosmwasm_std::{Addr, Decimal};
+use cosmwasm_std::Addr;
 use cw_asset::AssetInfo;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-pub const DEFAULT_TOTAL_FEE: &str = "0.003";
-pub const MAX_TOTAL_FEE: &str = "0.05";
-pub const DEFAULT_PROTOCOL_FEE: &str = "0.334";
-pub const MAX_PROTOCOL_FEE: &str = "0.8";
-
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-/// ## Description
-/// This structure describes a configuration of pair.
-pub struct FeeConfig {
-    pub total_fee: Decimal,
-    pub protocol_fee: Decimal,
-}
-
-impl FeeConfig {
-    pub fn is_valid(&self) -> bool {
-        self.total_fee <= Decimal::from_str(MAX_TOTAL_FEE).unwrap()
-            && self.protocol_fee <= Decimal::from_str(MAX_PROTOCOL_FEE).unwrap()
-    }
-}
-
-impl Default for FeeConfig {
-    fn default() -> Self {
-        Self {
-            total_fee: Decimal::from_str(DEFAULT_TOTAL_FEE).unwrap(),
-            protocol_fee: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
-        }
-    }
-}
+pub use crate::fee::{
+    FeeConfig, DEFAULT_PROTOCOL_FEE, DEFAULT_TOTAL_FEE, MAX_PROTOCOL_FEE, MAX_TOTAL_FEE,
+};
 
 /// ## Description
 /// This structure describes the basic settings for creating a contract.
@@ -69,6 +42,19 @@ pub enum ExecuteMsg {
         /// address assigned as admin to instantiated pairs
         pairs_admin: Option<Addr>,
     },
+    /// UpdatePairCreators replaces the addresses allowed to create pairs besides the owner
+    UpdatePairCreators { addresses: Vec<Addr> },
+    /// UpdateGuardian sets the address allowed to switch the withdraw-only mode besides the owner
+    UpdateGuardian { guardian: Option<Addr> },
+    /// SetWithdrawOnly switches the withdraw-only mode, in which no pair can be created and the
+    /// pairs only let liquidity be withdrawn
+    SetWithdrawOnly { withdraw_only: bool },
+    /// UpdateParamRegistry sets the parameter registry the pairs read their tunables from,
+    /// `None` leaving the pairs to their own settings
+    UpdateParamRegistry { param_registry: Option<Addr> },
+    /// UpdateStakingContract sets the staking contract the pairs bond the liquidity tokens of the
+    /// auto-staking providers in, `None` disabling the auto-stake
+    UpdateStakingContract { staking_contract: Option<Addr> },
     /// UpdatePairConfig updates configs of pair
     UpdatePairConfig {
         /// assets that indentify the registered pair
@@ -76,7 +62,7 @@ pub enum ExecuteMsg {
         /// new [`FeeConfig`] settings for pair
         fee_config: FeeConfig,
     },
-    /// CreatePair instantiates pair contract
+    /// CreatePair instantiates pair contract, by the owner or a pair creator
     CreatePair {
         /// the type of asset infos available in [`AssetInfo`]
         asset_infos: [AssetInfo; 2],
@@ -128,6 +114,17 @@ pub enum QueryMsg {
     },
 }
 
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Pair => PairInfo,
+        PairConfig => PairConfigResponse,
+        Pairs => PairsResponse,
+        PairsConfig => PairsConfigResponse,
+        FeeInfo => FeeInfoResponse,
+    }
+}
+
 /// ## Description
 /// A custom struct for each query response that returns controls settings of contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -142,6 +139,16 @@ pub struct ConfigResponse {
     pub collector: Addr,
     /// Address assigned as admin to instantiated pairs
     pub pairs_admin: Addr,
+    /// Addresses allowed to create pairs besides the owner
+    pub pair_creators: Vec<Addr>,
+    /// Address allowed to switch the withdraw-only mode besides the owner
+    pub guardian: Option<Addr>,
+    /// Whether the pairs only let liquidity be withdrawn
+    pub withdraw_only: bool,
+    /// The parameter registry the pairs read their tunables from
+    pub param_registry: Option<Addr>,
+    /// The staking contract the pairs bond the liquidity tokens of the auto-staking providers in
+    pub staking_contract: Option<Addr>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -151,10 +158,13 @@ pub struct PairConfigResponse {
 }
 
 /// ## Description
-/// This structure describes a migration message.
+/// This structure describes the migration messages of the contract, one variant for each version
+/// the code can be migrated to.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {
-    pub pairs_admin: Addr,
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    /// Migrates the legacy config, setting the admin of the pairs it did not store
+    V1_0_1 { pairs_admin: Addr },
 }
 
 /// ## Description
@@ -173,4 +183,10 @@ pub struct PairsConfigResponse {
 pub struct FeeInfoResponse {
     pub fee_config: FeeConfig,
     pub collector: Addr,
+    /// whether the pairs only let liquidity be withdrawn
+    pub withdraw_only: bool,
+    /// the parameter registry the pairs read their tunables from
+    pub param_registry: Option<Addr>,
+    /// the staking contract the liquidity tokens of the auto-staking providers are bonded in
+    pub staking_contract: Option<Addr>,
 }
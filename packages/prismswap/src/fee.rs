@@ -0,0 +1,114 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Addr, Api, Decimal, StdError, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_TOTAL_FEE: &str = "0.003";
+pub const MAX_TOTAL_FEE: &str = "0.05";
+pub const DEFAULT_PROTOCOL_FEE: &str = "0.334";
+pub const MAX_PROTOCOL_FEE: &str = "0.8";
+
+/// the basis points of a rate of 1
+pub const BPS_DENOMINATOR: u128 = 10_000;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+/// ## Description
+/// This structure describes the fees of a pair, set by the factory, read by the pair on every
+/// swap and reported by the collector, the protocol share of the commission being sent to the
+/// collector.
+pub struct FeeConfig {
+    /// the share of the swap return taken as commission
+    pub total_fee: Decimal,
+    /// the share of the commission sent to the collector, the rest staying in the pools
+    #[serde(alias = "protocol_fee")]
+    pub protocol_fee_share: Decimal,
+    /// the collector the protocol share is sent to, the factory collector if not set
+    pub collector: Option<Addr>,
+}
+
+impl FeeConfig {
+    /// Returns the fees of the basis points of the return and of the commission, sent to the
+    /// factory collector
+    pub fn from_bps(total_fee_bps: u64, protocol_fee_share_bps: u64) -> Self {
+        FeeConfig {
+            total_fee: Decimal::from_ratio(total_fee_bps, BPS_DENOMINATOR),
+            protocol_fee_share: Decimal::from_ratio(protocol_fee_share_bps, BPS_DENOMINATOR),
+            collector: None,
+        }
+    }
+
+    /// Returns the total fee in basis points, rounded down
+    pub fn total_fee_bps(&self) -> u64 {
+        (Uint128::from(BPS_DENOMINATOR) * self.total_fee).u128() as u64
+    }
+
+    /// Returns the protocol fee share in basis points, rounded down
+    pub fn protocol_fee_share_bps(&self) -> u64 {
+        (Uint128::from(BPS_DENOMINATOR) * self.protocol_fee_share).u128() as u64
+    }
+
+    /// ## Description
+    /// Returns an error if the total fee exceeds [`MAX_TOTAL_FEE`], the protocol fee share exceeds
+    /// [`MAX_PROTOCOL_FEE`] or the collector is not a valid address.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **api** is the object of type [`Api`].
+    pub fn validate(&self, api: &dyn Api) -> StdResult<()> {
+        if self.total_fee > Decimal::from_str(MAX_TOTAL_FEE)? {
+            return Err(StdError::generic_err(format!(
+                "total fee cannot exceed {}",
+                MAX_TOTAL_FEE
+            )));
+        }
+        if self.protocol_fee_share > Decimal::from_str(MAX_PROTOCOL_FEE)? {
+            return Err(StdError::generic_err(format!(
+                "protocol fee share cannot exceed {}",
+                MAX_PROTOCOL_FEE
+            )));
+        }
+        if let Some(collector) = &self.collector {
+            api.addr_validate(collector.as_str())?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the collector the protocol share is sent to, given the factory collector
+    pub fn collector_or(&self, factory_collector: Addr) -> Addr {
+        self.collector.clone().unwrap_or(factory_collector)
+    }
+
+    /// Returns the share of the commission sent to the collector
+    pub fn protocol_fee_amount(&self, commission_amount: Uint128) -> Uint128 {
+        commission_amount * self.protocol_fee_share
+    }
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        Self {
+            total_fee: Decimal::from_str(DEFAULT_TOTAL_FEE).unwrap(),
+            protocol_fee_share: Decimal::from_str(DEFAULT_PROTOCOL_FEE).unwrap(),
+            collector: None,
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes how a swap splits its offer, the amounts being in the ask asset but
+/// the tax, paid on the native offer before it reaches the pair.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct SwapBreakdown {
+    /// the amount returned once the commission is taken
+    pub return_amount: Uint128,
+    /// the return lost to the price impact
+    pub spread_amount: Uint128,
+    /// the commission taken from the return, the protocol fee included
+    pub commission_amount: Uint128,
+    /// the share of the commission sent to the collector
+    pub protocol_fee: Uint128,
+    /// the tax deducted from the native offer, in the offer asset
+    pub tax: Uint128,
+}
@@ -0,0 +1,159 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the token deposited to vote
+    pub xprism_token: Addr,
+    /// the staking contract whose allocation points follow the votes, which must register this
+    /// contract as its gauge
+    pub staking_contract: Addr,
+    /// the seconds every epoch lasts
+    pub epoch_length: u64,
+    /// the block time in seconds the first epoch starts at
+    pub start_time: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving xPRISM
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// Withdraw sends deposited xPRISM back, once the epoch the sender last voted in is over
+    Withdraw { amount: Uint128 },
+    /// Vote splits the deposit of the sender across pools for the current epoch, replacing its
+    /// previous vote of the epoch
+    Vote { votes: Vec<GaugeVote> },
+    /// Checkpoint sets the allocation points of the staking contract from the votes of the last
+    /// finished epoch. Anyone can execute it
+    Checkpoint {},
+}
+
+/// ## Description
+/// This structure describes the hook messages of the xPRISM sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposit the sent xPRISM as voting power of the sender
+    Deposit {},
+}
+
+/// ## Description
+/// This structure describes the share of its voting power a voter gives to a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GaugeVote {
+    pub lp_token: Addr,
+    /// the shares of a vote add up to 1 at most
+    pub ratio: Decimal,
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Epoch returns the current epoch
+    Epoch {},
+    /// Voter returns the deposit and the last vote of a voter
+    Voter { voter: Addr },
+    /// GaugeWeights returns the votes of the pools in an epoch according to the specified
+    /// parameters in `start_after` and `limit` variables.
+    GaugeWeights {
+        epoch: u64,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    /// VoterWeight returns the voting power a voter cast for a pool in an epoch
+    VoterWeight {
+        voter: Addr,
+        epoch: u64,
+        lp_token: Addr,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Epoch => EpochResponse,
+        Voter => VoterResponse,
+        GaugeWeights => GaugeWeightsResponse,
+        VoterWeight => VoterWeightResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub xprism_token: Addr,
+    pub staking_contract: Addr,
+    pub epoch_length: u64,
+    pub start_time: u64,
+    /// the last epoch whose votes were applied to the staking contract
+    pub last_applied_epoch: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the epoch query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochResponse {
+    pub epoch: u64,
+    pub start_time: u64,
+    pub end_time: u64,
+    /// the voting power cast in the epoch so far
+    pub total_weight: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the voter query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoterResponse {
+    pub voter: Addr,
+    pub deposit_amount: Uint128,
+    /// the epoch of the last vote
+    pub vote_epoch: Option<u64>,
+    /// the deposit the last vote was cast with
+    pub voting_power: Uint128,
+    pub votes: Vec<GaugeVote>,
+}
+
+/// ## Description
+/// This structure describes the voting power cast for a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GaugeWeight {
+    pub lp_token: Addr,
+    pub weight: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the gauge weights query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GaugeWeightsResponse {
+    pub epoch: u64,
+    pub total_weight: Uint128,
+    pub weights: Vec<GaugeWeight>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the voter weight query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VoterWeightResponse {
+    /// the voting power of the voter for the pool
+    pub weight: Uint128,
+    /// the voting power of every voter for the pool
+    pub gauge_weight: Uint128,
+}
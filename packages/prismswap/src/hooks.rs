@@ -0,0 +1,155 @@
+//! The messages PrismSwap sends to the contracts integrating with it, such as the staking
+//! contracts bonding liquidity on behalf of the providers, the analytics contracts listening to
+//! the pair creations and the collectors receiving the fees. The integrating contracts add the
+//! variants of [`HookExecuteMsg`] to their execute messages, and [`AutoStakeHookMsg`] to the
+//! hook messages of their cw20 tokens.
+
+use cosmwasm_std::{to_binary, Addr, Binary, Coin, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::custom_msg::CustomMsg;
+use crate::fee::FeeConfig;
+
+/// ## Description
+/// This enum describes the cw20 hook message the liquidity tokens of a provision are sent to a
+/// staking contract with, to bond them on behalf of the provider. It is the `Bond` hook of the
+/// PrismSwap staking contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoStakeHookMsg {
+    /// Bond bonds the sent liquidity tokens on behalf of `staker_addr`, the sender if not set
+    Bond { staker_addr: Option<Addr> },
+}
+
+impl AutoStakeHookMsg {
+    /// ## Description
+    /// Returns the message sending the liquidity tokens to the staking contract with the hook.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **liquidity_token** is the object of type [`Addr`].
+    ///
+    /// * **staking_contract** is the object of type [`Addr`].
+    ///
+    /// * **amount** is the object of type [`Uint128`].
+    pub fn into_send_msg<T>(
+        self,
+        liquidity_token: &Addr,
+        staking_contract: &Addr,
+        amount: Uint128,
+    ) -> StdResult<CosmosMsg<T>>
+    where
+        T: CustomMsg,
+    {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: liquidity_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: staking_contract.to_string(),
+                amount,
+                msg: to_binary(&self)?,
+            })?,
+            funds: vec![],
+        }))
+    }
+}
+
+/// ## Description
+/// This structure describes the message a listener receives once the factory created a pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairCreatedHookMsg {
+    pub pair_info: PairInfo,
+    pub fee_config: FeeConfig,
+}
+
+impl PairCreatedHookMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_binary(&HookExecuteMsg::PairCreated(self))
+    }
+
+    /// Returns the message executing the hook on the listener
+    pub fn into_cosmos_msg<T>(self, contract_addr: &Addr) -> StdResult<CosmosMsg<T>>
+    where
+        T: CustomMsg,
+    {
+        Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: self.into_binary()?,
+            funds: vec![],
+        }))
+    }
+}
+
+/// ## Description
+/// This structure describes the message a collector receives along with fees, the native fees
+/// being sent as the funds of the message and the cw20 ones transferred right before it.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeDepositHookMsg {
+    /// the contract the fees were taken by
+    pub source: Addr,
+    pub assets: Vec<Asset>,
+}
+
+impl FeeDepositHookMsg {
+    /// serializes the message
+    pub fn into_binary(self) -> StdResult<Binary> {
+        to_binary(&HookExecuteMsg::FeeDeposit(self))
+    }
+
+    /// ## Description
+    /// Returns the messages transferring the cw20 fees to the collector, then executing the hook
+    /// on it with the native fees as funds.
+    /// ## Params
+    /// * **self** is the type of the caller object.
+    ///
+    /// * **collector** is the object of type [`Addr`].
+    pub fn into_cosmos_msgs<T>(self, collector: &Addr) -> StdResult<Vec<CosmosMsg<T>>>
+    where
+        T: CustomMsg,
+    {
+        let mut messages: Vec<CosmosMsg<T>> = vec![];
+        let mut funds: Vec<Coin> = vec![];
+        for asset in self.assets.iter().filter(|asset| !asset.amount.is_zero()) {
+            match &asset.info {
+                AssetInfo::Cw20(contract_addr) => {
+                    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: contract_addr.to_string(),
+                        msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                            recipient: collector.to_string(),
+                            amount: asset.amount,
+                        })?,
+                        funds: vec![],
+                    }))
+                }
+                AssetInfo::Native(denom) => funds.push(Coin {
+                    denom: denom.clone(),
+                    amount: asset.amount,
+                }),
+            }
+        }
+        // the funds must be sorted by denom
+        funds.sort_by(|a, b| a.denom.cmp(&b.denom));
+
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: collector.to_string(),
+            msg: self.into_binary()?,
+            funds,
+        }));
+        Ok(messages)
+    }
+}
+
+/// ## Description
+/// This enum describes the execute messages the integrating contracts implement, each for the
+/// hooks they listen to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookExecuteMsg {
+    /// PairCreated is executed once the factory created a pair
+    PairCreated(PairCreatedHookMsg),
+    /// FeeDeposit is executed along with the deposited fees
+    FeeDeposit(FeeDepositHookMsg),
+}
@@ -0,0 +1,81 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::Asset;
+use cosmwasm_std::Addr;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance address allowed to pay out of the fund
+    pub owner: Addr,
+    /// the seconds a payout waits between its proposal and its execution
+    pub timelock: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// ProposePayout queues a payout out of the fund, executable once the timelock is over
+    ProposePayout { recipient: Addr, asset: Asset },
+    /// ExecutePayout sends a payout whose timelock is over
+    ExecutePayout { payout_id: u64 },
+    /// CancelPayout drops a payout not executed yet
+    CancelPayout { payout_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Payout returns a queued payout
+    Payout { payout_id: u64 },
+    /// Payouts returns the queued payouts according to the specified parameters in `start_after`
+    /// and `limit` variables
+    Payouts {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Payout => PayoutResponse,
+        Payouts => PayoutsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub timelock: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the payout query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutResponse {
+    pub payout_id: u64,
+    pub recipient: Addr,
+    pub asset: Asset,
+    /// the block time in seconds the payout can be executed from
+    pub executable_at: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the payouts query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PayoutsResponse {
+    pub payouts: Vec<PayoutResponse>,
+}
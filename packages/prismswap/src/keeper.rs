@@ -0,0 +1,132 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::Asset;
+use cosmwasm_std::{Addr, Binary, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration and the jobs
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving CW20 tokens
+    Receive(Cw20ReceiveMsg),
+    /// RegisterJob registers the execution of `msg` on `target` at most once every `interval`
+    /// seconds, paying `reward` from the funds of the job to the keeper working it
+    RegisterJob {
+        name: String,
+        target: Addr,
+        msg: Binary,
+        interval: u64,
+        reward: Asset,
+    },
+    /// UpdateJob updates the message, the interval and the reward amount of a job
+    UpdateJob {
+        job_id: u64,
+        msg: Option<Binary>,
+        interval: Option<u64>,
+        reward_amount: Option<Uint128>,
+    },
+    /// RemoveJob removes a job, sending the funds left to the owner
+    RemoveJob { job_id: u64 },
+    /// FundJob adds the native reward asset sent to the funds of a job. Anyone can execute it
+    FundJob { job_id: u64 },
+    /// Work executes a workable job, paying its reward to the sender. Anyone can execute it
+    Work { job_id: u64 },
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the CW20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// FundJob adds the tokens sent to the funds of a job rewarded in them
+    FundJob { job_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Job returns a job and whether it can be worked
+    Job { job_id: u64 },
+    /// Jobs returns the jobs according to the specified parameters in `start_after` and `limit`
+    /// variables
+    Jobs {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Workable returns the jobs due and funded enough to pay their reward
+    Workable {},
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Job => JobResponse,
+        Jobs => JobsResponse,
+        Workable => WorkableResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the job query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobResponse {
+    pub job_id: u64,
+    pub name: String,
+    pub target: Addr,
+    pub msg: Binary,
+    pub interval: u64,
+    /// the reward paid every time the job is worked
+    pub reward: Asset,
+    /// the funds left to pay the rewards
+    pub balance: Uint128,
+    /// the time the job was last worked, none before the first time
+    pub last_worked: Option<u64>,
+    pub workable: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the jobs query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct JobsResponse {
+    pub jobs: Vec<JobResponse>,
+}
+
+/// ## Description
+/// This structure describes a job a keeper can work.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WorkableJob {
+    pub job_id: u64,
+    pub name: String,
+    pub reward: Asset,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the workable query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WorkableResponse {
+    pub jobs: Vec<WorkableJob>,
+}
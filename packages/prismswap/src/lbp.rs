@@ -0,0 +1,125 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use crate::pair::SimulationResponse;
+use crate::sale::{ParticipantResponse, Whitelist};
+use cosmwasm_std::{Addr, Decimal};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the project providing the liquidity and withdrawing the proceeds
+    pub owner: Addr,
+    /// the factory the pool graduates into a pair of
+    pub factory: Addr,
+    /// the type of asset infos available in [`AssetInfo`]
+    pub asset_infos: [AssetInfo; 2],
+    /// the weights of the assets at the start, only their ratio mattering
+    pub start_weights: [Decimal; 2],
+    /// the weights of the assets at the end, reached linearly from the start weights
+    pub end_weights: [Decimal; 2],
+    /// the block time in seconds the swaps open at
+    pub start_time: u64,
+    /// the block time in seconds the swaps close at
+    pub end_time: u64,
+    /// the share of the return amount of every swap kept in the pool
+    pub commission_rate: Decimal,
+    /// the addresses allowed to swap, anyone if none
+    pub whitelist: Option<Whitelist>,
+    /// the most of an asset an address can receive from the swaps
+    pub purchase_cap: Option<Asset>,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// ## Description
+    /// Receives a message of type [`Cw20ReceiveMsg`]
+    Receive(Cw20ReceiveMsg),
+    /// ProvideLiquidity the owner provides the pool liquidity, before the start
+    ProvideLiquidity { assets: [Asset; 2] },
+    /// Swap an offer asset to the other, between the start and the end
+    Swap {
+        offer_asset: Asset,
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+    /// WithdrawLiquidity sends the whole pool to the owner, after the end
+    WithdrawLiquidity {},
+    /// Graduate seeds the factory pair of the assets with the pool at its final price, after the
+    /// end, creating the pair if the factory has none. The LP tokens and the rest of the pool
+    /// are sent to the owner
+    Graduate {},
+    /// Register whitelists the sender by a proof of its leaf in the merkle tree of the whitelist
+    Register { proof: Vec<String> },
+}
+
+/// ## Description
+/// This structure describes a CW20 hook message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Sell a given amount of asset
+    Swap {
+        belief_price: Option<Decimal>,
+        max_spread: Option<Decimal>,
+        to: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Returns controls settings that specified in custom [`ConfigResponse`] structure.
+    Config {},
+    /// Returns whether an address can take part and how much it bought in a
+    /// [`crate::sale::ParticipantResponse`] object.
+    Participant { address: String },
+    /// Returns the pool and the current weights in a [`PoolResponse`] object.
+    Pool {},
+    /// Returns information about the simulation of the swap in a
+    /// [`crate::pair::SimulationResponse`] object.
+    Simulation { offer_asset: Asset },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Participant => ParticipantResponse,
+        Pool => PoolResponse,
+        Simulation => SimulationResponse,
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub asset_infos: [AssetInfo; 2],
+    pub start_weights: [Decimal; 2],
+    pub end_weights: [Decimal; 2],
+    pub start_time: u64,
+    pub end_time: u64,
+    pub commission_rate: Decimal,
+    pub whitelist: Option<Whitelist>,
+    pub purchase_cap: Option<Asset>,
+    /// the pair the pool graduated into
+    pub graduated_pair: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for the pool query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub assets: [Asset; 2],
+    /// the weights of the assets at the current block time
+    pub weights: [Decimal; 2],
+}
@@ -1,6 +1,103 @@
+#[cfg(feature = "cosmwasm")]
+#[macro_use]
+mod macros;
+
+#[cfg(feature = "cosmwasm")]
+pub mod admin_proxy;
+#[cfg(feature = "cosmwasm")]
 pub mod asset;
+#[cfg(feature = "cosmwasm")]
+pub mod asset_list;
+#[cfg(feature = "cosmwasm")]
+pub mod auction;
+#[cfg(feature = "cosmwasm")]
+pub mod bond;
+#[cfg(feature = "cosmwasm")]
+pub mod bribes;
+#[cfg(feature = "cosmwasm")]
+pub mod builder;
+#[cfg(feature = "cosmwasm")]
+pub mod collector;
+#[cfg(feature = "cosmwasm")]
+pub mod custom_msg;
+#[cfg(feature = "cosmwasm")]
+pub mod dca;
+#[cfg(feature = "cosmwasm")]
+pub mod emergency;
+#[cfg(feature = "cosmwasm")]
+pub mod events;
+#[cfg(feature = "cosmwasm")]
 pub mod factory;
+#[cfg(feature = "cosmwasm")]
+pub mod fee;
+#[cfg(feature = "cosmwasm")]
+pub mod gauge;
+#[cfg(feature = "cosmwasm")]
+pub mod hooks;
+#[cfg(feature = "cosmwasm")]
+pub mod insurance;
+#[cfg(feature = "cosmwasm")]
+pub mod keeper;
+#[cfg(feature = "cosmwasm")]
+pub mod lbp;
+#[cfg(feature = "cosmwasm")]
+pub mod limit_order;
+#[cfg(feature = "cosmwasm")]
+pub mod lockdrop;
+pub mod math;
+#[cfg(feature = "cosmwasm")]
+pub mod merkle_airdrop;
+#[cfg(feature = "cosmwasm")]
+pub mod migrator;
+#[cfg(feature = "cosmwasm")]
+pub mod oracle;
+#[cfg(feature = "cosmwasm")]
+pub mod otc;
+#[cfg(feature = "cosmwasm")]
+pub mod pagination;
+#[cfg(feature = "cosmwasm")]
 pub mod pair;
+#[cfg(feature = "cosmwasm")]
+pub mod params;
+#[cfg(feature = "cosmwasm")]
+pub mod points;
+#[cfg(feature = "cosmwasm")]
+pub mod pol;
+#[cfg(feature = "cosmwasm")]
+pub mod price;
+#[cfg(feature = "cosmwasm")]
 pub mod querier;
+#[cfg(feature = "cosmwasm")]
+pub mod rebalancer;
+#[cfg(feature = "cosmwasm")]
+pub mod rfq;
+#[cfg(feature = "cosmwasm")]
 pub mod router;
+#[cfg(feature = "cosmwasm")]
+pub mod router_shim;
+#[cfg(feature = "cosmwasm")]
+pub mod sale;
+#[cfg(feature = "cosmwasm")]
+pub mod signed_decimal;
+#[cfg(feature = "cosmwasm")]
+pub mod staking;
+#[cfg(feature = "cosmwasm")]
+pub mod staking_strategy;
+#[cfg(feature = "cosmwasm")]
+pub mod stop_order;
+#[cfg(feature = "cosmwasm")]
+pub mod strategy;
+#[cfg(feature = "cosmwasm")]
+pub mod tax;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(test)]
+mod tests;
+#[cfg(feature = "cosmwasm")]
 pub mod token;
+#[cfg(feature = "cosmwasm")]
+pub mod treasury;
+#[cfg(feature = "cosmwasm")]
+pub mod vault;
+#[cfg(feature = "cosmwasm")]
+pub mod vesting;
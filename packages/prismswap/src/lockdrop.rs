@@ -0,0 +1,133 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration, receiving the early exit penalties
+    pub owner: Addr,
+    /// the PRISM token allocated, `total_allocation` of it being sent to the contract
+    pub prism_token: Addr,
+    /// the LP token locked
+    pub lp_token: Addr,
+    /// the block time in seconds the deposits open at
+    pub window_start: u64,
+    /// the block time in seconds the deposits close at, the lockups starting then
+    pub window_end: u64,
+    /// the PRISM shared out between the lockers by their weights
+    pub total_allocation: Uint128,
+    /// the durations the LP tokens can be locked for
+    pub lockup_durations: Vec<LockupDuration>,
+}
+
+/// ## Description
+/// This structure describes a duration the LP tokens can be locked for. A lockup weighs its
+/// amount times the multiplier of its duration, and exiting it after the deposit window and
+/// before its end forfeits the penalty share of the LP tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupDuration {
+    /// the seconds the LP tokens are locked for past the deposit window
+    pub duration: u64,
+    pub multiplier: Decimal,
+    pub penalty: Decimal,
+}
+
+impl LockupDuration {
+    pub fn is_valid(&self) -> bool {
+        !self.multiplier.is_zero() && self.penalty < Decimal::one()
+    }
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving the LP tokens locked
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// Unlock withdraws the LP tokens of a lockup of the sender. Withdrawing during the deposit
+    /// window drops the weight of the lockup, while exiting after it and before the end of the
+    /// lockup costs the penalty of its duration
+    Unlock { duration: u64 },
+    /// Claim sends the sender its PRISM allocation, once the deposit window is over
+    Claim {},
+}
+
+/// ## Description
+/// This structure describes the hook messages of the cw20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Lock the sent LP tokens for one of the lockup durations, during the deposit window
+    Lock { duration: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// State returns the total weight of the lockups
+    State {},
+    /// User returns the lockups and the PRISM allocation of a user
+    User { address: Addr },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        State => StateResponse,
+        User => UserResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub prism_token: Addr,
+    pub lp_token: Addr,
+    pub window_start: u64,
+    pub window_end: u64,
+    pub total_allocation: Uint128,
+    pub lockup_durations: Vec<LockupDuration>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the state query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    pub total_weight: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the user query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UserResponse {
+    pub address: Addr,
+    /// the weight of the lockups of the user, kept after an early exit
+    pub weight: Uint128,
+    /// the PRISM allocated to the user by the current total weight
+    pub allocation: Uint128,
+    pub claimed: bool,
+    pub lockups: Vec<LockupResponse>,
+}
+
+/// ## Description
+/// This structure describes a lockup of a user.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LockupResponse {
+    pub duration: u64,
+    pub amount: Uint128,
+    /// the block time in seconds the lockup can be exited from without penalty
+    pub unlock_time: u64,
+}
@@ -0,0 +1,24 @@
+/// Implements [`QueryResponses`](crate::querier::QueryResponses) for a query message, binding
+/// every variant to the type of its response. The match on the variants makes the build fail
+/// when a variant is left without a response type.
+macro_rules! query_responses {
+    ($msg:ident { $($variant:ident => $response:ty),+ $(,)? }) => {
+        impl $crate::querier::QueryResponses for $msg {
+            fn response_schemas(
+            ) -> std::collections::BTreeMap<String, schemars::schema::RootSchema> {
+                let mut schemas = std::collections::BTreeMap::new();
+                $(schemas.insert(
+                    $crate::querier::variant_key(stringify!($variant)),
+                    schemars::schema_for!($response),
+                );)+
+                schemas
+            }
+
+            fn response_schema(&self) -> schemars::schema::RootSchema {
+                match self {
+                    $($msg::$variant { .. } => schemars::schema_for!($response),)+
+                }
+            }
+        }
+    };
+}
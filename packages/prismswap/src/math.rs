@@ -0,0 +1,187 @@
+//! The constant product math of the pairs, kept free of the cosmwasm types so bots, frontends
+//! and property tests can reproduce the contract results exactly.
+//!
+//! Amounts are plain `u128` and rates are the atomics of an 18 decimals fixed point number,
+//! `DECIMAL_FRACTIONAL` being 1. Every division rounds down, the way the pair does.
+
+pub use self::uint256::U256;
+
+// the macro expansion is linted as if it was written here
+#[allow(clippy::assign_op_pattern, clippy::manual_div_ceil)]
+mod uint256 {
+    use uint::construct_uint;
+
+    construct_uint! {
+        /// 256 bits unsigned integer the intermediate products are computed in
+        pub struct U256(4);
+    }
+}
+
+/// the atomics of a rate of 1
+pub const DECIMAL_FRACTIONAL: u128 = 1_000_000_000_000_000_000;
+
+/// ## Description
+/// Returns the return amount, the spread amount and the commission amount of a swap of
+/// `offer_amount` against the pools, the commission being taken from the return amount. None if
+/// the offer pool is empty, the commission rate is not lower than 1 or the amounts overflow.
+/// ## Params
+/// * **offer_pool** is the pool of the offered asset.
+///
+/// * **ask_pool** is the pool of the asked asset.
+///
+/// * **offer_amount** is the amount offered.
+///
+/// * **commission_rate** is the atomics of the commission rate.
+pub fn compute_swap(
+    offer_pool: u128,
+    ask_pool: u128,
+    offer_amount: u128,
+    commission_rate: u128,
+) -> Option<(u128, u128, u128)> {
+    if commission_rate >= DECIMAL_FRACTIONAL {
+        return None;
+    }
+
+    let fractional = U256::from(DECIMAL_FRACTIONAL);
+    let offer_pool = U256::from(offer_pool);
+    let ask_pool = U256::from(ask_pool);
+    let offer_amount = U256::from(offer_amount);
+
+    // offer => ask
+    // ask_amount = (ask_pool - cp / (offer_pool + offer_amount)) * (1 - commission_rate)
+    let cp = offer_pool.checked_mul(ask_pool)?;
+    let return_amount = ask_pool.checked_mul(fractional)?.checked_sub(
+        cp.checked_mul(fractional)?
+            .checked_div(offer_pool.checked_add(offer_amount)?)?,
+    )? / fractional;
+
+    // calculate spread & commission
+    let spread_amount = (offer_amount
+        .checked_mul(ask_pool.checked_mul(fractional)?.checked_div(offer_pool)?)?
+        / fractional)
+        .saturating_sub(return_amount);
+    let commission_amount = return_amount.checked_mul(U256::from(commission_rate))? / fractional;
+
+    // commission will be absorbed to pool
+    let return_amount = return_amount - commission_amount;
+    Some((
+        to_u128(return_amount)?,
+        to_u128(spread_amount)?,
+        to_u128(commission_amount)?,
+    ))
+}
+
+/// ## Description
+/// Returns the offer amount, the spread amount and the commission amount of a swap returning
+/// `ask_amount` once the commission is taken. None if the pools cannot return it, the commission
+/// rate is not lower than 1 or the amounts overflow.
+/// ## Params
+/// * **offer_pool** is the pool of the offered asset.
+///
+/// * **ask_pool** is the pool of the asked asset.
+///
+/// * **ask_amount** is the amount to receive.
+///
+/// * **commission_rate** is the atomics of the commission rate.
+pub fn compute_offer_amount(
+    offer_pool: u128,
+    ask_pool: u128,
+    ask_amount: u128,
+    commission_rate: u128,
+) -> Option<(u128, u128, u128)> {
+    if commission_rate >= DECIMAL_FRACTIONAL {
+        return None;
+    }
+
+    let fractional = U256::from(DECIMAL_FRACTIONAL);
+
+    // ask => offer
+    // offer_amount = cp / (ask_pool - ask_amount / (1 - commission_rate)) - offer_pool
+    let cp = U256::from(offer_pool).checked_mul(U256::from(ask_pool))?;
+    let one_minus_commission = fractional - U256::from(commission_rate);
+    let inv_one_minus_commission = fractional * fractional / one_minus_commission;
+
+    let before_commission_deduction =
+        to_u128(U256::from(ask_amount).checked_mul(inv_one_minus_commission)? / fractional)?;
+    let remaining_ask_pool = ask_pool.checked_sub(before_commission_deduction)?;
+    if remaining_ask_pool == 0 {
+        return None;
+    }
+
+    let offer_amount = to_u128(cp / U256::from(remaining_ask_pool))?.checked_sub(offer_pool)?;
+
+    let spread_amount = to_u128(
+        U256::from(offer_amount).checked_mul(
+            (U256::from(ask_pool) * fractional).checked_div(U256::from(offer_pool))?,
+        )? / fractional,
+    )?
+    .saturating_sub(before_commission_deduction);
+    let commission_amount = to_u128(
+        U256::from(before_commission_deduction) * U256::from(commission_rate) / fractional,
+    )?;
+    Some((offer_amount, spread_amount, commission_amount))
+}
+
+/// Returns the value as a `u128`, none if it does not fit
+fn to_u128(value: U256) -> Option<u128> {
+    if value > U256::from(u128::MAX) {
+        return None;
+    }
+
+    Some(value.as_u128())
+}
+
+/// ## Description
+/// Returns the liquidity token amount minted for the deposits, the square root of their
+/// product for the first provision and their smallest share of the pools after.
+/// ## Params
+/// * **deposits** are the deposited amounts, in the order of the pools.
+///
+/// * **pools** are the pools before the deposit.
+///
+/// * **total_share** is the liquidity token supply before the deposit.
+pub fn compute_share(deposits: [u128; 2], pools: [u128; 2], total_share: u128) -> u128 {
+    if total_share == 0 {
+        // Initial share = collateral amount
+        return (U256::from(deposits[0]) * U256::from(deposits[1]))
+            .integer_sqrt()
+            .as_u128();
+    }
+
+    // min(1, 2)
+    // 1. sqrt(deposit_0 * exchange_rate_0_to_1 * deposit_0) * (total_share / sqrt(pool_0 * pool_1))
+    // == deposit_0 * total_share / pool_0
+    // 2. sqrt(deposit_1 * exchange_rate_1_to_0 * deposit_1) * (total_share / sqrt(pool_1 * pool_1))
+    // == deposit_1 * total_share / pool_1
+    std::cmp::min(
+        multiply_ratio(deposits[0], total_share, pools[0]),
+        multiply_ratio(deposits[1], total_share, pools[1]),
+    )
+}
+
+/// ## Description
+/// Returns the pool amounts refunded for burning `share` liquidity tokens out of `total_share`.
+/// ## Params
+/// * **pools** are the pools before the withdrawal.
+///
+/// * **share** is the liquidity token amount burned.
+///
+/// * **total_share** is the liquidity token supply before the withdrawal.
+pub fn compute_withdraw_amounts(pools: [u128; 2], share: u128, total_share: u128) -> [u128; 2] {
+    let share_ratio = multiply_ratio(share, DECIMAL_FRACTIONAL, total_share);
+    [
+        multiply_ratio(pools[0], share_ratio, DECIMAL_FRACTIONAL),
+        multiply_ratio(pools[1], share_ratio, DECIMAL_FRACTIONAL),
+    ]
+}
+
+/// Returns `value * numerator / denominator`, the product being computed in 256 bits
+pub fn multiply_ratio(value: u128, numerator: u128, denominator: u128) -> u128 {
+    (U256::from(value) * U256::from(numerator) / U256::from(denominator)).as_u128()
+}
+
+/// Returns the atomics of a decimal, the rates the functions of this module take
+#[cfg(feature = "cosmwasm")]
+pub fn decimal_atomics(value: cosmwasm_std::Decimal) -> u128 {
+    (cosmwasm_std::Uint128::from(DECIMAL_FRACTIONAL) * value).u128()
+}
@@ -0,0 +1,103 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to register the merkle roots and claw back the unclaimed rewards
+    pub owner: Addr,
+    /// the token the rewards are paid in
+    pub token: Addr,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving the reward tokens
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// Claim sends the rewards of the sender in a stage, proven against its merkle root
+    Claim {
+        stage: u8,
+        amount: Uint128,
+        /// the hex encoded sibling hashes from the leaf up to the root
+        proof: Vec<String>,
+    },
+    /// Clawback sends the rewards left unclaimed in a stage past its deadline to the recipient
+    Clawback { stage: u8, recipient: Addr },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the reward tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// RegisterMerkleRoot opens a new stage funded with the sent tokens. Only the owner can
+    /// execute it
+    RegisterMerkleRoot {
+        /// the hex encoded root of the tree, the leaves of which are the SHA-256 hashes of the
+        /// address followed by the amount
+        merkle_root: String,
+        /// the block time in seconds from which the stage can no longer be claimed
+        deadline: u64,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Stage returns the merkle root and the claimed rewards of a stage
+    Stage { stage: u8 },
+    /// IsClaimed returns whether an address claimed its rewards in a stage
+    IsClaimed { stage: u8, address: String },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Stage => StageResponse,
+        IsClaimed => IsClaimedResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub token: Addr,
+    /// the last stage registered, 0 before the first one
+    pub latest_stage: u8,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the stage query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StageResponse {
+    pub stage: u8,
+    pub merkle_root: String,
+    pub deadline: u64,
+    /// the rewards the stage was funded with
+    pub total_amount: Uint128,
+    pub claimed_amount: Uint128,
+    /// whether the unclaimed rewards were clawed back
+    pub clawed_back: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the is claimed query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct IsClaimedResponse {
+    pub is_claimed: bool,
+}
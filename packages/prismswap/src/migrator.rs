@@ -0,0 +1,139 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::AssetInfo;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the PrismSwap factory the destination pairs are looked up in
+    pub factory: Addr,
+    /// the staking contract the migrated LP tokens are bonded to, none to disable staking
+    pub staking: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving Terraswap or Astroport LP tokens
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner and the staking contract
+    UpdateConfig {
+        owner: Option<Addr>,
+        staking: Option<Addr>,
+    },
+    /// Internal use
+    Callback(CallbackMsg),
+}
+
+/// ## Description
+/// This structure describes the hook messages of the LP tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Migrate withdraws the sent LP tokens from `source_pair`, the Terraswap or Astroport pair
+    /// issuing them, and provides the withdrawn assets to the PrismSwap pair of the same assets,
+    /// sending the PrismSwap LP tokens to the sender, or bonding them for it if `stake` is set
+    Migrate {
+        source_pair: Addr,
+        slippage_tolerance: Option<Decimal>,
+        stake: bool,
+    },
+}
+
+/// ## Description
+/// This structure describes the actions run once the liquidity is withdrawn from the source pair.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Provide the assets received since `prev_balances` to the PrismSwap pair
+    ProvideLiquidity {
+        receiver: Addr,
+        asset_infos: [AssetInfo; 2],
+        prev_balances: [Uint128; 2],
+        slippage_tolerance: Option<Decimal>,
+        stake: bool,
+    },
+    /// Bond the LP tokens received since `prev_balance` to the staking contract for `receiver`
+    StakeLiquidity {
+        receiver: Addr,
+        lp_token: Addr,
+        prev_balance: Uint128,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub staking: Option<Addr>,
+}
+
+/// ## Description
+/// This enum describes the asset format of the Terraswap and Astroport pairs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LegacyAssetInfo {
+    Token { contract_addr: String },
+    NativeToken { denom: String },
+}
+
+impl From<LegacyAssetInfo> for AssetInfo {
+    fn from(asset_info: LegacyAssetInfo) -> Self {
+        match asset_info {
+            LegacyAssetInfo::Token { contract_addr } => {
+                AssetInfo::Cw20(Addr::unchecked(contract_addr))
+            }
+            LegacyAssetInfo::NativeToken { denom } => AssetInfo::Native(denom),
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the pair info returned by the Terraswap and Astroport pairs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyPairInfo {
+    pub asset_infos: [LegacyAssetInfo; 2],
+    pub contract_addr: String,
+    pub liquidity_token: String,
+}
+
+/// ## Description
+/// This structure describes the query messages of the Terraswap and Astroport pairs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LegacyPairQueryMsg {
+    Pair {},
+}
+
+/// ## Description
+/// This structure describes the hook messages of the Terraswap and Astroport LP tokens.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LegacyPairCw20HookMsg {
+    WithdrawLiquidity {},
+}
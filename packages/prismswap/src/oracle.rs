@@ -0,0 +1,225 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::AssetInfo;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+/// the maximum number of routes a feed combines
+pub const MAX_ROUTES: usize = 5;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration and the tracked pairs
+    pub owner: Addr,
+    /// the factory the tracked pairs are looked up in
+    pub factory: Addr,
+    /// the minimum number of seconds between two observations of a pair
+    pub observation_period: u64,
+    /// the half-lives in seconds of the exponential moving averages kept for every pair
+    pub ema_half_lives: Vec<u64>,
+    /// the number of seconds the feeds answering the standard price query are averaged over
+    pub price_window: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// AddPair starts recording the price accumulators of the pair of the assets
+    AddPair { asset_infos: [AssetInfo; 2] },
+    /// RemovePair stops tracking the pair of the assets, dropping its observations
+    RemovePair { asset_infos: [AssetInfo; 2] },
+    /// SetFeed prices `base` in `quote` by combining the TWAPs of the routes, every route being a
+    /// path of assets from `base` to `quote` through tracked pairs
+    SetFeed {
+        base: AssetInfo,
+        quote: AssetInfo,
+        routes: Vec<Vec<AssetInfo>>,
+        aggregation: Aggregation,
+    },
+    /// RemoveFeed removes the feed of `base` in `quote`
+    RemoveFeed { base: AssetInfo, quote: AssetInfo },
+    /// Update records the price accumulators of every tracked pair last observed at least one
+    /// observation period ago. Anyone can execute it
+    Update {},
+    /// UpdateConfig updates the owner, the observation period, the EMA half-lives and the price
+    /// window
+    UpdateConfig {
+        owner: Option<Addr>,
+        observation_period: Option<u64>,
+        ema_half_lives: Option<Vec<u64>>,
+        price_window: Option<u64>,
+    },
+}
+
+/// ## Description
+/// This enum describes how the prices of the routes of a feed are combined.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Aggregation {
+    /// the median of the route prices, the mean of the two middle ones for an even count
+    Median,
+    /// the mean of the route prices weighted by the liquidity of their shallowest pool
+    LiquidityWeighted,
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Pairs returns the tracked pairs according to the specified parameters in `start_after`
+    /// and `limit` variables
+    Pairs {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    /// Price returns the rate of `base` in `quote`, denoms or token addresses, from their feed
+    /// over the price window, in the schema of the oracles Terra lending protocols consume
+    Price { base: String, quote: String },
+    /// Decimals returns the number of decimals of the rates
+    Decimals {},
+    /// Twap returns the time-weighted average price of `base` in `quote` over at least `window`
+    /// seconds
+    Twap {
+        base: AssetInfo,
+        quote: AssetInfo,
+        window: u64,
+    },
+    /// Ema returns the exponential moving average price of `base` in `quote` with the given
+    /// half-life, as of the last observation
+    Ema {
+        base: AssetInfo,
+        quote: AssetInfo,
+        half_life: u64,
+    },
+    /// Feed returns the routes and the aggregation of the feed of `base` in `quote`
+    Feed { base: AssetInfo, quote: AssetInfo },
+    /// AggregatePrice returns the price of `base` in `quote` combining the TWAPs over at least
+    /// `window` seconds of the routes of their feed
+    AggregatePrice {
+        base: AssetInfo,
+        quote: AssetInfo,
+        window: u64,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Pairs => PairsResponse,
+        Price => PriceResponse,
+        Decimals => DecimalsResponse,
+        Twap => TwapResponse,
+        Ema => EmaResponse,
+        Feed => FeedResponse,
+        AggregatePrice => AggregatePriceResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub observation_period: u64,
+    pub ema_half_lives: Vec<u64>,
+    pub price_window: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the pair query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairResponse {
+    pub pair: Addr,
+    pub asset_infos: [AssetInfo; 2],
+    /// the number of observations recorded, the oldest dropped past the history kept
+    pub observation_count: u64,
+    /// the time of the last observation, none before the first
+    pub last_observation_time: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the pairs query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PairsResponse {
+    pub pairs: Vec<PairResponse>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the price query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PriceResponse {
+    /// the price of the base asset in the quote asset
+    pub rate: Decimal,
+    /// the time of the oldest last observation of the pairs the rate is computed from
+    pub last_updated_base: u64,
+    /// same as `last_updated_base`, the base and quote assets being priced together
+    pub last_updated_quote: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the decimals query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DecimalsResponse {
+    pub decimals: u32,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the TWAP query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapResponse {
+    /// the average price of the base asset in the quote asset
+    pub price: Decimal,
+    /// the number of seconds actually averaged over
+    pub window: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the EMA query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmaResponse {
+    /// the moving average price of the base asset in the quote asset
+    pub price: Decimal,
+    pub half_life: u64,
+    /// the time of the observation the average was last updated at
+    pub last_updated: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the feed query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeedResponse {
+    pub base: AssetInfo,
+    pub quote: AssetInfo,
+    pub routes: Vec<Vec<AssetInfo>>,
+    pub aggregation: Aggregation,
+}
+
+/// ## Description
+/// This structure describes the price of a route of a feed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RoutePrice {
+    pub route: Vec<AssetInfo>,
+    /// the product of the TWAPs of the pairs along the route
+    pub price: Decimal,
+    /// the depth of the shallowest pool of the route, valued in the quote asset
+    pub liquidity: Uint128,
+    /// the time of the oldest last observation of the pairs of the route
+    pub last_updated: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the aggregate price query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AggregatePriceResponse {
+    /// the combined price of the base asset in the quote asset
+    pub price: Decimal,
+    pub route_prices: Vec<RoutePrice>,
+}
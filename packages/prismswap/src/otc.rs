@@ -0,0 +1,92 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::Asset;
+use cosmwasm_std::Addr;
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving cw20 tokens
+    Receive(Cw20ReceiveMsg),
+    /// CreateOffer escrows the sent native asset until it is filled with the ask asset
+    CreateOffer {
+        /// the asset and the exact amount the maker wants in return
+        ask: Asset,
+        /// the only address allowed to fill the offer, anyone if not set
+        counterparty: Option<Addr>,
+        /// the block time in seconds from which the offer can no longer be filled
+        expiry: u64,
+    },
+    /// Fill sends the ask asset of an offer as the sent native asset to its maker and the escrowed
+    /// asset to the sender
+    Fill { offer_id: u64 },
+    /// Reclaim sends the escrowed asset of an expired offer back to its maker
+    Reclaim { offer_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the cw20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// CreateOffer escrows the sent tokens until they are filled with the ask asset
+    CreateOffer {
+        ask: Asset,
+        counterparty: Option<Addr>,
+        expiry: u64,
+    },
+    /// Fill fills an offer with the sent tokens
+    Fill { offer_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Offer returns an open offer
+    Offer { offer_id: u64 },
+    /// Offers returns the open offers, of a maker if set, according to the specified parameters in
+    /// `start_after` and `limit` variables
+    Offers {
+        maker: Option<Addr>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Offer => OfferResponse,
+        Offers => OffersResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the offer query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OfferResponse {
+    pub offer_id: u64,
+    pub maker: Addr,
+    /// the escrowed asset
+    pub offer: Asset,
+    pub ask: Asset,
+    pub counterparty: Option<Addr>,
+    pub expiry: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the offers query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OffersResponse {
+    pub offers: Vec<OfferResponse>,
+}
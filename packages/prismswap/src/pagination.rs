@@ -0,0 +1,30 @@
+use cw_storage_plus::{Bound, U64Key};
+
+/// the page size of the list queries when no limit is given
+pub const DEFAULT_LIMIT: u32 = 10;
+/// the largest page size of the list queries
+pub const MAX_LIMIT: u32 = 30;
+
+/// Returns the page size of a list query, capped to [`MAX_LIMIT`]
+pub fn calc_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+/// Returns the first key after the given one, by appending a 1 byte, for the composite keys
+/// whose parts are not length prefixed
+pub fn calc_range_start(start_after: Option<Vec<u8>>) -> Option<Vec<u8>> {
+    start_after.map(|mut key| {
+        key.push(1);
+        key
+    })
+}
+
+/// Returns the bound starting a range after the key
+pub fn start_after_bound<K: Into<Vec<u8>>>(start_after: Option<K>) -> Option<Bound> {
+    start_after.map(Bound::exclusive)
+}
+
+/// Returns the bound starting a range after the id, for the maps keyed by [`U64Key`]
+pub fn start_after_id_bound(start_after: Option<u64>) -> Option<Bound> {
+    start_after.map(|id| Bound::exclusive(U64Key::new(id)))
+}
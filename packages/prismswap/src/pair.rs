@@ -3,6 +3,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use crate::asset::PairInfo;
+use crate::fee::{FeeConfig, SwapBreakdown};
 
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use cw20::Cw20ReceiveMsg;
@@ -22,6 +23,8 @@ pub struct InstantiateMsg {
     pub token_code_id: u64,
     /// the factory contract address
     pub factory: Addr,
+    /// the fees the pair is created with, the pair reading its fees from the factory afterwards
+    pub fee_config: FeeConfig,
 }
 
 /// ## Description
@@ -40,6 +43,8 @@ pub enum ExecuteMsg {
         slippage_tolerance: Option<Decimal>,
         /// the receiver of provide liquidity
         receiver: Option<String>,
+        /// bond the liquidity tokens in the factory staking contract on behalf of the receiver
+        auto_stake: Option<bool>,
     },
     /// Swap an offer asset to the other
     Swap {
@@ -80,6 +85,23 @@ pub enum QueryMsg {
     Simulation { offer_asset: Asset },
     /// Returns information about the reverse simulation in a [`ReverseSimulationResponse`] object.
     ReverseSimulation { ask_asset: Asset },
+    /// Returns the price accumulators of the pool in a [`CumulativePricesResponse`] object.
+    CumulativePrices {},
+    /// Returns the time-weighted average prices over at least `window` seconds in a
+    /// [`TwapResponse`] object.
+    Twap { window: u64 },
+}
+
+query_responses! {
+    QueryMsg {
+        Pair => PairInfo,
+        Pool => PoolResponse,
+        Config => ConfigResponse,
+        Simulation => SimulationResponse,
+        ReverseSimulation => ReverseSimulationResponse,
+        CumulativePrices => CumulativePricesResponse,
+        Twap => TwapResponse,
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -103,6 +125,10 @@ pub struct SimulationResponse {
     pub return_amount: Uint128,
     pub spread_amount: Uint128,
     pub commission_amount: Uint128,
+    /// the same amounts with the protocol fee, in the shape the router reports for every
+    /// operation
+    #[serde(default)]
+    pub breakdown: SwapBreakdown,
 }
 
 /// ## Description
@@ -113,3 +139,41 @@ pub struct ReverseSimulationResponse {
     pub spread_amount: Uint128,
     pub commission_amount: Uint128,
 }
+
+/// ## Description
+/// CumulativePricesResponse returns the price accumulators of the pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CumulativePricesResponse {
+    pub assets: [Asset; 2],
+    /// the sum of the asset 0 price in asset 1 over every second, scaled by 1e18 and wrapping
+    /// on overflow
+    pub price0_cumulative: Uint128,
+    /// the sum of the asset 1 price in asset 0 over every second, scaled by 1e18 and wrapping
+    /// on overflow
+    pub price1_cumulative: Uint128,
+    /// the block time the accumulators are computed at
+    pub timestamp: u64,
+}
+
+/// ## Description
+/// TwapResponse returns the time-weighted average prices of the pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapResponse {
+    pub asset_infos: [AssetInfo; 2],
+    /// the average price of asset 0 in asset 1
+    pub price0: Decimal,
+    /// the average price of asset 1 in asset 0
+    pub price1: Decimal,
+    /// the number of seconds actually averaged over
+    pub window: u64,
+}
+
+/// ## Description
+/// This structure describes the migration messages of the contract, one variant for each version
+/// the code can be migrated to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    /// Migrates the code, the state being unchanged
+    V1_0_0 {},
+}
@@ -0,0 +1,170 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use std::cmp::Ordering;
+
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
+
+/// the spread the swaps setting none are limited to
+pub const DEFAULT_MAX_SPREAD_KEY: &str = "default_max_spread";
+/// the most of the converted PRISM a collect can pay its caller, below the collector setting
+pub const MAX_CALLER_INCENTIVE_KEY: &str = "max_caller_incentive";
+/// whether the swaps through the pairs and the router and the fee collection are halted
+pub const PAUSED_KEY: &str = "paused";
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance address allowed to register and set the parameters
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This enum describes the typed value of a parameter.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ParamValue {
+    Bool(bool),
+    Decimal(Decimal),
+    Uint128(Uint128),
+}
+
+impl ParamValue {
+    /// Compares two values of the same type, none if they differ in type or are flags
+    pub fn compare(&self, other: &ParamValue) -> Option<Ordering> {
+        match (self, other) {
+            (ParamValue::Decimal(a), ParamValue::Decimal(b)) => a.partial_cmp(b),
+            (ParamValue::Uint128(a), ParamValue::Uint128(b)) => a.partial_cmp(b),
+            _ => None,
+        }
+    }
+
+    pub fn same_type(&self, other: &ParamValue) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn as_bool(&self) -> StdResult<bool> {
+        match self {
+            ParamValue::Bool(value) => Ok(*value),
+            _ => Err(StdError::generic_err("parameter is not a bool")),
+        }
+    }
+
+    pub fn as_decimal(&self) -> StdResult<Decimal> {
+        match self {
+            ParamValue::Decimal(value) => Ok(*value),
+            _ => Err(StdError::generic_err("parameter is not a decimal")),
+        }
+    }
+
+    pub fn as_uint128(&self) -> StdResult<Uint128> {
+        match self {
+            ParamValue::Uint128(value) => Ok(*value),
+            _ => Err(StdError::generic_err("parameter is not a uint128")),
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the inclusive bounds the value of a parameter is kept within, of the
+/// value type.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ParamBounds {
+    pub min: Option<ParamValue>,
+    pub max: Option<ParamValue>,
+}
+
+impl ParamBounds {
+    /// Checks the value is within the bounds, the bounds being of its type
+    pub fn check(&self, value: &ParamValue) -> StdResult<()> {
+        if let Some(min) = &self.min {
+            match value.compare(min) {
+                None => return Err(StdError::generic_err("bounds must match the value type")),
+                Some(Ordering::Less) => {
+                    return Err(StdError::generic_err("value is below the minimum"))
+                }
+                _ => {}
+            }
+        }
+        if let Some(max) = &self.max {
+            match value.compare(max) {
+                None => return Err(StdError::generic_err("bounds must match the value type")),
+                Some(Ordering::Greater) => {
+                    return Err(StdError::generic_err("value is above the maximum"))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// RegisterParam registers a parameter or replaces its type and bounds, its value being
+    /// within the bounds
+    RegisterParam {
+        key: String,
+        value: ParamValue,
+        bounds: Option<ParamBounds>,
+    },
+    /// SetParam updates the value of a registered parameter, of its type and within its bounds
+    SetParam { key: String, value: ParamValue },
+    /// RemoveParam removes a parameter, the readers falling back to their own settings
+    RemoveParam { key: String },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Param returns a parameter, its value being none if not registered
+    Param { key: String },
+    /// Params returns the registered parameters according to the specified parameters in
+    /// `start_after` and `limit` variables
+    Params {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Param => ParamResponse,
+        Params => ParamsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the param query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ParamResponse {
+    pub key: String,
+    pub value: Option<ParamValue>,
+    pub bounds: Option<ParamBounds>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the params query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ParamsResponse {
+    pub params: Vec<ParamResponse>,
+}
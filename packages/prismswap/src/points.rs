@@ -0,0 +1,131 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the contracts allowed to report swaps, like the router
+    pub reporters: Vec<Addr>,
+    /// the assets swaps are measured in
+    pub volume_assets: Vec<VolumeAsset>,
+    /// the block time in seconds the first epoch starts at
+    pub start_time: u64,
+    /// the length of an epoch in seconds
+    pub epoch_length: u64,
+}
+
+/// ## Description
+/// This structure describes an asset the volume of swaps is measured in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VolumeAsset {
+    pub asset_info: AssetInfo,
+    /// the volume counted per unit of the asset, like its price in the quote asset
+    pub weight: Decimal,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner, the reporters and the volume assets
+    UpdateConfig {
+        owner: Option<Addr>,
+        reporters: Option<Vec<Addr>>,
+        volume_assets: Option<Vec<VolumeAsset>>,
+    },
+    /// ReportSwap adds the volume of a completed swap to the trader in the current epoch,
+    /// measured on the offer asset if it is a volume asset, else the return asset. Only the
+    /// reporters can execute it
+    ReportSwap {
+        trader: Addr,
+        offer_asset: Asset,
+        return_asset: Asset,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Epoch returns the totals of an epoch, the current one if not set
+    Epoch { epoch: Option<u64> },
+    /// TraderVolume returns the volume of a trader in an epoch, the current one if not set
+    TraderVolume { trader: Addr, epoch: Option<u64> },
+    /// Leaderboard returns the traders of an epoch, the current one if not set, by descending
+    /// volume according to the specified parameters in `start_after` and `limit` variables
+    Leaderboard {
+        epoch: Option<u64>,
+        start_after: Option<LeaderboardEntry>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Epoch => EpochResponse,
+        TraderVolume => TraderVolumeResponse,
+        Leaderboard => LeaderboardResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub reporters: Vec<Addr>,
+    pub volume_assets: Vec<VolumeAsset>,
+    pub start_time: u64,
+    pub epoch_length: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the epoch query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EpochResponse {
+    pub epoch: u64,
+    /// the block time in seconds the epoch starts at
+    pub start_time: u64,
+    pub end_time: u64,
+    pub total_volume: Uint128,
+    /// the number of swaps reported
+    pub swap_count: u64,
+    /// the number of traders with volume
+    pub trader_count: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the trader volume query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TraderVolumeResponse {
+    pub trader: Addr,
+    pub epoch: u64,
+    pub volume: Uint128,
+}
+
+/// ## Description
+/// This structure describes a trader on the leaderboard of an epoch.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LeaderboardEntry {
+    pub trader: Addr,
+    pub volume: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the leaderboard query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LeaderboardResponse {
+    pub epoch: u64,
+    pub entries: Vec<LeaderboardEntry>,
+}
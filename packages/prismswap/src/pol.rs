@@ -0,0 +1,125 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance address allowed to set the target ranges and move the assets out
+    pub owner: Addr,
+    /// address allowed to provide and withdraw liquidity within the target ranges besides the owner
+    pub operator: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes the range of the pair LP supply the contract should own.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TargetRange {
+    /// the share of the LP supply withdrawals cannot go below
+    pub min_share: Decimal,
+    /// the share of the LP supply provisions cannot go above
+    pub max_share: Decimal,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner and the operator
+    UpdateConfig {
+        owner: Option<Addr>,
+        operator: Option<Addr>,
+    },
+    /// SetPosition designates a pair the contract provides liquidity to and sets its target range
+    SetPosition {
+        pair: Addr,
+        target_range: TargetRange,
+    },
+    /// RemovePosition removes a designated pair the contract holds no LP tokens of
+    RemovePosition { pair: Addr },
+    /// ProvideLiquidity provides the assets held to a designated pair, as long as the LP supply
+    /// share owned stays within the target range
+    ProvideLiquidity {
+        pair: Addr,
+        assets: [Asset; 2],
+        slippage_tolerance: Option<Decimal>,
+    },
+    /// WithdrawLiquidity withdraws LP tokens from a designated pair, as long as the LP supply
+    /// share owned stays within the target range
+    WithdrawLiquidity { pair: Addr, amount: Uint128 },
+    /// Transfer sends an asset held by the contract to the recipient
+    Transfer { asset: Asset, recipient: Addr },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Position returns the liquidity owned in a designated pair and its PnL
+    Position { pair: Addr },
+    /// Positions returns the designated pairs according to the specified parameters in
+    /// `start_after` and `limit` variables
+    Positions {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Position => PositionResponse,
+        Positions => PositionsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub operator: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the position query response. The values are
+/// in the second asset of the pair at the current pool price.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    pub pair: Addr,
+    pub asset_infos: [AssetInfo; 2],
+    pub lp_token: Addr,
+    pub target_range: TargetRange,
+    /// the LP tokens held
+    pub lp_amount: Uint128,
+    /// the share of the LP supply held
+    pub share: Decimal,
+    /// the pool assets the LP tokens held are worth
+    pub underlying: [Asset; 2],
+    /// the assets provided so far
+    pub provided: [Asset; 2],
+    /// the assets withdrawn so far
+    pub withdrawn: [Asset; 2],
+    /// the value of the underlying and the withdrawn assets
+    pub value: Uint128,
+    /// the value of the provided assets, had they been held instead
+    pub cost: Uint128,
+    /// the difference between the value and the cost
+    pub pnl: Uint128,
+    /// whether the value is below the cost
+    pub is_loss: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the positions query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionsResponse {
+    pub positions: Vec<PositionResponse>,
+}
@@ -0,0 +1,141 @@
+//! Conversions of the prices between assets. The prices are computed on the atomics of the
+//! decimals in 256 bits, so compositions do not lose precision at every step nor overflow in
+//! the intermediate products.
+
+use cosmwasm_std::{Decimal, StdError, StdResult, Uint128};
+
+use crate::math::{decimal_atomics, DECIMAL_FRACTIONAL, U256};
+
+/// Returns the decimal of the atomics, an error if they do not fit in a decimal
+fn from_atomics(atomics: U256) -> StdResult<Decimal> {
+    if atomics > U256::from(u128::MAX) {
+        return Err(StdError::generic_err("price overflow"));
+    }
+
+    // the whole part of the ratio would overflow `from_ratio`, above 340 or so
+    let atomics = atomics.as_u128();
+    Ok(Decimal::from_ratio(atomics / DECIMAL_FRACTIONAL, 1u128)
+        + Decimal::from_ratio(atomics % DECIMAL_FRACTIONAL, DECIMAL_FRACTIONAL))
+}
+
+/// Returns `10^decimals` in 256 bits, an error if they do not fit
+fn decimals_unit(decimals: u8) -> StdResult<U256> {
+    U256::from(10u8)
+        .checked_pow(U256::from(decimals))
+        .ok_or_else(|| StdError::generic_err("decimals overflow"))
+}
+
+/// Returns the atomics of the price scaled by `10^numerator_decimals / 10^denominator_decimals`
+fn scale_price(
+    price: Decimal,
+    numerator_decimals: u8,
+    denominator_decimals: u8,
+) -> StdResult<Decimal> {
+    let scaled = U256::from(decimal_atomics(price))
+        .checked_mul(decimals_unit(numerator_decimals)?)
+        .ok_or_else(|| StdError::generic_err("price overflow"))?;
+    from_atomics(scaled / decimals_unit(denominator_decimals)?)
+}
+
+/// ## Description
+/// Returns the price of the quote asset in the base asset from the price of the base asset in
+/// the quote asset.
+/// ## Params
+/// * **price** is the price of the base asset in the quote asset.
+pub fn invert_price(price: Decimal) -> StdResult<Decimal> {
+    if price.is_zero() {
+        return Err(StdError::generic_err("cannot invert a zero price"));
+    }
+
+    let fractional = U256::from(DECIMAL_FRACTIONAL);
+    from_atomics(fractional * fractional / U256::from(decimal_atomics(price)))
+}
+
+/// ## Description
+/// Returns the product of two prices, the price of `a` in `c` from the price of `a` in `b` and
+/// the price of `b` in `c`.
+/// ## Params
+/// * **a_in_b** is the price of the first asset in the intermediate asset.
+///
+/// * **b_in_c** is the price of the intermediate asset in the last asset.
+pub fn multiply_prices(a_in_b: Decimal, b_in_c: Decimal) -> StdResult<Decimal> {
+    from_atomics(
+        U256::from(decimal_atomics(a_in_b)) * U256::from(decimal_atomics(b_in_c))
+            / U256::from(DECIMAL_FRACTIONAL),
+    )
+}
+
+/// ## Description
+/// Returns the price along a route, the product of the prices of its hops, each hop being
+/// priced in the asset of the next one.
+/// ## Params
+/// * **prices** are the prices of the hops of the route, in order.
+pub fn compose_prices(prices: &[Decimal]) -> StdResult<Decimal> {
+    prices
+        .iter()
+        .try_fold(Decimal::one(), |price, hop| multiply_prices(price, *hop))
+}
+
+/// ## Description
+/// Returns the cross rate of two assets priced in a common asset, the price of the base asset
+/// in the quote asset.
+/// ## Params
+/// * **base_price** is the price of the base asset in the common asset.
+///
+/// * **quote_price** is the price of the quote asset in the common asset.
+pub fn cross_rate(base_price: Decimal, quote_price: Decimal) -> StdResult<Decimal> {
+    if quote_price.is_zero() {
+        return Err(StdError::generic_err("cannot divide by a zero price"));
+    }
+
+    from_atomics(
+        U256::from(decimal_atomics(base_price)) * U256::from(DECIMAL_FRACTIONAL)
+            / U256::from(decimal_atomics(quote_price)),
+    )
+}
+
+/// ## Description
+/// Returns the amount of the base asset worth `quote_amount` of the quote asset at the price,
+/// rounded down.
+/// ## Params
+/// * **quote_amount** is the amount of the quote asset.
+///
+/// * **price** is the price of the base asset in the quote asset.
+pub fn divide_by_price(quote_amount: Uint128, price: Decimal) -> StdResult<Uint128> {
+    if price.is_zero() {
+        return Err(StdError::generic_err("cannot divide by a zero price"));
+    }
+
+    Ok(quote_amount.multiply_ratio(DECIMAL_FRACTIONAL, decimal_atomics(price)))
+}
+
+/// ## Description
+/// Returns the price of a whole base token in whole quote tokens, what frontends display, from
+/// the price of the base asset atomics in the quote asset atomics, what the pairs answer.
+/// ## Params
+/// * **price** is the price of an atomic unit of the base asset in atomic units of the quote
+///   asset.
+///
+/// * **base_decimals** are the decimals of the base asset.
+///
+/// * **quote_decimals** are the decimals of the quote asset.
+pub fn to_token_price(price: Decimal, base_decimals: u8, quote_decimals: u8) -> StdResult<Decimal> {
+    scale_price(price, base_decimals, quote_decimals)
+}
+
+/// ## Description
+/// Returns the price of the base asset atomics in the quote asset atomics from the price of a
+/// whole base token in whole quote tokens, the conversion back of [`to_token_price`].
+/// ## Params
+/// * **token_price** is the price of a whole base token in whole quote tokens.
+///
+/// * **base_decimals** are the decimals of the base asset.
+///
+/// * **quote_decimals** are the decimals of the quote asset.
+pub fn to_atomic_price(
+    token_price: Decimal,
+    base_decimals: u8,
+    quote_decimals: u8,
+) -> StdResult<Decimal> {
+    scale_price(token_price, quote_decimals, base_decimals)
+}
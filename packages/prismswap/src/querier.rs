@@ -1,18 +1,78 @@
-use crate::asset::PairInfo;
+use crate::asset::{sort_asset_infos, PairInfo};
 use crate::factory::{
     ConfigResponse as FactoryConfigResponse, FeeInfoResponse, PairsResponse,
     QueryMsg as FactoryQueryMsg,
 };
-use crate::pair::{QueryMsg as PairQueryMsg, ReverseSimulationResponse, SimulationResponse};
+use crate::pair::{
+    CumulativePricesResponse, PoolResponse, QueryMsg as PairQueryMsg, ReverseSimulationResponse,
+    SimulationResponse, TwapResponse,
+};
+use crate::params::{ParamResponse, ParamValue, QueryMsg as ParamsQueryMsg};
+use crate::strategy::{StrategyInfoResponse, StrategyQueryMsg, TotalAssetsResponse};
 
 use cosmwasm_std::{
     to_binary, Addr, AllBalanceResponse, BalanceResponse, BankQuery, Coin, QuerierWrapper,
     QueryRequest, StdResult, Uint128, WasmQuery,
 };
 
+use schemars::schema::RootSchema;
+use schemars::{schema_for, JsonSchema};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
 use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 use cw_asset::{Asset, AssetInfo};
 
+/// ## Description
+/// This trait binds the variants of a query message to the types of their responses, the
+/// [`smart_query`] helper checking the requested type against it.
+pub trait QueryResponses {
+    /// Returns the schemas of the responses, keyed by the snake case name of the variants
+    fn response_schemas() -> BTreeMap<String, RootSchema>;
+
+    /// Returns the schema of the response to the query
+    fn response_schema(&self) -> RootSchema;
+}
+
+/// Returns the snake case name of a variant, the name it is serialized under
+pub(crate) fn variant_key(variant: &str) -> String {
+    let mut key = String::new();
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            key.push('_');
+        }
+        key.push(c.to_ascii_lowercase());
+    }
+    key
+}
+
+/// ## Description
+/// Returns the response of a smart query to a PrismSwap contract. Debug builds, the tests among
+/// them, panic when the requested type is not the response type bound to the query.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **contract_addr** is the object of type [`Addr`].
+///
+/// * **msg** is the query message, of a type implementing [`QueryResponses`].
+pub fn smart_query<M, T>(querier: &QuerierWrapper, contract_addr: &Addr, msg: &M) -> StdResult<T>
+where
+    M: QueryResponses + Serialize,
+    T: DeserializeOwned + JsonSchema,
+{
+    debug_assert!(
+        schema_for!(T) == msg.response_schema(),
+        "{} is not the response type of the query",
+        std::any::type_name::<T>()
+    );
+
+    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(msg)?,
+    }))
+}
+
 /// ## Description
 /// Returns the balance of the denom at the specified account address.
 /// ## Params
@@ -53,7 +113,7 @@ pub fn query_all_balances(querier: &QuerierWrapper, account_addr: &Addr) -> StdR
 /// * **querier** is the object of type [`QuerierWrapper`].
 ///
 /// * **contract_addr** is the object of type [`Addr`]. Sets the address of the contract for which
-/// the balance will be requested
+///   the balance will be requested
 ///
 /// * **account_addr** is the object of type [`Addr`].
 pub fn query_token_balance(
@@ -116,10 +176,7 @@ pub fn query_factory_config(
     querier: &QuerierWrapper,
     factory_contract: &Addr,
 ) -> StdResult<FactoryConfigResponse> {
-    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::Config {})?,
-    }))
+    smart_query(querier, factory_contract, &FactoryQueryMsg::Config {})
 }
 
 /// ## Description
@@ -135,16 +192,42 @@ pub fn query_fee_info(
     factory_contract: &Addr,
     asset_infos: &[AssetInfo; 2],
 ) -> StdResult<FeeInfoResponse> {
-    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::FeeInfo {
+    smart_query(
+        querier,
+        factory_contract,
+        &FactoryQueryMsg::FeeInfo {
             asset_infos: asset_infos.clone(),
-        })?,
-    }))
+        },
+    )
 }
 
 /// ## Description
-/// Returns the pair information at the specified assets of type [`AssetInfo`].
+/// Returns the value of a parameter of the registry, none if not registered.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **param_registry** is the object of type [`Addr`].
+///
+/// * **key** is the object of type [`&str`].
+pub fn query_param(
+    querier: &QuerierWrapper,
+    param_registry: &Addr,
+    key: &str,
+) -> StdResult<Option<ParamValue>> {
+    let res: ParamResponse = smart_query(
+        querier,
+        param_registry,
+        &ParamsQueryMsg::Param {
+            key: key.to_string(),
+        },
+    )?;
+
+    Ok(res.value)
+}
+
+/// ## Description
+/// Returns the pair information at the specified assets of type [`AssetInfo`]. The asset infos
+/// are sent in the canonical order of the factory, so they can be given in any order.
 /// ## Params
 /// * **querier** is the object of type [`QuerierWrapper`].
 ///
@@ -156,12 +239,26 @@ pub fn query_pair_info(
     factory_contract: &Addr,
     asset_infos: &[AssetInfo; 2],
 ) -> StdResult<PairInfo> {
-    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::Pair {
-            asset_infos: asset_infos.clone(),
-        })?,
-    }))
+    smart_query(
+        querier,
+        factory_contract,
+        &FactoryQueryMsg::Pair {
+            asset_infos: sort_asset_infos(asset_infos),
+        },
+    )
+}
+
+/// ## Description
+/// Returns the pair information stored in the specified pair contract.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **pair_contract** is the object of type [`Addr`].
+pub fn query_pair_info_from_pair(
+    querier: &QuerierWrapper,
+    pair_contract: &Addr,
+) -> StdResult<PairInfo> {
+    smart_query(querier, pair_contract, &PairQueryMsg::Pair {})
 }
 
 /// ## Description
@@ -180,10 +277,11 @@ pub fn query_pairs_info(
     start_after: Option<[AssetInfo; 2]>,
     limit: Option<u32>,
 ) -> StdResult<PairsResponse> {
-    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: factory_contract.to_string(),
-        msg: to_binary(&FactoryQueryMsg::Pairs { start_after, limit })?,
-    }))
+    smart_query(
+        querier,
+        factory_contract,
+        &FactoryQueryMsg::Pairs { start_after, limit },
+    )
 }
 
 /// ## Description
@@ -199,12 +297,13 @@ pub fn simulate(
     pair_contract: &Addr,
     offer_asset: &Asset,
 ) -> StdResult<SimulationResponse> {
-    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: pair_contract.to_string(),
-        msg: to_binary(&PairQueryMsg::Simulation {
+    smart_query(
+        querier,
+        pair_contract,
+        &PairQueryMsg::Simulation {
             offer_asset: offer_asset.clone(),
-        })?,
-    }))
+        },
+    )
 }
 
 /// ## Description
@@ -220,10 +319,165 @@ pub fn reverse_simulate(
     pair_contract: &Addr,
     ask_asset: &Asset,
 ) -> StdResult<ReverseSimulationResponse> {
-    querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-        contract_addr: pair_contract.to_string(),
-        msg: to_binary(&PairQueryMsg::ReverseSimulation {
+    smart_query(
+        querier,
+        pair_contract,
+        &PairQueryMsg::ReverseSimulation {
             ask_asset: ask_asset.clone(),
-        })?,
-    }))
+        },
+    )
+}
+
+/// ## Description
+/// Returns the vault and the LP token of the specified strategy contract.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **strategy_contract** is the object of type [`Addr`].
+pub fn query_strategy_info(
+    querier: &QuerierWrapper,
+    strategy_contract: &Addr,
+) -> StdResult<StrategyInfoResponse> {
+    smart_query(
+        querier,
+        strategy_contract,
+        &StrategyQueryMsg::StrategyInfo {},
+    )
+}
+
+/// ## Description
+/// Returns the LP tokens the specified strategy contract manages.
+/// ## Params
+/// * **querier** is the object of type [`QuerierWrapper`].
+///
+/// * **strategy_contract** is the object of type [`Addr`].
+pub fn query_strategy_total_assets(
+    querier: &QuerierWrapper,
+    strategy_contract: &Addr,
+) -> StdResult<Uint128> {
+    let res: TotalAssetsResponse = smart_query(
+        querier,
+        strategy_contract,
+        &StrategyQueryMsg::TotalAssets {},
+    )?;
+
+    Ok(res.amount)
+}
+
+/// ## Description
+/// This structure wraps a [`QuerierWrapper`] to query the PrismSwap contracts and the balances
+/// in typed responses.
+pub struct PrismSwapQuerier<'a> {
+    querier: &'a QuerierWrapper<'a>,
+}
+
+impl<'a> PrismSwapQuerier<'a> {
+    pub fn new(querier: &'a QuerierWrapper<'a>) -> Self {
+        PrismSwapQuerier { querier }
+    }
+
+    /// Returns the balance of an asset held by the account, native or cw20
+    pub fn balance(&self, asset_info: &AssetInfo, account_addr: &Addr) -> StdResult<Uint128> {
+        match asset_info {
+            AssetInfo::Native(denom) => query_balance(self.querier, account_addr, denom.clone()),
+            AssetInfo::Cw20(contract_addr) => {
+                query_token_balance(self.querier, contract_addr, account_addr)
+            }
+        }
+    }
+
+    pub fn all_balances(&self, account_addr: &Addr) -> StdResult<Vec<Coin>> {
+        query_all_balances(self.querier, account_addr)
+    }
+
+    pub fn token_symbol(&self, contract_addr: &Addr) -> StdResult<String> {
+        query_token_symbol(self.querier, contract_addr)
+    }
+
+    pub fn supply(&self, contract_addr: &Addr) -> StdResult<Uint128> {
+        query_supply(self.querier, contract_addr)
+    }
+
+    pub fn factory_config(&self, factory_contract: &Addr) -> StdResult<FactoryConfigResponse> {
+        query_factory_config(self.querier, factory_contract)
+    }
+
+    pub fn fee_info(
+        &self,
+        factory_contract: &Addr,
+        asset_infos: &[AssetInfo; 2],
+    ) -> StdResult<FeeInfoResponse> {
+        query_fee_info(self.querier, factory_contract, asset_infos)
+    }
+
+    pub fn param(&self, param_registry: &Addr, key: &str) -> StdResult<Option<ParamValue>> {
+        query_param(self.querier, param_registry, key)
+    }
+
+    /// Returns the pair of the asset infos registered in the factory
+    pub fn pair_info(
+        &self,
+        factory_contract: &Addr,
+        asset_infos: &[AssetInfo; 2],
+    ) -> StdResult<PairInfo> {
+        query_pair_info(self.querier, factory_contract, asset_infos)
+    }
+
+    /// Returns the pair information stored in the pair contract
+    pub fn pair_info_from_pair(&self, pair_contract: &Addr) -> StdResult<PairInfo> {
+        query_pair_info_from_pair(self.querier, pair_contract)
+    }
+
+    pub fn pairs(
+        &self,
+        factory_contract: &Addr,
+        start_after: Option<[AssetInfo; 2]>,
+        limit: Option<u32>,
+    ) -> StdResult<PairsResponse> {
+        query_pairs_info(self.querier, factory_contract, start_after, limit)
+    }
+
+    pub fn pool(&self, pair_contract: &Addr) -> StdResult<PoolResponse> {
+        self.query_pair(pair_contract, &PairQueryMsg::Pool {})
+    }
+
+    pub fn simulate(
+        &self,
+        pair_contract: &Addr,
+        offer_asset: &Asset,
+    ) -> StdResult<SimulationResponse> {
+        simulate(self.querier, pair_contract, offer_asset)
+    }
+
+    pub fn reverse_simulate(
+        &self,
+        pair_contract: &Addr,
+        ask_asset: &Asset,
+    ) -> StdResult<ReverseSimulationResponse> {
+        reverse_simulate(self.querier, pair_contract, ask_asset)
+    }
+
+    pub fn cumulative_prices(&self, pair_contract: &Addr) -> StdResult<CumulativePricesResponse> {
+        self.query_pair(pair_contract, &PairQueryMsg::CumulativePrices {})
+    }
+
+    pub fn twap(&self, pair_contract: &Addr, window: u64) -> StdResult<TwapResponse> {
+        self.query_pair(pair_contract, &PairQueryMsg::Twap { window })
+    }
+
+    pub fn strategy_info(&self, strategy_contract: &Addr) -> StdResult<StrategyInfoResponse> {
+        query_strategy_info(self.querier, strategy_contract)
+    }
+
+    pub fn strategy_total_assets(&self, strategy_contract: &Addr) -> StdResult<Uint128> {
+        query_strategy_total_assets(self.querier, strategy_contract)
+    }
+
+    fn query_pair<T: DeserializeOwned + JsonSchema>(
+        &self,
+        pair_contract: &Addr,
+        msg: &PairQueryMsg,
+    ) -> StdResult<T> {
+        smart_query(self.querier, pair_contract, msg)
+    }
 }
@@ -0,0 +1,124 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use crate::signed_decimal::SignedDecimal;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance address allowed to update the configuration and the targets
+    pub owner: Addr,
+    /// the router the rebalancing swaps go through
+    pub router: Addr,
+    /// the oracle the holdings are valued with
+    pub oracle: Addr,
+    /// the asset the holdings are valued in, which swaps between other assets go through
+    pub base_asset: AssetInfo,
+    /// the weight drift of a holding above which it is rebalanced
+    pub drift_threshold: Decimal,
+    /// the share the swap output can fall below its value at the oracle TWAPs
+    pub max_slippage: Decimal,
+    /// the seconds the oracle prices are averaged over
+    pub twap_window: u64,
+    /// the minimum seconds between two rebalances
+    pub cooldown: u64,
+    /// the reward paid out of the holdings to the sender of a rebalance
+    pub keeper_reward: Option<Asset>,
+}
+
+/// ## Description
+/// This structure describes the weight a holding should have in the total value.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Target {
+    pub asset_info: AssetInfo,
+    pub weight: Decimal,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner and the rebalancing settings
+    UpdateConfig {
+        owner: Option<Addr>,
+        drift_threshold: Option<Decimal>,
+        max_slippage: Option<Decimal>,
+        twap_window: Option<u64>,
+        cooldown: Option<u64>,
+        keeper_reward: Option<Asset>,
+    },
+    /// SetTargets replaces the target weights, which must add up to 1
+    SetTargets { targets: Vec<Target> },
+    /// Rebalance swaps the most overweight holding into the most underweight one when their
+    /// drift exceeds the threshold, paying the keeper reward to the sender. Anyone can execute it
+    Rebalance {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Holdings returns the value and the weight of every target holding at the oracle TWAPs
+    Holdings {},
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Holdings => HoldingsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub router: Addr,
+    pub oracle: Addr,
+    pub base_asset: AssetInfo,
+    pub drift_threshold: Decimal,
+    pub max_slippage: Decimal,
+    pub twap_window: u64,
+    pub cooldown: u64,
+    pub keeper_reward: Option<Asset>,
+    pub targets: Vec<Target>,
+    pub last_rebalance_time: u64,
+}
+
+/// ## Description
+/// This structure describes a holding of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Holding {
+    pub asset_info: AssetInfo,
+    pub amount: Uint128,
+    /// the oracle TWAP of the asset in the base asset
+    pub price: Decimal,
+    /// the value of the amount in the base asset
+    pub value: Uint128,
+    /// the share of the total value
+    pub weight: Decimal,
+    pub target_weight: Decimal,
+    /// the weight less the target weight, negative when the holding is underweight
+    pub drift: SignedDecimal,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the holdings query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HoldingsResponse {
+    pub holdings: Vec<Holding>,
+    /// the value of the holdings in the base asset
+    pub total_value: Uint128,
+    /// the largest difference between the weight and the target weight of a holding
+    pub max_drift: Decimal,
+    /// whether a rebalance can be executed now
+    pub workable: bool,
+}
@@ -0,0 +1,121 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use crate::asset_list::AssetList;
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {}
+
+/// ## Description
+/// This structure describes a quote a market maker signs off-chain.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Quote {
+    /// the market maker settling the quote out of its inventory
+    pub maker: Addr,
+    /// the only address allowed to fill the quote, anyone if not set
+    pub taker: Option<Addr>,
+    /// the asset the taker offers
+    pub offer_asset_info: AssetInfo,
+    /// the asset the maker pays out
+    pub ask_asset_info: AssetInfo,
+    /// the ask asset paid per offer asset
+    pub price: Decimal,
+    /// the maximum offer amount the quote can be filled with
+    pub size: Uint128,
+    /// the block time in seconds from which the quote can no longer be filled
+    pub expiry: u64,
+    /// the number of the quote, each of which can be filled once
+    pub nonce: u64,
+}
+
+/// ## Description
+/// This structure describes what a market maker signs, the digest being its SHA-256 hash as
+/// returned by the [`QueryMsg::QuoteDigest`] query.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuotePayload {
+    /// the RFQ contract, so that the quote cannot be replayed on another one
+    pub contract: Addr,
+    pub quote: Quote,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving cw20 tokens
+    Receive(Cw20ReceiveMsg),
+    /// RegisterMaker sets the secp256k1 public key the quotes of the sender are signed with
+    RegisterMaker { public_key: Binary },
+    /// Deposit adds the sent native assets to the inventory of the sender
+    Deposit {},
+    /// Withdraw sends an asset of the inventory of the sender back to it
+    Withdraw { asset: Asset },
+    /// WithdrawMany sends several assets of the inventory of the sender back to it
+    WithdrawMany { assets: AssetList },
+    /// Fill settles a quote with the sent native asset, signed by its maker over the quote digest
+    Fill { quote: Quote, signature: Binary },
+    /// CancelNonce prevents the quote of the sender with the nonce from being filled
+    CancelNonce { nonce: u64 },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the cw20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposit adds the sent tokens to the inventory of the sender
+    Deposit {},
+    /// Fill settles a quote with the sent tokens
+    Fill { quote: Quote, signature: Binary },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Maker returns the public key and the inventory of a market maker
+    Maker { maker: Addr },
+    /// QuoteDigest returns the digest a market maker signs for the quote
+    QuoteDigest { quote: Quote },
+    /// NonceUsed returns whether the quote of a market maker with the nonce was filled or
+    /// cancelled
+    NonceUsed { maker: Addr, nonce: u64 },
+}
+
+query_responses! {
+    QueryMsg {
+        Maker => MakerResponse,
+        QuoteDigest => QuoteDigestResponse,
+        NonceUsed => NonceUsedResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the maker query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MakerResponse {
+    pub maker: Addr,
+    pub public_key: Binary,
+    pub inventory: Vec<Asset>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the quote digest query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QuoteDigestResponse {
+    pub digest: Binary,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the nonce used query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NonceUsedResponse {
+    pub used: bool,
+}
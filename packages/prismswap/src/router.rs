@@ -1,8 +1,11 @@
-use cw_asset::AssetInfo;
+use cw_asset::{Asset, AssetInfo};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128};
+use crate::asset::PairInfo;
+use crate::fee::SwapBreakdown;
+pub use crate::tax::TaxInfo;
+use cosmwasm_std::{Addr, Decimal, StdError, StdResult, Uint128};
 use cw20::Cw20ReceiveMsg;
 
 pub const MAX_SWAP_OPERATIONS: usize = 50;
@@ -13,6 +16,109 @@ pub const MAX_SWAP_OPERATIONS: usize = 50;
 pub struct InstantiateMsg {
     /// the prismswap factory contract address
     pub factory: Addr,
+    /// address allowed to update configuration and sweep stranded balances
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes the fee the router takes on top of the swap output.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeConfig {
+    /// the share of the swap output sent to the fee collector
+    pub fee_rate: Decimal,
+    /// the recipient of the router fee
+    pub fee_collector: Addr,
+    /// the maximum referral fee an integrator can add on top of the router fee
+    pub max_referral_fee: Decimal,
+}
+
+/// ## Description
+/// This structure describes the relayers allowed to submit swaps on behalf of users.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RelayerConfig {
+    /// the share of the swap output paid to the relayer
+    pub fee_rate: Decimal,
+    /// the addresses allowed to relay swaps
+    pub relayers: Vec<Addr>,
+}
+
+/// ## Description
+/// This structure describes how far swap outputs may fall below the pair TWAPs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TwapConfig {
+    /// the minimum number of seconds the pair prices are averaged over
+    pub window: u64,
+    /// the maximum share the swap output can fall below the output at the TWAPs
+    pub max_deviation: Decimal,
+}
+
+/// ## Description
+/// This structure describes a swap a user authorizes the relayers to execute, the relayer
+/// choosing only when.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RelayOrder {
+    /// the cw20 asset to pull from the user allowance
+    pub offer_asset: Asset,
+    /// operations for swap
+    pub operations: Vec<SwapOperation>,
+    /// the minimum receive for swap, after the relayer fee
+    pub minimum_receive: Uint128,
+    /// the block time in seconds after which the order cannot be executed
+    pub deadline: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes the slippage protection of swaps that do not set their own.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SlippageConfig {
+    /// the maximum spread of every PrismSwap operation
+    pub max_spread: Decimal,
+    /// the share the output can fall below the simulated output without a minimum receive
+    pub minimum_receive_tolerance: Decimal,
+}
+
+/// ## Description
+/// This structure describes the pairs and the hops swaps cannot go through.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RouteBlacklist {
+    /// the pair contracts swaps cannot go through
+    pub pairs: Vec<Addr>,
+    /// the asset pairs swaps cannot go between, in either direction
+    pub hops: Vec<[AssetInfo; 2]>,
+}
+
+/// ## Description
+/// This structure describes a staking contract swap outputs can be bonded in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingContract {
+    /// the staking or vault contract
+    pub contract_addr: Addr,
+    /// the asset the contract bonds
+    pub asset_info: AssetInfo,
+    /// the token the contract issues for bonded assets, which unstake and swap redeems
+    pub share_token: Option<Addr>,
+}
+
+/// ## Description
+/// This enum describes the message the router bonds swap outputs with, sent as the cw20 hook
+/// or executed with the native funds.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StakingHookMsg {
+    /// Bond the sent asset on behalf of the staker
+    Bond { staker_addr: Option<Addr> },
+    /// Redeem the sent share tokens, sending the bonded asset back to the sender
+    Unbond {},
+}
+
+/// ## Description
+/// This structure describes the referral fee an integrator adds on top of a swap.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Referral {
+    /// the recipient of the referral fee
+    pub address: Addr,
+    /// the share of the swap output sent to the referrer
+    pub fee: Decimal,
 }
 
 /// ## Description
@@ -33,10 +139,27 @@ pub enum SwapOperation {
         offer_asset_info: AssetInfo,
         /// the asks asset info
         ask_asset_info: AssetInfo,
+        /// the pair swapping the assets, the factory pair of the assets if missing
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pair: Option<Addr>,
+        /// the maximum spread of the hop, the maximum spread of the swap if missing
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max_spread: Option<Decimal>,
     },
 }
 
 impl SwapOperation {
+    pub fn get_offer_asset_info(&self) -> AssetInfo {
+        match self {
+            SwapOperation::NativeSwap { offer_denom, .. } => {
+                AssetInfo::Native(offer_denom.to_string())
+            }
+            SwapOperation::PrismSwap {
+                offer_asset_info, ..
+            } => offer_asset_info.clone(),
+        }
+    }
+
     pub fn get_target_asset_info(&self) -> AssetInfo {
         match self {
             SwapOperation::NativeSwap { ask_denom, .. } => AssetInfo::Native(ask_denom.to_string()),
@@ -45,6 +168,113 @@ impl SwapOperation {
     }
 }
 
+/// ## Description
+/// Returns the operations swapping along an ordered asset path, a [`SwapOperation::NativeSwap`]
+/// for the hops between two native denoms and a [`SwapOperation::PrismSwap`] for the others.
+/// Returns an error if the path has less than two assets or more hops than the router runs, or
+/// if two adjacent assets are the same.
+/// ## Params
+/// * **path** is the assets the swap goes through, from the offer asset to the ask asset.
+pub fn path_to_operations(path: &[AssetInfo]) -> StdResult<Vec<SwapOperation>> {
+    if path.len() < 2 {
+        return Err(StdError::generic_err("path must have at least two assets"));
+    }
+    if path.len() - 1 > MAX_SWAP_OPERATIONS {
+        return Err(StdError::generic_err("too many swap operations"));
+    }
+
+    path.windows(2)
+        .map(|hop| match (&hop[0], &hop[1]) {
+            (offer_asset_info, ask_asset_info) if offer_asset_info == ask_asset_info => Err(
+                StdError::generic_err(format!("path swaps {} into itself", offer_asset_info)),
+            ),
+            (AssetInfo::Native(offer_denom), AssetInfo::Native(ask_denom)) => {
+                Ok(SwapOperation::NativeSwap {
+                    offer_denom: offer_denom.clone(),
+                    ask_denom: ask_denom.clone(),
+                })
+            }
+            (offer_asset_info, ask_asset_info) => Ok(SwapOperation::PrismSwap {
+                offer_asset_info: offer_asset_info.clone(),
+                ask_asset_info: ask_asset_info.clone(),
+                pair: None,
+                max_spread: None,
+            }),
+        })
+        .collect()
+}
+
+/// ## Description
+/// This enum describes how much of the router balance a swap operation offers.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapAmount {
+    /// the whole router balance of the offer asset
+    All,
+    /// exactly the given amount
+    Exact(Uint128),
+    /// the router balance received on top of the given previous balance
+    ReceivedSince(Uint128),
+}
+
+/// ## Description
+/// This structure describes a multi-hop swap, sent either with native funds or through
+/// the cw20 hook.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecuteSwapOperationsMsg {
+    /// operations for swap
+    pub operations: Vec<SwapOperation>,
+    /// the minimum receive for swap
+    pub minimum_receive: Option<Uint128>,
+    /// the maximum spread of every PrismSwap operation
+    pub max_spread: Option<Decimal>,
+    /// the recipient
+    pub to: Option<Addr>,
+    /// the block time in seconds after which the swap reverts
+    pub deadline: Option<u64>,
+    /// the referral fee added on top of the router fee
+    pub referral: Option<Referral>,
+    /// swap the whole router balance at every hop instead of only the amounts of this swap
+    pub swap_all: Option<bool>,
+    /// revert when the output falls below the output at the pair TWAPs by more than the
+    /// configured deviation
+    pub twap_check: Option<bool>,
+    /// the TWAP bound of this swap in place of the configured one, checking the output against
+    /// it even without `twap_check`
+    pub twap_config: Option<TwapConfig>,
+    /// bond the output in this registered staking contract on behalf of the recipient
+    pub staking_contract: Option<Addr>,
+}
+
+/// ## Description
+/// This structure describes one of the routes a split swap sends part of the offer through.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapRoute {
+    /// the part of the offer amount sent through the route
+    pub offer_amount: Uint128,
+    /// operations for swap
+    pub operations: Vec<SwapOperation>,
+}
+
+/// ## Description
+/// This structure describes a swap split across several routes ending in the same asset, sent
+/// either with native funds or through the cw20 hook.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecuteSplitSwapOperationsMsg {
+    /// the routes, offering amounts that add up to the sent amount
+    pub routes: Vec<SwapRoute>,
+    /// the minimum receive of all the routes together
+    pub minimum_receive: Option<Uint128>,
+    /// the maximum spread of every PrismSwap operation
+    pub max_spread: Option<Decimal>,
+    /// the recipient
+    pub to: Option<Addr>,
+    /// the block time in seconds after which the swap reverts
+    pub deadline: Option<u64>,
+    /// the referral fee added on top of the router fee
+    pub referral: Option<Referral>,
+}
+
 /// ## Description
 /// This structure describes the execute messages of the contract.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -53,18 +283,102 @@ pub enum ExecuteMsg {
     /// Receives a message of type [`Cw20ReceiveMsg`] and processes it depending on the received
     /// template.
     Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the router configuration
+    UpdateConfig {
+        /// address allowed to update configuration and sweep stranded balances
+        owner: Option<Addr>,
+        /// address allowed to pause the router besides the owner
+        guardian: Option<Addr>,
+    },
+    /// Pause makes every swap revert until the owner or the guardian unpauses the router
+    Pause {},
+    /// Unpause resumes swaps after a pause
+    Unpause {},
+    /// Sweep sends the whole router balance of the given assets to the recipient
+    Sweep {
+        /// the assets to recover
+        assets: Vec<AssetInfo>,
+        /// the recipient of the swept balances
+        to: Addr,
+    },
+    /// UpdateFeeConfig sets the router fee, `None` disables it
+    UpdateFeeConfig { fee_config: Option<FeeConfig> },
+    /// UpdateAskAssetWhitelist restricts the assets swaps can end in, `None` allows any asset
+    UpdateAskAssetWhitelist {
+        ask_asset_infos: Option<Vec<AssetInfo>>,
+    },
+    /// UpdateFeeExemptions replaces the addresses whose swaps pay no router fee
+    UpdateFeeExemptions { addresses: Vec<Addr> },
+    /// UpdateRelayerConfig sets the allowed relayers and their fee, `None` disables relayed swaps
+    UpdateRelayerConfig {
+        relayer_config: Option<RelayerConfig>,
+    },
+    /// UpdateSlippageConfig sets the slippage protection of swaps that omit it, `None` disables it
+    UpdateSlippageConfig {
+        slippage_config: Option<SlippageConfig>,
+    },
+    /// UpdateRouteBlacklist sets the pairs and the hops swaps cannot go through, `None` allows all
+    UpdateRouteBlacklist {
+        route_blacklist: Option<RouteBlacklist>,
+    },
+    /// UpdateStakingContracts replaces the staking contracts swap outputs can be bonded in
+    UpdateStakingContracts {
+        staking_contracts: Vec<StakingContract>,
+    },
+    /// UpdateTwapConfig sets the bound of the opt-in TWAP check, `None` disables it
+    UpdateTwapConfig { twap_config: Option<TwapConfig> },
+    /// UpdatePointsTracker sets the contract completed swaps are reported to, `None` stops
+    /// reporting them
+    UpdatePointsTracker { points_tracker: Option<Addr> },
+    /// UpdateParamRegistry sets the parameter registry whose pause flag halts the swaps besides
+    /// the router pause, `None` stops reading it
+    UpdateParamRegistry { param_registry: Option<Addr> },
+    /// InvalidatePairCache drops the cached pairs of the given asset pairs, `None` drops all of them
+    InvalidatePairCache {
+        asset_infos: Option<Vec<[AssetInfo; 2]>>,
+    },
     /// Execute multiple BuyOperation
-    ExecuteSwapOperations {
-        operations: Vec<SwapOperation>,
-        minimum_receive: Option<Uint128>,
+    ExecuteSwapOperations(ExecuteSwapOperationsMsg),
+    /// Execute several routes for the same assets, checking only the total output
+    ExecuteSplitSwapOperations(ExecuteSplitSwapOperationsMsg),
+
+    /// SubmitRelayOrder authorizes the relayers to execute the swap on behalf of the sender,
+    /// replacing the previous order of the sender
+    SubmitRelayOrder { order: RelayOrder },
+    /// CancelRelayOrder removes the relay order of the sender
+    CancelRelayOrder {},
+    /// Execute the relay order of a user, pulling the cw20 offer asset from the user allowance
+    /// and paying the relayer fee from the output. The order is executed once
+    ExecuteRelayedSwapOperations {
+        /// the owner of the order and the allowance, and the recipient of the output
+        user: Addr,
+    },
+
+    /// Swap the optimal portion of the sent native asset through the pair and provide liquidity
+    /// with both sides, sending the minted LP tokens to the recipient
+    ProvideLiquidityFromSingleAsset {
+        /// the pair contract to provide liquidity to
+        pair: Addr,
+        /// the minimum amount of LP tokens to receive
+        min_lp: Option<Uint128>,
+        /// the maximum spread of the swap of the offer asset portion
+        max_spread: Option<Decimal>,
+        /// the recipient of the LP tokens
         to: Option<Addr>,
+        /// bond the LP tokens in this registered staking contract on behalf of the recipient
+        staking_contract: Option<Addr>,
     },
 
     /// Internal use
-    /// Swap all offer tokens to ask token
+    /// Swap offer tokens to ask token, the whole router balance unless an amount is given
     ExecuteSwapOperation {
         operation: SwapOperation,
         to: Option<Addr>,
+        amount: Option<SwapAmount>,
+        /// the tax rate and caps queried when the swap started, queried by the hop if missing
+        tax_info: Option<TaxInfo>,
+        /// the maximum spread of a PrismSwap operation
+        max_spread: Option<Decimal>,
     },
     /// Internal use
     /// Check the swap amount is exceed minimum_receive
@@ -74,19 +388,108 @@ pub enum ExecuteMsg {
         minimum_receive: Uint128,
         receiver: Addr,
     },
+    /// Internal use
+    /// Provide the remaining offer asset along with the swapped ask asset to the pair
+    ProvideSwappedLiquidity {
+        /// the pair contract to provide liquidity to
+        pair: Addr,
+        /// the part of the offer asset that was not swapped
+        offer_asset: Asset,
+        /// the asset received from the swap
+        ask_asset_info: AssetInfo,
+        /// the router balance of the ask asset before the swap
+        prev_ask_balance: Uint128,
+        /// the recipient of the LP tokens
+        receiver: Addr,
+    },
+    /// Internal use
+    /// Transfer the router balance of an asset received since `prev_balance`
+    TransferBalance {
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        receiver: Addr,
+    },
+    /// Internal use
+    /// Return to the receiver what the router holds above the previous balances,
+    /// less the tax of the transfer
+    RefundDust {
+        prev_balances: Vec<Asset>,
+        receiver: Addr,
+    },
+    /// Internal use
+    /// Take the router and referral fees from the swap output and send the rest to the receiver
+    DistributeSwapOutput {
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        receiver: Addr,
+        referral: Option<Referral>,
+        relayer: Option<Addr>,
+        /// skip the router fee, set for swaps sent by a fee exempt address
+        fee_exempt: bool,
+        /// bond the output in this staking contract on behalf of the receiver
+        staking_contract: Option<Addr>,
+        /// the minimum bonded amount, as bonded outputs never reach the receiver balance
+        minimum_receive: Option<Uint128>,
+    },
+    /// Internal use
+    /// Emit the realized amounts of every hop once the last operation has executed
+    EmitSwapReceipt {},
+    /// Internal use
+    /// Execute the relayed swap once the offer asset has been pulled from the user
+    RelaySwapOperations {
+        user: Addr,
+        offer_asset: Asset,
+        operations: Vec<SwapOperation>,
+        minimum_receive: Uint128,
+        relayer: Addr,
+    },
+    /// Internal use
+    /// Swap the balance received since `prev_balance` on behalf of the user
+    SwapReceivedBalance {
+        user: Addr,
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+        msg: ExecuteSwapOperationsMsg,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    ExecuteSwapOperations {
-        /// operations for swap
-        operations: Vec<SwapOperation>,
-        /// the minimum receive for swap
+    /// Execute multiple BuyOperation with the sent tokens
+    ExecuteSwapOperations(ExecuteSwapOperationsMsg),
+    /// Execute several routes for the same assets with the sent tokens
+    ExecuteSplitSwapOperations(ExecuteSplitSwapOperationsMsg),
+    ProvideLiquidityFromSingleAsset {
+        /// the pair contract to provide liquidity to
+        pair: Addr,
+        /// the minimum amount of LP tokens to receive
+        min_lp: Option<Uint128>,
+        /// the maximum spread of the swap of the offer asset portion
+        max_spread: Option<Decimal>,
+        /// the recipient of the LP tokens
+        to: Option<Addr>,
+        /// bond the LP tokens in this registered staking contract on behalf of the recipient
+        staking_contract: Option<Addr>,
+    },
+    /// Withdraw the sent LP tokens and swap the withdrawn assets into one of the pair assets
+    WithdrawLiquidityToSingleAsset {
+        /// the pair contract of the sent LP tokens
+        pair: Addr,
+        /// the pair asset to receive
+        ask_asset_info: AssetInfo,
+        /// the minimum receive for the withdrawal
         minimum_receive: Option<Uint128>,
         /// the recipient
         to: Option<Addr>,
     },
+    /// Redeem the sent share tokens in their staking contract and swap the unbonded asset
+    UnstakeAndSwap {
+        /// the registered staking contract issuing the sent share tokens
+        staking_contract: Addr,
+        /// the swap of the unbonded asset, its offer amount being the amount unbonded
+        swap: ExecuteSwapOperationsMsg,
+    },
 }
 
 /// ## Description
@@ -96,33 +499,167 @@ pub enum Cw20HookMsg {
 pub enum QueryMsg {
     /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
     Config {},
+    /// CachedPair returns the pair info the router cached for the asset pair, if any
+    CachedPair { asset_infos: [AssetInfo; 2] },
     /// Simulates multi-hop swap operations
     SimulateSwapOperations {
         /// the offer amount
         offer_amount: Uint128,
         /// operations for swap
         operations: Vec<SwapOperation>,
+        /// the referral fee added on top of the router fee
+        referral: Option<Referral>,
+        /// whether the swap is submitted by a relayer
+        relayed: Option<bool>,
+    },
+    /// Finds the PrismSwap route needing the lowest offer amount to receive the ask asset
+    /// after fees, trying the direct pair and every route through one intermediate asset
+    EstimateRequiredOffer {
+        /// the asset to offer
+        offer_asset_info: AssetInfo,
+        /// the asset and the amount to receive
+        ask_asset: Asset,
+        /// the assets routes may go through
+        intermediate_assets: Option<Vec<AssetInfo>>,
+        /// the referral fee added on top of the router fee
+        referral: Option<Referral>,
+        /// whether the swap is submitted by a relayer
+        relayed: Option<bool>,
+    },
+    /// RelayOrder returns the swap the user authorized the relayers to execute, if any
+    RelayOrder { user: Addr },
+    /// AssetVolumes returns the cumulative volume routed in and out of each asset
+    AssetVolumes {
+        /// the asset to start reading after
+        start_after: Option<AssetInfo>,
+        /// the number of items to be read
+        limit: Option<u32>,
     },
 }
 
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        CachedPair => Option<PairInfo>,
+        SimulateSwapOperations => SimulateSwapOperationsResponse,
+        EstimateRequiredOffer => EstimateRequiredOfferResponse,
+        RelayOrder => Option<RelayOrder>,
+        AssetVolumes => AssetVolumesResponse,
+    }
+}
+
 /// ## Description
 /// This structure describes the custom struct for each query response.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     /// the prismswap factory contract address
     pub factory: Addr,
+    /// address allowed to update configuration and sweep stranded balances
+    pub owner: Addr,
+    /// address allowed to pause the router besides the owner
+    pub guardian: Option<Addr>,
+    /// whether swaps are halted
+    pub paused: bool,
+    /// the fee taken on top of the swap output
+    pub fee_config: Option<FeeConfig>,
+    /// the addresses whose swaps pay no router fee
+    pub fee_exemptions: Vec<Addr>,
+    /// the only assets swaps can end in, if restricted
+    pub ask_asset_whitelist: Option<Vec<AssetInfo>>,
+    /// the relayers allowed to submit swaps on behalf of users
+    pub relayer_config: Option<RelayerConfig>,
+    /// the bound of the opt-in TWAP check
+    pub twap_config: Option<TwapConfig>,
+    /// the slippage protection of swaps that omit it
+    pub slippage_config: Option<SlippageConfig>,
+    /// the pairs and the hops swaps cannot go through
+    pub route_blacklist: Option<RouteBlacklist>,
+    /// the staking contracts swap outputs can be bonded in
+    pub staking_contracts: Vec<StakingContract>,
+    /// the contract completed swaps are reported to
+    pub points_tracker: Option<Addr>,
+    /// the parameter registry whose pause flag halts the swaps
+    pub param_registry: Option<Addr>,
 }
 
 /// ## Description
 /// This structure describes the custom struct for each query response.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct SimulateSwapOperationsResponse {
-    /// the amount of swap
+    /// the amount of swap received after fees
     pub amount: Uint128,
+    /// the router fee taken from the swap output
+    pub fee_amount: Uint128,
+    /// the referral fee taken from the swap output
+    pub referral_amount: Uint128,
+    /// the relayer fee taken from the swap output
+    pub relayer_amount: Uint128,
+    /// the breakdown of every operation, in their order
+    #[serde(default)]
+    pub breakdowns: Vec<SwapBreakdown>,
+}
+
+/// ## Description
+/// This structure describes the custom struct for each query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EstimateRequiredOfferResponse {
+    /// the operations of the best route
+    pub operations: Vec<SwapOperation>,
+    /// the offer amount the route needs to return the ask amount after fees
+    pub offer_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes the realized amounts of a single swap operation.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SwapHopReceipt {
+    /// the asset sent to the pair or the market module
+    pub offer_asset: Asset,
+    /// the asset received from the operation
+    pub return_asset: Asset,
+    /// the return amount per offered unit
+    pub price: Decimal,
 }
 
 /// ## Description
-/// This structure describes a migration message.
-/// We currently take no arguments for migrations.
+/// This structure describes the realized amounts of a multi-hop swap, set as the data of
+/// the swap transaction.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct SwapReceipt {
+    /// the realized amounts of every operation
+    pub hops: Vec<SwapHopReceipt>,
+    /// the asset offered to the first operation
+    pub offer_asset: Asset,
+    /// the asset returned by the last operation, before router fees
+    pub return_asset: Asset,
+    /// the effective return amount per offered unit over the whole route
+    pub price: Decimal,
+}
+
+/// ## Description
+/// This structure describes the cumulative volume the router swapped from and into an asset.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetVolume {
+    pub asset_info: AssetInfo,
+    /// the total amount offered by swaps starting from the asset
+    pub offer_volume: Uint128,
+    /// the total amount returned by swaps ending in the asset, before router fees
+    pub ask_volume: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the asset volumes query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetVolumesResponse {
+    pub volumes: Vec<AssetVolume>,
+}
+
+/// ## Description
+/// This structure describes the migration messages of the contract, one variant for each version
+/// the code can be migrated to.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrateMsg {
+    /// Migrates the legacy config, setting the owner it did not store
+    V1_0_0 { owner: Addr },
+}
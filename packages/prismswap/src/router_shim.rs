@@ -0,0 +1,120 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::migrator::LegacyAssetInfo;
+use crate::router::SwapOperation;
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the PrismSwap router the swaps are forwarded to
+    pub router: Addr,
+}
+
+/// ## Description
+/// This enum describes the swap operation in the Terraswap router format.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TerraswapSwapOperation {
+    /// Native swap
+    NativeSwap {
+        offer_denom: String,
+        ask_denom: String,
+    },
+    /// Terraswap swap, routed through the PrismSwap pair of the same assets
+    TerraSwap {
+        offer_asset_info: LegacyAssetInfo,
+        ask_asset_info: LegacyAssetInfo,
+    },
+}
+
+impl From<TerraswapSwapOperation> for SwapOperation {
+    fn from(operation: TerraswapSwapOperation) -> Self {
+        match operation {
+            TerraswapSwapOperation::NativeSwap {
+                offer_denom,
+                ask_denom,
+            } => SwapOperation::NativeSwap {
+                offer_denom,
+                ask_denom,
+            },
+            TerraswapSwapOperation::TerraSwap {
+                offer_asset_info,
+                ask_asset_info,
+            } => SwapOperation::PrismSwap {
+                offer_asset_info: offer_asset_info.into(),
+                ask_asset_info: ask_asset_info.into(),
+                pair: None,
+                max_spread: None,
+            },
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract, those of the Terraswap router.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving the cw20 offer asset
+    Receive(Cw20ReceiveMsg),
+    /// ExecuteSwapOperations swaps the sent native asset through the PrismSwap router
+    ExecuteSwapOperations {
+        operations: Vec<TerraswapSwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the cw20 offer assets sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// ExecuteSwapOperations swaps the sent tokens through the PrismSwap router
+    ExecuteSwapOperations {
+        operations: Vec<TerraswapSwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract, those of the Terraswap router.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns the PrismSwap factory in the [`ConfigResponse`] structure
+    Config {},
+    /// SimulateSwapOperations simulates the swap through the PrismSwap router
+    SimulateSwapOperations {
+        offer_amount: Uint128,
+        operations: Vec<TerraswapSwapOperation>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        SimulateSwapOperations => SimulateSwapOperationsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    /// the PrismSwap factory, under the Terraswap field name
+    pub terraswap_factory: String,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the simulate swap operations query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateSwapOperationsResponse {
+    /// the amount received after the router fees
+    pub amount: Uint128,
+}
@@ -0,0 +1,65 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, StdError, StdResult, Uint128};
+use sha2::{Digest, Sha256};
+
+/// ## Description
+/// This enum describes the addresses allowed to take part in a launch sale.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Whitelist {
+    /// the addresses listed
+    Addresses { addresses: Vec<Addr> },
+    /// the addresses proven leaves of the merkle tree of the hex encoded root, the leaves being
+    /// the sha256 hashes of the addresses and every node the hash of its children sorted
+    MerkleRoot { root: String },
+}
+
+impl Whitelist {
+    pub fn validate(&self) -> StdResult<()> {
+        if let Whitelist::MerkleRoot { root } = self {
+            decode_hash(root)?;
+        }
+        Ok(())
+    }
+
+    /// whether `address` is listed, or proven by `proof` a leaf of the merkle tree
+    pub fn verify(&self, address: &Addr, proof: &[String]) -> StdResult<bool> {
+        match self {
+            Whitelist::Addresses { addresses } => Ok(addresses.contains(address)),
+            Whitelist::MerkleRoot { root } => {
+                let mut hash: [u8; 32] = Sha256::digest(address.as_bytes()).into();
+                for node in proof {
+                    let node = decode_hash(node)?;
+                    let (left, right) = if hash <= node {
+                        (hash, node)
+                    } else {
+                        (node, hash)
+                    };
+                    hash = Sha256::digest(&[left, right].concat()).into();
+                }
+                Ok(hash == decode_hash(root)?)
+            }
+        }
+    }
+}
+
+fn decode_hash(hash: &str) -> StdResult<[u8; 32]> {
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(hash, &mut bytes)
+        .map_err(|_| StdError::generic_err("Merkle hashes must be 32 hex encoded bytes"))?;
+    Ok(bytes)
+}
+
+/// ## Description
+/// This structure describes a custom struct for the participant query response of the launch
+/// sales.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ParticipantResponse {
+    pub address: Addr,
+    /// whether the address can take part in the sale
+    pub whitelisted: bool,
+    /// the amount of the capped asset the address bought
+    pub purchased: Uint128,
+}
@@ -0,0 +1,191 @@
+//! A decimal with a sign, for the price deltas, the weight drifts and the profits and losses
+//! that can be negative. It serializes as a string like [`Decimal`], prefixed with `-` when
+//! negative, and zero is never negative.
+
+use cosmwasm_std::{Decimal, StdError};
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{de, ser, Deserialize, Deserializer, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// ## Description
+/// This structure describes a signed fixed point decimal, a [`Decimal`] magnitude and a sign.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub struct SignedDecimal {
+    magnitude: Decimal,
+    negative: bool,
+}
+
+impl SignedDecimal {
+    /// Returns the decimal of the magnitude and the sign, zero being never negative
+    pub fn new(magnitude: Decimal, negative: bool) -> Self {
+        SignedDecimal {
+            magnitude,
+            negative: negative && !magnitude.is_zero(),
+        }
+    }
+
+    pub fn zero() -> Self {
+        SignedDecimal::default()
+    }
+
+    /// Returns the negative decimal of the magnitude
+    pub fn negative(magnitude: Decimal) -> Self {
+        SignedDecimal::new(magnitude, true)
+    }
+
+    /// Returns `a - b`, the change from `b` to `a`
+    pub fn diff(a: Decimal, b: Decimal) -> Self {
+        if a >= b {
+            SignedDecimal::new(a - b, false)
+        } else {
+            SignedDecimal::negative(b - a)
+        }
+    }
+
+    /// Returns the absolute value
+    pub fn abs(&self) -> Decimal {
+        self.magnitude
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    /// Returns the decimal if it is not negative, none otherwise
+    pub fn to_decimal(&self) -> Option<Decimal> {
+        if self.negative {
+            None
+        } else {
+            Some(self.magnitude)
+        }
+    }
+}
+
+impl From<Decimal> for SignedDecimal {
+    fn from(magnitude: Decimal) -> Self {
+        SignedDecimal::new(magnitude, false)
+    }
+}
+
+impl Neg for SignedDecimal {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        SignedDecimal::new(self.magnitude, !self.negative)
+    }
+}
+
+impl Add for SignedDecimal {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        match (self.negative, other.negative) {
+            (false, false) => SignedDecimal::from(self.magnitude + other.magnitude),
+            (true, true) => SignedDecimal::negative(self.magnitude + other.magnitude),
+            (false, true) => SignedDecimal::diff(self.magnitude, other.magnitude),
+            (true, false) => SignedDecimal::diff(other.magnitude, self.magnitude),
+        }
+    }
+}
+
+impl Sub for SignedDecimal {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, other: Self) -> Self {
+        self + -other
+    }
+}
+
+impl Ord for SignedDecimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for SignedDecimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for SignedDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.negative {
+            write!(f, "-{}", self.magnitude)
+        } else {
+            write!(f, "{}", self.magnitude)
+        }
+    }
+}
+
+impl FromStr for SignedDecimal {
+    type Err = StdError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.strip_prefix('-') {
+            Some(magnitude) => Ok(SignedDecimal::negative(Decimal::from_str(magnitude)?)),
+            None => Ok(SignedDecimal::from(Decimal::from_str(input)?)),
+        }
+    }
+}
+
+impl JsonSchema for SignedDecimal {
+    fn schema_name() -> String {
+        "SignedDecimal".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+impl Serialize for SignedDecimal {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: ser::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for SignedDecimal {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(SignedDecimalVisitor)
+    }
+}
+
+struct SignedDecimalVisitor;
+
+impl<'de> de::Visitor<'de> for SignedDecimalVisitor {
+    type Value = SignedDecimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("string-encoded signed decimal")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        SignedDecimal::from_str(v)
+            .map_err(|e| E::custom(format!("Error parsing signed decimal '{}': {}", v, e)))
+    }
+}
@@ -0,0 +1,316 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to manage pools and update configuration
+    pub owner: Addr,
+    /// the PRISM token paid as reward
+    pub prism_token: Addr,
+    /// the PRISM emitted every block, split between the pools by allocation points
+    pub tokens_per_block: Uint128,
+    /// the block emissions start at
+    pub start_block: u64,
+    /// the xPRISM token boosting the emissions of its holders, none to disable boosts
+    pub xprism_token: Option<Addr>,
+    /// the most a staker's share of the emissions can be scaled by, at least 1
+    pub max_boost: Decimal,
+    /// the periods emitting at their own rate instead of `tokens_per_block`
+    pub emission_schedule: Vec<EmissionPeriod>,
+}
+
+/// ## Description
+/// This structure describes a period of the emission schedule, from `start_block` included to
+/// `end_block` excluded.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionPeriod {
+    pub start_block: u64,
+    pub end_block: u64,
+    pub tokens_per_block: Uint128,
+}
+
+/// ## Description
+/// This structure describes the contract a partner streams its own token to the stakers of a pool
+/// through, alongside PRISM.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardProxy {
+    /// the proxy contract, executed with [`ProxyExecuteMsg::Claim`]
+    pub contract_addr: Addr,
+    /// the cw20 token the proxy pays out
+    pub reward_token: Addr,
+}
+
+/// ## Description
+/// This structure describes the cut of the PRISM rewards paid out by a withdrawal within `period`
+/// seconds of the deposit time of the staker, decaying linearly from `max_penalty` right after the
+/// deposit to nothing at the end of the period. A top-up averages the deposit time with the bond,
+/// weighted by the amounts. The cut is streamed to the other stakers of the pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarlyUnstakePenalty {
+    pub period: u64,
+    /// the share of the rewards cut right after a deposit, at most 1
+    pub max_penalty: Decimal,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving LP tokens
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner, the emission rate and schedule, the boost settings and the
+    /// gauge contract. Boosts of existing stakers follow the new settings on their next action
+    UpdateConfig {
+        owner: Option<Addr>,
+        tokens_per_block: Option<Uint128>,
+        xprism_token: Option<Addr>,
+        max_boost: Option<Decimal>,
+        emission_schedule: Option<Vec<EmissionPeriod>>,
+        gauge: Option<Addr>,
+    },
+    /// AddPool registers an LP token with its share of the emissions, withdrawals being released
+    /// `unbonding_period` seconds after they are requested if set
+    AddPool {
+        lp_token: Addr,
+        alloc_point: u64,
+        reward_proxy: Option<RewardProxy>,
+        unbonding_period: Option<u64>,
+    },
+    /// SetPool updates the share of the emissions of a registered LP token, and the unbonding
+    /// period of the withdrawals requested from now on
+    SetPool {
+        lp_token: Addr,
+        alloc_point: u64,
+        unbonding_period: Option<u64>,
+    },
+    /// SetAllocPoints replaces the allocation points of every pool, the pools not listed getting
+    /// none. Only the owner and the gauge contract can execute it
+    SetAllocPoints { alloc_points: Vec<PoolAllocPoint> },
+    /// SetRewardProxy adds the partner rewards of a pool that has none yet
+    SetRewardProxy {
+        lp_token: Addr,
+        reward_proxy: RewardProxy,
+    },
+    /// SetEarlyUnstakePenalty sets or removes the early unstake penalty of a pool
+    SetEarlyUnstakePenalty {
+        lp_token: Addr,
+        early_unstake_penalty: Option<EarlyUnstakePenalty>,
+    },
+    /// Deposit bonds LP tokens the contract is allowed to transfer from the sender
+    Deposit { lp_token: Addr, amount: Uint128 },
+    /// Withdraw unbonds LP tokens, claiming the pending rewards. The LP tokens of a pool with an
+    /// unbonding period are queued until released
+    Withdraw { lp_token: Addr, amount: Uint128 },
+    /// WithdrawUnbonded sends the queued LP tokens of the sender whose unbonding period is over
+    WithdrawUnbonded { lp_token: Addr },
+    /// Claim sends the pending rewards of the pool, the proxy rewards included
+    Claim { lp_token: Addr },
+    /// EmergencyWithdraw unbonds the LP tokens of the sender without running the reward
+    /// accounting, forfeiting the pending rewards. They are still queued for the unbonding period
+    /// of the pool
+    EmergencyWithdraw { lp_token: Addr },
+    /// UpdateGuardian sets the address allowed to switch the withdraw-only mode besides the owner
+    UpdateGuardian { guardian: Option<Addr> },
+    /// SetWithdrawOnly switches the withdraw-only mode, in which LP tokens can no longer be
+    /// deposited while withdrawals and claims go on
+    SetWithdrawOnly { withdraw_only: bool },
+    /// Internal use
+    Callback(CallbackMsg),
+}
+
+/// ## Description
+/// This structure describes the allocation points of a pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolAllocPoint {
+    pub lp_token: Addr,
+    pub alloc_point: u64,
+}
+
+/// ## Description
+/// This structure describes the hook messages of the LP tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Bond the sent LP tokens on behalf of `staker_addr`, the sender if not set
+    Bond { staker_addr: Option<Addr> },
+}
+
+/// ## Description
+/// This structure describes the actions run once the reward proxy of the pool has paid out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Accrue the proxy rewards received since `prev_balance` to the stakers of the pool
+    UpdateProxyReward {
+        lp_token: Addr,
+        prev_balance: Uint128,
+    },
+    Bond {
+        lp_token: Addr,
+        staker: Addr,
+        amount: Uint128,
+    },
+    Unbond {
+        lp_token: Addr,
+        staker: Addr,
+        amount: Uint128,
+    },
+    Claim {
+        lp_token: Addr,
+        staker: Addr,
+    },
+}
+
+/// ## Description
+/// This structure describes the messages reward proxies must handle.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxyExecuteMsg {
+    /// Send the rewards accrued for the staking contract to it
+    Claim {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Pool returns the state of the pool of an LP token
+    Pool { lp_token: Addr },
+    /// Pools returns the pools according to the specified parameters in `start_after` and `limit` variables.
+    Pools {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    /// StakerInfo returns the LP tokens a staker bonded in a pool
+    StakerInfo { lp_token: Addr, staker: Addr },
+    /// UnbondingClaims returns the LP tokens of a staker queued for withdrawal from a pool
+    UnbondingClaims { lp_token: Addr, staker: Addr },
+    /// PendingRewards returns the rewards a user can claim from a pool
+    PendingRewards { user: Addr, lp_token: Addr },
+    /// AllPendingRewards returns the rewards a user can claim from the pools it is bonded in,
+    /// according to the specified parameters in `start_after` and `limit` variables
+    AllPendingRewards {
+        user: Addr,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Pool => PoolResponse,
+        Pools => PoolsResponse,
+        StakerInfo => StakerInfoResponse,
+        UnbondingClaims => UnbondingClaimsResponse,
+        PendingRewards => PendingRewardsResponse,
+        AllPendingRewards => AllPendingRewardsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub prism_token: Addr,
+    pub tokens_per_block: Uint128,
+    /// the sum of the allocation points of every pool
+    pub total_alloc_point: u64,
+    pub start_block: u64,
+    pub xprism_token: Option<Addr>,
+    pub max_boost: Decimal,
+    pub emission_schedule: Vec<EmissionPeriod>,
+    /// the contract allowed to set the allocation points along with the owner
+    pub gauge: Option<Addr>,
+    /// the address allowed to switch the withdraw-only mode besides the owner
+    pub guardian: Option<Addr>,
+    /// whether deposits are halted
+    pub withdraw_only: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the pool query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolResponse {
+    pub lp_token: Addr,
+    pub alloc_point: u64,
+    /// the LP tokens bonded in the pool
+    pub total_bond: Uint128,
+    /// the boosted bonds the emissions are shared by
+    pub total_working_amount: Uint128,
+    /// the rewards accrued per unit of working amount as of `last_reward_block`
+    pub reward_index: Decimal,
+    pub last_reward_block: u64,
+    pub reward_proxy: Option<RewardProxy>,
+    /// the proxy rewards accrued per bonded LP token as of the last claim from the proxy
+    pub proxy_reward_index: Decimal,
+    /// the seconds withdrawals are queued for, 0 if they are sent right away
+    pub unbonding_period: u64,
+    pub early_unstake_penalty: Option<EarlyUnstakePenalty>,
+    /// the penalties not streamed to the stakers yet
+    pub pending_penalty: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the pools query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PoolsResponse {
+    pub pools: Vec<PoolResponse>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the staker info query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerInfoResponse {
+    pub staker: Addr,
+    pub lp_token: Addr,
+    pub bond_amount: Uint128,
+    /// the bond boosted by the xPRISM of the staker as of its last action
+    pub working_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes LP tokens withdrawn from a pool and waiting for their unbonding period.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingClaim {
+    pub amount: Uint128,
+    /// the block time in seconds the LP tokens can be withdrawn from
+    pub release_at: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the unbonding claims query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnbondingClaimsResponse {
+    pub claims: Vec<UnbondingClaim>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the pending rewards query response. The proxy
+/// rewards only include what the contract received from the proxy so far.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingRewardsResponse {
+    pub lp_token: Addr,
+    /// the PRISM accrued, before any early unstake penalty
+    pub pending_reward: Uint128,
+    /// the token of the reward proxy of the pool, if any
+    pub proxy_reward_token: Option<Addr>,
+    pub pending_proxy_reward: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the all pending rewards query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllPendingRewardsResponse {
+    pub pending_rewards: Vec<PendingRewardsResponse>,
+}
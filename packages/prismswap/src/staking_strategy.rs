@@ -0,0 +1,109 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::AssetInfo;
+use crate::router::SwapOperation;
+use crate::strategy::{StrategyInfoResponse, TotalAssetsResponse};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the vault the strategy manages the LP tokens of
+    pub vault: Addr,
+    /// the staking contract the LP tokens are bonded in, their pool having no unbonding period
+    pub staking: Addr,
+    /// the router the rewards are swapped and provided through
+    pub router: Addr,
+    /// the PRISM token the staking contract pays as reward
+    pub prism_token: Addr,
+    /// the pair of the LP tokens
+    pub pair: Addr,
+    /// the swap operations converting PRISM to an asset of the pair, none if PRISM is one
+    pub compound_operations: Vec<SwapOperation>,
+    /// the maximum spread of every hop of a conversion
+    pub max_spread: Decimal,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract, extending
+/// [`crate::strategy::StrategyExecuteMsg`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving LP tokens from the vault
+    Receive(Cw20ReceiveMsg),
+    /// Withdraw unbonds the LP tokens, sending them back to the vault
+    Withdraw { amount: Uint128 },
+    /// Harvest claims the PRISM rewards, converts them to LP tokens through the router and bonds
+    /// them
+    Harvest {},
+    /// EmergencyExit unbonds every LP token, sending them back to the vault. The owner can
+    /// execute it as well
+    EmergencyExit {},
+    /// UpdateConfig updates the owner, the conversion of the rewards and its protection
+    UpdateConfig {
+        owner: Option<Addr>,
+        compound_operations: Option<Vec<SwapOperation>>,
+        max_spread: Option<Decimal>,
+    },
+    /// Internal use
+    Callback(CallbackMsg),
+}
+
+/// ## Description
+/// This structure describes the actions run once the rewards are claimed.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Swap the PRISM balance to an asset of the pair, or provide it if PRISM is one
+    SwapRewards {},
+    /// Provide the asset received since `prev_balance` to the pair
+    ProvideRewards {
+        asset_info: AssetInfo,
+        prev_balance: Uint128,
+    },
+    /// Bond the LP token balance
+    BondLiquidity {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract, extending
+/// [`crate::strategy::StrategyQueryMsg`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// StrategyInfo returns the vault and the LP token of the strategy
+    StrategyInfo {},
+    /// TotalAssets returns the LP tokens bonded and held
+    TotalAssets {},
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+}
+
+query_responses! {
+    QueryMsg {
+        StrategyInfo => StrategyInfoResponse,
+        TotalAssets => TotalAssetsResponse,
+        Config => ConfigResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub vault: Addr,
+    pub staking: Addr,
+    pub router: Addr,
+    pub prism_token: Addr,
+    pub pair: Addr,
+    pub lp_token: Addr,
+    pub compound_operations: Vec<SwapOperation>,
+    pub max_spread: Decimal,
+}
@@ -0,0 +1,147 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo};
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+pub const MAX_KEEPER_FEE: &str = "0.05";
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration
+    pub owner: Addr,
+    /// the factory the pairs whose TWAP triggers the orders are looked up in
+    pub factory: Addr,
+    /// the router the orders are executed through
+    pub router: Addr,
+    /// the share of the offer asset paid to the keeper executing an order
+    pub keeper_fee: Decimal,
+    /// the seconds the TWAP triggering the orders is averaged over
+    pub twap_window: u64,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving CW20 tokens
+    Receive(Cw20ReceiveMsg),
+    /// SubmitOrder escrows the native offer asset sent until the order is executed, expires or is
+    /// cancelled
+    SubmitOrder {
+        offer_asset: Asset,
+        ask_asset_info: AssetInfo,
+        trigger_price: Decimal,
+        max_spread: Decimal,
+        expires_at: Option<u64>,
+    },
+    /// CancelOrder sends the offer asset back to the maker. Only the maker can execute it, or
+    /// anyone once the order expired
+    CancelOrder { order_id: u64 },
+    /// CancelOrders cancels several orders at once, as CancelOrder does
+    CancelOrders { order_ids: Vec<u64> },
+    /// ExecuteOrder swaps the offer asset through the router once the TWAP of the pair fell to the
+    /// trigger price, paying the keeper fee to the sender. Anyone can execute it
+    ExecuteOrder { order_id: u64 },
+    /// UpdateConfig updates the owner, the keeper fee and the TWAP window
+    UpdateConfig {
+        owner: Option<Addr>,
+        keeper_fee: Option<Decimal>,
+        twap_window: Option<u64>,
+    },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the CW20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// SubmitOrder escrows the sent tokens until the order is executed, expires or is cancelled
+    SubmitOrder {
+        ask_asset_info: AssetInfo,
+        trigger_price: Decimal,
+        max_spread: Decimal,
+        expires_at: Option<u64>,
+    },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Order returns an open order
+    Order { order_id: u64 },
+    /// Orders returns the open orders, of a maker if set, according to the specified parameters
+    /// in `start_after` and `limit` variables
+    Orders {
+        maker: Option<Addr>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Trigger returns the TWAP of the pair of an order and whether it can be executed
+    Trigger { order_id: u64 },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Order => OrderResponse,
+        Orders => OrdersResponse,
+        Trigger => TriggerResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub factory: Addr,
+    pub router: Addr,
+    pub keeper_fee: Decimal,
+    pub twap_window: u64,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the order query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrderResponse {
+    pub order_id: u64,
+    pub maker: Addr,
+    /// the pair whose TWAP triggers the order and the offer asset is swapped in
+    pub pair: Addr,
+    pub offer_asset: Asset,
+    pub ask_asset_info: AssetInfo,
+    /// the TWAP of the offer asset in the ask asset at or below which the order can be executed
+    pub trigger_price: Decimal,
+    /// the maximum spread of the swap
+    pub max_spread: Decimal,
+    /// the time the order can no longer be executed at, none if it never expires
+    pub expires_at: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the orders query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct OrdersResponse {
+    pub orders: Vec<OrderResponse>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the trigger query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TriggerResponse {
+    /// the TWAP of the offer asset in the ask asset
+    pub twap_price: Decimal,
+    /// the share of the offer asset paid to the keeper
+    pub keeper_fee_amount: Uint128,
+    /// whether the order can be executed, never once it expired
+    pub triggered: bool,
+}
@@ -0,0 +1,69 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the execute messages every strategy implements, the vault delegating
+/// its LP tokens through them. A strategy only accepts them from its vault, its own messages
+/// extending them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyExecuteMsg {
+    /// Receive calls a hook message after receiving LP tokens from the vault
+    Receive(Cw20ReceiveMsg),
+    /// Withdraw sends the LP tokens back to the vault
+    Withdraw { amount: Uint128 },
+    /// Harvest claims the rewards and compounds them into the LP tokens managed
+    Harvest {},
+    /// EmergencyExit sends every LP token back to the vault without harvesting, the strategy
+    /// refusing deposits afterwards
+    EmergencyExit {},
+}
+
+/// ## Description
+/// This structure describes the hook messages of the LP tokens the vault sends to a strategy.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyCw20HookMsg {
+    /// Deposit puts the sent LP tokens to work
+    Deposit {},
+}
+
+/// ## Description
+/// This structure describes the query messages every strategy implements.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyQueryMsg {
+    /// StrategyInfo returns the vault and the LP token of the strategy in custom
+    /// [`StrategyInfoResponse`] structure
+    StrategyInfo {},
+    /// TotalAssets returns the LP tokens the strategy manages for the vault
+    TotalAssets {},
+}
+
+query_responses! {
+    StrategyQueryMsg {
+        StrategyInfo => StrategyInfoResponse,
+        TotalAssets => TotalAssetsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the strategy info query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StrategyInfoResponse {
+    pub vault: Addr,
+    pub lp_token: Addr,
+    /// whether the strategy exited, refusing deposits
+    pub exited: bool,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the total assets query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalAssetsResponse {
+    /// the LP tokens managed, deposited or held
+    pub amount: Uint128,
+}
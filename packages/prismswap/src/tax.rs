@@ -0,0 +1,101 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Coin, Decimal, StdResult, Uint128};
+#[cfg(feature = "terra")]
+use {cosmwasm_std::QuerierWrapper, terra_cosmwasm::TerraQuerier};
+
+const DECIMAL_FRACTION: Uint128 = Uint128::new(1_000_000_000_000_000_000u128);
+
+/// ## Description
+/// This structure describes the Terra tax rate and the caps of the denoms a swap pays tax in.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TaxInfo {
+    /// the tax rate of native transfers
+    pub rate: Decimal,
+    /// the maximum tax of a single transfer for each denom
+    pub caps: Vec<Coin>,
+}
+
+impl TaxInfo {
+    /// ## Description
+    /// Queries the tax rate and the caps of the denoms once, for the whole call to compute the
+    /// taxes of its transfers without querying the treasury again. Luna is not taxed so its cap
+    /// is not queried.
+    /// ## Params
+    /// * **querier** is the object of type [`QuerierWrapper`].
+    ///
+    /// * **denoms** are the denoms the call transfers.
+    #[cfg(feature = "terra")]
+    pub fn query(querier: &QuerierWrapper, denoms: &[String]) -> StdResult<Self> {
+        let terra_querier = TerraQuerier::new(querier);
+
+        let rate = terra_querier.query_tax_rate()?.rate;
+        let mut caps: Vec<Coin> = vec![];
+        for denom in denoms {
+            if denom == "uluna" || caps.iter().any(|cap| cap.denom == *denom) {
+                continue;
+            }
+            caps.push(Coin {
+                denom: denom.to_string(),
+                amount: terra_querier.query_tax_cap(denom.to_string())?.cap,
+            });
+        }
+
+        Ok(TaxInfo { rate, caps })
+    }
+
+    /// Returns the tax cap of the denom, none for luna and the denoms without a queried cap,
+    /// which are not taxed
+    fn cap(&self, denom: &str) -> Option<Uint128> {
+        if denom == "uluna" {
+            return None;
+        }
+
+        self.caps
+            .iter()
+            .find(|cap| cap.denom == denom)
+            .map(|cap| cap.amount)
+    }
+
+    /// ## Description
+    /// Returns the amount left once the tax charged on top of sending it is taken out, so that
+    /// the sent amount and its tax add up to `coin`, the tax being capped.
+    /// ## Params
+    /// * **coin** is the amount held to send.
+    pub fn deduct_tax(&self, coin: &Coin) -> StdResult<Uint128> {
+        let cap = match self.cap(&coin.denom) {
+            Some(cap) => cap,
+            None => return Ok(coin.amount),
+        };
+
+        let tax = std::cmp::min(
+            coin.amount.checked_sub(coin.amount.multiply_ratio(
+                DECIMAL_FRACTION,
+                DECIMAL_FRACTION * self.rate + DECIMAL_FRACTION,
+            ))?,
+            cap,
+        );
+
+        Ok(coin.amount.checked_sub(tax)?)
+    }
+
+    /// ## Description
+    /// Returns the amount to hold so that sending it leaves at least `coin` once the capped tax
+    /// is taken out, adding one to the rounded down tax so the amount is never short.
+    /// ## Params
+    /// * **coin** is the amount to send.
+    pub fn add_tax(&self, coin: &Coin) -> StdResult<Uint128> {
+        let cap = match self.cap(&coin.denom) {
+            Some(cap) => cap,
+            None => return Ok(coin.amount),
+        };
+
+        if self.rate.is_zero() || coin.amount.is_zero() {
+            return Ok(coin.amount);
+        }
+        let tax = coin.amount * self.rate + Uint128::new(1);
+
+        Ok(coin.amount.checked_add(std::cmp::min(tax, cap))?)
+    }
+}
@@ -0,0 +1,247 @@
+//! Multi-test setups running the real PrismSwap contracts, for protocols integrating with them to
+//! test against the contract logic instead of hand-written mock queriers.
+//!
+//! The package cannot depend on the contract crates, which depend on it, so the factory, pair
+//! and router code is handed over in [`PrismSwapContracts`], built from the contract crates
+//! compiled with their `library` feature. The token code is the cw20 base contract.
+//!
+//! The app serves no Terra queries nor messages: swaps through pairs work with any asset, but
+//! router swaps should stick to cw20 hops, native hops querying the Terra treasury for taxes.
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
+use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
+use cw20::{BalanceResponse, Cw20Coin, MinterResponse};
+use cw_multi_test::{App, AppResponse, BankKeeper, Contract, ContractWrapper, Executor};
+
+use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::builder::{ProvideLiquidityBuilder, RouterSwapBuilder, SwapMsgBuilder};
+use crate::custom_msg::DefaultMsg;
+use crate::factory::QueryMsg as FactoryQueryMsg;
+use crate::factory::{ExecuteMsg as FactoryExecuteMsg, InstantiateMsg as FactoryInstantiateMsg};
+use crate::router::{InstantiateMsg as RouterInstantiateMsg, SwapOperation};
+use crate::token::InstantiateMsg as TokenInstantiateMsg;
+
+/// the address instantiating the contracts and owning them
+pub const OWNER: &str = "owner";
+/// the address the factory sends the fees to
+pub const COLLECTOR: &str = "collector";
+
+/// ## Description
+/// This structure describes the code of the contracts the app runs.
+pub struct PrismSwapContracts {
+    pub factory: Box<dyn Contract<DefaultMsg>>,
+    pub pair: Box<dyn Contract<DefaultMsg>>,
+    pub router: Box<dyn Contract<DefaultMsg>>,
+}
+
+/// Returns the cw20 base contract, used as the code of the mock tokens and the liquidity tokens
+pub fn token_contract() -> Box<dyn Contract<DefaultMsg>> {
+    Box::new(ContractWrapper::new_with_empty(
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
+    ))
+}
+
+/// ## Description
+/// This structure describes a multi-test app with an instantiated factory and router.
+pub struct PrismSwapApp {
+    pub app: App<DefaultMsg>,
+    pub owner: Addr,
+    pub factory: Addr,
+    pub router: Addr,
+    pub token_code_id: u64,
+}
+
+impl PrismSwapApp {
+    /// Stores the contracts and instantiates the factory and the router, owned by [`OWNER`]
+    pub fn new(contracts: PrismSwapContracts) -> Self {
+        let mut app = App::new(
+            MockApi::default(),
+            mock_env().block,
+            BankKeeper::new(),
+            MockStorage::new(),
+        );
+        let owner = Addr::unchecked(OWNER);
+
+        let token_code_id = app.store_code(token_contract());
+        let pair_code_id = app.store_code(contracts.pair);
+        let factory_code_id = app.store_code(contracts.factory);
+        let router_code_id = app.store_code(contracts.router);
+
+        let factory = app
+            .instantiate_contract(
+                factory_code_id,
+                owner.clone(),
+                &FactoryInstantiateMsg {
+                    token_code_id,
+                    pair_code_id,
+                    collector: Addr::unchecked(COLLECTOR),
+                    owner: owner.clone(),
+                    pairs_admin: owner.clone(),
+                },
+                &[],
+                "factory",
+                None,
+            )
+            .unwrap();
+
+        let router = app
+            .instantiate_contract(
+                router_code_id,
+                owner.clone(),
+                &RouterInstantiateMsg {
+                    factory: factory.clone(),
+                    owner: owner.clone(),
+                },
+                &[],
+                "router",
+                None,
+            )
+            .unwrap();
+
+        PrismSwapApp {
+            app,
+            owner,
+            factory,
+            router,
+            token_code_id,
+        }
+    }
+
+    /// Instantiates a 6 decimals cw20 token minted by the owner, with the initial balances
+    pub fn create_token(&mut self, symbol: &str, initial_balances: &[(&str, u128)]) -> Addr {
+        self.app
+            .instantiate_contract(
+                self.token_code_id,
+                self.owner.clone(),
+                &TokenInstantiateMsg {
+                    name: format!("{} token", symbol),
+                    symbol: symbol.to_string(),
+                    decimals: 6,
+                    initial_balances: initial_balances
+                        .iter()
+                        .map(|(address, amount)| Cw20Coin {
+                            address: address.to_string(),
+                            amount: Uint128::from(*amount),
+                        })
+                        .collect(),
+                    mint: Some(MinterResponse {
+                        minter: self.owner.to_string(),
+                        cap: None,
+                    }),
+                },
+                &[],
+                symbol,
+                None,
+            )
+            .unwrap()
+    }
+
+    /// Sets the native balance of an address
+    pub fn set_native_balance(&mut self, address: &str, amount: Vec<Coin>) {
+        self.app
+            .init_bank_balance(&Addr::unchecked(address), amount)
+            .unwrap();
+    }
+
+    /// Creates the pair of the asset infos with the default fees and returns it
+    pub fn create_pair(&mut self, asset_infos: [AssetInfo; 2]) -> PairInfo {
+        self.app
+            .execute_contract(
+                self.owner.clone(),
+                self.factory.clone(),
+                &FactoryExecuteMsg::CreatePair {
+                    asset_infos: asset_infos.clone(),
+                    fee_config: None,
+                },
+                &[],
+            )
+            .unwrap();
+
+        self.app
+            .wrap()
+            .query_wasm_smart(&self.factory, &FactoryQueryMsg::Pair { asset_infos })
+            .unwrap()
+    }
+
+    /// Provides the assets to the pair, approving the cw20 ones and sending the native ones
+    pub fn provide(
+        &mut self,
+        sender: &str,
+        pair: &PairInfo,
+        assets: [Asset; 2],
+    ) -> AnyResult<Vec<AppResponse>> {
+        let msgs = ProvideLiquidityBuilder::new(pair.contract_addr.clone(), assets).build()?;
+        self.app.execute_multi(Addr::unchecked(sender), msgs)
+    }
+
+    /// Swaps the offer asset in the pair, the return being sent to the sender
+    pub fn swap(
+        &mut self,
+        sender: &str,
+        pair: &PairInfo,
+        offer_asset: Asset,
+        max_spread: Option<Decimal>,
+    ) -> AnyResult<AppResponse> {
+        let mut builder = SwapMsgBuilder::new(pair.contract_addr.clone(), offer_asset);
+        if let Some(max_spread) = max_spread {
+            builder = builder.max_spread(max_spread);
+        }
+        self.app.execute(Addr::unchecked(sender), builder.build()?)
+    }
+
+    /// Swaps the offer asset through the router operations, the return being sent to the sender
+    pub fn swap_operations(
+        &mut self,
+        sender: &str,
+        offer_asset: Asset,
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+    ) -> AnyResult<AppResponse> {
+        let mut builder = RouterSwapBuilder::new(self.router.clone(), offer_asset, operations);
+        if let Some(minimum_receive) = minimum_receive {
+            builder = builder.minimum_receive(minimum_receive);
+        }
+        self.app.execute(Addr::unchecked(sender), builder.build()?)
+    }
+
+    /// Returns the balance of the asset held by the address
+    pub fn query_balance(&self, address: &str, asset_info: &AssetInfo) -> Uint128 {
+        match asset_info {
+            AssetInfo::Cw20(contract_addr) => {
+                let res: BalanceResponse = self
+                    .app
+                    .wrap()
+                    .query_wasm_smart(
+                        contract_addr,
+                        &cw20::Cw20QueryMsg::Balance {
+                            address: address.to_string(),
+                        },
+                    )
+                    .unwrap();
+                res.balance
+            }
+            AssetInfo::Native(denom) => {
+                self.app
+                    .wrap()
+                    .query_balance(address, denom)
+                    .unwrap()
+                    .amount
+            }
+        }
+    }
+
+    /// Panics unless the address holds the expected balance of the asset
+    pub fn assert_balance(&self, address: &str, asset_info: &AssetInfo, expected: u128) {
+        let balance = self.query_balance(address, asset_info);
+        assert_eq!(
+            balance,
+            Uint128::from(expected),
+            "balance of {} for {}",
+            asset_info,
+            address
+        );
+    }
+}
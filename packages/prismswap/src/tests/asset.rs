@@ -0,0 +1,93 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::Addr;
+
+use crate::asset::{validate_native_denom, AssetInfo, PrismSwapAssetInfo};
+
+const IBC_HASH: &str = "27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2";
+
+fn assert_valid(denom: &str) {
+    let api = MockApi::default();
+    validate_native_denom(&api, denom).unwrap();
+}
+
+fn assert_invalid(denom: &str) {
+    let api = MockApi::default();
+    validate_native_denom(&api, denom).unwrap_err();
+}
+
+#[test]
+fn ibc_denom() {
+    assert_valid(&format!("ibc/{}", IBC_HASH));
+    assert_valid(&format!("ibc/{}", "0".repeat(64)));
+
+    // the hash is uppercase
+    assert_invalid(&format!("ibc/{}", IBC_HASH.to_lowercase()));
+    assert_invalid(&format!("ibc/{}a", &IBC_HASH[..63]));
+    // and 64 hexadecimal characters
+    assert_invalid(&format!("ibc/{}", &IBC_HASH[..63]));
+    assert_invalid(&format!("ibc/{}0", IBC_HASH));
+    assert_invalid(&format!("ibc/{}G", &IBC_HASH[..63]));
+    assert_invalid("ibc/");
+    assert_invalid(&format!("ibc/{}/", IBC_HASH));
+}
+
+#[test]
+fn factory_denom() {
+    assert_valid("factory/creator0000/uprism");
+    assert_valid("factory/creator0000/lp/uusd.uluna");
+
+    // the creator is a valid address
+    assert_invalid("factory/ab/uprism");
+    assert_invalid(&format!("factory/{}/uprism", "c".repeat(100)));
+    assert_invalid("factory//uprism");
+    // followed by a subdenom
+    assert_invalid("factory/creator0000");
+    assert_invalid("factory/creator0000/");
+    // of the bank denom characters
+    assert_invalid("factory/creator0000/u prism");
+
+    // without the prefix it is a bank denom
+    assert_valid("factory");
+}
+
+#[test]
+fn bank_denom() {
+    assert_valid("uusd");
+    assert_valid("uluna");
+    assert_valid("gamm/pool/1");
+    assert_valid("cw20:terra1token.x_y-z");
+
+    // 3 to 128 characters
+    assert_invalid("");
+    assert_invalid("us");
+    assert_valid("usd");
+    assert_valid(&format!("u{}", "a".repeat(127)));
+    assert_invalid(&format!("u{}", "a".repeat(128)));
+
+    // starting with a letter
+    assert_invalid("1usd");
+    assert_invalid("/usd");
+    assert_invalid("-usd");
+    // followed by letters, digits or /:._-
+    assert_invalid("u usd");
+    assert_invalid("uusd!");
+    assert_invalid("uüsd");
+}
+
+#[test]
+fn validate_asset_info() {
+    let api = MockApi::default();
+
+    AssetInfo::Native("uusd".to_string())
+        .validate(&api)
+        .unwrap();
+    AssetInfo::Native("us".to_string())
+        .validate(&api)
+        .unwrap_err();
+    AssetInfo::Cw20(Addr::unchecked("token0000"))
+        .validate(&api)
+        .unwrap();
+    AssetInfo::Cw20(Addr::unchecked("ab"))
+        .validate(&api)
+        .unwrap_err();
+}
@@ -0,0 +1,167 @@
+use cosmwasm_std::{from_binary, to_binary, Addr, Coin, CosmosMsg, Empty, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+use crate::asset::{Asset, AssetInfo, PairInfo};
+use crate::fee::FeeConfig;
+use crate::hooks::{AutoStakeHookMsg, FeeDepositHookMsg, HookExecuteMsg, PairCreatedHookMsg};
+use crate::router::StakingHookMsg;
+use crate::staking::Cw20HookMsg as StakingCw20HookMsg;
+
+#[test]
+fn auto_stake_hook_is_staking_bond() {
+    let msg = AutoStakeHookMsg::Bond {
+        staker_addr: Some(Addr::unchecked("addr0000")),
+    };
+    assert_eq!(
+        to_binary(&msg).unwrap().to_vec(),
+        br#"{"bond":{"staker_addr":"addr0000"}}"#.to_vec()
+    );
+
+    // the staking contract and the router bond with the same message
+    assert_eq!(
+        from_binary::<StakingCw20HookMsg>(&to_binary(&msg).unwrap()).unwrap(),
+        StakingCw20HookMsg::Bond {
+            staker_addr: Some(Addr::unchecked("addr0000")),
+        }
+    );
+    assert_eq!(
+        to_binary(&msg).unwrap(),
+        to_binary(&StakingHookMsg::Bond {
+            staker_addr: Some(Addr::unchecked("addr0000")),
+        })
+        .unwrap()
+    );
+
+    // the staker defaults to the sender
+    assert_eq!(
+        from_binary::<AutoStakeHookMsg>(&br#"{"bond":{}}"#.to_vec().into()).unwrap(),
+        AutoStakeHookMsg::Bond { staker_addr: None }
+    );
+}
+
+#[test]
+fn auto_stake_send_msg() {
+    let msg = AutoStakeHookMsg::Bond {
+        staker_addr: Some(Addr::unchecked("addr0000")),
+    };
+    assert_eq!(
+        msg.clone()
+            .into_send_msg::<Empty>(
+                &Addr::unchecked("liquidity0000"),
+                &Addr::unchecked("staking0000"),
+                Uint128::from(100u128),
+            )
+            .unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "liquidity0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: "staking0000".to_string(),
+                amount: Uint128::from(100u128),
+                msg: to_binary(&msg).unwrap(),
+            })
+            .unwrap(),
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn pair_created_hook() {
+    let msg = PairCreatedHookMsg {
+        pair_info: PairInfo {
+            asset_infos: [
+                AssetInfo::Native("uusd".to_string()),
+                AssetInfo::Cw20(Addr::unchecked("asset0000")),
+            ],
+            contract_addr: Addr::unchecked("pair0000"),
+            liquidity_token: Addr::unchecked("liquidity0000"),
+        },
+        fee_config: FeeConfig::from_bps(30, 5000),
+    };
+
+    let binary = msg.clone().into_binary().unwrap();
+    assert_eq!(
+        from_binary::<HookExecuteMsg>(&binary).unwrap(),
+        HookExecuteMsg::PairCreated(msg.clone())
+    );
+    assert_eq!(
+        std::str::from_utf8(&binary).unwrap(),
+        concat!(
+            r#"{"pair_created":{"pair_info":{"asset_infos":[{"native":"uusd"},{"cw20":"asset0000"}],"#,
+            r#""contract_addr":"pair0000","liquidity_token":"liquidity0000"},"#,
+            r#""fee_config":{"total_fee":"0.003","protocol_fee_share":"0.5","collector":null}}}"#,
+        )
+    );
+
+    assert_eq!(
+        msg.into_cosmos_msg::<Empty>(&Addr::unchecked("listener0000"))
+            .unwrap(),
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: "listener0000".to_string(),
+            msg: binary,
+            funds: vec![],
+        })
+    );
+}
+
+#[test]
+fn fee_deposit_hook() {
+    let msg = FeeDepositHookMsg {
+        source: Addr::unchecked("pair0000"),
+        assets: vec![
+            Asset {
+                info: AssetInfo::Native("uusd".to_string()),
+                amount: Uint128::from(100u128),
+            },
+            Asset {
+                info: AssetInfo::Cw20(Addr::unchecked("asset0000")),
+                amount: Uint128::from(200u128),
+            },
+            Asset {
+                info: AssetInfo::Native("ukrw".to_string()),
+                amount: Uint128::from(300u128),
+            },
+            Asset {
+                info: AssetInfo::Cw20(Addr::unchecked("asset0001")),
+                amount: Uint128::zero(),
+            },
+        ],
+    };
+
+    let binary = msg.clone().into_binary().unwrap();
+    assert_eq!(
+        from_binary::<HookExecuteMsg>(&binary).unwrap(),
+        HookExecuteMsg::FeeDeposit(msg.clone())
+    );
+
+    // the cw20 fees are transferred first and the native ones are sent sorted with the hook
+    assert_eq!(
+        msg.into_cosmos_msgs::<Empty>(&Addr::unchecked("collector0000"))
+            .unwrap(),
+        vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "asset0000".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "collector0000".to_string(),
+                    amount: Uint128::from(200u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: "collector0000".to_string(),
+                msg: binary,
+                funds: vec![
+                    Coin {
+                        denom: "ukrw".to_string(),
+                        amount: Uint128::from(300u128),
+                    },
+                    Coin {
+                        denom: "uusd".to_string(),
+                        amount: Uint128::from(100u128),
+                    },
+                ],
+            }),
+        ]
+    );
+}
@@ -0,0 +1,179 @@
+use crate::math::{
+    compute_offer_amount, compute_share, compute_swap, compute_withdraw_amounts, DECIMAL_FRACTIONAL,
+};
+
+// 0.3%
+const COMMISSION_RATE: u128 = DECIMAL_FRACTIONAL * 3 / 1000;
+
+#[test]
+fn swap() {
+    assert_eq!(
+        compute_swap(1_000_000, 1_000_000, 1_000, COMMISSION_RATE),
+        Some((997, 1, 2))
+    );
+
+    // the spread follows the pool price
+    assert_eq!(
+        compute_swap(1_000_000, 2_000_000, 1_000, 0),
+        Some((1998, 2, 0))
+    );
+
+    // nothing offered, nothing returned
+    assert_eq!(
+        compute_swap(1_000_000, 1_000_000, 0, COMMISSION_RATE),
+        Some((0, 0, 0))
+    );
+}
+
+#[test]
+fn swap_rounds_down() {
+    // 10 - 3 * 10 / (3 + 1) = 2.5
+    assert_eq!(compute_swap(3, 10, 1, 0), Some((2, 1, 0)));
+
+    // the 0.3% commission of 997 is 2.991
+    let (return_amount, _, commission_amount) =
+        compute_swap(1_000_000, 1_000_000, 1_000, COMMISSION_RATE).unwrap();
+    assert_eq!(commission_amount, 2);
+    assert_eq!(return_amount + commission_amount, 999);
+}
+
+#[test]
+fn swap_zero_pools() {
+    // an empty ask pool returns nothing
+    assert_eq!(
+        compute_swap(1_000_000, 0, 1_000, COMMISSION_RATE),
+        Some((0, 0, 0))
+    );
+}
+
+#[test]
+fn swap_empty_offer_pool() {
+    // the pool price is undefined
+    assert_eq!(compute_swap(0, 1_000_000, 1_000, COMMISSION_RATE), None);
+    assert_eq!(compute_swap(0, 0, 0, COMMISSION_RATE), None);
+}
+
+#[test]
+fn swap_whole_commission() {
+    // a commission rate of 1 leaves nothing to return
+    assert_eq!(
+        compute_swap(1_000_000, 1_000_000, 1_000, DECIMAL_FRACTIONAL),
+        None
+    );
+    assert_eq!(
+        compute_swap(1_000_000, 1_000_000, 1_000, DECIMAL_FRACTIONAL + 1),
+        None
+    );
+    assert_eq!(
+        compute_offer_amount(1_000_000, 1_000_000, 1_000, DECIMAL_FRACTIONAL),
+        None
+    );
+}
+
+#[test]
+fn swap_overflow() {
+    // the spread of a large offer against a tiny offer pool does not fit in 128 bits
+    assert_eq!(compute_swap(1, u128::MAX, u128::MAX, 0), None);
+    // nor do the products of the largest pools in 256 bits
+    assert_eq!(
+        compute_swap(u128::MAX, u128::MAX, 1_000, COMMISSION_RATE),
+        None
+    );
+    // nor the offer amount draining the largest pools to a single unit
+    assert_eq!(
+        compute_offer_amount(u128::MAX, u128::MAX, u128::MAX - 1, 0),
+        None
+    );
+}
+
+#[test]
+fn offer_amount() {
+    assert_eq!(
+        compute_offer_amount(1_000_000, 1_000_000, 5_000, COMMISSION_RATE),
+        Some((5040, 25, 15))
+    );
+
+    // the whole ask pool cannot be returned
+    assert_eq!(
+        compute_offer_amount(1_000_000, 1_000_000, 1_000_000, 0),
+        None
+    );
+    assert_eq!(
+        compute_offer_amount(1_000_000, 1_000_000, 1_000_001, 0),
+        None
+    );
+}
+
+#[test]
+fn offer_amount_zero_pools() {
+    assert_eq!(compute_offer_amount(1_000_000, 0, 1, COMMISSION_RATE), None);
+    assert_eq!(compute_offer_amount(0, 0, 0, COMMISSION_RATE), None);
+}
+
+#[test]
+fn swap_offer_round_trip() {
+    for (offer_pool, ask_pool) in [
+        (1_000_000u128, 1_000_000u128),
+        (1_000_000, 3_000_000),
+        (3_000_000, 1_000_000),
+        (123_456_789, 987_654_321),
+    ] {
+        for ask_amount in (1..20_000).step_by(7) {
+            let (offer_amount, _, _) =
+                compute_offer_amount(offer_pool, ask_pool, ask_amount, COMMISSION_RATE).unwrap();
+
+            // the offer amount rounds down, short of the ask amount by at most one unit offered
+            let (return_amount, _, _) =
+                compute_swap(offer_pool, ask_pool, offer_amount, COMMISSION_RATE).unwrap();
+            assert!(return_amount <= ask_amount);
+            let (return_amount, _, _) =
+                compute_swap(offer_pool, ask_pool, offer_amount + 1, COMMISSION_RATE).unwrap();
+            assert!(return_amount >= ask_amount);
+        }
+    }
+}
+
+#[test]
+fn share() {
+    // the first provision mints the square root of the deposits product
+    assert_eq!(compute_share([1_000_000, 4_000_000], [0, 0], 0), 2_000_000);
+    // rounded down, sqrt(2) = 1.41
+    assert_eq!(compute_share([1, 2], [0, 0], 0), 1);
+    assert_eq!(compute_share([0, 1_000_000], [0, 0], 0), 0);
+
+    // the smallest share of the pools is minted
+    assert_eq!(
+        compute_share([1_000, 3_000], [1_000_000, 2_000_000], 1_414_213),
+        1_414
+    );
+    // 1 * 1_000 / 3 = 333.3
+    assert_eq!(compute_share([1, 1], [3, 3], 1_000), 333);
+    assert_eq!(
+        compute_share([0, 1_000], [1_000_000, 1_000_000], 1_000_000),
+        0
+    );
+}
+
+#[test]
+fn withdraw_amounts() {
+    assert_eq!(
+        compute_withdraw_amounts([1_000_000, 2_000_000], 250_000, 1_000_000),
+        [250_000, 500_000]
+    );
+
+    // the whole supply withdraws the whole pools
+    assert_eq!(
+        compute_withdraw_amounts([1_000_000, 2_000_000], 1_000_000, 1_000_000),
+        [1_000_000, 2_000_000]
+    );
+
+    // rounded down, a third of 1_000 is 333.3
+    assert_eq!(compute_withdraw_amounts([1_000, 1_000], 1, 3), [333, 333]);
+
+    // empty pools refund nothing
+    assert_eq!(compute_withdraw_amounts([0, 0], 1_000, 1_000_000), [0, 0]);
+    assert_eq!(
+        compute_withdraw_amounts([1_000, 1_000], 0, 1_000_000),
+        [0, 0]
+    );
+}
@@ -0,0 +1,11 @@
+#[cfg(feature = "cosmwasm")]
+mod asset;
+#[cfg(feature = "cosmwasm")]
+mod hooks;
+mod math;
+#[cfg(feature = "cosmwasm")]
+mod price;
+#[cfg(feature = "cosmwasm")]
+mod router;
+#[cfg(feature = "cosmwasm")]
+mod signed_decimal;
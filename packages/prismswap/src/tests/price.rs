@@ -0,0 +1,193 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{Decimal, StdError, Uint128};
+
+use crate::price::{
+    compose_prices, cross_rate, divide_by_price, invert_price, multiply_prices, to_atomic_price,
+    to_token_price,
+};
+
+fn price_overflow() -> StdError {
+    StdError::generic_err("price overflow")
+}
+
+#[test]
+fn invert() {
+    assert_eq!(
+        invert_price(Decimal::percent(25)).unwrap(),
+        Decimal::from_str("4").unwrap()
+    );
+    // rounded down
+    assert_eq!(
+        invert_price(Decimal::from_str("3").unwrap()).unwrap(),
+        Decimal::from_str("0.333333333333333333").unwrap()
+    );
+    // the smallest price inverts to the largest one short of an overflow
+    assert_eq!(
+        invert_price(Decimal::from_str("0.000000000000000001").unwrap()).unwrap(),
+        Decimal::from_str("1000000000000000000").unwrap()
+    );
+
+    assert_eq!(
+        invert_price(Decimal::zero()).unwrap_err(),
+        StdError::generic_err("cannot invert a zero price")
+    );
+}
+
+#[test]
+fn multiply() {
+    assert_eq!(
+        multiply_prices(Decimal::percent(150), Decimal::from_str("2").unwrap()).unwrap(),
+        Decimal::from_str("3").unwrap()
+    );
+    // rounded down
+    assert_eq!(
+        multiply_prices(
+            Decimal::from_str("0.333333333333333333").unwrap(),
+            Decimal::from_str("3").unwrap()
+        )
+        .unwrap(),
+        Decimal::from_str("0.999999999999999999").unwrap()
+    );
+    assert_eq!(
+        multiply_prices(Decimal::MAX, Decimal::zero()).unwrap(),
+        Decimal::zero()
+    );
+
+    assert_eq!(
+        multiply_prices(Decimal::MAX, Decimal::from_str("2").unwrap()).unwrap_err(),
+        price_overflow()
+    );
+}
+
+#[test]
+fn compose() {
+    // an empty route keeps the asset
+    assert_eq!(compose_prices(&[]).unwrap(), Decimal::one());
+    assert_eq!(
+        compose_prices(&[
+            Decimal::from_str("2").unwrap(),
+            Decimal::percent(50),
+            Decimal::from_str("3").unwrap(),
+        ])
+        .unwrap(),
+        Decimal::from_str("3").unwrap()
+    );
+
+    assert_eq!(
+        compose_prices(&[
+            Decimal::MAX,
+            Decimal::percent(50),
+            Decimal::from_str("4").unwrap()
+        ])
+        .unwrap_err(),
+        price_overflow()
+    );
+}
+
+#[test]
+fn cross() {
+    assert_eq!(
+        cross_rate(
+            Decimal::from_str("2").unwrap(),
+            Decimal::from_str("4").unwrap()
+        )
+        .unwrap(),
+        Decimal::percent(50)
+    );
+    // rounded down
+    assert_eq!(
+        cross_rate(Decimal::one(), Decimal::from_str("3").unwrap()).unwrap(),
+        Decimal::from_str("0.333333333333333333").unwrap()
+    );
+
+    assert_eq!(
+        cross_rate(Decimal::one(), Decimal::zero()).unwrap_err(),
+        StdError::generic_err("cannot divide by a zero price")
+    );
+    assert_eq!(
+        cross_rate(Decimal::MAX, Decimal::percent(50)).unwrap_err(),
+        price_overflow()
+    );
+}
+
+#[test]
+fn divide() {
+    assert_eq!(
+        divide_by_price(Uint128::from(1000u128), Decimal::percent(50)).unwrap(),
+        Uint128::from(2000u128)
+    );
+    // rounded down, 1000 / 0.3 = 3333.3
+    assert_eq!(
+        divide_by_price(Uint128::from(1000u128), Decimal::percent(30)).unwrap(),
+        Uint128::from(3333u128)
+    );
+
+    assert_eq!(
+        divide_by_price(Uint128::from(1000u128), Decimal::zero()).unwrap_err(),
+        StdError::generic_err("cannot divide by a zero price")
+    );
+}
+
+#[test]
+fn token_price() {
+    // a whole 6 decimals base token is worth 2 whole 18 decimals quote tokens
+    let atomic_price = Decimal::from_str("2000000000000").unwrap();
+    assert_eq!(
+        to_token_price(atomic_price, 6, 18).unwrap(),
+        Decimal::from_str("2").unwrap()
+    );
+    assert_eq!(
+        to_atomic_price(Decimal::from_str("2").unwrap(), 6, 18).unwrap(),
+        atomic_price
+    );
+
+    // the same decimals keep the price
+    assert_eq!(
+        to_token_price(Decimal::percent(150), 8, 8).unwrap(),
+        Decimal::percent(150)
+    );
+    assert_eq!(
+        to_atomic_price(Decimal::percent(150), 8, 8).unwrap(),
+        Decimal::percent(150)
+    );
+
+    // rounded down
+    assert_eq!(
+        to_token_price(Decimal::from_str("0.000000000000000001").unwrap(), 0, 6).unwrap(),
+        Decimal::zero()
+    );
+}
+
+#[test]
+fn token_price_overflow() {
+    // 10^78 does not fit in 256 bits
+    assert_eq!(
+        to_token_price(Decimal::one(), 78, 0).unwrap_err(),
+        StdError::generic_err("decimals overflow")
+    );
+    assert_eq!(
+        to_atomic_price(Decimal::one(), 0, u8::MAX).unwrap_err(),
+        StdError::generic_err("decimals overflow")
+    );
+    assert_eq!(
+        to_atomic_price(Decimal::one(), u8::MAX, 0).unwrap_err(),
+        StdError::generic_err("decimals overflow")
+    );
+
+    // the scaled atomics do not fit in 256 bits
+    assert_eq!(
+        to_token_price(Decimal::one(), 77, 0).unwrap_err(),
+        price_overflow()
+    );
+    assert_eq!(
+        to_atomic_price(Decimal::MAX, 0, 40).unwrap_err(),
+        price_overflow()
+    );
+
+    // the price does not fit in a decimal
+    assert_eq!(
+        to_token_price(Decimal::one(), 30, 0).unwrap_err(),
+        price_overflow()
+    );
+}
@@ -0,0 +1,104 @@
+use cosmwasm_std::{Addr, StdError};
+
+use crate::asset::AssetInfo;
+use crate::router::{path_to_operations, SwapOperation, MAX_SWAP_OPERATIONS};
+
+fn native(denom: &str) -> AssetInfo {
+    AssetInfo::Native(denom.to_string())
+}
+
+fn token(addr: &str) -> AssetInfo {
+    AssetInfo::Cw20(Addr::unchecked(addr))
+}
+
+fn prism_swap(offer_asset_info: AssetInfo, ask_asset_info: AssetInfo) -> SwapOperation {
+    SwapOperation::PrismSwap {
+        offer_asset_info,
+        ask_asset_info,
+        pair: None,
+        max_spread: None,
+    }
+}
+
+#[test]
+fn path_operations() {
+    // native to native goes through the market module
+    assert_eq!(
+        path_to_operations(&[native("uusd"), native("uluna")]).unwrap(),
+        vec![SwapOperation::NativeSwap {
+            offer_denom: "uusd".to_string(),
+            ask_denom: "uluna".to_string(),
+        }]
+    );
+
+    // any hop with a token goes through a pair
+    assert_eq!(
+        path_to_operations(&[native("uusd"), token("prism0000")]).unwrap(),
+        vec![prism_swap(native("uusd"), token("prism0000"))]
+    );
+    assert_eq!(
+        path_to_operations(&[token("prism0000"), native("uusd")]).unwrap(),
+        vec![prism_swap(token("prism0000"), native("uusd"))]
+    );
+
+    assert_eq!(
+        path_to_operations(&[
+            token("prism0000"),
+            native("uusd"),
+            native("uluna"),
+            token("asset0000"),
+        ])
+        .unwrap(),
+        vec![
+            prism_swap(token("prism0000"), native("uusd")),
+            SwapOperation::NativeSwap {
+                offer_denom: "uusd".to_string(),
+                ask_denom: "uluna".to_string(),
+            },
+            prism_swap(native("uluna"), token("asset0000")),
+        ]
+    );
+}
+
+#[test]
+fn path_too_short() {
+    // a path without a hop is not a swap
+    assert_eq!(
+        path_to_operations(&[]).unwrap_err(),
+        StdError::generic_err("path must have at least two assets")
+    );
+    assert_eq!(
+        path_to_operations(&[native("uusd")]).unwrap_err(),
+        StdError::generic_err("path must have at least two assets")
+    );
+}
+
+#[test]
+fn path_invalid_hops() {
+    assert_eq!(
+        path_to_operations(&[native("uusd"), token("prism0000"), token("prism0000")]).unwrap_err(),
+        StdError::generic_err("path swaps cw20:prism0000 into itself")
+    );
+    assert_eq!(
+        path_to_operations(&[native("uusd"), native("uusd")]).unwrap_err(),
+        StdError::generic_err("path swaps native:uusd into itself")
+    );
+
+    // the path can go back to an asset it went through
+    path_to_operations(&[native("uusd"), token("prism0000"), native("uusd")]).unwrap();
+
+    let path: Vec<AssetInfo> = (0..=MAX_SWAP_OPERATIONS)
+        .map(|i| token(&format!("token{:04}", i)))
+        .collect();
+    assert_eq!(
+        path_to_operations(&path).unwrap().len(),
+        MAX_SWAP_OPERATIONS
+    );
+    let path: Vec<AssetInfo> = (0..=MAX_SWAP_OPERATIONS + 1)
+        .map(|i| token(&format!("token{:04}", i)))
+        .collect();
+    assert_eq!(
+        path_to_operations(&path).unwrap_err(),
+        StdError::generic_err("too many swap operations")
+    );
+}
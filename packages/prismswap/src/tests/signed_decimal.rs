@@ -0,0 +1,143 @@
+use std::str::FromStr;
+
+use cosmwasm_std::{from_slice, to_vec, Decimal};
+
+use crate::signed_decimal::SignedDecimal;
+
+fn signed(input: &str) -> SignedDecimal {
+    SignedDecimal::from_str(input).unwrap()
+}
+
+#[test]
+fn zero_is_not_negative() {
+    assert!(!SignedDecimal::zero().is_negative());
+    assert!(!SignedDecimal::negative(Decimal::zero()).is_negative());
+    assert!(!SignedDecimal::new(Decimal::zero(), true).is_negative());
+    assert!(!(-SignedDecimal::zero()).is_negative());
+    assert!(!SignedDecimal::diff(Decimal::one(), Decimal::one()).is_negative());
+
+    assert_eq!(
+        SignedDecimal::negative(Decimal::zero()),
+        SignedDecimal::zero()
+    );
+    assert_eq!(SignedDecimal::zero().to_decimal(), Some(Decimal::zero()));
+}
+
+#[test]
+fn parse() {
+    assert_eq!(signed("1.5"), SignedDecimal::from(Decimal::percent(150)));
+    assert_eq!(
+        signed("-1.5"),
+        SignedDecimal::negative(Decimal::percent(150))
+    );
+
+    // negative zero is zero
+    assert_eq!(signed("-0"), SignedDecimal::zero());
+    assert!(!signed("-0").is_negative());
+    assert_eq!(signed("-0").to_string(), "0");
+    assert_eq!(signed("-0.000"), SignedDecimal::zero());
+
+    assert!(SignedDecimal::from_str("").is_err());
+    assert!(SignedDecimal::from_str("-").is_err());
+    assert!(SignedDecimal::from_str("--1").is_err());
+    assert!(SignedDecimal::from_str("1-").is_err());
+}
+
+#[test]
+fn order() {
+    let mut values = vec![
+        signed("1"),
+        signed("-0.5"),
+        signed("0"),
+        signed("-2"),
+        signed("0.5"),
+        signed("-0"),
+    ];
+    values.sort();
+    assert_eq!(
+        values,
+        vec![
+            signed("-2"),
+            signed("-0.5"),
+            signed("0"),
+            signed("0"),
+            signed("0.5"),
+            signed("1"),
+        ]
+    );
+
+    assert!(signed("-1") < signed("0"));
+    assert!(signed("-1") < signed("0.000000000000000001"));
+    assert!(signed("-2") < signed("-1"));
+    assert!(signed("2") > signed("1"));
+    assert_eq!(signed("-0").cmp(&signed("0")), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn add() {
+    assert_eq!(signed("1.5") + signed("2"), signed("3.5"));
+    assert_eq!(signed("-1.5") + signed("-2"), signed("-3.5"));
+    assert_eq!(signed("1.5") + signed("-2"), signed("-0.5"));
+    assert_eq!(signed("-1.5") + signed("2"), signed("0.5"));
+
+    // opposite values cancel out to a positive zero
+    let sum = signed("-1.5") + signed("1.5");
+    assert_eq!(sum, SignedDecimal::zero());
+    assert!(!sum.is_negative());
+}
+
+#[test]
+fn sub() {
+    assert_eq!(signed("3") - signed("1"), signed("2"));
+
+    // crossing zero both ways
+    assert_eq!(signed("1") - signed("3"), signed("-2"));
+    assert_eq!(signed("-1") - signed("-3"), signed("2"));
+    assert_eq!(signed("-1") - signed("2"), signed("-3"));
+    assert_eq!(signed("0") - signed("0.5"), signed("-0.5"));
+
+    let difference = signed("1.5") - signed("1.5");
+    assert_eq!(difference, SignedDecimal::zero());
+    assert!(!difference.is_negative());
+}
+
+#[test]
+fn diff() {
+    assert_eq!(
+        SignedDecimal::diff(Decimal::percent(150), Decimal::percent(50)),
+        signed("1")
+    );
+    assert_eq!(
+        SignedDecimal::diff(Decimal::percent(50), Decimal::percent(150)),
+        signed("-1")
+    );
+    assert_eq!(signed("-1").abs(), Decimal::one());
+    assert_eq!(signed("-1").to_decimal(), None);
+    assert_eq!(-signed("-1"), signed("1"));
+}
+
+#[test]
+fn serde() {
+    assert_eq!(to_vec(&signed("1.5")).unwrap(), br#""1.5""#.to_vec());
+    assert_eq!(to_vec(&signed("-1.5")).unwrap(), br#""-1.5""#.to_vec());
+    assert_eq!(to_vec(&signed("-0")).unwrap(), br#""0""#.to_vec());
+
+    assert_eq!(
+        from_slice::<SignedDecimal>(br#""-0.25""#).unwrap(),
+        signed("-0.25")
+    );
+    assert_eq!(
+        from_slice::<SignedDecimal>(br#""-0""#).unwrap(),
+        SignedDecimal::zero()
+    );
+    for value in ["0", "42", "-42", "0.000000000000000001", "-123.456"] {
+        assert_eq!(
+            from_slice::<SignedDecimal>(&to_vec(&signed(value)).unwrap()).unwrap(),
+            signed(value)
+        );
+    }
+
+    // a number is not a string
+    assert!(from_slice::<SignedDecimal>(b"1").is_err());
+    assert!(from_slice::<SignedDecimal>(br#""one""#).is_err());
+}
@@ -0,0 +1,91 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::AssetInfo;
+use cosmwasm_std::{Addr, Uint128};
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// the governance address allowed to manage the streams
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// CreateStream pays the recipient `rate` of the asset every second from `start_time` to
+    /// `end_time`, out of the treasury balance
+    CreateStream {
+        recipient: Addr,
+        asset_info: AssetInfo,
+        rate: Uint128,
+        start_time: u64,
+        end_time: u64,
+    },
+    /// CancelStream ends a stream now, what it paid so far staying claimable
+    CancelStream { stream_id: u64 },
+    /// Claim sends the recipient of a stream what it paid since the last claim
+    Claim { stream_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Stream returns a stream and what its recipient can claim
+    Stream { stream_id: u64 },
+    /// Streams returns the streams, of a recipient if set, according to the specified parameters
+    /// in `start_after` and `limit` variables
+    Streams {
+        recipient: Option<Addr>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Stream => StreamResponse,
+        Streams => StreamsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the stream query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StreamResponse {
+    pub stream_id: u64,
+    pub recipient: Addr,
+    pub asset_info: AssetInfo,
+    /// the amount paid every second
+    pub rate: Uint128,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub claimed_amount: Uint128,
+    /// the amount paid and not claimed yet
+    pub claimable_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the streams query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StreamsResponse {
+    pub streams: Vec<StreamResponse>,
+}
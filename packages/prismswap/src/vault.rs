@@ -0,0 +1,218 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+pub const MAX_PERFORMANCE_FEE: &str = "0.3";
+pub const MAX_MANAGEMENT_FEE: &str = "0.05";
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to update the configuration and the strategy
+    pub owner: Addr,
+    /// the LP token the vault issues shares over
+    pub lp_token: Addr,
+    /// the code id of the share token the contract instantiates
+    pub token_code_id: u64,
+    /// the collector the fees are sent to
+    pub collector: Addr,
+    /// the share of the harvested LP tokens charged, minted to the collector as shares
+    pub performance_fee: Decimal,
+    /// the yearly share of the LP tokens charged, minted to the collector as shares
+    pub management_fee: Decimal,
+    /// the queue withdrawals exceeding the liquid buffer wait in, none to withdraw every
+    /// withdrawal from the strategy at once
+    pub withdrawal_queue: Option<WithdrawalQueue>,
+}
+
+/// ## Description
+/// This structure describes the liquid buffer serving the withdrawals and the queue the
+/// withdrawals exceeding it are fulfilled from over the harvests.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalQueue {
+    /// the share of the LP tokens kept in the vault to serve withdrawals at once
+    pub buffer_rate: Decimal,
+    /// the maximum share of the strategy LP tokens withdrawn every harvest to fulfill the queue
+    pub unbond_rate: Decimal,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive calls a hook message after receiving LP or share tokens
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner, the fees and the withdrawal queue
+    UpdateConfig {
+        owner: Option<Addr>,
+        collector: Option<Addr>,
+        performance_fee: Option<Decimal>,
+        management_fee: Option<Decimal>,
+        withdrawal_queue: Option<WithdrawalQueue>,
+    },
+    /// UpdateStrategy exits the current strategy, if any, and puts the LP tokens to work in the
+    /// new one
+    UpdateStrategy { strategy: Addr },
+    /// EmergencyExit exits the strategy, the LP tokens staying in the vault until a new one is set
+    EmergencyExit {},
+    /// Compound harvests the strategy, raising the LP tokens every share is worth. Anyone can
+    /// execute it
+    Compound {},
+    /// Internal use
+    Callback(CallbackMsg),
+}
+
+/// ## Description
+/// This structure describes the hook messages of the tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Deposit puts the sent LP tokens to work in the strategy, minting the shares they are worth
+    /// to the sender
+    Deposit {},
+    /// Withdraw burns the sent share tokens, sending the LP tokens they are worth to the sender, or
+    /// queuing them if they exceed the liquid buffer
+    Withdraw {},
+}
+
+/// ## Description
+/// This structure describes the actions run once the strategy is harvested.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackMsg {
+    /// Charge the performance fee on the LP tokens the strategy gained since `prev_total_assets`
+    ProcessHarvest { prev_total_assets: Uint128 },
+    /// Fulfill the withdrawal queue and deposit the LP token balance exceeding the buffer in the
+    /// strategy
+    BondLiquidity {},
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// State returns the LP tokens managed and the shares issued
+    State {},
+    /// ExchangeRate returns the LP tokens a share token is worth
+    ExchangeRate {},
+    /// Position returns the share tokens of an address and the LP tokens they are worth
+    Position { address: String },
+    /// Fees returns the fee rates and the management fee accrued since it was last charged
+    Fees {},
+    /// Withdrawal returns a queued withdrawal, the LP tokens queued ahead and when it should be
+    /// fulfilled
+    Withdrawal { withdrawal_id: u64 },
+    /// Withdrawals returns the queued withdrawals, of an owner if set, according to the specified
+    /// parameters in `start_after` and `limit` variables
+    Withdrawals {
+        owner: Option<Addr>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        State => StateResponse,
+        ExchangeRate => ExchangeRateResponse,
+        Position => PositionResponse,
+        Fees => FeesResponse,
+        Withdrawal => WithdrawalResponse,
+        Withdrawals => WithdrawalsResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub lp_token: Addr,
+    pub share_token: Addr,
+    pub strategy: Option<Addr>,
+    pub collector: Addr,
+    pub performance_fee: Decimal,
+    pub management_fee: Decimal,
+    pub withdrawal_queue: Option<WithdrawalQueue>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the state query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StateResponse {
+    /// the LP tokens managed by the strategy
+    pub strategy_assets: Uint128,
+    /// the supply of the share token
+    pub total_share: Uint128,
+    /// the LP tokens held by the vault and not owed to the queue
+    pub buffer: Uint128,
+    /// the LP tokens owed to the queued withdrawals
+    pub total_queued: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the exchange rate query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateResponse {
+    pub share_token: Addr,
+    pub lp_token: Addr,
+    /// the LP tokens a share is worth, 1 before the first deposit
+    pub exchange_rate: Decimal,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the position query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PositionResponse {
+    pub address: Addr,
+    /// the share token balance of the address
+    pub share: Uint128,
+    /// the LP tokens a withdrawal of the whole balance would send
+    pub lp_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the fees query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeesResponse {
+    pub collector: Addr,
+    /// the share of every harvest charged
+    pub performance_fee: Decimal,
+    /// the yearly share of the LP tokens charged
+    pub management_fee: Decimal,
+    /// the time the management fee was last charged
+    pub last_charged_time: u64,
+    /// the shares the collector would be minted if the management fee was charged now
+    pub pending_management_fee_share: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the withdrawal query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalResponse {
+    pub withdrawal_id: u64,
+    pub owner: Addr,
+    /// the LP tokens still owed
+    pub amount: Uint128,
+    /// the LP tokens owed to the withdrawals queued ahead
+    pub queued_ahead: Uint128,
+    /// the harvests expected to fulfill it at the current unbond rate, none if it cannot be
+    pub harvests: Option<u64>,
+    /// the expected fulfillment time at the last harvest interval, none before two harvests
+    pub eta: Option<u64>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the withdrawals query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct WithdrawalsResponse {
+    pub withdrawals: Vec<WithdrawalResponse>,
+}
@@ -0,0 +1,144 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{Addr, Uint128};
+use cw20::Cw20ReceiveMsg;
+
+/// ## Description
+/// This structure describes the basic settings for creating a contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct InstantiateMsg {
+    /// address allowed to create the schedules and claw them back
+    pub owner: Addr,
+    /// the PRISM token vested
+    pub prism_token: Addr,
+}
+
+/// ## Description
+/// This structure describes the execute messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecuteMsg {
+    /// Receive the PRISM vested by a new schedule
+    Receive(Cw20ReceiveMsg),
+    /// UpdateConfig updates the owner
+    UpdateConfig { owner: Option<Addr> },
+    /// Claim sends the sender the PRISM vested and not claimed yet by one of its vestings, or by
+    /// all of them
+    Claim { vesting_id: Option<u64> },
+    /// Clawback ends a vesting of a recipient now, sending the owner the PRISM not vested yet.
+    /// The PRISM vested stays claimable by the recipient
+    Clawback { recipient: Addr, vesting_id: u64 },
+}
+
+/// ## Description
+/// This structure describes the hook messages of the cw20 tokens sent to the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    /// Vest the sent PRISM to a recipient, independently of its other vestings. Only the owner can
+    /// send it
+    CreateSchedule {
+        recipient: Addr,
+        schedule: VestingSchedule,
+    },
+}
+
+/// ## Description
+/// This structure describes a vesting schedule, in block times in seconds. Nothing vests before
+/// the cliff, then the amount vests linearly from the start to the end: a cliff at the start is a
+/// linear schedule and a cliff at the end vests the whole amount at once.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingSchedule {
+    pub start_time: u64,
+    pub cliff_time: u64,
+    pub end_time: u64,
+}
+
+impl VestingSchedule {
+    pub fn is_valid(&self) -> bool {
+        self.start_time <= self.cliff_time && self.cliff_time <= self.end_time
+    }
+
+    /// the part of `amount` vested at `time`
+    pub fn vested_amount(&self, amount: Uint128, time: u64) -> Uint128 {
+        if time < self.cliff_time {
+            Uint128::zero()
+        } else if time >= self.end_time {
+            amount
+        } else {
+            amount.multiply_ratio(time - self.start_time, self.end_time - self.start_time)
+        }
+    }
+}
+
+/// ## Description
+/// This structure describes the query messages of the contract.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Config returns controls settings that specified in custom [`ConfigResponse`] structure
+    Config {},
+    /// Vesting returns a vesting of a recipient and the PRISM it can claim
+    Vesting { recipient: Addr, vesting_id: u64 },
+    /// Vestings returns the vestings of a recipient according to the specified parameters in
+    /// `start_after` and `limit` variables
+    Vestings {
+        recipient: Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// Claimable returns the PRISM of all the vestings of a recipient
+    Claimable { recipient: Addr },
+}
+
+query_responses! {
+    QueryMsg {
+        Config => ConfigResponse,
+        Vesting => VestingResponse,
+        Vestings => VestingsResponse,
+        Claimable => ClaimableResponse,
+    }
+}
+
+/// ## Description
+/// This structure describes a custom struct for the config query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ConfigResponse {
+    pub owner: Addr,
+    pub prism_token: Addr,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the vesting query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingResponse {
+    pub vesting_id: u64,
+    pub recipient: Addr,
+    pub schedule: VestingSchedule,
+    /// the PRISM vested by the schedule once over
+    pub total_amount: Uint128,
+    pub vested_amount: Uint128,
+    pub claimed_amount: Uint128,
+    /// the PRISM vested and not claimed yet
+    pub claimable_amount: Uint128,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the vestings query response.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VestingsResponse {
+    pub vestings: Vec<VestingResponse>,
+}
+
+/// ## Description
+/// This structure describes a custom struct for the claimable query response, summing all the
+/// vestings of a recipient.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableResponse {
+    pub recipient: Addr,
+    pub total_amount: Uint128,
+    pub vested_amount: Uint128,
+    pub claimed_amount: Uint128,
+    pub claimable_amount: Uint128,
+}